@@ -1,17 +1,21 @@
 use crate::{
     api::{
-        endpoints::xtream_api::{get_xtream_player_api_stream_url, ApiStreamContext},
+        endpoints::xtream_api::{get_query_path, get_xtream_player_api_stream_url, ApiStreamContext},
         model::{
             create_active_client_stream, create_channel_unavailable_stream, create_custom_video_stream_response,
+            BoxedProviderStream,
             create_provider_connections_exhausted_stream, create_provider_stream, get_stream_response_with_headers,
-            tee_stream, AppState, CustomVideoStreamType, ProviderAllocation, ProviderConfig, ProviderStreamFactoryOptions,
-            ProviderStreamState, SharedStreamManager, StreamDetails, StreamError, StreamingStrategy, ThrottledStream,
-            UserApiRequest, UserSession,
+            tee_stream, AppState, CustomVideoStreamType, DeviceAdmission, DeviceClient, LoginFailure,
+            ProviderAllocation, ProviderConfig, ProviderStreamFactoryOptions, ProviderStreamState, SharedStreamManager,
+            StreamDetails, StreamError, StreamingStrategy, ThrottledStream, UserApiRequest, UserSession,
         },
     },
     auth::Fingerprint,
-    model::{ConfigInput, ConfigTarget, ProxyUserCredentials},
-    repository::{ConnectFailureReason, FailureStage},
+    model::{is_ingest_url, ConfigInput, ConfigTarget, ProxyUserCredentials, StreamStallDetectionConfig},
+    repository::{
+        load_channel_alternates, m3u_get_item_for_stream_id, xtream_get_item_for_stream_id, ConnectFailureReason,
+        FailureStage,
+    },
     utils::{
         async_file_reader, async_file_writer, create_new_file_for_write, debug_if_enabled, get_file_extension, request,
         request::{content_type_from_ext, parse_range, send_with_retry_and_provider},
@@ -35,11 +39,11 @@ use shared::{
     concat_string,
     model::{
         Claims, InputFetchMethod, PlaylistEntry, PlaylistItemType, ProxyType, StreamChannel, StreamInfo, TargetType,
-        UserConnectionPermission, VirtualId, XtreamCluster,
+        UserConnectionPermission, UserCountryCheck, VirtualId, XtreamCluster,
     },
     utils::{
-        bin_serialize, extract_extension_from_url, human_readable_kbps, is_sanitize_sensitive_info_enabled,
-        replace_url_extension, sanitize_sensitive_info,
+        bin_serialize, current_time_secs, extract_extension_from_url, human_readable_kbps,
+        is_sanitize_sensitive_info_enabled, replace_url_extension, resolve_client_ip, sanitize_sensitive_info,
         trim_slash, Internable, CONTENT_TYPE_CBOR, CONTENT_TYPE_JSON, DASH_EXT, HLS_EXT,
     },
 };
@@ -48,6 +52,7 @@ use std::{
     collections::HashMap,
     convert::Infallible,
     io::SeekFrom,
+    net::{IpAddr, SocketAddr},
     path::{Path, PathBuf},
     sync::Arc,
 };
@@ -117,9 +122,16 @@ pub(crate) fn record_connect_failed_attempt(attempt: ConnectFailedAttempt<'_>) {
 
 fn admission_failure_video_type(reason: ConnectFailureReason) -> Option<CustomVideoStreamType> {
     match reason {
-        ConnectFailureReason::UserAccountExpired => Some(CustomVideoStreamType::UserAccountExpired),
-        ConnectFailureReason::UserConnectionsExhausted => Some(CustomVideoStreamType::UserConnectionsExhausted),
+        ConnectFailureReason::UserAccountExpired
+        | ConnectFailureReason::OutsideAccessSchedule
+        | ConnectFailureReason::DeviceNotApproved
+        | ConnectFailureReason::CountryDenied => Some(CustomVideoStreamType::UserAccountExpired),
+        ConnectFailureReason::UserConnectionsExhausted | ConnectFailureReason::DeviceLimitReached => {
+            Some(CustomVideoStreamType::UserConnectionsExhausted)
+        }
+        ConnectFailureReason::UserQuotaExhausted => Some(CustomVideoStreamType::UserQuotaExhausted),
         ConnectFailureReason::ProviderConnectionsExhausted => Some(CustomVideoStreamType::ProviderConnectionsExhausted),
+        ConnectFailureReason::ParentalRestricted => Some(CustomVideoStreamType::ChannelUnavailable),
         _ => None,
     }
 }
@@ -150,6 +162,155 @@ pub(crate) fn admission_failure_response(
     create_custom_video_stream_response(app_state, &fingerprint.addr, video_type).into_response()
 }
 
+const MAX_DEVICE_ID_LENGTH: usize = 128;
+
+/// Registers the requesting device of a user with a device limit.
+/// Returns the failure reason when the device may not stream.
+pub(crate) async fn user_device_denied(
+    app_state: &AppState,
+    fingerprint: &Fingerprint,
+    user: &ProxyUserCredentials,
+    req_headers: &HeaderMap,
+    device_id: &str,
+) -> Option<ConnectFailureReason> {
+    let limit = user.device_limit.as_ref()?;
+    if !app_state.app_config.config.load().user_access_control {
+        return None;
+    }
+    let user_agent = req_headers.get(header::USER_AGENT).and_then(|value| value.to_str().ok()).unwrap_or_default();
+    let device_id = device_id.trim();
+    let client = DeviceClient {
+        client_ip: &fingerprint.client_ip,
+        user_agent,
+        device_id: (!device_id.is_empty() && device_id.len() <= MAX_DEVICE_ID_LENGTH).then_some(device_id),
+    };
+    match app_state.user_devices.admit(app_state, &user.username, limit, &client).await {
+        DeviceAdmission::Allowed => None,
+        DeviceAdmission::PendingApproval => {
+            debug!("User access denied, device not approved: {}", user.username);
+            Some(ConnectFailureReason::DeviceNotApproved)
+        }
+        DeviceAdmission::LimitReached => {
+            debug!("User access denied, device limit reached: {}", user.username);
+            Some(ConnectFailureReason::DeviceLimitReached)
+        }
+    }
+}
+
+enum ClientCountry {
+    Country(String),
+    /// Loopback, private and link-local networks.
+    Local,
+    /// The `GeoIP` database is disabled, not loaded yet or has no range for the address.
+    Unknown,
+}
+
+fn is_local_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => ip.is_loopback() || ip.is_private() || ip.is_link_local(),
+        IpAddr::V6(ip) => ip.is_loopback() || ip.is_unique_local() || ip.is_unicast_link_local(),
+    }
+}
+
+/// Returns the client ip of a request. The `X-Real-IP` / `X-Forwarded-For` headers are only used for requests
+/// from the `trusted_proxies` of the reverse proxy config, otherwise the socket peer is the client.
+pub(in crate::api) fn trusted_client_ip(app_state: &AppState, fingerprint: &Fingerprint) -> Option<IpAddr> {
+    let config = app_state.app_config.config.load();
+    let trusted_proxies = config.reverse_proxy.as_ref().map_or(&[][..], |rp| rp.trusted_proxy_networks.as_slice());
+    resolve_client_ip(fingerprint.addr.ip(), &fingerprint.client_ip, trusted_proxies)
+}
+
+/// Returns true for clients from loopback, private or link-local networks.
+pub(in crate::api) fn is_local_client(app_state: &AppState, fingerprint: &Fingerprint) -> bool {
+    trusted_client_ip(app_state, fingerprint).is_some_and(is_local_ip)
+}
+
+/// Resolves the country of the client.
+fn lookup_client_country(app_state: &AppState, client_ip: Option<IpAddr>) -> ClientCountry {
+    let Some(client_ip) = client_ip else {
+        return ClientCountry::Unknown;
+    };
+    if is_local_ip(client_ip) {
+        return ClientCountry::Local;
+    }
+    let geoip = app_state.geoip.load();
+    match geoip.as_ref().and_then(|geoip| geoip.lookup(&client_ip.to_string())) {
+        Some(country) if country.len() == 2 => ClientCountry::Country(country),
+        // private ranges resolve to labels like LAN or Docker
+        Some(_) => ClientCountry::Local,
+        None => ClientCountry::Unknown,
+    }
+}
+
+/// Checks the client country against the country access of the geoip config, the target and the user.
+/// The geoip and target lists are always enforced, the user list only with `user_access_control`.
+/// Clients from local networks are admitted, the client ip is resolved by [`trusted_client_ip`].
+/// Clients whose country can not be resolved are rejected when one of the lists only admits some countries.
+pub(crate) async fn country_access_denied(
+    app_state: &AppState,
+    fingerprint: &Fingerprint,
+    target: &ConfigTarget,
+    user: &ProxyUserCredentials,
+) -> bool {
+    let config = app_state.app_config.config.load();
+    let global_access =
+        config.reverse_proxy.as_ref().and_then(|rp| rp.geoip.as_ref()).and_then(|geoip| geoip.country_access.as_ref());
+    let target_access = target.options.as_ref().and_then(|options| options.country_access.as_ref());
+    let user_access = user.country_access.as_ref().filter(|_| config.user_access_control);
+    if global_access.is_none() && target_access.is_none() && user_access.is_none() {
+        return false;
+    }
+    let country = match lookup_client_country(app_state, trusted_client_ip(app_state, fingerprint)) {
+        ClientCountry::Country(country) => country,
+        ClientCountry::Local => return false,
+        ClientCountry::Unknown => {
+            let restricted = global_access.is_some_and(|access| !access.allow.is_empty())
+                || target_access.is_some_and(|access| !access.allow.is_empty())
+                || user_access.is_some_and(|access| !access.allow.is_empty() || access.home_country.is_some());
+            if restricted {
+                warn!("User access denied, country of the client could not be resolved: {}", user.username);
+            }
+            return restricted;
+        }
+    };
+    let denied = if global_access.is_some_and(|access| !access.is_allowed(&country))
+        || target_access.is_some_and(|access| !access.is_allowed(&country))
+    {
+        true
+    } else if let Some(access) = user_access {
+        match access.check(&country) {
+            UserCountryCheck::Allowed => false,
+            UserCountryCheck::Travel => {
+                !app_state.user_travel.use_travel_day(&user.username, access.travel_days, current_time_secs()).await
+            }
+            UserCountryCheck::Denied => true,
+        }
+    } else {
+        false
+    };
+    if denied {
+        debug!("User access denied, country {country} not allowed: {}", user.username);
+    }
+    denied
+}
+
+/// Admission of playlist downloads in the order of the stream admission: account status, quota, access schedule,
+/// country and device. Parental restrictions are applied per item by the playlist iterators.
+pub(crate) async fn user_playlist_access_denied(
+    app_state: &AppState,
+    fingerprint: &Fingerprint,
+    user: &ProxyUserCredentials,
+    target: &ConfigTarget,
+    req_headers: &HeaderMap,
+    device_id: &str,
+) -> bool {
+    user.permission_denied(app_state)
+        || user.quota_exhausted(app_state).await
+        || user.outside_access_schedule(app_state)
+        || country_access_denied(app_state, fingerprint, target, user).await
+        || user_device_denied(app_state, fingerprint, user, req_headers, device_id).await.is_some()
+}
+
 #[macro_export]
 macro_rules! try_option_bad_request {
     ($option:expr, $msg_is_error:expr, $msg:expr) => {
@@ -173,6 +334,13 @@ macro_rules! try_option_bad_request {
     };
 }
 
+/// Answers a failed player API login, the response is counted by the login protection.
+pub fn login_failure_response(status: StatusCode) -> axum::response::Response {
+    let mut response = status.into_response();
+    response.extensions_mut().insert(LoginFailure);
+    response
+}
+
 #[macro_export]
 macro_rules! try_option_forbidden {
     ($option:expr, $status:expr, $msg_is_error:expr, $msg:expr) => {
@@ -184,7 +352,7 @@ macro_rules! try_option_forbidden {
                 } else {
                     debug!("{}", $msg);
                 }
-                return $status.into_response();
+                return $crate::api::api_utils::login_failure_response($status);
             }
         }
     };
@@ -363,13 +531,24 @@ pub fn get_user_target_by_username(
     None
 }
 
+/// Identifies the client of a parental pin unlock.
+fn parental_client_key(api_req: &UserApiRequest, fingerprint: &Fingerprint) -> String {
+    let device_id = api_req.device_id.trim();
+    if device_id.is_empty() || device_id.len() > MAX_DEVICE_ID_LENGTH {
+        fingerprint.key.clone()
+    } else {
+        format!("{}|{device_id}", fingerprint.key)
+    }
+}
+
 pub fn get_user_target_by_credentials<'a>(
     username: &str,
     password: &str,
     api_req: &'a UserApiRequest,
     app_state: &'a AppState,
+    fingerprint: &Fingerprint,
 ) -> Option<(ProxyUserCredentials, Arc<ConfigTarget>)> {
-    if !username.is_empty() && !password.is_empty() {
+    let user_target = if !username.is_empty() && !password.is_empty() {
         app_state.app_config.get_target_for_user(username, password)
     } else {
         let token = api_req.token.as_str().trim();
@@ -378,16 +557,22 @@ pub fn get_user_target_by_credentials<'a>(
         } else {
             app_state.app_config.get_target_for_user_by_token(token)
         }
-    }
+    };
+    user_target.map(|(mut user, target)| {
+        let client = parental_client_key(api_req, fingerprint);
+        app_state.parental_unlocks.apply(&app_state.app_config, &mut user, &client, api_req.pin.trim());
+        (user, target)
+    })
 }
 
 pub fn get_user_target<'a>(
     api_req: &'a UserApiRequest,
     app_state: &'a AppState,
+    fingerprint: &Fingerprint,
 ) -> Option<(ProxyUserCredentials, Arc<ConfigTarget>)> {
     let username = api_req.username.as_str().trim();
     let password = api_req.password.as_str().trim();
-    get_user_target_by_credentials(username, password, api_req, app_state)
+    get_user_target_by_credentials(username, password, api_req, app_state, fingerprint)
 }
 
 pub struct StreamOptions {
@@ -395,6 +580,7 @@ pub struct StreamOptions {
     pub buffer_enabled: bool,
     pub buffer_size: usize,
     pub pipe_provider_stream: bool,
+    pub stall_detection: Option<StreamStallDetectionConfig>,
 }
 
 struct StreamingAcquireOptions<'a> {
@@ -436,20 +622,16 @@ pub struct ForceStreamRequestContext<'a> {
 ///
 /// Returns a `StreamOptions` instance with the resolved configuration.
 pub(in crate::api) fn get_stream_options(app_state: &Arc<AppState>) -> StreamOptions {
-    let (stream_retry, buffer_enabled, buffer_size) = app_state
-        .app_config
-        .config
-        .load()
-        .reverse_proxy
-        .as_ref()
-        .and_then(|reverse_proxy| reverse_proxy.stream.as_ref())
-        .map_or((true, false, 0), |stream| {
-            let (buffer_enabled, buffer_size) =
-                stream.buffer.as_ref().map_or((false, 0), |buffer| (buffer.enabled, buffer.size));
-            (stream.retry, buffer_enabled, buffer_size)
-        });
+    let config = app_state.app_config.config.load();
+    let stream_config = config.reverse_proxy.as_ref().and_then(|reverse_proxy| reverse_proxy.stream.as_ref());
+    let (stream_retry, buffer_enabled, buffer_size) = stream_config.map_or((true, false, 0), |stream| {
+        let (buffer_enabled, buffer_size) =
+            stream.buffer.as_ref().map_or((false, 0), |buffer| (buffer.enabled, buffer.size));
+        (stream.retry, buffer_enabled, buffer_size)
+    });
+    let stall_detection = stream_config.and_then(|stream| stream.stall_detection.clone());
     let pipe_provider_stream = !stream_retry && !buffer_enabled;
-    StreamOptions { stream_retry, buffer_enabled, buffer_size, pipe_provider_stream }
+    StreamOptions { stream_retry, buffer_enabled, buffer_size, pipe_provider_stream, stall_detection }
 }
 
 pub fn get_stream_alternative_url(stream_url: &str, input: &ConfigInput, alias_input: &Arc<ProviderConfig>) -> String {
@@ -617,6 +799,20 @@ async fn resolve_streaming_strategy(
     }
 }
 
+/// Returns true when the provider stream couldn't be opened and a linked alternate channel can be tried.
+fn is_failed_provider_open(stream_details: &StreamDetails) -> bool {
+    if stream_details.has_deferred_provider_open() {
+        return false;
+    }
+    match stream_details.stream_info.as_ref() {
+        Some((_, _, _, Some(custom_video_type))) => matches!(
+            custom_video_type,
+            CustomVideoStreamType::ChannelUnavailable | CustomVideoStreamType::ProviderConnectionsExhausted
+        ),
+        _ => !stream_details.has_stream(),
+    }
+}
+
 fn get_grace_period_millis(
     connection_permission: UserConnectionPermission,
     stream_response_params: &ProviderStreamState,
@@ -716,6 +912,7 @@ async fn create_stream_response_details(
                 provider_grace_active: false,
                 disable_provider_grace: false,
                 reconnect_flag: None,
+                stall_count: None,
                 provider_handle: streaming_strategy.provider_handle.clone(),
             })
         }
@@ -735,15 +932,15 @@ async fn create_stream_response_details(
             } else {
                 false
             };
-            let (stream, stream_info, reconnect_flag) = if defer_provider_stream_until_grace_check {
+            let (stream, stream_info, reconnect_flag, stall_count) = if defer_provider_stream_until_grace_check {
                 debug_if_enabled!(
                     "Deferring provider stream open until grace check completes for {}",
                     sanitize_sensitive_info(resolve_request_url_for_logging(input, request_url.as_ref()).as_ref())
                 );
-                (None, None, None)
+                (None, None, None, Some(Arc::default()))
             } else {
                 let parsed_url = Url::parse(&request_url);
-                let ((stream, stream_info), reconnect_flag) = if let Ok(url) = parsed_url {
+                let ((stream, stream_info), reconnect_flag, stall_count) = if let Ok(url) = parsed_url {
                     let default_user_agent = app_state.app_config.config.load().default_user_agent.clone();
                     let disabled_headers = app_state.get_disabled_headers();
                     let mut provider_stream_factory_options = ProviderStreamFactoryOptions::new(
@@ -768,6 +965,7 @@ async fn create_stream_response_details(
                     provider_stream_factory_options.set_provider(provider_config);
 
                     let reconnect_flag = provider_stream_factory_options.get_reconnect_flag_clone();
+                    let stall_count = provider_stream_factory_options.get_stall_count_clone();
                     let provider_stream = match create_provider_stream(
                         app_state,
                         &app_state.http_client.load(),
@@ -778,11 +976,11 @@ async fn create_stream_response_details(
                         None => (None, None),
                         Some((stream, info)) => (Some(stream), info),
                     };
-                    (provider_stream, Some(reconnect_flag))
+                    (provider_stream, Some(reconnect_flag), Some(stall_count))
                 } else {
-                    ((None, None), None)
+                    ((None, None), None, None)
                 };
-                (stream, stream_info, reconnect_flag)
+                (stream, stream_info, reconnect_flag, stall_count)
             };
 
             if log_enabled!(log::Level::Debug) {
@@ -816,6 +1014,7 @@ async fn create_stream_response_details(
                 provider_grace_active,
                 disable_provider_grace: false,
                 reconnect_flag,
+                stall_count,
                 provider_handle,
             })
         }
@@ -862,6 +1061,11 @@ pub async fn redirect_response<'a, P>(
 where
     P: PlaylistEntry,
 {
+    // ingest channels are only available through the shared stream of the encoder
+    if params.input.input_type.is_ingest() {
+        return None;
+    }
+
     let item_type = params.item.get_item_type();
     let provider_url = params.item.get_provider_url();
 
@@ -935,7 +1139,7 @@ where
     None
 }
 
-fn is_throttled_stream(item_type: PlaylistItemType, throttle_kbps: usize) -> bool {
+fn is_throttled_stream(item_type: PlaylistItemType, throttle_kbps: u64) -> bool {
     throttle_kbps > 0
         && matches!(
             item_type,
@@ -949,12 +1153,17 @@ fn is_throttled_stream(item_type: PlaylistItemType, throttle_kbps: usize) -> boo
         )
 }
 
-fn prepare_body_stream<S>(app_state: &Arc<AppState>, item_type: PlaylistItemType, stream: S) -> axum::body::Body
+fn prepare_body_stream<S>(
+    app_state: &Arc<AppState>,
+    user: &ProxyUserCredentials,
+    item_type: PlaylistItemType,
+    stream: S,
+) -> axum::body::Body
 where
     S: futures::Stream<Item = Result<bytes::Bytes, StreamError>> + Send + 'static,
 {
-    let throttle_kbps = usize::try_from(get_stream_throttle(app_state)).unwrap_or_default();
-    let body_stream = if is_throttled_stream(item_type, throttle_kbps) {
+    let throttle_kbps = usize::try_from(get_stream_throttle_kbps(app_state, user, item_type)).unwrap_or_default();
+    let body_stream = if throttle_kbps > 0 {
         info!("Stream throttling active: {}", human_readable_kbps(u64::try_from(throttle_kbps).unwrap_or_default()));
         axum::body::Body::from_stream(ThrottledStream::new(stream.boxed(), throttle_kbps))
     } else {
@@ -991,7 +1200,7 @@ pub async fn force_provider_stream_response(
         .connection_kind
         .unwrap_or(crate::api::model::ConnectionKind::Normal);
 
-    let stream_details = match create_stream_response_details(
+    let mut stream_details = match create_stream_response_details(
         app_state,
         &stream_options,
         &user_session.stream_url,
@@ -1045,6 +1254,7 @@ pub async fn force_provider_stream_response(
             .update_session_addr(&ctx.user.username, &user_session.token, &fingerprint.addr)
             .await;
         stream_channel.shared = share_stream;
+        cache_vod_stream(app_state, &stream_channel, &mut stream_details);
         let stream = create_active_client_stream(crate::api::model::ActiveClientStreamParams {
             stream_details,
             app_state,
@@ -1068,7 +1278,7 @@ pub async fn force_provider_stream_response(
             response = response.header(key, value);
         }
 
-        let body_stream = prepare_body_stream(app_state, item_type, stream);
+        let body_stream = prepare_body_stream(app_state, ctx.user, item_type, stream);
         debug_if_enabled!(
             "Streaming provider forced stream request from {}",
             sanitize_sensitive_info(resolve_request_url_for_logging(ctx.input, user_session.stream_url.as_ref()).as_ref())
@@ -1190,6 +1400,42 @@ pub async fn stream_response(
         .into_response();
     }
 
+    // Without a shared stream the encoder of the ingest channel is not pushing.
+    if is_ingest_url(stream_url) {
+        record_connect_failed_attempt(ConnectFailedAttempt {
+            app_state,
+            fingerprint,
+            user,
+            stream_channel: stream_channel.clone(),
+            provider_name: input.name.as_ref(),
+            req_headers,
+            reason: ConnectFailureReason::ChannelUnavailable,
+            failure_stage: FailureStage::ProviderOpen,
+        });
+        return create_custom_video_stream_response(
+            app_state,
+            &fingerprint.addr,
+            CustomVideoStreamType::ChannelUnavailable,
+        )
+        .into_response();
+    }
+
+    if let Some(response) = try_vod_cache_response(
+        fingerprint,
+        app_state,
+        &stream_channel,
+        req_headers,
+        input,
+        target,
+        user,
+        connection_permission,
+        connection_kind,
+    )
+    .await
+    {
+        return response;
+    }
+
     let stream_options = get_stream_options(app_state);
     let mut stream_details = match create_stream_response_details(
         app_state,
@@ -1219,6 +1465,57 @@ pub async fn stream_response(
         }
     };
 
+    // Same channel, other source: try the linked alternates of the channel in order.
+    if matches!(item_type, PlaylistItemType::Live | PlaylistItemType::LiveUnknown)
+        && target.options.as_ref().is_some_and(|options| options.channel_failover.is_some())
+        && is_failed_provider_open(&stream_details)
+    {
+        for alternate in load_channel_alternates(&app_state.app_config, &target.name, virtual_id).await {
+            let Some(alternate_input) = app_state.app_config.get_input_by_name(&alternate.input_name.intern()) else {
+                continue;
+            };
+            if !alternate_input.enabled {
+                continue;
+            }
+            app_state.connection_manager.release_provider_handle(stream_details.provider_handle.take()).await;
+            stream_details = match create_stream_response_details(
+                app_state,
+                &stream_options,
+                &alternate.url,
+                &user.username,
+                fingerprint,
+                req_headers,
+                &alternate_input,
+                &stream_channel,
+                item_type,
+                share_stream,
+                connection_permission,
+                None,
+                true,
+                virtual_id,
+                connection_priority_for_kind(user, connection_kind),
+                connection_kind,
+                Some(session_token),
+            )
+            .await
+            {
+                Ok(alternate_details) => alternate_details,
+                Err(err) => {
+                    error!("Failed to stream alternate of {}: {err}", alternate_input.name);
+                    continue;
+                }
+            };
+            if !is_failed_provider_open(&stream_details) {
+                info!(
+                    "Channel {} of input {} failed over to input {}",
+                    stream_channel.title, input.name, alternate_input.name
+                );
+                stream_channel.alternate_input = Some(Arc::clone(&alternate_input.name));
+                break;
+            }
+        }
+    }
+
     // When no provider stream is available, still create an ActiveClientStream if a grace period
     // needs to resolve (provider-grace with hold_stream, or user-grace). The grace task will
     // determine the correct mode (UserExhausted / ProviderExhausted / Inner) and serve the
@@ -1276,6 +1573,7 @@ pub async fn stream_response(
         } else {
             stream_channel.shared_joined_existing = None;
             stream_channel.shared_stream_id = None;
+            cache_vod_stream(app_state, &stream_channel, &mut stream_details);
         }
         let stream = create_active_client_stream(crate::api::model::ActiveClientStreamParams {
             stream_details,
@@ -1403,7 +1701,7 @@ pub async fn stream_response(
                 }
             }
 
-            let body_stream = prepare_body_stream(app_state, item_type, stream);
+            let body_stream = prepare_body_stream(app_state, user, item_type, stream);
             let mut response = try_unwrap_body!(response.body(body_stream));
             mark_response_as_uncompressed(&mut response);
             response
@@ -1415,16 +1713,85 @@ pub async fn stream_response(
     StatusCode::BAD_REQUEST.into_response()
 }
 
-fn get_stream_throttle(app_state: &Arc<AppState>) -> u64 {
-    app_state
-        .app_config
-        .config
-        .load()
-        .reverse_proxy
-        .as_ref()
-        .and_then(|reverse_proxy| reverse_proxy.stream.as_ref())
-        .map(|stream| stream.throttle_kbps)
-        .unwrap_or_default()
+/// Serves a movie or episode from the vod disk cache when the requested range is already stored.
+#[allow(clippy::too_many_arguments)]
+async fn try_vod_cache_response(
+    fingerprint: &Fingerprint,
+    app_state: &Arc<AppState>,
+    stream_channel: &StreamChannel,
+    req_headers: &HeaderMap,
+    input: &ConfigInput,
+    target: &ConfigTarget,
+    user: &ProxyUserCredentials,
+    connection_permission: UserConnectionPermission,
+    connection_kind: crate::api::model::ConnectionKind,
+) -> Option<axum::response::Response> {
+    if !matches!(stream_channel.item_type, PlaylistItemType::Video | PlaylistItemType::Series) {
+        return None;
+    }
+    let vod_cache = app_state.vod_cache.load_full()?;
+    let range = req_headers.get("range").and_then(|v| v.to_str().ok()).and_then(parse_range);
+    let path = vod_cache.lookup(&stream_channel.url, range).await?;
+    let mut cached_channel = stream_channel.clone();
+    cached_channel.url = Arc::from(path.to_string_lossy().as_ref());
+    // Without a playback session every seek is looked up in the cache again.
+    Some(
+        local_stream_response(
+            fingerprint,
+            app_state,
+            cached_channel,
+            req_headers,
+            input,
+            target,
+            user,
+            connection_permission,
+            connection_kind,
+            None,
+            false,
+        )
+        .await
+        .into_response(),
+    )
+}
+
+/// Fills the vod disk cache while the provider stream of a movie or episode is sent to the client.
+fn cache_vod_stream(app_state: &AppState, stream_channel: &StreamChannel, stream_details: &mut StreamDetails) {
+    if !matches!(stream_channel.item_type, PlaylistItemType::Video | PlaylistItemType::Series) {
+        return;
+    }
+    let Some(vod_cache) = app_state.vod_cache.load_full() else {
+        return;
+    };
+    let Some((headers, status, _url, None)) = stream_details.stream_info.as_ref() else {
+        return;
+    };
+    if let Some(stream) = stream_details.stream.take() {
+        stream_details.stream = Some(vod_cache.cache_stream(&stream_channel.url, *status, headers, stream));
+    }
+}
+
+/// Returns the bandwidth limit of a stream, the limit profile of the user overrides the global throttle.
+/// Returns 0 when the stream is not throttled.
+pub(crate) fn get_stream_throttle_kbps(
+    app_state: &AppState,
+    user: &ProxyUserCredentials,
+    item_type: PlaylistItemType,
+) -> u64 {
+    let profile_kbps = app_state.app_config.get_user_limit_profile(user).map_or(0, |profile| profile.throttle_kbps);
+    let throttle_kbps = if profile_kbps > 0 {
+        profile_kbps
+    } else {
+        app_state
+            .app_config
+            .config
+            .load()
+            .reverse_proxy
+            .as_ref()
+            .and_then(|reverse_proxy| reverse_proxy.stream.as_ref())
+            .map(|stream| stream.throttle_kbps)
+            .unwrap_or_default()
+    };
+    if is_throttled_stream(item_type, throttle_kbps) { throttle_kbps } else { 0 }
 }
 
 fn is_stream_metrics_enabled(app_state: &Arc<AppState>) -> bool {
@@ -1714,8 +2081,8 @@ pub async fn local_stream_response(
     let stream = ReaderStream::new(file.take(content_length))
         .map_err(|err| StreamError::Stream(err.to_string()))
         .boxed();
-    let throttle_kbps = usize::try_from(get_stream_throttle(app_state)).unwrap_or_default();
-    let stream = if is_throttled_stream(pli.item_type, throttle_kbps) {
+    let throttle_kbps = usize::try_from(get_stream_throttle_kbps(app_state, user, pli.item_type)).unwrap_or_default();
+    let stream = if throttle_kbps > 0 {
         info!("Stream throttling active: {}", human_readable_kbps(u64::try_from(throttle_kbps).unwrap_or_default()));
         ThrottledStream::new(stream, throttle_kbps).boxed()
     } else {
@@ -1800,6 +2167,185 @@ fn is_path_within_allowed_directories(sub_path: &Path, root_paths: &[String]) ->
     false
 }
 
+// Pre-warmed streams have the lowest priority, every viewer preempts them.
+const PREWARM_PRIORITY: i8 = i8::MAX;
+const PREWARM_USERNAME: &str = "prewarm";
+
+/// Resolves the provider url of a live channel. Targets with xtream output use the url of the xtream
+/// stream requests, a viewer of the channel joins the shared stream under this url.
+pub(in crate::api) async fn resolve_live_channel(
+    app_state: &Arc<AppState>,
+    target: &ConfigTarget,
+    virtual_id: VirtualId,
+) -> Option<(Arc<ConfigInput>, StreamChannel, Arc<str>)> {
+    if target.has_output(TargetType::Xtream) {
+        let pli = xtream_get_item_for_stream_id(virtual_id, app_state, target, Some(XtreamCluster::Live)).await.ok()?;
+        if pli.item_type != PlaylistItemType::Live {
+            return None;
+        }
+        let input = app_state.app_config.get_input_by_name(&pli.input_name)?;
+        let (query_path, _) = get_query_path("", None, &pli, app_state);
+        let stream_url = get_xtream_player_api_stream_url(&input, ApiStreamContext::Live, &query_path, &pli.url)?;
+        Some((input, pli.to_stream_channel(target.id), stream_url))
+    } else if target.has_output(TargetType::M3u) {
+        let pli = m3u_get_item_for_stream_id(virtual_id, app_state, target).await.ok()?;
+        if pli.item_type != PlaylistItemType::Live {
+            return None;
+        }
+        let input = app_state.app_config.get_input_by_name(&pli.input_name)?;
+        let stream_url = pli.url.clone();
+        Some((input, pli.to_stream_channel(target.id), stream_url))
+    } else {
+        None
+    }
+}
+
+/// Opens a live channel as idle shared stream when a provider connection beyond `reserve_slots` is free.
+/// Returns the url of the shared stream, `None` when the channel is unknown, already streamed or no
+/// connection is free. Inputs with panel api provisioning are skipped, an exhausted provider would
+/// provision a new account.
+pub(in crate::api) async fn open_prewarm_stream(
+    app_state: &Arc<AppState>,
+    target: &ConfigTarget,
+    virtual_id: VirtualId,
+    addr: SocketAddr,
+    reserve_slots: usize,
+) -> Option<Arc<str>> {
+    let (input, stream_channel, stream_url) = resolve_live_channel(app_state, target, virtual_id).await?;
+    if !input.enabled || can_provision_on_exhausted(app_state, &input) {
+        return None;
+    }
+    let capacities = app_state.active_provider.provider_capacities_for_input(&input.name).await;
+    if !capacities.iter().any(|(_, current, max)| *max == 0 || current.saturating_add(reserve_slots) < *max) {
+        return None;
+    }
+
+    let _shared_lock = app_state.app_config.file_locks.write_lock_str(&stream_url).await;
+    if app_state.shared_stream_manager.get_shared_state(&stream_url).await.is_some() {
+        return None;
+    }
+
+    let fingerprint = Fingerprint::new(String::new(), addr.ip().to_string(), addr);
+    let stream_options = get_stream_options(app_state);
+    let mut stream_details = create_stream_response_details(
+        app_state,
+        &stream_options,
+        &stream_url,
+        PREWARM_USERNAME,
+        &fingerprint,
+        &HeaderMap::new(),
+        &input,
+        &stream_channel,
+        PlaylistItemType::Live,
+        true,
+        UserConnectionPermission::Allowed,
+        None,
+        false,
+        virtual_id,
+        PREWARM_PRIORITY,
+        crate::api::model::ConnectionKind::Normal,
+        None,
+    )
+    .await
+    .ok()?;
+
+    let provider_handle = stream_details.provider_handle.take();
+    let is_provider_stream = matches!(stream_details.stream_info.as_ref(), None | Some((_, _, _, None)));
+    let Some(stream) = stream_details.stream.take().filter(|_| is_provider_stream) else {
+        app_state.connection_manager.release_provider_handle(provider_handle).await;
+        return None;
+    };
+    let headers = stream_details.stream_info.map_or_else(Vec::new, |(headers, _, _, _)| headers);
+    SharedStreamManager::register_prewarmed_stream(
+        app_state,
+        &stream_url,
+        stream,
+        &addr,
+        headers,
+        stream_options.buffer_size,
+        provider_handle,
+    )
+    .await;
+    Some(stream_url)
+}
+
+// Multicast groups are watched by set-top boxes, they have the priority of a regular viewer.
+const MULTICAST_PRIORITY: i8 = 0;
+const MULTICAST_USERNAME: &str = "multicast";
+
+/// Subscribes a multicast group to the shared stream of a live channel, the provider stream is opened
+/// and shared when nobody watches the channel yet. HTTP viewers of a target with `share_live_streams`
+/// join the same provider connection. Returns the stream and the url of the shared stream.
+pub(in crate::api) async fn open_multicast_stream(
+    app_state: &Arc<AppState>,
+    target: &ConfigTarget,
+    virtual_id: VirtualId,
+    addr: SocketAddr,
+) -> Option<(BoxedProviderStream, Arc<str>)> {
+    let (input, stream_channel, stream_url) = resolve_live_channel(app_state, target, virtual_id).await?;
+    if !input.enabled {
+        return None;
+    }
+
+    let _shared_lock = app_state.app_config.file_locks.write_lock_str(&stream_url).await;
+    if app_state.shared_stream_manager.get_shared_state(&stream_url).await.is_some() {
+        let (stream, _) = SharedStreamManager::subscribe_shared_stream(
+            app_state,
+            &stream_url,
+            &addr,
+            MULTICAST_PRIORITY,
+            crate::api::model::ConnectionKind::Normal,
+        )
+        .await?;
+        return Some((stream, stream_url));
+    }
+
+    let fingerprint = Fingerprint::new(String::new(), addr.ip().to_string(), addr);
+    let stream_options = get_stream_options(app_state);
+    let mut stream_details = create_stream_response_details(
+        app_state,
+        &stream_options,
+        &stream_url,
+        MULTICAST_USERNAME,
+        &fingerprint,
+        &HeaderMap::new(),
+        &input,
+        &stream_channel,
+        PlaylistItemType::Live,
+        true,
+        UserConnectionPermission::Allowed,
+        None,
+        false,
+        virtual_id,
+        MULTICAST_PRIORITY,
+        crate::api::model::ConnectionKind::Normal,
+        None,
+    )
+    .await
+    .ok()?;
+
+    let provider_handle = stream_details.provider_handle.take();
+    let is_provider_stream = matches!(stream_details.stream_info.as_ref(), None | Some((_, _, _, None)));
+    let Some(stream) = stream_details.stream.take().filter(|_| is_provider_stream) else {
+        app_state.connection_manager.release_provider_handle(provider_handle).await;
+        return None;
+    };
+    let headers = stream_details.stream_info.map_or_else(Vec::new, |(headers, _, _, _)| headers);
+    let (stream, _) = SharedStreamManager::register_shared_stream(
+        app_state,
+        &stream_url,
+        stream,
+        &addr,
+        headers,
+        stream_options.buffer_size,
+        provider_handle,
+        MULTICAST_PRIORITY,
+        crate::api::model::ConnectionKind::Normal,
+    )
+    .await?;
+    Some((stream, stream_url))
+}
+
 pub fn is_stream_share_enabled(item_type: PlaylistItemType, target: &ConfigTarget) -> bool {
     (item_type == PlaylistItemType::Live/* || item_type == PlaylistItemType::LiveHls */)
         && target.options.as_ref().is_some_and(|opt| opt.share_live_streams)
@@ -2265,7 +2811,16 @@ pub fn create_api_proxy_user(app_state: &Arc<AppState>) -> ProxyUserCredentials
         priority: 0,
         soft_connections: 0,
         soft_priority: 0,
+        quota: None,
+        access_schedule: None,
+        parental_profile: None,
+        device_limit: None,
+        country_access: None,
+        limit_profile: None,
+        reseller: None,
+        hls_packaging: None,
         t_is_api_user: true,
+        t_parental_unlocked: false,
     }
 }
 
@@ -2537,8 +3092,17 @@ mod tests {
             http_client: Arc::new(ArcSwap::from_pointee(reqwest::Client::new())),
             http_client_no_redirect: Arc::new(ArcSwap::from_pointee(reqwest::Client::new())),
             downloads: Arc::new(crate::api::model::DownloadQueue::new()),
+            user_quota: Arc::new(crate::api::model::UserQuotaManager::new_with_state_file(None)),
+            parental_unlocks: Arc::new(crate::api::model::ParentalUnlockManager::default()),
+            user_devices: Arc::new(crate::api::model::UserDeviceManager::new_with_state_file(None)),
+            user_travel: Arc::new(crate::api::model::UserTravelManager::new_with_state_file(None)),
+            channel_health: Arc::new(crate::api::model::ChannelHealthManager::new_with_state_file(None)),
+            login_bans: Arc::new(crate::api::model::LoginBanManager::new_with_state_file(None)),
+            user_rate_limits: Arc::new(crate::api::model::UserRateLimiter::default()),
             cache: Arc::new(ArcSwapOption::default()),
+            vod_cache: Arc::new(ArcSwapOption::default()),
             shared_stream_manager,
+            hls_segmenters: Arc::default(),
             active_users,
             active_provider,
             connection_manager,
@@ -2571,6 +3135,7 @@ mod tests {
             shared_joined_existing: None,
             shared_stream_id: None,
             technical: None,
+            alternate_input: None,
         }
     }
 
@@ -2589,6 +3154,7 @@ mod tests {
             shared_joined_existing: None,
             shared_stream_id: None,
             technical: None,
+            alternate_input: None,
         }
     }
 
@@ -2628,6 +3194,30 @@ mod tests {
             admission_failure_video_type(ConnectFailureReason::ProviderConnectionsExhausted),
             Some(CustomVideoStreamType::ProviderConnectionsExhausted)
         ));
+        assert!(matches!(
+            admission_failure_video_type(ConnectFailureReason::UserQuotaExhausted),
+            Some(CustomVideoStreamType::UserQuotaExhausted)
+        ));
+        assert!(matches!(
+            admission_failure_video_type(ConnectFailureReason::OutsideAccessSchedule),
+            Some(CustomVideoStreamType::UserAccountExpired)
+        ));
+        assert!(matches!(
+            admission_failure_video_type(ConnectFailureReason::ParentalRestricted),
+            Some(CustomVideoStreamType::ChannelUnavailable)
+        ));
+        assert!(matches!(
+            admission_failure_video_type(ConnectFailureReason::DeviceNotApproved),
+            Some(CustomVideoStreamType::UserAccountExpired)
+        ));
+        assert!(matches!(
+            admission_failure_video_type(ConnectFailureReason::CountryDenied),
+            Some(CustomVideoStreamType::UserAccountExpired)
+        ));
+        assert!(matches!(
+            admission_failure_video_type(ConnectFailureReason::DeviceLimitReached),
+            Some(CustomVideoStreamType::UserConnectionsExhausted)
+        ));
         assert!(admission_failure_video_type(ConnectFailureReason::ProviderError).is_none());
     }
 
@@ -2740,6 +3330,7 @@ mod tests {
                 stream_channel: &normal_channel,
                 user_agent: Cow::Borrowed("ua"),
                 session_token: Some("normal-session"),
+                throttle_kbps: 0,
             })
             .await
             .expect("normal stream should register");
@@ -2,6 +2,7 @@ use crate::api::model::AppState;
 use crate::{
     api::api_utils::{empty_json_list_response, json_or_bin_response, stream_json_or_bin_response_stream},
     model::{ConfigInput, ConfigTarget},
+    processing::processor::download_ingest_playlist,
     repository::{
         iter_raw_m3u_input_playlist, iter_raw_m3u_target_playlist, iter_raw_xtream_input_playlist,
         iter_raw_xtream_target_playlist,
@@ -155,7 +156,7 @@ pub(in crate::api::endpoints) async fn get_playlist_for_input(
             };
             let converted_stream = channel_iterator.map(UiPlaylistItem::from);
             return stream_json_or_bin_response_stream(accept, converted_stream).into_response();
-        } else if matches!(input.input_type, InputType::M3u | InputType::M3uBatch) {
+        } else if matches!(input.input_type, InputType::M3u | InputType::M3uBatch | InputType::Ingest) {
             let Some(channels) = iter_raw_m3u_input_playlist(&app_state.app_config, input, Some(cluster)).await else {
                 return empty_json_list_response();
             };
@@ -198,6 +199,7 @@ pub(in crate::api::endpoints) async fn get_playlist_for_custom_provider(
                     )
                         .into_response();
                 }
+                InputType::Ingest => download_ingest_playlist(&app_state.app_config, input),
             };
             if result.is_empty() {
                 let error_strings: Vec<String> = errors.iter().map(ToString::to_string).collect();
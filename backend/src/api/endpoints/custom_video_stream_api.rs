@@ -1,5 +1,8 @@
 use crate::{
-    api::model::{create_custom_video_stream_response, AppState, CustomVideoStreamType},
+    api::{
+        api_utils::login_failure_response,
+        model::{create_custom_video_stream_response, AppState, CustomVideoStreamType},
+    },
     auth::Fingerprint,
 };
 use axum::response::IntoResponse;
@@ -17,7 +20,7 @@ async fn cvs_api(
     };
 
     let Some((user, _target)) = app_state.app_config.get_target_for_user(&username, &password) else {
-        return app_state.app_config.get_auth_error_status().into_response();
+        return login_failure_response(app_state.app_config.get_auth_error_status());
     };
 
     if user.permission_denied(&app_state) {
@@ -0,0 +1,1131 @@
+use crate::api::api_utils::{is_local_client, try_unwrap_body};
+use crate::api::endpoints::xtream_api::{xtream_player_api_user_stream, ApiStreamContext};
+use crate::api::model::AppState;
+use crate::auth::{constant_time_eq, Fingerprint};
+use crate::model::{AppConfig, ConfigTarget, DlnaConfig, ProxyUserCredentials};
+use crate::repository::{xtream_get_file_path, xtream_get_storage_path, XtreamPlaylistIterator};
+use axum::response::IntoResponse;
+use futures::StreamExt;
+use log::{debug, trace};
+use quick_xml::escape::{escape, unescape};
+use shared::model::{StreamProperties, TargetType, XtreamCluster, XtreamPlaylistItem};
+use shared::utils::extract_extension_from_url;
+use std::fmt::{self, Write};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::UNIX_EPOCH;
+
+const DLNA_DEVICE_PATH: &str = "/dlna/device.xml";
+const DLNA_CONTENT_DIRECTORY_SCPD_PATH: &str = "/dlna/content_directory.xml";
+const DLNA_CONNECTION_MANAGER_SCPD_PATH: &str = "/dlna/connection_manager.xml";
+const DLNA_CONTENT_DIRECTORY_CONTROL_PATH: &str = "/dlna/control/content_directory";
+const DLNA_CONNECTION_MANAGER_CONTROL_PATH: &str = "/dlna/control/connection_manager";
+const DLNA_STREAM_PATH: &str = "/dlna/stream";
+
+const CONTENT_DIRECTORY_SERVICE: &str = "urn:schemas-upnp-org:service:ContentDirectory:1";
+const CONNECTION_MANAGER_SERVICE: &str = "urn:schemas-upnp-org:service:ConnectionManager:1";
+
+const ROOT_ID: &str = "0";
+const LIBRARY_ID: &str = "library";
+const CLASS_FOLDER: &str = "object.container.storageFolder";
+const CLASS_SERIES: &str = "object.container.album.videoAlbum";
+const CLASS_BROADCAST: &str = "object.item.videoItem.videoBroadcast";
+const CLASS_MOVIE: &str = "object.item.videoItem.movie";
+const CLASS_VIDEO: &str = "object.item.videoItem";
+
+const SOURCE_PROTOCOL_INFO: &str = "http-get:*:video/mp2t:*,http-get:*:video/mp4:*,http-get:*:video/x-matroska:*,\
+http-get:*:video/x-msvideo:*,http-get:*:video/quicktime:*,http-get:*:video/webm:*,http-get:*:video/mpeg:*";
+
+const UPNP_ERROR_INVALID_ACTION: u16 = 401;
+const UPNP_ERROR_INVALID_ARGS: u16 = 402;
+const UPNP_ERROR_NO_SUCH_OBJECT: u16 = 701;
+
+/// Top level sections of the content tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DlnaSection {
+    Live,
+    Vod,
+    Series,
+    LibraryVod,
+    LibrarySeries,
+}
+
+impl DlnaSection {
+    const PROVIDER: [DlnaSection; 3] = [DlnaSection::Live, DlnaSection::Vod, DlnaSection::Series];
+    const LIBRARY: [DlnaSection; 2] = [DlnaSection::LibraryVod, DlnaSection::LibrarySeries];
+
+    fn id(self) -> &'static str {
+        match self {
+            DlnaSection::Live => "live",
+            DlnaSection::Vod => "vod",
+            DlnaSection::Series => "series",
+            DlnaSection::LibraryVod => "library/vod",
+            DlnaSection::LibrarySeries => "library/series",
+        }
+    }
+
+    fn title(self) -> &'static str {
+        match self {
+            DlnaSection::Live => "Live TV",
+            DlnaSection::Vod | DlnaSection::LibraryVod => "Movies",
+            DlnaSection::Series | DlnaSection::LibrarySeries => "Series",
+        }
+    }
+
+    fn parent_id(self) -> &'static str {
+        if self.is_local() { LIBRARY_ID } else { ROOT_ID }
+    }
+
+    fn cluster(self) -> XtreamCluster {
+        match self {
+            DlnaSection::Live => XtreamCluster::Live,
+            DlnaSection::Vod | DlnaSection::LibraryVod => XtreamCluster::Video,
+            DlnaSection::Series | DlnaSection::LibrarySeries => XtreamCluster::Series,
+        }
+    }
+
+    fn is_local(self) -> bool { matches!(self, DlnaSection::LibraryVod | DlnaSection::LibrarySeries) }
+
+    fn is_series(self) -> bool { self.cluster() == XtreamCluster::Series }
+
+    fn matches(self, item: &XtreamPlaylistItem) -> bool { item.item_type.is_local() == self.is_local() }
+}
+
+/// Object ids are paths: `{section}/{category_id}/{virtual_id}[/{episode_id}]`.
+/// Within the series sections the third segment is a series container and the fourth an episode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DlnaObjectId {
+    Root,
+    Library,
+    Section(DlnaSection),
+    Category(DlnaSection, u32),
+    Series(DlnaSection, u32, u32),
+    Item(DlnaSection, u32, u32),
+    Episode(DlnaSection, u32, u32, u32),
+}
+
+impl DlnaObjectId {
+    fn parse(id: &str) -> Option<Self> {
+        let id = id.trim();
+        if id == ROOT_ID {
+            return Some(DlnaObjectId::Root);
+        }
+        if id == LIBRARY_ID {
+            return Some(DlnaObjectId::Library);
+        }
+        let (section, rest) = [DlnaSection::LibraryVod, DlnaSection::LibrarySeries, DlnaSection::Live, DlnaSection::Vod, DlnaSection::Series]
+            .into_iter()
+            .find_map(|section| {
+                let rest = id.strip_prefix(section.id())?;
+                (rest.is_empty() || rest.starts_with('/')).then_some((section, rest))
+            })?;
+        let parts = rest.split('/').skip(1).map(str::parse::<u32>).collect::<Result<Vec<_>, _>>().ok()?;
+        match (parts.as_slice(), section.is_series()) {
+            ([], _) => Some(DlnaObjectId::Section(section)),
+            ([category_id], _) => Some(DlnaObjectId::Category(section, *category_id)),
+            ([category_id, virtual_id], false) => Some(DlnaObjectId::Item(section, *category_id, *virtual_id)),
+            ([category_id, virtual_id], true) => Some(DlnaObjectId::Series(section, *category_id, *virtual_id)),
+            ([category_id, virtual_id, episode_id], true) => {
+                Some(DlnaObjectId::Episode(section, *category_id, *virtual_id, *episode_id))
+            }
+            _ => None,
+        }
+    }
+
+    fn parent(self) -> String {
+        match self {
+            DlnaObjectId::Root => "-1".to_string(),
+            DlnaObjectId::Library => ROOT_ID.to_string(),
+            DlnaObjectId::Section(section) => section.parent_id().to_string(),
+            DlnaObjectId::Category(section, _) => DlnaObjectId::Section(section).to_string(),
+            DlnaObjectId::Series(section, category_id, _) | DlnaObjectId::Item(section, category_id, _) => {
+                DlnaObjectId::Category(section, category_id).to_string()
+            }
+            DlnaObjectId::Episode(section, category_id, virtual_id, _) => {
+                DlnaObjectId::Series(section, category_id, virtual_id).to_string()
+            }
+        }
+    }
+}
+
+impl fmt::Display for DlnaObjectId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DlnaObjectId::Root => f.write_str(ROOT_ID),
+            DlnaObjectId::Library => f.write_str(LIBRARY_ID),
+            DlnaObjectId::Section(section) => f.write_str(section.id()),
+            DlnaObjectId::Category(section, category_id) => write!(f, "{}/{category_id}", section.id()),
+            DlnaObjectId::Series(section, category_id, virtual_id)
+            | DlnaObjectId::Item(section, category_id, virtual_id) => {
+                write!(f, "{}/{category_id}/{virtual_id}", section.id())
+            }
+            DlnaObjectId::Episode(section, category_id, virtual_id, episode_id) => {
+                write!(f, "{}/{category_id}/{virtual_id}/{episode_id}", section.id())
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum DlnaObject {
+    Container {
+        id: DlnaObjectId,
+        title: String,
+        class: &'static str,
+        child_count: Option<usize>,
+        album_art: Option<String>,
+    },
+    Item {
+        id: DlnaObjectId,
+        title: String,
+        class: &'static str,
+        url: String,
+        mime_type: &'static str,
+        album_art: Option<String>,
+    },
+}
+
+impl DlnaObject {
+    fn folder(id: DlnaObjectId, title: impl Into<String>, child_count: Option<usize>) -> Self {
+        DlnaObject::Container { id, title: title.into(), class: CLASS_FOLDER, child_count, album_art: None }
+    }
+
+    fn title(&self) -> &str {
+        match self {
+            DlnaObject::Container { title, .. } | DlnaObject::Item { title, .. } => title,
+        }
+    }
+}
+
+fn album_art(logo: &str) -> Option<String> { (!logo.is_empty()).then(|| logo.to_string()) }
+
+fn dlna_mime_type(cluster: XtreamCluster, extension: Option<&str>) -> &'static str {
+    if cluster == XtreamCluster::Live {
+        return "video/mp2t";
+    }
+    match extension.map(str::to_ascii_lowercase).as_deref() {
+        Some("mp4" | "m4v") => "video/mp4",
+        Some("mkv") => "video/x-matroska",
+        Some("avi") => "video/x-msvideo",
+        Some("mov") => "video/quicktime",
+        Some("webm") => "video/webm",
+        Some("ts") => "video/mp2t",
+        _ => "video/mpeg",
+    }
+}
+
+fn dlna_render_didl(objects: &[DlnaObject]) -> String {
+    let mut didl = String::from(
+        r#"<DIDL-Lite xmlns="urn:schemas-upnp-org:metadata-1-0/DIDL-Lite/" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:upnp="urn:schemas-upnp-org:metadata-1-0/upnp/">"#,
+    );
+    for object in objects {
+        match object {
+            DlnaObject::Container { id, title, class, child_count, album_art } => {
+                let _ = write!(
+                    didl,
+                    r#"<container id="{}" parentID="{}" restricted="1" searchable="1""#,
+                    escape(id.to_string().as_str()),
+                    escape(id.parent().as_str())
+                );
+                if let Some(count) = child_count {
+                    let _ = write!(didl, r#" childCount="{count}""#);
+                }
+                let _ = write!(didl, "><dc:title>{}</dc:title><upnp:class>{class}</upnp:class>", escape(title.as_str()));
+                if let Some(art) = album_art {
+                    let _ = write!(didl, "<upnp:albumArtURI>{}</upnp:albumArtURI>", escape(art.as_str()));
+                }
+                didl.push_str("</container>");
+            }
+            DlnaObject::Item { id, title, class, url, mime_type, album_art } => {
+                let _ = write!(
+                    didl,
+                    r#"<item id="{}" parentID="{}" restricted="1"><dc:title>{}</dc:title><upnp:class>{class}</upnp:class>"#,
+                    escape(id.to_string().as_str()),
+                    escape(id.parent().as_str()),
+                    escape(title.as_str())
+                );
+                if let Some(art) = album_art {
+                    let _ = write!(didl, "<upnp:albumArtURI>{}</upnp:albumArtURI>", escape(art.as_str()));
+                }
+                let _ = write!(didl, r#"<res protocolInfo="http-get:*:{mime_type}:*">{}</res></item>"#, escape(url.as_str()));
+            }
+        }
+    }
+    didl.push_str("</DIDL-Lite>");
+    didl
+}
+
+/// Returns the unescaped text of the first `<name>` element of a SOAP body.
+fn soap_argument(body: &str, name: &str) -> Option<String> {
+    let open = format!("<{name}");
+    let mut search_from = 0;
+    while let Some(pos) = body[search_from..].find(&open) {
+        let start = search_from + pos + open.len();
+        let rest = &body[start..];
+        let tag_end = rest.find('>')?;
+        // Make sure we matched the complete element name and not a prefix of another one.
+        if rest.starts_with(['>', ' ', '/', '\t', '\r', '\n']) {
+            if rest[..tag_end].ends_with('/') {
+                return Some(String::new());
+            }
+            let content = &rest[tag_end + 1..];
+            let close = content.find(&format!("</{name}>"))?;
+            let raw = &content[..close];
+            return Some(unescape(raw).map_or_else(|_| raw.to_string(), |value| value.to_string()));
+        }
+        search_from = start;
+    }
+    None
+}
+
+fn soap_action(headers: &axum::http::HeaderMap, body: &str) -> Option<String> {
+    if let Some(action) = headers
+        .get("soapaction")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().trim_matches('"').rsplit('#').next())
+        .filter(|value| !value.is_empty())
+    {
+        return Some(action.to_string());
+    }
+    // Fallback: the first element inside the SOAP body names the action.
+    let body_start = body.find(":Body>").or_else(|| body.find("<Body>"))?;
+    let rest = &body[body_start..];
+    let element = &rest[rest.find('>')? + 1..];
+    let element = &element[element.find('<')? + 1..];
+    let name_end = element.find(|c: char| c.is_whitespace() || c == '>' || c == '/')?;
+    let name = &element[..name_end];
+    Some(name.rsplit(':').next().unwrap_or(name).to_string())
+}
+
+fn soap_envelope(content: &str) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="utf-8"?><s:Envelope xmlns:s="http://schemas.xmlsoap.org/soap/envelope/" s:encodingStyle="http://schemas.xmlsoap.org/soap/encoding/"><s:Body>{content}</s:Body></s:Envelope>"#
+    )
+}
+
+fn soap_response(service: &str, action: &str, arguments: &[(&str, String)]) -> String {
+    let mut content = format!(r#"<u:{action}Response xmlns:u="{service}">"#);
+    for (name, value) in arguments {
+        let _ = write!(content, "<{name}>{}</{name}>", escape(value.as_str()));
+    }
+    let _ = write!(content, "</u:{action}Response>");
+    soap_envelope(&content)
+}
+
+fn soap_fault(code: u16, description: &str) -> String {
+    soap_envelope(&format!(
+        r#"<s:Fault><faultcode>s:Client</faultcode><faultstring>UPnPError</faultstring><detail><UPnPError xmlns="urn:schemas-upnp-org:control-1-0"><errorCode>{code}</errorCode><errorDescription>{}</errorDescription></UPnPError></detail></s:Fault>"#,
+        escape(description)
+    ))
+}
+
+fn xml_response(status: axum::http::StatusCode, content: String) -> axum::response::Response {
+    try_unwrap_body!(axum::response::Response::builder()
+        .status(status)
+        .header(axum::http::header::CONTENT_TYPE, r#"text/xml; charset="utf-8""#)
+        .body(axum::body::Body::from(content)))
+}
+
+fn fault_response(code: u16, description: &str) -> axum::response::Response {
+    xml_response(axum::http::StatusCode::INTERNAL_SERVER_ERROR, soap_fault(code, description))
+}
+
+/// Extracts the search terms of `dc:title contains "..."` expressions.
+/// An empty result means the criteria matches everything (`*`).
+fn dlna_search_terms(criteria: &str) -> Vec<String> {
+    let mut terms = vec![];
+    let mut rest = criteria;
+    while let Some(pos) = rest.find("contains") {
+        rest = &rest[pos + "contains".len()..];
+        let Some(start) = rest.find('"') else { break };
+        let value = &rest[start + 1..];
+        let Some(end) = value.find('"') else { break };
+        let term = value[..end].trim().to_lowercase();
+        if !term.is_empty() {
+            terms.push(term);
+        }
+        rest = &value[end + 1..];
+    }
+    terms
+}
+
+#[derive(Debug, Default, serde::Deserialize)]
+struct DlnaRequest {
+    #[serde(default)]
+    token: String,
+}
+
+/// Query appended to every DLNA url, carries the user token when the endpoints are not restricted to the LAN.
+fn dlna_query(dlna: &DlnaConfig, user: &ProxyUserCredentials) -> String {
+    match user.token.as_deref() {
+        Some(token) if !dlna.lan_only => {
+            format!("?token={}", url::form_urlencoded::byte_serialize(token.as_bytes()).collect::<String>())
+        }
+        _ => String::new(),
+    }
+}
+
+/// Absolute url of the device description, announced through SSDP.
+pub(in crate::api) fn get_dlna_location(app_config: &AppConfig) -> Option<String> {
+    let config = app_config.config.load();
+    let dlna = config.dlna.as_ref().filter(|dlna| dlna.enabled)?;
+    let (user, _target) = app_config.get_target_for_username(&dlna.username)?;
+    let base_url = app_config.get_user_server_info(&user).get_base_url();
+    Some(format!("{}{DLNA_DEVICE_PATH}{}", base_url.trim_end_matches('/'), dlna_query(dlna, &user)))
+}
+
+fn dlna_device_xml(dlna: &DlnaConfig, base_url: &str, query: &str) -> String {
+    let query = escape(query);
+    format!(
+        r#"<?xml version="1.0" encoding="utf-8"?>
+<root xmlns="urn:schemas-upnp-org:device-1-0" xmlns:dlna="urn:schemas-dlna-org:device-1-0">
+<specVersion>
+<major>1</major>
+<minor>0</minor>
+</specVersion>
+<URLBase>{}</URLBase>
+<device>
+  <deviceType>urn:schemas-upnp-org:device:MediaServer:1</deviceType>
+  <friendlyName>{}</friendlyName>
+  <manufacturer>Tuliprox</manufacturer>
+  <modelName>Tuliprox</modelName>
+  <modelNumber>{}</modelNumber>
+  <UDN>uuid:{}</UDN>
+  <dlna:X_DLNADOC>DMS-1.50</dlna:X_DLNADOC>
+  <serviceList>
+    <service>
+      <serviceType>{CONTENT_DIRECTORY_SERVICE}</serviceType>
+      <serviceId>urn:upnp-org:serviceId:ContentDirectory</serviceId>
+      <SCPDURL>{DLNA_CONTENT_DIRECTORY_SCPD_PATH}{query}</SCPDURL>
+      <controlURL>{DLNA_CONTENT_DIRECTORY_CONTROL_PATH}{query}</controlURL>
+      <eventSubURL>{DLNA_CONTENT_DIRECTORY_CONTROL_PATH}{query}</eventSubURL>
+    </service>
+    <service>
+      <serviceType>{CONNECTION_MANAGER_SERVICE}</serviceType>
+      <serviceId>urn:upnp-org:serviceId:ConnectionManager</serviceId>
+      <SCPDURL>{DLNA_CONNECTION_MANAGER_SCPD_PATH}{query}</SCPDURL>
+      <controlURL>{DLNA_CONNECTION_MANAGER_CONTROL_PATH}{query}</controlURL>
+      <eventSubURL>{DLNA_CONNECTION_MANAGER_CONTROL_PATH}{query}</eventSubURL>
+    </service>
+  </serviceList>
+</device>
+</root>"#,
+        escape(base_url),
+        escape(dlna.friendly_name.as_str()),
+        env!("CARGO_PKG_VERSION"),
+        escape(dlna.device_udn.as_str()),
+    )
+}
+
+fn scpd_xml(actions: &str, state_variables: &str) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="utf-8"?>
+<scpd xmlns="urn:schemas-upnp-org:service-1-0">
+<specVersion><major>1</major><minor>0</minor></specVersion>
+<actionList>{actions}</actionList>
+<serviceStateTable>{state_variables}</serviceStateTable>
+</scpd>"#
+    )
+}
+
+fn scpd_action(name: &str, arguments: &[(&str, &str, &str)]) -> String {
+    let mut action = format!("<action><name>{name}</name><argumentList>");
+    for (arg_name, direction, variable) in arguments {
+        let _ = write!(
+            action,
+            "<argument><name>{arg_name}</name><direction>{direction}</direction><relatedStateVariable>{variable}</relatedStateVariable></argument>"
+        );
+    }
+    action.push_str("</argumentList></action>");
+    action
+}
+
+fn scpd_variable(name: &str, data_type: &str, allowed: &[&str]) -> String {
+    let mut variable = format!(r#"<stateVariable sendEvents="no"><name>{name}</name><dataType>{data_type}</dataType>"#);
+    if !allowed.is_empty() {
+        variable.push_str("<allowedValueList>");
+        for value in allowed {
+            let _ = write!(variable, "<allowedValue>{value}</allowedValue>");
+        }
+        variable.push_str("</allowedValueList>");
+    }
+    variable.push_str("</stateVariable>");
+    variable
+}
+
+fn content_directory_scpd() -> String {
+    let actions = [
+        scpd_action("GetSearchCapabilities", &[("SearchCaps", "out", "SearchCapabilities")]),
+        scpd_action("GetSortCapabilities", &[("SortCaps", "out", "SortCapabilities")]),
+        scpd_action("GetSystemUpdateID", &[("Id", "out", "SystemUpdateID")]),
+        scpd_action(
+            "Browse",
+            &[
+                ("ObjectID", "in", "A_ARG_TYPE_ObjectID"),
+                ("BrowseFlag", "in", "A_ARG_TYPE_BrowseFlag"),
+                ("Filter", "in", "A_ARG_TYPE_Filter"),
+                ("StartingIndex", "in", "A_ARG_TYPE_Index"),
+                ("RequestedCount", "in", "A_ARG_TYPE_Count"),
+                ("SortCriteria", "in", "A_ARG_TYPE_SortCriteria"),
+                ("Result", "out", "A_ARG_TYPE_Result"),
+                ("NumberReturned", "out", "A_ARG_TYPE_Count"),
+                ("TotalMatches", "out", "A_ARG_TYPE_Count"),
+                ("UpdateID", "out", "A_ARG_TYPE_UpdateID"),
+            ],
+        ),
+        scpd_action(
+            "Search",
+            &[
+                ("ContainerID", "in", "A_ARG_TYPE_ObjectID"),
+                ("SearchCriteria", "in", "A_ARG_TYPE_SearchCriteria"),
+                ("Filter", "in", "A_ARG_TYPE_Filter"),
+                ("StartingIndex", "in", "A_ARG_TYPE_Index"),
+                ("RequestedCount", "in", "A_ARG_TYPE_Count"),
+                ("SortCriteria", "in", "A_ARG_TYPE_SortCriteria"),
+                ("Result", "out", "A_ARG_TYPE_Result"),
+                ("NumberReturned", "out", "A_ARG_TYPE_Count"),
+                ("TotalMatches", "out", "A_ARG_TYPE_Count"),
+                ("UpdateID", "out", "A_ARG_TYPE_UpdateID"),
+            ],
+        ),
+    ]
+    .concat();
+    let variables = [
+        scpd_variable("SearchCapabilities", "string", &[]),
+        scpd_variable("SortCapabilities", "string", &[]),
+        scpd_variable("SystemUpdateID", "ui4", &[]),
+        scpd_variable("A_ARG_TYPE_ObjectID", "string", &[]),
+        scpd_variable("A_ARG_TYPE_BrowseFlag", "string", &["BrowseMetadata", "BrowseDirectChildren"]),
+        scpd_variable("A_ARG_TYPE_Filter", "string", &[]),
+        scpd_variable("A_ARG_TYPE_Index", "ui4", &[]),
+        scpd_variable("A_ARG_TYPE_Count", "ui4", &[]),
+        scpd_variable("A_ARG_TYPE_SortCriteria", "string", &[]),
+        scpd_variable("A_ARG_TYPE_SearchCriteria", "string", &[]),
+        scpd_variable("A_ARG_TYPE_Result", "string", &[]),
+        scpd_variable("A_ARG_TYPE_UpdateID", "ui4", &[]),
+    ]
+    .concat();
+    scpd_xml(&actions, &variables)
+}
+
+fn connection_manager_scpd() -> String {
+    let actions = [
+        scpd_action(
+            "GetProtocolInfo",
+            &[("Source", "out", "SourceProtocolInfo"), ("Sink", "out", "SinkProtocolInfo")],
+        ),
+        scpd_action("GetCurrentConnectionIDs", &[("ConnectionIDs", "out", "CurrentConnectionIDs")]),
+        scpd_action(
+            "GetCurrentConnectionInfo",
+            &[
+                ("ConnectionID", "in", "A_ARG_TYPE_ConnectionID"),
+                ("RcsID", "out", "A_ARG_TYPE_RcsID"),
+                ("AVTransportID", "out", "A_ARG_TYPE_AVTransportID"),
+                ("ProtocolInfo", "out", "A_ARG_TYPE_ProtocolInfo"),
+                ("PeerConnectionManager", "out", "A_ARG_TYPE_ConnectionManager"),
+                ("PeerConnectionID", "out", "A_ARG_TYPE_ConnectionID"),
+                ("Direction", "out", "A_ARG_TYPE_Direction"),
+                ("Status", "out", "A_ARG_TYPE_ConnectionStatus"),
+            ],
+        ),
+    ]
+    .concat();
+    let variables = [
+        scpd_variable("SourceProtocolInfo", "string", &[]),
+        scpd_variable("SinkProtocolInfo", "string", &[]),
+        scpd_variable("CurrentConnectionIDs", "string", &[]),
+        scpd_variable("A_ARG_TYPE_ConnectionStatus", "string", &["OK", "ContentFormatMismatch", "InsufficientBandwidth", "UnreliableChannel", "Unknown"]),
+        scpd_variable("A_ARG_TYPE_ConnectionManager", "string", &[]),
+        scpd_variable("A_ARG_TYPE_Direction", "string", &["Input", "Output"]),
+        scpd_variable("A_ARG_TYPE_ProtocolInfo", "string", &[]),
+        scpd_variable("A_ARG_TYPE_ConnectionID", "i4", &[]),
+        scpd_variable("A_ARG_TYPE_AVTransportID", "i4", &[]),
+        scpd_variable("A_ARG_TYPE_RcsID", "i4", &[]),
+    ]
+    .concat();
+    scpd_xml(&actions, &variables)
+}
+
+/// Everything needed to answer requests for the configured DLNA user.
+struct DlnaContext {
+    app_state: Arc<AppState>,
+    dlna: DlnaConfig,
+    user: ProxyUserCredentials,
+    target: Arc<ConfigTarget>,
+    base_url: String,
+    query: String,
+}
+
+impl DlnaContext {
+    /// Resolves the context for an authorized client, LAN-only servers accept local clients,
+    /// all others require the token of the DLNA user. Forwarded headers only count from trusted proxies.
+    fn authorize(
+        app_state: &Arc<AppState>,
+        fingerprint: &Fingerprint,
+        request: &DlnaRequest,
+    ) -> Result<Self, axum::http::StatusCode> {
+        let ctx = Self::new(app_state).ok_or(axum::http::StatusCode::NOT_FOUND)?;
+        let authorized = if ctx.dlna.lan_only {
+            is_local_client(&ctx.app_state, fingerprint)
+        } else {
+            ctx.user.token.as_deref().is_some_and(|token| {
+                !token.is_empty() && constant_time_eq(token.as_bytes(), request.token.as_bytes())
+            })
+        };
+        if authorized {
+            Ok(ctx)
+        } else {
+            debug!("DLNA request denied for client {}", fingerprint.client_ip);
+            Err(axum::http::StatusCode::FORBIDDEN)
+        }
+    }
+
+    fn new(app_state: &Arc<AppState>) -> Option<Self> {
+        let app_config = &app_state.app_config;
+        let dlna = app_config.config.load().dlna.as_ref().filter(|dlna| dlna.enabled).cloned()?;
+        let Some((user, target)) = app_config.get_target_for_username(&dlna.username) else {
+            debug!("DLNA user {} not found", dlna.username);
+            return None;
+        };
+        if !target.has_output(TargetType::Xtream) {
+            debug!("DLNA requires an xtream output for target {}", target.name);
+            return None;
+        }
+        let base_url = app_config.get_user_server_info(&user).get_base_url().trim_end_matches('/').to_string();
+        let query = dlna_query(&dlna, &user);
+        Some(Self { app_state: Arc::clone(app_state), dlna, user, target, base_url, query })
+    }
+
+    fn has_library(&self) -> bool {
+        self.app_state
+            .app_config
+            .get_inputs_for_target(&self.target.name)
+            .is_some_and(|inputs| inputs.iter().any(|input| input.input_type.is_library()))
+    }
+
+    fn sections(&self) -> Vec<DlnaSection> {
+        let mut sections = DlnaSection::PROVIDER.to_vec();
+        if self.has_library() {
+            sections.extend(DlnaSection::LIBRARY);
+        }
+        sections
+    }
+
+    fn root_children(&self) -> Vec<DlnaObject> {
+        let mut children: Vec<DlnaObject> = DlnaSection::PROVIDER
+            .iter()
+            .map(|section| DlnaObject::folder(DlnaObjectId::Section(*section), section.title(), None))
+            .collect();
+        if self.has_library() {
+            children.push(DlnaObject::folder(DlnaObjectId::Library, "Library", Some(DlnaSection::LIBRARY.len())));
+        }
+        children
+    }
+
+    /// Stream urls are served by the DLNA endpoint for the configured user, which applies the proxy mode,
+    /// redirects and input url flags like the xtream stream endpoints without exposing the user credentials.
+    fn stream_url(&self, cluster: XtreamCluster, virtual_id: u32, extension: Option<&str>) -> String {
+        let (base_url, query, stream_type) = (&self.base_url, &self.query, cluster.as_stream_type());
+        // Live channels are always served as transport stream.
+        let extension = if cluster == XtreamCluster::Live { Some("ts") } else { extension };
+        match extension.filter(|ext| !ext.is_empty()) {
+            Some(ext) => format!("{base_url}{DLNA_STREAM_PATH}/{stream_type}/{virtual_id}.{ext}{query}"),
+            None => format!("{base_url}{DLNA_STREAM_PATH}/{stream_type}/{virtual_id}{query}"),
+        }
+    }
+
+    async fn section_items(&self, section: DlnaSection, category_id: Option<u32>) -> Vec<XtreamPlaylistItem> {
+        let app_config = &self.app_state.app_config;
+        let cluster = section.cluster();
+        let channel_health = if cluster == XtreamCluster::Live {
+            self.app_state.channel_health.output_filter(&self.target)
+        } else {
+            None
+        };
+        let Ok(mut iter) = XtreamPlaylistIterator::new(
+            cluster,
+            app_config,
+            &self.target,
+            category_id,
+            &self.user,
+            channel_health,
+        )
+        .await
+        else {
+            return vec![];
+        };
+        let mut items = vec![];
+        while let Some((item, _has_next)) = iter.next().await {
+            if section.matches(&item) && category_id.is_none_or(|cid| cid == item.category_id) {
+                items.push(item);
+            }
+        }
+        items
+    }
+
+    fn item_object(&self, section: DlnaSection, item: &XtreamPlaylistItem) -> DlnaObject {
+        if section.is_series() {
+            return DlnaObject::Container {
+                id: DlnaObjectId::Series(section, item.category_id, item.virtual_id),
+                title: item.title.to_string(),
+                class: CLASS_SERIES,
+                child_count: Some(series_episodes(item).len()),
+                album_art: album_art(&item.logo),
+            };
+        }
+        let extension = item
+            .get_container_extension()
+            .map(|ext| ext.to_string())
+            .or_else(|| extract_extension_from_url(&item.url).map(|ext| ext.trim_start_matches('.').to_string()));
+        let cluster = section.cluster();
+        DlnaObject::Item {
+            id: DlnaObjectId::Item(section, item.category_id, item.virtual_id),
+            title: item.title.to_string(),
+            class: if cluster == XtreamCluster::Live { CLASS_BROADCAST } else { CLASS_MOVIE },
+            url: self.stream_url(cluster, item.virtual_id, extension.as_deref()),
+            mime_type: dlna_mime_type(cluster, extension.as_deref()),
+            album_art: album_art(&item.logo),
+        }
+    }
+
+    fn episode_objects(&self, section: DlnaSection, series: &XtreamPlaylistItem) -> Vec<DlnaObject> {
+        series_episodes(series)
+            .iter()
+            .map(|episode| {
+                let title = if episode.title.is_empty() {
+                    format!("S{:02}E{:02}", episode.season, episode.episode_num)
+                } else {
+                    format!("S{:02}E{:02} - {}", episode.season, episode.episode_num, episode.title)
+                };
+                DlnaObject::Item {
+                    id: DlnaObjectId::Episode(section, series.category_id, series.virtual_id, episode.id),
+                    title,
+                    class: CLASS_VIDEO,
+                    url: self.stream_url(XtreamCluster::Series, episode.id, Some(&episode.container_extension)),
+                    mime_type: dlna_mime_type(XtreamCluster::Series, Some(&episode.container_extension)),
+                    album_art: album_art(&series.logo),
+                }
+            })
+            .collect()
+    }
+
+    async fn find_item(&self, section: DlnaSection, category_id: u32, virtual_id: u32) -> Option<XtreamPlaylistItem> {
+        self.section_items(section, Some(category_id)).await.into_iter().find(|item| item.virtual_id == virtual_id)
+    }
+
+    async fn children(&self, id: DlnaObjectId) -> Option<Vec<DlnaObject>> {
+        match id {
+            DlnaObjectId::Root => Some(self.root_children()),
+            DlnaObjectId::Library => self.has_library().then(|| {
+                DlnaSection::LIBRARY
+                    .iter()
+                    .map(|section| DlnaObject::folder(DlnaObjectId::Section(*section), section.title(), None))
+                    .collect()
+            }),
+            DlnaObjectId::Section(section) => {
+                let mut categories: Vec<(u32, String, usize)> = vec![];
+                for item in self.section_items(section, None).await {
+                    match categories.iter_mut().find(|(category_id, _, _)| *category_id == item.category_id) {
+                        Some((_, _, count)) => *count += 1,
+                        None => categories.push((item.category_id, item.group.to_string(), 1)),
+                    }
+                }
+                Some(
+                    categories
+                        .into_iter()
+                        .map(|(category_id, group, count)| {
+                            DlnaObject::folder(DlnaObjectId::Category(section, category_id), group, Some(count))
+                        })
+                        .collect(),
+                )
+            }
+            DlnaObjectId::Category(section, category_id) => Some(
+                self.section_items(section, Some(category_id))
+                    .await
+                    .iter()
+                    .map(|item| self.item_object(section, item))
+                    .collect(),
+            ),
+            DlnaObjectId::Series(section, category_id, virtual_id) => {
+                let series = self.find_item(section, category_id, virtual_id).await?;
+                Some(self.episode_objects(section, &series))
+            }
+            DlnaObjectId::Item(..) | DlnaObjectId::Episode(..) => Some(vec![]),
+        }
+    }
+
+    async fn metadata(&self, id: DlnaObjectId) -> Option<DlnaObject> {
+        match id {
+            DlnaObjectId::Root => {
+                Some(DlnaObject::folder(id, self.dlna.friendly_name.clone(), Some(self.root_children().len())))
+            }
+            DlnaObjectId::Library => self
+                .has_library()
+                .then(|| DlnaObject::folder(id, "Library", Some(DlnaSection::LIBRARY.len()))),
+            DlnaObjectId::Section(section) => Some(DlnaObject::folder(id, section.title(), None)),
+            DlnaObjectId::Category(section, category_id) => {
+                let items = self.section_items(section, Some(category_id)).await;
+                let group = items.first()?.group.to_string();
+                Some(DlnaObject::folder(id, group, Some(items.len())))
+            }
+            DlnaObjectId::Series(section, category_id, virtual_id)
+            | DlnaObjectId::Item(section, category_id, virtual_id) => {
+                let item = self.find_item(section, category_id, virtual_id).await?;
+                Some(self.item_object(section, &item))
+            }
+            DlnaObjectId::Episode(section, category_id, virtual_id, _) => {
+                let series = self.find_item(section, category_id, virtual_id).await?;
+                self.episode_objects(section, &series).into_iter().find(|episode| match episode {
+                    DlnaObject::Item { id: episode_id, .. } => *episode_id == id,
+                    DlnaObject::Container { .. } => false,
+                })
+            }
+        }
+    }
+
+    async fn search(&self, id: DlnaObjectId, terms: &[String]) -> Option<Vec<DlnaObject>> {
+        let (sections, category_id) = match id {
+            DlnaObjectId::Root => (self.sections(), None),
+            DlnaObjectId::Library => (DlnaSection::LIBRARY.to_vec(), None),
+            DlnaObjectId::Section(section) => (vec![section], None),
+            DlnaObjectId::Category(section, category_id) => (vec![section], Some(category_id)),
+            DlnaObjectId::Series(..) | DlnaObjectId::Item(..) | DlnaObjectId::Episode(..) => {
+                return self.children(id).await.map(|children| filter_by_terms(children, terms));
+            }
+        };
+        let mut result = vec![];
+        for section in sections {
+            for item in self.section_items(section, category_id).await {
+                result.push(self.item_object(section, &item));
+            }
+        }
+        Some(filter_by_terms(result, terms))
+    }
+
+    fn system_update_id(&self) -> u32 {
+        let config = self.app_state.app_config.config.load();
+        let Some(storage_path) = xtream_get_storage_path(&config, &self.target.name) else {
+            return 0;
+        };
+        [XtreamCluster::Live, XtreamCluster::Video, XtreamCluster::Series]
+            .into_iter()
+            .filter_map(|cluster| std::fs::metadata(xtream_get_file_path(&storage_path, cluster)).ok())
+            .filter_map(|metadata| metadata.modified().ok())
+            .filter_map(|modified| modified.duration_since(UNIX_EPOCH).ok())
+            .map(|duration| u32::try_from(duration.as_secs() % u64::from(u32::MAX)).unwrap_or_default())
+            .max()
+            .unwrap_or_default()
+    }
+}
+
+fn series_episodes(item: &XtreamPlaylistItem) -> &[shared::model::SeriesStreamDetailEpisodeProperties] {
+    match item.additional_properties.as_ref() {
+        Some(StreamProperties::Series(series)) => {
+            series.details.as_ref().and_then(|details| details.episodes.as_deref()).unwrap_or_default()
+        }
+        _ => &[],
+    }
+}
+
+fn filter_by_terms(objects: Vec<DlnaObject>, terms: &[String]) -> Vec<DlnaObject> {
+    if terms.is_empty() {
+        return objects;
+    }
+    objects
+        .into_iter()
+        .filter(|object| {
+            let title = object.title().to_lowercase();
+            terms.iter().all(|term| title.contains(term.as_str()))
+        })
+        .collect()
+}
+
+fn paginate(objects: Vec<DlnaObject>, starting_index: usize, requested_count: usize) -> (Vec<DlnaObject>, usize) {
+    let total = objects.len();
+    let page = objects
+        .into_iter()
+        .skip(starting_index)
+        .take(if requested_count == 0 { usize::MAX } else { requested_count })
+        .collect();
+    (page, total)
+}
+
+fn browse_result_response(action: &str, objects: Vec<DlnaObject>, body: &str, update_id: u32) -> axum::response::Response {
+    let starting_index = soap_argument(body, "StartingIndex").and_then(|v| v.trim().parse().ok()).unwrap_or(0);
+    let requested_count = soap_argument(body, "RequestedCount").and_then(|v| v.trim().parse().ok()).unwrap_or(0);
+    let (page, total) = paginate(objects, starting_index, requested_count);
+    let response = soap_response(
+        CONTENT_DIRECTORY_SERVICE,
+        action,
+        &[
+            ("Result", dlna_render_didl(&page)),
+            ("NumberReturned", page.len().to_string()),
+            ("TotalMatches", total.to_string()),
+            ("UpdateID", update_id.to_string()),
+        ],
+    );
+    xml_response(axum::http::StatusCode::OK, response)
+}
+
+async fn content_directory_control(ctx: &DlnaContext, action: &str, body: &str) -> axum::response::Response {
+    match action {
+        "GetSearchCapabilities" => xml_response(
+            axum::http::StatusCode::OK,
+            soap_response(CONTENT_DIRECTORY_SERVICE, action, &[("SearchCaps", "dc:title".to_string())]),
+        ),
+        "GetSortCapabilities" => xml_response(
+            axum::http::StatusCode::OK,
+            soap_response(CONTENT_DIRECTORY_SERVICE, action, &[("SortCaps", String::new())]),
+        ),
+        "GetSystemUpdateID" => xml_response(
+            axum::http::StatusCode::OK,
+            soap_response(CONTENT_DIRECTORY_SERVICE, action, &[("Id", ctx.system_update_id().to_string())]),
+        ),
+        "Browse" => {
+            let Some(id) = soap_argument(body, "ObjectID").and_then(|id| DlnaObjectId::parse(&id)) else {
+                return fault_response(UPNP_ERROR_NO_SUCH_OBJECT, "No such object");
+            };
+            let objects = match soap_argument(body, "BrowseFlag").as_deref().map(str::trim) {
+                Some("BrowseMetadata") => ctx.metadata(id).await.map(|object| vec![object]),
+                Some("BrowseDirectChildren") => ctx.children(id).await,
+                _ => return fault_response(UPNP_ERROR_INVALID_ARGS, "Invalid BrowseFlag"),
+            };
+            match objects {
+                Some(objects) => browse_result_response(action, objects, body, ctx.system_update_id()),
+                None => fault_response(UPNP_ERROR_NO_SUCH_OBJECT, "No such object"),
+            }
+        }
+        "Search" => {
+            let Some(id) = soap_argument(body, "ContainerID").and_then(|id| DlnaObjectId::parse(&id)) else {
+                return fault_response(UPNP_ERROR_NO_SUCH_OBJECT, "No such container");
+            };
+            let terms = dlna_search_terms(&soap_argument(body, "SearchCriteria").unwrap_or_default());
+            match ctx.search(id, &terms).await {
+                Some(objects) => browse_result_response(action, objects, body, ctx.system_update_id()),
+                None => fault_response(UPNP_ERROR_NO_SUCH_OBJECT, "No such container"),
+            }
+        }
+        _ => fault_response(UPNP_ERROR_INVALID_ACTION, "Invalid Action"),
+    }
+}
+
+fn connection_manager_control(action: &str) -> axum::response::Response {
+    let arguments = match action {
+        "GetProtocolInfo" => vec![("Source", SOURCE_PROTOCOL_INFO.to_string()), ("Sink", String::new())],
+        "GetCurrentConnectionIDs" => vec![("ConnectionIDs", "0".to_string())],
+        "GetCurrentConnectionInfo" => vec![
+            ("RcsID", "-1".to_string()),
+            ("AVTransportID", "-1".to_string()),
+            ("ProtocolInfo", String::new()),
+            ("PeerConnectionManager", String::new()),
+            ("PeerConnectionID", "-1".to_string()),
+            ("Direction", "Output".to_string()),
+            ("Status", "OK".to_string()),
+        ],
+        _ => return fault_response(UPNP_ERROR_INVALID_ACTION, "Invalid Action"),
+    };
+    xml_response(axum::http::StatusCode::OK, soap_response(CONNECTION_MANAGER_SERVICE, action, &arguments))
+}
+
+async fn dlna_device_description(
+    fingerprint: Fingerprint,
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+    axum::extract::Query(request): axum::extract::Query<DlnaRequest>,
+) -> impl IntoResponse + Send {
+    match DlnaContext::authorize(&app_state, &fingerprint, &request) {
+        Ok(ctx) => xml_response(axum::http::StatusCode::OK, dlna_device_xml(&ctx.dlna, &ctx.base_url, &ctx.query)),
+        Err(status) => status.into_response(),
+    }
+}
+
+async fn dlna_content_directory_scpd(
+    fingerprint: Fingerprint,
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+    axum::extract::Query(request): axum::extract::Query<DlnaRequest>,
+) -> impl IntoResponse + Send {
+    if let Err(status) = DlnaContext::authorize(&app_state, &fingerprint, &request) {
+        return status.into_response();
+    }
+    xml_response(axum::http::StatusCode::OK, content_directory_scpd())
+}
+
+async fn dlna_connection_manager_scpd(
+    fingerprint: Fingerprint,
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+    axum::extract::Query(request): axum::extract::Query<DlnaRequest>,
+) -> impl IntoResponse + Send {
+    if let Err(status) = DlnaContext::authorize(&app_state, &fingerprint, &request) {
+        return status.into_response();
+    }
+    xml_response(axum::http::StatusCode::OK, connection_manager_scpd())
+}
+
+async fn dlna_content_directory_control(
+    fingerprint: Fingerprint,
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+    axum::extract::Query(request): axum::extract::Query<DlnaRequest>,
+    headers: axum::http::HeaderMap,
+    body: String,
+) -> impl IntoResponse + Send {
+    let ctx = match DlnaContext::authorize(&app_state, &fingerprint, &request) {
+        Ok(ctx) => ctx,
+        Err(status) => return status.into_response(),
+    };
+    let Some(action) = soap_action(&headers, &body) else {
+        return fault_response(UPNP_ERROR_INVALID_ACTION, "Invalid Action");
+    };
+    trace!("DLNA ContentDirectory action {action}");
+    content_directory_control(&ctx, &action, &body).await
+}
+
+async fn dlna_connection_manager_control(
+    fingerprint: Fingerprint,
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+    axum::extract::Query(request): axum::extract::Query<DlnaRequest>,
+    headers: axum::http::HeaderMap,
+    body: String,
+) -> impl IntoResponse + Send {
+    if let Err(status) = DlnaContext::authorize(&app_state, &fingerprint, &request) {
+        return status.into_response();
+    }
+    match soap_action(&headers, &body) {
+        Some(action) => connection_manager_control(&action),
+        None => fault_response(UPNP_ERROR_INVALID_ACTION, "Invalid Action"),
+    }
+}
+
+async fn dlna_stream(
+    fingerprint: Fingerprint,
+    axum::extract::Path((stream_type, stream_id)): axum::extract::Path<(String, String)>,
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+    axum::extract::Query(request): axum::extract::Query<DlnaRequest>,
+    headers: axum::http::HeaderMap,
+) -> impl IntoResponse + Send {
+    let ctx = match DlnaContext::authorize(&app_state, &fingerprint, &request) {
+        Ok(ctx) => ctx,
+        Err(status) => return status.into_response(),
+    };
+    let Ok(context) = ApiStreamContext::from_str(&stream_type) else {
+        return axum::http::StatusCode::BAD_REQUEST.into_response();
+    };
+    xtream_player_api_user_stream(&fingerprint, &headers, &app_state, (ctx.user, ctx.target), context, &stream_id).await
+}
+
+pub fn dlna_api_register() -> axum::Router<Arc<AppState>> {
+    axum::Router::new()
+        .route(DLNA_DEVICE_PATH, axum::routing::get(dlna_device_description))
+        .route(DLNA_CONTENT_DIRECTORY_SCPD_PATH, axum::routing::get(dlna_content_directory_scpd))
+        .route(DLNA_CONNECTION_MANAGER_SCPD_PATH, axum::routing::get(dlna_connection_manager_scpd))
+        .route(DLNA_CONTENT_DIRECTORY_CONTROL_PATH, axum::routing::post(dlna_content_directory_control))
+        .route(DLNA_CONNECTION_MANAGER_CONTROL_PATH, axum::routing::post(dlna_connection_manager_control))
+        .route(&format!("{DLNA_STREAM_PATH}/{{stream_type}}/{{stream_id}}"), axum::routing::get(dlna_stream))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn object_id_roundtrip() {
+        let ids = [
+            DlnaObjectId::Root,
+            DlnaObjectId::Library,
+            DlnaObjectId::Section(DlnaSection::Live),
+            DlnaObjectId::Section(DlnaSection::LibrarySeries),
+            DlnaObjectId::Category(DlnaSection::Vod, 12),
+            DlnaObjectId::Item(DlnaSection::Live, 3, 4711),
+            DlnaObjectId::Series(DlnaSection::Series, 5, 100),
+            DlnaObjectId::Episode(DlnaSection::LibrarySeries, 5, 100, 101),
+        ];
+        for id in ids {
+            assert_eq!(DlnaObjectId::parse(&id.to_string()), Some(id));
+        }
+        assert_eq!(DlnaObjectId::parse("livestream"), None);
+        assert_eq!(DlnaObjectId::parse("vod/1/2/3"), None);
+        assert_eq!(DlnaObjectId::parse("live/abc"), None);
+        assert_eq!(DlnaObjectId::Episode(DlnaSection::Series, 1, 2, 3).parent(), "series/1/2");
+        assert_eq!(DlnaObjectId::Section(DlnaSection::LibraryVod).parent(), "library");
+    }
+
+    #[test]
+    fn soap_argument_and_action_are_extracted() {
+        let body = r#"<?xml version="1.0"?><s:Envelope xmlns:s="http://schemas.xmlsoap.org/soap/envelope/"><s:Body>
+            <u:Search xmlns:u="urn:schemas-upnp-org:service:ContentDirectory:1"><ContainerID>0</ContainerID>
+            <SearchCriteria>dc:title contains &quot;News&quot;</SearchCriteria><Filter/><StartingIndex>0</StartingIndex>
+            </u:Search></s:Body></s:Envelope>"#;
+        assert_eq!(soap_argument(body, "ContainerID").as_deref(), Some("0"));
+        assert_eq!(soap_argument(body, "Filter").as_deref(), Some(""));
+        assert_eq!(soap_argument(body, "SearchCriteria").as_deref(), Some(r#"dc:title contains "News""#));
+        assert_eq!(soap_argument(body, "Start"), None);
+        assert_eq!(soap_action(&axum::http::HeaderMap::new(), body).as_deref(), Some("Search"));
+
+        let mut headers = axum::http::HeaderMap::new();
+        headers.insert("SOAPAction", r#""urn:schemas-upnp-org:service:ContentDirectory:1#Browse""#.parse().unwrap());
+        assert_eq!(soap_action(&headers, body).as_deref(), Some("Browse"));
+    }
+
+    #[test]
+    fn search_terms_are_parsed() {
+        assert!(dlna_search_terms("*").is_empty());
+        assert_eq!(
+            dlna_search_terms(r#"upnp:class derivedfrom "object.item.videoItem" and dc:title contains "Sport HD""#),
+            vec!["sport hd".to_string()]
+        );
+    }
+
+    #[test]
+    fn didl_is_escaped() {
+        let objects = vec![
+            DlnaObject::folder(DlnaObjectId::Category(DlnaSection::Live, 1), "News & Info", Some(1)),
+            DlnaObject::Item {
+                id: DlnaObjectId::Item(DlnaSection::Live, 1, 7),
+                title: "<BBC>".to_string(),
+                class: CLASS_BROADCAST,
+                url: "http://host/live/u/p/7.ts?a=1&b=2".to_string(),
+                mime_type: "video/mp2t",
+                album_art: None,
+            },
+        ];
+        let didl = dlna_render_didl(&objects);
+        assert!(didl.contains(r#"<container id="live/1" parentID="live" restricted="1" searchable="1" childCount="1">"#));
+        assert!(didl.contains("<dc:title>News &amp; Info</dc:title>"));
+        assert!(didl.contains("<dc:title>&lt;BBC&gt;</dc:title>"));
+        assert!(didl.contains(r#"<res protocolInfo="http-get:*:video/mp2t:*">http://host/live/u/p/7.ts?a=1&amp;b=2</res>"#));
+
+        let response = soap_response(CONTENT_DIRECTORY_SERVICE, "Browse", &[("Result", didl)]);
+        assert!(response.contains("<Result>&lt;DIDL-Lite"));
+    }
+
+    #[test]
+    fn token_query_is_only_used_outside_the_lan() {
+        let mut dlna = DlnaConfig {
+            enabled: true,
+            username: "tv".to_string(),
+            friendly_name: "Tuliprox".to_string(),
+            device_udn: "1234".to_string(),
+            ssdp_discovery: true,
+            lan_only: true,
+        };
+        let mut user = ProxyUserCredentials::default();
+        user.token = Some("a&b".to_string());
+        assert_eq!(dlna_query(&dlna, &user), "");
+
+        dlna.lan_only = false;
+        let query = dlna_query(&dlna, &user);
+        assert_eq!(query, "?token=a%26b");
+        let xml = dlna_device_xml(&dlna, "http://host", &query);
+        assert!(xml.contains(&format!("<controlURL>{DLNA_CONTENT_DIRECTORY_CONTROL_PATH}?token=a%26b</controlURL>")));
+    }
+
+    #[test]
+    fn paginate_respects_window() {
+        let objects: Vec<DlnaObject> = (0..5)
+            .map(|i| DlnaObject::folder(DlnaObjectId::Category(DlnaSection::Vod, i), i.to_string(), None))
+            .collect();
+        let (page, total) = paginate(objects.clone(), 1, 2);
+        assert_eq!(total, 5);
+        assert_eq!(page.iter().map(DlnaObject::title).collect::<Vec<_>>(), vec!["1", "2"]);
+        let (page, _) = paginate(objects, 3, 0);
+        assert_eq!(page.len(), 2);
+    }
+}
@@ -3,11 +3,12 @@ use crate::{
         AppState, ActiveProviderManager, ConnectionManager, DownloadControl, DownloadKind, DownloadQueue,
         DownloadState, DownloadWaitOutcome, EventManager, EventMessage, FileDownload, FileDownloadRequest,
         FileRecordingRequest,
-        RecordingExecutionResult, run_recording,
+        RecordingExecutionResult, run_recording, VodCache,
     },
     model::{AppConfig, VideoDownloadConfig},
     utils::{async_file_writer, request, request::create_client, IO_BUFFER_SIZE},
 };
+use arc_swap::ArcSwapOption;
 use axum::response::IntoResponse;
 use futures::stream::TryStreamExt;
 use log::{debug, error, info, warn};
@@ -762,6 +763,13 @@ fn preemption_reason_for(download: &FileDownload) -> &'static str {
     }
 }
 
+/// Downloaded movies and episodes are served from the vod cache without a provider connection.
+async fn import_download_into_vod_cache(vod_cache: Arc<VodCache>, url: String, file_path: std::path::PathBuf) {
+    if let Err(err) = vod_cache.import_file(&url, &file_path).await {
+        warn!("Failed to add download to vod cache: {err}");
+    }
+}
+
 #[allow(clippy::too_many_lines)]
 pub(in crate::api) async fn ensure_download_worker_running(
     cfg: &AppConfig,
@@ -770,6 +778,7 @@ pub(in crate::api) async fn ensure_download_worker_running(
     event_manager: &Arc<EventManager>,
     active_provider: &Arc<ActiveProviderManager>,
     connection_manager: &Arc<ConnectionManager>,
+    vod_cache: &Arc<ArcSwapOption<VodCache>>,
 ) -> Result<(), String> {
     let mut worker_running = download_queue.worker_running.write().await;
     if *worker_running {
@@ -806,6 +815,7 @@ pub(in crate::api) async fn ensure_download_worker_running(
         let event_manager = Arc::clone(event_manager);
         let active_provider = Arc::clone(active_provider);
         let connection_manager = Arc::clone(connection_manager);
+        let vod_cache = Arc::clone(vod_cache);
         let download_cfg = download_cfg.clone();
 
         if let Ok(client) = create_client(cfg).default_headers(headers).build() {
@@ -988,11 +998,20 @@ pub(in crate::api) async fn ensure_download_worker_running(
                             {
                                 DownloadExecutionResult::Completed => {
                                     connection_manager.release_provider_handle(provider_handle).await;
+                                    let mut completed_download = None;
                                     if let Some(fd) = &mut *dq.active.write().await {
                                         fd.finished = true;
                                         fd.state = DownloadState::Completed;
                                         fd.next_retry_at = None;
                                         dq.finished.write().await.push(fd.clone());
+                                        if fd.kind == DownloadKind::Download {
+                                            completed_download = Some((fd.url.to_string(), fd.file_path.clone()));
+                                        }
+                                    }
+                                    if let (Some(cache), Some((url, file_path))) =
+                                        (vod_cache.load_full(), completed_download)
+                                    {
+                                        import_download_into_vod_cache(cache, url, file_path).await;
                                     }
                                     let _ = dq.persist_to_disk().await;
                                     *dq.active.write().await = dq.queue.lock().await.pop_front();
@@ -1195,6 +1214,7 @@ pub(in crate::api) async fn ensure_download_worker_running(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(in crate::api) fn start_download_scheduler(
     app_config: Arc<AppConfig>,
     download_cfg: VideoDownloadConfig,
@@ -1202,6 +1222,7 @@ pub(in crate::api) fn start_download_scheduler(
     event_manager: Arc<EventManager>,
     active_provider: Arc<ActiveProviderManager>,
     connection_manager: Arc<ConnectionManager>,
+    vod_cache: Arc<ArcSwapOption<VodCache>>,
     cancel_token: CancellationToken,
 ) {
     // Bridge task: whenever any provider connection is released, wake only the
@@ -1279,6 +1300,7 @@ pub(in crate::api) fn start_download_scheduler(
                 &event_manager,
                 &active_provider,
                 &connection_manager,
+                &vod_cache,
             )
             .await;
         }
@@ -1298,6 +1320,7 @@ pub(in crate::api) fn spawn_download_services(app_state: &AppState, cancel_token
         Arc::clone(&app_state.event_manager),
         Arc::clone(&app_state.active_provider),
         Arc::clone(&app_state.connection_manager),
+        Arc::clone(&app_state.vod_cache),
         cancel_token.clone(),
     );
 }
@@ -1317,6 +1340,7 @@ pub(in crate::api) async fn resume_download_worker_if_needed(
         &app_state.event_manager,
         &app_state.active_provider,
         &app_state.connection_manager,
+        &app_state.vod_cache,
     )
     .await
 }
@@ -1362,6 +1386,7 @@ pub async fn queue_download_file(
                             &app_state.event_manager,
                             &app_state.active_provider,
                             &app_state.connection_manager,
+                            &app_state.vod_cache,
                         )
                         .await
                         {
@@ -1510,6 +1535,7 @@ pub async fn resume_download(
                         &app_state.event_manager,
                         &app_state.active_provider,
                         &app_state.connection_manager,
+                        &app_state.vod_cache,
                     )
                     .await
                     {
@@ -1556,6 +1582,7 @@ pub async fn cancel_download(
                         &app_state.event_manager,
                         &app_state.active_provider,
                         &app_state.connection_manager,
+                        &app_state.vod_cache,
                     )
                     .await;
                 }
@@ -1603,6 +1630,7 @@ pub async fn retry_download(
                         &app_state.event_manager,
                         &app_state.active_provider,
                         &app_state.connection_manager,
+                        &app_state.vod_cache,
                     )
                     .await;
                 }
@@ -1959,8 +1987,17 @@ mod tests {
             http_client: Arc::new(ArcSwap::from_pointee(reqwest::Client::new())),
             http_client_no_redirect: Arc::new(ArcSwap::from_pointee(reqwest::Client::new())),
             downloads: Arc::new(DownloadQueue::new()),
+            user_quota: Arc::new(crate::api::model::UserQuotaManager::new_with_state_file(None)),
+            parental_unlocks: Arc::new(crate::api::model::ParentalUnlockManager::default()),
+            user_devices: Arc::new(crate::api::model::UserDeviceManager::new_with_state_file(None)),
+            user_travel: Arc::new(crate::api::model::UserTravelManager::new_with_state_file(None)),
+            channel_health: Arc::new(crate::api::model::ChannelHealthManager::new_with_state_file(None)),
+            login_bans: Arc::new(crate::api::model::LoginBanManager::new_with_state_file(None)),
+            user_rate_limits: Arc::new(crate::api::model::UserRateLimiter::default()),
             cache: Arc::new(ArcSwapOption::default()),
+            vod_cache: Arc::new(ArcSwapOption::default()),
             shared_stream_manager,
+            hls_segmenters: Arc::default(),
             active_users,
             active_provider,
             connection_manager,
@@ -0,0 +1,157 @@
+use crate::api::api_utils::{
+    get_user_target_by_credentials, try_option_forbidden, try_unwrap_body, user_playlist_access_denied,
+};
+use crate::api::model::{AppState, UserApiRequest};
+use crate::auth::Fingerprint;
+use crate::model::{ConfigTarget, Enigma2TargetOutput, ProxyUserCredentials};
+use crate::repository::{
+    enigma2_bouquet_prefix, enigma2_build_export, enigma2_epgimport_urls, enigma2_filter_item, enigma2_includes_cluster,
+    enigma2_item_cluster, enigma2_m3u_item_channel, enigma2_render_bouquet, enigma2_render_bouquets_index,
+    enigma2_render_epgimport_channels, enigma2_render_epgimport_sources, enigma2_xtream_item_channel, Enigma2Channel,
+    Enigma2Export, M3uPlaylistIterator, XtreamPlaylistIterator, ENIGMA2_BOUQUETS_INDEX,
+};
+use axum::response::IntoResponse;
+use futures::StreamExt;
+use log::{debug, error};
+use shared::model::{PlaylistItem, TargetType, XtreamCluster};
+use std::sync::Arc;
+
+async fn enigma2_collect_xtream_channels(
+    app_state: &AppState,
+    target: &ConfigTarget,
+    output: &Enigma2TargetOutput,
+    user: &ProxyUserCredentials,
+    base_url: &str,
+) -> Vec<Enigma2Channel> {
+    let app_config = &app_state.app_config;
+    let mut channels = vec![];
+    for cluster in [XtreamCluster::Live, XtreamCluster::Video] {
+        if !enigma2_includes_cluster(output, cluster) {
+            continue;
+        }
+        let channel_health =
+            if cluster == XtreamCluster::Live { app_state.channel_health.output_filter(target) } else { None };
+        let Ok(mut iter) = XtreamPlaylistIterator::new(cluster, app_config, target, None, user, channel_health).await
+        else {
+            continue;
+        };
+        while let Some((item, _has_next)) = iter.next().await {
+            let pli = PlaylistItem::from(&item);
+            if enigma2_filter_item(output, &pli) {
+                channels.push(enigma2_xtream_item_channel(app_config, target, user, base_url, &item));
+            }
+        }
+    }
+    channels
+}
+
+async fn enigma2_collect_m3u_channels(
+    app_state: &AppState,
+    target: &ConfigTarget,
+    output: &Enigma2TargetOutput,
+    user: &ProxyUserCredentials,
+) -> Vec<Enigma2Channel> {
+    let channel_health = app_state.channel_health.output_filter(target);
+    let mut iter = match M3uPlaylistIterator::new(&app_state.app_config, target, user, channel_health).await {
+        Ok(iter) => iter,
+        Err(err) => {
+            error!("Failed to read m3u playlist for enigma2 target {}: {err}", target.name);
+            return vec![];
+        }
+    };
+    let mut channels = vec![];
+    while let Some((item, _has_next)) = iter.next().await {
+        let included =
+            enigma2_item_cluster(item.item_type).is_some_and(|cluster| enigma2_includes_cluster(output, cluster));
+        if included && enigma2_filter_item(output, &PlaylistItem::from(&item)) {
+            channels.push(enigma2_m3u_item_channel(&item));
+        }
+    }
+    channels
+}
+
+/// Xtream targets get xtream stream urls, m3u-only targets the stream urls of the m3u playlist.
+async fn enigma2_collect_channels(
+    app_state: &AppState,
+    target: &ConfigTarget,
+    output: &Enigma2TargetOutput,
+    user: &ProxyUserCredentials,
+    base_url: &str,
+) -> Option<Vec<Enigma2Channel>> {
+    if target.has_output(TargetType::Xtream) {
+        Some(enigma2_collect_xtream_channels(app_state, target, output, user, base_url).await)
+    } else if target.has_output(TargetType::M3u) {
+        Some(enigma2_collect_m3u_channels(app_state, target, output, user).await)
+    } else {
+        None
+    }
+}
+
+fn enigma2_file_content(
+    export: &Enigma2Export,
+    target: &ConfigTarget,
+    user: &ProxyUserCredentials,
+    base_url: &str,
+    epgimport: bool,
+    file: &str,
+) -> Option<(String, mime::Mime)> {
+    if file == ENIGMA2_BOUQUETS_INDEX {
+        return Some((enigma2_render_bouquets_index(export), mime::TEXT_PLAIN_UTF_8));
+    }
+    if let Some(bouquet) = export.find_bouquet(file) {
+        return Some((enigma2_render_bouquet(bouquet), mime::TEXT_PLAIN_UTF_8));
+    }
+    if epgimport {
+        if file == export.epgimport_channels_filename() {
+            return Some((enigma2_render_epgimport_channels(export), mime::TEXT_XML));
+        }
+        if file == export.epgimport_sources_filename() {
+            let (channels_url, xmltv_url) = enigma2_epgimport_urls(base_url, user, export);
+            return Some((enigma2_render_epgimport_sources(&target.name, &channels_url, &xmltv_url), mime::TEXT_XML));
+        }
+    }
+    None
+}
+
+async fn enigma2_api(
+    fingerprint: Fingerprint,
+    req_headers: axum::http::HeaderMap,
+    axum::extract::Path((username, password, file)): axum::extract::Path<(String, String, String)>,
+    axum::extract::Query(api_req): axum::extract::Query<UserApiRequest>,
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+) -> impl IntoResponse + Send {
+    let auth_status = app_state.app_config.get_auth_error_status();
+    let (user, target) = try_option_forbidden!(
+        get_user_target_by_credentials(username.as_str(), password.as_str(), &api_req, &app_state, &fingerprint),
+        auth_status,
+        false,
+        format!("Could not find any user for enigma2 api {username}")
+    );
+
+    let Some(output) = target.get_enigma2_output() else {
+        debug!("Target has no enigma2 output {}", target.name);
+        return axum::http::StatusCode::BAD_REQUEST.into_response();
+    };
+    if user_playlist_access_denied(&app_state, &fingerprint, &user, &target, &req_headers, &api_req.device_id).await {
+        return axum::http::StatusCode::FORBIDDEN.into_response();
+    }
+
+    let base_url = app_state.app_config.get_user_server_info(&user).get_base_url();
+    let Some(channels) = enigma2_collect_channels(&app_state, &target, output, &user, &base_url).await else {
+        debug!("Enigma2 requires an xtream or m3u output for target {}", target.name);
+        return axum::http::StatusCode::BAD_REQUEST.into_response();
+    };
+    let export = enigma2_build_export(enigma2_bouquet_prefix(&target, output), channels);
+
+    match enigma2_file_content(&export, &target, &user, &base_url, output.epgimport, file.as_str()) {
+        Some((content, content_type)) => try_unwrap_body!(axum::response::Response::builder()
+            .status(axum::http::StatusCode::OK)
+            .header(axum::http::header::CONTENT_TYPE, content_type.to_string())
+            .body(axum::body::Body::from(content))),
+        None => axum::http::StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+pub fn enigma2_api_register() -> axum::Router<Arc<AppState>> {
+    axum::Router::new().route("/enigma2/{username}/{password}/{file}", axum::routing::get(enigma2_api))
+}
@@ -0,0 +1,141 @@
+use crate::api::api_utils::{
+    get_user_target_by_credentials, try_option_forbidden, try_unwrap_body, user_playlist_access_denied,
+};
+use crate::api::model::{AppState, UserApiRequest};
+use crate::auth::Fingerprint;
+use crate::model::{ConfigTarget, ProxyUserCredentials};
+use crate::repository::{
+    export_filter_item, export_m3u_item, export_xtream_item, M3uPlaylistIterator, PlaylistExportFormat,
+    XtreamPlaylistIterator,
+};
+use axum::response::IntoResponse;
+use futures::StreamExt;
+use log::{debug, error};
+use shared::foundation::Filter;
+use shared::model::{PlaylistItem, TargetType, XtreamCluster};
+use shared::utils::sanitize_sensitive_info;
+use std::sync::Arc;
+
+const EXPORT_FILE_JSON: &str = "playlist.json";
+const EXPORT_FILE_CSV: &str = "playlist.csv";
+
+async fn export_api(
+    fingerprint: Fingerprint,
+    req_headers: axum::http::HeaderMap,
+    axum::extract::Path((username, password, file)): axum::extract::Path<(String, String, String)>,
+    axum::extract::Query(api_req): axum::extract::Query<UserApiRequest>,
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+) -> impl IntoResponse + Send {
+    let auth_status = app_state.app_config.get_auth_error_status();
+    let (user, target) = try_option_forbidden!(
+        get_user_target_by_credentials(username.as_str(), password.as_str(), &api_req, &app_state, &fingerprint),
+        auth_status,
+        false,
+        format!("Could not find any user for export api {username}")
+    );
+
+    let (format, filter) = match file.as_str() {
+        EXPORT_FILE_JSON => {
+            let Some(output) = target.get_json_output() else {
+                debug!("Target has no json output {}", target.name);
+                return axum::http::StatusCode::NOT_FOUND.into_response();
+            };
+            (PlaylistExportFormat::Json, output.filter.as_ref())
+        }
+        EXPORT_FILE_CSV => {
+            let Some(output) = target.get_csv_output() else {
+                debug!("Target has no csv output {}", target.name);
+                return axum::http::StatusCode::NOT_FOUND.into_response();
+            };
+            (PlaylistExportFormat::Csv(output), output.filter.as_ref())
+        }
+        _ => return axum::http::StatusCode::NOT_FOUND.into_response(),
+    };
+    if user_playlist_access_denied(&app_state, &fingerprint, &user, &target, &req_headers, &api_req.device_id).await {
+        return axum::http::StatusCode::FORBIDDEN.into_response();
+    }
+
+    let items = if target.has_output(TargetType::M3u) {
+        export_collect_m3u_items(&app_state, &target, &user, filter).await
+    } else if target.has_output(TargetType::Xtream) {
+        Some(export_collect_xtream_items(&app_state, &target, &user, filter).await)
+    } else {
+        debug!("Export requires an m3u or xtream output for target {}", target.name);
+        return axum::http::StatusCode::BAD_REQUEST.into_response();
+    };
+    let Some(items) = items else {
+        return axum::http::StatusCode::NO_CONTENT.into_response();
+    };
+
+    let mut content = format.render_start();
+    let mut first = true;
+    for pli in &items {
+        if let Some(item) = format.render_item(&pli.header, first) {
+            content.push_str(&item);
+            first = false;
+        }
+    }
+    content.push_str(&format.render_end(first));
+
+    try_unwrap_body!(axum::response::Response::builder()
+        .status(axum::http::StatusCode::OK)
+        .header(axum::http::header::CONTENT_TYPE, format.content_type())
+        .header("Content-Disposition", format!("attachment; filename=\"{file}\""))
+        .body(axum::body::Body::from(content)))
+}
+
+/// Same items and rewritten urls as the m3u api delivers for this user.
+async fn export_collect_m3u_items(
+    app_state: &AppState,
+    target: &ConfigTarget,
+    user: &ProxyUserCredentials,
+    filter: Option<&Filter>,
+) -> Option<Vec<PlaylistItem>> {
+    let channel_health = app_state.channel_health.output_filter(target);
+    let mut iter = match M3uPlaylistIterator::new(&app_state.app_config, target, user, channel_health).await {
+        Ok(iter) => iter,
+        Err(err) => {
+            error!("{}", sanitize_sensitive_info(err.to_string().as_str()));
+            return None;
+        }
+    };
+    let mut items = vec![];
+    while let Some((m3u_item, _has_next)) = iter.next().await {
+        let pli = export_m3u_item(&m3u_item);
+        if export_filter_item(filter, &pli) {
+            items.push(pli);
+        }
+    }
+    Some(items)
+}
+
+/// Live and vod items of the xtream api with the stream urls of this user, series are listed without episodes there.
+async fn export_collect_xtream_items(
+    app_state: &AppState,
+    target: &ConfigTarget,
+    user: &ProxyUserCredentials,
+    filter: Option<&Filter>,
+) -> Vec<PlaylistItem> {
+    let app_config = &app_state.app_config;
+    let base_url = app_config.get_user_server_info(user).get_base_url();
+    let mut items = vec![];
+    for cluster in [XtreamCluster::Live, XtreamCluster::Video] {
+        let channel_health =
+            if cluster == XtreamCluster::Live { app_state.channel_health.output_filter(target) } else { None };
+        let Ok(mut iter) = XtreamPlaylistIterator::new(cluster, app_config, target, None, user, channel_health).await
+        else {
+            continue;
+        };
+        while let Some((item, _has_next)) = iter.next().await {
+            let pli = PlaylistItem::from(&item);
+            if export_filter_item(filter, &pli) {
+                items.push(export_xtream_item(app_config, target, user, &base_url, pli));
+            }
+        }
+    }
+    items
+}
+
+pub fn export_api_register() -> axum::Router<Arc<AppState>> {
+    axum::Router::new().route("/export/{username}/{password}/{file}", axum::routing::get(export_api))
+}
@@ -268,16 +268,33 @@ async fn lineup_status(
                 }
             } else if target.has_output(TargetType::Xtream) {
                 let credentials = Arc::new(user);
-                let live =
-                    match XtreamPlaylistIterator::new(XtreamCluster::Live, &cfg, &target, None, &credentials).await {
-                        Ok(stream) => stream.count().await,
-                        Err(_) => 0,
-                    };
-                let vod =
-                    match XtreamPlaylistIterator::new(XtreamCluster::Video, &cfg, &target, None, &credentials).await {
-                        Ok(stream) => stream.count().await,
-                        Err(_) => 0,
-                    };
+                let channel_health = app_state.app_state.channel_health.output_filter(&target);
+                let live = match XtreamPlaylistIterator::new(
+                    XtreamCluster::Live,
+                    &cfg,
+                    &target,
+                    None,
+                    &credentials,
+                    channel_health,
+                )
+                .await
+                {
+                    Ok(stream) => stream.count().await,
+                    Err(_) => 0,
+                };
+                let vod = match XtreamPlaylistIterator::new(
+                    XtreamCluster::Video,
+                    &cfg,
+                    &target,
+                    None,
+                    &credentials,
+                    None,
+                )
+                .await
+                {
+                    Ok(stream) => stream.count().await,
+                    Err(_) => 0,
+                };
                 live + vod
             } else {
                 0
@@ -333,8 +350,9 @@ async fn lineup(
     let use_all = use_output.is_none();
     let use_m3u = use_output.as_ref() == Some(&TargetType::M3u);
     let use_xtream = use_output.as_ref() == Some(&TargetType::Xtream);
+    let channel_health = app_state.app_state.channel_health.output_filter(target);
     if (use_all || use_m3u) && target.has_output(TargetType::M3u) {
-        let iterator = M3uPlaylistIterator::new(cfg, target, credentials).await.ok();
+        let iterator = M3uPlaylistIterator::new(cfg, target, credentials, channel_health).await.ok();
         let stream = m3u_item_to_lineup_stream(iterator);
         let body_stream = stream::once(async { Ok(Bytes::from("[")) })
             .chain(stream)
@@ -362,8 +380,10 @@ async fn lineup(
             Some(base_url)
         };
 
-        let live_channels = XtreamPlaylistIterator::new(XtreamCluster::Live, cfg, target, None, credentials).await.ok();
-        let vod_channels = XtreamPlaylistIterator::new(XtreamCluster::Video, cfg, target, None, credentials).await.ok();
+        let live_channels =
+            XtreamPlaylistIterator::new(XtreamCluster::Live, cfg, target, None, credentials, channel_health).await.ok();
+        let vod_channels =
+            XtreamPlaylistIterator::new(XtreamCluster::Video, cfg, target, None, credentials, None).await.ok();
         let live_stream = xtream_item_to_lineup_stream(
             Arc::clone(cfg),
             XtreamCluster::Live,
@@ -3,14 +3,14 @@ use crate::{
         api_utils::{
             connection_priority_for_kind, create_session_fingerprint, force_provider_stream_response, get_headers_from_request,
             get_hls_session_ttl_secs,
-            admission_failure_response, get_stream_alternative_url, is_seek_request, local_stream_response, try_option_bad_request,
+            admission_failure_response, country_access_denied, get_stream_alternative_url, is_seek_request, local_stream_response, try_option_bad_request,
             try_unwrap_body, HeaderFilter,
         },
         model::{
             AppState, CustomVideoStreamType, ProviderAllocation, UserSession,
         },
     },
-    auth::Fingerprint,
+    auth::{verify_access_token, Fingerprint},
     model::{ConfigInput, ConfigInputFlags, ConfigTarget, InputSource, ProxyUserCredentials},
     processing::parser::hls::{get_hls_session_token_and_url_from_token, rewrite_hls, RewriteHlsProps},
     repository::{m3u_get_item_for_stream_id, xtream_get_item_for_stream_id},
@@ -20,10 +20,13 @@ use axum::{http::HeaderMap, response::IntoResponse};
 use log::{debug, error};
 use serde::Deserialize;
 use shared::{
-    model::{PlaylistItemType, StreamChannel, TargetType, UserConnectionPermission, XtreamCluster},
+    concat_string,
+    model::{
+        HlsPackagingConfigDto, PlaylistItemType, StreamChannel, TargetType, UserConnectionPermission, XtreamCluster,
+    },
     utils::{is_hls_url, replace_url_extension, sanitize_sensitive_info, Internable, CUSTOM_VIDEO_PREFIX, HLS_EXT},
 };
-use std::sync::Arc;
+use std::{future::Future, sync::Arc, time::Duration};
 use url::Url;
 
 const PLAYLIST_TEMPLATE: &str = r"#EXTM3U
@@ -45,6 +48,21 @@ struct HlsApiPathParams {
     token: String,
 }
 
+#[derive(Debug, Deserialize)]
+struct HlsSegmentPathParams {
+    username: String,
+    password: String,
+    segmenter_id: u32,
+    segment: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct HlsTokenSegmentPathParams {
+    token: String,
+    segmenter_id: u32,
+    segment: String,
+}
+
 fn hls_response(hls_content: String) -> impl IntoResponse + Send {
     try_unwrap_body!(axum::response::Response::builder()
         .status(axum::http::StatusCode::OK)
@@ -52,6 +70,60 @@ fn hls_response(hls_content: String) -> impl IntoResponse + Send {
         .body(hls_content))
 }
 
+/// Returns the HLS packaging options of the user, the user setting overrides the target option.
+pub(in crate::api) fn get_hls_packaging(
+    user: &ProxyUserCredentials,
+    target: &ConfigTarget,
+) -> Option<HlsPackagingConfigDto> {
+    let target_packaging = target.options.as_ref().and_then(|options| options.hls_packaging.as_ref());
+    match user.hls_packaging {
+        Some(false) => None,
+        Some(true) => Some(target_packaging.cloned().unwrap_or_default()),
+        None => target_packaging.cloned(),
+    }
+}
+
+/// Serves the rolling playlist of a packaged MPEG-TS live channel.
+/// The first HLS viewer of the channel opens the provider stream with `open_stream`, later viewers share it.
+/// Web UI requests pass their `access_token`, which authorizes the segment urls instead of the user credentials.
+pub(in crate::api) async fn hls_packaging_response<F>(
+    app_state: &Arc<AppState>,
+    user: &ProxyUserCredentials,
+    access_token: Option<&str>,
+    stream_url: &str,
+    packaging: &HlsPackagingConfigDto,
+    open_stream: F,
+) -> axum::response::Response
+where
+    F: Future<Output = axum::response::Response>,
+{
+    let segmenter = {
+        let _start_lock =
+            app_state.app_config.file_locks.write_lock_str(&concat_string!("hls_packaging:", stream_url)).await;
+        if let Some(segmenter) = app_state.hls_segmenters.get(stream_url).await {
+            segmenter
+        } else {
+            let response = open_stream.await;
+            if !response.status().is_success() {
+                return response;
+            }
+            let source = response.into_body().into_data_stream();
+            app_state.hls_segmenters.start(stream_url, packaging, source).await
+        }
+    };
+
+    let base_url = app_state.app_config.get_user_server_info(user).get_base_url();
+    let segment_url = match access_token {
+        Some(token) => format!("{base_url}/hls-token-segment/{token}/{}", segmenter.id()),
+        None => format!("{base_url}/hls-segment/{}/{}/{}", user.username, user.password, segmenter.id()),
+    };
+    let first_segment_wait = Duration::from_secs(u64::from(packaging.segment_duration_secs) * 3 + 5);
+    match segmenter.playlist(&segment_url, first_segment_wait).await {
+        Some(playlist) => hls_response(playlist).into_response(),
+        None => axum::http::StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
 fn normalize_xtream_live_hls_url(hls_url: &str, input: &ConfigInput) -> String {
     if !input.input_type.is_xtream() || !input.has_flag(ConfigInputFlags::XtreamLiveStreamUsePrefix) {
         return hls_url.to_string();
@@ -340,6 +412,7 @@ async fn resolve_stream_channel(
             shared_joined_existing: None,
             shared_stream_id: None,
             technical: None,
+            alternate_input: None,
         },
     };
 
@@ -367,7 +440,18 @@ async fn hls_api_stream(
         format!("Can't find input {} for target {target_name}, stream_id {virtual_id}, hls", params.input_id)
     );
 
-    if user.permission_denied(&app_state) {
+    let denied_reason = if user.permission_denied(&app_state) {
+        Some(crate::repository::ConnectFailureReason::UserAccountExpired)
+    } else if user.quota_exhausted(&app_state).await {
+        Some(crate::repository::ConnectFailureReason::UserQuotaExhausted)
+    } else if user.outside_access_schedule(&app_state) {
+        Some(crate::repository::ConnectFailureReason::OutsideAccessSchedule)
+    } else if country_access_denied(&app_state, &fingerprint, &target, &user).await {
+        Some(crate::repository::ConnectFailureReason::CountryDenied)
+    } else {
+        None
+    };
+    if let Some(denied_reason) = denied_reason {
         let denied_channel = resolve_stream_channel(
             &app_state,
             &target,
@@ -383,7 +467,7 @@ async fn hls_api_stream(
             denied_channel,
             input.name.as_ref(),
             &req_headers,
-            crate::repository::ConnectFailureReason::UserAccountExpired,
+            denied_reason,
         );
     }
 
@@ -553,9 +637,52 @@ async fn hls_api_stream(
     }
 }
 
+async fn hls_api_segment(
+    axum::extract::Path(params): axum::extract::Path<HlsSegmentPathParams>,
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+) -> impl axum::response::IntoResponse + Send {
+    let (user, _target) = try_option_bad_request!(
+        app_state.app_config.get_target_for_user(&params.username, &params.password),
+        false,
+        format!("Could not find any user for hls segment {}", params.username)
+    );
+    if user.permission_denied(&app_state) {
+        return axum::http::StatusCode::FORBIDDEN.into_response();
+    }
+    hls_segment_response(&app_state, params.segmenter_id, &params.segment).await
+}
+
+async fn hls_api_token_segment(
+    axum::extract::Path(params): axum::extract::Path<HlsTokenSegmentPathParams>,
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+) -> impl axum::response::IntoResponse + Send {
+    if !verify_access_token(&params.token, &app_state.app_config.access_token_secret) {
+        return axum::http::StatusCode::FORBIDDEN.into_response();
+    }
+    hls_segment_response(&app_state, params.segmenter_id, &params.segment).await
+}
+
+async fn hls_segment_response(app_state: &AppState, segmenter_id: u32, segment: &str) -> axum::response::Response {
+    let Some(sequence) = segment.strip_suffix(".ts").and_then(|sequence| sequence.parse::<u64>().ok()) else {
+        return axum::http::StatusCode::BAD_REQUEST.into_response();
+    };
+    let Some(segmenter) = app_state.hls_segmenters.get_by_id(segmenter_id).await else {
+        return axum::http::StatusCode::NOT_FOUND.into_response();
+    };
+    match segmenter.segment(sequence).await {
+        Some(segment) => try_unwrap_body!(axum::response::Response::builder()
+            .status(axum::http::StatusCode::OK)
+            .header(axum::http::header::CONTENT_TYPE, "video/mp2t")
+            .body(axum::body::Body::from(segment))),
+        None => axum::http::StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
 pub fn hls_api_register() -> axum::Router<Arc<AppState>> {
     axum::Router::new()
         .route("/hls/{username}/{password}/{input_id}/{stream_id}/{token}", axum::routing::get(hls_api_stream))
+        .route("/hls-segment/{username}/{password}/{segmenter_id}/{segment}", axum::routing::get(hls_api_segment))
+        .route("/hls-token-segment/{token}/{segmenter_id}/{segment}", axum::routing::get(hls_api_token_segment))
     //cfg.service(web::resource("/hls/{token}/{stream}").route(web::get().to(xtream_player_api_hls_stream)));
     //cfg.service(web::resource("/play/{token}/{type}").route(web::get().to(xtream_player_api_play_stream)));
 }
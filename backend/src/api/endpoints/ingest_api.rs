@@ -0,0 +1,76 @@
+use crate::api::model::{AppState, SharedStreamManager};
+use crate::auth::AuthBearer;
+use crate::model::ingest_stream_url;
+use crate::utils::debug_if_enabled;
+use axum::extract::DefaultBodyLimit;
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::IntoResponse;
+use futures::StreamExt;
+use log::{info, warn};
+use serde::Deserialize;
+use std::sync::Arc;
+use tokio::sync::oneshot;
+
+const DEFAULT_INGEST_CONTENT_TYPE: &str = "video/mp2t";
+
+#[derive(Debug, Deserialize)]
+struct IngestQuery {
+    token: Option<String>,
+}
+
+/// Receives the continuous MPEG-TS stream of a local encoder and serves it to the viewers of the channel.
+/// The request stays open as long as the encoder sends.
+async fn ingest_stream(
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+    axum::extract::Path(key): axum::extract::Path<String>,
+    axum::extract::Query(query): axum::extract::Query<IngestQuery>,
+    headers: HeaderMap,
+    body: axum::body::Body,
+) -> impl IntoResponse + Send {
+    let token = AuthBearer::from_headers(&headers).ok().map(|AuthBearer(token)| token).or(query.token);
+    let authorized = {
+        let config = app_state.app_config.config.load();
+        let Some(ingest) = config.ingest.as_ref().filter(|ingest| ingest.enabled) else {
+            return StatusCode::NOT_FOUND.into_response();
+        };
+        let Some(channel) = ingest.get_channel(&key) else {
+            return StatusCode::NOT_FOUND.into_response();
+        };
+        token.as_deref().is_some_and(|token| ingest.is_authorized(channel, token))
+    };
+    if !authorized {
+        warn!("Rejected unauthorized ingest for channel {key}");
+        return StatusCode::UNAUTHORIZED.into_response();
+    }
+
+    let content_type = headers
+        .get(axum::http::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| value.starts_with("video/"))
+        .unwrap_or(DEFAULT_INGEST_CONTENT_TYPE);
+    let response_headers = vec![(axum::http::header::CONTENT_TYPE.to_string(), content_type.to_string())];
+
+    // The sender is dropped together with the body stream when the shared stream closes.
+    let (closed_tx, closed_rx) = oneshot::channel::<()>();
+    let body_stream = body.into_data_stream().map(move |chunk| {
+        let _ = &closed_tx;
+        chunk
+    });
+
+    let stream_url = ingest_stream_url(&key);
+    if !SharedStreamManager::register_ingest_stream(&app_state, &stream_url, body_stream, response_headers).await {
+        warn!("Rejected ingest for channel {key}, the channel already receives a stream");
+        return StatusCode::CONFLICT.into_response();
+    }
+
+    info!("Ingest started for channel {key}");
+    let _ = closed_rx.await;
+    debug_if_enabled!("Ingest ended for channel {key}");
+    StatusCode::NO_CONTENT.into_response()
+}
+
+pub fn ingest_api_register() -> axum::Router<Arc<AppState>> {
+    axum::Router::new()
+        .route("/ingest/{key}", axum::routing::put(ingest_stream).post(ingest_stream))
+        .layer(DefaultBodyLimit::disable())
+}
@@ -1,12 +1,19 @@
 use crate::{api::{
+    api_utils::{get_user_target_by_credentials, try_option_forbidden, try_unwrap_body, user_playlist_access_denied},
     library_scan::{spawn_library_scan, LibraryScanTaskOptions},
-    model::{AppState, EventMessage},
-}, auth::permission_layer, library::{resolve_metadata_storage_path, LibraryProcessor, MetadataStorage}};
+    model::{AppState, EventMessage, UserApiRequest},
+}, auth::{permission_layer, Fingerprint}, library::{
+    resolve_metadata_storage_path, LibraryProcessor, MetadataStorage,
+}, repository::target_id_mapping_contains_uuid};
+use crate::utils::request::parse_range;
 use axum::response::IntoResponse;
-use log::{debug, warn};
+use log::{debug, error, warn};
 use serde_json::json;
-use shared::model::{permission::Permission, LibraryScanRequest, LibraryScanSummary, LibraryScanSummaryStatus, LibraryStatus};
+use shared::model::{permission::Permission, LibraryScanRequest, LibraryScanSummary, LibraryScanSummaryStatus, LibraryStatus, UUIDType};
+use std::io::SeekFrom;
 use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+use tokio_util::io::ReaderStream;
 
 // Triggers a library scan
 async fn scan_library(
@@ -163,6 +170,99 @@ async fn serve_thumbnail_hash(
     }
 }
 
+fn sidecar_content_type(extension: &str) -> String {
+    match extension {
+        "srt" => "application/x-subrip".to_string(),
+        "vtt" => "text/vtt".to_string(),
+        "ass" | "ssa" => "text/x-ssa".to_string(),
+        _ => mime_guess::from_ext(extension).first_or_octet_stream().to_string(),
+    }
+}
+
+// Serves subtitle and audio files found next to the library videos.
+async fn get_sidecar(
+    fingerprint: Fingerprint,
+    axum::extract::Path((username, password, entry_id, file)): axum::extract::Path<(String, String, String, String)>,
+    axum::extract::Query(api_req): axum::extract::Query<UserApiRequest>,
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+    headers: axum::http::HeaderMap,
+) -> axum::response::Response {
+    let auth_status = app_state.app_config.get_auth_error_status();
+    let (user, target) = try_option_forbidden!(
+        get_user_target_by_credentials(username.as_str(), password.as_str(), &api_req, &app_state, &fingerprint),
+        auth_status,
+        false,
+        format!("Could not find any user for library sidecar {username}")
+    );
+    if user_playlist_access_denied(&app_state, &fingerprint, &user, &target, &headers, &api_req.device_id).await {
+        return axum::http::StatusCode::FORBIDDEN.into_response();
+    }
+
+    let storage_path = {
+        let config_snapshot = app_state.app_config.config.load();
+        if !config_snapshot.library.as_ref().is_some_and(|l| l.enabled) {
+            return axum::http::StatusCode::NOT_FOUND.into_response();
+        }
+        resolve_metadata_storage_path(config_snapshot.metadata_update.as_ref(), &config_snapshot.storage_dir)
+    };
+    let storage = MetadataStorage::new(storage_path);
+    let Some(entry) = storage.load_by_uuid(&entry_id).await else {
+        return axum::http::StatusCode::NOT_FOUND.into_response();
+    };
+    // movies and series infos of the library keep the uuid of their entry in the target playlists
+    if !target_id_mapping_contains_uuid(&app_state.app_config, &target, UUIDType::from_valid_uuid(&entry.uuid)).await {
+        debug!("Library entry {entry_id} is not part of target {} of user {username}", target.name);
+        return axum::http::StatusCode::NOT_FOUND.into_response();
+    }
+    let id = file.split('.').next().unwrap_or_default();
+    let Some(sidecar) = entry.find_sidecar(id) else {
+        return axum::http::StatusCode::NOT_FOUND.into_response();
+    };
+
+    let Ok(mut sidecar_file) = tokio::fs::File::open(&sidecar.file_path).await else {
+        return axum::http::StatusCode::NOT_FOUND.into_response();
+    };
+    let Ok(file_size) = sidecar_file.metadata().await.map(|m| m.len()) else {
+        return axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    };
+
+    let range = headers
+        .get(axum::http::header::RANGE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(parse_range)
+        .filter(|_| file_size > 0);
+    let (status, start, end) = match range {
+        Some((start, end)) => {
+            let end = end.unwrap_or(file_size - 1).min(file_size - 1);
+            if start > end {
+                return axum::http::StatusCode::RANGE_NOT_SATISFIABLE.into_response();
+            }
+            (axum::http::StatusCode::PARTIAL_CONTENT, start, end)
+        }
+        None => (axum::http::StatusCode::OK, 0, file_size.saturating_sub(1)),
+    };
+    if start > 0 && sidecar_file.seek(SeekFrom::Start(start)).await.is_err() {
+        return axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    }
+    let content_length = if file_size == 0 { 0 } else { end - start + 1 };
+
+    let mut builder = axum::response::Response::builder()
+        .status(status)
+        .header(axum::http::header::CONTENT_TYPE, sidecar_content_type(&sidecar.extension))
+        .header(axum::http::header::CONTENT_LENGTH, content_length)
+        .header(axum::http::header::ACCEPT_RANGES, "bytes");
+    if status == axum::http::StatusCode::PARTIAL_CONTENT {
+        builder = builder.header(axum::http::header::CONTENT_RANGE, format!("bytes {start}-{end}/{file_size}"));
+    }
+    let body = axum::body::Body::from_stream(ReaderStream::new(sidecar_file.take(content_length)));
+    try_unwrap_body!(builder.body(body))
+}
+
+/// Registers the sidecar route, it is authenticated with the user credentials like the xtream api.
+pub fn library_sidecar_api_register() -> axum::Router<Arc<AppState>> {
+    axum::Router::new().route("/library/sidecar/{username}/{password}/{entry}/{file}", axum::routing::get(get_sidecar))
+}
+
 /// Registers Library API routes.
 pub fn library_api_register(
     router: axum::Router<Arc<AppState>>,
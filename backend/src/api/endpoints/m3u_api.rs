@@ -4,12 +4,12 @@ use crate::{
             create_catchup_session_key, create_session_fingerprint, force_provider_stream_response,
             get_session_reservation_ttl_secs, get_user_target, get_user_target_by_credentials, is_seek_request,
             is_stream_share_enabled, local_stream_response, redirect, redirect_response, resource_response,
-            admission_failure_response, separate_number_and_remainder, should_allow_exhausted_shared_reconnect, stream_response,
+            admission_failure_response, country_access_denied, separate_number_and_remainder, should_allow_exhausted_shared_reconnect, stream_response,
             try_option_bad_request, try_option_forbidden, try_result_bad_request, try_result_not_found,
-            try_unwrap_body, RedirectParams,
+            try_unwrap_body, user_device_denied, RedirectParams,
         },
         endpoints::{
-            hls_api::handle_hls_stream_request,
+            hls_api::{get_hls_packaging, handle_hls_stream_request, hls_packaging_response},
             xtream_api::{ApiStreamContext, ApiStreamRequest},
         },
         model::{AppState, UserApiRequestQueryOrBody, UserApiRequest},
@@ -28,17 +28,23 @@ use shared::{
 };
 use std::sync::Arc;
 
-async fn m3u_api(api_req: &UserApiRequest, app_state: &AppState) -> impl IntoResponse + Send {
+async fn m3u_api(api_req: &UserApiRequest, app_state: &AppState, fingerprint: &Fingerprint) -> impl IntoResponse + Send {
     api_req.log_sanitized("m3u_api");
     let auth_status = app_state.app_config.get_auth_error_status();
     let (user, target) = try_option_forbidden!(
-        get_user_target(api_req, app_state),
+        get_user_target(api_req, app_state, fingerprint),
         auth_status,
         false,
         format!("Could not find any user for m3u api {}", api_req.username)
     );
+    if user.outside_access_schedule(app_state)
+        || country_access_denied(app_state, fingerprint, &target, &user).await
+    {
+        return axum::http::StatusCode::FORBIDDEN.into_response();
+    }
 
-    match m3u_load_rewrite_playlist(&app_state.app_config, &target, &user).await {
+    let channel_health = app_state.channel_health.output_filter(&target);
+    match m3u_load_rewrite_playlist(&app_state.app_config, &target, &user, channel_health).await {
         Ok(m3u_iter) => {
             // Convert the stream into a stream of `Bytes`
             let content_stream = m3u_iter.map(|mut line| {
@@ -62,17 +68,19 @@ async fn m3u_api(api_req: &UserApiRequest, app_state: &AppState) -> impl IntoRes
 }
 
 async fn m3u_api_get(
+    fingerprint: Fingerprint,
     axum::extract::Query(api_req): axum::extract::Query<UserApiRequest>,
     axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
 ) -> impl IntoResponse + Send {
-    m3u_api(&api_req, &app_state).await
+    m3u_api(&api_req, &app_state, &fingerprint).await
 }
 
 async fn m3u_api_post(
+    fingerprint: Fingerprint,
     axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
     UserApiRequestQueryOrBody(api_req): UserApiRequestQueryOrBody,
 ) -> impl IntoResponse + Send {
-    m3u_api(&api_req, &app_state).await.into_response()
+    m3u_api(&api_req, &app_state, &fingerprint).await.into_response()
 }
 
 #[allow(clippy::too_many_lines)]
@@ -86,7 +94,7 @@ async fn m3u_api_stream(
 ) -> impl IntoResponse + Send {
     let auth_status = app_state.app_config.get_auth_error_status();
     let (user, target) = try_option_forbidden!(
-        get_user_target_by_credentials(stream_req.username, stream_req.password, api_req, app_state),
+        get_user_target_by_credentials(stream_req.username, stream_req.password, api_req, app_state, fingerprint),
         auth_status,
         false,
         format!("Could not find any user for m3u stream {}", stream_req.username)
@@ -131,6 +139,66 @@ async fn m3u_api_stream(
         );
     }
 
+    if user.quota_exhausted(app_state).await {
+        return admission_failure_response(
+            app_state,
+            fingerprint,
+            &user,
+            pli.to_stream_channel(target.id),
+            pli.input_name.as_ref(),
+            req_headers,
+            crate::repository::ConnectFailureReason::UserQuotaExhausted,
+        );
+    }
+
+    if user.outside_access_schedule(app_state) {
+        return admission_failure_response(
+            app_state,
+            fingerprint,
+            &user,
+            pli.to_stream_channel(target.id),
+            pli.input_name.as_ref(),
+            req_headers,
+            crate::repository::ConnectFailureReason::OutsideAccessSchedule,
+        );
+    }
+
+    if country_access_denied(app_state, fingerprint, &target, &user).await {
+        return admission_failure_response(
+            app_state,
+            fingerprint,
+            &user,
+            pli.to_stream_channel(target.id),
+            pli.input_name.as_ref(),
+            req_headers,
+            crate::repository::ConnectFailureReason::CountryDenied,
+        );
+    }
+
+    if user.parental_restricted(app_state, &pli) {
+        return admission_failure_response(
+            app_state,
+            fingerprint,
+            &user,
+            pli.to_stream_channel(target.id),
+            pli.input_name.as_ref(),
+            req_headers,
+            crate::repository::ConnectFailureReason::ParentalRestricted,
+        );
+    }
+
+    if let Some(reason) = user_device_denied(app_state, fingerprint, &user, req_headers, &api_req.device_id).await {
+        return admission_failure_response(
+            app_state,
+            fingerprint,
+            &user,
+            pli.to_stream_channel(target.id),
+            pli.input_name.as_ref(),
+            req_headers,
+            reason,
+        );
+    }
+
     if pli.item_type.is_local() {
         let admission = app_state
             .get_connection_admission(&user.username, user.max_connections, user.soft_connections)
@@ -272,6 +340,30 @@ async fn m3u_api_stream(
 
     let extension = stream_ext.unwrap_or_else(|| extract_extension_from_url(&pli.url).unwrap_or_default());
 
+    if pli.item_type == PlaylistItemType::Live && extension == HLS_EXT {
+        if let Some(packaging) = get_hls_packaging(&user, &target) {
+            let open_stream = Box::pin(async {
+                stream_response(
+                    fingerprint,
+                    app_state,
+                    &session_key,
+                    pli.to_stream_channel(target.id),
+                    &session_url,
+                    req_headers,
+                    &input,
+                    &target,
+                    &user,
+                    connection_permission,
+                    connection_kind,
+                    allow_exhausted_shared_reconnect,
+                )
+                .await
+                .into_response()
+            });
+            return hls_packaging_response(app_state, &user, None, &session_url, &packaging, open_stream).await;
+        }
+    }
+
     let is_hls_request = pli.item_type == PlaylistItemType::LiveHls
         || pli.item_type == PlaylistItemType::LiveDash
         || extension == HLS_EXT;
@@ -312,6 +404,7 @@ async fn m3u_api_stream(
 }
 
 async fn m3u_api_resource(
+    fingerprint: Fingerprint,
     req_headers: axum::http::HeaderMap,
     axum::extract::Query(api_req): axum::extract::Query<UserApiRequest>,
     axum::extract::Path((username, password, stream_id, resource)): axum::extract::Path<(
@@ -327,12 +420,12 @@ async fn m3u_api_resource(
     };
     let auth_status = app_state.app_config.get_auth_error_status();
     let (user, target) = try_option_forbidden!(
-        get_user_target_by_credentials(&username, &password, &api_req, &app_state),
+        get_user_target_by_credentials(&username, &password, &api_req, &app_state, &fingerprint),
         auth_status,
         false,
         format!("Could not find any user for m3u resource {username}")
     );
-    if user.permission_denied(&app_state) {
+    if user.permission_denied(&app_state) || user.outside_access_schedule(&app_state) {
         return axum::http::StatusCode::FORBIDDEN.into_response();
     }
 
@@ -1,12 +1,18 @@
 mod api_playlist_utils;
 pub(in crate::api) mod custom_video_stream_api;
+pub(in crate::api) mod dlna_api;
 pub(in crate::api) mod download_api;
+pub(in crate::api) mod enigma2_api;
+pub(in crate::api) mod export_api;
 mod extract_accept_header;
 pub(in crate::api) mod hdhomerun_api;
 pub(in crate::api) mod hls_api;
-mod library_api;
+pub(in crate::api) mod ingest_api;
+pub(in crate::api) mod library_api;
 pub(in crate::api) mod m3u_api;
+pub(in crate::api) mod multicast_api;
 mod rbac_api;
+mod reseller_api;
 mod stream_history_api;
 mod user_api;
 pub(in crate::api) mod v1_api;
@@ -0,0 +1,43 @@
+use crate::api::api_utils::{get_user_target_by_credentials, try_option_forbidden, try_unwrap_body};
+use crate::api::model::{AppState, UserApiRequest};
+use crate::auth::Fingerprint;
+use crate::repository::{multicast_render_m3u, multicast_resolve_channels};
+use axum::response::IntoResponse;
+use log::{debug, error};
+use std::sync::Arc;
+
+const MULTICAST_FILE_M3U: &str = "playlist.m3u";
+
+async fn multicast_api(
+    fingerprint: Fingerprint,
+    axum::extract::Path((username, password, file)): axum::extract::Path<(String, String, String)>,
+    axum::extract::Query(api_req): axum::extract::Query<UserApiRequest>,
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+) -> impl IntoResponse + Send {
+    let auth_status = app_state.app_config.get_auth_error_status();
+    let (_user, target) = try_option_forbidden!(
+        get_user_target_by_credentials(username.as_str(), password.as_str(), &api_req, &app_state, &fingerprint),
+        auth_status,
+        false,
+        format!("Could not find any user for multicast api {username}")
+    );
+
+    if file != MULTICAST_FILE_M3U {
+        return axum::http::StatusCode::NOT_FOUND.into_response();
+    }
+    let Some(output) = target.get_multicast_output() else {
+        debug!("Target has no multicast output {}", target.name);
+        return axum::http::StatusCode::NOT_FOUND.into_response();
+    };
+
+    let channels = multicast_resolve_channels(&app_state.app_config, &target, output).await;
+    try_unwrap_body!(axum::response::Response::builder()
+        .status(axum::http::StatusCode::OK)
+        .header(axum::http::header::CONTENT_TYPE, mime::TEXT_PLAIN_UTF_8.to_string())
+        .header("Content-Disposition", format!("attachment; filename=\"{file}\""))
+        .body(axum::body::Body::from(multicast_render_m3u(&channels))))
+}
+
+pub fn multicast_api_register() -> axum::Router<Arc<AppState>> {
+    axum::Router::new().route("/multicast/{username}/{password}/{file}", axum::routing::get(multicast_api))
+}
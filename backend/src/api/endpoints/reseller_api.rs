@@ -0,0 +1,314 @@
+use crate::{
+    api::{
+        api_utils::get_username_from_auth_header,
+        endpoints::{
+            stream_history_api::users_stream_history, v1_api_config::intern_save_config_api_proxy,
+            v1_api_user::save_api_proxy_users,
+        },
+        model::AppState,
+    },
+    auth::{validator_reseller, AuthBearer},
+    model::{ApiProxyConfig, ProxyUserCredentials, Reseller, TargetUser},
+};
+use axum::response::IntoResponse;
+use chrono::{DateTime, Months, Utc};
+use log::{error, info};
+use serde_json::json;
+use shared::{
+    model::{
+        ApiProxyConfigDto, ProxyUserCredentialsDto, ProxyUserStatus, ResellerAccountDto, ResellerStreamDto,
+        ResellerStreamHistoryDto, ResellerUserCreateDto, ResellerUserDto, ResellerUserExtendDto, UserStreamDto,
+    },
+    utils::current_time_secs,
+};
+use std::{collections::HashSet, path::Path, sync::Arc};
+
+/// Days of stream history shown to a reseller when not requested otherwise.
+const RESELLER_HISTORY_DAYS: u64 = 7;
+const RESELLER_HISTORY_MAX_DAYS: u64 = 31;
+
+fn bad_request(message: &str) -> axum::response::Response {
+    (axum::http::StatusCode::BAD_REQUEST, axum::Json(json!({"error": message}))).into_response()
+}
+
+fn load_api_proxy(app_state: &AppState) -> ApiProxyConfig {
+    app_state
+        .app_config
+        .api_proxy
+        .load()
+        .as_ref()
+        .map_or_else(ApiProxyConfig::default, |api_proxy| (**api_proxy).clone())
+}
+
+fn owned_users<'a>(
+    api_proxy: &'a ApiProxyConfig,
+    reseller: &'a Reseller,
+) -> impl Iterator<Item = (&'a str, &'a ProxyUserCredentials)> + 'a {
+    api_proxy.user.iter().flat_map(move |target_user| {
+        target_user
+            .credentials
+            .iter()
+            .filter(|user| user.reseller.as_deref() == Some(reseller.name.as_str()))
+            .map(|user| (target_user.target.as_str(), user))
+    })
+}
+
+fn owned_usernames(app_state: &AppState, reseller_name: &str) -> HashSet<String> {
+    let api_proxy = load_api_proxy(app_state);
+    api_proxy.get_reseller(reseller_name).map_or_else(HashSet::new, |reseller| {
+        owned_users(&api_proxy, reseller).map(|(_, user)| user.username.clone()).collect()
+    })
+}
+
+/// Adds the months to the expiration date, or to now when the user is already expired.
+fn extend_exp_date(exp_date: Option<i64>, months: u32) -> Option<i64> {
+    let now = Utc::now();
+    let start = exp_date.and_then(|ts| DateTime::from_timestamp(ts, 0)).filter(|date| *date > now).unwrap_or(now);
+    start.checked_add_months(Months::new(months)).map(|date| date.timestamp())
+}
+
+/// Stores the api-proxy.yml without the users, which are kept in the user db.
+async fn save_api_proxy_without_users(app_state: &AppState, api_proxy: &ApiProxyConfig) -> Result<(), String> {
+    let config = app_state.app_config.config.load();
+    let backup_dir = config.get_backup_dir();
+    let paths = app_state.app_config.paths.load();
+    let mut api_proxy_dto = ApiProxyConfigDto::from(api_proxy);
+    api_proxy_dto.user.clear();
+    match intern_save_config_api_proxy(backup_dir.as_ref(), &api_proxy_dto, paths.api_proxy_file_path.as_str()).await {
+        Some(err) => Err(err.to_string()),
+        None => Ok(()),
+    }
+}
+
+/// Stores the changed users together with the consumed credits.
+/// Without the user db both are written to the api-proxy.yml at once, with the user db the credits
+/// are restored when the users could not be stored.
+async fn save_reseller_changes(app_state: &AppState, api_proxy: ApiProxyConfig) -> Result<(), String> {
+    if !api_proxy.use_user_db {
+        return save_api_proxy_users(app_state, api_proxy).await;
+    }
+    let previous = load_api_proxy(app_state);
+    save_api_proxy_without_users(app_state, &api_proxy).await?;
+    if let Err(err) = save_api_proxy_users(app_state, api_proxy).await {
+        if let Err(restore_err) = save_api_proxy_without_users(app_state, &previous).await {
+            error!("Failed to restore reseller credits: {restore_err}");
+        }
+        return Err(err);
+    }
+    Ok(())
+}
+
+async fn reseller_account(
+    AuthBearer(token): AuthBearer,
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+) -> impl axum::response::IntoResponse + Send {
+    let Some(name) = get_username_from_auth_header(&token, &app_state) else {
+        return axum::http::StatusCode::FORBIDDEN.into_response();
+    };
+    let api_proxy = load_api_proxy(&app_state);
+    let Some(reseller) = api_proxy.get_reseller(&name) else {
+        return axum::http::StatusCode::FORBIDDEN.into_response();
+    };
+    let mut users = Vec::new();
+    for (target, user) in owned_users(&api_proxy, reseller) {
+        users.push(ResellerUserDto {
+            username: user.username.clone(),
+            password: user.password.clone(),
+            target: target.to_string(),
+            status: user.status,
+            created_at: user.created_at,
+            exp_date: user.exp_date,
+            max_connections: user.max_connections,
+            active_connections: app_state.active_users.user_connections(&user.username).await,
+            comment: user.comment.clone(),
+        });
+    }
+    axum::Json(ResellerAccountDto {
+        name: reseller.name.clone(),
+        credits: reseller.credits,
+        max_connections: reseller.max_connections,
+        targets: reseller.targets.clone(),
+        users,
+    })
+    .into_response()
+}
+
+async fn create_reseller_user(
+    AuthBearer(token): AuthBearer,
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+    axum::extract::Json(mut request): axum::extract::Json<ResellerUserCreateDto>,
+) -> impl axum::response::IntoResponse + Send {
+    let Some(name) = get_username_from_auth_header(&token, &app_state) else {
+        return axum::http::StatusCode::FORBIDDEN.into_response();
+    };
+    request.prepare();
+    if let Err(err) = request.validate() {
+        return bad_request(&err.to_string());
+    }
+
+    let api_proxy_file_path = app_state.app_config.paths.load().api_proxy_file_path.clone();
+    let _lock = app_state.app_config.file_locks.write_lock(Path::new(&api_proxy_file_path)).await;
+
+    let mut api_proxy = load_api_proxy(&app_state);
+    let Some(reseller_idx) = api_proxy.resellers.iter().position(|r| r.name.eq_ignore_ascii_case(&name)) else {
+        return axum::http::StatusCode::FORBIDDEN.into_response();
+    };
+    let reseller = &api_proxy.resellers[reseller_idx];
+    if !reseller.has_target(&request.target) {
+        return bad_request(&format!("Target {} not allowed", request.target));
+    }
+    if request.max_connections > reseller.max_connections {
+        return bad_request(&format!("At most {} connections allowed", reseller.max_connections));
+    }
+    if reseller.credits < request.months {
+        return bad_request("Not enough credits");
+    }
+    if api_proxy.user.iter().flat_map(|target_user| &target_user.credentials).any(|u| u.username == request.username) {
+        return bad_request(&format!("Duplicate username {}", request.username));
+    }
+
+    let credential = ProxyUserCredentialsDto {
+        username: request.username.clone(),
+        password: request.password.clone(),
+        created_at: i64::try_from(current_time_secs()).ok(),
+        exp_date: extend_exp_date(None, request.months),
+        max_connections: request.max_connections,
+        status: Some(ProxyUserStatus::Active),
+        ui_enabled: true,
+        comment: request.comment.clone(),
+        reseller: Some(reseller.name.clone()),
+        ..ProxyUserCredentialsDto::default()
+    };
+    let reseller_name = reseller.name.clone();
+    api_proxy.resellers[reseller_idx].credits -= request.months;
+    let user = ProxyUserCredentials::from(&credential);
+    if let Some(target_user) = api_proxy.user.iter_mut().find(|t| t.target == request.target) {
+        target_user.credentials.push(user);
+    } else {
+        api_proxy.user.push(TargetUser { target: request.target.clone(), credentials: vec![user] });
+    }
+
+    if let Err(err) = save_reseller_changes(&app_state, api_proxy).await {
+        return (axum::http::StatusCode::INTERNAL_SERVER_ERROR, axum::Json(json!({"error": err}))).into_response();
+    }
+    info!("Reseller {reseller_name} created user {} for {} months", request.username, request.months);
+    axum::http::StatusCode::OK.into_response()
+}
+
+async fn extend_reseller_user(
+    AuthBearer(token): AuthBearer,
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+    axum::extract::Path(username): axum::extract::Path<String>,
+    axum::extract::Json(request): axum::extract::Json<ResellerUserExtendDto>,
+) -> impl axum::response::IntoResponse + Send {
+    let Some(name) = get_username_from_auth_header(&token, &app_state) else {
+        return axum::http::StatusCode::FORBIDDEN.into_response();
+    };
+    if let Err(err) = request.validate() {
+        return bad_request(&err.to_string());
+    }
+
+    let api_proxy_file_path = app_state.app_config.paths.load().api_proxy_file_path.clone();
+    let _lock = app_state.app_config.file_locks.write_lock(Path::new(&api_proxy_file_path)).await;
+
+    let mut api_proxy = load_api_proxy(&app_state);
+    let Some(reseller_idx) = api_proxy.resellers.iter().position(|r| r.name.eq_ignore_ascii_case(&name)) else {
+        return axum::http::StatusCode::FORBIDDEN.into_response();
+    };
+    let reseller_name = api_proxy.resellers[reseller_idx].name.clone();
+    if api_proxy.resellers[reseller_idx].credits < request.months {
+        return bad_request("Not enough credits");
+    }
+    // users of other resellers are reported as missing
+    let Some(user) = api_proxy
+        .user
+        .iter_mut()
+        .flat_map(|target_user| target_user.credentials.iter_mut())
+        .find(|user| user.username == username && user.reseller.as_deref() == Some(reseller_name.as_str()))
+    else {
+        return axum::http::StatusCode::NOT_FOUND.into_response();
+    };
+    user.exp_date = extend_exp_date(user.exp_date, request.months);
+    if user.status == Some(ProxyUserStatus::Expired) {
+        user.status = Some(ProxyUserStatus::Active);
+    }
+    api_proxy.resellers[reseller_idx].credits -= request.months;
+
+    if let Err(err) = save_reseller_changes(&app_state, api_proxy).await {
+        return (axum::http::StatusCode::INTERNAL_SERVER_ERROR, axum::Json(json!({"error": err}))).into_response();
+    }
+    info!("Reseller {reseller_name} extended user {username} by {} months", request.months);
+    axum::http::StatusCode::OK.into_response()
+}
+
+async fn reseller_streams(
+    AuthBearer(token): AuthBearer,
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+) -> impl axum::response::IntoResponse + Send {
+    let Some(name) = get_username_from_auth_header(&token, &app_state) else {
+        return axum::http::StatusCode::FORBIDDEN.into_response();
+    };
+    let usernames = owned_usernames(&app_state, &name);
+    let streams: Vec<ResellerStreamDto> = app_state
+        .active_users
+        .active_streams()
+        .await
+        .into_iter()
+        .filter(|stream| usernames.contains(&stream.username))
+        .map(|stream| ResellerStreamDto { username: stream.username.clone(), stream: UserStreamDto::from(stream) })
+        .collect();
+    axum::Json(streams).into_response()
+}
+
+#[derive(serde::Deserialize)]
+struct ResellerHistoryParams {
+    days: Option<u64>,
+}
+
+async fn reseller_history(
+    AuthBearer(token): AuthBearer,
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+    axum::extract::Query(params): axum::extract::Query<ResellerHistoryParams>,
+) -> impl axum::response::IntoResponse + Send {
+    let Some(name) = get_username_from_auth_header(&token, &app_state) else {
+        return axum::http::StatusCode::FORBIDDEN.into_response();
+    };
+    let days = params.days.unwrap_or(RESELLER_HISTORY_DAYS).clamp(1, RESELLER_HISTORY_MAX_DAYS);
+    let usernames = owned_usernames(&app_state, &name);
+    let sessions: Vec<ResellerStreamHistoryDto> = users_stream_history(&app_state, &usernames, days)
+        .await
+        .into_iter()
+        .map(|(username, session)| ResellerStreamHistoryDto { username, session })
+        .collect();
+    axum::Json(sessions).into_response()
+}
+
+pub fn reseller_api_register(app_state: &Arc<AppState>) -> axum::Router<Arc<AppState>> {
+    axum::Router::new().nest(
+        "/reseller",
+        axum::Router::new()
+            .route("/account", axum::routing::get(reseller_account))
+            .route("/user", axum::routing::post(create_reseller_user))
+            .route("/user/{username}/extend", axum::routing::post(extend_reseller_user))
+            .route("/streams", axum::routing::get(reseller_streams))
+            .route("/history", axum::routing::get(reseller_history))
+            .route_layer(axum::middleware::from_fn_with_state(Arc::clone(app_state), validator_reseller)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::extend_exp_date;
+    use chrono::{Months, Utc};
+
+    #[test]
+    fn extend_starts_at_now_for_expired_users() {
+        let now = Utc::now();
+        let in_one_month = now.checked_add_months(Months::new(1)).map(|date| date.timestamp()).unwrap_or_default();
+        let extended = extend_exp_date(Some(now.timestamp() - 3600), 1).unwrap_or_default();
+        assert!((extended - in_one_month).abs() <= 1);
+
+        let extended_twice = extend_exp_date(Some(in_one_month), 1).unwrap_or_default();
+        assert!(extended_twice > in_one_month + 27 * 86_400);
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::io;
 use std::path::Path;
 use std::sync::Arc;
@@ -6,17 +6,23 @@ use std::sync::Arc;
 use axum::extract::{Path as AxumPath, Query, State};
 use axum::response::{IntoResponse, Response};
 use axum::http::StatusCode;
+use log::error;
 use serde::{Deserialize, Serialize};
+use shared::model::UserStreamHistoryDto;
+use shared::utils::current_time_secs;
 
 use crate::api::model::AppState;
 use crate::api::api_utils::json_or_bin_response;
 use crate::api::endpoints::extract_accept_header::ExtractAcceptHeader;
 use crate::repository::{QosSnapshotRecord, QosSnapshotRepository, StreamHistoryFileReader};
 use crate::utils::stream_history_viewer::{
-    CompiledFilter, StreamHistoryQuery, TimeRange,
+    CompiledFilter, StreamHistoryQuery, TimeRange, SECS_PER_DAY,
     discover_files, resolve_time_range,
 };
 
+/// Maximum number of sessions returned by the user stream history.
+const USER_HISTORY_LIMIT: usize = 500;
+
 #[derive(Deserialize)]
 pub(crate) struct HistoryQueryParams {
     pub from: Option<String>,
@@ -34,6 +40,11 @@ pub(crate) struct QosSnapshotQueryParams {
     pub filter: HashMap<String, String>,
 }
 
+#[derive(Deserialize)]
+pub(crate) struct ChannelHealthQueryParams {
+    pub target: Option<String>,
+}
+
 #[derive(Serialize)]
 struct ErrorResponse {
     error: String,
@@ -230,6 +241,26 @@ pub(crate) async fn qos_snapshot_detail_query(
     }
 }
 
+/// Reports the tracked live channels with their health, optionally for a single target.
+pub(crate) async fn channel_health_query(
+    ExtractAcceptHeader(accept): ExtractAcceptHeader,
+    State(app_state): State<Arc<AppState>>,
+    Query(params): Query<ChannelHealthQueryParams>,
+) -> Response {
+    let target_id = match params.target.as_deref() {
+        Some(name) => match app_state.app_config.get_target_by_name(name) {
+            Some(target) => Some(target.id),
+            None => return error_response(StatusCode::NOT_FOUND, format!("Target {name} not found")),
+        },
+        None => None,
+    };
+    let mut records = app_state.channel_health.report().await;
+    if let Some(target_id) = target_id {
+        records.retain(|record| record.target_id == target_id);
+    }
+    json_or_bin_response(accept.as_deref(), &records).into_response()
+}
+
 fn collect_records(
     dir: &str,
     time_range: &TimeRange,
@@ -265,6 +296,71 @@ fn collect_records(
     Ok(records)
 }
 
+/// Returns the finished stream sessions of a user within the last `days`, newest first.
+pub(crate) async fn user_stream_history(app_state: &AppState, username: &str, days: u64) -> Vec<UserStreamHistoryDto> {
+    let usernames = HashSet::from([username.to_string()]);
+    users_stream_history(app_state, &usernames, days).await.into_iter().map(|(_, session)| session).collect()
+}
+
+/// Finished stream sessions of the given users with their usernames, newest first.
+pub(crate) async fn users_stream_history(
+    app_state: &AppState,
+    usernames: &HashSet<String>,
+    days: u64,
+) -> Vec<(String, UserStreamHistoryDto)> {
+    if usernames.is_empty() {
+        return Vec::new();
+    }
+    let Some(history_dir) = get_history_directory(app_state) else {
+        return Vec::new();
+    };
+    let now = current_time_secs();
+    let time_range = (now.saturating_sub(days * SECS_PER_DAY), now);
+    let mut raw_filter = HashMap::from([("event_type".to_string(), "disconnect".to_string())]);
+    if let (1, Some(username)) = (usernames.len(), usernames.iter().next()) {
+        raw_filter.insert("api_username".to_string(), username.clone());
+    }
+    let Ok(filters) = CompiledFilter::compile(&raw_filter) else {
+        return Vec::new();
+    };
+    let result = tokio::task::spawn_blocking(move || collect_records(&history_dir, &time_range, &filters)).await;
+    let records = match result {
+        Ok(Ok(records)) => records,
+        Ok(Err(err)) => {
+            if err.kind() != io::ErrorKind::NotFound {
+                error!("Failed to read user stream history: {err}");
+            }
+            return Vec::new();
+        }
+        Err(err) => {
+            error!("User stream history task failed: {err}");
+            return Vec::new();
+        }
+    };
+    // the filter matches usernames case-insensitive
+    let mut sessions: Vec<(String, UserStreamHistoryDto)> = records
+        .into_iter()
+        .filter_map(|record| {
+            let username = record.api_username.clone().filter(|name| usernames.contains(name))?;
+            Some((
+                username,
+                UserStreamHistoryDto {
+                    ts: record.connect_ts_utc.unwrap_or(record.event_ts_utc),
+                    title: record.title,
+                    group: record.group,
+                    item_type: record.item_type,
+                    duration_secs: record.session_duration,
+                    bytes_sent: record.bytes_sent,
+                    client_ip: record.source_addr,
+                },
+            ))
+        })
+        .collect();
+    sessions.sort_by_key(|(_, session)| std::cmp::Reverse(session.ts));
+    sessions.truncate(USER_HISTORY_LIMIT);
+    sessions
+}
+
 pub(crate) fn aggregate_provider_summaries(
     records: &[crate::repository::StreamHistoryRecord],
 ) -> Vec<ProviderSummary> {
@@ -411,6 +507,7 @@ mod tests {
             provider_name: Some(provider_name.to_string()),
             provider_username: None,
             input_name: Some(String::from("input")),
+            alternate_input: None,
             virtual_id: Some(1),
             item_type: Some(String::from("live")),
             title: Some(String::from("Title")),
@@ -436,6 +533,8 @@ mod tests {
             bytes_sent,
             first_byte_latency_ms,
             provider_reconnect_count: None,
+            provider_stall_count: None,
+            ts_quality: None,
             failure_stage: None,
             provider_http_status: None,
             provider_error_class: None,
@@ -519,11 +618,15 @@ mod tests {
         cfg.reverse_proxy = Some(ReverseProxyConfig {
             resource_rewrite_disabled: false,
             rewrite_secret: [0; 16],
+            trusted_proxies: Vec::new(),
+            trusted_proxy_networks: Vec::new(),
             resource_retry: ResourceRetryConfig::default(),
             disabled_header: None,
             stream: None,
             cache: None,
+            vod_cache: None,
             rate_limit: None,
+            login_protection: None,
             geoip: None,
             stream_history: None,
             qos_aggregation: Some(crate::model::QosAggregationConfig {
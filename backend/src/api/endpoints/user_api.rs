@@ -1,6 +1,7 @@
 use crate::{
     api::{
         api_utils::{get_user_target_by_username, get_username_from_auth_header, try_unwrap_body},
+        endpoints::{stream_history_api::user_stream_history, v1_api_user::save_api_proxy_users},
         model::AppState,
     },
     auth::{validator_api_user, AuthBearer},
@@ -12,12 +13,17 @@ use crate::{
 use axum::response::IntoResponse;
 use bytes::Bytes;
 use futures::{stream, StreamExt};
-use log::error;
+use log::{error, info};
+use serde_json::json;
 use shared::{
-    model::{PlaylistBouquetDto, TargetType, XtreamCluster},
+    model::{PlaylistBouquetDto, TargetType, UserAccountDto, UserPasswordChangeDto, UserStreamDto, XtreamCluster},
     utils::concat_path_leading_slash,
 };
-use std::{collections::HashSet, sync::Arc};
+use std::{collections::HashSet, path::Path, sync::Arc};
+
+/// Days of stream history shown to a user when not requested otherwise.
+const USER_HISTORY_DAYS: u64 = 7;
+const USER_HISTORY_MAX_DAYS: u64 = 31;
 
 fn get_categories_from_xtream(categories: Option<Vec<PlaylistXtreamCategory>>) -> Vec<String> {
     let mut groups: Vec<String> = Vec::new();
@@ -178,12 +184,173 @@ async fn playlist_bouquet(
         .body(axum::body::Body::from("{}")))
 }
 
+async fn user_quota(
+    AuthBearer(token): AuthBearer,
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+) -> impl axum::response::IntoResponse + Send {
+    if let Some(username) = get_username_from_auth_header(&token, &app_state) {
+        if let Some(user) = app_state.app_config.get_user_credentials(&username) {
+            return axum::Json(user.quota_status(&app_state).await).into_response();
+        }
+    }
+    axum::http::StatusCode::FORBIDDEN.into_response()
+}
+
+async fn user_devices(
+    AuthBearer(token): AuthBearer,
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+) -> impl axum::response::IntoResponse + Send {
+    if let Some(username) = get_username_from_auth_header(&token, &app_state) {
+        return axum::Json(app_state.user_devices.list(&username).await).into_response();
+    }
+    axum::http::StatusCode::FORBIDDEN.into_response()
+}
+
+async fn revoke_user_device(
+    AuthBearer(token): AuthBearer,
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+    axum::extract::Path(device): axum::extract::Path<String>,
+) -> impl axum::response::IntoResponse + Send {
+    if let Some(username) = get_username_from_auth_header(&token, &app_state) {
+        if app_state.user_devices.revoke(&username, &device).await {
+            return axum::http::StatusCode::OK.into_response();
+        }
+        return axum::http::StatusCode::NOT_FOUND.into_response();
+    }
+    axum::http::StatusCode::FORBIDDEN.into_response()
+}
+
+async fn user_account(
+    AuthBearer(token): AuthBearer,
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+) -> impl axum::response::IntoResponse + Send {
+    if let Some(username) = get_username_from_auth_header(&token, &app_state) {
+        if let Some((user, target)) = app_state.app_config.get_target_for_username(&username) {
+            let base_url = app_state.app_config.get_user_server_info(&user).get_base_url();
+            let credentials = url::form_urlencoded::Serializer::new(String::new())
+                .append_pair("username", &user.username)
+                .append_pair("password", &user.password)
+                .finish();
+            let has_xtream = target.has_output(TargetType::Xtream);
+            let has_m3u = target.has_output(TargetType::M3u);
+            let account = UserAccountDto {
+                username: user.username.clone(),
+                target: target.name.clone(),
+                status: user.status,
+                created_at: user.created_at,
+                exp_date: user.exp_date,
+                max_connections: user.max_connections,
+                active_connections: app_state.active_users.user_connections(&username).await,
+                quota: user.quota_status(&app_state).await,
+                xtream_url: has_xtream.then(|| base_url.clone()),
+                m3u_url: has_m3u.then(|| format!("{base_url}/get.php?{credentials}&type=m3u_plus")),
+                xmltv_url: (has_xtream || has_m3u).then(|| format!("{base_url}/xmltv.php?{credentials}")),
+            };
+            return axum::Json(account).into_response();
+        }
+    }
+    axum::http::StatusCode::FORBIDDEN.into_response()
+}
+
+async fn change_user_password(
+    AuthBearer(token): AuthBearer,
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+    axum::extract::Json(change): axum::extract::Json<UserPasswordChangeDto>,
+) -> impl axum::response::IntoResponse + Send {
+    let Some(username) = get_username_from_auth_header(&token, &app_state) else {
+        return axum::http::StatusCode::FORBIDDEN.into_response();
+    };
+    if let Err(err) = change.validate() {
+        return (axum::http::StatusCode::BAD_REQUEST, axum::Json(json!({"error": err.to_string()}))).into_response();
+    }
+    let api_proxy_file_path = app_state.app_config.paths.load().api_proxy_file_path.clone();
+    let _lock = app_state.app_config.file_locks.write_lock(Path::new(&api_proxy_file_path)).await;
+
+    let Some(old_api_proxy) = app_state.app_config.api_proxy.load().clone() else {
+        return axum::http::StatusCode::FORBIDDEN.into_response();
+    };
+    let mut api_proxy = (*old_api_proxy).clone();
+    let Some(user) = api_proxy
+        .user
+        .iter_mut()
+        .flat_map(|target_user| target_user.credentials.iter_mut())
+        .find(|user| user.username == username)
+    else {
+        return axum::http::StatusCode::FORBIDDEN.into_response();
+    };
+    if user.password != change.password.trim() {
+        return (axum::http::StatusCode::BAD_REQUEST, axum::Json(json!({"error": "Invalid password"})))
+            .into_response();
+    }
+    user.password = change.new_password.trim().to_string();
+    if let Err(err) = save_api_proxy_users(&app_state, api_proxy).await {
+        return (axum::http::StatusCode::INTERNAL_SERVER_ERROR, axum::Json(json!({"error": err}))).into_response();
+    }
+    info!("User {username} changed the password");
+    axum::http::StatusCode::OK.into_response()
+}
+
+async fn user_streams(
+    AuthBearer(token): AuthBearer,
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+) -> impl axum::response::IntoResponse + Send {
+    if let Some(username) = get_username_from_auth_header(&token, &app_state) {
+        let streams: Vec<UserStreamDto> =
+            app_state.active_users.user_streams(&username).await.into_iter().map(UserStreamDto::from).collect();
+        return axum::Json(streams).into_response();
+    }
+    axum::http::StatusCode::FORBIDDEN.into_response()
+}
+
+async fn stop_user_stream(
+    AuthBearer(token): AuthBearer,
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+    axum::extract::Path(uid): axum::extract::Path<u32>,
+) -> impl axum::response::IntoResponse + Send {
+    if let Some(username) = get_username_from_auth_header(&token, &app_state) {
+        let streams = app_state.active_users.user_streams(&username).await;
+        if let Some(stream) = streams.iter().find(|stream| stream.uid == uid) {
+            // the user stops the own stream, the channel is not blocked like after a kick
+            if app_state.connection_manager.kick_connection(&stream.addr, stream.channel.virtual_id, 0).await {
+                return axum::http::StatusCode::OK.into_response();
+            }
+        }
+        return axum::http::StatusCode::NOT_FOUND.into_response();
+    }
+    axum::http::StatusCode::FORBIDDEN.into_response()
+}
+
+#[derive(serde::Deserialize)]
+struct UserHistoryParams {
+    days: Option<u64>,
+}
+
+async fn user_history(
+    AuthBearer(token): AuthBearer,
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+    axum::extract::Query(params): axum::extract::Query<UserHistoryParams>,
+) -> impl axum::response::IntoResponse + Send {
+    if let Some(username) = get_username_from_auth_header(&token, &app_state) {
+        let days = params.days.unwrap_or(USER_HISTORY_DAYS).clamp(1, USER_HISTORY_MAX_DAYS);
+        return axum::Json(user_stream_history(&app_state, &username, days).await).into_response();
+    }
+    axum::http::StatusCode::FORBIDDEN.into_response()
+}
+
 pub fn user_api_register(app_state: &Arc<AppState>, web_ui_path: &str) -> axum::Router<Arc<AppState>> {
     axum::Router::new().nest(
         &concat_path_leading_slash(web_ui_path, "/api/v1/user"),
         axum::Router::new()
             .route("/playlist/categories", axum::routing::get(playlist_categories))
             .route("/playlist/bouquet", axum::routing::get(playlist_bouquet).post(save_playlist_bouquet))
+            .route("/account", axum::routing::get(user_account))
+            .route("/password", axum::routing::post(change_user_password))
+            .route("/quota", axum::routing::get(user_quota))
+            .route("/streams", axum::routing::get(user_streams))
+            .route("/streams/{uid}", axum::routing::delete(stop_user_stream))
+            .route("/history", axum::routing::get(user_history))
+            .route("/devices", axum::routing::get(user_devices))
+            .route("/devices/{device}", axum::routing::delete(revoke_user_device))
             .route_layer(axum::middleware::from_fn_with_state(Arc::clone(app_state), validator_api_user)),
     )
 
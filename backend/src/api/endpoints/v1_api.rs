@@ -4,6 +4,7 @@ use crate::{
         endpoints::{
             download_api, extract_accept_header::ExtractAcceptHeader, library_api::library_api_register,
             rbac_api::rbac_api_register,
+            reseller_api::reseller_api_register,
             user_api::user_api_register, v1_api_config::v1_api_config_register,
             v1_api_config::v1_api_config_register_with_permissions, v1_api_playlist::{
                 v1_api_playlist_register_public,
@@ -130,7 +131,8 @@ pub fn v1_api_register(
         .route(
             "/qos-snapshots/{stream_identity_key}",
             axum::routing::get(super::stream_history_api::qos_snapshot_detail_query),
-        );
+        )
+        .route("/channel-health", axum::routing::get(super::stream_history_api::channel_health_query));
 
     let system_write = axum::routing::Router::new()
         .route("/geoip/update", axum::routing::get(geoip_update));
@@ -159,7 +161,8 @@ pub fn v1_api_register(
             .merge(v1_api_user_register_with_permissions(axum::routing::Router::new(), app_state))
             .merge(v1_api_playlist_register_with_permissions(axum::routing::Router::new(), app_state))
             .merge(library_api_register(axum::routing::Router::new(), Some(app_state)))
-            .merge(rbac_api_register(Arc::clone(app_state)));
+            .merge(rbac_api_register(Arc::clone(app_state)))
+            .merge(reseller_api_register(app_state));
     } else {
         router = router
             .merge(system_read)
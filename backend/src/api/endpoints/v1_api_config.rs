@@ -98,6 +98,9 @@ fn filter_api_proxy_by_permissions(api_proxy: &mut ApiProxyConfigDto, permission
     }
     if !permissions.contains(Permission::UserRead) {
         api_proxy.user.clear();
+        api_proxy.parental_profiles.clear();
+        api_proxy.limit_profiles.clear();
+        api_proxy.resellers.clear();
     }
 }
 
@@ -118,6 +121,9 @@ fn filter_app_config_by_permissions(app_config: &mut shared::model::AppConfigDto
     if let Some(api_proxy) = app_config.api_proxy.as_mut() {
         if !permissions.contains(Permission::UserRead) {
             api_proxy.user.clear();
+            api_proxy.parental_profiles.clear();
+            api_proxy.limit_profiles.clear();
+            api_proxy.resellers.clear();
         }
     }
 }
@@ -716,6 +722,9 @@ mod tests {
                 }],
                 use_user_db: true,
                 auth_error_status: 401,
+                parental_profiles: vec![],
+                limit_profiles: vec![],
+                resellers: vec![],
             }),
         };
 
@@ -750,6 +759,9 @@ mod tests {
             }],
             use_user_db: true,
             auth_error_status: 401,
+            parental_profiles: vec![],
+            limit_profiles: vec![],
+            resellers: vec![],
         };
 
         filter_api_proxy_by_permissions(&mut api_proxy, permissions);
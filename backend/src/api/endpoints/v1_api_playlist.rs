@@ -17,13 +17,18 @@ use shared::{
         InputType, PlaylistEpgRequest, PlaylistRequest, PlaylistUrlResolveRequest, ProxyType, TargetType, UiPlaylistItem,
         XtreamCluster,
     },
-    utils::{concat_path_leading_slash, sanitize_sensitive_info, Internable},
+    concat_string,
+    utils::{concat_path_leading_slash, sanitize_sensitive_info, Internable, HLS_EXT},
 };
 use std::sync::Arc;
 use url::Url;
 use shared::utils::deobfuscate_text;
 use crate::api::api_utils::resource_response;
 
+const WEBPLAYER_ACCESS_TOKEN_TTL_SECS: u16 = 30;
+// The preview player reloads the packaged HLS playlist and seeks in VOD with the same token.
+const PREVIEW_ACCESS_TOKEN_TTL_SECS: u16 = 4 * 3600;
+
 fn create_config_input_for_m3u(url: &str) -> ConfigInput {
     ConfigInput {
         id: 0,
@@ -115,6 +120,21 @@ fn build_playlist_webplayer_url(
     )
 }
 
+fn build_playlist_preview_url(
+    base_url: &str,
+    access_token: &str,
+    target_id: u16,
+    virtual_id: u32,
+    cluster: XtreamCluster,
+) -> String {
+    let url = build_playlist_webplayer_url(base_url, access_token, target_id, virtual_id, cluster);
+    if cluster == XtreamCluster::Live {
+        concat_string!(&url, HLS_EXT)
+    } else {
+        url
+    }
+}
+
 async fn playlist_update(
     axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
     axum::extract::Json(targets): axum::extract::Json<Vec<String>>,
@@ -268,24 +288,38 @@ async fn playlist_series_info(
     axum::http::StatusCode::NO_CONTENT.into_response()
 }
 
-fn playlist_webplayer(
-    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
-    target_id: u16,
-    virtual_id: u32,
-    cluster: XtreamCluster,
-) -> impl axum::response::IntoResponse + Send {
-    let access_token = create_access_token(&app_state.app_config.access_token_secret, 30);
+fn get_player_base_url(app_state: &AppState) -> String {
     let config = app_state.app_config.config.load();
     let server_name = config
         .web_ui
         .as_ref()
         .and_then(|web_ui| web_ui.player_server.as_ref())
         .map_or("default", |server_name| server_name.as_str());
-    let server_info = app_state.app_config.get_server_info(server_name);
-    let base_url = server_info.get_base_url();
+    app_state.app_config.get_server_info(server_name).get_base_url()
+}
+
+fn playlist_webplayer(
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+    target_id: u16,
+    virtual_id: u32,
+    cluster: XtreamCluster,
+) -> impl axum::response::IntoResponse + Send {
+    let access_token = create_access_token(&app_state.app_config.access_token_secret, WEBPLAYER_ACCESS_TOKEN_TTL_SECS);
+    let base_url = get_player_base_url(&app_state);
     build_playlist_webplayer_url(&base_url, &access_token, target_id, virtual_id, cluster).into_response()
 }
 
+fn playlist_preview(
+    app_state: &AppState,
+    target_id: u16,
+    virtual_id: u32,
+    cluster: XtreamCluster,
+) -> impl axum::response::IntoResponse + Send {
+    let access_token = create_access_token(&app_state.app_config.access_token_secret, PREVIEW_ACCESS_TOKEN_TTL_SECS);
+    let base_url = get_player_base_url(app_state);
+    build_playlist_preview_url(&base_url, &access_token, target_id, virtual_id, cluster).into_response()
+}
+
 async fn playlist_epg(
     ExtractAcceptHeader(accept): ExtractAcceptHeader,
     axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
@@ -355,6 +389,9 @@ async fn playlist_resolve_url(
             )
             .into_response()
         }
+        PlaylistUrlResolveRequest::Preview { target_id, virtual_id, cluster } => {
+            playlist_preview(&app_state, target_id, virtual_id, cluster).into_response()
+        }
         PlaylistUrlResolveRequest::Provider { playlist_request, url } => {
             resolve_provider_url_for_request(&app_state.app_config, &playlist_request, &url).into_response()
         }
@@ -671,6 +708,15 @@ mod tests {
         assert_eq!(movie, "http://player.example/token/token123/1/movie/42");
         assert_eq!(series, "http://player.example/token/token123/1/series/42");
     }
+
+    #[test]
+    fn build_playlist_preview_url_requests_hls_for_live() {
+        let live = super::build_playlist_preview_url("http://player.example", "token123", 1, 42, XtreamCluster::Live);
+        let movie = super::build_playlist_preview_url("http://player.example", "token123", 1, 42, XtreamCluster::Video);
+
+        assert_eq!(live, "http://player.example/token/token123/1/live/42.m3u8");
+        assert_eq!(movie, "http://player.example/token/token123/1/movie/42");
+    }
 }
 
 async fn playlist_episode_item(
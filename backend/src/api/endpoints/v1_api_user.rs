@@ -6,10 +6,37 @@ use axum::{response::IntoResponse, Router};
 use serde_json::json;
 use shared::{
     model::{permission::Permission, ApiProxyConfigDto, ProxyUserCredentialsDto},
-    utils::mask_credentials,
+    utils::{current_time_secs, mask_credentials},
 };
 use std::{path::Path, sync::Arc};
 
+/// Stores the users in the user db or the `api-proxy.yml` and activates the changed config.
+pub(in crate::api::endpoints) async fn save_api_proxy_users(
+    app_state: &AppState,
+    api_proxy: ApiProxyConfig,
+) -> Result<(), String> {
+    let new_api_proxy = Arc::new(api_proxy);
+    if new_api_proxy.use_user_db {
+        store_api_user(&app_state.app_config, &new_api_proxy.user).await.map_err(|err| err.to_string())?;
+    } else {
+        let config = app_state.app_config.config.load();
+        let backup_dir = config.get_backup_dir();
+        let paths = app_state.app_config.paths.load();
+        if let Some(err) = crate::api::endpoints::v1_api_config::intern_save_config_api_proxy(
+            backup_dir.as_ref(),
+            &ApiProxyConfigDto::from(&*new_api_proxy),
+            paths.api_proxy_file_path.as_str(),
+        )
+        .await
+        {
+            return Err(err.to_string());
+        }
+    }
+    // Update state after successful save
+    app_state.app_config.api_proxy.store(Some(new_api_proxy));
+    Ok(())
+}
+
 #[allow(clippy::too_many_lines)]
 async fn save_config_api_proxy_user(
     method: axum::http::Method,
@@ -23,8 +50,7 @@ async fn save_config_api_proxy_user(
     };
     let _lock = app_state.app_config.file_locks.write_lock(Path::new(&api_proxy_file_path)).await;
 
-    credential.prepare();
-    if let Err(err) = credential.validate() {
+    if let Err(err) = credential.prepare().and_then(|()| credential.validate()) {
         return (axum::http::StatusCode::BAD_REQUEST, axum::Json(json!({"error": err.to_string()}))).into_response();
     }
 
@@ -113,32 +139,10 @@ async fn save_config_api_proxy_user(
         api_proxy.user[target_idx].credentials.push(ProxyUserCredentials::from(&credential));
     }
 
-    let new_api_proxy = Arc::new(api_proxy);
-
-    if new_api_proxy.use_user_db {
-        if let Err(err) = store_api_user(&app_state.app_config, &new_api_proxy.user).await {
-            return (axum::http::StatusCode::INTERNAL_SERVER_ERROR, axum::Json(json!({"error": err.to_string()})))
-                .into_response();
-        }
-    } else {
-        let config = app_state.app_config.config.load();
-        let backup_dir = config.get_backup_dir();
-        let paths = app_state.app_config.paths.load();
-        if let Some(err) = crate::api::endpoints::v1_api_config::intern_save_config_api_proxy(
-            backup_dir.as_ref(),
-            &ApiProxyConfigDto::from(&*new_api_proxy),
-            paths.api_proxy_file_path.as_str(),
-        )
-        .await
-        {
-            return (axum::http::StatusCode::INTERNAL_SERVER_ERROR, axum::Json(json!({"error": err.to_string()})))
-                .into_response();
-        }
+    if let Err(err) = save_api_proxy_users(&app_state, api_proxy).await {
+        return (axum::http::StatusCode::INTERNAL_SERVER_ERROR, axum::Json(json!({"error": err}))).into_response();
     }
 
-    // Update state after successful save
-    app_state.app_config.api_proxy.store(Some(Arc::clone(&new_api_proxy)));
-
     if target_has_alias_pool_min(&app_state, &target_name) {
         let app_state_clone = Arc::clone(&app_state);
         let target_name_clone = target_name.clone();
@@ -167,34 +171,12 @@ async fn delete_config_api_proxy_user(
             }
         }
         if modified {
-            let new_api_proxy = Arc::new(api_proxy);
-            if new_api_proxy.use_user_db {
-                if let Err(err) = store_api_user(&app_state.app_config, &new_api_proxy.user).await {
-                    return (
-                        axum::http::StatusCode::INTERNAL_SERVER_ERROR,
-                        axum::Json(json!({"error": err.to_string()})),
-                    )
-                        .into_response();
-                }
-            } else {
-                let config = app_state.app_config.config.load();
-                let backup_dir = config.get_backup_dir();
-                let paths = app_state.app_config.paths.load();
-                if let Some(err) = crate::api::endpoints::v1_api_config::intern_save_config_api_proxy(
-                    backup_dir.as_ref(),
-                    &ApiProxyConfigDto::from(&*new_api_proxy),
-                    paths.api_proxy_file_path.as_str(),
-                )
-                .await
-                {
-                    return (
-                        axum::http::StatusCode::INTERNAL_SERVER_ERROR,
-                        axum::Json(json!({"error": err.to_string()})),
-                    )
-                        .into_response();
-                }
+            if let Err(err) = save_api_proxy_users(&app_state, api_proxy).await {
+                return (axum::http::StatusCode::INTERNAL_SERVER_ERROR, axum::Json(json!({"error": err})))
+                    .into_response();
             }
-            app_state.app_config.api_proxy.store(Some(Arc::clone(&new_api_proxy)));
+            app_state.user_devices.remove_user(&username).await;
+            app_state.user_travel.remove_user(&username).await;
         } else {
             return (
                 axum::http::StatusCode::BAD_REQUEST,
@@ -206,11 +188,67 @@ async fn delete_config_api_proxy_user(
     axum::http::StatusCode::OK.into_response()
 }
 
+async fn list_user_devices(
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+    axum::extract::Path(username): axum::extract::Path<String>,
+) -> impl axum::response::IntoResponse + Send {
+    axum::Json(app_state.user_devices.list(&username).await).into_response()
+}
+
+async fn approve_user_device(
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+    axum::extract::Path((username, device)): axum::extract::Path<(String, String)>,
+) -> impl axum::response::IntoResponse + Send {
+    if app_state.user_devices.approve(&username, &device).await {
+        axum::http::StatusCode::OK.into_response()
+    } else {
+        axum::http::StatusCode::NOT_FOUND.into_response()
+    }
+}
+
+async fn revoke_user_device(
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+    axum::extract::Path((username, device)): axum::extract::Path<(String, String)>,
+) -> impl axum::response::IntoResponse + Send {
+    if app_state.user_devices.revoke(&username, &device).await {
+        axum::http::StatusCode::OK.into_response()
+    } else {
+        axum::http::StatusCode::NOT_FOUND.into_response()
+    }
+}
+
+async fn list_login_bans(
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+) -> impl axum::response::IntoResponse + Send {
+    axum::Json(app_state.login_bans.list(current_time_secs()).await).into_response()
+}
+
+async fn unban_login(
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+    axum::extract::Path((kind, key)): axum::extract::Path<(String, String)>,
+) -> impl axum::response::IntoResponse + Send {
+    let user = match kind.as_str() {
+        "ip" => false,
+        "user" => true,
+        _ => return axum::http::StatusCode::BAD_REQUEST.into_response(),
+    };
+    if app_state.login_bans.unban(&key, user).await {
+        axum::http::StatusCode::OK.into_response()
+    } else {
+        axum::http::StatusCode::NOT_FOUND.into_response()
+    }
+}
+
 pub fn v1_api_user_register(router: Router<Arc<AppState>>) -> axum::Router<Arc<AppState>> {
     router
         .route("/user/{target}", axum::routing::post(save_config_api_proxy_user))
         .route("/user/{target}", axum::routing::put(save_config_api_proxy_user))
         .route("/user/{target}/{username}", axum::routing::delete(delete_config_api_proxy_user))
+        .route("/devices/{username}", axum::routing::get(list_user_devices))
+        .route("/devices/{username}/{device}", axum::routing::delete(revoke_user_device))
+        .route("/devices/{username}/{device}/approve", axum::routing::post(approve_user_device))
+        .route("/login_bans", axum::routing::get(list_login_bans))
+        .route("/login_bans/{kind}/{key}", axum::routing::delete(unban_login))
 }
 
 pub fn v1_api_user_register_with_permissions(
@@ -229,5 +267,16 @@ pub fn v1_api_user_register_with_permissions(
         )
         .layer(permission_layer!(app_state, Permission::UserWrite));
 
-    router.nest("/user", user_write_routes)
+    let device_read_routes = Router::new()
+        .route("/devices/{username}", axum::routing::get(list_user_devices))
+        .route("/login_bans", axum::routing::get(list_login_bans))
+        .layer(permission_layer!(app_state, Permission::UserRead));
+
+    let device_write_routes = Router::new()
+        .route("/devices/{username}/{device}", axum::routing::delete(revoke_user_device))
+        .route("/devices/{username}/{device}/approve", axum::routing::post(approve_user_device))
+        .route("/login_bans/{kind}/{key}", axum::routing::delete(unban_login))
+        .layer(permission_layer!(app_state, Permission::UserWrite));
+
+    router.nest("/user", user_write_routes).merge(device_read_routes).merge(device_write_routes)
 }
@@ -31,6 +31,20 @@ fn no_web_auth_token() -> impl axum::response::IntoResponse + Send {
 
 fn api_user_can_access_web_ui(ui_enabled: bool) -> bool { ui_enabled }
 
+fn web_user_roles(app_state: &AppState, username: &str) -> Vec<String> {
+    let is_reseller = app_state
+        .app_config
+        .api_proxy
+        .load()
+        .as_ref()
+        .is_some_and(|api_proxy| api_proxy.get_reseller(username).is_some());
+    if is_reseller {
+        vec![shared::model::ROLE_RESELLER.to_string()]
+    } else {
+        Vec::new()
+    }
+}
+
 async fn token(
     axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
     axum::extract::Json(mut req): axum::extract::Json<UserCredential>,
@@ -67,7 +81,8 @@ async fn token(
                         let token_result = if is_admin {
                             create_jwt_admin(web_auth, username, pwd_version)
                         } else {
-                            create_jwt_web_user(web_auth, username, permissions, pwd_version)
+                            let roles = web_user_roles(&app_state, username);
+                            create_jwt_web_user(web_auth, username, roles, permissions, pwd_version)
                         };
                         if let Ok(token) = token_result {
                             req.zeroize();
@@ -154,7 +169,8 @@ async fn token_refresh(
                 let new_token = if is_admin {
                     create_jwt_admin(web_auth, username, current_pwd_version)
                 } else {
-                    create_jwt_web_user(web_auth, username, resolved_permissions, current_pwd_version)
+                    let roles = web_user_roles(&app_state, username);
+                    create_jwt_web_user(web_auth, username, roles, resolved_permissions, current_pwd_version)
                 };
                 if let Ok(token) = new_token {
                     return axum::Json(TokenResponse { token, username: user.username.clone() }).into_response();
@@ -6,6 +6,7 @@ use crate::{
         },
         model::{AppState, UserApiRequestQueryOrBody, UserApiRequest},
     },
+    auth::Fingerprint,
     model::{Config, ConfigTarget, ProxyUserCredentials, TargetOutput, EPG_ATTRIB_ID, EPG_TAG_CHANNEL},
     repository::{
         get_target_storage_path, m3u_get_epg_file_path_for_target, storage_const, xtream_get_epg_file_path_for_target,
@@ -72,7 +73,12 @@ pub(in crate::api) fn get_epg_path_for_target(config: &Config, target: &ConfigTa
                     );
                 }
             }
-            TargetOutput::Strm(_) | TargetOutput::HdHomeRun(_) => {}
+            TargetOutput::Strm(_)
+            | TargetOutput::HdHomeRun(_)
+            | TargetOutput::Enigma2(_)
+            | TargetOutput::Json(_)
+            | TargetOutput::Csv(_)
+            | TargetOutput::Multicast(_) => {}
         }
     }
     None
@@ -477,11 +483,15 @@ pub async fn serve_short_epg(
 /// let router = xmltv_api_register();
 /// // A GET request to /xmltv.php with valid query parameters will invoke this handler.
 /// ```
-async fn xmltv_api(api_req: UserApiRequest, app_state: &Arc<AppState>) -> impl IntoResponse + Send {
+async fn xmltv_api(
+    api_req: UserApiRequest,
+    app_state: &Arc<AppState>,
+    fingerprint: &Fingerprint,
+) -> impl IntoResponse + Send {
     api_req.log_sanitized("xmltv_api");
     let auth_status = app_state.app_config.get_auth_error_status();
     let (user, target) = try_option_forbidden!(
-        get_user_target(&api_req, app_state),
+        get_user_target(&api_req, app_state, fingerprint),
         auth_status,
         false,
         format!("Could not find any user for xmltv api {}", api_req.username)
@@ -502,20 +512,23 @@ async fn xmltv_api(api_req: UserApiRequest, app_state: &Arc<AppState>) -> impl I
 }
 
 async fn xmltv_api_get(
+    fingerprint: Fingerprint,
     axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
     axum::extract::Query(api_req): axum::extract::Query<UserApiRequest>,
 ) -> impl IntoResponse + Send {
-    xmltv_api(api_req, &app_state).await
+    xmltv_api(api_req, &app_state, &fingerprint).await
 }
 
 async fn xmltv_api_post(
+    fingerprint: Fingerprint,
     axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
     UserApiRequestQueryOrBody(api_req): UserApiRequestQueryOrBody,
 ) -> impl IntoResponse + Send {
-    xmltv_api(api_req, &app_state).await
+    xmltv_api(api_req, &app_state, &fingerprint).await
 }
 
 async fn epg_api_resource(
+    fingerprint: Fingerprint,
     req_headers: axum::http::HeaderMap,
     axum::extract::Query(api_req): axum::extract::Query<UserApiRequest>,
     axum::extract::Path((username, password, resource)): axum::extract::Path<(String, String, String)>,
@@ -523,7 +536,7 @@ async fn epg_api_resource(
 ) -> impl IntoResponse + Send {
     let auth_status = app_state.app_config.get_auth_error_status();
     let (user, _target) = try_option_forbidden!(
-        get_user_target_by_credentials(&username, &password, &api_req, &app_state),
+        get_user_target_by_credentials(&username, &password, &api_req, &app_state, &fingerprint),
         auth_status,
         false,
         format!("Could not find any user for epg resource {username}")
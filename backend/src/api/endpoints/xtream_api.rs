@@ -7,28 +7,29 @@ use crate::{
             create_api_proxy_user, create_catchup_session_key, create_session_fingerprint, empty_json_response_as_array,
             empty_json_response_as_object, force_provider_stream_response, get_session_reservation_ttl_secs,
             get_user_target, get_user_target_by_credentials, internal_server_error, is_seek_request,
-            admission_failure_response, is_stream_share_enabled, local_stream_response, redirect, redirect_response, resource_response,
+            admission_failure_response, country_access_denied, is_stream_share_enabled, local_stream_response, redirect, redirect_response, resource_response,
             separate_number_and_remainder, should_allow_exhausted_shared_reconnect, stream_response,
             try_option_bad_request, try_option_forbidden, try_result_bad_request, try_result_not_found,
-            try_unwrap_body, RedirectParams,
+            try_unwrap_body, user_device_denied, RedirectParams,
         },
         endpoints::{
-            hls_api::handle_hls_stream_request,
+            hls_api::{get_hls_packaging, handle_hls_stream_request, hls_packaging_response},
             xmltv_api::{get_empty_epg_response, get_epg_path_for_target, serve_short_epg},
         },
         model::{
             create_custom_video_stream_response, AppState, CustomVideoStreamType, UserApiRequestQueryOrBody, UserApiRequest,
-            XtreamAuthorizationResponse,
+            XtreamAuthorizationResponse, ChannelHealthFilter, UNAVAILABLE_CATEGORY_ID,
         },
     },
-    auth::Fingerprint,
+    auth::{verify_access_token, Fingerprint},
     model::{
-        xtream_mapping_option_from_target_options, Config, ConfigInput, ConfigInputFlags, ConfigTarget, InputSource,
-        ProxyUserCredentials,
+        xtream_mapping_option_from_target_options, AppConfig, ConfigInput, ConfigInputFlags, ConfigTarget,
+        InputSource, ProxyUserCredentials,
     },
     repository::{
         get_target_id_mapping, get_target_storage_path, storage_const, user_get_bouquet_filter,
         xtream_get_collection_path, xtream_get_item_for_stream_id, xtream_load_rewrite_playlist, VirtualIdRecord,
+        XtreamPlaylistIterator,
     },
     utils::{
         apply_timeshift, debug_if_enabled, file_exists_async, parse_timeshift, request, trace_if_enabled, xtream,
@@ -57,6 +58,7 @@ use shared::{
     },
 };
 use std::{
+    collections::HashSet,
     fmt::{Display, Formatter, Write},
     str::FromStr,
     sync::Arc,
@@ -144,7 +146,7 @@ impl<'a> ApiStreamRequest<'a> {
         stream_id: &'a str,
         action_path: &'a str,
     ) -> Self {
-        Self { context, access_token: false, username: "", password, stream_id, action_path }
+        Self { context, access_token: true, username: "", password, stream_id, action_path }
     }
 }
 
@@ -187,16 +189,25 @@ pub(in crate::api) fn get_xtream_player_api_stream_url(
     }
 }
 
-async fn get_user_info(user: &ProxyUserCredentials, app_state: &AppState) -> XtreamAuthorizationResponse {
+async fn get_user_info(
+    user: &ProxyUserCredentials,
+    app_state: &AppState,
+    country_denied: bool,
+) -> XtreamAuthorizationResponse {
     let server_info = app_state.app_config.get_user_server_info(user);
     let active_connections = app_state.get_active_connections_for_user(&user.username).await;
 
-    XtreamAuthorizationResponse::new(
+    let mut response = XtreamAuthorizationResponse::new(
         &server_info,
         user,
         active_connections,
         app_state.app_config.config.load().user_access_control,
-    )
+    );
+    response.user_info.quota = user.quota_status(app_state).await;
+    if country_denied || user.outside_access_schedule(app_state) {
+        response.user_info.auth = 0;
+    }
+    response
 }
 
 #[allow(clippy::too_many_lines)]
@@ -225,7 +236,7 @@ async fn xtream_player_api_stream(
     let auth_status = app_state.app_config.get_auth_error_status();
     let (user, target) = match user_target {
         None => try_option_forbidden!(
-            get_user_target_by_credentials(stream_req.username, stream_req.password, api_req, app_state),
+            get_user_target_by_credentials(stream_req.username, stream_req.password, api_req, app_state, fingerprint),
             auth_status,
             false,
             format!("Could not find any user for xc stream {}", stream_req.username)
@@ -282,6 +293,66 @@ async fn xtream_player_api_stream(
         );
     }
 
+    if user.quota_exhausted(app_state).await {
+        return admission_failure_response(
+            app_state,
+            fingerprint,
+            &user,
+            create_stream_channel_with_type(target.id, &pli, pli.item_type),
+            pli.input_name.as_ref(),
+            req_headers,
+            crate::repository::ConnectFailureReason::UserQuotaExhausted,
+        );
+    }
+
+    if user.outside_access_schedule(app_state) {
+        return admission_failure_response(
+            app_state,
+            fingerprint,
+            &user,
+            create_stream_channel_with_type(target.id, &pli, pli.item_type),
+            pli.input_name.as_ref(),
+            req_headers,
+            crate::repository::ConnectFailureReason::OutsideAccessSchedule,
+        );
+    }
+
+    if country_access_denied(app_state, fingerprint, &target, &user).await {
+        return admission_failure_response(
+            app_state,
+            fingerprint,
+            &user,
+            create_stream_channel_with_type(target.id, &pli, pli.item_type),
+            pli.input_name.as_ref(),
+            req_headers,
+            crate::repository::ConnectFailureReason::CountryDenied,
+        );
+    }
+
+    if user.parental_restricted(app_state, &pli) {
+        return admission_failure_response(
+            app_state,
+            fingerprint,
+            &user,
+            create_stream_channel_with_type(target.id, &pli, pli.item_type),
+            pli.input_name.as_ref(),
+            req_headers,
+            crate::repository::ConnectFailureReason::ParentalRestricted,
+        );
+    }
+
+    if let Some(reason) = user_device_denied(app_state, fingerprint, &user, req_headers, &api_req.device_id).await {
+        return admission_failure_response(
+            app_state,
+            fingerprint,
+            &user,
+            create_stream_channel_with_type(target.id, &pli, pli.item_type),
+            pli.input_name.as_ref(),
+            req_headers,
+            reason,
+        );
+    }
+
     if pli.item_type.is_local() {
         let admission = if (user.max_connections > 0 || user.soft_connections > 0)
             && app_state.app_config.config.load().user_access_control
@@ -448,6 +519,38 @@ async fn xtream_player_api_stream(
         )
     );
 
+    if item_type == PlaylistItemType::Live && extension == HLS_EXT {
+        if let Some(packaging) = get_hls_packaging(&user, &target) {
+            // Packaged from the provider MPEG-TS stream.
+            let (ts_query_path, _) = get_query_path(stream_req.action_path, None, &pli, app_state);
+            let ts_stream_url = try_option_bad_request!(
+                get_xtream_player_api_stream_url(&input, stream_req.context, &ts_query_path, &session_url),
+                true,
+                format!("Can't find stream url for target {target_name}, hls packaging, stream_id {virtual_id}")
+            );
+            let stream_channel = create_stream_channel_with_type(target.id, &pli, item_type);
+            let open_stream = Box::pin(async {
+                stream_response(
+                    fingerprint,
+                    app_state,
+                    session_key.as_str(),
+                    stream_channel,
+                    &ts_stream_url,
+                    req_headers,
+                    &input,
+                    &target,
+                    &user,
+                    connection_permission,
+                    connection_kind,
+                    allow_exhausted_shared_reconnect,
+                )
+                .await
+                .into_response()
+            });
+            return hls_packaging_response(app_state, &user, None, &ts_stream_url, &packaging, open_stream).await;
+        }
+    }
+
     let is_hls_request =
         item_type == PlaylistItemType::LiveHls || item_type == PlaylistItemType::LiveDash || extension == HLS_EXT;
     // Reverse proxy mode
@@ -488,7 +591,7 @@ async fn xtream_player_api_stream(
     .into_response()
 }
 
-fn get_query_path(
+pub(in crate::api) fn get_query_path(
     action_path: &str,
     stream_ext: Option<&String>,
     pli: &XtreamPlaylistItem,
@@ -583,6 +686,38 @@ async fn xtream_player_api_stream_with_token(
 
         let session_key = create_session_fingerprint(fingerprint, "webui", virtual_id);
 
+        if pli.item_type == PlaylistItemType::Live && stream_ext.as_deref() == Some(HLS_EXT) {
+            // Browsers can't play MPEG-TS, the Web UI preview always gets the packaged stream.
+            let packaging = get_hls_packaging(&user, &target).unwrap_or_default();
+            let (ts_query_path, _) = get_query_path(stream_req.action_path, None, &pli, app_state);
+            let ts_stream_url = try_option_bad_request!(
+                get_xtream_player_api_stream_url(&input, stream_req.context, &ts_query_path, &pli.url),
+                true,
+                format!("Can't find stream url for target {target_name}, hls packaging, stream_id {virtual_id}")
+            );
+            let open_stream = Box::pin(async {
+                stream_response(
+                    fingerprint,
+                    app_state,
+                    session_key.as_str(),
+                    pli.to_stream_channel(target.id),
+                    &ts_stream_url,
+                    req_headers,
+                    &input,
+                    &target,
+                    &user,
+                    UserConnectionPermission::Allowed,
+                    crate::api::model::ConnectionKind::Normal,
+                    false,
+                )
+                .await
+                .into_response()
+            });
+            let access_token = Some(stream_req.password);
+            return hls_packaging_response(app_state, &user, access_token, &ts_stream_url, &packaging, open_stream)
+                .await;
+        }
+
         let is_hls_request = pli.item_type == PlaylistItemType::LiveHls || stream_ext.as_deref() == Some(HLS_EXT);
 
         // TODO how should we use fixed provider for hls in multi provider config?
@@ -639,6 +774,7 @@ async fn xtream_player_api_stream_with_token(
 }
 
 async fn xtream_player_api_resource(
+    fingerprint: &Fingerprint,
     req_headers: &HeaderMap,
     api_req: &UserApiRequest,
     app_state: &Arc<AppState>,
@@ -646,12 +782,12 @@ async fn xtream_player_api_resource(
 ) -> impl IntoResponse {
     let auth_status = app_state.app_config.get_auth_error_status();
     let (user, target) = try_option_forbidden!(
-        get_user_target_by_credentials(resource_req.username, resource_req.password, api_req, app_state),
+        get_user_target_by_credentials(resource_req.username, resource_req.password, api_req, app_state, fingerprint),
         auth_status,
         false,
         format!("Could not find any user xc resource {}", resource_req.username)
     );
-    if user.permission_denied(app_state) {
+    if user.permission_denied(app_state) || user.outside_access_schedule(app_state) {
         return axum::http::StatusCode::FORBIDDEN.into_response();
     }
     let target_name = &target.name;
@@ -709,6 +845,7 @@ macro_rules! create_xtream_player_api_stream {
 macro_rules! create_xtream_player_api_resource {
     ($fn_name:ident, $context:expr) => {
         async fn $fn_name(
+            fingerprint: Fingerprint,
             axum::extract::Path((username, password, stream_id, resource)): axum::extract::Path<(
                 String,
                 String,
@@ -720,6 +857,7 @@ macro_rules! create_xtream_player_api_resource {
             req_headers: HeaderMap,
         ) -> impl IntoResponse {
             xtream_player_api_resource(
+                &fingerprint,
                 &req_headers,
                 &api_req,
                 &app_state,
@@ -768,7 +906,7 @@ async fn xtream_player_api_timeshift_stream(
 
     let auth_status = app_state.app_config.get_auth_error_status();
     let (user, target) = try_option_forbidden!(
-        get_user_target_by_credentials(&api_req.username, &api_req.password, &api_req, &app_state),
+        get_user_target_by_credentials(&api_req.username, &api_req.password, &api_req, &app_state, &fingerprint),
         auth_status,
         false,
         format!("Could not find any user {}", api_req.username)
@@ -818,7 +956,7 @@ async fn xtream_player_api_timeshift_query_stream(
 
     let auth_status = app_state.app_config.get_auth_error_status();
     let (user, target) = try_option_forbidden!(
-        get_user_target_by_credentials(&api_req.username, &api_req.password, &api_req, &app_state),
+        get_user_target_by_credentials(&api_req.username, &api_req.password, &api_req, &app_state, &fingerprint),
         auth_status,
         false,
         format!("Could not find any user {}", api_req.username)
@@ -850,6 +988,29 @@ async fn xtream_player_api_timeshift_query_stream(
     .into_response()
 }
 
+/// Streams for a user resolved by the caller, for endpoints whose urls must not contain the user credentials.
+pub(in crate::api) async fn xtream_player_api_user_stream(
+    fingerprint: &Fingerprint,
+    req_headers: &HeaderMap,
+    app_state: &Arc<AppState>,
+    user_target: (ProxyUserCredentials, Arc<ConfigTarget>),
+    context: ApiStreamContext,
+    stream_id: &str,
+) -> axum::response::Response {
+    let api_req = UserApiRequest::default();
+    let (username, password) = (user_target.0.username.clone(), user_target.0.password.clone());
+    xtream_player_api_stream(
+        fingerprint,
+        req_headers,
+        app_state,
+        &api_req,
+        ApiStreamRequest::from(context, &username, &password, stream_id, ""),
+        Some(user_target),
+    )
+    .await
+    .into_response()
+}
+
 pub async fn xtream_get_stream_info_response(
     app_state: &Arc<AppState>,
     user: &ProxyUserCredentials,
@@ -863,6 +1024,9 @@ pub async fn xtream_get_stream_info_response(
     };
 
     if let Ok(pli) = xtream_get_item_for_stream_id(virtual_id, app_state, target, Some(cluster)).await {
+        if user.parental_restricted(app_state, &pli) {
+            return try_unwrap_body!(empty_json_response_as_array());
+        }
         if pli.item_type.is_local() {
             let Ok(xtream_output) = target
                 .get_xtream_output()
@@ -1004,13 +1168,33 @@ async fn xtream_get_short_epg(
     axum::Json(json!(ShortEpgResultDto::default())).into_response()
 }
 
+/// Collects the categories which still have content for the user after applying the parental profile,
+/// `None` when the user is not restricted.
+async fn xtream_get_parental_visible_categories(
+    app_config: &AppConfig,
+    target: &ConfigTarget,
+    cluster: XtreamCluster,
+    user: &ProxyUserCredentials,
+    channel_health: Option<ChannelHealthFilter>,
+) -> Option<HashSet<String>> {
+    app_config.get_user_parental_restriction(user)?;
+    let mut visible = HashSet::new();
+    if let Ok(mut iter) = XtreamPlaylistIterator::new(cluster, app_config, target, None, user, channel_health).await {
+        while let Some((item, _)) = iter.next().await {
+            visible.insert(item.category_id.to_string());
+        }
+    }
+    Some(visible)
+}
+
 async fn xtream_player_api_handle_content_action(
-    config: &Config,
-    target_name: &str,
+    app_state: &Arc<AppState>,
+    target: &ConfigTarget,
     action: &str,
     category_id: Option<u32>,
     user: &ProxyUserCredentials,
 ) -> Option<impl IntoResponse> {
+    let app_config = &app_state.app_config;
     let (collection, cluster) = match action {
         crate::model::XC_ACTION_GET_LIVE_CATEGORIES => (storage_const::COL_CAT_LIVE, XtreamCluster::Live),
         crate::model::XC_ACTION_GET_VOD_CATEGORIES => (storage_const::COL_CAT_VOD, XtreamCluster::Video),
@@ -1018,17 +1202,36 @@ async fn xtream_player_api_handle_content_action(
         // we dont handle this action
         _ => return None,
     };
-    if let Ok(file_path) = xtream_get_collection_path(config, target_name, collection) {
+    let config = app_config.config.load();
+    if let Ok(file_path) = xtream_get_collection_path(&config, &target.name, collection) {
         match tokio::fs::read_to_string(&file_path).await {
             Ok(content) => {
                 let filter =
-                    user_get_bouquet_filter(config, &user.username, category_id, TargetType::Xtream, cluster).await;
+                    user_get_bouquet_filter(&config, &user.username, category_id, TargetType::Xtream, cluster).await;
 
                 match serde_json::from_str::<Vec<XtreamCategoryEntry>>(&content) {
                     Ok(mut categories) => {
                         if let Some(fltr) = filter {
                             categories.retain(|c| fltr.contains(&c.category_id));
                         }
+                        let channel_health = if cluster == XtreamCluster::Live {
+                            app_state.channel_health.output_filter(target)
+                        } else {
+                            None
+                        };
+                        if let Some(group) = channel_health.as_ref().and_then(ChannelHealthFilter::unavailable_group) {
+                            categories.push(XtreamCategoryEntry {
+                                category_id: UNAVAILABLE_CATEGORY_ID.to_string(),
+                                category_name: group.to_string(),
+                                parent_id: 0,
+                            });
+                        }
+                        if let Some(visible) =
+                            xtream_get_parental_visible_categories(app_config, target, cluster, user, channel_health)
+                                .await
+                        {
+                            categories.retain(|c| visible.contains(&c.category_id));
+                        }
                         return Some(axum::Json(categories).into_response());
                     }
                     Err(err) => error!("Failed to parse json file {}: {err}", file_path.display()),
@@ -1192,25 +1395,30 @@ macro_rules! skip_flag_optional {
 }
 
 #[allow(clippy::too_many_lines)]
-async fn xtream_player_api(api_req: UserApiRequest, app_state: &Arc<AppState>) -> impl IntoResponse + Send {
+async fn xtream_player_api(
+    api_req: UserApiRequest,
+    app_state: &Arc<AppState>,
+    fingerprint: &Fingerprint,
+) -> impl IntoResponse + Send {
     api_req.log_sanitized("xtream_player_api");
     let auth_status = app_state.app_config.get_auth_error_status();
     let (user, target) = try_option_forbidden!(
-        get_user_target(&api_req, app_state),
+        get_user_target(&api_req, app_state, fingerprint),
         auth_status,
         false,
         format!("Could not find any user for xc player api {}", api_req.username)
     );
+    let country_denied = country_access_denied(app_state, fingerprint, &target, &user).await;
     if !target.has_output(TargetType::Xtream) {
-            return axum::response::Json(get_user_info(&user, app_state).await).into_response();
+            return axum::response::Json(get_user_info(&user, app_state, country_denied).await).into_response();
         }
 
         let action = api_req.action.trim();
         if action.is_empty() {
-            return axum::response::Json(get_user_info(&user, app_state).await).into_response();
+            return axum::response::Json(get_user_info(&user, app_state, country_denied).await).into_response();
         }
 
-        if user.permission_denied(app_state) {
+        if country_denied || user.permission_denied(app_state) || user.outside_access_schedule(app_state) {
             return axum::http::StatusCode::FORBIDDEN.into_response();
         }
 
@@ -1231,7 +1439,7 @@ async fn xtream_player_api(api_req: UserApiRequest, app_state: &Arc<AppState>) -
 
         match action {
             crate::model::XC_ACTION_GET_ACCOUNT_INFO => {
-                return axum::response::Json(get_user_info(&user, app_state).await).into_response();
+                return axum::response::Json(get_user_info(&user, app_state, country_denied).await).into_response();
             }
             crate::model::XC_ACTION_GET_SERIES_INFO => {
                 skip_json_response_if_flag_set!(
@@ -1284,8 +1492,8 @@ async fn xtream_player_api(api_req: UserApiRequest, app_state: &Arc<AppState>) -
         let category_id = api_req.category_id.trim().parse::<u32>().ok();
         // Handle general content actions
         if let Some(response) = xtream_player_api_handle_content_action(
-            &app_state.app_config.config.load(),
-            &target.name,
+            app_state,
+            &target,
             action,
             category_id,
             &user,
@@ -1327,7 +1535,7 @@ async fn xtream_player_api(api_req: UserApiRequest, app_state: &Arc<AppState>) -
                     }
                     Err(err) => {
                         error!("Failed response for xtream target: {} action: {} error: {}", &target.name, action, err);
-                        axum::response::Json(get_user_info(&user, app_state).await).into_response()
+                        axum::response::Json(get_user_info(&user, app_state, country_denied).await).into_response()
                     }
                 }
             }
@@ -1354,17 +1562,19 @@ where
 }
 
 async fn xtream_player_api_get(
+    fingerprint: Fingerprint,
     axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
     axum::extract::Query(api_req): axum::extract::Query<UserApiRequest>,
 ) -> impl IntoResponse + Send {
-    xtream_player_api(api_req, &app_state).await
+    xtream_player_api(api_req, &app_state, &fingerprint).await
 }
 
 async fn xtream_player_api_post(
+    fingerprint: Fingerprint,
     axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
     UserApiRequestQueryOrBody(api_req): UserApiRequestQueryOrBody,
 ) -> impl IntoResponse + Send {
-    xtream_player_api(api_req, &app_state).await
+    xtream_player_api(api_req, &app_state, &fingerprint).await
 }
 
 macro_rules! register_xtream_api {
@@ -1413,6 +1623,9 @@ async fn xtream_player_token_stream(
     axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
     req_headers: HeaderMap,
 ) -> impl IntoResponse + Send {
+    if !verify_access_token(&token, &app_state.app_config.access_token_secret) {
+        return axum::http::StatusCode::FORBIDDEN.into_response();
+    }
     let ctxt = try_result_bad_request!(ApiStreamContext::from_str(cluster.as_str()));
     xtream_player_api_stream_with_token(
         &fingerprint,
@@ -1,4 +1,5 @@
-use crate::model::{AppConfig, HdHomeRunDeviceConfig, HdHomeRunFlags};
+use crate::api::endpoints::dlna_api::get_dlna_location;
+use crate::model::{AppConfig, DlnaConfig, HdHomeRunDeviceConfig, HdHomeRunFlags};
 use log::{error, info, trace};
 use socket2::{Domain, Protocol, Socket, Type};
 use std::{
@@ -11,6 +12,8 @@ use tokio_util::sync::CancellationToken;
 
 const SSDP_GROUP: Ipv4Addr = Ipv4Addr::new(239, 255, 255, 250);
 const SSDP_PORT: u16 = 1900;
+const SSDP_ROOT_DEVICE: &str = "upnp:rootdevice";
+const MEDIA_SERVER_DEVICE_TYPE: &str = "urn:schemas-upnp-org:device:MediaServer:1";
 
 fn create_ssdp_response(device: &HdHomeRunDeviceConfig, server_host: &str) -> String {
     format!(
@@ -25,13 +28,37 @@ fn create_ssdp_response(device: &HdHomeRunDeviceConfig, server_host: &str) -> St
     )
 }
 
+fn create_dlna_ssdp_response(dlna: &DlnaConfig, location: &str, st: &str) -> String {
+    // Answer with the requested search target, `ssdp:all` is answered with the device type.
+    let st = if st == SSDP_ROOT_DEVICE { SSDP_ROOT_DEVICE } else { MEDIA_SERVER_DEVICE_TYPE };
+    format!(
+        "HTTP/1.1 200 OK\r\n\
+        Cache-Control: max-age=1800\r\n\
+        EXT:\r\n\
+        LOCATION: {location}\r\n\
+        SERVER: Tuliprox/1.0 UPnP/1.1 Tuliprox-DLNA/1.0\r\n\
+        ST: {st}\r\n\
+        USN: uuid:{}::{st}\r\n\
+        \r\n",
+        dlna.device_udn
+    )
+}
+
+async fn send_ssdp_response(socket: &UdpSocket, response: &str, remote_addr: SocketAddr, name: &str) {
+    if let Err(e) = socket.send_to(response.as_bytes(), remote_addr).await {
+        error!("Failed to send SSDP response to {remote_addr}: {e}");
+    } else {
+        trace!("Sent SSDP response for device '{name}' to {remote_addr}");
+    }
+}
+
 async fn ssdp_task_loop(socket: UdpSocket, app_config: Arc<AppConfig>, server_host: String) {
     let mut buf = [0; 1024];
     loop {
         let (len, remote_addr) = match socket.recv_from(&mut buf).await {
             Ok(result) => result,
             Err(e) => {
-                error!("HDHomeRun SSDP socket error: {e}");
+                error!("SSDP socket error: {e}");
                 tokio::time::sleep(Duration::from_secs(1)).await; // Prevent spamming logs on error
                 continue;
             }
@@ -52,7 +79,7 @@ async fn ssdp_task_loop(socket: UdpSocket, app_config: Arc<AppConfig>, server_ho
             .unwrap_or_else(|| "ssdp:all".to_string());
         let mx: u64 = req.lines().find_map(|l| l.strip_prefix("mx:").and_then(|v| v.trim().parse().ok())).unwrap_or(1);
         // Normalize to the set we support
-        let supported = ["urn:schemas-upnp-org:device:mediaserver:1", "upnp:rootdevice", "ssdp:all"];
+        let supported = ["urn:schemas-upnp-org:device:mediaserver:1", SSDP_ROOT_DEVICE, "ssdp:all"];
         if !supported.contains(&st.as_str()) {
             continue;
         }
@@ -62,22 +89,26 @@ async fn ssdp_task_loop(socket: UdpSocket, app_config: Arc<AppConfig>, server_ho
             tokio::time::sleep(Duration::from_millis(delay_ms)).await;
         }
 
-        trace!("Received HDHomeRun M-SEARCH from {remote_addr}");
+        trace!("Received SSDP M-SEARCH from {remote_addr}");
         let hdhomerun_guard = app_config.hdhomerun.load();
         if let Some(hd_config) = &*hdhomerun_guard {
             if hd_config.flags.contains(HdHomeRunFlags::Enabled) {
                 for device in &hd_config.devices {
-                    if device.t_enabled {
+                    if device.t_enabled && hd_config.flags.contains(HdHomeRunFlags::SsdpDiscovery) {
                         let response = create_ssdp_response(device, &server_host);
-                        if let Err(e) = socket.send_to(response.as_bytes(), remote_addr).await {
-                            error!("Failed to send SSDP response to {remote_addr}: {e}");
-                        } else {
-                            trace!("Sent SSDP response for device '{}' to {remote_addr}", device.name);
-                        }
+                        send_ssdp_response(&socket, &response, remote_addr, &device.name).await;
                     }
                 }
             }
         }
+
+        let dlna = app_config.config.load().dlna.clone().filter(DlnaConfig::is_ssdp_enabled);
+        if let Some(dlna) = dlna {
+            if let Some(location) = get_dlna_location(&app_config) {
+                let response = create_dlna_ssdp_response(&dlna, &location, &st);
+                send_ssdp_response(&socket, &response, remote_addr, &dlna.friendly_name).await;
+            }
+        }
     }
 }
 
@@ -113,11 +144,11 @@ pub fn spawn_ssdp_discover_task(app_config: Arc<AppConfig>, server_host: String,
         }
         match UdpSocket::from_std(std_udp_socket) {
             Ok(socket) => {
-                info!("HDHomeRun SSDP discovery listener started on {addr}");
+                info!("SSDP discovery listener started on {addr}");
                 tokio::select! {
                     () = ssdp_task_loop(socket, app_config, server_host) => {},
                     () = cancel_token.cancelled() => {
-                        info!("HDHomeRun SSDP discovery listener shutting down.");
+                        info!("SSDP discovery listener shutting down.");
                     }
                 }
             }
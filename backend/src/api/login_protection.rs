@@ -0,0 +1,101 @@
+use crate::{
+    api::{
+        api_utils::trusted_client_ip,
+        model::{AppState, LoginFailure},
+    },
+    auth::Fingerprint,
+    messaging::send_message,
+    model::MessageContent,
+};
+use axum::{
+    extract::{MatchedPath, Request, State},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Router,
+};
+use log::{info, warn};
+use shared::{
+    model::LoginBanDto,
+    utils::{current_time_secs, sanitize_sensitive_info},
+};
+use std::sync::Arc;
+
+/// Returns a credential parameter of a player API request from the query or the `{name}` path segment.
+pub(in crate::api) fn request_param(req: &Request, matched_path: Option<&MatchedPath>, name: &str) -> Option<String> {
+    if let Some(query) = req.uri().query() {
+        if let Some((_, value)) = url::form_urlencoded::parse(query.as_bytes()).find(|(key, _)| key == name) {
+            return Some(value.trim().to_string()).filter(|value| !value.is_empty());
+        }
+    }
+    let template = matched_path?.as_str();
+    let segment = format!("{{{name}}}");
+    let index = template.split('/').position(|part| part == segment)?;
+    req.uri().path().split('/').nth(index).map(ToString::to_string).filter(|value| !value.is_empty())
+}
+
+fn notify_ban(app_state: &AppState, ban: &LoginBanDto) {
+    let subject = if ban.user { "Username" } else { "Ip" };
+    let message = format!(
+        "{subject} {} banned for {}s after repeated failed logins (level {})",
+        sanitize_sensitive_info(&ban.key),
+        ban.until.saturating_sub(ban.banned_at),
+        ban.level
+    );
+    warn!("{message}");
+    let app_config = Arc::clone(&app_state.app_config);
+    let client = app_state.http_client.load_full();
+    tokio::spawn(async move {
+        send_message(&app_config, &client, MessageContent::Info(message)).await;
+    });
+}
+
+/// Rejects banned clients and usernames and counts the failed logins of the player API.
+async fn login_protection(
+    State(app_state): State<Arc<AppState>>,
+    fingerprint: Fingerprint,
+    matched_path: Option<MatchedPath>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let config = app_state.app_config.config.load();
+    let Some(protection) =
+        config.reverse_proxy.as_ref().and_then(|r| r.login_protection.as_ref()).filter(|p| p.enabled).cloned()
+    else {
+        return next.run(req).await;
+    };
+
+    // forwarded headers only count from trusted proxies, an unparsable forwarded entry is banned as is
+    let client_ip = trusted_client_ip(&app_state, &fingerprint);
+    if client_ip.is_some_and(|ip| protection.allowlist_networks.iter().any(|network| network.contains(&ip))) {
+        return next.run(req).await;
+    }
+    let ip_key = client_ip.map_or_else(|| fingerprint.client_ip.clone(), |ip| ip.to_string());
+    let username = request_param(&req, matched_path.as_ref(), "username");
+
+    let now = current_time_secs();
+    let ip_banned = app_state.login_bans.is_banned(&ip_key, false, now).await;
+    let user_banned = match username.as_deref() {
+        Some(username) => app_state.login_bans.is_banned(username, true, now).await,
+        None => false,
+    };
+    if ip_banned || user_banned {
+        info!("Rejected request from banned client {}", sanitize_sensitive_info(&ip_key));
+        return app_state.app_config.get_auth_error_status().into_response();
+    }
+
+    let response = next.run(req).await;
+    if response.extensions().get::<LoginFailure>().is_some() {
+        let bans = app_state.login_bans.record_failure(&protection, Some(&ip_key), username.as_deref(), now).await;
+        for ban in &bans {
+            notify_ban(&app_state, ban);
+        }
+    }
+    response
+}
+
+pub(in crate::api) fn add_login_protection(
+    router: Router<Arc<AppState>>,
+    app_state: &Arc<AppState>,
+) -> Router<Arc<AppState>> {
+    router.layer(axum::middleware::from_fn_with_state(Arc::clone(app_state), login_protection))
+}
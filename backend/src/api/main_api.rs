@@ -4,10 +4,16 @@ use crate::{
         config_watch::exec_config_watch,
         endpoints::{
             custom_video_stream_api::cvs_api_register,
+            dlna_api::{dlna_api_register, get_dlna_location},
             download_api::{resume_download_worker_if_needed, spawn_download_services},
+            enigma2_api::enigma2_api_register,
+            export_api::export_api_register,
             hdhomerun_api::hdhr_api_register,
             hls_api::hls_api_register,
+            ingest_api::ingest_api_register,
+            library_api::library_sidecar_api_register,
             m3u_api::m3u_api_register,
+            multicast_api::multicast_api_register,
             v1_api::v1_api_register,
             web_index::{index_register_with_path, index_register_without_path},
             websocket_api::ws_api_register,
@@ -16,21 +22,28 @@ use crate::{
         },
         hdhomerun_proprietary::spawn_proprietary_tasks,
         hdhomerun_ssdp::spawn_ssdp_discover_task,
+        login_protection::add_login_protection,
         model::{
-            create_cache, create_http_client, create_http_client_no_redirect, exec_provider_dns,
-            ActiveProviderManager, ActiveUserManager, AppState, CancelTokens, ConnectionManager, DownloadQueue,
-            EventManager, EventMessage, HdHomerunAppState, MetadataUpdateManager, PlaylistStorageState,
-            SharedStreamManager, UpdateGuard, exec_qos_aggregation,
+            create_cache, create_http_client, create_vod_cache, create_http_client_no_redirect, exec_provider_dns,
+            ActiveProviderManager, ActiveUserManager, AppState, CancelTokens, ChannelHealthManager, ConnectionManager,
+            DownloadQueue,
+            EventManager, EventMessage, HdHomerunAppState, HlsSegmenterManager, LoginBanManager, MetadataUpdateManager,
+            ParentalUnlockManager, PlaylistStorageState, SharedStreamManager, UpdateGuard, UserDeviceManager,
+            UserQuotaManager, UserRateLimiter, UserTravelManager,
+            exec_channel_health_check, exec_qos_aggregation,
+            exec_stream_multicast, exec_stream_prewarm, exec_user_access_schedule_enforcement,
+            exec_user_quota_accounting,
         },
         panel_api::sync_panel_api_exp_dates_on_boot,
         scheduler::{exec_interner_prune, exec_scheduler},
         serve::serve,
         sys_usage::exec_system_usage,
+        user_rate_limit::add_user_rate_limiter,
     },
-    model::{AppConfig, Config, HdHomeRunFlags, Healthcheck, ProcessTargets, RateLimitConfig},
-    processing::processor::exec_processing,
+    model::{AppConfig, Config, DlnaConfig, HdHomeRunFlags, Healthcheck, ProcessTargets, RateLimitConfig},
+    processing::{geoip::load_geoip_db, processor::exec_processing},
     repository::{get_geoip_path, load_playlists_into_memory_cache},
-    utils::{exec_file_lock_prune, get_default_web_root_path, GeoIp},
+    utils::{exec_file_lock_prune, get_default_web_root_path},
     VERSION,
 };
 use arc_swap::{ArcSwap, ArcSwapOption};
@@ -261,12 +274,16 @@ async fn create_shared_data(
 ) -> Result<(AppState, mpsc::Receiver<Arc<ProcessTargets>>), TuliproxError> {
     let config = app_config.config.load();
     let downloads_state_file = std::path::PathBuf::from(&config.storage_dir).join("downloads_state.json");
+    let user_quota_state_file = std::path::PathBuf::from(&config.storage_dir).join("user_quota.json");
+    let user_devices_state_file = std::path::PathBuf::from(&config.storage_dir).join("user_devices.json");
+    let user_travel_state_file = std::path::PathBuf::from(&config.storage_dir).join("user_travel.json");
+    let login_bans_state_file = std::path::PathBuf::from(&config.storage_dir).join("login_bans.json");
+    let channel_health_state_file = std::path::PathBuf::from(&config.storage_dir).join("channel_health.json");
 
     let use_geoip = config.is_geoip_enabled();
     let geoip = if use_geoip {
         let path = get_geoip_path(&config.storage_dir);
-        let _file_lock = app_config.file_locks.read_lock(&path).await;
-        match GeoIp::load(&path) {
+        match load_geoip_db(app_config, &path).await {
             Ok(db) => {
                 info!("GeoIp db loaded");
                 Arc::new(ArcSwapOption::from(Some(Arc::new(db))))
@@ -281,6 +298,7 @@ async fn create_shared_data(
     };
 
     let cache = create_cache(&config);
+    let vod_cache = create_vod_cache(&config);
     let event_manager = Arc::new(EventManager::new());
     let active_provider = Arc::new(ActiveProviderManager::new(app_config, &event_manager));
     let shared_stream_manager = Arc::new(SharedStreamManager::new(Arc::clone(&active_provider)));
@@ -312,8 +330,17 @@ async fn create_shared_data(
             http_client: Arc::new(ArcSwap::from_pointee(client)),
             http_client_no_redirect: Arc::new(ArcSwap::from_pointee(client_no_redirect)),
             downloads: Arc::new(DownloadQueue::new_with_state_file(Some(downloads_state_file))),
+            user_quota: Arc::new(UserQuotaManager::new_with_state_file(Some(user_quota_state_file))),
+            parental_unlocks: Arc::new(ParentalUnlockManager::default()),
+            user_devices: Arc::new(UserDeviceManager::new_with_state_file(Some(user_devices_state_file))),
+            user_travel: Arc::new(UserTravelManager::new_with_state_file(Some(user_travel_state_file))),
+            channel_health: Arc::new(ChannelHealthManager::new_with_state_file(Some(channel_health_state_file))),
+            login_bans: Arc::new(LoginBanManager::new_with_state_file(Some(login_bans_state_file))),
+            user_rate_limits: Arc::new(UserRateLimiter::default()),
             cache: Arc::new(ArcSwapOption::from(cache)),
+            vod_cache: Arc::new(ArcSwapOption::from(vod_cache)),
             shared_stream_manager,
+            hls_segmenters: Arc::new(HlsSegmenterManager::default()),
             active_users,
             active_provider,
             connection_manager,
@@ -327,6 +354,21 @@ async fn create_shared_data(
         };
 
     recover_persisted_downloads_state_for_startup(&app_state.downloads).await;
+    if let Err(err) = app_state.user_quota.load_from_disk().await {
+        error!("Failed to load user quota usage: {err}");
+    }
+    if let Err(err) = app_state.user_devices.load_from_disk().await {
+        error!("Failed to load user devices: {err}");
+    }
+    if let Err(err) = app_state.user_travel.load_from_disk().await {
+        error!("Failed to load user travel days: {err}");
+    }
+    if let Err(err) = app_state.login_bans.load_from_disk().await {
+        error!("Failed to load login bans: {err}");
+    }
+    if let Err(err) = app_state.channel_health.load_from_disk().await {
+        error!("Failed to load channel health: {err}");
+    }
 
     Ok((app_state, manual_update_rx))
 }
@@ -363,7 +405,27 @@ async fn cancel_all_service_tokens(app_state: &Arc<AppState>) {
     cancel_tokens.provider_dns.cancel();
     cancel_tokens.qos_aggregation.cancel();
     cancel_tokens.downloads.cancel();
+    cancel_tokens.user_quota.cancel();
+    cancel_tokens.access_schedule.cancel();
+    cancel_tokens.stream_prewarm.cancel();
+    cancel_tokens.channel_health.cancel();
+    cancel_tokens.multicast.cancel();
     app_state.active_users.shutdown();
+    if let Err(err) = app_state.user_quota.persist_to_disk().await {
+        error!("Failed to persist user quota usage: {err}");
+    }
+    if let Err(err) = app_state.user_devices.persist_to_disk().await {
+        error!("Failed to persist user devices: {err}");
+    }
+    if let Err(err) = app_state.user_travel.persist_to_disk().await {
+        error!("Failed to persist user travel days: {err}");
+    }
+    if let Err(err) = app_state.login_bans.persist_to_disk().await {
+        error!("Failed to persist login bans: {err}");
+    }
+    if let Err(err) = app_state.channel_health.persist_to_disk().await {
+        error!("Failed to persist channel health: {err}");
+    }
     // Use the manager's shutdown() rather than cancelling the token directly so
     // the is_shutdown flag is set and workers do not attempt to restart after cancellation.
     app_state.metadata_manager.shutdown();
@@ -554,7 +616,6 @@ pub(in crate::api) fn start_hdhomerun(
         if hdhomerun.flags.contains(HdHomeRunFlags::Enabled) {
             if hdhomerun.flags.contains(HdHomeRunFlags::SsdpDiscovery) {
                 info!("HDHomeRun SSDP discovery is enabled.");
-                spawn_ssdp_discover_task(Arc::clone(app_config), host.clone(), cancel_token.clone());
             } else {
                 info!("HDHomeRun SSDP discovery is disabled.");
             }
@@ -599,6 +660,25 @@ pub(in crate::api) fn start_hdhomerun(
             }
         }
     }
+    start_ssdp_discovery(app_config, infos, cancel_token);
+}
+
+/// The SSDP responder is shared by `HDHomeRun` and DLNA and runs with the `HDHomeRun` services.
+fn start_ssdp_discovery(app_config: &Arc<AppConfig>, infos: &mut Vec<String>, cancel_token: &CancellationToken) {
+    let hdhomerun_ssdp = app_config.hdhomerun.load().as_ref().is_some_and(|hdhomerun| {
+        hdhomerun.flags.contains(HdHomeRunFlags::Enabled) && hdhomerun.flags.contains(HdHomeRunFlags::SsdpDiscovery)
+    });
+    let config = app_config.config.load();
+    let dlna_ssdp = config.dlna.as_ref().is_some_and(DlnaConfig::is_ssdp_enabled);
+    if let Some(location) = get_dlna_location(app_config) {
+        infos.push(format!("DLNA Server running: {location}"));
+        if !dlna_ssdp {
+            info!("DLNA SSDP discovery is disabled.");
+        }
+    }
+    if hdhomerun_ssdp || dlna_ssdp {
+        spawn_ssdp_discover_task(Arc::clone(app_config), config.api.host.clone(), cancel_token.clone());
+    }
 }
 
 #[allow(clippy::too_many_lines)]
@@ -671,6 +751,11 @@ pub async fn start_server(app_config: Arc<AppConfig>, targets: Arc<ProcessTarget
     exec_config_watch(&app_state, &cancel_token_file_watch);
     exec_provider_dns(&app_state, &cancel_token_provider_dns);
     exec_qos_aggregation(&app_state, &cancel_token_qos_aggregation);
+    exec_user_quota_accounting(&app_state, &app_state.cancel_tokens.load().user_quota);
+    exec_user_access_schedule_enforcement(&app_state, &app_state.cancel_tokens.load().access_schedule);
+    exec_stream_prewarm(&app_state, &app_state.cancel_tokens.load().stream_prewarm);
+    exec_stream_multicast(&app_state, &app_state.cancel_tokens.load().multicast);
+    exec_channel_health_check(&app_state, &app_state.cancel_tokens.load().channel_health);
 
     let web_auth_enabled = is_web_auth_enabled(&cfg, web_ui_enabled);
 
@@ -708,9 +793,17 @@ pub async fn start_server(app_config: Arc<AppConfig>, targets: Arc<ProcessTarget
     let mut api_router = axum::Router::new()
         .merge(xtream_api_register())
         .merge(m3u_api_register())
+        .merge(enigma2_api_register())
+        .merge(dlna_api_register())
+        .merge(export_api_register())
+        .merge(multicast_api_register())
         .merge(xmltv_api_register())
         .merge(hls_api_register())
+        .merge(ingest_api_register())
+        .merge(library_sidecar_api_register())
         .merge(cvs_api_register());
+    api_router = add_login_protection(api_router, &shared_data);
+    api_router = add_user_rate_limiter(api_router, &shared_data);
     if let Some(rate_limiter) = cfg.reverse_proxy.as_ref().and_then(|r| r.rate_limit.clone()) {
         api_router = add_rate_limiter(api_router, &rate_limiter);
     }
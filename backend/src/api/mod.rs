@@ -9,7 +9,9 @@ mod endpoints;
 pub(crate) mod hdhomerun_proprietary;
 pub(crate) mod hdhomerun_ssdp;
 mod library_scan;
+mod login_protection;
 pub mod main_api;
 mod scheduler;
 mod serve;
 mod sys_usage;
+mod user_rate_limit;
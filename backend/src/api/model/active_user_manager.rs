@@ -251,6 +251,7 @@ pub struct ActiveUserConnectionParams<'a> {
     pub stream_channel: &'a StreamChannel,
     pub user_agent: Cow<'a, str>,
     pub session_token: Option<&'a str>,
+    pub throttle_kbps: u64,
 }
 
 pub struct CreateUserSessionParams<'a> {
@@ -813,6 +814,7 @@ impl ActiveUserManager {
             stream_channel,
             user_agent,
             session_token,
+            throttle_kbps,
         } = update;
         let stream_info = {
             let mut user_connections = self.connections.write().await;
@@ -861,6 +863,7 @@ impl ActiveUserManager {
                     stream_info.channel = stream_channel.clone();
                     stream_info.provider = provider.to_string();
                     stream_info.user_agent.clone_from(&user_agent_string);
+                    stream_info.throttle_kbps = throttle_kbps;
                     if preserve_started_at {
                         let now = current_time_secs();
                         if utc_day_from_secs(stream_info.ts) != utc_day_from_secs(now) {
@@ -888,7 +891,7 @@ impl ActiveUserManager {
             } else {
                 let country_code = self.lookup_country(&fingerprint.client_ip);
 
-                let stream_info = StreamInfo::new(
+                let mut stream_info = StreamInfo::new(
                     uid,
                     meter_uid,
                     username,
@@ -900,6 +903,7 @@ impl ActiveUserManager {
                     country_code,
                     session_token,
                 );
+                stream_info.throttle_kbps = throttle_kbps;
 
                 let tracked_socket_count = user_connections.key_by_addr.len();
 
@@ -1215,6 +1219,16 @@ impl ActiveUserManager {
         streams
     }
 
+    pub async fn user_streams(&self, username: &str) -> Vec<StreamInfo> {
+        self.gc();
+        self.connections
+            .read()
+            .await
+            .by_key
+            .get(username)
+            .map_or_else(Vec::new, |connection_data| connection_data.streams.clone())
+    }
+
     fn log_connection_added(
         username: &str,
         addr: &SocketAddr,
@@ -1526,6 +1540,7 @@ mod tests {
             shared_joined_existing: None,
             shared_stream_id: None,
             technical: None,
+            alternate_input: None,
         }
     }
 
@@ -1557,6 +1572,7 @@ mod tests {
                 stream_channel: &test_channel(1001),
                 user_agent: Cow::Borrowed("ua"),
                 session_token: Some("tok-1"),
+                throttle_kbps: 0,
             })
             .await;
         assert!(first.is_some());
@@ -1581,6 +1597,7 @@ mod tests {
                 stream_channel: &test_channel(1002),
                 user_agent: Cow::Borrowed("ua"),
                 session_token: Some("tok-2"),
+                throttle_kbps: 0,
             })
             .await;
         assert!(second.is_some());
@@ -1636,6 +1653,7 @@ mod tests {
                 stream_channel: &test_channel(2001),
                 user_agent: Cow::Borrowed("ua"),
                 session_token: Some("tok-hls"),
+                throttle_kbps: 0,
             })
             .await;
 
@@ -1660,6 +1678,7 @@ mod tests {
                 stream_channel: &test_channel(2001),
                 user_agent: Cow::Borrowed("ua"),
                 session_token: Some("tok-hls"),
+                throttle_kbps: 0,
             })
             .await;
 
@@ -1712,6 +1731,7 @@ mod tests {
                 stream_channel: &test_channel(2002),
                 user_agent: Cow::Borrowed("ua"),
                 session_token: Some("tok-prio"),
+                throttle_kbps: 0,
             })
             .await;
         manager
@@ -1729,6 +1749,7 @@ mod tests {
                 stream_channel: &test_channel(2002),
                 user_agent: Cow::Borrowed("ua"),
                 session_token: Some("tok-prio"),
+                throttle_kbps: 0,
             })
             .await;
 
@@ -1763,6 +1784,7 @@ mod tests {
                 stream_channel: &test_channel(3001),
                 user_agent: Cow::Borrowed("ua"),
                 session_token: Some("tok-meter"),
+                throttle_kbps: 0,
             })
             .await
             .expect("initial stream should register");
@@ -1784,6 +1806,7 @@ mod tests {
                 stream_channel: &test_channel(3002),
                 user_agent: Cow::Borrowed("ua"),
                 session_token: Some("tok-meter"),
+                throttle_kbps: 0,
             })
             .await
             .expect("reused stream should register");
@@ -1845,6 +1868,7 @@ mod tests {
                 },
                 user_agent: Cow::Borrowed("ua"),
                 session_token: Some("tok-hls"),
+                throttle_kbps: 0,
             })
             .await
             .expect("initial adaptive session should register");
@@ -1879,6 +1903,7 @@ mod tests {
                 },
                 user_agent: Cow::Borrowed("ua-2"),
                 session_token: Some("tok-hls"),
+                throttle_kbps: 0,
             })
             .await
             .expect("adaptive session should reuse logical stream");
@@ -1938,6 +1963,7 @@ mod tests {
                 },
                 user_agent: Cow::Borrowed("ua"),
                 session_token: Some("tok-hls"),
+                throttle_kbps: 0,
             })
             .await;
 
@@ -1991,6 +2017,7 @@ mod tests {
                 },
                 user_agent: Cow::Borrowed("ua"),
                 session_token: Some("tok-expire"),
+                throttle_kbps: 0,
             })
             .await;
         let released = manager.release_connection(&addr).await;
@@ -2066,6 +2093,7 @@ mod tests {
                 },
                 user_agent: Cow::Borrowed("ua-normal"),
                 session_token: Some("tok-expire-normal"),
+                throttle_kbps: 0,
             })
             .await;
         manager
@@ -2083,6 +2111,7 @@ mod tests {
                 stream_channel: &test_channel(6003),
                 user_agent: Cow::Borrowed("ua-soft"),
                 session_token: None,
+                throttle_kbps: 0,
             })
             .await;
 
@@ -2156,6 +2185,7 @@ mod tests {
                 },
                 user_agent: Cow::Borrowed("ua-a"),
                 session_token: Some("tok-reuse"),
+                throttle_kbps: 0,
             })
             .await;
         let released = manager.release_connection(&addr_a).await;
@@ -2180,6 +2210,7 @@ mod tests {
                 },
                 user_agent: Cow::Borrowed("ua-b"),
                 session_token: Some("tok-reuse"),
+                throttle_kbps: 0,
             })
             .await;
         let released = manager.release_connection(&addr_b).await;
@@ -2239,6 +2270,7 @@ mod tests {
                 },
                 user_agent: Cow::Borrowed("ua"),
                 session_token: Some("tok-event"),
+                throttle_kbps: 0,
             })
             .await;
         let _ = events.try_recv();
@@ -2279,6 +2311,7 @@ mod tests {
                 },
                 user_agent: Cow::Borrowed("ua"),
                 session_token: Some("missing-session"),
+                throttle_kbps: 0,
             })
             .await;
 
@@ -2331,6 +2364,7 @@ mod tests {
                 },
                 user_agent: Cow::Borrowed("ua"),
                 session_token: Some("tok-reschedule"),
+                throttle_kbps: 0,
             })
             .await;
         let released = manager.release_connection(&addr).await;
@@ -2413,6 +2447,7 @@ mod tests {
                 },
                 user_agent: Cow::Borrowed("ua"),
                 session_token: Some("tok-stale"),
+                throttle_kbps: 0,
             })
             .await;
         let released = manager.release_connection(&addr).await;
@@ -2484,6 +2519,7 @@ mod tests {
                 },
                 user_agent: Cow::Borrowed("ua"),
                 session_token: Some("tok-full-channel"),
+                throttle_kbps: 0,
             })
             .await;
         let released = manager.release_connection(&addr).await;
@@ -2568,6 +2604,7 @@ mod tests {
                 },
                 user_agent: Cow::Borrowed("ua"),
                 session_token: Some("tok-rollover"),
+                throttle_kbps: 0,
             })
             .await
             .expect("initial adaptive session should register");
@@ -2608,6 +2645,7 @@ mod tests {
                 },
                 user_agent: Cow::Borrowed("ua"),
                 session_token: Some("tok-rollover"),
+                throttle_kbps: 0,
             })
             .await
             .expect("adaptive session should reconnect");
@@ -2674,6 +2712,7 @@ mod tests {
                 stream_channel: &test_channel(9201),
                 user_agent: Cow::Borrowed("ua"),
                 session_token: None,
+                throttle_kbps: 0,
             })
             .await
             .expect("stale stream should register");
@@ -2692,6 +2731,7 @@ mod tests {
                 stream_channel: &test_channel(9202),
                 user_agent: Cow::Borrowed("ua"),
                 session_token: None,
+                throttle_kbps: 0,
             })
             .await
             .expect("fresh stream should register");
@@ -2809,6 +2849,7 @@ mod tests {
                 },
                 user_agent: Cow::Borrowed("ua"),
                 session_token: Some("tok-move"),
+                throttle_kbps: 0,
             })
             .await
             .expect("initial movie stream should register");
@@ -2855,6 +2896,7 @@ mod tests {
                 stream_channel: &test_channel(9001),
                 user_agent: Cow::Borrowed("ua"),
                 session_token: None,
+                throttle_kbps: 0,
             })
             .await
             .expect("ts stream should register");
@@ -6,8 +6,9 @@ use crate::{
         model::{
             qos_aggregation_manager::exec_qos_aggregation,
             metadata_update_manager::MetadataUpdateManager, ActiveProviderManager, ActiveUserManager,
-            ConnectionManager, DownloadQueue, EventManager, PlaylistStorage, PlaylistStorageState, SharedStreamManager,
-            UpdateGuard,
+            ChannelHealthManager, ConnectionManager, DownloadQueue, EventManager, HlsSegmenterManager, PlaylistStorage,
+            PlaylistStorageState, SharedStreamManager, LoginBanManager, ParentalUnlockManager, UpdateGuard,
+            UserDeviceManager, UserQuotaManager, UserRateLimiter, UserTravelManager, VodCache,
         },
         scheduler::exec_scheduler,
         model::active_user_manager::ConnectionAdmission,
@@ -16,6 +17,7 @@ use crate::{
         AppConfig, Config, ConfigProvider, ConfigTarget, GracePeriodOptions, HdHomeRunConfig, HdHomeRunDeviceConfig,
         ProcessTargets, ReverseProxyDisabledHeaderConfig, ScheduleConfig, SourcesConfig,
     },
+    processing::geoip::load_geoip_db,
     repository::{get_geoip_path, load_target_into_memory_cache},
     utils::{
         request::{create_client, create_client_with_redirect},
@@ -185,6 +187,11 @@ fn cancel_services(app_state: &Arc<AppState>, changes: &UpdateChanges) {
         metadata,
         qos_aggregation,
         downloads,
+        user_quota: cancel_tokens.user_quota.clone(),
+        access_schedule: cancel_tokens.access_schedule.clone(),
+        stream_prewarm: cancel_tokens.stream_prewarm.clone(),
+        channel_health: cancel_tokens.channel_health.clone(),
+        multicast: cancel_tokens.multicast.clone(),
     };
 
     app_state.cancel_tokens.store(Arc::new(tokens));
@@ -351,6 +358,20 @@ pub fn create_cache(config: &Config) -> Option<Arc<Mutex<LRUResourceCache>>> {
     None
 }
 
+pub fn create_vod_cache(config: &Config) -> Option<Arc<VodCache>> {
+    let vod_cache_config = config.reverse_proxy.as_ref().and_then(|r| r.vod_cache.as_ref()).filter(|c| c.enabled)?;
+    let vod_cache = Arc::new(VodCache::new(vod_cache_config));
+    let cache_scanner = Arc::clone(&vod_cache);
+    tokio::spawn(async move {
+        match task::spawn_blocking(move || cache_scanner.scan()).await {
+            Ok(Ok(())) => {}
+            Ok(Err(err)) => error!("Failed to scan vod cache {err}"),
+            Err(err) => error!("Failed to scan vod cache {err}"),
+        }
+    });
+    Some(vod_cache)
+}
+
 pub struct CancelTokens {
     pub(crate) scheduler: CancellationToken,
     pub(crate) hdhomerun: CancellationToken,
@@ -359,6 +380,11 @@ pub struct CancelTokens {
     pub(crate) metadata: CancellationToken,
     pub(crate) qos_aggregation: CancellationToken,
     pub(crate) downloads: CancellationToken,
+    pub(crate) user_quota: CancellationToken,
+    pub(crate) access_schedule: CancellationToken,
+    pub(crate) stream_prewarm: CancellationToken,
+    pub(crate) channel_health: CancellationToken,
+    pub(crate) multicast: CancellationToken,
 }
 impl Default for CancelTokens {
     fn default() -> Self {
@@ -370,6 +396,11 @@ impl Default for CancelTokens {
             metadata: CancellationToken::new(),
             qos_aggregation: CancellationToken::new(),
             downloads: CancellationToken::new(),
+            user_quota: CancellationToken::new(),
+            access_schedule: CancellationToken::new(),
+            stream_prewarm: CancellationToken::new(),
+            channel_health: CancellationToken::new(),
+            multicast: CancellationToken::new(),
         }
     }
 }
@@ -395,8 +426,17 @@ pub struct AppState {
     pub http_client: Arc<ArcSwap<Client>>,
     pub http_client_no_redirect: Arc<ArcSwap<Client>>,
     pub downloads: Arc<DownloadQueue>,
+    pub user_quota: Arc<UserQuotaManager>,
+    pub parental_unlocks: Arc<ParentalUnlockManager>,
+    pub user_devices: Arc<UserDeviceManager>,
+    pub user_travel: Arc<UserTravelManager>,
+    pub channel_health: Arc<ChannelHealthManager>,
+    pub login_bans: Arc<LoginBanManager>,
+    pub user_rate_limits: Arc<UserRateLimiter>,
     pub cache: Arc<ArcSwapOption<Mutex<LRUResourceCache>>>,
+    pub vod_cache: Arc<ArcSwapOption<VodCache>>,
     pub shared_stream_manager: Arc<SharedStreamManager>,
+    pub hls_segmenters: Arc<HlsSegmenterManager>,
     pub active_users: Arc<ActiveUserManager>,
     pub active_provider: Arc<ActiveProviderManager>,
     pub connection_manager: Arc<ConnectionManager>,
@@ -432,8 +472,7 @@ impl AppState {
         if geoip_reload_needed {
             let new_geoip = if use_geoip {
                 let path = get_geoip_path(&storage_dir);
-                let _file_lock = self.app_config.file_locks.read_lock(&path).await;
-                GeoIp::load(&path).ok().map(Arc::new)
+                load_geoip_db(&self.app_config, &path).await.ok().map(Arc::new)
             } else {
                 None
             };
@@ -470,6 +509,18 @@ impl AppState {
             let cache = create_cache(&config);
             self.cache.store(cache);
         }
+
+        // vod cache
+        let vod_cache_config =
+            config.reverse_proxy.as_ref().and_then(|r| r.vod_cache.as_ref()).filter(|c| c.enabled);
+        match (self.vod_cache.load().as_ref(), vod_cache_config) {
+            (Some(vod_cache), Some(vod_cache_config))
+                if vod_cache.directory() == std::path::Path::new(&vod_cache_config.directory) =>
+            {
+                vod_cache.update_config(vod_cache_config);
+            }
+            _ => self.vod_cache.store(create_vod_cache(&config)),
+        }
         Ok(())
     }
 
@@ -543,7 +594,8 @@ impl AppState {
         let old_library_enabled = old_config.library.as_ref().is_some_and(|library| library.enabled);
         let changed_library_enabled = library_enabled != old_library_enabled;
         let changed_hdhomerun =
-            change_detect!(hdhomerun_changed, old_config.hdhomerun.as_ref(), config.hdhomerun.as_ref());
+            change_detect!(hdhomerun_changed, old_config.hdhomerun.as_ref(), config.hdhomerun.as_ref())
+                || old_config.dlna != config.dlna;
         let changed_file_watch =
             change_detect!(string_changed, old_config.mapping_path.as_ref(), config.mapping_path.as_ref())
                 || change_detect!(string_changed, old_config.template_path.as_ref(), config.template_path.as_ref());
@@ -0,0 +1,469 @@
+use crate::{
+    api::{api_utils::resolve_live_channel, model::AppState},
+    model::{AppConfig, ConfigTarget},
+    utils::{
+        debug_if_enabled,
+        ffmpeg::{FfmpegExecutor, ProbeUrlOutcome},
+        file_exists_async,
+    },
+};
+use arc_swap::ArcSwap;
+use log::{error, info};
+use serde::{Deserialize, Serialize};
+use shared::{
+    model::{ChannelHealthConfigDto, ChannelHealthMode, StreamChannel},
+    utils::current_time_secs,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tokio::{fs, sync::RwLock};
+use tokio_util::sync::CancellationToken;
+
+/// Xtream category of the dead channels when they are grouped.
+pub const UNAVAILABLE_CATEGORY_ID: u32 = u32::MAX;
+
+const CHANNEL_HEALTH_CHECK_INTERVAL_SECS: u64 = 60;
+/// Dead channels probed per check, the remaining ones are probed with the next check.
+const MAX_PROBES_PER_CHECK: usize = 5;
+/// Longest pause between two probes of a dead channel.
+const MAX_REPROBE_SECS: u64 = 86_400;
+/// Recovered channels are kept in the report for a week.
+const HEALTHY_RETENTION_SECS: u64 = 7 * 86_400;
+// Probes run with the lowest priority, every viewer preempts them.
+const PROBE_PRIORITY: i8 = i8::MAX;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChannelHealthState {
+    Healthy,
+    /// Failed, but less than `dead_after` times in a row.
+    Degraded,
+    /// Taken out of the outputs until a probe or a connect succeeds.
+    Dead,
+}
+
+/// Health of a live channel of a target, channels are tracked after their first failure.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChannelHealthRecord {
+    pub target_id: u16,
+    pub virtual_id: u32,
+    pub input_name: String,
+    pub provider_id: u32,
+    pub title: String,
+    pub state: ChannelHealthState,
+    /// Consecutive failed connects and probes.
+    pub failures: u16,
+    pub last_failure_ts: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_success_ts: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_probe_ts: Option<u64>,
+    /// Failed probes since the channel died, each one doubles the pause until the next probe.
+    #[serde(default)]
+    pub probe_failures: u16,
+}
+
+impl ChannelHealthRecord {
+    fn new(channel: &StreamChannel) -> Self {
+        Self {
+            target_id: channel.target_id,
+            virtual_id: channel.virtual_id,
+            input_name: channel.input_name.to_string(),
+            provider_id: channel.provider_id,
+            title: channel.title.to_string(),
+            state: ChannelHealthState::Healthy,
+            failures: 0,
+            last_failure_ts: 0,
+            last_success_ts: None,
+            next_probe_ts: None,
+            probe_failures: 0,
+        }
+    }
+
+    fn from_probe(target_id: u16, virtual_id: u32, input_name: &str, provider_id: u32) -> Self {
+        Self {
+            target_id,
+            virtual_id,
+            input_name: input_name.to_string(),
+            provider_id,
+            title: String::new(),
+            state: ChannelHealthState::Healthy,
+            failures: 0,
+            last_failure_ts: 0,
+            last_success_ts: None,
+            next_probe_ts: None,
+            probe_failures: 0,
+        }
+    }
+
+    fn apply_failure(&mut self, config: &ChannelHealthConfigDto, now: u64) {
+        self.failures = self.failures.saturating_add(1);
+        self.last_failure_ts = now;
+        if self.failures < config.dead_after {
+            self.state = ChannelHealthState::Degraded;
+            return;
+        }
+        if self.state == ChannelHealthState::Dead {
+            self.probe_failures = self.probe_failures.saturating_add(1);
+        }
+        self.state = ChannelHealthState::Dead;
+        self.next_probe_ts = Some(now + reprobe_delay_secs(config, self.probe_failures));
+    }
+
+    fn apply_success(&mut self, now: u64) {
+        self.state = ChannelHealthState::Healthy;
+        self.failures = 0;
+        self.last_success_ts = Some(now);
+        self.next_probe_ts = None;
+        self.probe_failures = 0;
+    }
+
+    /// Lists a dead channel again without a probe, one more failure takes it out again.
+    fn readmit(&mut self, config: &ChannelHealthConfigDto) {
+        self.state = ChannelHealthState::Degraded;
+        self.failures = config.dead_after.saturating_sub(1);
+        self.next_probe_ts = None;
+        self.probe_failures = self.probe_failures.saturating_add(1);
+    }
+}
+
+fn reprobe_delay_secs(config: &ChannelHealthConfigDto, probe_failures: u16) -> u64 {
+    let base = u64::from(config.reprobe_mins) * 60;
+    base.saturating_mul(1 << probe_failures.min(16)).min(MAX_REPROBE_SECS)
+}
+
+fn channel_health_config(app_config: &AppConfig, target_id: u16) -> Option<ChannelHealthConfigDto> {
+    app_config.get_target_by_id(target_id)?.options.as_ref()?.channel_health.clone()
+}
+
+/// Dead channels of a target for the playlist outputs.
+#[derive(Debug, Clone)]
+pub struct ChannelHealthFilter {
+    dead: Arc<HashSet<u32>>,
+    group: Option<String>,
+}
+
+impl ChannelHealthFilter {
+    pub fn is_dead(&self, virtual_id: u32) -> bool { self.dead.contains(&virtual_id) }
+
+    /// Group of the dead channels, `None` when they are hidden.
+    pub fn unavailable_group(&self) -> Option<&str> { self.group.as_deref() }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PersistedChannelHealth {
+    channels: Vec<ChannelHealthRecord>,
+}
+
+/// Tracks the health of live channels from connect results and probes.
+pub struct ChannelHealthManager {
+    state_file: Option<PathBuf>,
+    channels: RwLock<HashMap<(u16, u32), ChannelHealthRecord>>,
+    dead_channels: ArcSwap<HashMap<u16, Arc<HashSet<u32>>>>,
+    dirty: AtomicBool,
+}
+
+impl ChannelHealthManager {
+    pub fn new_with_state_file(state_file: Option<PathBuf>) -> Self {
+        Self {
+            state_file,
+            channels: RwLock::new(HashMap::new()),
+            dead_channels: ArcSwap::from_pointee(HashMap::new()),
+            dirty: AtomicBool::new(false),
+        }
+    }
+
+    pub async fn load_from_disk(&self) -> std::io::Result<()> {
+        let Some(state_file) = self.state_file.as_ref() else {
+            return Ok(());
+        };
+        if !file_exists_async(state_file).await {
+            return Ok(());
+        }
+        let content = fs::read_to_string(state_file).await?;
+        let persisted: PersistedChannelHealth =
+            serde_json::from_str(&content).map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+        let mut channels = self.channels.write().await;
+        *channels = persisted
+            .channels
+            .into_iter()
+            .map(|record| ((record.target_id, record.virtual_id), record))
+            .collect();
+        self.update_dead_channels(&channels);
+        Ok(())
+    }
+
+    pub async fn persist_to_disk(&self) -> std::io::Result<()> {
+        let Some(state_file) = self.state_file.as_ref() else {
+            return Ok(());
+        };
+        if !self.dirty.swap(false, Ordering::AcqRel) {
+            return Ok(());
+        }
+        let content = {
+            let channels = self.channels.read().await.values().cloned().collect();
+            serde_json::to_vec(&PersistedChannelHealth { channels }).map_err(std::io::Error::other)?
+        };
+        if let Some(parent) = state_file.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        let tmp_file = state_file.with_extension("json.tmp");
+        fs::write(&tmp_file, content).await?;
+        fs::rename(&tmp_file, state_file).await
+    }
+
+    fn update_dead_channels(&self, channels: &HashMap<(u16, u32), ChannelHealthRecord>) {
+        let mut dead: HashMap<u16, HashSet<u32>> = HashMap::new();
+        for record in channels.values().filter(|record| record.state == ChannelHealthState::Dead) {
+            dead.entry(record.target_id).or_default().insert(record.virtual_id);
+        }
+        self.dead_channels.store(Arc::new(dead.into_iter().map(|(id, set)| (id, Arc::new(set))).collect()));
+        self.dirty.store(true, Ordering::Release);
+    }
+
+    /// Counts a failed connect or probe of a live channel of a target with `channel_health`.
+    pub async fn record_failure(&self, app_config: &AppConfig, channel: &StreamChannel) {
+        if !channel.item_type.is_live() {
+            return;
+        }
+        self.apply_failure(app_config, (channel.target_id, channel.virtual_id), || ChannelHealthRecord::new(channel))
+            .await;
+    }
+
+    async fn apply_failure<F>(&self, app_config: &AppConfig, key: (u16, u32), new_record: F)
+    where
+        F: FnOnce() -> ChannelHealthRecord,
+    {
+        let Some(config) = channel_health_config(app_config, key.0) else {
+            return;
+        };
+        let mut channels = self.channels.write().await;
+        let record = channels.entry(key).or_insert_with(new_record);
+        let was_dead = record.state == ChannelHealthState::Dead;
+        record.apply_failure(&config, current_time_secs());
+        if !was_dead && record.state == ChannelHealthState::Dead {
+            info!("Channel {} ({}) of target {} is dead", record.title, record.virtual_id, record.target_id);
+        }
+        self.update_dead_channels(&channels);
+    }
+
+    /// Restores a tracked channel after a successful connect or probe.
+    pub async fn record_success(&self, target_id: u16, virtual_id: u32) {
+        let mut channels = self.channels.write().await;
+        let Some(record) = channels.get_mut(&(target_id, virtual_id)) else {
+            return;
+        };
+        if record.state == ChannelHealthState::Healthy {
+            return;
+        }
+        if record.state == ChannelHealthState::Dead {
+            info!("Channel {} ({}) of target {} is alive again", record.title, virtual_id, target_id);
+        }
+        record.apply_success(current_time_secs());
+        self.update_dead_channels(&channels);
+    }
+
+    /// Applies the result of a live probe of an input to all tracked channels of the provider stream.
+    pub async fn record_probe_result(&self, app_config: &AppConfig, input_name: &str, provider_id: u32, success: bool) {
+        let keys: Vec<(u16, u32)> = self
+            .channels
+            .read()
+            .await
+            .values()
+            .filter(|record| record.provider_id == provider_id && record.input_name == input_name)
+            .map(|record| (record.target_id, record.virtual_id))
+            .collect();
+        for (target_id, virtual_id) in keys {
+            if success {
+                self.record_success(target_id, virtual_id).await;
+            } else {
+                self.apply_failure(app_config, (target_id, virtual_id), || {
+                    ChannelHealthRecord::from_probe(target_id, virtual_id, input_name, provider_id)
+                })
+                .await;
+            }
+        }
+    }
+
+    /// Returns the dead channels of the target when its outputs take them out.
+    pub fn output_filter(&self, target: &ConfigTarget) -> Option<ChannelHealthFilter> {
+        let config = target.options.as_ref()?.channel_health.as_ref()?;
+        let dead = self.dead_channels.load().get(&target.id).cloned()?;
+        let group = (config.mode == ChannelHealthMode::Group).then(|| config.group.clone());
+        Some(ChannelHealthFilter { dead, group })
+    }
+
+    pub async fn report(&self) -> Vec<ChannelHealthRecord> {
+        let mut records: Vec<ChannelHealthRecord> = self.channels.read().await.values().cloned().collect();
+        records.sort_by_key(|record| (record.target_id, record.virtual_id));
+        records
+    }
+
+    /// Drops channels of targets without `channel_health` and channels recovered a week ago.
+    async fn prune(&self, app_config: &AppConfig, now: u64) {
+        let mut channels = self.channels.write().await;
+        let len = channels.len();
+        channels.retain(|_, record| {
+            channel_health_config(app_config, record.target_id).is_some()
+                && (record.state != ChannelHealthState::Healthy
+                    || record.last_success_ts.unwrap_or_default() + HEALTHY_RETENTION_SECS > now)
+        });
+        if channels.len() != len {
+            self.update_dead_channels(&channels);
+        }
+    }
+
+    async fn remove(&self, target_id: u16, virtual_id: u32) {
+        let mut channels = self.channels.write().await;
+        if channels.remove(&(target_id, virtual_id)).is_some() {
+            self.update_dead_channels(&channels);
+        }
+    }
+
+    async fn readmit(&self, app_config: &AppConfig, target_id: u16, virtual_id: u32) {
+        let Some(config) = channel_health_config(app_config, target_id) else {
+            return;
+        };
+        let mut channels = self.channels.write().await;
+        if let Some(record) = channels.get_mut(&(target_id, virtual_id)) {
+            record.readmit(&config);
+            self.update_dead_channels(&channels);
+        }
+    }
+
+    async fn due_probes(&self, now: u64) -> Vec<(u16, u32)> {
+        let mut due: Vec<(u64, (u16, u32))> = self
+            .channels
+            .read()
+            .await
+            .values()
+            .filter(|record| record.state == ChannelHealthState::Dead)
+            .filter_map(|record| {
+                record.next_probe_ts.filter(|ts| *ts <= now).map(|ts| (ts, (record.target_id, record.virtual_id)))
+            })
+            .collect();
+        due.sort_unstable();
+        due.into_iter().take(MAX_PROBES_PER_CHECK).map(|(_, key)| key).collect()
+    }
+}
+
+/// Probes a dead channel, `None` when no provider connection is free.
+async fn probe_dead_channel(app_state: &Arc<AppState>, input_name: &Arc<str>, stream_url: &str) -> Option<bool> {
+    let handle = app_state.active_provider.acquire_connection_for_probe(input_name, PROBE_PRIORITY).await?;
+    let config = app_state.app_config.config.load();
+    let metadata_update = config.metadata_update.clone().unwrap_or_default();
+    let outcome = FfmpegExecutor::new()
+        .probe_url(
+            stream_url,
+            config.default_user_agent.as_deref(),
+            metadata_update.ffprobe.live_analyze_duration_micros,
+            metadata_update.ffprobe.live_probe_size_bytes,
+            metadata_update.ffprobe.timeout.unwrap_or(60),
+            config.proxy.as_ref(),
+        )
+        .await;
+    app_state.active_provider.release_handle(&handle).await;
+    Some(matches!(outcome, ProbeUrlOutcome::Success(..)))
+}
+
+async fn check_channel_health(app_state: &Arc<AppState>) {
+    let health = &app_state.channel_health;
+    let now = current_time_secs();
+    health.prune(&app_state.app_config, now).await;
+
+    let ffprobe_enabled = app_state.app_config.is_ffprobe_enabled().await;
+    for (target_id, virtual_id) in health.due_probes(now).await {
+        let Some(target) = app_state.app_config.get_target_by_id(target_id) else {
+            continue;
+        };
+        let Some((input, channel, stream_url)) = resolve_live_channel(app_state, &target, virtual_id).await else {
+            // the channel is no longer part of the target
+            health.remove(target_id, virtual_id).await;
+            continue;
+        };
+        if !ffprobe_enabled {
+            health.readmit(&app_state.app_config, target_id, virtual_id).await;
+            continue;
+        }
+        debug_if_enabled!("Probing dead channel {} of target {}", virtual_id, target.name);
+        match probe_dead_channel(app_state, &input.name, &stream_url).await {
+            Some(true) => health.record_success(target_id, virtual_id).await,
+            Some(false) => health.record_failure(&app_state.app_config, &channel).await,
+            None => {}
+        }
+    }
+
+    if let Err(err) = health.persist_to_disk().await {
+        error!("Failed to persist channel health: {err}");
+    }
+}
+
+pub(in crate::api) fn exec_channel_health_check(app_state: &Arc<AppState>, cancel_token: &CancellationToken) {
+    let app_state = Arc::clone(app_state);
+    let cancel = cancel_token.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(CHANNEL_HEALTH_CHECK_INTERVAL_SECS));
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        loop {
+            tokio::select! {
+                () = cancel.cancelled() => {
+                    info!("Channel health check loop stopped");
+                    break;
+                }
+                _ = interval.tick() => {}
+            }
+            check_channel_health(&app_state).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> ChannelHealthConfigDto {
+        ChannelHealthConfigDto { dead_after: 2, reprobe_mins: 10, ..ChannelHealthConfigDto::default() }
+    }
+
+    #[test]
+    fn channel_dies_after_consecutive_failures_and_backs_off() {
+        let config = config();
+        let mut record = ChannelHealthRecord::from_probe(1, 1, "input", 1);
+        record.apply_failure(&config, 100);
+        assert_eq!(record.state, ChannelHealthState::Degraded);
+        assert_eq!(record.next_probe_ts, None);
+
+        record.apply_failure(&config, 200);
+        assert_eq!(record.state, ChannelHealthState::Dead);
+        assert_eq!(record.next_probe_ts, Some(200 + 600));
+
+        // a failed probe doubles the pause
+        record.apply_failure(&config, 800);
+        assert_eq!(record.next_probe_ts, Some(800 + 1_200));
+
+        record.apply_success(2_000);
+        assert_eq!(record.state, ChannelHealthState::Healthy);
+        assert_eq!((record.failures, record.probe_failures, record.next_probe_ts), (0, 0, None));
+    }
+
+    #[test]
+    fn readmitted_channel_dies_with_the_next_failure() {
+        let config = config();
+        let mut record = ChannelHealthRecord::from_probe(1, 1, "input", 1);
+        record.apply_failure(&config, 100);
+        record.apply_failure(&config, 200);
+        record.readmit(&config);
+        assert_eq!(record.state, ChannelHealthState::Degraded);
+        record.apply_failure(&config, 900);
+        assert_eq!(record.state, ChannelHealthState::Dead);
+        assert_eq!(record.next_probe_ts, Some(900 + 1_200));
+        assert_eq!(reprobe_delay_secs(&config, 16), MAX_REPROBE_SECS);
+    }
+}
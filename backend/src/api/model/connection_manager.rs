@@ -4,7 +4,9 @@ use crate::{
         EventMessage, ProviderHandle, SharedStreamManager,
     },
     model::StreamHistoryConfig,
-    repository::{ConnectFailureReason, DisconnectQos, DisconnectReason, FailureStage, StreamHistoryRecord},
+    repository::{
+        ConnectFailureReason, DisconnectQos, DisconnectReason, FailureStage, StreamHistoryRecord, TsQualityStats,
+    },
     auth::Fingerprint,
     utils::debug_if_enabled,
 };
@@ -56,6 +58,8 @@ pub(crate) enum CleanupEvent {
         addr: SocketAddr,
         provider_end_reason: u8,
         reconnect_count: u8,
+        stall_count: u8,
+        ts_quality: Option<TsQualityStats>,
         provider_error_class: Option<&'static str>,
         provider_http_status: Option<u16>,
     },
@@ -66,6 +70,8 @@ pub(crate) enum CleanupEvent {
         handle: Option<ProviderHandle>,
         provider_end_reason: u8,
         reconnect_count: u8,
+        stall_count: u8,
+        ts_quality: Option<TsQualityStats>,
         provider_error_class: Option<&'static str>,
         provider_http_status: Option<u16>,
     },
@@ -160,11 +166,19 @@ async fn release_connection_parts(
     notify_capacity(capacity_notify.as_ref());
 }
 
+/// Provider reconnects, stalls and transport metrics of a client stream, reported with its disconnect record.
+#[derive(Clone, Copy, Debug, Default)]
+struct SessionQos {
+    reconnects: u8,
+    stalls: u8,
+    ts_quality: Option<TsQualityStats>,
+}
+
 async fn handle_release_stream(
     deps: &CleanupWorkerDeps,
     addr: SocketAddr,
     provider_end_reason: u8,
-    reconnect_count: u8,
+    session_qos: SessionQos,
     provider_error_class: Option<&'static str>,
     provider_http_status: Option<u16>,
 ) {
@@ -172,7 +186,7 @@ async fn handle_release_stream(
         deps,
         addr,
         provider_end_reason,
-        reconnect_count,
+        session_qos,
         provider_error_class,
         provider_http_status,
     )
@@ -196,7 +210,7 @@ async fn handle_release_stream_and_provider_handle(
     addr: SocketAddr,
     handle: Option<ProviderHandle>,
     provider_end_reason: u8,
-    reconnect_count: u8,
+    session_qos: SessionQos,
     provider_error_class: Option<&'static str>,
     provider_http_status: Option<u16>,
 ) {
@@ -210,7 +224,7 @@ async fn handle_release_stream_and_provider_handle(
         deps,
         addr,
         provider_end_reason,
-        reconnect_count,
+        session_qos,
         provider_error_class,
         provider_http_status,
     )
@@ -275,7 +289,7 @@ async fn release_stream_with_disconnect(
     deps: &CleanupWorkerDeps,
     addr: SocketAddr,
     provider_end_reason: u8,
-    reconnect_count: u8,
+    session_qos: SessionQos,
     provider_error_class: Option<&'static str>,
     provider_http_status: Option<u16>,
 ) -> bool {
@@ -285,12 +299,19 @@ async fn release_stream_with_disconnect(
     let (bytes_sent, first_byte_latency_ms) = deps.event_manager.read_meter_qos(stream_info.meter_uid).await;
     deps.event_manager.unregister_meter_client(stream_info.uid).await;
     let reason = resolve_disconnect_reason(provider_end_reason, &stream_info);
-    let provider_reconnect_count = (reconnect_count > 0).then_some(reconnect_count);
+    let provider_reconnect_count = (session_qos.reconnects > 0).then_some(session_qos.reconnects);
+    let provider_stall_count = (session_qos.stalls > 0).then_some(session_qos.stalls);
     emit_disconnect_record(
         &deps.history_writer,
         &stream_info,
         &reason,
-        &DisconnectQos { bytes_sent, first_byte_latency_ms, provider_reconnect_count },
+        &DisconnectQos {
+            bytes_sent,
+            first_byte_latency_ms,
+            provider_reconnect_count,
+            provider_stall_count,
+            ts_quality: session_qos.ts_quality,
+        },
         provider_error_class,
         provider_http_status,
     );
@@ -337,6 +358,7 @@ pub struct ConnectionParams<'a> {
     pub stream_channel: &'a StreamChannel,
     pub user_agent: Cow<'a, str>,
     pub session_token: Option<&'a str>,
+    pub throttle_kbps: u64,
 }
 
 impl ConnectionManager {
@@ -554,6 +576,8 @@ impl ConnectionManager {
                         addr,
                         provider_end_reason,
                         reconnect_count,
+                        stall_count,
+                        ts_quality,
                         provider_error_class,
                         provider_http_status,
                     } => {
@@ -561,7 +585,7 @@ impl ConnectionManager {
                             &deps,
                             addr,
                             provider_end_reason,
-                            reconnect_count,
+                            SessionQos { reconnects: reconnect_count, stalls: stall_count, ts_quality },
                             provider_error_class,
                             provider_http_status,
                         )
@@ -575,6 +599,8 @@ impl ConnectionManager {
                         handle,
                         provider_end_reason,
                         reconnect_count,
+                        stall_count,
+                        ts_quality,
                         provider_error_class,
                         provider_http_status,
                     } => {
@@ -583,7 +609,7 @@ impl ConnectionManager {
                             addr,
                             handle,
                             provider_end_reason,
-                            reconnect_count,
+                            SessionQos { reconnects: reconnect_count, stalls: stall_count, ts_quality },
                             provider_error_class,
                             provider_http_status,
                         )
@@ -798,6 +824,7 @@ impl ConnectionManager {
                 stream_channel: update.stream_channel,
                 user_agent: update.user_agent,
                 session_token: update.session_token,
+                throttle_kbps: update.throttle_kbps,
             })
             .await
         {
@@ -852,6 +879,7 @@ fn resolve_disconnect_reason(provider_end_reason: u8, stream_info: &StreamInfo)
                 CustomVideoStreamType::LowPriorityPreempted => return DisconnectReason::Preempted,
                 CustomVideoStreamType::UserConnectionsExhausted => return DisconnectReason::UserConnectionsExhausted,
                 CustomVideoStreamType::ProviderConnectionsExhausted => return DisconnectReason::ProviderConnectionsExhausted,
+                CustomVideoStreamType::UserQuotaExhausted => return DisconnectReason::UserQuotaExhausted,
                 CustomVideoStreamType::ChannelUnavailable => {
                     return match provider_end_reason {
                         PROVIDER_END_CLOSED => DisconnectReason::ProviderClosed,
@@ -942,6 +970,7 @@ mod tests {
             shared_joined_existing: None,
             shared_stream_id: None,
             technical: None,
+            alternate_input: None,
         };
         StreamInfo::new(0, 0, "test", &addr, "127.0.0.1", provider, channel, String::new(), None, None)
     }
@@ -235,6 +235,14 @@ impl EventManager {
         }
     }
 
+    /// Read the total bytes a meter has delivered, shared meters included.
+    pub async fn read_meter_bytes_total(&self, meter_uid: u32) -> Option<u64> {
+        if meter_uid == 0 {
+            return None;
+        }
+        self.meter_registry.read().await.meters.get(&meter_uid).map(|m| m.bytes_total())
+    }
+
     pub async fn unregister_meter_client(&self, client_uid: u32) {
         if client_uid == 0 {
             return;
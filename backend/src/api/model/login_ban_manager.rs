@@ -0,0 +1,268 @@
+use crate::{model::LoginProtectionConfig, utils::file_exists_async};
+use log::error;
+use serde::{Deserialize, Serialize};
+use shared::model::LoginBanDto;
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::atomic::{AtomicBool, Ordering},
+};
+use tokio::{fs, sync::RwLock};
+
+/// Failure logs above this size are pruned from stale entries.
+const FAILURE_LOG_PRUNE_SIZE: usize = 4096;
+
+/// Marks a player API response as a failed login, the login protection middleware counts it.
+#[derive(Debug, Clone, Copy)]
+pub struct LoginFailure;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PersistedLoginBans {
+    bans: Vec<LoginBanDto>,
+}
+
+#[derive(Default)]
+struct LoginFailures {
+    ips: HashMap<String, Vec<u64>>,
+    users: HashMap<String, Vec<u64>>,
+}
+
+#[derive(Default)]
+struct LoginBans {
+    ips: HashMap<String, LoginBanDto>,
+    users: HashMap<String, LoginBanDto>,
+}
+
+impl LoginBans {
+    fn get_mut(&mut self, user: bool) -> &mut HashMap<String, LoginBanDto> {
+        if user { &mut self.users } else { &mut self.ips }
+    }
+}
+
+/// Records the failure and returns `true` when the failures within the find time reached the limit.
+fn track_failure(
+    failures: &mut HashMap<String, Vec<u64>>,
+    key: &str,
+    max_failures: u32,
+    find_time_secs: u64,
+    now: u64,
+) -> bool {
+    if failures.len() > FAILURE_LOG_PRUNE_SIZE {
+        failures.retain(|_, times| times.iter().any(|time| time + find_time_secs > now));
+    }
+    let times = failures.entry(key.to_string()).or_default();
+    times.retain(|time| time + find_time_secs > now);
+    times.push(now);
+    if times.len() >= max_failures as usize {
+        failures.remove(key);
+        return true;
+    }
+    false
+}
+
+/// Tracks failed player API logins per client ip and username and bans them with escalating durations.
+pub struct LoginBanManager {
+    state_file: Option<PathBuf>,
+    failures: RwLock<LoginFailures>,
+    bans: RwLock<LoginBans>,
+    dirty: AtomicBool,
+}
+
+impl LoginBanManager {
+    pub fn new_with_state_file(state_file: Option<PathBuf>) -> Self {
+        Self {
+            state_file,
+            failures: RwLock::new(LoginFailures::default()),
+            bans: RwLock::new(LoginBans::default()),
+            dirty: AtomicBool::new(false),
+        }
+    }
+
+    pub async fn load_from_disk(&self) -> std::io::Result<()> {
+        let Some(state_file) = self.state_file.as_ref() else {
+            return Ok(());
+        };
+        if !file_exists_async(state_file).await {
+            return Ok(());
+        }
+        let content = fs::read_to_string(state_file).await?;
+        let persisted: PersistedLoginBans =
+            serde_json::from_str(&content).map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+        let mut bans = self.bans.write().await;
+        for ban in persisted.bans {
+            bans.get_mut(ban.user).insert(ban.key.clone(), ban);
+        }
+        Ok(())
+    }
+
+    pub async fn persist_to_disk(&self) -> std::io::Result<()> {
+        let Some(state_file) = self.state_file.as_ref() else {
+            return Ok(());
+        };
+        if !self.dirty.swap(false, Ordering::AcqRel) {
+            return Ok(());
+        }
+        let content = {
+            let bans = self.bans.read().await;
+            let bans = bans.ips.values().chain(bans.users.values()).cloned().collect();
+            serde_json::to_vec(&PersistedLoginBans { bans }).map_err(std::io::Error::other)?
+        };
+        if let Some(parent) = state_file.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        let tmp_file = state_file.with_extension("json.tmp");
+        fs::write(&tmp_file, content).await?;
+        fs::rename(&tmp_file, state_file).await
+    }
+
+    async fn persist(&self) {
+        if let Err(err) = self.persist_to_disk().await {
+            error!("Failed to persist login bans: {err}");
+        }
+    }
+
+    pub async fn is_banned(&self, key: &str, user: bool, now: u64) -> bool {
+        let bans = self.bans.read().await;
+        let bans = if user { &bans.users } else { &bans.ips };
+        bans.get(key).is_some_and(|ban| ban.until > now)
+    }
+
+    /// Records a failed login of the client ip and username, returns the bans created by it.
+    pub async fn record_failure(
+        &self,
+        config: &LoginProtectionConfig,
+        client_ip: Option<&str>,
+        username: Option<&str>,
+        now: u64,
+    ) -> Vec<LoginBanDto> {
+        let mut banned = Vec::new();
+        {
+            let mut failures = self.failures.write().await;
+            if let Some(ip) = client_ip {
+                if track_failure(&mut failures.ips, ip, config.max_failures, config.find_time_secs, now) {
+                    banned.push((ip, false));
+                }
+            }
+            if let Some(username) = username.filter(|_| config.user_max_failures > 0) {
+                if track_failure(&mut failures.users, username, config.user_max_failures, config.find_time_secs, now) {
+                    banned.push((username, true));
+                }
+            }
+        }
+        if banned.is_empty() {
+            return Vec::new();
+        }
+
+        let new_bans = {
+            let mut bans = self.bans.write().await;
+            // expired bans are kept to escalate the next ban until they are forgiven after max_ban_secs
+            bans.ips.retain(|_, ban| ban.until + config.max_ban_secs > now);
+            bans.users.retain(|_, ban| ban.until + config.max_ban_secs > now);
+            banned
+                .into_iter()
+                .map(|(key, user)| {
+                    let entries = bans.get_mut(user);
+                    let level = entries.get(key).map_or(1, |ban| ban.level.saturating_add(1));
+                    let duration = config
+                        .ban_secs
+                        .saturating_mul(1u64 << (level - 1).min(32))
+                        .min(config.max_ban_secs);
+                    let ban =
+                        LoginBanDto { key: key.to_string(), user, banned_at: now, until: now + duration, level };
+                    entries.insert(key.to_string(), ban.clone());
+                    ban
+                })
+                .collect()
+        };
+        self.dirty.store(true, Ordering::Release);
+        self.persist().await;
+        new_bans
+    }
+
+    /// Returns the active bans.
+    pub async fn list(&self, now: u64) -> Vec<LoginBanDto> {
+        let bans = self.bans.read().await;
+        let mut active: Vec<LoginBanDto> =
+            bans.ips.values().chain(bans.users.values()).filter(|ban| ban.until > now).cloned().collect();
+        active.sort_by_key(|ban| std::cmp::Reverse(ban.banned_at));
+        active
+    }
+
+    /// Lifts a ban and resets its escalation level.
+    pub async fn unban(&self, key: &str, user: bool) -> bool {
+        let removed = self.bans.write().await.get_mut(user).remove(key).is_some();
+        {
+            let mut failures = self.failures.write().await;
+            if user { failures.users.remove(key) } else { failures.ips.remove(key) };
+        }
+        if removed {
+            self.dirty.store(true, Ordering::Release);
+            self.persist().await;
+        }
+        removed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::model::LoginProtectionConfigDto;
+
+    fn config(user_max_failures: u32) -> LoginProtectionConfig {
+        LoginProtectionConfig::from(&LoginProtectionConfigDto {
+            enabled: true,
+            max_failures: 3,
+            user_max_failures,
+            find_time_secs: 60,
+            ban_secs: 100,
+            max_ban_secs: 300,
+            ..LoginProtectionConfigDto::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn repeated_failures_ban_with_escalating_durations() {
+        let manager = LoginBanManager::new_with_state_file(None);
+        let config = config(0);
+        let ip = Some("10.0.0.1");
+
+        assert!(manager.record_failure(&config, ip, Some("alice"), 0).await.is_empty());
+        assert!(manager.record_failure(&config, ip, Some("alice"), 10).await.is_empty());
+        // failures outside the find time are not counted
+        assert!(manager.record_failure(&config, ip, Some("alice"), 65).await.is_empty());
+        let bans = manager.record_failure(&config, ip, Some("alice"), 66).await;
+        assert_eq!(bans.len(), 1);
+        assert_eq!((bans[0].until, bans[0].level), (166, 1));
+        assert!(manager.is_banned("10.0.0.1", false, 100).await);
+        assert!(!manager.is_banned("10.0.0.1", false, 166).await);
+        assert!(!manager.is_banned("alice", true, 100).await);
+
+        for now in 200..202 {
+            manager.record_failure(&config, ip, None, now).await;
+        }
+        let bans = manager.record_failure(&config, ip, None, 202).await;
+        assert_eq!((bans[0].until, bans[0].level), (402, 2));
+        for now in 500..502 {
+            manager.record_failure(&config, ip, None, now).await;
+        }
+        // the duration is capped by max_ban_secs
+        let bans = manager.record_failure(&config, ip, None, 502).await;
+        assert_eq!((bans[0].until, bans[0].level), (802, 3));
+
+        assert_eq!(manager.list(600).await.len(), 1);
+        assert!(manager.unban("10.0.0.1", false).await);
+        assert!(manager.list(600).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn usernames_are_locked_across_ips() {
+        let manager = LoginBanManager::new_with_state_file(None);
+        let config = config(2);
+        assert!(manager.record_failure(&config, Some("10.0.0.1"), Some("bob"), 0).await.is_empty());
+        let bans = manager.record_failure(&config, Some("10.0.0.2"), Some("bob"), 1).await;
+        assert_eq!(bans.len(), 1);
+        assert!(bans[0].user);
+        assert!(manager.is_banned("bob", true, 2).await);
+        assert!(!manager.is_banned("10.0.0.2", false, 2).await);
+    }
+}
@@ -3483,7 +3483,7 @@ impl InputWorker {
                 // ProbeLive always probes, so we must never use a cached handle here.
                 db_handles.remove(&XtreamCluster::Live);
 
-                match update_live_stream_metadata(
+                let result = update_live_stream_metadata(
                     &app_state.app_config,
                     input,
                     id.clone(),
@@ -3492,8 +3492,14 @@ impl InputWorker {
                     active_handle,
                     &app_state.active_provider,
                 )
-                .await
-                {
+                .await;
+                if let (ProviderIdType::Id(provider_id), Ok(Some(_)) | Err(_)) = (id, &result) {
+                    app_state
+                        .channel_health
+                        .record_probe_result(&app_state.app_config, &input.name, *provider_id, result.is_ok())
+                        .await;
+                }
+                match result {
                     Ok(Some(props)) => {
                         collector.add_live(id.clone(), props);
                         Ok((false, false))
@@ -1,11 +1,14 @@
 mod active_provider_manager;
 mod active_user_manager;
 mod app_state;
+mod channel_health_manager;
 mod connection_manager;
 mod download;
 mod event_manager;
+mod login_ban_manager;
 mod metadata_update_manager;
 mod model_utils;
+mod parental_unlock_manager;
 mod playlist_mem_cache;
 mod provider_config;
 mod provider_dns_manager;
@@ -15,18 +18,29 @@ mod recording_worker;
 mod request;
 mod stream;
 mod stream_error;
+mod stream_multicast;
+mod stream_prewarm;
 mod streams;
 mod update_guard;
+mod user_access_schedule;
+mod user_device_manager;
+mod user_quota_manager;
+mod user_rate_limiter;
+mod user_travel_manager;
+mod vod_cache;
 mod xtream;
 
 pub(crate) use self::streams::*;
 pub use self::{
-    active_provider_manager::*, app_state::*, connection_manager::*, event_manager::*, metadata_update_manager::*,
-    playlist_mem_cache::*, provider_dns_manager::*, provider_lineup_manager::*, stream::*, update_guard::*,
+    active_provider_manager::*, app_state::*, channel_health_manager::*, connection_manager::*, event_manager::*,
+    metadata_update_manager::*, playlist_mem_cache::*, provider_dns_manager::*, provider_lineup_manager::*, stream::*,
+    update_guard::*, vod_cache::*,
 };
 pub(in crate::api) use self::{
-    active_user_manager::*, download::*, model_utils::*, provider_config::*, qos_aggregation_manager::*,
-    recording_worker::*, request::*, stream_error::*, xtream::*,
+    active_user_manager::*, download::*, login_ban_manager::*, model_utils::*, parental_unlock_manager::*,
+    provider_config::*, qos_aggregation_manager::*, recording_worker::*, request::*, stream_error::*,
+    stream_multicast::*, stream_prewarm::*, user_access_schedule::*, user_device_manager::*, user_quota_manager::*,
+    user_rate_limiter::*, user_travel_manager::*, xtream::*,
 };
 mod batch_result_collector;
 pub use self::batch_result_collector::*;
@@ -0,0 +1,195 @@
+use crate::model::{AppConfig, ParentalProfile, ProxyUserCredentials};
+use dashmap::DashMap;
+use log::debug;
+use shared::{model::ParentalMode, utils::current_time_secs};
+
+/// Failed pin attempts of one client before its pin checks are locked.
+const PIN_MAX_CLIENT_FAILURES: u32 = 5;
+/// Failed pin attempts of all clients of a user before the pin checks of the user are locked.
+const PIN_MAX_USER_FAILURES: u32 = 20;
+const PIN_LOCKOUT_SECS: u64 = 60;
+const PIN_MAX_LOCKOUT_SECS: u64 = 3_600;
+/// Failures are forgotten after this time without a new failure.
+const PIN_FAILURE_RESET_SECS: u64 = 3_600;
+/// Maps above this size are pruned from expired entries.
+const PRUNE_SIZE: usize = 4096;
+
+#[derive(Debug, Default, Clone, Copy)]
+struct PinFailures {
+    count: u32,
+    last_failure: u64,
+    locked_until: u64,
+}
+
+impl PinFailures {
+    fn is_locked(&self, now: u64) -> bool { self.locked_until > now }
+
+    fn is_expired(&self, now: u64) -> bool {
+        !self.is_locked(now) && self.last_failure + PIN_FAILURE_RESET_SECS <= now
+    }
+
+    /// Counts the failure, every failure above the limit doubles the lockout.
+    fn record(&mut self, max_failures: u32, now: u64) {
+        if self.is_expired(now) {
+            *self = Self::default();
+        }
+        self.count = self.count.saturating_add(1);
+        self.last_failure = now;
+        if self.count >= max_failures {
+            let level = (self.count - max_failures).min(16);
+            self.locked_until = now + PIN_LOCKOUT_SECS.saturating_mul(1u64 << level).min(PIN_MAX_LOCKOUT_SECS);
+        }
+    }
+}
+
+/// Remembers the clients of users who unlocked their parental profile with the pin, so follow-up requests
+/// of the same client without the pin (e.g. stream urls from a pin-unlocked playlist) are accepted.
+/// A client is identified by its ip, user agent and the optional device id.
+/// Failed pin attempts lock the pin check per client and per user with escalating durations.
+#[derive(Default)]
+pub struct ParentalUnlockManager {
+    unlocked_until: DashMap<String, u64>,
+    failures: DashMap<String, PinFailures>,
+}
+
+impl ParentalUnlockManager {
+    /// Marks the user as unlocked for this request when `pin` matches the profile pin
+    /// or an earlier unlock of the same client has not expired yet.
+    pub fn apply(&self, app_config: &AppConfig, user: &mut ProxyUserCredentials, client: &str, pin: &str) {
+        if let Some(profile) = app_config.get_user_parental_restriction(user) {
+            self.unlock(&profile, user, client, pin, current_time_secs());
+        }
+    }
+
+    fn is_locked(&self, key: &str, now: u64) -> bool {
+        self.failures.get(key).is_some_and(|failures| failures.is_locked(now))
+    }
+
+    fn record_failure(&self, key: String, max_failures: u32, now: u64) {
+        if self.failures.len() > PRUNE_SIZE {
+            self.failures.retain(|_, failures| !failures.is_expired(now));
+        }
+        self.failures.entry(key).or_default().record(max_failures, now);
+    }
+
+    fn unlock(&self, profile: &ParentalProfile, user: &mut ProxyUserCredentials, client: &str, pin: &str, now: u64) {
+        if profile.mode != ParentalMode::Pin {
+            return;
+        }
+        let client_key = format!("{}|{client}", user.username);
+        if !pin.is_empty() {
+            if self.is_locked(&user.username, now) || self.is_locked(&client_key, now) {
+                debug!("Parental pin check locked after failed attempts for user: {}", user.username);
+            } else if profile.is_pin_valid(pin) {
+                if self.unlocked_until.len() > PRUNE_SIZE {
+                    self.unlocked_until.retain(|_, until| *until > now);
+                }
+                self.failures.remove(&client_key);
+                self.unlocked_until.insert(client_key, now + u64::from(profile.unlock_mins) * 60);
+                user.t_parental_unlocked = true;
+                return;
+            } else {
+                debug!("Invalid parental pin for user: {}", user.username);
+                self.record_failure(client_key.clone(), PIN_MAX_CLIENT_FAILURES, now);
+                self.record_failure(user.username.clone(), PIN_MAX_USER_FAILURES, now);
+            }
+        }
+        let unlocked = self.unlocked_until.get(&client_key).map(|until| *until);
+        match unlocked {
+            Some(until) if until > now => user.t_parental_unlocked = true,
+            Some(_) => {
+                self.unlocked_until.remove(&client_key);
+            }
+            None => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ParentalUnlockManager, PIN_LOCKOUT_SECS, PIN_MAX_CLIENT_FAILURES, PIN_MAX_USER_FAILURES};
+    use crate::model::{ParentalProfile, ProxyUserCredentials};
+    use shared::model::ParentalMode;
+
+    fn profile() -> ParentalProfile {
+        ParentalProfile {
+            name: "kids".to_string(),
+            filter: None,
+            adult: true,
+            mode: ParentalMode::Pin,
+            pin: Some("1234".to_string()),
+            unlock_mins: 5,
+        }
+    }
+
+    fn new_user() -> ProxyUserCredentials {
+        let mut user = ProxyUserCredentials::default();
+        user.username = "child".to_string();
+        user.parental_profile = Some("kids".to_string());
+        user
+    }
+
+    #[test]
+    fn test_parental_pin_unlocks_following_requests() {
+        let profile = profile();
+        let manager = ParentalUnlockManager::default();
+        let now = 1_000;
+
+        let mut user = new_user();
+        manager.unlock(&profile, &mut user, "tv", "", now);
+        assert!(!user.t_parental_unlocked);
+        manager.unlock(&profile, &mut user, "tv", "0000", now);
+        assert!(!user.t_parental_unlocked);
+        manager.unlock(&profile, &mut user, "tv", "1234", now);
+        assert!(user.t_parental_unlocked);
+
+        let mut user = new_user();
+        manager.unlock(&profile, &mut user, "tv", "", now + 60);
+        assert!(user.t_parental_unlocked);
+
+        // the unlock belongs to the client which entered the pin
+        let mut user = new_user();
+        manager.unlock(&profile, &mut user, "tablet", "", now + 60);
+        assert!(!user.t_parental_unlocked);
+
+        let mut user = new_user();
+        manager.unlock(&profile, &mut user, "tv", "", now + 5 * 60);
+        assert!(!user.t_parental_unlocked);
+
+        let hide_profile = ParentalProfile { mode: ParentalMode::Hide, ..profile };
+        let mut user = new_user();
+        manager.unlock(&hide_profile, &mut user, "tv", "1234", now);
+        assert!(!user.t_parental_unlocked);
+    }
+
+    #[test]
+    fn test_parental_pin_failures_lock_the_pin_check() {
+        let profile = profile();
+        let manager = ParentalUnlockManager::default();
+        let now = 1_000;
+
+        for _ in 0..PIN_MAX_CLIENT_FAILURES {
+            manager.unlock(&profile, &mut new_user(), "tv", "0000", now);
+        }
+        // the correct pin is not checked while the client is locked
+        let mut user = new_user();
+        manager.unlock(&profile, &mut user, "tv", "1234", now + 1);
+        assert!(!user.t_parental_unlocked);
+        // other clients of the user are not locked yet
+        let mut user = new_user();
+        manager.unlock(&profile, &mut user, "tablet", "1234", now + 1);
+        assert!(user.t_parental_unlocked);
+
+        let mut user = new_user();
+        manager.unlock(&profile, &mut user, "tv", "1234", now + PIN_LOCKOUT_SECS);
+        assert!(user.t_parental_unlocked);
+
+        // rotating clients ends in the lock of the user
+        for attempt in 0..PIN_MAX_USER_FAILURES {
+            manager.unlock(&profile, &mut new_user(), &format!("client-{attempt}"), "0000", now + 100);
+        }
+        let mut user = new_user();
+        manager.unlock(&profile, &mut user, "phone", "1234", now + 101);
+        assert!(!user.t_parental_unlocked);
+    }
+}
@@ -183,6 +183,19 @@ pub(crate) fn fold_record_into_bucket(bucket: &mut QosSnapshotDailyBucket, recor
                     bucket.total_provider_reconnect_count.saturating_add(u64::from(reconnects));
                 bucket.total_provider_reconnect_samples = bucket.total_provider_reconnect_samples.saturating_add(1);
             }
+            if let Some(stalls) = record.provider_stall_count {
+                bucket.provider_stall_count = bucket.provider_stall_count.saturating_add(u64::from(stalls));
+            }
+            if let Some(ts_quality) = record.ts_quality {
+                bucket.ts_packet_count = bucket.ts_packet_count.saturating_add(ts_quality.packets);
+                bucket.ts_null_packet_count = bucket.ts_null_packet_count.saturating_add(ts_quality.null_packets);
+                bucket.ts_cc_error_count = bucket.ts_cc_error_count.saturating_add(ts_quality.cc_errors);
+                bucket.ts_pcr_gap_count = bucket.ts_pcr_gap_count.saturating_add(ts_quality.pcr_gaps);
+                if let Some(bitrate) = ts_quality.video_bitrate_kbps {
+                    bucket.total_video_bitrate_kbps = bucket.total_video_bitrate_kbps.saturating_add(bitrate);
+                    bucket.total_video_bitrate_samples = bucket.total_video_bitrate_samples.saturating_add(1);
+                }
+            }
             if matches!(
                 record.disconnect_reason,
                 Some(DisconnectReason::ProviderError | DisconnectReason::ProviderClosed | DisconnectReason::Preempted)
@@ -209,6 +222,10 @@ fn build_window(snapshot: &QosSnapshotRecord, today_utc: &str, max_day_distance:
     let mut duration_samples = 0u64;
     let mut reconnect_total = 0u64;
     let mut reconnect_samples = 0u64;
+    let mut ts_packet_total = 0u64;
+    let mut ts_null_packet_total = 0u64;
+    let mut bitrate_total = 0u64;
+    let mut bitrate_samples = 0u64;
 
     for (day, bucket) in &snapshot.daily_buckets {
         if !bucket_in_window(today, day, max_day_distance) {
@@ -224,6 +241,9 @@ fn build_window(snapshot: &QosSnapshotRecord, today_utc: &str, max_day_distance:
         window.runtime_abort_count = window.runtime_abort_count.saturating_add(bucket.runtime_abort_count);
         window.provider_closed_count = window.provider_closed_count.saturating_add(bucket.provider_closed_count);
         window.preempt_count = window.preempt_count.saturating_add(bucket.preempt_count);
+        window.provider_stall_count = window.provider_stall_count.saturating_add(bucket.provider_stall_count);
+        window.ts_cc_error_count = window.ts_cc_error_count.saturating_add(bucket.ts_cc_error_count);
+        window.ts_pcr_gap_count = window.ts_pcr_gap_count.saturating_add(bucket.ts_pcr_gap_count);
         window.last_success_ts = max_opt(window.last_success_ts, bucket.last_success_ts);
         window.last_failure_ts = max_opt(window.last_failure_ts, bucket.last_failure_ts);
         latency_total = latency_total.saturating_add(bucket.total_first_byte_latency_ms);
@@ -232,11 +252,17 @@ fn build_window(snapshot: &QosSnapshotRecord, today_utc: &str, max_day_distance:
         duration_samples = duration_samples.saturating_add(bucket.total_session_duration_samples);
         reconnect_total = reconnect_total.saturating_add(bucket.total_provider_reconnect_count);
         reconnect_samples = reconnect_samples.saturating_add(bucket.total_provider_reconnect_samples);
+        ts_packet_total = ts_packet_total.saturating_add(bucket.ts_packet_count);
+        ts_null_packet_total = ts_null_packet_total.saturating_add(bucket.ts_null_packet_count);
+        bitrate_total = bitrate_total.saturating_add(bucket.total_video_bitrate_kbps);
+        bitrate_samples = bitrate_samples.saturating_add(bucket.total_video_bitrate_samples);
     }
 
     window.avg_first_byte_latency_ms = average_opt(latency_total, latency_samples);
     window.avg_session_duration_secs = average_opt(duration_total, duration_samples);
     window.avg_provider_reconnect_count = average_opt(reconnect_total, reconnect_samples);
+    window.ts_null_packet_permille = average_opt(ts_null_packet_total.saturating_mul(1_000), ts_packet_total);
+    window.avg_video_bitrate_kbps = average_opt(bitrate_total, bitrate_samples);
     window.sample_size = window
         .connect_count
         .saturating_add(window.connect_failed_count)
@@ -322,7 +348,7 @@ fn bucket_in_window(today: Option<NaiveDate>, bucket_day_utc: &str, max_day_dist
 
 fn parse_utc_day(day: &str) -> Option<NaiveDate> { NaiveDate::parse_from_str(day, "%Y-%m-%d").ok() }
 
-fn discover_history_days(history_dir: &Path) -> io::Result<Vec<String>> {
+pub(in crate::api) fn discover_history_days(history_dir: &Path) -> io::Result<Vec<String>> {
     if !history_dir.exists() {
         return Ok(Vec::new());
     }
@@ -364,6 +390,38 @@ fn aggregate_day_entries(history_dir: &Path, day_utc: &str) -> io::Result<HashMa
     Ok(entries)
 }
 
+/// Calls `visitor` for every stream history record of the UTC day partition.
+pub(in crate::api) fn visit_history_day_records<F: FnMut(&StreamHistoryRecord)>(
+    history_dir: &Path,
+    day_utc: &str,
+    mut visitor: F,
+) -> io::Result<()> {
+    for file in discover_day_files(history_dir, day_utc)? {
+        if file.is_archive {
+            let reader = StreamHistoryFileReader::from_archive(&file.path, None).map(|(reader, _)| reader)?;
+            visit_reader_records(day_utc, reader, &mut visitor)?;
+        } else {
+            let reader = StreamHistoryFileReader::from_pending(&file.path, None).map(|(reader, _)| reader)?;
+            visit_reader_records(day_utc, reader, &mut visitor)?;
+        }
+    }
+    Ok(())
+}
+
+fn visit_reader_records<R: io::Read, F: FnMut(&StreamHistoryRecord)>(
+    day_utc: &str,
+    reader: StreamHistoryFileReader<R>,
+    visitor: &mut F,
+) -> io::Result<()> {
+    for record in reader {
+        let record = record?;
+        if record.partition_day_utc == day_utc {
+            visitor(&record);
+        }
+    }
+    Ok(())
+}
+
 fn aggregate_reader_entries<R: io::Read>(
     day_utc: &str,
     reader: StreamHistoryFileReader<R>,
@@ -524,7 +582,7 @@ mod tests {
         current_utc_day, serialize_named, write_block_magic, write_file_magic, write_framed, BlockHeaderBody,
         CompressionKind, ConnectFailureReason, CONTAINER_FORMAT_VERSION, DisconnectReason, EventType, FailureStage,
         FileHeaderBody, QosSnapshotDailyBucket, QosSnapshotRecord, QosSnapshotRepository, QosSnapshotWindow,
-        RecordEncodingKind, RECORD_SCHEMA_VERSION, SOURCE_KIND_STREAM_HISTORY, StreamHistoryRecord,
+        RecordEncodingKind, RECORD_SCHEMA_VERSION, SOURCE_KIND_STREAM_HISTORY, StreamHistoryRecord, TsQualityStats,
     };
 
     use super::{fold_record_into_bucket, history_day_revision, qos_aggregation_is_enabled, rebuild_windows, run_aggregation_once};
@@ -602,6 +660,7 @@ mod tests {
             provider_name: Some("provider-a".to_string()),
             provider_username: None,
             input_name: Some("input-a".to_string()),
+            alternate_input: None,
             virtual_id: Some(33),
             item_type: Some("live".to_string()),
             title: None,
@@ -627,6 +686,8 @@ mod tests {
             bytes_sent: None,
             first_byte_latency_ms: None,
             provider_reconnect_count: None,
+            provider_stall_count: None,
+            ts_quality: None,
             failure_stage: None,
             provider_http_status: None,
             provider_error_class: None,
@@ -668,6 +729,14 @@ mod tests {
         streaming.disconnect_reason = Some(DisconnectReason::ProviderClosed);
         streaming.session_duration = Some(900);
         streaming.provider_reconnect_count = Some(2);
+        streaming.provider_stall_count = Some(1);
+        streaming.ts_quality = Some(TsQualityStats {
+            packets: 1_000,
+            cc_errors: 3,
+            pcr_gaps: 1,
+            null_packets: 50,
+            video_bitrate_kbps: Some(4_000),
+        });
         fold_record_into_bucket(&mut bucket, &streaming);
 
         assert_eq!(bucket.first_byte_failure_count, 1);
@@ -676,6 +745,13 @@ mod tests {
         assert_eq!(bucket.total_first_byte_latency_ms, 250);
         assert_eq!(bucket.total_session_duration_secs, 900);
         assert_eq!(bucket.total_provider_reconnect_count, 2);
+        assert_eq!(bucket.provider_stall_count, 1);
+        assert_eq!(bucket.ts_packet_count, 1_000);
+        assert_eq!(bucket.ts_null_packet_count, 50);
+        assert_eq!(bucket.ts_cc_error_count, 3);
+        assert_eq!(bucket.ts_pcr_gap_count, 1);
+        assert_eq!(bucket.total_video_bitrate_kbps, 4_000);
+        assert_eq!(bucket.total_video_bitrate_samples, 1);
     }
 
     #[test]
@@ -753,11 +829,15 @@ mod tests {
         config.reverse_proxy = Some(ReverseProxyConfig {
             resource_rewrite_disabled: false,
             rewrite_secret: [0; 16],
+            trusted_proxies: Vec::new(),
+            trusted_proxy_networks: Vec::new(),
             resource_retry: Default::default(),
             disabled_header: None,
             stream: None,
             cache: None,
+            vod_cache: None,
             rate_limit: None,
+            login_protection: None,
             geoip: None,
             stream_history: Some(StreamHistoryConfig {
                 stream_history_enabled: false,
@@ -37,6 +37,10 @@ pub struct UserApiRequest {
     pub duration: String,
     #[serde(default, alias = "type")]
     pub content_type: String,
+    #[serde(default)]
+    pub pin: String,
+    #[serde(default)]
+    pub device_id: String,
 }
 
 /// Custom extractor that parses `UserApiRequest` from query parameters and request body
@@ -223,6 +227,8 @@ impl UserApiRequest {
             "stream" => self.stream = value.to_string(),
             "duration" => self.duration = value.to_string(),
             "type" | "content_type" => self.content_type = value.to_string(),
+            "pin" => self.pin = value.to_string(),
+            "device_id" => self.device_id = value.to_string(),
             _ => {}
         }
     }
@@ -251,6 +257,8 @@ impl UserApiRequest {
             stream: pick(&primary.stream, &fallback.stream),
             duration: pick(&primary.duration, &fallback.duration),
             content_type: pick(&primary.content_type, &fallback.content_type),
+            pin: pick(&primary.pin, &fallback.pin),
+            device_id: pick(&primary.device_id, &fallback.device_id),
         }
     }
 
@@ -276,6 +284,8 @@ impl UserApiRequest {
             if !self.stream.is_empty() { let _ = write!(msg, " stream={}", self.stream); }
             if !self.duration.is_empty() { let _ = write!(msg, " duration={}", self.duration); }
             if !self.content_type.is_empty() { let _ = write!(msg, " type={}", self.content_type); }
+            if !self.pin.is_empty() { msg.push_str(" pin=***"); }
+            if !self.device_id.is_empty() { let _ = write!(msg, " device_id={}", self.device_id); }
             log::debug!("{msg}");
         }
     }
@@ -334,6 +344,8 @@ mod tests {
             stream: String::from(" "),
             duration: String::from(" "),
             content_type: String::from(" "),
+            pin: String::from(" "),
+            device_id: String::from(" "),
         };
         let fallback = UserApiRequest {
             username: String::from("user"),
@@ -350,6 +362,8 @@ mod tests {
             stream: String::from("300"),
             duration: String::from("60"),
             content_type: String::from("m3u_plus"),
+            pin: String::from("1234"),
+            device_id: String::from("living-room-tv"),
         };
 
         let merged = UserApiRequest::merge_prefer_primary(&primary, &fallback);
@@ -368,6 +382,8 @@ mod tests {
         assert_eq!(merged.stream, "300");
         assert_eq!(merged.duration, "60");
         assert_eq!(merged.content_type, "m3u_plus");
+        assert_eq!(merged.pin, "1234");
+        assert_eq!(merged.device_id, "living-room-tv");
     }
 
     #[test]
@@ -5,7 +5,10 @@ use crate::{
 use axum::http::StatusCode;
 use bytes::Bytes;
 use futures::stream::BoxStream;
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::HashMap,
+    sync::{atomic::AtomicU8, Arc},
+};
 use tokio_util::sync::CancellationToken;
 use url::Url;
 
@@ -35,6 +38,8 @@ pub struct StreamDetails {
     pub provider_grace_active: bool,
     pub disable_provider_grace: bool,
     pub reconnect_flag: Option<CancellationToken>,
+    /// Stalls of the live provider stream detected by the reconnecting provider stream.
+    pub stall_count: Option<Arc<AtomicU8>>,
     pub provider_handle: Option<ProviderHandle>,
 }
 
@@ -49,6 +54,7 @@ impl StreamDetails {
             provider_grace_active: false,
             disable_provider_grace: false,
             reconnect_flag: None,
+            stall_count: None,
             provider_handle: None,
         }
     }
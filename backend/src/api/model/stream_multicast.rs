@@ -0,0 +1,613 @@
+use crate::api::{
+    api_utils::open_multicast_stream,
+    model::{AppState, BoxedProviderStream},
+};
+use crate::model::{ConfigTarget, MulticastChannelConfig};
+use crate::repository::{multicast_resolve_channels, MulticastChannel};
+use bytes::{Bytes, BytesMut};
+use futures::StreamExt;
+use log::{debug, info, warn};
+use parking_lot::Mutex;
+use socket2::{Domain, Protocol, Socket, Type};
+use std::{
+    collections::{HashMap, HashSet},
+    io::Read,
+    net::{Ipv4Addr, SocketAddr, SocketAddrV4, UdpSocket as StdUdpSocket},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::{net::UdpSocket, sync::Notify, task::JoinHandle};
+use tokio_util::sync::CancellationToken;
+
+const MULTICAST_CHECK_INTERVAL_SECS: u64 = 5;
+const MULTICAST_RESOLVE_INTERVAL_SECS: u64 = 300;
+const MULTICAST_RETRY_DELAY_SECS: u64 = 10;
+// Hosts answer the group specific query of the querier within a second after a leave.
+const IGMP_LEAVE_GRACE_SECS: u64 = 3;
+const IGMP_READ_TIMEOUT_SECS: u64 = 1;
+const IGMP_PROTOCOL: i32 = 2;
+const IGMP_ALL_ROUTERS: Ipv4Addr = Ipv4Addr::new(224, 0, 0, 2);
+const IGMP_V3_REPORTS: Ipv4Addr = Ipv4Addr::new(224, 0, 0, 22);
+
+const TS_PACKET_SIZE: usize = 188;
+/// Seven transport stream packets fit into an ethernet frame.
+const MULTICAST_PAYLOAD_SIZE: usize = 7 * TS_PACKET_SIZE;
+const RTP_HEADER_SIZE: usize = 12;
+const RTP_VERSION: u8 = 2 << 6;
+const RTP_PAYLOAD_TYPE_MP2T: u8 = 33;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum IgmpMessage {
+    Report(Ipv4Addr),
+    Leave(Ipv4Addr),
+}
+
+/// Returns the source address and the IGMP payload of a packet received on a raw socket.
+fn parse_ipv4_packet(packet: &[u8]) -> Option<(Ipv4Addr, &[u8])> {
+    let header_len = usize::from(packet.first()? & 0x0f) * 4;
+    if packet.len() < header_len || header_len < 20 || packet[0] >> 4 != 4 || i32::from(packet[9]) != IGMP_PROTOCOL {
+        return None;
+    }
+    let source = Ipv4Addr::new(packet[12], packet[13], packet[14], packet[15]);
+    Some((source, &packet[header_len..]))
+}
+
+fn read_ipv4(data: &[u8], offset: usize) -> Option<Ipv4Addr> {
+    let bytes: [u8; 4] = data.get(offset..offset + 4)?.try_into().ok()?;
+    Some(Ipv4Addr::from(bytes))
+}
+
+/// Parses the membership reports and leaves of IGMP v1, v2 and v3. Queries are ignored.
+fn parse_igmp_message(igmp: &[u8]) -> Vec<IgmpMessage> {
+    let mut messages = Vec::new();
+    match igmp.first() {
+        Some(0x12 | 0x16) => {
+            if let Some(group) = read_ipv4(igmp, 4) {
+                messages.push(IgmpMessage::Report(group));
+            }
+        }
+        Some(0x17) => {
+            if let Some(group) = read_ipv4(igmp, 4) {
+                messages.push(IgmpMessage::Leave(group));
+            }
+        }
+        Some(0x22) => {
+            let Some(record_count) = igmp.get(6..8).map(|count| u16::from_be_bytes([count[0], count[1]])) else {
+                return messages;
+            };
+            let mut offset = 8;
+            for _ in 0..record_count {
+                let (Some(record_type), Some(aux_len), Some(source_count), Some(group)) = (
+                    igmp.get(offset),
+                    igmp.get(offset + 1),
+                    igmp.get(offset + 2..offset + 4).map(|count| usize::from(u16::from_be_bytes([count[0], count[1]]))),
+                    read_ipv4(igmp, offset + 4),
+                ) else {
+                    break;
+                };
+                // An exclude record joins the group, an include record without sources leaves it.
+                match (record_type, source_count) {
+                    (2 | 4, _) | (1 | 3 | 5, 1..) => messages.push(IgmpMessage::Report(group)),
+                    (3, 0) => messages.push(IgmpMessage::Leave(group)),
+                    _ => {}
+                }
+                offset += 8 + source_count * 4 + usize::from(*aux_len) * 4;
+            }
+        }
+        _ => {}
+    }
+    messages
+}
+
+struct GroupMembership {
+    last_report: Instant,
+    left_at: Option<Instant>,
+}
+
+#[derive(Default)]
+struct IgmpMemberships {
+    groups: HashMap<Ipv4Addr, GroupMembership>,
+    known_groups: HashSet<Ipv4Addr>,
+    local_addrs: HashSet<Ipv4Addr>,
+}
+
+impl IgmpMemberships {
+    fn apply(&mut self, source: Ipv4Addr, message: IgmpMessage, now: Instant) -> bool {
+        // the reports of our own group memberships are no viewers
+        if self.local_addrs.contains(&source) {
+            return false;
+        }
+        match message {
+            IgmpMessage::Report(group) if self.known_groups.contains(&group) => {
+                self.groups.insert(group, GroupMembership { last_report: now, left_at: None });
+                true
+            }
+            IgmpMessage::Leave(group) => {
+                if let Some(membership) = self.groups.get_mut(&group) {
+                    membership.left_at = Some(now);
+                }
+                false
+            }
+            IgmpMessage::Report(_) => false,
+        }
+    }
+
+    fn is_watched(&self, group: Ipv4Addr, now: Instant, idle_timeout: Duration) -> bool {
+        self.groups.get(&group).is_some_and(|membership| match membership.left_at {
+            Some(left_at) => now.duration_since(left_at) < Duration::from_secs(IGMP_LEAVE_GRACE_SECS),
+            None => now.duration_since(membership.last_report) < idle_timeout,
+        })
+    }
+}
+
+/// Listens on a raw socket for the IGMP messages of the set-top boxes joining and leaving the groups.
+struct IgmpListener {
+    socket: Arc<Socket>,
+    joined: HashSet<(Ipv4Addr, Ipv4Addr)>,
+    memberships: Arc<Mutex<IgmpMemberships>>,
+    stop: CancellationToken,
+}
+
+impl IgmpListener {
+    fn start(notify: Arc<Notify>) -> std::io::Result<Self> {
+        let socket = Socket::new(Domain::IPV4, Type::RAW, Some(Protocol::from(IGMP_PROTOCOL)))?;
+        socket.set_read_timeout(Some(Duration::from_secs(IGMP_READ_TIMEOUT_SECS)))?;
+        let socket = Arc::new(socket);
+        let memberships = Arc::new(Mutex::new(IgmpMemberships::default()));
+        let stop = CancellationToken::new();
+        {
+            let socket = Arc::clone(&socket);
+            let memberships = Arc::clone(&memberships);
+            let stop = stop.clone();
+            tokio::task::spawn_blocking(move || {
+                let mut buf = [0u8; 2048];
+                while !stop.is_cancelled() {
+                    let len = match (&*socket).read(&mut buf) {
+                        Ok(len) => len,
+                        Err(err)
+                            if matches!(err.kind(), std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut) =>
+                        {
+                            continue;
+                        }
+                        Err(err) => {
+                            warn!("Multicast IGMP listener stopped: {err}");
+                            break;
+                        }
+                    };
+                    let Some((source, igmp)) = parse_ipv4_packet(&buf[..len]) else {
+                        continue;
+                    };
+                    let now = Instant::now();
+                    let mut joined = false;
+                    {
+                        let mut memberships = memberships.lock();
+                        for message in parse_igmp_message(igmp) {
+                            joined |= memberships.apply(source, message, now);
+                        }
+                    }
+                    if joined {
+                        notify.notify_one();
+                    }
+                }
+            });
+        }
+        let mut listener = Self { socket, joined: HashSet::new(), memberships, stop };
+        for group in [IGMP_ALL_ROUTERS, IGMP_V3_REPORTS] {
+            listener.join(group, Ipv4Addr::UNSPECIFIED);
+        }
+        Ok(listener)
+    }
+
+    // IGMP v2 reports are sent to the group itself, they only arrive when the host is a member of the group.
+    fn join(&mut self, group: Ipv4Addr, interface: Ipv4Addr) {
+        if self.joined.contains(&(group, interface)) {
+            return;
+        }
+        match self.socket.join_multicast_v4(&group, &interface) {
+            Ok(()) => {
+                self.joined.insert((group, interface));
+            }
+            Err(err) => warn!("Failed to join multicast group {group} for IGMP: {err}"),
+        }
+    }
+
+    fn update_groups(&mut self, groups: &[(Ipv4Addr, Ipv4Addr)]) {
+        for (group, interface) in groups {
+            self.join(*group, *interface);
+        }
+        let known_groups: HashSet<Ipv4Addr> = groups.iter().map(|(group, _)| *group).collect();
+        let mut memberships = self.memberships.lock();
+        memberships.groups.retain(|group, _| known_groups.contains(group));
+        memberships.known_groups = known_groups;
+        memberships.local_addrs = groups
+            .iter()
+            .map(|(_, interface)| *interface)
+            .filter(|interface| !interface.is_unspecified())
+            .chain(default_multicast_interface_addr())
+            .collect();
+    }
+
+    fn is_watched(&self, group: Ipv4Addr, now: Instant, idle_timeout: Duration) -> bool {
+        self.memberships.lock().is_watched(group, now, idle_timeout)
+    }
+}
+
+impl Drop for IgmpListener {
+    fn drop(&mut self) { self.stop.cancel(); }
+}
+
+/// The address the system sends multicast from when no interface is configured.
+/// Connecting a udp socket sends no packet, it only selects the route.
+fn default_multicast_interface_addr() -> Option<Ipv4Addr> {
+    let socket = StdUdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).ok()?;
+    socket.connect((IGMP_V3_REPORTS, 9)).ok()?;
+    match socket.local_addr().ok()? {
+        SocketAddr::V4(addr) if !addr.ip().is_unspecified() => Some(*addr.ip()),
+        _ => None,
+    }
+}
+
+fn create_rtp_header(sequence: u16, timestamp: u32, ssrc: u32) -> [u8; RTP_HEADER_SIZE] {
+    let mut header = [0u8; RTP_HEADER_SIZE];
+    header[0] = RTP_VERSION;
+    header[1] = RTP_PAYLOAD_TYPE_MP2T;
+    header[2..4].copy_from_slice(&sequence.to_be_bytes());
+    header[4..8].copy_from_slice(&timestamp.to_be_bytes());
+    header[8..12].copy_from_slice(&ssrc.to_be_bytes());
+    header
+}
+
+fn create_multicast_socket(interface: Option<Ipv4Addr>, ttl: u8) -> std::io::Result<UdpSocket> {
+    let socket = Socket::new(Domain::IPV4, Type::DGRAM, Some(Protocol::UDP))?;
+    socket.set_multicast_ttl_v4(u32::from(ttl))?;
+    let bind_addr = interface.unwrap_or(Ipv4Addr::UNSPECIFIED);
+    if let Some(interface) = interface {
+        socket.set_multicast_if_v4(&interface)?;
+    }
+    socket.bind(&SocketAddr::from((bind_addr, 0)).into())?;
+    socket.set_nonblocking(true)?;
+    UdpSocket::from_std(socket.into())
+}
+
+/// Sends the transport stream in datagrams of seven packets, with rtp each datagram gets an rtp header
+/// with a 90 kHz timestamp.
+async fn run_multicast_sender(
+    mut stream: BoxedProviderStream,
+    socket: UdpSocket,
+    group: SocketAddrV4,
+    rtp: bool,
+    stop: CancellationToken,
+) {
+    let started = Instant::now();
+    let ssrc = fastrand::u32(..);
+    let mut sequence = fastrand::u16(..);
+    let mut pending = BytesMut::with_capacity(MULTICAST_PAYLOAD_SIZE * 8);
+    let mut datagram = Vec::with_capacity(RTP_HEADER_SIZE + MULTICAST_PAYLOAD_SIZE);
+    loop {
+        let chunk: Bytes = tokio::select! {
+            () = stop.cancelled() => break,
+            chunk = stream.next() => match chunk {
+                Some(Ok(chunk)) => chunk,
+                Some(Err(err)) => {
+                    debug!("Multicast stream for {group} failed: {err}");
+                    break;
+                }
+                None => break,
+            },
+        };
+        pending.extend_from_slice(&chunk);
+        while pending.len() >= MULTICAST_PAYLOAD_SIZE {
+            let payload = pending.split_to(MULTICAST_PAYLOAD_SIZE);
+            datagram.clear();
+            if rtp {
+                #[allow(clippy::cast_possible_truncation)]
+                let timestamp = (started.elapsed().as_micros() * 9 / 100) as u32;
+                datagram.extend_from_slice(&create_rtp_header(sequence, timestamp, ssrc));
+                sequence = sequence.wrapping_add(1);
+            }
+            datagram.extend_from_slice(&payload);
+            if let Err(err) = socket.send_to(&datagram, group).await {
+                warn!("Failed to send multicast datagram to {group}: {err}");
+                return;
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct MulticastKey {
+    target_id: u16,
+    virtual_id: u32,
+    group: SocketAddrV4,
+    rtp: bool,
+    interface: Option<Ipv4Addr>,
+    ttl: u8,
+}
+
+impl MulticastKey {
+    // The group is unique over all targets, it identifies the connection of the channel.
+    fn connection_addr(&self) -> SocketAddr { SocketAddr::V4(self.group) }
+}
+
+struct MulticastSender {
+    stream_url: Arc<str>,
+    stop: CancellationToken,
+    task: JoinHandle<()>,
+}
+
+struct ResolvedTarget {
+    resolved_at: Instant,
+    configs: Vec<MulticastChannelConfig>,
+    channels: Vec<MulticastChannel>,
+}
+
+#[derive(Default)]
+struct MulticastState {
+    targets: HashMap<u16, ResolvedTarget>,
+    running: HashMap<MulticastKey, MulticastSender>,
+    failed: HashMap<MulticastKey, Instant>,
+    igmp: Option<IgmpListener>,
+    igmp_unavailable: bool,
+}
+
+async fn stop_multicast_sender(app_state: &Arc<AppState>, key: &MulticastKey, sender: MulticastSender) {
+    sender.stop.cancel();
+    sender.task.abort();
+    app_state.connection_manager.release_provider_connection(&key.connection_addr()).await;
+    debug!("Stopped multicast stream {} for {}", key.group, sender.stream_url);
+}
+
+async fn start_multicast_sender(
+    app_state: &Arc<AppState>,
+    target: &ConfigTarget,
+    key: MulticastKey,
+) -> Option<MulticastSender> {
+    let socket = match create_multicast_socket(key.interface, key.ttl) {
+        Ok(socket) => socket,
+        Err(err) => {
+            warn!("Failed to create multicast socket for {}: {err}", key.group);
+            return None;
+        }
+    };
+    let (stream, stream_url) = open_multicast_stream(app_state, target, key.virtual_id, key.connection_addr()).await?;
+    let stop = CancellationToken::new();
+    let task = tokio::spawn(run_multicast_sender(stream, socket, key.group, key.rtp, stop.clone()));
+    debug!("Started multicast stream {} of target {}", key.group, target.name);
+    Some(MulticastSender { stream_url, stop, task })
+}
+
+/// Resolves the channels of a target again when its config changed or the playlist may have been updated.
+async fn resolve_multicast_targets(
+    app_state: &Arc<AppState>,
+    state: &mut MulticastState,
+    targets: &[Arc<ConfigTarget>],
+    now: Instant,
+) {
+    state.targets.retain(|target_id, _| targets.iter().any(|target| target.id == *target_id));
+    for target in targets {
+        let Some(output) = target.get_multicast_output() else {
+            continue;
+        };
+        let is_current = state.targets.get(&target.id).is_some_and(|resolved| {
+            resolved.configs == output.channels
+                && now.duration_since(resolved.resolved_at) < Duration::from_secs(MULTICAST_RESOLVE_INTERVAL_SECS)
+        });
+        if !is_current {
+            let channels = multicast_resolve_channels(&app_state.app_config, target, output).await;
+            state
+                .targets
+                .insert(target.id, ResolvedTarget { resolved_at: now, configs: output.channels.clone(), channels });
+        }
+    }
+}
+
+/// Starts the IGMP listener while channels are streamed on demand and joins their groups.
+fn update_igmp_listener(state: &mut MulticastState, targets: &[Arc<ConfigTarget>], notify: &Arc<Notify>) {
+    let on_demand_groups: Vec<(Ipv4Addr, Ipv4Addr)> = targets
+        .iter()
+        .filter_map(|target| target.get_multicast_output())
+        .flat_map(|output| {
+            let interface = output.interface.unwrap_or(Ipv4Addr::UNSPECIFIED);
+            output
+                .channels
+                .iter()
+                .filter(|channel| !channel.always_on)
+                .map(move |channel| (*channel.address.ip(), interface))
+        })
+        .collect();
+    if on_demand_groups.is_empty() {
+        state.igmp = None;
+    } else if state.igmp.is_none() && !state.igmp_unavailable {
+        match IgmpListener::start(Arc::clone(notify)) {
+            Ok(listener) => state.igmp = Some(listener),
+            Err(err) => {
+                warn!(
+                    "Failed to listen for IGMP, multicast channels are always on. \
+                     Raw sockets need the CAP_NET_RAW capability: {err}"
+                );
+                state.igmp_unavailable = true;
+            }
+        }
+    }
+    if let Some(igmp) = state.igmp.as_mut() {
+        igmp.update_groups(&on_demand_groups);
+    }
+}
+
+async fn run_stream_multicast(app_state: &Arc<AppState>, state: &mut MulticastState, notify: &Arc<Notify>) {
+    let targets: Vec<Arc<ConfigTarget>> = app_state
+        .app_config
+        .sources
+        .load()
+        .sources
+        .iter()
+        .flat_map(|source| source.targets.iter())
+        .filter(|target| target.enabled && target.get_multicast_output().is_some())
+        .cloned()
+        .collect();
+
+    let now = Instant::now();
+    resolve_multicast_targets(app_state, state, &targets, now).await;
+    update_igmp_listener(state, &targets, notify);
+
+    let mut desired: HashMap<MulticastKey, Arc<ConfigTarget>> = HashMap::new();
+    for target in &targets {
+        let (Some(output), Some(resolved)) = (target.get_multicast_output(), state.targets.get(&target.id)) else {
+            continue;
+        };
+        let idle_timeout = Duration::from_secs(u64::from(output.idle_timeout_secs));
+        for channel in &resolved.channels {
+            let watched = channel.config.always_on
+                || state
+                    .igmp
+                    .as_ref()
+                    .is_none_or(|igmp| igmp.is_watched(*channel.config.address.ip(), now, idle_timeout));
+            if watched {
+                let key = MulticastKey {
+                    target_id: target.id,
+                    virtual_id: channel.virtual_id,
+                    group: channel.config.address,
+                    rtp: channel.config.rtp,
+                    interface: output.interface,
+                    ttl: output.ttl,
+                };
+                desired.insert(key, Arc::clone(target));
+            }
+        }
+    }
+
+    let stopped: Vec<MulticastKey> = state
+        .running
+        .iter()
+        .filter(|(key, sender)| sender.task.is_finished() || !desired.contains_key(key))
+        .map(|(key, _)| *key)
+        .collect();
+    for key in stopped {
+        if let Some(sender) = state.running.remove(&key) {
+            stop_multicast_sender(app_state, &key, sender).await;
+        }
+    }
+
+    state.failed.retain(|key, failed_at| {
+        desired.contains_key(key) && now.duration_since(*failed_at) < Duration::from_secs(MULTICAST_RETRY_DELAY_SECS)
+    });
+    for (key, target) in desired {
+        if state.running.contains_key(&key) || state.failed.contains_key(&key) {
+            continue;
+        }
+        if let Some(sender) = start_multicast_sender(app_state, &target, key).await {
+            state.running.insert(key, sender);
+        } else {
+            debug!("Failed to start multicast stream {} of target {}", key.group, target.name);
+            state.failed.insert(key, now);
+        }
+    }
+}
+
+async fn stop_stream_multicast(app_state: &Arc<AppState>, state: &mut MulticastState) {
+    for (key, sender) in std::mem::take(&mut state.running) {
+        stop_multicast_sender(app_state, &key, sender).await;
+    }
+    state.igmp = None;
+}
+
+pub(in crate::api) fn exec_stream_multicast(app_state: &Arc<AppState>, cancel_token: &CancellationToken) {
+    let app_state = Arc::clone(app_state);
+    let cancel = cancel_token.clone();
+    tokio::spawn(async move {
+        let notify = Arc::new(Notify::new());
+        let mut state = MulticastState::default();
+        let mut interval = tokio::time::interval(Duration::from_secs(MULTICAST_CHECK_INTERVAL_SECS));
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        let mut announced = false;
+        loop {
+            tokio::select! {
+                () = cancel.cancelled() => {
+                    stop_stream_multicast(&app_state, &mut state).await;
+                    info!("Multicast output loop stopped");
+                    break;
+                }
+                _ = interval.tick() => {}
+                () = notify.notified() => {}
+            }
+            run_stream_multicast(&app_state, &mut state, &notify).await;
+            if !announced && !state.targets.is_empty() {
+                info!("Multicast output started for {} target(s)", state.targets.len());
+                announced = true;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        create_rtp_header, parse_igmp_message, parse_ipv4_packet, IgmpMemberships, IgmpMessage, IGMP_LEAVE_GRACE_SECS,
+    };
+    use std::net::Ipv4Addr;
+    use std::time::{Duration, Instant};
+
+    const GROUP: Ipv4Addr = Ipv4Addr::new(239, 1, 1, 1);
+
+    #[test]
+    fn igmp_v2_report_and_leave_are_parsed() {
+        let mut packet = vec![0x46, 0, 0, 32, 0, 0, 0, 0, 1, 2, 0, 0, 192, 168, 1, 20, 239, 1, 1, 1, 0x94, 4, 0, 0];
+        packet.extend_from_slice(&[0x16, 0, 0, 0, 239, 1, 1, 1]);
+        let (source, igmp) = parse_ipv4_packet(&packet).unwrap();
+        assert_eq!(source, Ipv4Addr::new(192, 168, 1, 20));
+        assert_eq!(parse_igmp_message(igmp), vec![IgmpMessage::Report(GROUP)]);
+        assert_eq!(parse_igmp_message(&[0x17, 0, 0, 0, 239, 1, 1, 1]), vec![IgmpMessage::Leave(GROUP)]);
+        // queries are no membership messages
+        assert!(parse_igmp_message(&[0x11, 100, 0, 0, 0, 0, 0, 0]).is_empty());
+    }
+
+    #[test]
+    fn igmp_v3_records_are_parsed() {
+        let report = [
+            0x22, 0, 0, 0, 0, 0, 0, 3, // header with three records
+            4, 0, 0, 0, 239, 1, 1, 1, // change to exclude {} joins
+            3, 0, 0, 0, 239, 1, 1, 2, // change to include {} leaves
+            1, 0, 0, 1, 239, 1, 1, 3, 10, 0, 0, 1, // include with a source joins
+        ];
+        assert_eq!(
+            parse_igmp_message(&report),
+            vec![
+                IgmpMessage::Report(GROUP),
+                IgmpMessage::Leave(Ipv4Addr::new(239, 1, 1, 2)),
+                IgmpMessage::Report(Ipv4Addr::new(239, 1, 1, 3)),
+            ]
+        );
+        // truncated records are skipped
+        assert!(parse_igmp_message(&report[..12]).is_empty());
+    }
+
+    #[test]
+    fn igmp_memberships_expire_and_leave_with_grace() {
+        let mut memberships = IgmpMemberships::default();
+        memberships.known_groups.insert(GROUP);
+        memberships.local_addrs.insert(Ipv4Addr::new(192, 168, 1, 2));
+        let now = Instant::now();
+        let idle_timeout = Duration::from_secs(260);
+
+        assert!(!memberships.apply(Ipv4Addr::new(192, 168, 1, 2), IgmpMessage::Report(GROUP), now));
+        let unknown_group = Ipv4Addr::new(239, 9, 9, 9);
+        assert!(!memberships.apply(Ipv4Addr::new(192, 168, 1, 20), IgmpMessage::Report(unknown_group), now));
+        assert!(!memberships.is_watched(GROUP, now, idle_timeout));
+
+        assert!(memberships.apply(Ipv4Addr::new(192, 168, 1, 20), IgmpMessage::Report(GROUP), now));
+        assert!(memberships.is_watched(GROUP, now + Duration::from_secs(259), idle_timeout));
+        assert!(!memberships.is_watched(GROUP, now + idle_timeout, idle_timeout));
+
+        memberships.apply(Ipv4Addr::new(192, 168, 1, 20), IgmpMessage::Leave(GROUP), now);
+        assert!(memberships.is_watched(GROUP, now, idle_timeout));
+        assert!(!memberships.is_watched(GROUP, now + Duration::from_secs(IGMP_LEAVE_GRACE_SECS), idle_timeout));
+    }
+
+    #[test]
+    fn rtp_header_is_created() {
+        assert_eq!(
+            create_rtp_header(0x0102, 0x0304_0506, 0x0708_090a),
+            [0x80, 33, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a]
+        );
+    }
+}
@@ -0,0 +1,271 @@
+use crate::api::{
+    api_utils::open_prewarm_stream,
+    model::{discover_history_days, visit_history_day_records, AppState},
+};
+use crate::repository::{now_utc_secs, EventType};
+use chrono::{DateTime, Datelike, TimeZone, Timelike, Utc};
+use chrono_tz::Tz;
+use log::{debug, info, warn};
+use shared::model::{AccessScheduleDay, PlaylistItemType, StreamPrewarmConfigDto};
+use std::{
+    collections::{HashMap, HashSet},
+    net::{Ipv4Addr, SocketAddr},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+use tokio_util::sync::CancellationToken;
+
+const PREWARM_CHECK_INTERVAL_SECS: u64 = 60;
+const SECS_PER_DAY: u64 = 86_400;
+
+/// Target id and virtual id of a pre-warmed channel.
+type PrewarmChannel = (u16, u32);
+
+#[derive(Default)]
+struct PrewarmState {
+    running: HashMap<PrewarmChannel, Arc<str>>,
+    // The ranking is computed once per local hour.
+    ranking: Option<(String, Vec<PrewarmChannel>)>,
+    next_port: u16,
+}
+
+impl PrewarmState {
+    // Every pre-warmed stream needs its own connection address, 0.0.0.0 is never a client address.
+    fn next_addr(&mut self) -> SocketAddr {
+        self.next_port = self.next_port.wrapping_add(1).max(1);
+        SocketAddr::from((Ipv4Addr::UNSPECIFIED, self.next_port))
+    }
+}
+
+fn prewarm_timezone(prewarm: &StreamPrewarmConfigDto) -> Tz {
+    prewarm.timezone.as_deref().and_then(|name| name.parse::<Tz>().ok()).unwrap_or(Tz::UTC)
+}
+
+fn is_prewarm_window_open(prewarm: &StreamPrewarmConfigDto, now: DateTime<Utc>) -> bool {
+    if prewarm.windows.is_empty() {
+        return true;
+    }
+    let local_now = now.with_timezone(&prewarm_timezone(prewarm));
+    let day = AccessScheduleDay::from_index(local_now.weekday().num_days_from_monday());
+    #[allow(clippy::cast_possible_truncation)]
+    let minute_of_day = (local_now.hour() * 60 + local_now.minute()) as u16;
+    prewarm.windows.iter().any(|window| window.contains(day, minute_of_day))
+}
+
+/// Returns the `top_n` channels with the most views, ties are ordered by target and virtual id.
+fn rank_channels<I: IntoIterator<Item = PrewarmChannel>>(views: I, top_n: usize) -> Vec<PrewarmChannel> {
+    let mut counts: HashMap<PrewarmChannel, u64> = HashMap::new();
+    for channel in views {
+        *counts.entry(channel).or_default() += 1;
+    }
+    let mut ranked: Vec<(PrewarmChannel, u64)> = counts.into_iter().collect();
+    ranked.sort_by(|(lhs, lhs_count), (rhs, rhs_count)| rhs_count.cmp(lhs_count).then_with(|| lhs.cmp(rhs)));
+    ranked.into_iter().take(top_n).map(|(channel, _)| channel).collect()
+}
+
+/// Ranks the live channels by their connects in the current local hour of the last `lookback_days`.
+fn rank_history_channels(
+    history_dir: &Path,
+    tz: Tz,
+    hour: u32,
+    lookback_days: u16,
+    top_n: usize,
+) -> Vec<PrewarmChannel> {
+    let since_ts = now_utc_secs().saturating_sub(u64::from(lookback_days) * SECS_PER_DAY);
+    let since_day = i64::try_from(since_ts)
+        .ok()
+        .and_then(|ts| DateTime::from_timestamp(ts, 0))
+        .map(|ts| ts.format("%Y-%m-%d").to_string())
+        .unwrap_or_default();
+    let days = match discover_history_days(history_dir) {
+        Ok(days) => days,
+        Err(err) => {
+            warn!("Failed to read stream history for pre-warming: {err}");
+            return Vec::new();
+        }
+    };
+
+    let mut views = Vec::new();
+    for day in days.iter().filter(|day| day.as_str() >= since_day.as_str()) {
+        let result = visit_history_day_records(history_dir, day, |record| {
+            if record.event_type != EventType::Connect
+                || record.event_ts_utc < since_ts
+                || record.item_type.as_deref() != Some(PlaylistItemType::Live.as_str())
+            {
+                return;
+            }
+            let (Some(target_id), Some(virtual_id)) = (record.target_id, record.virtual_id) else {
+                return;
+            };
+            let local_hour = i64::try_from(record.event_ts_utc)
+                .ok()
+                .and_then(|ts| tz.timestamp_opt(ts, 0).single())
+                .map(|ts| ts.hour());
+            if local_hour == Some(hour) {
+                views.push((target_id, virtual_id));
+            }
+        });
+        if let Err(err) = result {
+            warn!("Failed to read stream history day {day} for pre-warming: {err}");
+        }
+    }
+    rank_channels(views, top_n)
+}
+
+async fn desired_channels(
+    app_state: &Arc<AppState>,
+    prewarm: &StreamPrewarmConfigDto,
+    state: &mut PrewarmState,
+) -> Vec<PrewarmChannel> {
+    if !prewarm.channels.is_empty() {
+        return prewarm
+            .channels
+            .iter()
+            .filter_map(|channel| {
+                let target = app_state.app_config.get_target_by_name(&channel.target);
+                if target.is_none() {
+                    warn!("Unknown pre-warm target {}", channel.target);
+                }
+                target.map(|target| (target.id, channel.virtual_id))
+            })
+            .collect();
+    }
+
+    let history_dir = {
+        let config = app_state.app_config.config.load();
+        let history = config.reverse_proxy.as_ref().and_then(|rp| rp.stream_history.as_ref());
+        match history {
+            Some(history) if history.stream_history_enabled => PathBuf::from(&history.stream_history_directory),
+            _ => return Vec::new(),
+        }
+    };
+    let tz = prewarm_timezone(prewarm);
+    let local_now = Utc::now().with_timezone(&tz);
+    let hour_key = local_now.format("%Y-%m-%d %H").to_string();
+    if let Some((key, ranking)) = state.ranking.as_ref() {
+        if *key == hour_key {
+            return ranking.clone();
+        }
+    }
+
+    let hour = local_now.hour();
+    let lookback_days = prewarm.lookback_days;
+    let top_n = usize::from(prewarm.top_n);
+    let ranking =
+        tokio::task::spawn_blocking(move || rank_history_channels(&history_dir, tz, hour, lookback_days, top_n))
+            .await
+            .unwrap_or_default();
+    state.ranking = Some((hour_key, ranking.clone()));
+    ranking
+}
+
+async fn run_stream_prewarm(app_state: &Arc<AppState>, state: &mut PrewarmState) {
+    let prewarm = app_state
+        .app_config
+        .config
+        .load()
+        .reverse_proxy
+        .as_ref()
+        .and_then(|rp| rp.stream.as_ref())
+        .and_then(|stream| stream.prewarm.clone());
+
+    let desired = match prewarm.as_ref() {
+        Some(prewarm) if is_prewarm_window_open(prewarm, Utc::now()) => {
+            desired_channels(app_state, prewarm, state).await
+        }
+        _ => Vec::new(),
+    };
+    let desired_set: HashSet<PrewarmChannel> = desired.iter().copied().collect();
+
+    let mut stopped = Vec::new();
+    for (channel, stream_url) in &state.running {
+        let is_warm = app_state
+            .shared_stream_manager
+            .get_shared_state(stream_url)
+            .await
+            .is_some_and(|shared_state| shared_state.is_prewarmed());
+        if !is_warm || !desired_set.contains(channel) {
+            stopped.push(*channel);
+        }
+    }
+    for channel in stopped {
+        if let Some(stream_url) = state.running.remove(&channel) {
+            app_state.shared_stream_manager.release_prewarmed_stream(&stream_url).await;
+        }
+    }
+
+    let Some(prewarm) = prewarm else {
+        return;
+    };
+    let reserve_slots = usize::from(prewarm.reserve_slots);
+    for channel in desired {
+        if state.running.contains_key(&channel) {
+            continue;
+        }
+        let (target_id, virtual_id) = channel;
+        let Some(target) = app_state.app_config.get_target_by_id(target_id) else {
+            continue;
+        };
+        let addr = state.next_addr();
+        if let Some(stream_url) = open_prewarm_stream(app_state, &target, virtual_id, addr, reserve_slots).await {
+            debug!("Pre-warming channel {virtual_id} of target {}", target.name);
+            state.running.insert(channel, stream_url);
+        }
+    }
+}
+
+async fn stop_stream_prewarm(app_state: &Arc<AppState>, state: &mut PrewarmState) {
+    for (_, stream_url) in state.running.drain() {
+        app_state.shared_stream_manager.release_prewarmed_stream(&stream_url).await;
+    }
+}
+
+pub(in crate::api) fn exec_stream_prewarm(app_state: &Arc<AppState>, cancel_token: &CancellationToken) {
+    let app_state = Arc::clone(app_state);
+    let cancel = cancel_token.clone();
+    tokio::spawn(async move {
+        let mut state = PrewarmState::default();
+        let mut interval = tokio::time::interval(Duration::from_secs(PREWARM_CHECK_INTERVAL_SECS));
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        loop {
+            tokio::select! {
+                () = cancel.cancelled() => {
+                    stop_stream_prewarm(&app_state, &mut state).await;
+                    info!("Stream pre-warming loop stopped");
+                    break;
+                }
+                _ = interval.tick() => {}
+            }
+            run_stream_prewarm(&app_state, &mut state).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_prewarm_window_open, rank_channels};
+    use chrono::{TimeZone, Utc};
+    use shared::model::{AccessWindowDto, StreamPrewarmConfigDto};
+
+    #[test]
+    fn rank_channels_orders_by_views() {
+        let views = [(1, 10), (1, 20), (1, 20), (2, 5), (2, 5), (1, 30)];
+        assert_eq!(rank_channels(views, 3), vec![(1, 20), (2, 5), (1, 10)]);
+        assert_eq!(rank_channels(views, 1), vec![(1, 20)]);
+    }
+
+    #[test]
+    fn prewarm_window_uses_configured_timezone() {
+        let mut prewarm = StreamPrewarmConfigDto::default();
+        // Monday 2026-03-02 17:30 UTC is 18:30 in Berlin.
+        let now = Utc.with_ymd_and_hms(2026, 3, 2, 17, 30, 0).unwrap();
+        assert!(is_prewarm_window_open(&prewarm, now));
+
+        prewarm.windows =
+            vec![AccessWindowDto { days: Vec::new(), from: "18:00".to_string(), to: "23:00".to_string() }];
+        assert!(!is_prewarm_window_open(&prewarm, now));
+        prewarm.timezone = Some("Europe/Berlin".to_string());
+        assert!(is_prewarm_window_open(&prewarm, now));
+    }
+}
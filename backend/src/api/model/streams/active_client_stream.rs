@@ -1,11 +1,12 @@
 use crate::{
     api::{
-        api_utils::get_stream_options,
+        api_utils::{get_stream_options, get_stream_throttle_kbps},
         model::{
             connection_manager::{PROVIDER_END_NOT_SET, PROVIDER_END_CLOSED, PROVIDER_END_ERROR},
             create_provider_stream, AppState, BoxedProviderStream, CleanupEvent, ConnectionManager,
             CustomVideoStreamType, EventManager, MeteringStream, ProviderHandle, ProviderStreamFactoryOptions,
             StreamDetails, StreamError, StreamMeterHandle, TimedClientStream, TransportStreamBuffer,
+            TsQualityInspector,
         },
         panel_api::{can_provision_on_exhausted, find_input_by_provider_name, run_panel_api_provisioning_probe},
     },
@@ -166,6 +167,10 @@ struct ActiveClientStreamState {
     provider_http_status: Option<u16>,
     /// Count of successful provider reconnections during this session (grace period / deferred open).
     provider_reconnect_count: AtomicU8,
+    /// Count of stalled live provider stream segments, shared with the reconnecting provider stream.
+    provider_stall_count: Option<Arc<AtomicU8>>,
+    /// Transport-level metrics of the provider packets delivered to the client (live streams only).
+    ts_quality: Option<TsQualityInspector>,
 }
 
 impl ActiveClientStreamState {
@@ -176,7 +181,7 @@ impl ActiveClientStreamState {
             CustomVideoStreamType::ProviderConnectionsExhausted => Some(StreamMode::ProviderExhausted),
             CustomVideoStreamType::LowPriorityPreempted => Some(StreamMode::LowPriorityPreempted),
             CustomVideoStreamType::Provisioning => Some(StreamMode::Provisioning),
-            CustomVideoStreamType::UserAccountExpired => None,
+            CustomVideoStreamType::UserAccountExpired | CustomVideoStreamType::UserQuotaExhausted => None,
         }
     }
 
@@ -212,6 +217,10 @@ impl ActiveClientStreamState {
         }
     }
 
+    fn stall_count(&self) -> u8 {
+        self.provider_stall_count.as_ref().map_or(0, |count| count.load(Ordering::Relaxed))
+    }
+
     fn release_user_stream(&mut self) {
         if self.user_stream_released {
             return;
@@ -221,6 +230,8 @@ impl ActiveClientStreamState {
             addr: self.fingerprint.addr,
             provider_end_reason: self.provider_end_reason.load(Ordering::Relaxed),
             reconnect_count: self.provider_reconnect_count.load(Ordering::Relaxed),
+            stall_count: self.stall_count(),
+            ts_quality: self.ts_quality.as_ref().and_then(TsQualityInspector::stats),
             provider_error_class: self.provider_error_class,
             provider_http_status: self.provider_http_status,
         });
@@ -451,6 +462,9 @@ fn create_deferred_provider_open_future(
         },
     );
     provider_stream_factory_options.set_provider(input.get_resolve_provider(stream_url.as_ref()));
+    if let Some(stall_count) = stream_details.stall_count.as_ref() {
+        provider_stream_factory_options.set_stall_count(Arc::clone(stall_count));
+    }
 
     Some(DeferredProviderOpenState::Pending(Box::new(DeferredProviderOpenContext {
         app_state: Arc::clone(app_state),
@@ -570,7 +584,12 @@ impl Stream for ActiveClientStream {
                     }
 
                     match self.state.inner.as_mut().map(|inner| Pin::new(inner).poll_next(cx)) {
-                        Some(Poll::Ready(Some(Ok(bytes)))) => return Poll::Ready(Some(Ok(bytes))),
+                        Some(Poll::Ready(Some(Ok(bytes)))) => {
+                            if let Some(inspector) = self.state.ts_quality.as_mut() {
+                                inspector.inspect(&bytes);
+                            }
+                            return Poll::Ready(Some(Ok(bytes)));
+                        }
                         Some(Poll::Ready(Some(Err(e)))) => {
                             error!("Inner stream error: {e:?}");
                             self.state.provider_error_class = Some(e.provider_error_class());
@@ -684,6 +703,8 @@ impl Drop for ActiveClientStream {
                 handle: handle_for_cleanup,
                 provider_end_reason: self.state.provider_end_reason.load(Ordering::Relaxed),
                 reconnect_count: self.state.provider_reconnect_count.load(Ordering::Relaxed),
+                stall_count: self.state.stall_count(),
+                ts_quality: self.state.ts_quality.as_ref().and_then(TsQualityInspector::stats),
                 provider_error_class: self.state.provider_error_class,
                 provider_http_status: self.state.provider_http_status,
             });
@@ -732,6 +753,7 @@ pub(crate) async fn create_active_client_stream(request: ActiveClientStreamParam
             stream_channel: &stream_channel,
             user_agent,
             session_token,
+            throttle_kbps: get_stream_throttle_kbps(app_state, user, stream_channel.item_type),
         })
         .await;
     if let Some((_, _, _m_, Some(cvt))) = stream_details.stream_info.as_ref() {
@@ -815,6 +837,8 @@ pub(crate) async fn create_active_client_stream(request: ActiveClientStreamParam
 
     let deferred_provider_open =
         create_deferred_provider_open_future(app_state, &stream_details, fingerprint, &stream_channel, req_headers);
+    let provider_stall_count = stream_details.stall_count.clone();
+    let ts_quality = stream_channel.item_type.is_live().then(TsQualityInspector::default);
     let timed_stream_context = deferred_provider_open
         .as_ref()
         .and_then(|_| create_timed_stream_context(app_state, virtual_id));
@@ -875,6 +899,8 @@ pub(crate) async fn create_active_client_stream(request: ActiveClientStreamParam
         provider_error_class: None,
         provider_http_status: None,
         provider_reconnect_count: AtomicU8::new(0),
+        provider_stall_count,
+        ts_quality,
     };
 
     ActiveClientStream { state }.boxed()
@@ -1212,8 +1238,17 @@ mod tests {
             http_client: Arc::new(ArcSwap::from_pointee(Client::new())),
             http_client_no_redirect: Arc::new(ArcSwap::from_pointee(Client::new())),
             downloads: Arc::new(DownloadQueue::new()),
+            user_quota: Arc::new(crate::api::model::UserQuotaManager::new_with_state_file(None)),
+            parental_unlocks: Arc::new(crate::api::model::ParentalUnlockManager::default()),
+            user_devices: Arc::new(crate::api::model::UserDeviceManager::new_with_state_file(None)),
+            user_travel: Arc::new(crate::api::model::UserTravelManager::new_with_state_file(None)),
+            channel_health: Arc::new(crate::api::model::ChannelHealthManager::new_with_state_file(None)),
+            login_bans: Arc::new(crate::api::model::LoginBanManager::new_with_state_file(None)),
+            user_rate_limits: Arc::new(crate::api::model::UserRateLimiter::default()),
             cache: Arc::new(ArcSwapOption::default()),
+            vod_cache: Arc::new(ArcSwapOption::default()),
             shared_stream_manager,
+            hls_segmenters: Arc::default(),
             active_users,
             active_provider,
             connection_manager,
@@ -1253,6 +1288,7 @@ mod tests {
             shared_joined_existing: None,
             shared_stream_id: None,
             technical: None,
+            alternate_input: None,
         }
     }
 
@@ -1279,6 +1315,7 @@ mod tests {
             provider_grace_active: true,
             disable_provider_grace: false,
             reconnect_flag: None,
+            stall_count: None,
             provider_handle: Some(provider_handle),
         }
     }
@@ -1364,6 +1401,8 @@ mod tests {
             provider_error_class: None,
             provider_http_status: None,
             provider_reconnect_count: AtomicU8::new(0),
+            provider_stall_count: None,
+            ts_quality: None,
         };
 
         let stream = ActiveClientStream { state };
@@ -1659,6 +1698,8 @@ mod tests {
             provider_error_class: None,
             provider_http_status: None,
             provider_reconnect_count: AtomicU8::new(0),
+            provider_stall_count: None,
+            ts_quality: None,
         };
         let stream = ActiveClientStream { state };
         pin_mut!(stream);
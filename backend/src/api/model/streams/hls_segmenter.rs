@@ -0,0 +1,580 @@
+use crate::utils::debug_if_enabled;
+use bytes::{Bytes, BytesMut};
+use futures::{Stream, StreamExt};
+use log::{debug, warn};
+use shared::{
+    model::{HlsPackagingConfigDto, HlsSegmentStorage},
+    utils::sanitize_sensitive_info,
+};
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt::Write,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
+};
+use tokio::{
+    sync::{watch, Mutex, RwLock},
+    time::{sleep, timeout, Duration, Instant},
+};
+
+const TS_PACKET_SIZE: usize = 188;
+const TS_SYNC_BYTE: u8 = 0x47;
+const PAT_PID: u16 = 0;
+const PTS_CLOCK: u64 = 90_000;
+const PTS_MASK: u64 = (1 << 33) - 1;
+// Segments stay downloadable a little longer than they are listed, for clients reading an older playlist.
+const RETAINED_EXTRA_SEGMENTS: usize = 2;
+const MIN_VIEWER_IDLE_SECS: u64 = 20;
+
+fn is_video_stream_type(stream_type: u8) -> bool { matches!(stream_type, 0x01 | 0x02 | 0x10 | 0x1B | 0x24 | 0x42) }
+
+fn is_audio_stream_type(stream_type: u8) -> bool {
+    matches!(stream_type, 0x03 | 0x04 | 0x0F | 0x11 | 0x81 | 0x87)
+}
+
+fn get_pid(packet: &[u8]) -> u16 { (u16::from(packet[1] & 0x1F) << 8) | u16::from(packet[2]) }
+
+/// Returns the payload of a TS packet, `None` for adaptation-only packets.
+fn get_payload(packet: &[u8]) -> Option<&[u8]> {
+    let adaptation_field_control = (packet[3] >> 4) & 0x03;
+    if adaptation_field_control & 0x01 == 0 {
+        return None;
+    }
+    let offset = if adaptation_field_control & 0x02 == 0 { 4 } else { 5 + usize::from(packet[4]) };
+    packet.get(offset..)
+}
+
+fn is_random_access(packet: &[u8]) -> bool { packet[3] & 0x20 != 0 && packet[4] > 0 && packet[5] & 0x40 != 0 }
+
+/// Returns the PSI section of a packet starting a table.
+fn get_section(payload: &[u8]) -> Option<&[u8]> {
+    let pointer = usize::from(*payload.first()?);
+    let section = payload.get(1 + pointer..)?;
+    let section_length = usize::from(u16::from(*section.get(1)? & 0x0F) << 8 | u16::from(*section.get(2)?));
+    // The CRC is not part of the entries.
+    section.get(..(3 + section_length).checked_sub(4)?)
+}
+
+fn get_pes_pts(payload: &[u8]) -> Option<u64> {
+    if payload.len() < 14 || payload[..3] != [0, 0, 1] || payload[7] & 0x80 == 0 {
+        return None;
+    }
+    let pts = &payload[9..14];
+    Some(
+        (u64::from(pts[0] >> 1) & 0x07) << 30
+            | u64::from(pts[1]) << 22
+            | u64::from(pts[2] >> 1) << 15
+            | u64::from(pts[3]) << 7
+            | u64::from(pts[4] >> 1),
+    )
+}
+
+/// A finished segment, the duration is in seconds.
+#[derive(Debug)]
+pub(in crate::api) struct TsSegment {
+    pub duration: f64,
+    pub data: Bytes,
+}
+
+/// Cuts an MPEG-TS stream into segments that start with PAT and PMT at a random access point of the
+/// first video stream, audio-only streams are cut at their PES starts.
+#[derive(Debug)]
+pub(in crate::api) struct TsSegmenter {
+    target_duration: u64,
+    remainder: BytesMut,
+    current: BytesMut,
+    pat: Option<Bytes>,
+    pmts: HashMap<u16, Bytes>,
+    timing_pid: Option<u16>,
+    timing_is_video: bool,
+    random_access_seen: bool,
+    segment_start_pts: Option<u64>,
+    last_pts: Option<u64>,
+}
+
+impl TsSegmenter {
+    pub fn new(segment_duration_secs: u16) -> Self {
+        Self {
+            target_duration: u64::from(segment_duration_secs) * PTS_CLOCK,
+            remainder: BytesMut::new(),
+            current: BytesMut::new(),
+            pat: None,
+            pmts: HashMap::new(),
+            timing_pid: None,
+            timing_is_video: false,
+            random_access_seen: false,
+            segment_start_pts: None,
+            last_pts: None,
+        }
+    }
+
+    /// Adds stream data and returns the segments finished by it.
+    pub fn push(&mut self, data: &[u8]) -> Vec<TsSegment> {
+        self.remainder.extend_from_slice(data);
+        let mut segments = Vec::new();
+        while self.remainder.len() >= TS_PACKET_SIZE {
+            if self.remainder[0] != TS_SYNC_BYTE {
+                let skip = self.remainder[1..]
+                    .iter()
+                    .position(|b| *b == TS_SYNC_BYTE)
+                    .map_or(self.remainder.len(), |pos| pos + 1);
+                let _ = self.remainder.split_to(skip);
+                continue;
+            }
+            let packet = self.remainder.split_to(TS_PACKET_SIZE).freeze();
+            if let Some(segment) = self.process_packet(&packet) {
+                segments.push(segment);
+            }
+        }
+        segments
+    }
+
+    /// Returns the unfinished last segment when the stream ends.
+    pub fn finish(&mut self) -> Option<TsSegment> {
+        let duration = self.segment_duration(self.last_pts?)?;
+        if duration == 0 {
+            return None;
+        }
+        self.segment_start_pts = None;
+        Some(TsSegment { duration: ticks_to_secs(duration), data: self.current.split().freeze() })
+    }
+
+    fn segment_duration(&self, pts: u64) -> Option<u64> {
+        self.segment_start_pts.map(|start| pts.wrapping_sub(start) & PTS_MASK)
+    }
+
+    fn process_packet(&mut self, packet: &Bytes) -> Option<TsSegment> {
+        let pid = get_pid(packet);
+        let unit_start = packet[1] & 0x40 != 0;
+        if unit_start {
+            if pid == PAT_PID {
+                self.update_pat(packet);
+            } else if self.pmts.contains_key(&pid) {
+                self.update_pmt(pid, packet);
+            }
+        }
+
+        let mut finished = None;
+        if unit_start && Some(pid) == self.timing_pid {
+            if let Some(pts) = get_payload(packet).and_then(get_pes_pts) {
+                let random_access = self.timing_is_video && is_random_access(packet);
+                self.random_access_seen |= random_access;
+                let cut_point = !self.timing_is_video || !self.random_access_seen || random_access;
+                match self.segment_duration(pts) {
+                    None if cut_point => self.start_segment(pts),
+                    Some(duration) if cut_point && duration >= self.target_duration => {
+                        finished = Some(TsSegment {
+                            duration: ticks_to_secs(duration),
+                            data: self.current.split().freeze(),
+                        });
+                        self.start_segment(pts);
+                    }
+                    _ => {}
+                }
+                self.last_pts = Some(pts);
+            }
+        }
+        if self.segment_start_pts.is_some() {
+            self.current.extend_from_slice(packet);
+        }
+        finished
+    }
+
+    fn start_segment(&mut self, pts: u64) {
+        self.segment_start_pts = Some(pts);
+        if let Some(pat) = &self.pat {
+            self.current.extend_from_slice(pat);
+        }
+        for pmt in self.pmts.values() {
+            self.current.extend_from_slice(pmt);
+        }
+    }
+
+    fn update_pat(&mut self, packet: &Bytes) {
+        let Some(section) = get_payload(packet).and_then(get_section) else {
+            return;
+        };
+        let mut pmt_pids = Vec::new();
+        for program in section.get(8..).unwrap_or_default().chunks_exact(4) {
+            let program_number = u16::from(program[0]) << 8 | u16::from(program[1]);
+            if program_number != 0 {
+                pmt_pids.push(u16::from(program[2] & 0x1F) << 8 | u16::from(program[3]));
+            }
+        }
+        self.pmts.retain(|pid, _| pmt_pids.contains(pid));
+        for pid in pmt_pids {
+            self.pmts.entry(pid).or_default();
+        }
+        self.pat = Some(packet.clone());
+    }
+
+    fn update_pmt(&mut self, pid: u16, packet: &Bytes) {
+        let Some(section) = get_payload(packet).and_then(get_section) else {
+            return;
+        };
+        self.pmts.insert(pid, packet.clone());
+        if self.timing_pid.is_some() || section.len() < 12 {
+            return;
+        }
+        let program_info_length = usize::from(u16::from(section[10] & 0x0F) << 8 | u16::from(section[11]));
+        let mut offset = 12 + program_info_length;
+        let mut audio_pid = None;
+        while offset + 5 <= section.len() {
+            let stream_type = section[offset];
+            let es_pid = u16::from(section[offset + 1] & 0x1F) << 8 | u16::from(section[offset + 2]);
+            let es_info_length =
+                usize::from(u16::from(section[offset + 3] & 0x0F) << 8 | u16::from(section[offset + 4]));
+            if is_video_stream_type(stream_type) {
+                self.timing_pid = Some(es_pid);
+                self.timing_is_video = true;
+                return;
+            }
+            if audio_pid.is_none() && is_audio_stream_type(stream_type) {
+                audio_pid = Some(es_pid);
+            }
+            offset += 5 + es_info_length;
+        }
+        self.timing_pid = audio_pid;
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn ticks_to_secs(ticks: u64) -> f64 { ticks as f64 / PTS_CLOCK as f64 }
+
+#[derive(Debug)]
+enum HlsSegmentData {
+    Memory(Bytes),
+    File(PathBuf),
+}
+
+#[derive(Debug)]
+struct HlsSegment {
+    sequence: u64,
+    duration: f64,
+    data: HlsSegmentData,
+}
+
+#[derive(Debug, Default)]
+struct HlsSegmentWindow {
+    segments: VecDeque<HlsSegment>,
+    next_sequence: u64,
+    finished: bool,
+}
+
+/// Rolling HLS window of one live channel, shared by all HLS viewers of the channel.
+#[derive(Debug)]
+pub(in crate::api) struct HlsSegmenter {
+    id: u32,
+    stream_url: Arc<str>,
+    window_size: usize,
+    temp_dir: Option<tempfile::TempDir>,
+    window: RwLock<HlsSegmentWindow>,
+    ready: watch::Sender<bool>,
+    last_access: Mutex<Instant>,
+}
+
+impl HlsSegmenter {
+    pub fn id(&self) -> u32 { self.id }
+
+    pub async fn touch(&self) { *self.last_access.lock().await = Instant::now(); }
+
+    async fn add_segment(&self, segment: TsSegment) {
+        let mut window = self.window.write().await;
+        let sequence = window.next_sequence;
+        let data = match &self.temp_dir {
+            Some(dir) => {
+                let path = dir.path().join(format!("{sequence}.ts"));
+                if let Err(err) = tokio::fs::write(&path, &segment.data).await {
+                    warn!("Failed to write hls segment {}: {err}", path.display());
+                    return;
+                }
+                HlsSegmentData::File(path)
+            }
+            None => HlsSegmentData::Memory(segment.data),
+        };
+        window.next_sequence += 1;
+        window.segments.push_back(HlsSegment { sequence, duration: segment.duration, data });
+        while window.segments.len() > self.window_size + RETAINED_EXTRA_SEGMENTS {
+            if let Some(HlsSegment { data: HlsSegmentData::File(path), .. }) = window.segments.pop_front() {
+                let _ = tokio::fs::remove_file(&path).await;
+            }
+        }
+        drop(window);
+        self.ready.send_replace(true);
+    }
+
+    async fn finish(&self) {
+        self.window.write().await.finished = true;
+        self.ready.send_replace(true);
+    }
+
+    /// Renders the media playlist, waits up to `wait` for the first segment.
+    /// Returns `None` when the stream ended without segments.
+    pub async fn playlist(&self, segment_url: &str, wait: Duration) -> Option<String> {
+        self.touch().await;
+        let mut ready = self.ready.subscribe();
+        let _ = timeout(wait, ready.wait_for(|ready| *ready)).await;
+        let window = self.window.read().await;
+        let start = window.segments.len().saturating_sub(self.window_size);
+        let listed: Vec<&HlsSegment> = window.segments.iter().skip(start).collect();
+        let first = listed.first()?;
+        let target_duration = listed.iter().map(|segment| segment.duration.ceil()).fold(1.0, f64::max);
+        let mut playlist = String::with_capacity(256 + listed.len() * (segment_url.len() + 32));
+        let _ = write!(
+            playlist,
+            "#EXTM3U\n#EXT-X-VERSION:3\n#EXT-X-TARGETDURATION:{target_duration}\n#EXT-X-MEDIA-SEQUENCE:{}\n",
+            first.sequence
+        );
+        for segment in listed {
+            let _ = write!(playlist, "#EXTINF:{:.3},\n{segment_url}/{}.ts\n", segment.duration, segment.sequence);
+        }
+        if window.finished {
+            playlist.push_str("#EXT-X-ENDLIST\n");
+        }
+        Some(playlist)
+    }
+
+    pub async fn segment(&self, sequence: u64) -> Option<Bytes> {
+        self.touch().await;
+        let data = {
+            let window = self.window.read().await;
+            match &window.segments.iter().find(|segment| segment.sequence == sequence)?.data {
+                HlsSegmentData::Memory(data) => return Some(data.clone()),
+                HlsSegmentData::File(path) => path.clone(),
+            }
+        };
+        tokio::fs::read(&data).await.ok().map(Bytes::from)
+    }
+
+    async fn run<S, E>(self: Arc<Self>, mut source: S, mut segmenter: TsSegmenter, idle_timeout: Duration)
+    where
+        S: Stream<Item = Result<Bytes, E>> + Unpin + Send,
+        E: std::fmt::Display + Send,
+    {
+        loop {
+            tokio::select! {
+                () = sleep(Duration::from_secs(1)) => {
+                    if self.last_access.lock().await.elapsed() > idle_timeout {
+                        debug_if_enabled!(
+                            "No hls viewers left, stopping hls packaging of {}",
+                            sanitize_sensitive_info(&self.stream_url)
+                        );
+                        break;
+                    }
+                }
+                chunk = source.next() => match chunk {
+                    Some(Ok(data)) => {
+                        for segment in segmenter.push(&data) {
+                            self.add_segment(segment).await;
+                        }
+                    }
+                    Some(Err(err)) => {
+                        debug!("Hls packaging source error {}: {err}", sanitize_sensitive_info(&self.stream_url));
+                        break;
+                    }
+                    None => {
+                        if let Some(segment) = segmenter.finish() {
+                            self.add_segment(segment).await;
+                        }
+                        break;
+                    }
+                }
+            }
+        }
+        self.finish().await;
+    }
+}
+
+/// Runs one `HlsSegmenter` per live channel, keyed by the provider stream url.
+#[derive(Debug, Default)]
+pub struct HlsSegmenterManager {
+    next_id: AtomicU32,
+    segmenters: RwLock<HashMap<Arc<str>, Arc<HlsSegmenter>>>,
+}
+
+impl HlsSegmenterManager {
+    pub(in crate::api) async fn get(&self, stream_url: &str) -> Option<Arc<HlsSegmenter>> {
+        self.segmenters.read().await.get(stream_url).map(Arc::clone)
+    }
+
+    pub(in crate::api) async fn get_by_id(&self, id: u32) -> Option<Arc<HlsSegmenter>> {
+        self.segmenters.read().await.values().find(|segmenter| segmenter.id == id).map(Arc::clone)
+    }
+
+    /// Starts packaging `source`, the segmenter stops when the source ends or all viewers left.
+    pub(in crate::api) async fn start<S, E>(
+        self: &Arc<Self>,
+        stream_url: &str,
+        packaging: &HlsPackagingConfigDto,
+        source: S,
+    ) -> Arc<HlsSegmenter>
+    where
+        S: Stream<Item = Result<Bytes, E>> + Unpin + Send + 'static,
+        E: std::fmt::Display + Send + 'static,
+    {
+        let temp_dir = if packaging.storage == HlsSegmentStorage::TempDir {
+            match tempfile::Builder::new().prefix("hls_").tempdir() {
+                Ok(dir) => Some(dir),
+                Err(err) => {
+                    warn!("Failed to create hls segment directory, keeping segments in memory: {err}");
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        let stream_key: Arc<str> = Arc::from(stream_url);
+        let segmenter = Arc::new(HlsSegmenter {
+            id: self.next_id.fetch_add(1, Ordering::Relaxed),
+            stream_url: Arc::clone(&stream_key),
+            window_size: usize::from(packaging.window_size),
+            temp_dir,
+            window: RwLock::new(HlsSegmentWindow::default()),
+            ready: watch::channel(false).0,
+            last_access: Mutex::new(Instant::now()),
+        });
+        self.segmenters.write().await.insert(stream_key, Arc::clone(&segmenter));
+        debug_if_enabled!("Started hls packaging of {}", sanitize_sensitive_info(stream_url));
+
+        let idle_timeout =
+            Duration::from_secs((u64::from(packaging.segment_duration_secs) * 3).max(MIN_VIEWER_IDLE_SECS));
+        let ts_segmenter = TsSegmenter::new(packaging.segment_duration_secs);
+        let manager = Arc::clone(self);
+        let task_segmenter = Arc::clone(&segmenter);
+        tokio::spawn(async move {
+            Arc::clone(&task_segmenter).run(source, ts_segmenter, idle_timeout).await;
+            manager.remove(&task_segmenter).await;
+        });
+        segmenter
+    }
+
+    async fn remove(&self, segmenter: &HlsSegmenter) {
+        let mut segmenters = self.segmenters.write().await;
+        if segmenters.get(&segmenter.stream_url).is_some_and(|current| current.id == segmenter.id) {
+            segmenters.remove(&segmenter.stream_url);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HlsSegmenterManager, TsSegmenter, TS_PACKET_SIZE, TS_SYNC_BYTE};
+    use bytes::Bytes;
+    use futures::stream;
+    use shared::model::HlsPackagingConfigDto;
+    use std::{convert::Infallible, sync::Arc};
+    use tokio::time::Duration;
+
+    const PMT_PID: u16 = 0x1000;
+    const VIDEO_PID: u16 = 0x100;
+
+    fn packet(pid: u16, unit_start: bool, payload: &[u8], random_access: bool) -> Vec<u8> {
+        let mut packet = vec![0xFF; TS_PACKET_SIZE];
+        packet[0] = TS_SYNC_BYTE;
+        packet[1] = (if unit_start { 0x40 } else { 0 }) | u8::try_from(pid >> 8).unwrap();
+        packet[2] = u8::try_from(pid & 0xFF).unwrap();
+        let offset = if random_access {
+            packet[3] = 0x30;
+            packet[4] = 1;
+            packet[5] = 0x40;
+            6
+        } else {
+            packet[3] = 0x10;
+            4
+        };
+        packet[offset..offset + payload.len()].copy_from_slice(payload);
+        packet
+    }
+
+    fn section(table_id: u8, entries: &[u8]) -> Vec<u8> {
+        let section_length = u8::try_from(5 + entries.len() + 4).unwrap();
+        let mut payload = vec![0, table_id, 0xB0, section_length, 0, 1, 0xC1, 0, 0];
+        payload.extend_from_slice(entries);
+        payload.extend_from_slice(&[0; 4]);
+        payload
+    }
+
+    fn program_packets() -> Vec<u8> {
+        let pat = section(0, &[0, 1, 0xE0 | u8::try_from(PMT_PID >> 8).unwrap(), 0]);
+        let pmt = section(2, &[0xE1, 0x00, 0xF0, 0, 0x1B, 0xE1, 0x00, 0xF0, 0]);
+        [packet(0, true, &pat, false), packet(PMT_PID, true, &pmt, false)].concat()
+    }
+
+    fn video_packet(pts: u64, random_access: bool) -> Vec<u8> {
+        let pes = [
+            0,
+            0,
+            1,
+            0xE0,
+            0,
+            0,
+            0x80,
+            0x80,
+            5,
+            u8::try_from(0x21 | ((pts >> 29) & 0x0E)).unwrap(),
+            u8::try_from((pts >> 22) & 0xFF).unwrap(),
+            u8::try_from(0x01 | ((pts >> 14) & 0xFE)).unwrap(),
+            u8::try_from((pts >> 7) & 0xFF).unwrap(),
+            u8::try_from(0x01 | ((pts << 1) & 0xFE)).unwrap(),
+        ];
+        packet(VIDEO_PID, true, &pes, random_access)
+    }
+
+    fn test_stream() -> Vec<u8> {
+        let mut data = program_packets();
+        // one frame per second, key frames every two seconds
+        for second in 0..7_u64 {
+            data.extend(video_packet(second * 90_000, second % 2 == 0));
+            data.extend(packet(VIDEO_PID, false, &[0; 8], false));
+        }
+        data
+    }
+
+    #[test]
+    fn test_segments_start_at_key_frames_with_program_tables() {
+        let data = test_stream();
+        let mut segmenter = TsSegmenter::new(2);
+        // feed in uneven chunks
+        let mut segments = Vec::new();
+        for chunk in data.chunks(100) {
+            segments.extend(segmenter.push(chunk));
+        }
+        segments.extend(segmenter.finish());
+
+        let durations: Vec<f64> = segments.iter().map(|segment| segment.duration).collect();
+        assert_eq!(durations, vec![2.0, 2.0, 2.0]);
+        for segment in &segments {
+            assert_eq!(segment.data.len() % TS_PACKET_SIZE, 0);
+            let pids: Vec<u16> = segment.data.chunks(TS_PACKET_SIZE).take(3).map(super::get_pid).collect();
+            assert_eq!(pids, vec![0, PMT_PID, VIDEO_PID]);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_shared_segmenter_serves_rolling_playlist() {
+        let manager = Arc::new(HlsSegmenterManager::default());
+        let packaging =
+            HlsPackagingConfigDto { segment_duration_secs: 2, window_size: 2, ..HlsPackagingConfigDto::default() };
+        let source = stream::iter(vec![Ok::<_, Infallible>(Bytes::from(test_stream()))]);
+        let segmenter = manager.start("http://provider/live/1.ts", &packaging, source).await;
+
+        let mut playlist = segmenter.playlist("/hls-segment/u/p/0", Duration::from_secs(5)).await.unwrap();
+        for _ in 0..50 {
+            if playlist.contains("#EXT-X-ENDLIST") {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            playlist = segmenter.playlist("/hls-segment/u/p/0", Duration::from_secs(5)).await.unwrap();
+        }
+        assert_eq!(
+            playlist,
+            "#EXTM3U\n#EXT-X-VERSION:3\n#EXT-X-TARGETDURATION:2\n#EXT-X-MEDIA-SEQUENCE:1\n\
+             #EXTINF:2.000,\n/hls-segment/u/p/0/1.ts\n#EXTINF:2.000,\n/hls-segment/u/p/0/2.ts\n#EXT-X-ENDLIST\n"
+        );
+        assert!(segmenter.segment(0).await.is_some());
+        assert!(segmenter.segment(3).await.is_none());
+    }
+}
@@ -1,6 +1,7 @@
 mod buffered_stream;
 mod client_stream;
 mod custom_video_stream;
+mod hls_segmenter;
 mod provisioning_stream;
 mod timed_client_stream;
 mod transport_stream_buffer;
@@ -12,13 +13,15 @@ mod provider_stream_factory;
 mod shared_stream_manager;
 mod metering_stream;
 mod throttled_stream;
+mod stall_detecting_stream;
+mod ts_quality_inspector;
 
 pub use self::persist_pipe_stream::*;
 pub(crate) use self::transport_stream_buffer::*;
 pub(in crate::api) use self::{
-    active_client_stream::*, custom_video_stream::*, metering_stream::*, provider_stream::*,
+    active_client_stream::*, custom_video_stream::*, hls_segmenter::*, metering_stream::*, provider_stream::*,
     provider_stream_factory::*, provisioning_stream::*, shared_stream_manager::*,
-    throttled_stream::*, timed_client_stream::*,
+    stall_detecting_stream::*, throttled_stream::*, timed_client_stream::*, ts_quality_inspector::*,
 };
 
 pub const STREAM_IDLE_TIMEOUT: u64 = 60;
@@ -24,6 +24,7 @@ pub enum CustomVideoStreamType {
     ProviderConnectionsExhausted,
     LowPriorityPreempted,
     UserAccountExpired,
+    UserQuotaExhausted,
     Provisioning,
 }
 
@@ -35,6 +36,7 @@ impl fmt::Display for CustomVideoStreamType {
             CustomVideoStreamType::ProviderConnectionsExhausted => "provider_connections_exhausted",
             CustomVideoStreamType::LowPriorityPreempted => "low_priority_preempted",
             CustomVideoStreamType::UserAccountExpired => "user_account_expired",
+            CustomVideoStreamType::UserQuotaExhausted => "user_quota_exhausted",
             CustomVideoStreamType::Provisioning => "provisioning",
         };
         write!(f, "{s}")
@@ -51,6 +53,7 @@ impl FromStr for CustomVideoStreamType {
             "provider_connections_exhausted" => Ok(Self::ProviderConnectionsExhausted),
             "low_priority_preempted" => Ok(Self::LowPriorityPreempted),
             "user_account_expired" => Ok(Self::UserAccountExpired),
+            "user_quota_exhausted" => Ok(Self::UserQuotaExhausted),
             "provisioning" => Ok(Self::Provisioning),
             _ => Err(format!("Unknown stream type: {s}")),
         }
@@ -209,6 +212,18 @@ pub fn create_user_account_expired_stream(cfg: &AppConfig, headers: &[(String, S
     )
 }
 
+pub fn create_user_quota_exhausted_stream(cfg: &AppConfig, headers: &[(String, String)]) -> ProviderStreamResponse {
+    let custom_stream_response = cfg.custom_stream_response.load();
+    let video = custom_stream_response.as_ref().and_then(|c| c.user_quota_exhausted.as_ref());
+    create_ok_video_stream(
+        cfg,
+        CustomVideoStreamType::UserQuotaExhausted,
+        video,
+        headers,
+        "Streaming response user quota exhausted",
+    )
+}
+
 pub fn create_panel_api_provisioning_stream(cfg: &AppConfig, headers: &[(String, String)]) -> ProviderStreamResponse {
     let custom_stream_response = cfg.custom_stream_response.load();
     let video = custom_stream_response.as_ref().and_then(|c| c.panel_api_provisioning.as_ref());
@@ -257,6 +272,7 @@ pub fn create_custom_video_stream_response(
         }
         CustomVideoStreamType::LowPriorityPreempted => create_low_priority_preempted_stream(config, &[]),
         CustomVideoStreamType::UserAccountExpired => create_user_account_expired_stream(config, &[]),
+        CustomVideoStreamType::UserQuotaExhausted => create_user_quota_exhausted_stream(config, &[]),
         CustomVideoStreamType::Provisioning => create_panel_api_provisioning_stream(config, &[]),
     } {
         app_state.connection_manager.send_cleanup(CleanupEvent::UpdateDetailAndReleaseProviderConnection {
@@ -349,6 +365,7 @@ mod tests {
             provider_connections_exhausted: None,
             low_priority_preempted: None,
             user_account_expired: None,
+            user_quota_exhausted: None,
             panel_api_provisioning: None,
         })));
         app_cfg
@@ -3,11 +3,13 @@ use crate::{
             api_utils::{get_headers_from_request, StreamOptions},
             model::{
                 create_channel_unavailable_stream, get_header_filter_for_item_type, get_response_headers,
-            streams::{buffered_stream::BufferedStream, client_stream::ClientStream},
+            streams::{
+                buffered_stream::BufferedStream, client_stream::ClientStream, ProviderStallState, StallDetectingStream,
+            },
             AppState, BoxedProviderStream, CustomVideoStreamType, ProviderStreamFactoryResponse, StreamError,
         },
     },
-    model::{ConfigProvider, ReverseProxyDisabledHeaderConfig},
+    model::{ConfigProvider, ReverseProxyDisabledHeaderConfig, StreamStallDetectionConfig},
     repository::{ConnectFailureReason, FailureStage},
     utils::{
         debug_if_enabled,
@@ -35,7 +37,7 @@ use std::{
     collections::HashMap,
     net::SocketAddr,
     sync::{
-        atomic::{AtomicUsize, Ordering},
+        atomic::{AtomicU8, AtomicUsize, Ordering},
         Arc,
     },
     time::{Duration, Instant},
@@ -74,6 +76,8 @@ pub struct ProviderStreamFactoryOptions {
     user_agent: Option<String>,
     stream_channel: Option<StreamChannel>,
     connect_failure_stage: Option<FailureStage>,
+    stall_detection: Option<StreamStallDetectionConfig>,
+    stall_state: ProviderStallState,
 }
 
 pub(crate) struct ProviderStreamFactoryParams<'a> {
@@ -150,6 +154,11 @@ impl ProviderStreamFactoryOptions {
         if requested_range.is_some() {
             flags.set(ProviderStreamFactoryFlags::RangeRequested);
         }
+        let stall_detection = if matches!(item_type, PlaylistItemType::Live | PlaylistItemType::LiveUnknown) {
+            stream_options.stall_detection.clone()
+        } else {
+            None
+        };
 
         Self {
             // item_type,
@@ -167,6 +176,8 @@ impl ProviderStreamFactoryOptions {
             user_agent,
             stream_channel: stream_channel.cloned(),
             connect_failure_stage: *connect_failure_stage,
+            stall_detection,
+            stall_state: ProviderStallState::default(),
         }
     }
 
@@ -198,6 +209,40 @@ impl ProviderStreamFactoryOptions {
     #[inline]
     pub fn should_reconnect(&self) -> bool { self.flags.contains(ProviderStreamFactoryFlags::ReconnectEnabled) }
 
+    #[inline]
+    pub fn get_stall_count_clone(&self) -> Arc<AtomicU8> { self.stall_state.get_stall_count_clone() }
+
+    pub fn set_stall_count(&mut self, stall_count: Arc<AtomicU8>) {
+        self.stall_state = ProviderStallState::with_stall_count(stall_count);
+    }
+
+    /// Wraps a provider stream segment into stall detection, a spliced segment follows a reconnect.
+    fn detect_stalls(&self, stream: BoxedProviderStream, splice: bool) -> BoxedProviderStream {
+        match self.stall_detection.as_ref() {
+            Some(config) => {
+                StallDetectingStream::new(stream, config, self.stall_state.clone(), splice, self.get_url_as_str())
+                    .boxed()
+            }
+            None => stream,
+        }
+    }
+
+    /// Switches to the next provider url when the previous segment stalled and stall failover is enabled.
+    fn failover_after_stall(&self) {
+        if !self.stall_state.take_stalled() || !self.stall_detection.as_ref().is_some_and(|config| config.failover) {
+            return;
+        }
+        if let Some(provider) = self.provider.as_ref() {
+            if provider.rotate_to_next_url_with_cycle_check(provider.get_current_index()).is_some() {
+                warn!(
+                    "Provider '{}' stalled -> switching to URL index {}",
+                    provider.name,
+                    provider.get_current_index()
+                );
+            }
+        }
+    }
+
     #[inline]
     pub fn get_headers(&self) -> &HeaderMap { &self.headers }
 
@@ -269,6 +314,20 @@ fn record_provider_open_failure(
         );
 }
 
+/// Feeds the outcome of opening a provider stream into the channel health of the requested channel.
+async fn record_channel_health(
+    app_state: &Arc<AppState>,
+    stream_options: &ProviderStreamFactoryOptions,
+    success: bool,
+) {
+    let Some(channel) = stream_options.stream_channel.as_ref() else { return };
+    if success {
+        app_state.channel_health.record_success(channel.target_id, channel.virtual_id).await;
+    } else {
+        app_state.channel_health.record_failure(&app_state.app_config, channel).await;
+    }
+}
+
 fn classify_provider_status_error(status: StatusCode) -> &'static str {
     if status.is_client_error() {
         "http_4xx"
@@ -779,6 +838,11 @@ pub async fn create_provider_stream(
                 if let Some(reason) = reason {
                     record_provider_open_failure(app_state, &stream_options, reason, None, None);
                 }
+                if matches!(custom_video_type, CustomVideoStreamType::ChannelUnavailable) {
+                    record_channel_health(app_state, &stream_options, false).await;
+                }
+            } else if info.is_some() {
+                record_channel_health(app_state, &stream_options, true).await;
             }
             let is_media_stream_or_not_piped = if let Some((headers, _, _, _custom_video_type)) = &info {
                 // if it is piped or no video stream, then we don't reconnect
@@ -805,6 +869,7 @@ pub async fn create_provider_stream(
                             app_state_clone.connection_manager.release_provider_connection(&stream_opts.addr).await;
                             None
                         } else {
+                            stream_opts.failover_after_stall();
                             match get_provider_stream(&app_state_clone, &client, &stream_opts).await {
                                 Ok(Some((stream, info))) => {
                                     // If we reconnected with a byte offset and the provider responded
@@ -823,7 +888,7 @@ pub async fn create_provider_stream(
                                             return None;
                                         }
                                     }
-                                    Some((stream, ()))
+                                    Some((stream_opts.detect_stalls(stream, true), ()))
                                 }
                                 Ok(None) => {
                                     app_state_clone
@@ -868,7 +933,7 @@ pub async fn create_provider_stream(
                 .boxed();
                 Some((
                     client_stream_factory(
-                        init_stream.chain(unfold).boxed(),
+                        stream_options.detect_stalls(init_stream, false).chain(unfold).boxed(),
                         continue_client_signal.clone(),
                         stream_options.get_range_bytes_clone(),
                     )
@@ -898,6 +963,7 @@ pub async fn create_provider_stream(
                 Some(status),
                 Some(failure.provider_error_class()),
             );
+            record_channel_health(app_state, &stream_options, false).await;
             if let (Some(boxed_provider_stream), response_info) = create_channel_unavailable_stream(
                 &app_state.app_config,
                 &get_response_headers(stream_options.get_headers()),
@@ -924,7 +990,13 @@ mod tests {
         let addr = "127.0.0.1:8080".parse().unwrap();
         let stream_url = Url::parse("http://example.com/stream").unwrap();
         let stream_options =
-            StreamOptions { stream_retry: true, buffer_enabled: true, buffer_size: 1024, pipe_provider_stream: false };
+            StreamOptions {
+                stream_retry: true,
+                buffer_enabled: true,
+                buffer_size: 1024,
+                pipe_provider_stream: false,
+                stall_detection: None,
+            };
         let disabled_headers = None;
 
         // Case 1: VOD, no initial range requested
@@ -1015,7 +1087,13 @@ mod tests {
         let stream_url = Url::parse("http://example.com/segment.ts").unwrap();
         let req_headers = HeaderMap::new();
         let stream_options =
-            StreamOptions { stream_retry: true, buffer_enabled: true, buffer_size: 1024, pipe_provider_stream: false };
+            StreamOptions {
+                stream_retry: true,
+                buffer_enabled: true,
+                buffer_size: 1024,
+                pipe_provider_stream: false,
+                stall_detection: None,
+            };
 
         let hls_options = ProviderStreamFactoryOptions::new(&ProviderStreamFactoryParams {
             addr,
@@ -1059,7 +1137,13 @@ mod tests {
         let stream_url = Url::parse("http://example.com/shared.ts").unwrap();
         let req_headers = HeaderMap::new();
         let stream_options =
-            StreamOptions { stream_retry: true, buffer_enabled: true, buffer_size: 1024, pipe_provider_stream: false };
+            StreamOptions {
+                stream_retry: true,
+                buffer_enabled: true,
+                buffer_size: 1024,
+                pipe_provider_stream: false,
+                stall_detection: None,
+            };
 
         let shared_options = ProviderStreamFactoryOptions::new(&ProviderStreamFactoryParams {
             addr,
@@ -1089,7 +1173,13 @@ mod tests {
         let stream_url = Url::parse("http://example.com/stream").unwrap();
         let req_headers = HeaderMap::new();
         let stream_options =
-            StreamOptions { stream_retry: true, buffer_enabled: true, buffer_size: 1024, pipe_provider_stream: false };
+            StreamOptions {
+                stream_retry: true,
+                buffer_enabled: true,
+                buffer_size: 1024,
+                pipe_provider_stream: false,
+                stall_detection: None,
+            };
 
         let options = ProviderStreamFactoryOptions::new(&ProviderStreamFactoryParams {
             addr,
@@ -1117,6 +1207,7 @@ mod tests {
                 shared_joined_existing: None,
                 shared_stream_id: None,
                 technical: None,
+                alternate_input: None,
             }),
             connect_failure_stage: Some(FailureStage::ProviderOpen),
         });
@@ -3,7 +3,7 @@ use crate::{
         streams::buffered_stream::CHANNEL_SIZE, ActiveProviderManager, AppState, BoxedProviderStream, ProviderHandle,
         StreamError, STREAM_IDLE_TIMEOUT,
     },
-    model::Config,
+    model::{is_ingest_url, Config},
     utils::{debug_if_enabled, trace_if_enabled},
 };
 use bytes::Bytes;
@@ -16,7 +16,10 @@ use std::{
     fmt::{Debug, Formatter},
     net::SocketAddr,
     pin::Pin,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
     task::{Context, Poll},
 };
 use tokio::{
@@ -132,6 +135,8 @@ pub struct SharedStreamState {
     stop_token: CancellationToken,
     burst_buffer: Arc<RwLock<BurstBuffer>>,
     task_handles: RwLock<Vec<tokio::task::JoinHandle<()>>>,
+    // Pre-warmed streams keep the provider connection open without subscribers.
+    prewarmed: Arc<AtomicBool>,
 }
 
 impl SharedStreamState {
@@ -155,9 +160,12 @@ impl SharedStreamState {
             stop_token: CancellationToken::new(),
             burst_buffer: Arc::new(RwLock::new(BurstBuffer::new(burst_buffer_size_in_bytes))),
             task_handles: RwLock::new(Vec::new()),
+            prewarmed: Arc::new(AtomicBool::new(false)),
         }
     }
 
+    pub fn is_prewarmed(&self) -> bool { self.prewarmed.load(Ordering::Acquire) }
+
     #[allow(clippy::too_many_lines)]
     async fn subscribe(
         &self,
@@ -351,6 +359,7 @@ impl SharedStreamState {
         let sender = self.broadcaster.clone();
         let stop_token = self.stop_token.clone();
         let burst_buffer = Arc::clone(&self.burst_buffer);
+        let prewarmed = Arc::clone(&self.prewarmed);
         let broadcast_started_at = Instant::now();
 
         tokio::spawn(async move {
@@ -425,7 +434,7 @@ impl SharedStreamState {
                                          tokio::task::yield_now().await;
                                          counter = 0;
                                      }
-                                 } else {
+                                 } else if !prewarmed.load(Ordering::Acquire) {
                                      debug_if_enabled!(
                                          "Shared stream send error,no subscribers closing {}",
                                          sanitize_sensitive_info(&streaming_url)
@@ -603,7 +612,7 @@ impl SharedStreamManager {
                 sanitize_sensitive_info(&addr.to_string())
             );
 
-            if is_empty {
+            if is_empty && !state.is_prewarmed() {
                 if let Some(url) = stream_url.as_ref() {
                     debug_if_enabled!(
                         "No subscribers remain for {} after removing {}",
@@ -718,6 +727,80 @@ impl SharedStreamManager {
         subscribed_stream
     }
 
+    /// Registers a provider stream without subscribers. The stream fills its burst buffer until the first
+    /// viewer subscribes and stays open until `release_prewarmed_stream` or a preemption of its connection.
+    pub(crate) async fn register_prewarmed_stream<S, E>(
+        app_state: &AppState,
+        stream_url: &str,
+        bytes_stream: S,
+        addr: &SocketAddr,
+        headers: Vec<(String, String)>,
+        buffer_size: usize,
+        provider_handle: Option<ProviderHandle>,
+    ) where
+        S: Stream<Item=Result<Bytes, E>> + Unpin + 'static + Send,
+        E: std::fmt::Debug + Send,
+    {
+        let buf_size = CHANNEL_SIZE.max(buffer_size);
+        let config = app_state.app_config.config.load();
+        let min_buffer_bytes = resolve_min_burst_buffer_bytes(&config);
+        let shared_state = Arc::new(SharedStreamState::new(headers, buf_size, provider_handle, min_buffer_bytes, None));
+        shared_state.prewarmed.store(true, Ordering::Release);
+        app_state
+            .shared_stream_manager
+            .shared_streams
+            .write()
+            .await
+            .by_key
+            .insert(Arc::from(stream_url), Arc::clone(&shared_state));
+        app_state.active_provider.make_shared_connection(addr, stream_url).await;
+        shared_state.broadcast(stream_url, bytes_stream, Arc::clone(&app_state.shared_stream_manager));
+        debug_if_enabled!("Created pre-warmed shared stream {}", sanitize_sensitive_info(stream_url));
+    }
+
+    /// Registers the stream an encoder pushes to an ingest channel. Like a pre-warmed stream it stays open
+    /// without subscribers, but it holds no provider connection and closes when the encoder stops sending.
+    /// Returns `false` when the channel already receives a stream.
+    pub(crate) async fn register_ingest_stream<S, E>(
+        app_state: &AppState,
+        stream_url: &str,
+        bytes_stream: S,
+        headers: Vec<(String, String)>,
+    ) -> bool
+    where
+        S: Stream<Item=Result<Bytes, E>> + Unpin + 'static + Send,
+        E: std::fmt::Debug + Send,
+    {
+        let config = app_state.app_config.config.load();
+        let min_buffer_bytes = resolve_min_burst_buffer_bytes(&config);
+        let shared_state = Arc::new(SharedStreamState::new(headers, CHANNEL_SIZE, None, min_buffer_bytes, None));
+        shared_state.prewarmed.store(true, Ordering::Release);
+        {
+            let mut shared_streams = app_state.shared_stream_manager.shared_streams.write().await;
+            if shared_streams.by_key.contains_key(stream_url) {
+                return false;
+            }
+            shared_streams.by_key.insert(Arc::from(stream_url), Arc::clone(&shared_state));
+        }
+        shared_state.broadcast(stream_url, bytes_stream, Arc::clone(&app_state.shared_stream_manager));
+        debug_if_enabled!("Created ingest shared stream {}", sanitize_sensitive_info(stream_url));
+        true
+    }
+
+    /// Ends the pre-warming of a stream, it closes now without subscribers or with its last subscriber.
+    pub async fn release_prewarmed_stream(&self, stream_url: &str) {
+        let Some(shared_state) = self.get_shared_state(stream_url).await else {
+            return;
+        };
+        if !shared_state.prewarmed.swap(false, Ordering::AcqRel) {
+            return;
+        }
+        if shared_state.subscribers.read().await.is_empty() {
+            debug_if_enabled!("Releasing pre-warmed shared stream {}", sanitize_sensitive_info(stream_url));
+            self.unregister(stream_url, true).await;
+        }
+    }
+
     pub async fn subscribe_shared_stream(
         app_state: &AppState,
         stream_url: &str,
@@ -727,6 +810,10 @@ impl SharedStreamManager {
     ) -> Option<(BoxedProviderStream, Option<Arc<str>>)> {
         let manager = Arc::clone(&app_state.shared_stream_manager);
         if let Some(result) = app_state.shared_stream_manager.subscribe_stream(stream_url, addr, manager).await {
+            // ingest streams are pushed by local encoders and hold no provider connection
+            if is_ingest_url(stream_url) {
+                return Some(result);
+            }
             match app_state
                 .active_provider
                 .add_shared_connection(
@@ -769,7 +856,11 @@ mod tests {
         model::{ConfigPaths, InputFetchMethod, InputType},
         utils::Internable,
     };
-    use std::{collections::HashMap, net::SocketAddr, sync::Arc};
+    use std::{
+        collections::HashMap,
+        net::SocketAddr,
+        sync::{atomic::Ordering, Arc},
+    };
     use tokio::time::{timeout, Duration};
     use tokio_util::sync::CancellationToken;
 
@@ -919,6 +1010,33 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_prewarmed_stream_outlives_its_last_subscriber_until_released() {
+        let app_cfg = create_test_app_config();
+        let event_manager = Arc::new(EventManager::new());
+        let provider_manager = Arc::new(ActiveProviderManager::new(&app_cfg, &event_manager));
+        let shared_manager = Arc::new(SharedStreamManager::new(provider_manager));
+
+        let stream_url = "https://example.invalid/live/prewarm.ts";
+        let addr_1: SocketAddr = "127.0.0.1:43001".parse().unwrap_or_else(|_| unreachable!());
+        let state = Arc::new(SharedStreamState::new(Vec::new(), CHANNEL_SIZE.max(8), None, 1024, None));
+        state.prewarmed.store(true, Ordering::Release);
+
+        {
+            let mut reg = shared_manager.shared_streams.write().await;
+            reg.by_key.insert(Arc::from(stream_url), Arc::clone(&state));
+            reg.key_by_addr.insert(addr_1, Arc::from(stream_url));
+        }
+        state.subscribers.write().await.insert(addr_1, CancellationToken::new());
+
+        shared_manager.release_connection(&addr_1, false).await;
+        assert!(shared_manager.get_shared_state(stream_url).await.is_some());
+
+        shared_manager.release_prewarmed_stream(stream_url).await;
+        assert!(!state.is_prewarmed());
+        assert!(shared_manager.get_shared_state(stream_url).await.is_none());
+    }
+
     #[tokio::test]
     async fn test_preempted_shared_subscriber_switches_to_low_priority_fallback() {
         let app_cfg = create_test_app_config();
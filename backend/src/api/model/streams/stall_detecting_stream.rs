@@ -0,0 +1,276 @@
+use crate::{
+    api::model::{BoxedProviderStream, StreamError},
+    model::StreamStallDetectionConfig,
+};
+use bytes::{Bytes, BytesMut};
+use futures::Stream;
+use log::warn;
+use shared::utils::sanitize_sensitive_info;
+use std::{
+    collections::HashSet,
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, AtomicU8, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::time::{sleep, Instant, Sleep};
+
+const TS_PACKET_SIZE: usize = 188;
+const TS_SYNC_BYTE: u8 = 0x47;
+const TS_NULL_PID: u16 = 0x1FFF;
+// The first packets of a spliced segment cover PAT, PMT and the elementary streams.
+const DISCONTINUITY_MARK_PACKETS: usize = 2048;
+
+/// Stall state of a provider stream, shared by all segments of the reconnecting stream.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ProviderStallState {
+    stall_count: Arc<AtomicU8>,
+    stalled: Arc<AtomicBool>,
+}
+
+impl ProviderStallState {
+    pub fn with_stall_count(stall_count: Arc<AtomicU8>) -> Self { Self { stall_count, stalled: Arc::default() } }
+
+    pub fn get_stall_count_clone(&self) -> Arc<AtomicU8> { Arc::clone(&self.stall_count) }
+
+    /// Returns true once after a segment ended with a stall.
+    pub fn take_stalled(&self) -> bool { self.stalled.swap(false, Ordering::AcqRel) }
+
+    fn record_stall(&self) {
+        let _ = self
+            .stall_count
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |count| Some(count.saturating_add(1)));
+        self.stalled.store(true, Ordering::Release);
+    }
+}
+
+/// Ends a live provider stream segment when no payload arrives for `timeout_secs` or the bitrate
+/// drops below `min_bitrate`, so the reconnecting provider stream opens the next segment while the
+/// client connection stays open.
+///
+/// MPEG-TS segments are cut at packet boundaries, a spliced segment announces the
+/// discontinuity per PID with an adaptation-only packet, so players recover without a restart.
+pub(in crate::api::model) struct StallDetectingStream {
+    inner: BoxedProviderStream,
+    state: ProviderStallState,
+    timeout: Duration,
+    idle_sleep: Pin<Box<Sleep>>,
+    min_bitrate_kbps: u64,
+    bitrate_window: Duration,
+    window_start: Instant,
+    window_bytes: u64,
+    transport_stream: Option<bool>,
+    remainder: BytesMut,
+    splice: bool,
+    marked_packets: usize,
+    marked_pids: HashSet<u16>,
+    url: String,
+    finished: bool,
+}
+
+impl StallDetectingStream {
+    pub(crate) fn new(
+        inner: BoxedProviderStream,
+        config: &StreamStallDetectionConfig,
+        state: ProviderStallState,
+        splice: bool,
+        url: &str,
+    ) -> Self {
+        let timeout = Duration::from_secs(config.timeout_secs);
+        Self {
+            inner,
+            state,
+            timeout,
+            idle_sleep: Box::pin(sleep(timeout)),
+            min_bitrate_kbps: config.min_bitrate_kbps,
+            bitrate_window: Duration::from_secs(config.bitrate_window_secs),
+            window_start: Instant::now(),
+            window_bytes: 0,
+            transport_stream: None,
+            remainder: BytesMut::new(),
+            splice,
+            marked_packets: 0,
+            marked_pids: HashSet::new(),
+            url: url.to_string(),
+            finished: false,
+        }
+    }
+
+    fn stall(&mut self, reason: &str) -> Poll<Option<Result<Bytes, StreamError>>> {
+        warn!("Provider stream stalled ({reason}), reconnecting {}", sanitize_sensitive_info(&self.url));
+        self.state.record_stall();
+        self.finished = true;
+        Poll::Ready(None)
+    }
+
+    fn is_below_min_bitrate(&mut self, len: usize) -> bool {
+        if self.min_bitrate_kbps == 0 {
+            return false;
+        }
+        self.window_bytes = self.window_bytes.saturating_add(len as u64);
+        let elapsed = self.window_start.elapsed();
+        if elapsed < self.bitrate_window {
+            return false;
+        }
+        let elapsed_millis = u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX).max(1);
+        let kbps = self.window_bytes.saturating_mul(8) / elapsed_millis;
+        self.window_start = Instant::now();
+        self.window_bytes = 0;
+        kbps < self.min_bitrate_kbps
+    }
+
+    /// Returns the complete TS packets received so far, a trailing partial packet is kept.
+    fn align(&mut self, bytes: Bytes) -> Option<Bytes> {
+        let transport_stream = *self.transport_stream.get_or_insert(bytes.first() == Some(&TS_SYNC_BYTE));
+        if !transport_stream {
+            return Some(bytes);
+        }
+        let marking = self.splice && self.marked_packets < DISCONTINUITY_MARK_PACKETS;
+        if self.remainder.is_empty() && bytes.len().is_multiple_of(TS_PACKET_SIZE) && !marking {
+            return Some(bytes);
+        }
+        self.remainder.extend_from_slice(&bytes);
+        let complete = self.remainder.len() - self.remainder.len() % TS_PACKET_SIZE;
+        if complete == 0 {
+            return None;
+        }
+        let packets = self.remainder.split_to(complete);
+        if marking {
+            Some(self.mark_discontinuity(&packets))
+        } else {
+            Some(packets.freeze())
+        }
+    }
+
+    /// Prepends an adaptation-only packet with the discontinuity indicator to the first packet of each PID.
+    fn mark_discontinuity(&mut self, packets: &[u8]) -> Bytes {
+        let mut out = BytesMut::with_capacity(packets.len() + TS_PACKET_SIZE * 4);
+        for packet in packets.chunks_exact(TS_PACKET_SIZE) {
+            if self.marked_packets < DISCONTINUITY_MARK_PACKETS && packet[0] == TS_SYNC_BYTE {
+                self.marked_packets += 1;
+                let pid = (u16::from(packet[1] & 0x1F) << 8) | u16::from(packet[2]);
+                if pid != TS_NULL_PID && self.marked_pids.insert(pid) {
+                    write_discontinuity_packet(packet, &mut out);
+                }
+            }
+            out.extend_from_slice(packet);
+        }
+        out.freeze()
+    }
+}
+
+fn write_discontinuity_packet(packet: &[u8], out: &mut BytesMut) {
+    let start = out.len();
+    out.resize(start + TS_PACKET_SIZE, 0xFF);
+    let discontinuity = &mut out[start..start + TS_PACKET_SIZE];
+    discontinuity[0] = TS_SYNC_BYTE;
+    discontinuity[1] = packet[1] & 0x1F;
+    discontinuity[2] = packet[2];
+    // Adaptation only, the continuity counter of the following packet is not advanced.
+    discontinuity[3] = 0x20 | ((packet[3] & 0x0F).wrapping_sub(1) & 0x0F);
+    discontinuity[4] = 183;
+    discontinuity[5] = 0x80;
+}
+
+impl Stream for StallDetectingStream {
+    type Item = Result<Bytes, StreamError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.finished {
+            return Poll::Ready(None);
+        }
+        loop {
+            match Pin::new(&mut this.inner).poll_next(cx) {
+                Poll::Ready(Some(Ok(bytes))) => {
+                    if bytes.is_empty() {
+                        return Poll::Ready(Some(Ok(bytes)));
+                    }
+                    let deadline = Instant::now() + this.timeout;
+                    this.idle_sleep.as_mut().reset(deadline);
+                    if this.is_below_min_bitrate(bytes.len()) {
+                        return this.stall("bitrate below minimum");
+                    }
+                    if let Some(aligned) = this.align(bytes) {
+                        return Poll::Ready(Some(Ok(aligned)));
+                    }
+                }
+                Poll::Ready(Some(Err(err))) => return Poll::Ready(Some(Err(err))),
+                Poll::Ready(None) => {
+                    this.finished = true;
+                    return Poll::Ready(None);
+                }
+                Poll::Pending => {
+                    if this.idle_sleep.as_mut().poll(cx).is_ready() {
+                        return this.stall("no payload");
+                    }
+                    return Poll::Pending;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ProviderStallState, StallDetectingStream, TS_PACKET_SIZE, TS_SYNC_BYTE};
+    use crate::model::StreamStallDetectionConfig;
+    use bytes::Bytes;
+    use futures::{stream, StreamExt};
+    use std::sync::atomic::Ordering;
+
+    fn config() -> StreamStallDetectionConfig {
+        StreamStallDetectionConfig {
+            timeout_secs: 5,
+            min_bitrate_str: None,
+            min_bitrate_kbps: 0,
+            bitrate_window_secs: 10,
+            failover: false,
+        }
+    }
+
+    fn packet(pid: u16, cc: u8) -> Vec<u8> {
+        let mut packet = vec![0xFF; TS_PACKET_SIZE];
+        packet[0] = TS_SYNC_BYTE;
+        packet[1] = u8::try_from(pid >> 8).unwrap() & 0x1F;
+        packet[2] = u8::try_from(pid & 0xFF).unwrap();
+        packet[3] = 0x10 | cc;
+        packet
+    }
+
+    #[tokio::test]
+    async fn test_spliced_segment_is_packet_aligned_and_marked() {
+        let mut data = [packet(0x100, 3), packet(0x101, 7), packet(0x100, 4)].concat();
+        data.extend_from_slice(&[TS_SYNC_BYTE, 0x01]);
+        let chunks = vec![Ok(Bytes::copy_from_slice(&data[..100])), Ok(Bytes::copy_from_slice(&data[100..]))];
+        let inner = stream::iter(chunks).boxed();
+        let stream = StallDetectingStream::new(inner, &config(), ProviderStallState::default(), true, "http://x");
+        let out: Vec<u8> = stream.map(|chunk| chunk.unwrap().to_vec()).concat().await;
+
+        assert_eq!(out.len(), 5 * TS_PACKET_SIZE);
+        let headers: Vec<&[u8]> = out.chunks(TS_PACKET_SIZE).map(|packet| &packet[..6]).collect();
+        assert_eq!(headers[0], &[TS_SYNC_BYTE, 0x01, 0x00, 0x22, 183, 0x80]);
+        assert_eq!(&headers[1][..4], &[TS_SYNC_BYTE, 0x01, 0x00, 0x13]);
+        assert_eq!(headers[2], &[TS_SYNC_BYTE, 0x01, 0x01, 0x26, 183, 0x80]);
+        assert_eq!(&headers[3][..4], &[TS_SYNC_BYTE, 0x01, 0x01, 0x17]);
+        assert_eq!(&headers[4][..4], &[TS_SYNC_BYTE, 0x01, 0x00, 0x14]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_stalled_segment_ends_and_counts() {
+        let first = Bytes::from(packet(0x100, 0));
+        let inner = stream::iter(vec![Ok(first)]).chain(stream::pending()).boxed();
+        let state = ProviderStallState::default();
+        let mut stream = StallDetectingStream::new(inner, &config(), state.clone(), false, "http://x");
+
+        assert_eq!(stream.next().await.unwrap().unwrap().len(), TS_PACKET_SIZE);
+        assert!(stream.next().await.is_none());
+        assert_eq!(state.get_stall_count_clone().load(Ordering::Relaxed), 1);
+        assert!(state.take_stalled());
+        assert!(!state.take_stalled());
+    }
+}
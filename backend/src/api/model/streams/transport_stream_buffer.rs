@@ -9,7 +9,7 @@ use std::{
 // PCR wraps at 2^33 * 300 (base is 33-bit, multiplied by 300 to get 27 MHz units).
 // Using 1<<42 was slightly too large and could cause strict-decoder issues on hardware
 // that computes modulo 2^33 on the base before multiplying.
-const MAX_PCR: u64 = (1u64 << 33) * 300;
+pub(super) const MAX_PCR: u64 = (1u64 << 33) * 300;
 const MAX_PTS_DTS: u64 = 1 << 33; // 33 bit PTS/DTS cycle
 
 const TS_PACKET_SIZE: usize = 188;
@@ -17,7 +17,7 @@ const SYNC_BYTE: u8 = 0x47;
 const PACKET_COUNT: usize = 7; // Reduced from 250 to 7 (1316 bytes) to prevent latency/timeout on low-bitrate streams
 const CHUNK_SIZE: usize = TS_PACKET_SIZE * PACKET_COUNT;
 
-const ADAPTATION_FIELD_FLAG_PCR: u8 = 0x10; // PCR flag bit in adaptation field flags
+pub(super) const ADAPTATION_FIELD_FLAG_PCR: u8 = 0x10; // PCR flag bit in adaptation field flags
 
 /// Byte offset of PTS within a PES payload (after the 3-byte start code, `stream_id`, length, flags).
 const PES_PTS_OFFSET: usize = 9;
@@ -72,7 +72,7 @@ fn encode_timestamp(ts: u64) -> [u8; 5] {
 
 /// Decode PCR from 6 bytes (adaptation field) into 42-bit PCR base + 9-bit extension as u64
 #[inline]
-fn decode_pcr(pcr_bytes: &[u8]) -> u64 {
+pub(super) fn decode_pcr(pcr_bytes: &[u8]) -> u64 {
     let pcr_base = (u64::from(pcr_bytes[0]) << 25)
         | ((u64::from(pcr_bytes[1])) << 17)
         | ((u64::from(pcr_bytes[2])) << 9)
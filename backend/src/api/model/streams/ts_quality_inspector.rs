@@ -0,0 +1,208 @@
+use super::transport_stream_buffer::{decode_pcr, ADAPTATION_FIELD_FLAG_PCR, MAX_PCR};
+use crate::repository::TsQualityStats;
+use std::collections::{HashMap, HashSet};
+use tokio::time::Instant;
+
+const TS_PACKET_SIZE: usize = 188;
+const TS_SYNC_BYTE: u8 = 0x47;
+const TS_NULL_PID: u16 = 0x1FFF;
+const ADAPTATION_FIELD_FLAG_DISCONTINUITY: u8 = 0x80;
+/// ISO 13818-1 requires a PCR at least every 100 ms (27 MHz clock).
+const MAX_PCR_INTERVAL: u64 = 2_700_000;
+/// Streams without a TS packet in their first bytes are not inspected.
+const MAX_UNSYNCED_BYTES: usize = 64 * 1024;
+
+fn get_pid(packet: &[u8]) -> u16 { (u16::from(packet[1] & 0x1F) << 8) | u16::from(packet[2]) }
+
+/// Collects transport-level quality metrics from the MPEG-TS packets delivered to a client.
+#[derive(Default)]
+pub(crate) struct TsQualityInspector {
+    remainder: Vec<u8>,
+    unsynced_bytes: usize,
+    disabled: bool,
+    continuity: HashMap<u16, u8>,
+    last_pcr: HashMap<u16, u64>,
+    video_pids: HashSet<u16>,
+    video_bytes: u64,
+    first_packet: Option<Instant>,
+    last_packet: Option<Instant>,
+    stats: TsQualityStats,
+}
+
+impl TsQualityInspector {
+    pub fn inspect(&mut self, data: &[u8]) {
+        if self.disabled {
+            return;
+        }
+        let mut data = data;
+        // complete the packet split across the previous chunk
+        if !self.remainder.is_empty() {
+            let missing = TS_PACKET_SIZE - self.remainder.len();
+            if data.len() < missing {
+                self.remainder.extend_from_slice(data);
+                return;
+            }
+            self.remainder.extend_from_slice(&data[..missing]);
+            data = &data[missing..];
+            let packet = std::mem::take(&mut self.remainder);
+            self.process_packet(&packet);
+        }
+        while data.len() >= TS_PACKET_SIZE {
+            if data[0] != TS_SYNC_BYTE {
+                let skip = data[1..].iter().position(|b| *b == TS_SYNC_BYTE).map_or(data.len(), |pos| pos + 1);
+                data = &data[skip..];
+                self.unsynced_bytes += skip;
+                if self.stats.packets == 0 && self.unsynced_bytes > MAX_UNSYNCED_BYTES {
+                    self.disabled = true;
+                    return;
+                }
+                continue;
+            }
+            self.process_packet(&data[..TS_PACKET_SIZE]);
+            data = &data[TS_PACKET_SIZE..];
+        }
+        if let Some(pos) = data.iter().position(|b| *b == TS_SYNC_BYTE) {
+            self.remainder.extend_from_slice(&data[pos..]);
+        }
+        let now = Instant::now();
+        if self.stats.packets > 0 {
+            self.first_packet.get_or_insert(now);
+            self.last_packet = Some(now);
+        }
+    }
+
+    fn process_packet(&mut self, packet: &[u8]) {
+        self.stats.packets += 1;
+        let pid = get_pid(packet);
+        if pid == TS_NULL_PID {
+            self.stats.null_packets += 1;
+            return;
+        }
+
+        let adaptation_field_control = (packet[3] >> 4) & 0x03;
+        let has_payload = adaptation_field_control & 0x01 != 0;
+        let mut payload_offset = 4;
+        let mut adaptation_field: &[u8] = &[];
+        if adaptation_field_control & 0x02 != 0 {
+            let length = usize::from(packet[4]);
+            payload_offset = 5 + length;
+            if payload_offset > TS_PACKET_SIZE {
+                return;
+            }
+            adaptation_field = &packet[5..payload_offset];
+        }
+
+        if adaptation_field.first().is_some_and(|flags| flags & ADAPTATION_FIELD_FLAG_DISCONTINUITY != 0) {
+            self.continuity.remove(&pid);
+            self.last_pcr.remove(&pid);
+        }
+
+        // the continuity counter only increments with a payload, a single duplicate packet is allowed
+        if has_payload {
+            let counter = packet[3] & 0x0F;
+            if let Some(last) = self.continuity.insert(pid, counter) {
+                if counter != last && counter != (last + 1) & 0x0F {
+                    self.stats.cc_errors += 1;
+                }
+            }
+        }
+
+        if adaptation_field.len() >= 7 && adaptation_field[0] & ADAPTATION_FIELD_FLAG_PCR != 0 {
+            let pcr = decode_pcr(&adaptation_field[1..7]);
+            if let Some(last) = self.last_pcr.insert(pid, pcr) {
+                if (pcr + MAX_PCR - last) % MAX_PCR > MAX_PCR_INTERVAL {
+                    self.stats.pcr_gaps += 1;
+                }
+            }
+        }
+
+        let payload_start = packet[1] & 0x40 != 0;
+        if has_payload && payload_start && !self.video_pids.contains(&pid) {
+            let payload = &packet[payload_offset..];
+            // PES stream ids 0xE0-0xEF are video streams
+            if payload.len() > 3 && payload.starts_with(&[0x00, 0x00, 0x01]) && payload[3] & 0xF0 == 0xE0 {
+                self.video_pids.insert(pid);
+            }
+        }
+        if self.video_pids.contains(&pid) {
+            self.video_bytes += TS_PACKET_SIZE as u64;
+        }
+    }
+
+    /// Returns the metrics of the session, `None` when no MPEG-TS packet was delivered.
+    pub fn stats(&self) -> Option<TsQualityStats> {
+        if self.stats.packets == 0 {
+            return None;
+        }
+        let elapsed_millis = match (self.first_packet, self.last_packet) {
+            (Some(first), Some(last)) => u64::try_from(last.duration_since(first).as_millis()).unwrap_or(u64::MAX),
+            _ => 0,
+        };
+        let video_bitrate_kbps =
+            (elapsed_millis >= 1_000 && self.video_bytes > 0).then(|| self.video_bytes * 8 / elapsed_millis);
+        Some(TsQualityStats { video_bitrate_kbps, ..self.stats })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TsQualityInspector, TS_NULL_PID, TS_PACKET_SIZE, TS_SYNC_BYTE};
+
+    fn packet(pid: u16, counter: u8, adaptation_field: &[u8]) -> Vec<u8> {
+        let mut packet = vec![0xFF; TS_PACKET_SIZE];
+        packet[0] = TS_SYNC_BYTE;
+        packet[1] = u8::try_from(pid >> 8).unwrap() & 0x1F;
+        packet[2] = u8::try_from(pid & 0xFF).unwrap();
+        if adaptation_field.is_empty() {
+            packet[3] = 0x10 | counter;
+        } else {
+            packet[3] = 0x30 | counter;
+            packet[4] = u8::try_from(adaptation_field.len()).unwrap();
+            packet[5..5 + adaptation_field.len()].copy_from_slice(adaptation_field);
+        }
+        packet
+    }
+
+    fn pcr_field(pcr_base: u64) -> [u8; 7] {
+        let bytes = (pcr_base << 15).to_be_bytes();
+        [0x10, bytes[2], bytes[3], bytes[4], bytes[5], bytes[6] | 0x7E, 0]
+    }
+
+    #[test]
+    fn counts_continuity_errors_and_null_packets() {
+        let mut inspector = TsQualityInspector::default();
+        let mut data = Vec::new();
+        for counter in [0, 1, 1, 2, 5, 6] {
+            data.extend(packet(0x100, counter, &[]));
+        }
+        data.extend(packet(TS_NULL_PID, 0, &[]));
+        // a signalled discontinuity resets the counter
+        data.extend(packet(0x100, 9, &[0x80]));
+        // split across chunks
+        inspector.inspect(&data[..500]);
+        inspector.inspect(&data[500..]);
+
+        let stats = inspector.stats().unwrap();
+        assert_eq!(stats.packets, 8);
+        assert_eq!(stats.null_packets, 1);
+        assert_eq!(stats.cc_errors, 1);
+    }
+
+    #[test]
+    fn counts_pcr_gaps() {
+        let mut inspector = TsQualityInspector::default();
+        // 40 ms, then 200 ms between the PCRs (90 kHz base)
+        for (counter, pcr_base) in [(0, 0), (1, 3_600), (2, 21_600)] {
+            inspector.inspect(&packet(0x100, counter, &pcr_field(pcr_base)));
+        }
+        assert_eq!(inspector.stats().unwrap().pcr_gaps, 1);
+    }
+
+    #[test]
+    fn ignores_non_transport_streams() {
+        let mut inspector = TsQualityInspector::default();
+        inspector.inspect(&vec![0u8; 128 * 1024]);
+        inspector.inspect(&packet(0x100, 0, &[]));
+        assert!(inspector.stats().is_none());
+    }
+}
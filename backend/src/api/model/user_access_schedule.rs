@@ -0,0 +1,55 @@
+use crate::api::model::AppState;
+use log::{debug, info};
+use shared::utils::default_kick_secs;
+use std::{collections::HashMap, sync::Arc, time::Duration};
+use tokio_util::sync::CancellationToken;
+
+const ACCESS_SCHEDULE_CHECK_INTERVAL_SECS: u64 = 60;
+
+/// Kicks the running streams of users whose access window closed and who have `kick_on_close` set.
+async fn enforce_user_access_schedules(app_state: &Arc<AppState>) {
+    if !app_state.app_config.config.load().user_access_control {
+        return;
+    }
+    let streams = app_state.active_users.active_streams().await;
+    if streams.is_empty() {
+        return;
+    }
+    let kick_secs = app_state.app_config.config.load().web_ui.as_ref().map_or_else(default_kick_secs, |wc| wc.kick_secs);
+
+    let mut outside_schedule: HashMap<String, bool> = HashMap::new();
+    for stream in streams {
+        let kick = *outside_schedule.entry(stream.username.clone()).or_insert_with(|| {
+            app_state.app_config.get_user_credentials(&stream.username).is_some_and(|user| {
+                user.access_schedule.as_ref().is_some_and(|schedule| schedule.kick_on_close)
+                    && user.outside_access_schedule(app_state)
+            })
+        });
+        if kick {
+            debug!("Access window closed for user {}, kicking stream {}", stream.username, stream.channel.virtual_id);
+            app_state.connection_manager.kick_connection(&stream.addr, stream.channel.virtual_id, kick_secs).await;
+        }
+    }
+}
+
+pub(in crate::api) fn exec_user_access_schedule_enforcement(
+    app_state: &Arc<AppState>,
+    cancel_token: &CancellationToken,
+) {
+    let app_state = Arc::clone(app_state);
+    let cancel = cancel_token.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(ACCESS_SCHEDULE_CHECK_INTERVAL_SECS));
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        loop {
+            tokio::select! {
+                () = cancel.cancelled() => {
+                    info!("Access schedule enforcement loop stopped");
+                    break;
+                }
+                _ = interval.tick() => {}
+            }
+            enforce_user_access_schedules(&app_state).await;
+        }
+    });
+}
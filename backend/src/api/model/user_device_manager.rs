@@ -0,0 +1,367 @@
+use crate::{api::model::AppState, messaging::send_message, model::MessageContent, utils::file_exists_async};
+use log::{error, info};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use shared::{
+    model::{ProxyUserDeviceLimitDto, UserDeviceDto},
+    utils::{current_time_secs, short_hash},
+};
+use std::{
+    collections::{HashMap, HashSet},
+    net::IpAddr,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+use tokio::{fs, sync::RwLock};
+
+/// Unknown devices beyond this number are rejected until pending devices are approved or revoked.
+const MAX_PENDING_DEVICES_PER_USER: usize = 10;
+/// Minimum interval between two approval notifications of the same user.
+const PENDING_NOTIFICATION_INTERVAL_SECS: u64 = 300;
+
+/// Outcome of checking a device against the device limit of its user.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DeviceAdmission {
+    Allowed,
+    PendingApproval,
+    LimitReached,
+}
+
+/// The device a request originates from.
+pub struct DeviceClient<'a> {
+    pub client_ip: &'a str,
+    pub user_agent: &'a str,
+    pub device_id: Option<&'a str>,
+}
+
+impl DeviceClient<'_> {
+    /// The client ip reduced to its /24 (IPv4) or /64 (IPv6) network.
+    fn network(&self) -> String {
+        let client_ip = self.client_ip.split(',').next().unwrap_or_default().trim();
+        match client_ip.parse::<IpAddr>() {
+            Ok(IpAddr::V4(ip)) => {
+                let octets = ip.octets();
+                format!("{}.{}.{}.0/24", octets[0], octets[1], octets[2])
+            }
+            Ok(IpAddr::V6(ip)) => {
+                let segments = ip.segments();
+                format!("{:x}:{:x}:{:x}:{:x}::/64", segments[0], segments[1], segments[2], segments[3])
+            }
+            Err(_) => client_ip.to_string(),
+        }
+    }
+
+    /// A client provided device id identifies the device across networks,
+    /// otherwise user agent and network are used.
+    fn fingerprint(&self, network: &str) -> String {
+        match self.device_id {
+            Some(device_id) => short_hash(&format!("id|{device_id}|{}", self.user_agent)),
+            None => short_hash(&format!("net|{network}|{}", self.user_agent)),
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PersistedUserDevices {
+    users: HashMap<String, Vec<UserDeviceDto>>,
+}
+
+/// Approval notifications sent for a user, a device is only announced once.
+#[derive(Debug, Default)]
+struct PendingNotifications {
+    notified: HashSet<String>,
+    last_sent: u64,
+    suppressed: usize,
+}
+
+impl PendingNotifications {
+    /// Returns the number of devices not announced since the last notification,
+    /// or `None` when the device was already announced or the user was notified recently.
+    fn announce(&mut self, id: &str, now: u64) -> Option<usize> {
+        if !self.notified.insert(id.to_string()) {
+            return None;
+        }
+        if self.last_sent > 0 && now.saturating_sub(self.last_sent) < PENDING_NOTIFICATION_INTERVAL_SECS {
+            self.suppressed += 1;
+            return None;
+        }
+        self.last_sent = now;
+        Some(std::mem::take(&mut self.suppressed))
+    }
+}
+
+/// Registers the devices of users with a device limit on first use.
+pub struct UserDeviceManager {
+    state_file: Option<PathBuf>,
+    devices: RwLock<HashMap<String, Vec<UserDeviceDto>>>,
+    notifications: Mutex<HashMap<String, PendingNotifications>>,
+    dirty: AtomicBool,
+}
+
+impl UserDeviceManager {
+    pub fn new_with_state_file(state_file: Option<PathBuf>) -> Self {
+        Self {
+            state_file,
+            devices: RwLock::new(HashMap::new()),
+            notifications: Mutex::new(HashMap::new()),
+            dirty: AtomicBool::new(false),
+        }
+    }
+
+    pub async fn load_from_disk(&self) -> std::io::Result<()> {
+        let Some(state_file) = self.state_file.as_ref() else {
+            return Ok(());
+        };
+        if !file_exists_async(state_file).await {
+            return Ok(());
+        }
+        let content = fs::read_to_string(state_file).await?;
+        let mut persisted: PersistedUserDevices =
+            serde_json::from_str(&content).map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+        for user_devices in persisted.users.values_mut() {
+            let mut ids = HashSet::new();
+            user_devices.retain(|device| ids.insert(device.id.clone()));
+        }
+        *self.devices.write().await = persisted.users;
+        Ok(())
+    }
+
+    pub async fn persist_to_disk(&self) -> std::io::Result<()> {
+        let Some(state_file) = self.state_file.as_ref() else {
+            return Ok(());
+        };
+        if !self.dirty.swap(false, Ordering::AcqRel) {
+            return Ok(());
+        }
+        let content = {
+            let users = self.devices.read().await.clone();
+            serde_json::to_vec(&PersistedUserDevices { users }).map_err(std::io::Error::other)?
+        };
+        if let Some(parent) = state_file.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        let tmp_file = state_file.with_extension("json.tmp");
+        fs::write(&tmp_file, content).await?;
+        fs::rename(&tmp_file, state_file).await
+    }
+
+    async fn persist(&self) {
+        if let Err(err) = self.persist_to_disk().await {
+            error!("Failed to persist user devices: {err}");
+        }
+    }
+
+    /// Looks up the device and registers it when it is new and the limit allows it.
+    /// Returns the admission and the device when it was registered by this call.
+    async fn register(
+        &self,
+        username: &str,
+        limit: &ProxyUserDeviceLimitDto,
+        client: &DeviceClient<'_>,
+        now: u64,
+    ) -> (DeviceAdmission, Option<UserDeviceDto>) {
+        let network = client.network();
+        let id = client.fingerprint(&network);
+        let mut devices = self.devices.write().await;
+        let user_devices = devices.entry(username.to_string()).or_default();
+        if let Some(device) = user_devices.iter_mut().find(|device| device.id == id) {
+            device.last_seen = now;
+            client.client_ip.clone_into(&mut device.last_ip);
+            self.dirty.store(true, Ordering::Release);
+            let admission = if device.approved { DeviceAdmission::Allowed } else { DeviceAdmission::PendingApproval };
+            return (admission, None);
+        }
+        if limit.max_devices > 0 && user_devices.len() >= usize::from(limit.max_devices) {
+            return (DeviceAdmission::LimitReached, None);
+        }
+        if limit.approve_new
+            && user_devices.iter().filter(|device| !device.approved).count() >= MAX_PENDING_DEVICES_PER_USER
+        {
+            return (DeviceAdmission::LimitReached, None);
+        }
+        let device = UserDeviceDto {
+            id,
+            device_id: client.device_id.map(ToString::to_string),
+            user_agent: client.user_agent.to_string(),
+            network,
+            last_ip: client.client_ip.to_string(),
+            first_seen: now,
+            last_seen: now,
+            approved: !limit.approve_new,
+        };
+        user_devices.push(device.clone());
+        self.dirty.store(true, Ordering::Release);
+        let admission = if device.approved { DeviceAdmission::Allowed } else { DeviceAdmission::PendingApproval };
+        (admission, Some(device))
+    }
+
+    /// Checks the device against the device limit of the user, new devices are registered
+    /// and the admin is notified when they need an approval.
+    pub async fn admit(
+        &self,
+        app_state: &AppState,
+        username: &str,
+        limit: &ProxyUserDeviceLimitDto,
+        client: &DeviceClient<'_>,
+    ) -> DeviceAdmission {
+        let now = current_time_secs();
+        let (admission, registered) = self.register(username, limit, client, now).await;
+        if let Some(device) = registered {
+            self.persist().await;
+            info!("Registered new device {} for user {username}", device.id);
+            let announce = if device.approved {
+                None
+            } else {
+                self.notifications.lock().entry(username.to_string()).or_default().announce(&device.id, now)
+            };
+            if let Some(suppressed) = announce {
+                let more =
+                    if suppressed > 0 { format!(" ({suppressed} more devices await approval)") } else { String::new() };
+                let message = format!(
+                    "New device {} of user {username} awaits approval: {} from {}{more}",
+                    device.id, device.user_agent, device.last_ip
+                );
+                let app_config = Arc::clone(&app_state.app_config);
+                let client = app_state.http_client.load_full();
+                tokio::spawn(async move {
+                    send_message(&app_config, &client, MessageContent::Info(message)).await;
+                });
+            }
+        }
+        admission
+    }
+
+    pub async fn list(&self, username: &str) -> Vec<UserDeviceDto> {
+        self.devices.read().await.get(username).cloned().unwrap_or_default()
+    }
+
+    pub async fn approve(&self, username: &str, id: &str) -> bool {
+        let approved = {
+            let mut devices = self.devices.write().await;
+            devices
+                .get_mut(username)
+                .and_then(|user_devices| user_devices.iter_mut().find(|device| device.id == id))
+                .map(|device| device.approved = true)
+                .is_some()
+        };
+        if approved {
+            if let Some(notifications) = self.notifications.lock().get_mut(username) {
+                notifications.notified.remove(id);
+            }
+            self.dirty.store(true, Ordering::Release);
+            self.persist().await;
+        }
+        approved
+    }
+
+    pub async fn revoke(&self, username: &str, id: &str) -> bool {
+        let revoked = {
+            let mut devices = self.devices.write().await;
+            devices.get_mut(username).is_some_and(|user_devices| {
+                let count = user_devices.len();
+                user_devices.retain(|device| device.id != id);
+                count != user_devices.len()
+            })
+        };
+        if revoked {
+            self.dirty.store(true, Ordering::Release);
+            self.persist().await;
+        }
+        revoked
+    }
+
+    pub async fn remove_user(&self, username: &str) {
+        self.notifications.lock().remove(username);
+        if self.devices.write().await.remove(username).is_some() {
+            self.dirty.store(true, Ordering::Release);
+            self.persist().await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn client<'a>(client_ip: &'a str, user_agent: &'a str, device_id: Option<&'a str>) -> DeviceClient<'a> {
+        DeviceClient { client_ip, user_agent, device_id }
+    }
+
+    #[test]
+    fn network_masks_client_ip() {
+        assert_eq!(client("192.168.1.77", "", None).network(), "192.168.1.0/24");
+        assert_eq!(client("10.0.0.1, 172.16.0.1", "", None).network(), "10.0.0.0/24");
+        assert_eq!(client("2001:db8:1:2:3:4:5:6", "", None).network(), "2001:db8:1:2::/64");
+    }
+
+    #[tokio::test]
+    async fn devices_are_registered_until_the_limit_is_reached() {
+        let manager = UserDeviceManager::new_with_state_file(None);
+        let limit = ProxyUserDeviceLimitDto { max_devices: 2, approve_new: false };
+
+        let tv = client("192.168.1.10", "TV", None);
+        assert_eq!(manager.register("alice", &limit, &tv, 1).await.0, DeviceAdmission::Allowed);
+        // same user agent within the same network is the same device
+        let tv_new_ip = client("192.168.1.20", "TV", None);
+        assert_eq!(manager.register("alice", &limit, &tv_new_ip, 2).await, (DeviceAdmission::Allowed, None));
+        // a client provided device id identifies the device across networks
+        let phone = client("10.0.0.1", "Phone", Some("abc"));
+        assert_eq!(manager.register("alice", &limit, &phone, 3).await.0, DeviceAdmission::Allowed);
+        let phone_roaming = client("172.16.0.1", "Phone", Some("abc"));
+        assert_eq!(manager.register("alice", &limit, &phone_roaming, 4).await.0, DeviceAdmission::Allowed);
+
+        let laptop = client("192.168.1.30", "Laptop", None);
+        assert_eq!(manager.register("alice", &limit, &laptop, 5).await.0, DeviceAdmission::LimitReached);
+
+        let devices = manager.list("alice").await;
+        assert_eq!(devices.len(), 2);
+        assert_eq!(devices[1].last_ip, "172.16.0.1");
+        assert!(manager.revoke("alice", &devices[0].id).await);
+        assert_eq!(manager.register("alice", &limit, &laptop, 6).await.0, DeviceAdmission::Allowed);
+    }
+
+    #[tokio::test]
+    async fn new_devices_wait_for_approval() {
+        let manager = UserDeviceManager::new_with_state_file(None);
+        let limit = ProxyUserDeviceLimitDto { max_devices: 0, approve_new: true };
+        let tv = client("192.168.1.10", "TV", None);
+
+        let (admission, registered) = manager.register("bob", &limit, &tv, 1).await;
+        assert_eq!(admission, DeviceAdmission::PendingApproval);
+        let device = registered.unwrap();
+        assert_eq!(manager.register("bob", &limit, &tv, 2).await.0, DeviceAdmission::PendingApproval);
+
+        assert!(manager.approve("bob", &device.id).await);
+        assert_eq!(manager.register("bob", &limit, &tv, 3).await.0, DeviceAdmission::Allowed);
+    }
+
+    #[tokio::test]
+    async fn pending_devices_are_capped() {
+        let manager = UserDeviceManager::new_with_state_file(None);
+        let limit = ProxyUserDeviceLimitDto { max_devices: 0, approve_new: true };
+        for idx in 0..MAX_PENDING_DEVICES_PER_USER {
+            let user_agent = format!("Device {idx}");
+            let device = client("192.168.1.10", &user_agent, None);
+            assert_eq!(manager.register("carol", &limit, &device, 1).await.0, DeviceAdmission::PendingApproval);
+        }
+        let device = client("192.168.1.10", "Another Device", None);
+        assert_eq!(manager.register("carol", &limit, &device, 2).await, (DeviceAdmission::LimitReached, None));
+        // known devices are not affected by the cap
+        let known = client("192.168.1.10", "Device 0", None);
+        assert_eq!(manager.register("carol", &limit, &known, 3).await.0, DeviceAdmission::PendingApproval);
+    }
+
+    #[test]
+    fn pending_notifications_are_deduplicated_and_rate_limited() {
+        let mut notifications = PendingNotifications::default();
+        assert_eq!(notifications.announce("a", 1000), Some(0));
+        assert_eq!(notifications.announce("a", 2000), None);
+        assert_eq!(notifications.announce("b", 1010), None);
+        assert_eq!(notifications.announce("c", 1020), None);
+        assert_eq!(notifications.announce("d", 1000 + PENDING_NOTIFICATION_INTERVAL_SECS), Some(2));
+        assert_eq!(notifications.announce("e", 1000 + PENDING_NOTIFICATION_INTERVAL_SECS + 1), None);
+    }
+}
@@ -0,0 +1,311 @@
+use crate::{api::model::AppState, repository::DisconnectReason, utils::file_exists_async};
+use log::{debug, error, info};
+use serde::{Deserialize, Serialize};
+use shared::{
+    model::{ProxyUserQuotaDto, StreamInfo, UserQuotaPeriod, UserQuotaStatusDto},
+    utils::current_time_secs,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tokio::{
+    fs,
+    sync::{Mutex, RwLock},
+};
+use tokio_util::sync::CancellationToken;
+
+const QUOTA_SAMPLE_INTERVAL_SECS: u64 = 30;
+const QUOTA_BUCKET_SECS: u64 = 3_600;
+
+/// Usage of one user within one hour.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct UsageBucket {
+    hour: u64,
+    secs: u64,
+    bytes: u64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PersistedUserQuotaUsage {
+    users: HashMap<String, Vec<UsageBucket>>,
+}
+
+struct TrackedStream {
+    username: String,
+    last_ts: u64,
+    last_bytes: u64,
+}
+
+/// Tracks streamed time and transferred bytes per user in hourly buckets
+/// so quotas can be evaluated over rolling day/week/month windows.
+pub struct UserQuotaManager {
+    state_file: Option<PathBuf>,
+    usage: RwLock<HashMap<String, Vec<UsageBucket>>>,
+    tracked: Mutex<HashMap<u32, TrackedStream>>,
+    dirty: AtomicBool,
+}
+
+impl UserQuotaManager {
+    pub fn new_with_state_file(state_file: Option<PathBuf>) -> Self {
+        Self {
+            state_file,
+            usage: RwLock::new(HashMap::new()),
+            tracked: Mutex::new(HashMap::new()),
+            dirty: AtomicBool::new(false),
+        }
+    }
+
+    pub async fn load_from_disk(&self) -> std::io::Result<()> {
+        let Some(state_file) = self.state_file.as_ref() else {
+            return Ok(());
+        };
+        if !file_exists_async(state_file).await {
+            return Ok(());
+        }
+        let content = fs::read_to_string(state_file).await?;
+        let persisted: PersistedUserQuotaUsage =
+            serde_json::from_str(&content).map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+        *self.usage.write().await = persisted.users;
+        Ok(())
+    }
+
+    pub async fn persist_to_disk(&self) -> std::io::Result<()> {
+        let Some(state_file) = self.state_file.as_ref() else {
+            return Ok(());
+        };
+        if !self.dirty.swap(false, Ordering::AcqRel) {
+            return Ok(());
+        }
+        let content = {
+            let users = self.usage.read().await.clone();
+            serde_json::to_vec(&PersistedUserQuotaUsage { users }).map_err(std::io::Error::other)?
+        };
+        if let Some(parent) = state_file.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        let tmp_file = state_file.with_extension("json.tmp");
+        fs::write(&tmp_file, content).await?;
+        fs::rename(&tmp_file, state_file).await
+    }
+
+    async fn record_usage(&self, username: &str, now: u64, secs: u64, bytes: u64) {
+        if secs == 0 && bytes == 0 {
+            return;
+        }
+        let hour = now / QUOTA_BUCKET_SECS;
+        let oldest_hour = now.saturating_sub(UserQuotaPeriod::Month.window_secs()) / QUOTA_BUCKET_SECS;
+        let mut usage = self.usage.write().await;
+        let buckets = usage.entry(username.to_string()).or_default();
+        buckets.retain(|bucket| bucket.hour >= oldest_hour);
+        match buckets.last_mut() {
+            Some(bucket) if bucket.hour == hour => {
+                bucket.secs += secs;
+                bucket.bytes += bytes;
+            }
+            _ => buckets.push(UsageBucket { hour, secs, bytes }),
+        }
+        self.dirty.store(true, Ordering::Release);
+    }
+
+    /// Returns `(secs, bytes)` used within the rolling window ending at `now`.
+    async fn usage_in_window(&self, username: &str, period: UserQuotaPeriod, now: u64) -> (u64, u64) {
+        let first_hour = now.saturating_sub(period.window_secs()) / QUOTA_BUCKET_SECS;
+        let usage = self.usage.read().await;
+        usage.get(username).map_or((0, 0), |buckets| {
+            buckets
+                .iter()
+                .filter(|bucket| bucket.hour > first_hour)
+                .fold((0, 0), |(secs, bytes), bucket| (secs + bucket.secs, bytes + bucket.bytes))
+        })
+    }
+
+    pub async fn status(&self, username: &str, quota: &ProxyUserQuotaDto) -> UserQuotaStatusDto {
+        let (used_secs, used_bytes) = self.usage_in_window(username, quota.period, current_time_secs()).await;
+        UserQuotaStatusDto::new(quota, used_secs, used_bytes)
+    }
+
+    /// Books the time and bytes since the previous sample for every active stream.
+    /// `streams` carries the current meter total of each stream.
+    /// Returns the usernames that consumed quota in this sample.
+    async fn sample(&self, streams: &[(StreamInfo, Option<u64>)], now: u64) -> HashSet<String> {
+        let mut consumed: HashMap<String, (u64, u64)> = HashMap::new();
+        {
+            let mut tracked = self.tracked.lock().await;
+            let active_uids: HashSet<u32> = streams.iter().map(|(stream, _)| stream.uid).collect();
+            tracked.retain(|uid, _| active_uids.contains(uid));
+
+            for (stream, bytes_total) in streams {
+                let bytes_total = bytes_total.unwrap_or(0);
+                let (secs, bytes) = match tracked.get_mut(&stream.uid) {
+                    Some(entry) if entry.username == stream.username => {
+                        let secs = now.saturating_sub(entry.last_ts);
+                        let bytes = if bytes_total >= entry.last_bytes { bytes_total - entry.last_bytes } else { bytes_total };
+                        entry.last_ts = now;
+                        entry.last_bytes = bytes_total;
+                        (secs, bytes)
+                    }
+                    _ => {
+                        // A client joining a running shared stream only pays for what it receives from now on.
+                        let joined_shared = stream.channel.shared_joined_existing.unwrap_or(false);
+                        let bytes = if joined_shared { 0 } else { bytes_total };
+                        tracked.insert(
+                            stream.uid,
+                            TrackedStream { username: stream.username.clone(), last_ts: now, last_bytes: bytes_total },
+                        );
+                        (now.saturating_sub(stream.ts).min(QUOTA_SAMPLE_INTERVAL_SECS), bytes)
+                    }
+                };
+                let entry = consumed.entry(stream.username.clone()).or_default();
+                entry.0 += secs;
+                entry.1 += bytes;
+            }
+        }
+
+        let mut usernames = HashSet::new();
+        for (username, (secs, bytes)) in consumed {
+            if secs > 0 || bytes > 0 {
+                self.record_usage(&username, now, secs, bytes).await;
+                usernames.insert(username);
+            }
+        }
+        usernames
+    }
+}
+
+fn is_quota_relevant_stream(stream: &StreamInfo) -> bool {
+    // custom videos and idle adaptive sessions do not count against the quota
+    !stream.preserved && stream.provider != "tuliprox"
+}
+
+async fn account_user_quota_usage(app_state: &Arc<AppState>) {
+    let mut streams = Vec::new();
+    for stream in app_state.active_users.active_streams().await {
+        if is_quota_relevant_stream(&stream) {
+            let bytes_total = app_state.event_manager.read_meter_bytes_total(stream.meter_uid).await;
+            streams.push((stream, bytes_total));
+        }
+    }
+    let usernames = app_state.user_quota.sample(&streams, current_time_secs()).await;
+
+    for username in usernames {
+        let Some(user) = app_state.app_config.get_user_credentials(&username) else {
+            continue;
+        };
+        if user.quota_exhausted(app_state).await {
+            debug!("Quota exhausted for user {username}, closing active streams");
+            for (stream, _) in streams.iter().filter(|(stream, _)| stream.username == username) {
+                app_state
+                    .connection_manager
+                    .close_connection_with_reason(&stream.addr, DisconnectReason::UserQuotaExhausted);
+            }
+        }
+    }
+
+    if let Err(err) = app_state.user_quota.persist_to_disk().await {
+        error!("Failed to persist user quota usage: {err}");
+    }
+}
+
+pub(in crate::api) fn exec_user_quota_accounting(app_state: &Arc<AppState>, cancel_token: &CancellationToken) {
+    let app_state = Arc::clone(app_state);
+    let cancel = cancel_token.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(QUOTA_SAMPLE_INTERVAL_SECS));
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        loop {
+            tokio::select! {
+                () = cancel.cancelled() => {
+                    info!("User quota accounting loop stopped");
+                    break;
+                }
+                _ = interval.tick() => {}
+            }
+            account_user_quota_usage(&app_state).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::model::{PlaylistItemType, StreamChannel, XtreamCluster};
+    use std::net::SocketAddr;
+
+    fn test_stream(uid: u32, username: &str, ts: u64) -> StreamInfo {
+        let channel = StreamChannel {
+            target_id: 1,
+            virtual_id: uid,
+            provider_id: 1,
+            input_name: "input".into(),
+            item_type: PlaylistItemType::Live,
+            cluster: XtreamCluster::Live,
+            group: "group".into(),
+            title: "title".into(),
+            url: "http://localhost/live/1.ts".into(),
+            shared: false,
+            shared_joined_existing: None,
+            shared_stream_id: None,
+            technical: None,
+            alternate_input: None,
+        };
+        let addr: SocketAddr = "127.0.0.1:4000".parse().unwrap();
+        let mut stream = StreamInfo::new(uid, 0, username, &addr, "127.0.0.1", "provider", channel, String::new(), None, None);
+        stream.ts = ts;
+        stream
+    }
+
+    #[tokio::test]
+    async fn sample_books_elapsed_time_and_byte_deltas() {
+        let manager = UserQuotaManager::new_with_state_file(None);
+        let now = 1_000_000;
+        let stream = test_stream(1, "alice", now - 10);
+
+        manager.sample(&[(stream.clone(), Some(1_000))], now).await;
+        manager.sample(&[(stream.clone(), Some(5_000))], now + 30).await;
+        manager.sample(&[(stream, Some(6_000))], now + 60).await;
+
+        let quota = ProxyUserQuotaDto { period: UserQuotaPeriod::Day, max_hours: Some(1), max_traffic_mb: None };
+        let (secs, bytes) = manager.usage_in_window("alice", quota.period, now + 60).await;
+        assert_eq!(secs, 70);
+        assert_eq!(bytes, 6_000);
+
+        let status = UserQuotaStatusDto::new(&quota, secs, bytes);
+        assert_eq!(status.remaining_secs, Some(3_530));
+        assert_eq!(status.remaining_bytes, None);
+        assert!(!status.exhausted);
+    }
+
+    #[tokio::test]
+    async fn usage_outside_rolling_window_is_ignored() {
+        let manager = UserQuotaManager::new_with_state_file(None);
+        let now = 10 * UserQuotaPeriod::Week.window_secs();
+        manager.record_usage("bob", now - 2 * 86_400, 3_600, 1_048_576).await;
+        manager.record_usage("bob", now, 1_800, 0).await;
+
+        assert_eq!(manager.usage_in_window("bob", UserQuotaPeriod::Day, now).await, (1_800, 0));
+        assert_eq!(manager.usage_in_window("bob", UserQuotaPeriod::Week, now).await, (5_400, 1_048_576));
+
+        let quota = ProxyUserQuotaDto { period: UserQuotaPeriod::Week, max_hours: None, max_traffic_mb: Some(1) };
+        assert!(UserQuotaStatusDto::new(&quota, 5_400, 1_048_576).exhausted);
+    }
+
+    #[tokio::test]
+    async fn usage_survives_persist_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let state_file = dir.path().join("user_quota.json");
+        let manager = UserQuotaManager::new_with_state_file(Some(state_file.clone()));
+        let now = current_time_secs();
+        manager.record_usage("carol", now, 120, 2_048).await;
+        manager.persist_to_disk().await.unwrap();
+
+        let reloaded = UserQuotaManager::new_with_state_file(Some(state_file));
+        reloaded.load_from_disk().await.unwrap();
+        assert_eq!(reloaded.usage_in_window("carol", UserQuotaPeriod::Day, now).await, (120, 2_048));
+    }
+}
@@ -0,0 +1,60 @@
+use dashmap::DashMap;
+use std::time::Instant;
+
+/// Buckets above this size are pruned from users whose bucket is full again.
+const BUCKET_PRUNE_SIZE: usize = 4096;
+
+/// Limits the player API requests of users with a limit profile.
+///
+/// Each user has a token bucket of `burst_size` requests which refills one request every `period_millis`.
+/// The bucket is stored as the time it is completely refilled.
+pub struct UserRateLimiter {
+    started: Instant,
+    refilled_at: DashMap<String, u64>,
+}
+
+impl Default for UserRateLimiter {
+    fn default() -> Self { Self { started: Instant::now(), refilled_at: DashMap::new() } }
+}
+
+impl UserRateLimiter {
+    /// Takes a request token of the user, returns `false` when the user has no tokens left.
+    pub fn try_acquire(&self, username: &str, period_millis: u64, burst_size: u32) -> bool {
+        let now = u64::try_from(self.started.elapsed().as_millis()).unwrap_or(u64::MAX);
+        self.try_acquire_at(username, period_millis, burst_size, now)
+    }
+
+    fn try_acquire_at(&self, username: &str, period_millis: u64, burst_size: u32, now: u64) -> bool {
+        if self.refilled_at.len() > BUCKET_PRUNE_SIZE {
+            self.refilled_at.retain(|_, refilled_at| *refilled_at > now);
+        }
+        let mut refilled_at = self.refilled_at.entry(username.to_string()).or_insert(now);
+        let start = (*refilled_at).max(now);
+        let capacity = period_millis.saturating_mul(u64::from(burst_size));
+        if start + period_millis > now + capacity {
+            return false;
+        }
+        *refilled_at = start + period_millis;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UserRateLimiter;
+
+    #[test]
+    fn test_user_rate_limiter_refills_per_period() {
+        let limiter = UserRateLimiter::default();
+        for _ in 0..3 {
+            assert!(limiter.try_acquire_at("guest", 1000, 3, 0));
+        }
+        assert!(!limiter.try_acquire_at("guest", 1000, 3, 0));
+        // other users have their own bucket
+        assert!(limiter.try_acquire_at("other", 1000, 3, 0));
+        assert!(!limiter.try_acquire_at("guest", 1000, 3, 999));
+        assert!(limiter.try_acquire_at("guest", 1000, 3, 1000));
+        assert!(!limiter.try_acquire_at("guest", 1000, 3, 1000));
+        assert!(limiter.try_acquire_at("guest", 1000, 3, 10_000));
+    }
+}
@@ -0,0 +1,110 @@
+use crate::utils::file_exists_async;
+use log::error;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::atomic::{AtomicBool, Ordering},
+};
+use tokio::{fs, sync::RwLock};
+
+const SECS_PER_DAY: u64 = 86_400;
+/// Travel days are counted within a rolling year.
+const TRAVEL_WINDOW_DAYS: u64 = 365;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PersistedUserTravel {
+    users: HashMap<String, Vec<u64>>,
+}
+
+/// Tracks the days users streamed from outside their home country.
+pub struct UserTravelManager {
+    state_file: Option<PathBuf>,
+    travel_days: RwLock<HashMap<String, Vec<u64>>>,
+    dirty: AtomicBool,
+}
+
+impl UserTravelManager {
+    pub fn new_with_state_file(state_file: Option<PathBuf>) -> Self {
+        Self { state_file, travel_days: RwLock::new(HashMap::new()), dirty: AtomicBool::new(false) }
+    }
+
+    pub async fn load_from_disk(&self) -> std::io::Result<()> {
+        let Some(state_file) = self.state_file.as_ref() else {
+            return Ok(());
+        };
+        if !file_exists_async(state_file).await {
+            return Ok(());
+        }
+        let content = fs::read_to_string(state_file).await?;
+        let persisted: PersistedUserTravel =
+            serde_json::from_str(&content).map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+        *self.travel_days.write().await = persisted.users;
+        Ok(())
+    }
+
+    pub async fn persist_to_disk(&self) -> std::io::Result<()> {
+        let Some(state_file) = self.state_file.as_ref() else {
+            return Ok(());
+        };
+        if !self.dirty.swap(false, Ordering::AcqRel) {
+            return Ok(());
+        }
+        let content = {
+            let users = self.travel_days.read().await.clone();
+            serde_json::to_vec(&PersistedUserTravel { users }).map_err(std::io::Error::other)?
+        };
+        if let Some(parent) = state_file.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        let tmp_file = state_file.with_extension("json.tmp");
+        fs::write(&tmp_file, content).await?;
+        fs::rename(&tmp_file, state_file).await
+    }
+
+    /// Admits a request from abroad when today already counts as a travel day or travel days are left,
+    /// a newly used travel day is recorded.
+    pub async fn use_travel_day(&self, username: &str, allowed_days: u16, now: u64) -> bool {
+        let today = now / SECS_PER_DAY;
+        {
+            let mut travel_days = self.travel_days.write().await;
+            let days = travel_days.entry(username.to_string()).or_default();
+            days.retain(|day| day + TRAVEL_WINDOW_DAYS > today);
+            if days.contains(&today) {
+                return true;
+            }
+            if days.len() >= usize::from(allowed_days) {
+                return false;
+            }
+            days.push(today);
+        }
+        self.dirty.store(true, Ordering::Release);
+        if let Err(err) = self.persist_to_disk().await {
+            error!("Failed to persist user travel days: {err}");
+        }
+        true
+    }
+
+    pub async fn remove_user(&self, username: &str) {
+        if self.travel_days.write().await.remove(username).is_some() {
+            self.dirty.store(true, Ordering::Release);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn travel_days_are_limited_within_a_rolling_year() {
+        let manager = UserTravelManager::new_with_state_file(None);
+        assert!(manager.use_travel_day("alice", 2, 0).await);
+        assert!(manager.use_travel_day("alice", 2, 3_600).await);
+        assert!(manager.use_travel_day("alice", 2, SECS_PER_DAY).await);
+        assert!(!manager.use_travel_day("alice", 2, 2 * SECS_PER_DAY).await);
+        // the first travel day leaves the window after a year
+        assert!(manager.use_travel_day("alice", 2, TRAVEL_WINDOW_DAYS * SECS_PER_DAY).await);
+        assert!(!manager.use_travel_day("bob", 0, 0).await);
+    }
+}
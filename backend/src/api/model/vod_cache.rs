@@ -0,0 +1,502 @@
+use crate::api::model::{BoxedProviderStream, StreamError};
+use crate::model::VodCacheConfig;
+use crate::utils::{debug_if_enabled, encode_base64_hash};
+use axum::http::StatusCode;
+use bytes::Bytes;
+use futures::StreamExt;
+use log::{debug, error, info, warn};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use shared::utils::{current_time_secs, human_readable_byte_size, sanitize_sensitive_info};
+use std::collections::HashMap;
+use std::io::SeekFrom;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::io::{AsyncSeekExt, AsyncWriteExt};
+use tokio::sync::mpsc;
+
+const META_FILE_EXT: &str = "meta";
+// Chunks queued for the cache writer, the fill is dropped when the writer falls behind.
+const FILL_CHANNEL_CAPACITY: usize = 256;
+
+/// A cached movie or episode, the data file is sparse and holds the byte ranges fetched so far.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct VodCacheEntry {
+    file_name: String,
+    total_size: u64,
+    /// Sorted and merged byte ranges, the end is exclusive.
+    ranges: Vec<(u64, u64)>,
+    last_access: u64,
+}
+
+impl VodCacheEntry {
+    fn filled_size(&self) -> u64 { self.ranges.iter().map(|(start, end)| end - start).sum() }
+
+    fn covers(&self, start: u64, end: u64) -> bool {
+        self.ranges.iter().any(|&(range_start, range_end)| range_start <= start && end <= range_end)
+    }
+
+    fn is_complete(&self) -> bool { self.covers(0, self.total_size) }
+
+    fn add_range(&mut self, start: u64, end: u64) {
+        if start >= end {
+            return;
+        }
+        self.ranges.push((start, end));
+        self.ranges.sort_unstable();
+        let mut merged: Vec<(u64, u64)> = Vec::with_capacity(self.ranges.len());
+        for &(range_start, range_end) in &self.ranges {
+            match merged.last_mut() {
+                Some(last) if range_start <= last.1 => last.1 = last.1.max(range_end),
+                _ => merged.push((range_start, range_end)),
+            }
+        }
+        self.ranges = merged;
+    }
+}
+
+struct VodCacheState {
+    capacity: u64,
+    max_age_secs: u64,
+    current_size: u64,
+    entries: HashMap<String, VodCacheEntry>,
+}
+
+/// `VodCache`
+///
+/// Read-through disk cache for movies and episodes streamed through the reverse proxy.
+/// The provider response is written into a sparse file while the client plays it, requests whose
+/// range is already stored are served from disk without a provider connection.
+/// Files are evicted when they were not requested for `max_age` and, least recently used first,
+/// when the stored bytes exceed the configured size.
+pub struct VodCache {
+    directory: PathBuf,
+    state: Mutex<VodCacheState>,
+}
+
+impl VodCache {
+    pub fn new(config: &VodCacheConfig) -> Self {
+        Self {
+            directory: PathBuf::from(&config.directory),
+            state: Mutex::new(VodCacheState {
+                capacity: config.size,
+                max_age_secs: config.max_age_secs,
+                current_size: 0,
+                entries: HashMap::new(),
+            }),
+        }
+    }
+
+    pub fn directory(&self) -> &Path { &self.directory }
+
+    pub fn update_config(&self, config: &VodCacheConfig) {
+        let mut state = self.state.lock();
+        state.capacity = config.size;
+        state.max_age_secs = config.max_age_secs;
+        let removed = self.evict(&mut state, None);
+        drop(state);
+        delete_files(removed);
+    }
+
+    /// Restores the entries of the previous run from their metadata files.
+    pub fn scan(&self) -> std::io::Result<()> {
+        std::fs::create_dir_all(&self.directory)?;
+        let mut restored = Vec::new();
+        for dir_entry in std::fs::read_dir(&self.directory)?.flatten() {
+            let path = dir_entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some(META_FILE_EXT) {
+                continue;
+            }
+            let Some(key) = path.file_stem().and_then(|stem| stem.to_str()).map(ToString::to_string) else {
+                continue;
+            };
+            let entry = std::fs::read(&path)
+                .ok()
+                .and_then(|content| serde_json::from_slice::<VodCacheEntry>(&content).ok())
+                .filter(|entry| self.directory.join(&entry.file_name).exists());
+            if let Some(entry) = entry {
+                restored.push((key, entry));
+            } else {
+                debug!("Removing orphaned vod cache metadata {}", path.display());
+                let _ = std::fs::remove_file(&path);
+            }
+        }
+
+        let (removed, current_size, capacity) = {
+            let mut state = self.state.lock();
+            for (key, entry) in restored {
+                if !state.entries.contains_key(&key) {
+                    state.current_size += entry.filled_size();
+                    state.entries.insert(key, entry);
+                }
+            }
+            let removed = self.evict(&mut state, None);
+            (removed, state.current_size, state.capacity)
+        };
+        delete_files(removed);
+        info!(
+            "VOD cache scanned, current size {} / {}",
+            human_readable_byte_size(current_size),
+            human_readable_byte_size(capacity)
+        );
+        Ok(())
+    }
+
+    /// Returns the cached file when it holds the requested range, without a range the file must be complete.
+    pub async fn lookup(&self, url: &str, range: Option<(u64, Option<u64>)>) -> Option<PathBuf> {
+        let key = encode_base64_hash(url);
+        let now = current_time_secs();
+        let (path, expired) = {
+            let state = self.state.lock();
+            let entry = state.entries.get(&key)?;
+            let expired = state.max_age_secs > 0 && now.saturating_sub(entry.last_access) > state.max_age_secs;
+            (self.directory.join(&entry.file_name), expired)
+        };
+        if expired || !tokio::fs::try_exists(&path).await.unwrap_or(false) {
+            // Deleted before returning, a fill started by this request writes a new file under the same name.
+            let removed = self.remove_entry(&mut self.state.lock(), &key);
+            remove_files(removed).await;
+            return None;
+        }
+
+        let mut state = self.state.lock();
+        let entry = state.entries.get_mut(&key)?;
+        let covered = match range {
+            Some((start, end)) => {
+                let end = end.map_or(entry.total_size, |end| end.saturating_add(1).min(entry.total_size));
+                start < end && entry.covers(start, end)
+            }
+            None => entry.is_complete(),
+        };
+        if !covered {
+            return None;
+        }
+        entry.last_access = now;
+        debug_if_enabled!("Serving vod from cache for {}", sanitize_sensitive_info(url));
+        Some(path)
+    }
+
+    /// Writes the provider response into the cache while it is streamed to the client.
+    /// Responses without a known position inside the file are passed through untouched.
+    pub fn cache_stream(
+        self: &Arc<Self>,
+        url: &str,
+        status: StatusCode,
+        headers: &[(String, String)],
+        stream: BoxedProviderStream,
+    ) -> BoxedProviderStream {
+        let Some((offset, total_size)) = get_response_position(status, headers) else {
+            return stream;
+        };
+        let Some((key, path, replaced)) = self.begin_fill(url, total_size) else {
+            return stream;
+        };
+
+        let (tx, rx) = mpsc::channel::<Bytes>(FILL_CHANNEL_CAPACITY);
+        tokio::spawn(fill_cache_file(Arc::clone(self), key, path, replaced, total_size, offset, rx));
+        let mut sender = Some(tx);
+        stream
+            .map(move |item: Result<Bytes, StreamError>| {
+                if let (Ok(bytes), Some(tx)) = (item.as_ref(), sender.as_ref()) {
+                    if tx.try_send(bytes.clone()).is_err() {
+                        debug!("VOD cache writer fell behind, stopped filling");
+                        sender = None;
+                    }
+                }
+                item
+            })
+            .boxed()
+    }
+
+    /// Adds a completely downloaded file to the cache, so it is served without a provider connection.
+    /// The file is copied, the cache evicts its files independently of the download directory.
+    pub async fn import_file(&self, url: &str, source: &Path) -> std::io::Result<()> {
+        let total_size = tokio::fs::metadata(source).await?.len();
+        if total_size == 0 || total_size > self.state.lock().capacity {
+            return Ok(());
+        }
+        tokio::fs::create_dir_all(&self.directory).await?;
+        let key = encode_base64_hash(url);
+        let file_name = cache_file_name(&key, url);
+        let path = self.directory.join(&file_name);
+
+        let replaced = self.remove_entry(&mut self.state.lock(), &key);
+        remove_files(replaced).await;
+        tokio::fs::copy(source, &path).await?;
+        let removed = {
+            let mut state = self.state.lock();
+            let mut entry =
+                VodCacheEntry { file_name, total_size, ranges: Vec::new(), last_access: current_time_secs() };
+            entry.add_range(0, total_size);
+            state.current_size += total_size;
+            state.entries.insert(key.clone(), entry);
+            self.evict(&mut state, Some(&key))
+        };
+        delete_files(removed);
+        self.persist_entry(&key).await;
+        debug_if_enabled!("Imported download into vod cache for {}", sanitize_sensitive_info(url));
+        Ok(())
+    }
+
+    /// Registers the entry to fill, returns the files of a replaced entry which are deleted before filling.
+    fn begin_fill(&self, url: &str, total_size: u64) -> Option<(String, PathBuf, Vec<PathBuf>)> {
+        let key = encode_base64_hash(url);
+        let mut state = self.state.lock();
+        if total_size == 0 || total_size > state.capacity {
+            return None;
+        }
+        let mut replaced = Vec::new();
+        if let Some(entry) = state.entries.get(&key) {
+            if entry.total_size == total_size {
+                return Some((key.clone(), self.directory.join(&entry.file_name), replaced));
+            }
+            // The provider serves another file under the same url.
+            replaced = self.remove_entry(&mut state, &key);
+        }
+        let file_name = cache_file_name(&key, url);
+        let path = self.directory.join(&file_name);
+        state.entries.insert(
+            key.clone(),
+            VodCacheEntry { file_name, total_size, ranges: Vec::new(), last_access: current_time_secs() },
+        );
+        Some((key, path, replaced))
+    }
+
+    /// Records a written range, returns false when the entry was evicted meanwhile.
+    fn mark_filled(&self, key: &str, start: u64, end: u64) -> bool {
+        let (filled, removed) = {
+            let mut state = self.state.lock();
+            let Some(entry) = state.entries.get_mut(key) else {
+                return false;
+            };
+            let filled_before = entry.filled_size();
+            entry.add_range(start, end);
+            entry.last_access = current_time_secs();
+            let added = entry.filled_size() - filled_before;
+            state.current_size += added;
+            let removed =
+                if state.current_size > state.capacity { self.evict(&mut state, Some(key)) } else { Vec::new() };
+            (state.entries.contains_key(key), removed)
+        };
+        delete_files(removed);
+        filled
+    }
+
+    async fn persist_entry(&self, key: &str) {
+        let Some(entry) = self.state.lock().entries.get(key).cloned() else {
+            return;
+        };
+        match serde_json::to_vec(&entry) {
+            Ok(content) => {
+                if let Err(err) = tokio::fs::write(self.meta_path(key), content).await {
+                    error!("Failed to write vod cache metadata {err}");
+                }
+            }
+            Err(err) => error!("Failed to serialize vod cache metadata {err}"),
+        }
+    }
+
+    fn meta_path(&self, key: &str) -> PathBuf { self.directory.join(format!("{key}.{META_FILE_EXT}")) }
+
+    /// Removes the entry from the state and returns its files, they are deleted after the lock is released.
+    fn remove_entry(&self, state: &mut VodCacheState, key: &str) -> Vec<PathBuf> {
+        let Some(entry) = state.entries.remove(key) else {
+            return Vec::new();
+        };
+        state.current_size = state.current_size.saturating_sub(entry.filled_size());
+        vec![self.directory.join(&entry.file_name), self.meta_path(key)]
+    }
+
+    /// Removes expired entries and then the least recently used ones until the cache fits its size.
+    /// The entry currently filled is evicted last. Returns the files of the removed entries.
+    fn evict(&self, state: &mut VodCacheState, keep: Option<&str>) -> Vec<PathBuf> {
+        let mut removed = Vec::new();
+        if state.max_age_secs > 0 {
+            let now = current_time_secs();
+            let expired: Vec<String> = state
+                .entries
+                .iter()
+                .filter(|(key, entry)| {
+                    Some(key.as_str()) != keep && now.saturating_sub(entry.last_access) > state.max_age_secs
+                })
+                .map(|(key, _)| key.clone())
+                .collect();
+            for key in expired {
+                removed.extend(self.remove_entry(state, &key));
+            }
+        }
+        while state.current_size > state.capacity {
+            let oldest = state
+                .entries
+                .iter()
+                .filter(|(key, _)| Some(key.as_str()) != keep)
+                .min_by_key(|(_, entry)| entry.last_access)
+                .map(|(key, _)| key.clone())
+                .or_else(|| keep.map(ToString::to_string));
+            let Some(key) = oldest else {
+                break;
+            };
+            removed.extend(self.remove_entry(state, &key));
+        }
+        removed
+    }
+}
+
+async fn remove_files(paths: Vec<PathBuf>) {
+    for path in paths {
+        match tokio::fs::remove_file(&path).await {
+            Ok(()) => debug!("Removed file from vod cache: {}", path.display()),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => error!("Failed to delete cached vod file {} {err}", path.display()),
+        }
+    }
+}
+
+/// Deletes the files of removed entries without blocking the caller.
+fn delete_files(paths: Vec<PathBuf>) {
+    if !paths.is_empty() {
+        tokio::spawn(remove_files(paths));
+    }
+}
+
+fn cache_file_name(key: &str, url: &str) -> String {
+    let extension = url::Url::parse(url).ok().and_then(|url| {
+        Path::new(url.path())
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .filter(|ext| ext.len() <= 5 && ext.chars().all(|c| c.is_ascii_alphanumeric()))
+            .map(str::to_ascii_lowercase)
+    });
+    match extension {
+        Some(ext) if ext != META_FILE_EXT => format!("{key}.{ext}"),
+        _ => key.to_string(),
+    }
+}
+
+/// Returns the offset of the response body inside the file and the file size.
+fn get_response_position(status: StatusCode, headers: &[(String, String)]) -> Option<(u64, u64)> {
+    let header = |name: &str| headers.iter().find(|(key, _)| key.eq_ignore_ascii_case(name)).map(|(_, v)| v.trim());
+    // Compressed bodies do not match the file bytes.
+    if header("content-encoding").is_some_and(|encoding| !encoding.eq_ignore_ascii_case("identity")) {
+        return None;
+    }
+    match status {
+        StatusCode::OK => header("content-length").and_then(|len| len.parse::<u64>().ok()).map(|len| (0, len)),
+        StatusCode::PARTIAL_CONTENT => {
+            // bytes 0-1975828543/1975828544
+            let (range, total) = header("content-range")?.strip_prefix("bytes ")?.split_once('/')?;
+            let (start, _) = range.split_once('-')?;
+            Some((start.trim().parse().ok()?, total.trim().parse().ok()?))
+        }
+        _ => None,
+    }
+}
+
+async fn fill_cache_file(
+    cache: Arc<VodCache>,
+    key: String,
+    path: PathBuf,
+    replaced: Vec<PathBuf>,
+    total_size: u64,
+    mut offset: u64,
+    mut rx: mpsc::Receiver<Bytes>,
+) {
+    remove_files(replaced).await;
+    let file = async {
+        tokio::fs::create_dir_all(&cache.directory).await?;
+        let mut file = tokio::fs::OpenOptions::new().create(true).truncate(false).write(true).open(&path).await?;
+        if file.metadata().await?.len() != total_size {
+            file.set_len(total_size).await?;
+        }
+        file.seek(SeekFrom::Start(offset)).await?;
+        Ok::<_, std::io::Error>(file)
+    };
+    let mut file = match file.await {
+        Ok(file) => file,
+        Err(err) => {
+            warn!("Failed to open vod cache file {} {err}", path.display());
+            return;
+        }
+    };
+
+    while let Some(bytes) = rx.recv().await {
+        let end = offset.saturating_add(bytes.len() as u64).min(total_size);
+        let Ok(len) = usize::try_from(end - offset) else { break };
+        // The range is only marked after the bytes are flushed, readers never see unwritten data.
+        if let Err(err) = async {
+            file.write_all(&bytes[..len]).await?;
+            file.flush().await
+        }
+        .await
+        {
+            warn!("Failed to write vod cache file {} {err}", path.display());
+            break;
+        }
+        if !cache.mark_filled(&key, offset, end) {
+            break;
+        }
+        offset = end;
+        if offset >= total_size {
+            break;
+        }
+    }
+    cache.persist_entry(&key).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_response_position, VodCache, VodCacheEntry};
+    use crate::model::VodCacheConfig;
+    use axum::http::StatusCode;
+
+    #[test]
+    fn test_vod_cache_entry_merges_ranges() {
+        let mut entry = VodCacheEntry { file_name: String::new(), total_size: 100, ranges: Vec::new(), last_access: 0 };
+        entry.add_range(50, 80);
+        entry.add_range(0, 20);
+        assert!(!entry.covers(10, 60));
+        entry.add_range(20, 50);
+        assert_eq!(entry.ranges, vec![(0, 80)]);
+        assert!(entry.covers(10, 60));
+        assert!(!entry.is_complete());
+        entry.add_range(70, 100);
+        assert!(entry.is_complete());
+        assert_eq!(entry.filled_size(), 100);
+    }
+
+    #[test]
+    fn test_vod_cache_response_position() {
+        let headers = vec![("Content-Range".to_string(), "bytes 1000-1999/5000".to_string())];
+        assert_eq!(get_response_position(StatusCode::PARTIAL_CONTENT, &headers), Some((1000, 5000)));
+        let headers = vec![("content-length".to_string(), "5000".to_string())];
+        assert_eq!(get_response_position(StatusCode::OK, &headers), Some((0, 5000)));
+        let headers = vec![
+            ("content-length".to_string(), "5000".to_string()),
+            ("content-encoding".to_string(), "gzip".to_string()),
+        ];
+        assert_eq!(get_response_position(StatusCode::OK, &headers), None);
+        assert_eq!(get_response_position(StatusCode::PARTIAL_CONTENT, &[]), None);
+    }
+
+    #[tokio::test]
+    async fn test_vod_cache_import_copies_download() {
+        let temp = tempfile::tempdir().unwrap();
+        let download = temp.path().join("movie.mkv");
+        std::fs::write(&download, b"movie").unwrap();
+        let cache = VodCache::new(&VodCacheConfig {
+            enabled: true,
+            directory: temp.path().join("cache").to_string_lossy().to_string(),
+            size: 1024,
+            size_str: None,
+            max_age_secs: 0,
+            max_age_str: None,
+        });
+        let url = "http://provider/movie/1.mkv";
+        cache.import_file(url, &download).await.unwrap();
+
+        let cached = cache.lookup(url, None).await.unwrap();
+        std::fs::write(&download, b"replaced").unwrap();
+        assert_eq!(std::fs::read(&cached).unwrap(), b"movie");
+        assert_eq!(cache.lookup(url, Some((1, Some(3)))).await, Some(cached));
+    }
+}
@@ -2,7 +2,7 @@ use crate::model::{ApiProxyServerInfo, ProxyUserCredentials};
 use chrono::{Duration, Local};
 use serde::{Deserialize, Serialize};
 use shared::{
-    model::ProxyUserStatus,
+    model::{ProxyUserStatus, UserQuotaStatusDto},
     utils::{serialize_number_as_string, CONSTANTS},
 };
 
@@ -21,6 +21,8 @@ pub struct XtreamUserInfoResponse {
     pub created_at: i64, //1623429679,
     pub max_connections: String,
     pub allowed_output_formats: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quota: Option<UserQuotaStatusDto>,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -97,6 +99,7 @@ impl XtreamAuthorizationResponse {
                 created_at,
                 max_connections,
                 allowed_output_formats: CONSTANTS.allowed_output_formats.clone(),
+                quota: None,
             },
             server_info: XtreamServerInfoResponse {
                 url: server_info.host.clone(),
@@ -3741,6 +3741,7 @@ pub fn create_panel_api_provisioning_stream_details(
             provider_grace_active: false,
             disable_provider_grace: true,
             reconnect_flag: None,
+            stall_count: None,
             provider_handle: None,
         };
     }
@@ -3765,6 +3766,7 @@ pub fn create_panel_api_provisioning_stream_details(
         provider_grace_active: false,
         disable_provider_grace: true,
         reconnect_flag: None,
+        stall_count: None,
         provider_handle: None,
     }
 }
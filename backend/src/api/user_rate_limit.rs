@@ -0,0 +1,52 @@
+use crate::api::{login_protection::request_param, model::AppState};
+use axum::{
+    extract::{MatchedPath, Request, State},
+    http::StatusCode,
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Router,
+};
+use log::debug;
+use std::sync::Arc;
+
+/// Rejects player API requests of users exceeding the request rate of their limit profile.
+async fn user_rate_limit(
+    State(app_state): State<Arc<AppState>>,
+    matched_path: Option<MatchedPath>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let has_rate_limits = app_state
+        .app_config
+        .api_proxy
+        .load()
+        .as_ref()
+        .is_some_and(|api_proxy| api_proxy.limit_profiles.iter().any(|profile| profile.has_rate_limit()));
+    if !has_rate_limits {
+        return next.run(req).await;
+    }
+    let credentials = request_param(&req, matched_path.as_ref(), "username")
+        .zip(request_param(&req, matched_path.as_ref(), "password"));
+    // only authenticated requests count, nobody can drain the requests of another user
+    let user = credentials
+        .and_then(|(username, password)| app_state.app_config.get_target_for_user(&username, &password))
+        .map(|(user, _)| user);
+    if let Some(user) = user {
+        let profile =
+            app_state.app_config.get_user_limit_profile(&user).filter(|profile| profile.has_rate_limit());
+        if let Some(profile) = profile {
+            if !app_state.user_rate_limits.try_acquire(&user.username, profile.period_millis, profile.burst_size) {
+                debug!("Request rate of limit profile {} exceeded by user {}", profile.name, user.username);
+                return StatusCode::TOO_MANY_REQUESTS.into_response();
+            }
+        }
+    }
+    next.run(req).await
+}
+
+pub(in crate::api) fn add_user_rate_limiter(
+    router: Router<Arc<AppState>>,
+    app_state: &Arc<AppState>,
+) -> Router<Arc<AppState>> {
+    router.layer(axum::middleware::from_fn_with_state(Arc::clone(app_state), user_rate_limit))
+}
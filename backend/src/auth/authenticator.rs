@@ -8,7 +8,7 @@ use crate::api::model::AppState;
 use crate::auth::AuthBearer;
 use shared::error::to_io_error;
 use shared::model::permission::{permission_to_name, Permission, PermissionSet, PERM_ALL};
-use shared::model::{Claims, ROLE_ADMIN, ROLE_API_USER};
+use shared::model::{Claims, ROLE_ADMIN, ROLE_API_USER, ROLE_RESELLER};
 
 pub fn create_jwt_admin(web_auth_config: &WebAuthConfig, username: &str, pwd_version: u32) -> Result<String, std::io::Error> {
     create_jwt(web_auth_config, username, vec![ROLE_ADMIN.to_string()], PERM_ALL, pwd_version)
@@ -21,10 +21,11 @@ pub fn create_jwt_api_user(web_auth_config: &WebAuthConfig, username: &str) -> R
 pub fn create_jwt_web_user(
     web_auth_config: &WebAuthConfig,
     username: &str,
+    roles: Vec<String>,
     permissions: PermissionSet,
     pwd_version: u32,
 ) -> Result<String, std::io::Error> {
-    create_jwt(web_auth_config, username, Vec::new(), permissions, pwd_version)
+    create_jwt(web_auth_config, username, roles, permissions, pwd_version)
 }
 
 fn create_jwt(
@@ -136,6 +137,32 @@ pub async fn validator_api_user(
     }
 }
 
+pub async fn validator_reseller(
+    axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
+    AuthBearer(token): AuthBearer,
+    request: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> Result<axum::response::Response, axum::http::StatusCode> {
+    let config = app_state.app_config.config.load();
+    let Some(web_auth_config) = config.web_ui.as_ref().and_then(|c| c.auth.as_ref()) else {
+        return Err(axum::http::StatusCode::UNAUTHORIZED);
+    };
+    let Some(token_data) = verify_token(&token, web_auth_config.secret.as_bytes()) else {
+        return Err(axum::http::StatusCode::UNAUTHORIZED);
+    };
+    // the reseller could have been removed from the config since the token was issued
+    let is_reseller = app_state
+        .app_config
+        .api_proxy
+        .load()
+        .as_ref()
+        .is_some_and(|api_proxy| api_proxy.get_reseller(&token_data.claims.username).is_some());
+    if is_reseller && token_data.claims.roles.iter().any(|role| role == ROLE_RESELLER) {
+        return Ok(next.run(request).await);
+    }
+    Err(axum::http::StatusCode::FORBIDDEN)
+}
+
 pub async fn require_permission_inner(
     permission: Permission,
     axum::extract::State(app_state): axum::extract::State<Arc<AppState>>,
@@ -136,6 +136,7 @@ mod tests {
             size_bytes: 1024,
             modified_timestamp: 0,
             content_type,
+            sidecars: Vec::new(),
         }
     }
 
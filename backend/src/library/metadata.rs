@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
-use crate::library::ScannedMediaFile;
+use crate::library::{LibrarySidecar, ScannedMediaFile};
 use crate::library::tmdb::{TmdbCredits, TmdbNetwork, TmdbSeriesInfoEpisodeDetails};
 
 // Source of metadata information
@@ -179,6 +179,8 @@ pub struct EpisodeMetadata {
     pub file_modified: i64,
     #[serde(default)]
     pub technical: Option<TechnicalMetadata>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sidecars: Vec<LibrarySidecar>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -292,6 +294,8 @@ pub struct MetadataCacheEntry {
     pub thumbnail_hash: Option<String>,
     #[serde(default)]
     pub thumbnail_mtime: Option<i64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sidecars: Vec<LibrarySidecar>,
 }
 
 impl MetadataCacheEntry {
@@ -310,6 +314,7 @@ impl MetadataCacheEntry {
             metadata,
             thumbnail_hash: None,
             thumbnail_mtime: None,
+            sidecars: Vec::new(),
         }
     }
 
@@ -318,11 +323,24 @@ impl MetadataCacheEntry {
         Uuid::new_v4().to_string()
     }
 
+    // Finds a sidecar of the movie or one of the episodes by its id
+    pub fn find_sidecar(&self, id: &str) -> Option<&LibrarySidecar> {
+        let episode_sidecars = match &self.metadata {
+            MediaMetadata::Series(series) => series.episodes.as_deref().unwrap_or_default(),
+            MediaMetadata::Movie(_) => &[],
+        };
+        self.sidecars
+            .iter()
+            .chain(episode_sidecars.iter().flat_map(|episode| &episode.sidecars))
+            .find(|sidecar| sidecar.id == id)
+    }
+
     // Checks if the file has been modified since this entry was created
     pub fn is_file_modified(&self, file: &ScannedMediaFile, season_num: u32, episode_num: u32) -> bool {
         match &self.metadata {
             MediaMetadata::Movie(_) => {
                 self.file_size != file.size_bytes || self.file_modified != file.modified_timestamp || self.file_path != file.file_path
+                    || self.sidecars != file.sidecars
             }
 
             MediaMetadata::Series(series) => {
@@ -333,7 +351,8 @@ impl MetadataCacheEntry {
 
                 for episode in episodes {
                     if episode.season == season_num && episode.episode == episode_num && episode.file_path == file.file_path {
-                        return episode.file_size != file.size_bytes || episode.file_modified != file.modified_timestamp;
+                        return episode.file_size != file.size_bytes || episode.file_modified != file.modified_timestamp
+                            || episode.sidecars != file.sidecars;
                     }
                 }
 
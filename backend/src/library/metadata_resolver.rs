@@ -281,6 +281,7 @@ mod tests {
             size_bytes: 1024,
             modified_timestamp: 0,
             content_type: shared::model::LibraryContentType::Auto,
+            sidecars: Vec::new(),
         }
     }
 
@@ -9,6 +9,7 @@ mod processor;
 mod metadata_iterator;
 mod tmdb;
 mod thumbnail;
+mod sidecar;
 
 pub use scanner::*;
 pub use classifier::*;
@@ -20,3 +21,4 @@ pub use metadata_resolver::*;
 pub use processor::*;
 pub use metadata_iterator::*;
 pub use thumbnail::*;
+pub use sidecar::*;
@@ -287,6 +287,7 @@ impl LibraryProcessor {
                 metadata,
                 thumbnail_hash: existing_entry.thumbnail_hash.clone(),
                 thumbnail_mtime: existing_entry.thumbnail_mtime,
+                sidecars: file.sidecars.clone(),
             };
 
             (entry, ProcessAction::Updated)
@@ -295,12 +296,13 @@ impl LibraryProcessor {
             let mut metadata = self.resolve_metadata(group).await?;
             self.enrich_movie_metadata_with_ffprobe(&mut metadata, &file.file_path, can_probe).await;
 
-            let entry = MetadataCacheEntry::new(
+            let mut entry = MetadataCacheEntry::new(
                 file.file_path.clone(),
                 file.size_bytes,
                 file.modified_timestamp,
                 metadata,
             );
+            entry.sidecars.clone_from(&file.sidecars);
 
             (entry, ProcessAction::Added)
         };
@@ -379,6 +381,7 @@ impl LibraryProcessor {
                 metadata,
                 thumbnail_hash: existing_entry.thumbnail_hash.clone(),
                 thumbnail_mtime: existing_entry.thumbnail_mtime,
+                sidecars: Vec::new(),
             };
             (entry, ProcessAction::Updated)
         } else {
@@ -433,6 +436,7 @@ impl LibraryProcessor {
                                 series_episode.file_path.clone_from(&episode.file.file_path);
                                 series_episode.file_modified = episode.file.modified_timestamp;
                                 series_episode.file_size = episode.file.size_bytes;
+                                series_episode.sidecars.clone_from(&episode.file.sidecars);
                                 self.update_episode_thumbnail(
                                     series_episode,
                                     &episode.file.file_path,
@@ -445,6 +449,7 @@ impl LibraryProcessor {
                                 new_episode.file_path.clone_from(&episode.file.file_path);
                                 new_episode.file_modified = episode.file.modified_timestamp;
                                 new_episode.file_size = episode.file.size_bytes;
+                                new_episode.sidecars.clone_from(&episode.file.sidecars);
                                 self.update_episode_thumbnail(
                                     &mut new_episode,
                                     &episode.file.file_path,
@@ -470,6 +475,7 @@ impl LibraryProcessor {
                             file_path: episode.file.file_path.clone(),
                             file_modified: episode.file.modified_timestamp,
                             file_size: episode.file.size_bytes,
+                            sidecars: episode.file.sidecars.clone(),
                             thumbnail_id,
                             ..EpisodeMetadata::default()
                         };
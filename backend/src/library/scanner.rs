@@ -1,6 +1,6 @@
-use crate::library::{MediaClassification, MediaClassifier};
+use crate::library::{sidecar_kind_from_extension, LibrarySidecar, MediaClassification, MediaClassifier};
 use crate::model::{LibraryConfig, LibraryScanDirectory};
-use shared::model::LibraryContentType;
+use shared::model::{LibraryContentType, MediaSidecarKind};
 use log::{debug, error, info, trace, warn};
 use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
@@ -153,6 +153,7 @@ pub struct ScannedMediaFile {
     pub size_bytes: u64,
     pub modified_timestamp: i64,
     pub content_type: LibraryContentType,
+    pub sidecars: Vec<LibrarySidecar>,
 }
 
 impl ScannedMediaFile {
@@ -170,12 +171,7 @@ impl ScannedMediaFile {
             .unwrap_or_default()
             .to_lowercase();
 
-        let modified_timestamp = metadata
-            .modified()
-            .ok()
-            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
-            .and_then(|d| i64::try_from(d.as_secs()).ok())
-            .unwrap_or(0);
+        let modified_timestamp = file_modified_timestamp(&metadata);
 
         Ok(Self {
             file_path: path.display().to_string(),
@@ -185,8 +181,44 @@ impl ScannedMediaFile {
             size_bytes: metadata.len(),
             modified_timestamp,
             content_type,
+            sidecars: Vec::new(),
         })
     }
+
+    fn file_stem(&self) -> &str {
+        self.path.file_stem().and_then(|s| s.to_str()).unwrap_or_default()
+    }
+}
+
+fn file_modified_timestamp(metadata: &std::fs::Metadata) -> i64 {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .and_then(|d| i64::try_from(d.as_secs()).ok())
+        .unwrap_or(0)
+}
+
+/// Assigns the subtitle and audio files of a directory to its videos.
+/// A sidecar belongs to the video with the longest matching file name,
+/// `Movie.Extended.de.srt` belongs to `Movie.Extended.mkv` and not to `Movie.mkv`.
+fn attach_sidecars(videos: &mut [ScannedMediaFile], candidates: &[(PathBuf, MediaSidecarKind, u64, i64)]) {
+    for (path, kind, size, modified) in candidates {
+        let best = videos
+            .iter_mut()
+            .filter_map(|video| {
+                let stem_len = video.file_stem().len();
+                LibrarySidecar::for_video(path, *kind, video.file_stem(), *size, *modified).map(|sidecar| (stem_len, video, sidecar))
+            })
+            .max_by_key(|(stem_len, _, _)| *stem_len);
+        if let Some((_, video, sidecar)) = best {
+            trace!("Found {kind:?} sidecar {} for {}", sidecar.file_path, video.file_path);
+            video.sidecars.push(sidecar);
+        }
+    }
+    for video in videos {
+        video.sidecars.sort_by(|a, b| a.file_path.cmp(&b.file_path));
+    }
 }
 
 /// Library file scanner for local VOD directories
@@ -258,6 +290,8 @@ impl LibraryScanner {
     ) -> std::pin::Pin<Box<dyn std::future::Future<Output=io::Result<()>> + Send + 'a>> {
         Box::pin(async move {
             let mut entries = fs::read_dir(path).await?;
+            let mut directory_files = Vec::new();
+            let mut sidecar_candidates = Vec::new();
 
             while let Some(entry) = entries.next_entry().await? {
                 let entry_path = entry.path();
@@ -284,17 +318,22 @@ impl LibraryScanner {
                             match ScannedMediaFile::from_path(&entry_path, content_type).await {
                                 Ok(video_file) => {
                                     trace!("Found video file: {}", video_file.file_path);
-                                    files.push(video_file);
+                                    directory_files.push(video_file);
                                 }
                                 Err(err) => {
                                     error!("Failed to read metadata for {}: {err}", entry_path.display());
                                 }
                             }
+                        } else if let Some(kind) = sidecar_kind_from_extension(&ext_lower, &self.config.supported_extensions) {
+                            sidecar_candidates.push((entry_path, kind, metadata.len(), file_modified_timestamp(&metadata)));
                         }
                     }
                 }
             }
 
+            attach_sidecars(&mut directory_files, &sidecar_candidates);
+            files.append(&mut directory_files);
+
             Ok(())
         })
     }
@@ -366,6 +405,7 @@ mod tests {
             size_bytes: 1024,
             modified_timestamp,
             content_type,
+            sidecars: Vec::new(),
         }
     }
 
@@ -408,4 +448,26 @@ mod tests {
         assert_eq!(episode_numbers["MyShow.2021.720p.mkv"], 4);
         assert_eq!(episode_numbers["MyShow.2020.1080p.mkv"], 5);
     }
+
+    #[test]
+    fn test_attach_sidecars_to_longest_matching_video() {
+        let mut videos = vec![
+            create_group_test_file("Movie.mkv", "/movies", 0, LibraryContentType::Movie),
+            create_group_test_file("Movie.Extended.mkv", "/movies", 0, LibraryContentType::Movie),
+        ];
+        let candidates = vec![
+            (PathBuf::from("/movies/Movie.en.srt"), MediaSidecarKind::Subtitle, 10, 0),
+            (PathBuf::from("/movies/Movie.Extended.de.forced.srt"), MediaSidecarKind::Subtitle, 10, 0),
+            (PathBuf::from("/movies/Movie.Extended.ac3"), MediaSidecarKind::Audio, 10, 0),
+            (PathBuf::from("/movies/Other.srt"), MediaSidecarKind::Subtitle, 10, 0),
+        ];
+
+        attach_sidecars(&mut videos, &candidates);
+
+        assert_eq!(videos[0].sidecars.len(), 1);
+        assert_eq!(videos[0].sidecars[0].language.as_deref(), Some("en"));
+        assert_eq!(videos[1].sidecars.len(), 2);
+        assert!(videos[1].sidecars.iter().any(|s| s.kind == MediaSidecarKind::Audio));
+        assert!(videos[1].sidecars.iter().any(|s| s.forced && s.language.as_deref() == Some("de")));
+    }
 }
@@ -0,0 +1,166 @@
+use crate::library::file_hash;
+use serde::{Deserialize, Serialize};
+use shared::model::MediaSidecarKind;
+use std::path::Path;
+
+pub const SUBTITLE_EXTENSIONS: &[&str] = &["srt", "ass", "ssa", "vtt", "sub"];
+pub const AUDIO_EXTENSIONS: &[&str] = &["mka", "aac", "ac3", "eac3", "dts", "flac", "m4a", "opus", "mp3"];
+
+const FORCED_TAGS: &[&str] = &["forced", "foreign"];
+const HEARING_IMPAIRED_TAGS: &[&str] = &["sdh", "cc"];
+// `hi` is also the language tag of Hindi, it only marks hearing impaired next to a language like `en.hi`
+const HEARING_IMPAIRED_OR_HINDI_TAG: &str = "hi";
+
+/// Subtitle or external audio file next to a library video,
+/// e.g. `movie.de.forced.srt` for `movie.mkv`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LibrarySidecar {
+    pub kind: MediaSidecarKind,
+    pub id: String,
+    pub file_path: String,
+    pub extension: String,
+    // file name part after the video name, e.g. `de.forced.srt`
+    pub suffix: String,
+    #[serde(default)]
+    pub language: Option<String>,
+    #[serde(default)]
+    pub forced: bool,
+    #[serde(default)]
+    pub hearing_impaired: bool,
+    #[serde(default)]
+    pub file_size: u64,
+    #[serde(default)]
+    pub file_modified: i64,
+}
+
+pub fn sidecar_kind_from_extension(extension: &str, video_extensions: &[String]) -> Option<MediaSidecarKind> {
+    if video_extensions.iter().any(|ext| ext == extension) {
+        None
+    } else if SUBTITLE_EXTENSIONS.contains(&extension) {
+        Some(MediaSidecarKind::Subtitle)
+    } else if AUDIO_EXTENSIONS.contains(&extension) {
+        Some(MediaSidecarKind::Audio)
+    } else {
+        None
+    }
+}
+
+fn is_language_tag(tag: &str) -> bool {
+    // `de`, `ger`, `pt-br`, `zh_hans`
+    let mut parts = tag.split(['-', '_']);
+    let primary = parts.next().unwrap_or_default();
+    (2..=3).contains(&primary.len())
+        && primary.chars().all(|c| c.is_ascii_alphabetic())
+        && parts.all(|part| (2..=4).contains(&part.len()) && part.chars().all(|c| c.is_ascii_alphanumeric()))
+}
+
+impl LibrarySidecar {
+    /// Returns the sidecar if the file belongs to the video with the given file stem.
+    pub fn for_video(
+        path: &Path,
+        kind: MediaSidecarKind,
+        video_stem: &str,
+        file_size: u64,
+        file_modified: i64,
+    ) -> Option<Self> {
+        let file_name = path.file_name()?.to_str()?;
+        let extension = path.extension()?.to_str()?.to_lowercase();
+        let stem = path.file_stem()?.to_str()?;
+        let tags = if stem.eq_ignore_ascii_case(video_stem) {
+            ""
+        } else {
+            let prefix = stem.get(..video_stem.len()).filter(|prefix| prefix.eq_ignore_ascii_case(video_stem))?;
+            stem[prefix.len()..].strip_prefix('.')?
+        };
+
+        let mut language = None;
+        let mut forced = false;
+        let mut hearing_impaired = false;
+        let mut hi_tag = false;
+        for tag in tags.split('.').map(str::to_lowercase).filter(|tag| !tag.is_empty()) {
+            if tag == HEARING_IMPAIRED_OR_HINDI_TAG {
+                hi_tag = true;
+            } else if FORCED_TAGS.contains(&tag.as_str()) {
+                forced = true;
+            } else if HEARING_IMPAIRED_TAGS.contains(&tag.as_str()) {
+                hearing_impaired = true;
+            } else if language.is_none() && is_language_tag(&tag) {
+                language = Some(tag);
+            }
+        }
+        if hi_tag {
+            if language.is_some() {
+                hearing_impaired = true;
+            } else {
+                language = Some(HEARING_IMPAIRED_OR_HINDI_TAG.to_string());
+            }
+        }
+
+        let file_path = path.display().to_string();
+        Some(Self {
+            kind,
+            id: file_hash(&file_path),
+            file_path,
+            extension,
+            suffix: file_name[video_stem.len()..].trim_start_matches('.').to_string(),
+            language,
+            forced,
+            hearing_impaired,
+            file_size,
+            file_modified,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn sidecar(file_name: &str, video_stem: &str) -> Option<LibrarySidecar> {
+        let path = PathBuf::from("/movies/Movie (2020)").join(file_name);
+        LibrarySidecar::for_video(&path, MediaSidecarKind::Subtitle, video_stem, 0, 0)
+    }
+
+    #[test]
+    fn test_sidecar_language_and_flags() {
+        let sub = sidecar("Movie.2020.de.forced.srt", "Movie.2020").unwrap();
+        assert_eq!(sub.language.as_deref(), Some("de"));
+        assert!(sub.forced);
+        assert!(!sub.hearing_impaired);
+        assert_eq!(sub.extension, "srt");
+        assert_eq!(sub.suffix, "de.forced.srt");
+
+        let sub = sidecar("Movie.2020.pt-BR.SDH.ass", "Movie.2020").unwrap();
+        assert_eq!(sub.language.as_deref(), Some("pt-br"));
+        assert!(sub.hearing_impaired);
+
+        let sub = sidecar("Movie.2020.hi.srt", "Movie.2020").unwrap();
+        assert_eq!(sub.language.as_deref(), Some("hi"));
+        assert!(!sub.hearing_impaired);
+
+        let sub = sidecar("Movie.2020.en.hi.srt", "Movie.2020").unwrap();
+        assert_eq!(sub.language.as_deref(), Some("en"));
+        assert!(sub.hearing_impaired);
+
+        let sub = sidecar("movie.2020.srt", "Movie.2020").unwrap();
+        assert_eq!(sub.language, None);
+        assert_eq!(sub.suffix, "srt");
+    }
+
+    #[test]
+    fn test_sidecar_of_other_video_is_ignored() {
+        assert!(sidecar("Movie.2020.Extended.de.srt", "Movie.2020.Extended.Cut").is_none());
+        assert!(sidecar("Movie.20201.srt", "Movie.2020").is_none());
+        assert!(sidecar("Other.srt", "Movie.2020").is_none());
+    }
+
+    #[test]
+    fn test_sidecar_kind_excludes_video_extensions() {
+        let video_extensions = vec!["mkv".to_string(), "mka".to_string()];
+        assert_eq!(sidecar_kind_from_extension("srt", &video_extensions), Some(MediaSidecarKind::Subtitle));
+        assert_eq!(sidecar_kind_from_extension("ac3", &video_extensions), Some(MediaSidecarKind::Audio));
+        assert_eq!(sidecar_kind_from_extension("mka", &video_extensions), None);
+        assert_eq!(sidecar_kind_from_extension("nfo", &video_extensions), None);
+    }
+}
@@ -237,6 +237,7 @@ impl TmdbSeriesInfoEpisodeDetails {
             file_size: 0,
             file_modified: 0,
             technical: None,
+            sidecars: Vec::new(),
         }
     }
 }
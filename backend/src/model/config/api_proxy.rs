@@ -1,4 +1,6 @@
-use crate::model::{macros, AppConfig, Config, ProxyUserCredentials, TargetUser};
+use crate::model::{
+    macros, AppConfig, Config, LimitProfile, ParentalProfile, ProxyUserCredentials, Reseller, TargetUser,
+};
 use crate::repository::{backup_api_user_db_file, get_api_user_db_path, load_api_user, merge_api_user};
 use log::debug;
 use std::cmp::PartialEq;
@@ -6,7 +8,10 @@ use std::fs;
 use std::sync::Arc;
 use arc_swap::access::Access;
 use arc_swap::ArcSwap;
-use shared::model::{ApiProxyConfigDto, ApiProxyServerInfoDto, ConfigPaths, TargetUserDto};
+use shared::model::{
+    ApiProxyConfigDto, ApiProxyServerInfoDto, ConfigPaths, LimitProfileDto, ParentalProfileDto, ResellerDto,
+    TargetUserDto,
+};
 use crate::{utils};
 use crate::utils::file_exists_async;
 
@@ -75,6 +80,9 @@ pub struct ApiProxyConfig {
     pub use_user_db: bool,
     /// HTTP status code for auth failures. 0 means default (403).
     pub auth_error_status: u16,
+    pub parental_profiles: Vec<Arc<ParentalProfile>>,
+    pub limit_profiles: Vec<Arc<LimitProfile>>,
+    pub resellers: Vec<Reseller>,
 }
 
 macros::from_impl!(ApiProxyConfig);
@@ -85,6 +93,9 @@ impl From<&ApiProxyConfigDto> for ApiProxyConfig {
             user: dto.user.iter().map(TargetUser::from).collect(),
             use_user_db: dto.use_user_db,
             auth_error_status: dto.auth_error_status,
+            parental_profiles: dto.parental_profiles.iter().map(|p| Arc::new(ParentalProfile::from(p))).collect(),
+            limit_profiles: dto.limit_profiles.iter().map(|p| Arc::new(LimitProfile::from(p))).collect(),
+            resellers: dto.resellers.iter().map(Reseller::from).collect(),
         }
     }
 }
@@ -96,6 +107,9 @@ impl From<&ApiProxyConfig> for ApiProxyConfigDto {
             user: instance.user.iter().map(TargetUserDto::from).collect(),
             use_user_db: instance.use_user_db,
             auth_error_status: instance.auth_error_status,
+            parental_profiles: instance.parental_profiles.iter().map(|p| ParentalProfileDto::from(p.as_ref())).collect(),
+            limit_profiles: instance.limit_profiles.iter().map(|p| LimitProfileDto::from(p.as_ref())).collect(),
+            resellers: instance.resellers.iter().map(ResellerDto::from).collect(),
         }
     }
 }
@@ -189,6 +203,18 @@ impl ApiProxyConfig {
         None
     }
 
+    pub fn get_parental_profile(&self, name: &str) -> Option<Arc<ParentalProfile>> {
+        self.parental_profiles.iter().find(|profile| profile.name == name).cloned()
+    }
+
+    pub fn get_limit_profile(&self, name: &str) -> Option<Arc<LimitProfile>> {
+        self.limit_profiles.iter().find(|profile| profile.name == name).cloned()
+    }
+
+    pub fn get_reseller(&self, name: &str) -> Option<&Reseller> {
+        self.resellers.iter().find(|reseller| reseller.name.eq_ignore_ascii_case(name))
+    }
+
     pub fn get_user_credentials(&self, username: &str) -> Option<ProxyUserCredentials> {
         let result = self.user.iter()
             .flat_map(|target_user| &target_user.credentials)
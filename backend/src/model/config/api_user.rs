@@ -2,9 +2,14 @@ use crate::api::model::AppState;
 use crate::model::{macros, Config};
 use arc_swap::access::Access;
 use arc_swap::ArcSwap;
-use chrono::Local;
+use chrono::{DateTime, Datelike, Local, Timelike, Utc};
+use chrono_tz::Tz;
 use log::debug;
-use shared::model::{ProxyType, ProxyUserCredentialsDto, ProxyUserStatus, TargetUserDto, UserConnectionPermission};
+use shared::model::{
+    AccessScheduleDay, PlaylistItem, ProxyType, ProxyUserAccessScheduleDto, ProxyUserCountryAccessDto,
+    ProxyUserCredentialsDto, ProxyUserDeviceLimitDto, ProxyUserQuotaDto, ProxyUserStatus, TargetUserDto,
+    UserConnectionPermission, UserQuotaStatusDto,
+};
 use std::sync::Arc;
 use zeroize::Zeroize;
 
@@ -26,7 +31,17 @@ pub struct ProxyUserCredentials {
     pub priority: i8,
     pub soft_connections: u16,
     pub soft_priority: i8,
+    pub quota: Option<ProxyUserQuotaDto>,
+    pub access_schedule: Option<ProxyUserAccessScheduleDto>,
+    pub parental_profile: Option<String>,
+    pub device_limit: Option<ProxyUserDeviceLimitDto>,
+    pub country_access: Option<ProxyUserCountryAccessDto>,
+    pub limit_profile: Option<String>,
+    pub reseller: Option<String>,
+    pub hls_packaging: Option<bool>,
     pub t_is_api_user: bool,
+    /// Set per request when the pin of the user's parental profile unlocked restricted content.
+    pub t_parental_unlocked: bool,
 }
 
 macros::from_impl!(ProxyUserCredentials);
@@ -49,7 +64,16 @@ impl From<&ProxyUserCredentialsDto> for ProxyUserCredentials {
             priority: dto.priority,
             soft_connections: dto.soft_connections,
             soft_priority: dto.soft_priority,
+            quota: dto.quota.clone(),
+            access_schedule: dto.access_schedule.clone(),
+            parental_profile: dto.parental_profile.clone(),
+            device_limit: dto.device_limit,
+            country_access: dto.country_access.clone(),
+            limit_profile: dto.limit_profile.clone(),
+            reseller: dto.reseller.clone(),
+            hls_packaging: dto.hls_packaging,
             t_is_api_user: false,
+            t_parental_unlocked: false,
         }
     }
 }
@@ -73,6 +97,14 @@ impl From<&ProxyUserCredentials> for ProxyUserCredentialsDto {
             priority: instance.priority,
             soft_connections: instance.soft_connections,
             soft_priority: instance.soft_priority,
+            quota: instance.quota.clone(),
+            access_schedule: instance.access_schedule.clone(),
+            parental_profile: instance.parental_profile.clone(),
+            device_limit: instance.device_limit,
+            country_access: instance.country_access.clone(),
+            limit_profile: instance.limit_profile.clone(),
+            reseller: instance.reseller.clone(),
+            hls_packaging: instance.hls_packaging,
         }
     }
 }
@@ -124,6 +156,72 @@ impl ProxyUserCredentials {
         }
         UserConnectionPermission::Allowed
     }
+
+    /// Returns the usage of the configured quota, `None` when the user has no quota
+    /// or user access control is disabled.
+    pub async fn quota_status(&self, app_state: &AppState) -> Option<UserQuotaStatusDto> {
+        let quota = self.quota.as_ref()?;
+        if !<Arc<ArcSwap<Config>> as Access<Config>>::load(&app_state.app_config.config).user_access_control {
+            return None;
+        }
+        Some(app_state.user_quota.status(&self.username, quota).await)
+    }
+
+    pub async fn quota_exhausted(&self, app_state: &AppState) -> bool {
+        let exhausted = self.quota_status(app_state).await.is_some_and(|status| status.exhausted);
+        if exhausted {
+            debug!("User access denied, quota exhausted: {}", self.username);
+        }
+        exhausted
+    }
+
+    /// Checks the access schedule at `now`. The schedule timezone takes precedence over
+    /// `server_timezone`, UTC is used when neither can be parsed.
+    pub fn is_within_access_schedule_at(&self, now: DateTime<Utc>, server_timezone: &str) -> bool {
+        let Some(schedule) = self.access_schedule.as_ref() else {
+            return true;
+        };
+        let tz = schedule
+            .timezone
+            .as_deref()
+            .and_then(|name| name.parse::<Tz>().ok())
+            .or_else(|| server_timezone.parse::<Tz>().ok())
+            .unwrap_or(Tz::UTC);
+        let local_now = now.with_timezone(&tz);
+        let day = AccessScheduleDay::from_index(local_now.weekday().num_days_from_monday());
+        #[allow(clippy::cast_possible_truncation)]
+        let minute_of_day = (local_now.hour() * 60 + local_now.minute()) as u16;
+        schedule.is_allowed(day, minute_of_day)
+    }
+
+    pub fn outside_access_schedule(&self, app_state: &AppState) -> bool {
+        if self.access_schedule.is_none()
+            || !<Arc<ArcSwap<Config>> as Access<Config>>::load(&app_state.app_config.config).user_access_control
+        {
+            return false;
+        }
+        let server_info = app_state.app_config.get_user_server_info(self);
+        let outside = !self.is_within_access_schedule_at(Utc::now(), &server_info.timezone);
+        if outside {
+            debug!("User access denied, outside of access schedule: {}", self.username);
+        }
+        outside
+    }
+
+    /// Returns true when the user's parental profile hides or locks the item.
+    pub fn parental_restricted<'a, T>(&self, app_state: &AppState, item: &'a T) -> bool
+    where
+        PlaylistItem: From<&'a T>,
+    {
+        let restricted = app_state
+            .app_config
+            .get_user_parental_restriction(self)
+            .is_some_and(|profile| profile.is_restricted(&PlaylistItem::from(item)));
+        if restricted {
+            debug!("User access denied, content restricted by parental profile: {}", self.username);
+        }
+        restricted
+    }
 }
 
 impl Drop for ProxyUserCredentials {
@@ -178,3 +276,40 @@ impl TargetUser {
             .map(|credentials| (credentials, self.target.as_str()))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::ProxyUserCredentials;
+    use chrono::{TimeZone, Utc};
+    use shared::model::{AccessScheduleDay, AccessWindowDto, ProxyUserAccessScheduleDto};
+
+    fn scheduled_user(timezone: Option<&str>) -> ProxyUserCredentials {
+        let mut user = ProxyUserCredentials::default();
+        user.username = "kid".to_string();
+        user.access_schedule = Some(ProxyUserAccessScheduleDto {
+            timezone: timezone.map(ToString::to_string),
+            windows: vec![AccessWindowDto {
+                days: vec![AccessScheduleDay::Mon],
+                from: "18:00".to_string(),
+                to: "20:00".to_string(),
+            }],
+            kick_on_close: false,
+        });
+        user
+    }
+
+    #[test]
+    fn access_schedule_uses_user_timezone_before_server_timezone() {
+        // Monday 17:30 UTC is 19:30 in Berlin (CEST)
+        let now = Utc.with_ymd_and_hms(2026, 6, 1, 17, 30, 0).unwrap();
+        assert!(scheduled_user(Some("Europe/Berlin")).is_within_access_schedule_at(now, "UTC"));
+        assert!(!scheduled_user(None).is_within_access_schedule_at(now, "UTC"));
+        assert!(scheduled_user(None).is_within_access_schedule_at(now, "Europe/Berlin"));
+    }
+
+    #[test]
+    fn user_without_schedule_is_always_allowed() {
+        let user = ProxyUserCredentials::default();
+        assert!(user.is_within_access_schedule_at(Utc::now(), "UTC"));
+    }
+}
@@ -1,8 +1,8 @@
 use crate::api::model::TransportStreamBuffer;
 use crate::model::{
     ApiProxyConfig, ApiProxyServerInfo, Config, ConfigInput, ConfigInputOptions, ConfigTarget, CustomStreamResponse,
-    GracePeriodOptions, HdHomeRunConfig, HdHomeRunFlags, Mappings, MediaToolCapabilities, ProxyUserCredentials,
-    ReverseProxyDisabledHeaderConfig, SourcesConfig, TargetOutput,
+    GracePeriodOptions, HdHomeRunConfig, HdHomeRunFlags, LimitProfile, Mappings, MediaToolCapabilities, ParentalProfile,
+    ProxyUserCredentials, ReverseProxyDisabledHeaderConfig, SourcesConfig, TargetOutput,
 };
 use crate::utils;
 use arc_swap::{ArcSwap, ArcSwapOption};
@@ -13,7 +13,7 @@ use shared::info_err_res;
 use shared::model::ConfigPaths;
 use shared::utils::{
     CHANNEL_UNAVAILABLE, LOW_PRIORITY_PREEMPTED, PANEL_API_PROVISIONING, PROVIDER_CONNECTIONS_EXHAUSTED,
-    USER_ACCOUNT_EXPIRED, USER_CONNECTIONS_EXHAUSTED,
+    USER_ACCOUNT_EXPIRED, USER_CONNECTIONS_EXHAUSTED, USER_QUOTA_EXHAUSTED,
 };
 use std::borrow::Cow;
 use std::collections::HashSet;
@@ -104,14 +104,29 @@ impl AppConfig {
                 .is_some_and(|h| h.flags.contains(HdHomeRunFlags::Enabled))
         };
         let sources = self.sources.load();
+        let mut multicast_addresses = HashSet::new();
         for source in &sources.sources {
             for target in &source.targets {
                 for output in &target.output {
                     match output {
-                        TargetOutput::Xtream(_) | TargetOutput::M3u(_) => {}
+                        TargetOutput::Xtream(_) | TargetOutput::M3u(_) | TargetOutput::Json(_) | TargetOutput::Csv(_) => {}
+                        TargetOutput::Multicast(multicast_output) => {
+                            for channel in &multicast_output.channels {
+                                if !multicast_addresses.insert(channel.address) {
+                                    return info_err_res!(
+                                        "Multicast address {} of target {} is already used by another target",
+                                        channel.address,
+                                        target.name
+                                    );
+                                }
+                            }
+                        }
                         TargetOutput::Strm(strm_output) => {
                             self.check_username(strm_output.username.as_deref(), &target.name)?;
                         }
+                        TargetOutput::Enigma2(enigma2_output) => {
+                            self.check_username(Some(&enigma2_output.username), &target.name)?;
+                        }
                         TargetOutput::HdHomeRun(hdhomerun_output) => {
                             if check_homerun {
                                 let hdhr_name = &hdhomerun_output.device;
@@ -133,6 +148,19 @@ impl AppConfig {
             }
         }
 
+        let dlna = self.config.load().dlna.as_ref()
+            .filter(|dlna| dlna.enabled)
+            .map(|dlna| (dlna.username.clone(), dlna.lan_only));
+        if let Some((username, lan_only)) = dlna {
+            match self.get_target_for_username(&username) {
+                None => return info_err_res!("DLNA user: {username} does not exist"),
+                Some((user, _)) if !lan_only && user.token.as_deref().is_none_or(str::is_empty) => {
+                    return info_err_res!("DLNA user: {username} needs a token when lan_only is disabled");
+                }
+                Some(_) => {}
+            }
+        }
+
         let guard = self.hdhomerun.load();
         if let Some(hdhomerun) = &*guard {
             for device in &hdhomerun.devices {
@@ -258,6 +286,11 @@ impl AppConfig {
         sources.get_target_by_id(target_id)
     }
 
+    pub fn get_target_by_name(&self, target_name: &str) -> Option<Arc<ConfigTarget>> {
+        let sources = self.sources.load();
+        sources.get_target_by_name(target_name)
+    }
+
     fn check_unique_input_names(&self) -> Result<(), TuliproxError> {
         let mut seen_names: HashSet<String> = HashSet::new();
         let sources = self.sources.load();
@@ -427,6 +460,8 @@ impl AppConfig {
             let low_priority_preempted = load_and_set_file(&path.join(LOW_PRIORITY_PREEMPTED))
                 .or_else(|| provider_connections_exhausted.clone());
             let user_account_expired = load_and_set_file(&path.join(USER_ACCOUNT_EXPIRED));
+            let user_quota_exhausted = load_and_set_file(&path.join(USER_QUOTA_EXHAUSTED))
+                .or_else(|| user_connections_exhausted.clone());
             let panel_api_provisioning = load_and_set_file(&path.join(PANEL_API_PROVISIONING));
             self.custom_stream_response.store(Some(Arc::new(CustomStreamResponse {
                 channel_unavailable,
@@ -434,6 +469,7 @@ impl AppConfig {
                 provider_connections_exhausted,
                 low_priority_preempted,
                 user_account_expired,
+                user_quota_exhausted,
                 panel_api_provisioning,
             })));
         }
@@ -461,6 +497,29 @@ impl AppConfig {
         self.get_server_info(server_info_name)
     }
 
+    /// Returns the parental profile restricting the user, `None` when the user has no profile
+    /// or the restricted content was unlocked with the profile pin.
+    pub fn get_user_parental_restriction(&self, user: &ProxyUserCredentials) -> Option<Arc<ParentalProfile>> {
+        if user.t_parental_unlocked {
+            return None;
+        }
+        let profile_name = user.parental_profile.as_deref()?;
+        self.api_proxy.load().as_ref().and_then(|api_proxy| api_proxy.get_parental_profile(profile_name))
+    }
+
+    /// Returns the limit profile of the user, users without a profile use the profile of their target.
+    pub fn get_user_limit_profile(&self, user: &ProxyUserCredentials) -> Option<Arc<LimitProfile>> {
+        let api_proxy = self.api_proxy.load();
+        let api_proxy = api_proxy.as_ref().filter(|api_proxy| !api_proxy.limit_profiles.is_empty())?;
+        if let Some(profile_name) = user.limit_profile.as_deref() {
+            return api_proxy.get_limit_profile(profile_name);
+        }
+        let (_, target) =
+            self.intern_get_target_for_user(api_proxy.get_target_name(&user.username, &user.password))?;
+        let profile_name = target.options.as_ref()?.limit_profile.as_deref()?;
+        api_proxy.get_limit_profile(profile_name)
+    }
+
     pub fn get_disabled_headers(&self) -> Option<ReverseProxyDisabledHeaderConfig> {
         let config = self.config.load();
         config.get_disabled_headers()
@@ -1,14 +1,14 @@
 use crate::model::{
-    macros, ConfigApi, HdHomeRunConfig, HdHomeRunFlags, IpCheckConfig, LibraryConfig, LogConfig, MetadataUpdateConfig,
-    MessagingConfig, ProxyConfig, ReverseProxyConfig, ReverseProxyDisabledHeaderConfig, ScheduleConfig, VideoConfig,
-    WebUiConfig,
+    macros, ConfigApi, DlnaConfig, HdHomeRunConfig, HdHomeRunFlags, IngestConfig, IpCheckConfig, LibraryConfig,
+    LogConfig, MetadataUpdateConfig, MessagingConfig, ProxyConfig, ReverseProxyConfig, ReverseProxyDisabledHeaderConfig,
+    ScheduleConfig, VideoConfig, WebUiConfig,
 };
 use crate::utils;
 use log::{error, info};
 use path_clean::PathClean;
 use shared::error::TuliproxError;
 use shared::model::{ConfigDto, HdHomeRunDeviceOverview};
-use shared::utils::{default_grace_period_millis, default_grace_period_timeout_secs, set_sanitize_sensitive_info, DEFAULT_BACKUP_DIR, DEFAULT_CACHE_DIR, DEFAULT_DOWNLOAD_DIR, DEFAULT_STORAGE_DIR, DEFAULT_STORAGE_TEMP_DIR, DEFAULT_USER_CONFIG_DIR};
+use shared::utils::{default_grace_period_millis, default_grace_period_timeout_secs, set_sanitize_sensitive_info, DEFAULT_BACKUP_DIR, DEFAULT_CACHE_DIR, DEFAULT_DOWNLOAD_DIR, DEFAULT_STORAGE_DIR, DEFAULT_STORAGE_TEMP_DIR, DEFAULT_USER_CONFIG_DIR, DEFAULT_VOD_CACHE_DIR};
 use std::borrow::Cow;
 use std::path::{Path, PathBuf};
 use crate::utils::get_default_path_for_home;
@@ -21,6 +21,7 @@ fn create_directories(cfg: &Config, temp_path: &Path) {
         cfg.user_config_dir.clone(),
         cfg.video.as_ref().and_then(|v| v.download.as_ref()).map(|d| d.directory.clone()),
         cfg.reverse_proxy.as_ref().and_then(|r| r.cache.as_ref().and_then(|c| if c.enabled { Some(c.directory.clone()) } else { None })),
+        cfg.reverse_proxy.as_ref().and_then(|r| r.vod_cache.as_ref().and_then(|c| if c.enabled { Some(c.directory.clone()) } else { None })),
         cfg.metadata_update.as_ref().map(|m| m.cache_path.clone()),
     ];
 
@@ -88,9 +89,11 @@ pub struct Config {
     pub messaging: Option<MessagingConfig>,
     pub reverse_proxy: Option<ReverseProxyConfig>,
     pub hdhomerun: Option<HdHomeRunConfig>,
+    pub dlna: Option<DlnaConfig>,
     pub proxy: Option<ProxyConfig>,
     pub ipcheck: Option<IpCheckConfig>,
     pub library: Option<LibraryConfig>,
+    pub ingest: Option<IngestConfig>,
 }
 
 impl Config {
@@ -182,6 +185,12 @@ impl Config {
         let Some(reverse_proxy) = self.reverse_proxy.as_mut() else {
             return;
         };
+        if let Some(vod_cache) = reverse_proxy.vod_cache.as_mut().filter(|c| c.enabled) {
+            let normalized =
+                Self::normalize_storage_path(raw_storage_dir, &self.storage_dir, &vod_cache.directory, DEFAULT_VOD_CACHE_DIR)
+                    .unwrap_or_else(|| PathBuf::from(vod_cache.directory.trim()));
+            vod_cache.directory = normalized.clean().to_string_lossy().to_string();
+        }
         let Some(cache) = reverse_proxy.cache.as_mut() else {
             return;
         };
@@ -311,9 +320,11 @@ impl From<&ConfigDto> for Config {
             messaging: dto.messaging.as_ref().map(Into::into),
             reverse_proxy: dto.reverse_proxy.as_ref().map(Into::into),
             hdhomerun: dto.hdhomerun.as_ref().map(Into::into),
+            dlna: dto.dlna.as_ref().map(Into::into),
             proxy: dto.proxy.as_ref().map(Into::into),
             ipcheck: dto.ipcheck.as_ref().map(Into::into),
             library: dto.library.as_ref().map(Into::into),
+            ingest: dto.ingest.as_ref().map(Into::into),
         }
     }
 }
@@ -0,0 +1,30 @@
+use crate::model::macros;
+use shared::model::DlnaConfigDto;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DlnaConfig {
+    pub enabled: bool,
+    pub username: String,
+    pub friendly_name: String,
+    pub device_udn: String,
+    pub ssdp_discovery: bool,
+    pub lan_only: bool,
+}
+
+impl DlnaConfig {
+    pub fn is_ssdp_enabled(&self) -> bool { self.enabled && self.ssdp_discovery }
+}
+
+macros::from_impl!(DlnaConfig);
+impl From<&DlnaConfigDto> for DlnaConfig {
+    fn from(dto: &DlnaConfigDto) -> Self {
+        Self {
+            enabled: dto.enabled,
+            username: dto.username.clone(),
+            friendly_name: dto.friendly_name.clone(),
+            device_udn: dto.device_udn.clone().unwrap_or_default(),
+            ssdp_discovery: dto.ssdp_discovery,
+            lan_only: dto.lan_only,
+        }
+    }
+}
@@ -1,10 +1,12 @@
-use shared::model::GeoIpConfigDto;
+use shared::model::{CountryAccessDto, GeoIpConfigDto};
 use crate::model::macros;
 
 #[derive(Debug, Clone)]
 pub struct GeoIpConfig {
     pub(crate) enabled: bool,
     pub(crate) url: String,
+    pub(crate) ipv6_url: String,
+    pub(crate) country_access: Option<CountryAccessDto>,
 }
 
 macros::from_impl!(GeoIpConfig);
@@ -14,6 +16,8 @@ impl From<&GeoIpConfigDto> for GeoIpConfig {
         Self {
             enabled: dto.enabled,
             url: dto.url.clone(),
+            ipv6_url: dto.ipv6_url.clone(),
+            country_access: dto.country_access.clone(),
         }
     }
 }
@@ -23,6 +27,8 @@ impl From<&GeoIpConfig> for GeoIpConfigDto {
         Self {
             enabled: instance.enabled,
             url: instance.url.clone(),
+            ipv6_url: instance.ipv6_url.clone(),
+            country_access: instance.country_access.clone(),
         }
     }
 }
@@ -0,0 +1,69 @@
+use crate::auth::constant_time_eq;
+use crate::model::macros;
+use shared::model::{IngestChannelConfigDto, IngestConfigDto};
+
+/// Url scheme of the playlist items of ingest channels, the stream url of an ingest channel is `ingest://{key}`.
+pub const INGEST_URL_SCHEME: &str = "ingest://";
+
+pub fn ingest_stream_url(key: &str) -> String { format!("{INGEST_URL_SCHEME}{key}") }
+
+pub fn is_ingest_url(url: &str) -> bool { url.starts_with(INGEST_URL_SCHEME) }
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IngestChannelConfig {
+    pub key: String,
+    pub name: String,
+    pub group: Option<String>,
+    pub logo: Option<String>,
+    pub epg_channel_id: Option<String>,
+    pub token: Option<String>,
+}
+
+macros::from_impl!(IngestChannelConfig);
+impl From<&IngestChannelConfigDto> for IngestChannelConfig {
+    fn from(dto: &IngestChannelConfigDto) -> Self {
+        Self {
+            key: dto.key.clone(),
+            name: dto.name.clone(),
+            group: dto.group.clone(),
+            logo: dto.logo.clone(),
+            epg_channel_id: dto.epg_channel_id.clone(),
+            token: dto.token.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IngestConfig {
+    pub enabled: bool,
+    pub token: Option<String>,
+    pub group: String,
+    pub channels: Vec<IngestChannelConfig>,
+}
+
+impl IngestConfig {
+    pub fn get_channel(&self, key: &str) -> Option<&IngestChannelConfig> {
+        self.channels.iter().find(|channel| channel.key == key)
+    }
+
+    /// Checks the token of an encoder, the channel token takes precedence over the ingest token.
+    pub fn is_authorized(&self, channel: &IngestChannelConfig, token: &str) -> bool {
+        channel
+            .token
+            .as_deref()
+            .or(self.token.as_deref())
+            .is_some_and(|expected| constant_time_eq(expected.as_bytes(), token.as_bytes()))
+    }
+}
+
+macros::from_impl!(IngestConfig);
+impl From<&IngestConfigDto> for IngestConfig {
+    fn from(dto: &IngestConfigDto) -> Self {
+        Self {
+            enabled: dto.enabled,
+            token: dto.token.clone(),
+            group: dto.group.clone(),
+            channels: dto.channels.iter().map(Into::into).collect(),
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use crate::model::macros;
+use shared::model::LimitProfileDto;
+
+#[derive(Debug, Clone)]
+pub struct LimitProfile {
+    pub name: String,
+    pub throttle_str: Option<String>,
+    pub throttle_kbps: u64,
+    pub period_millis: u64,
+    pub burst_size: u32,
+}
+
+macros::from_impl!(LimitProfile);
+impl From<&LimitProfileDto> for LimitProfile {
+    fn from(dto: &LimitProfileDto) -> Self {
+        Self {
+            name: dto.name.clone(),
+            throttle_str: dto.throttle.clone(),
+            throttle_kbps: dto.throttle_kbps,
+            period_millis: dto.period_millis,
+            burst_size: dto.burst_size,
+        }
+    }
+}
+
+impl From<&LimitProfile> for LimitProfileDto {
+    fn from(instance: &LimitProfile) -> Self {
+        Self {
+            name: instance.name.clone(),
+            throttle: instance.throttle_str.clone(),
+            period_millis: instance.period_millis,
+            burst_size: instance.burst_size,
+            throttle_kbps: instance.throttle_kbps,
+        }
+    }
+}
+
+impl LimitProfile {
+    pub fn has_rate_limit(&self) -> bool { self.period_millis > 0 && self.burst_size > 0 }
+}
@@ -0,0 +1,45 @@
+use shared::model::LoginProtectionConfigDto;
+use shared::utils::IpNetwork;
+use crate::model::macros;
+
+#[derive(Debug, Clone)]
+pub struct LoginProtectionConfig {
+    pub enabled: bool,
+    pub max_failures: u32,
+    pub user_max_failures: u32,
+    pub find_time_secs: u64,
+    pub ban_secs: u64,
+    pub max_ban_secs: u64,
+    pub allowlist: Vec<String>,
+    pub allowlist_networks: Vec<IpNetwork>,
+}
+
+macros::from_impl!(LoginProtectionConfig);
+impl From<&LoginProtectionConfigDto> for LoginProtectionConfig {
+    fn from(dto: &LoginProtectionConfigDto) -> Self {
+        Self {
+            enabled: dto.enabled,
+            max_failures: dto.max_failures,
+            user_max_failures: dto.user_max_failures,
+            find_time_secs: dto.find_time_secs,
+            ban_secs: dto.ban_secs,
+            max_ban_secs: dto.max_ban_secs,
+            allowlist: dto.allowlist.clone(),
+            allowlist_networks: dto.allowlist.iter().filter_map(|entry| IpNetwork::parse(entry)).collect(),
+        }
+    }
+}
+
+impl From<&LoginProtectionConfig> for LoginProtectionConfigDto {
+    fn from(instance: &LoginProtectionConfig) -> Self {
+        Self {
+            enabled: instance.enabled,
+            max_failures: instance.max_failures,
+            user_max_failures: instance.user_max_failures,
+            find_time_secs: instance.find_time_secs,
+            ban_secs: instance.ban_secs,
+            max_ban_secs: instance.max_ban_secs,
+            allowlist: instance.allowlist.clone(),
+        }
+    }
+}
@@ -6,6 +6,8 @@ mod messaging;
 mod metadata_update;
 mod media_tools;
 mod hdhomerun;
+mod dlna;
+mod ingest;
 mod ip_check;
 mod source;
 mod target;
@@ -19,7 +21,9 @@ mod epg;
 mod epg_smart_match;
 mod reverse_proxy;
 mod cache;
+mod vod_cache;
 mod rate_limit;
+mod login_protection;
 mod proxy;
 mod schedule;
 mod api_proxy;
@@ -33,14 +37,19 @@ pub mod macros;
 mod favourites;
 mod geoip;
 mod library;
+mod limit_profile;
 mod panel_api;
+mod parental;
 mod qos_aggregation;
+mod reseller;
 
 pub use api::*;
 pub use api_proxy::*;
 pub use api_user::*;
 pub use app::*;
 pub use base::*;
+pub use dlna::*;
+pub use ingest::*;
 pub use epg::*;
 pub use epg_smart_match::*;
 pub use hdhomerun::*;
@@ -53,6 +62,7 @@ pub use media_tools::*;
 pub use metadata_update::*;
 pub use proxy::*;
 pub use rate_limit::*;
+pub use login_protection::*;
 pub use rename::*;
 pub use reverse_proxy::*;
 pub use geoip::*;
@@ -65,9 +75,13 @@ pub use target::*;
 pub use trakt::*;
 pub use trakt_api::*;
 pub use video_download::*;
+pub use vod_cache::*;
 pub use web_auth::*;
 pub use web_ui::*;
 pub use favourites::*;
 pub use library::*;
+pub use limit_profile::*;
 pub use panel_api::*;
+pub use parental::*;
 pub use qos_aggregation::*;
+pub use reseller::*;
@@ -0,0 +1,84 @@
+use crate::auth::constant_time_eq;
+use crate::model::macros;
+use shared::foundation::{Filter, ValueProvider};
+use shared::model::{ParentalMode, ParentalProfileDto, PlaylistItem, StreamProperties};
+
+#[derive(Debug, Clone)]
+pub struct ParentalProfile {
+    pub name: String,
+    pub filter: Option<Filter>,
+    pub adult: bool,
+    pub mode: ParentalMode,
+    pub pin: Option<String>,
+    pub unlock_mins: u32,
+}
+
+macros::from_impl!(ParentalProfile);
+impl From<&ParentalProfileDto> for ParentalProfile {
+    fn from(dto: &ParentalProfileDto) -> Self {
+        Self {
+            name: dto.name.clone(),
+            filter: dto.t_filter.clone(),
+            adult: dto.adult,
+            mode: dto.mode,
+            pin: dto.pin.clone(),
+            unlock_mins: dto.unlock_mins,
+        }
+    }
+}
+
+impl From<&ParentalProfile> for ParentalProfileDto {
+    fn from(instance: &ParentalProfile) -> Self {
+        Self {
+            name: instance.name.clone(),
+            filter: instance.filter.as_ref().map(ToString::to_string),
+            adult: instance.adult,
+            mode: instance.mode,
+            pin: instance.pin.clone(),
+            unlock_mins: instance.unlock_mins,
+            t_filter: instance.filter.clone(),
+        }
+    }
+}
+
+impl ParentalProfile {
+    /// Returns true when the item matches the profile filter or carries the provider adult flag.
+    pub fn is_restricted(&self, pli: &PlaylistItem) -> bool {
+        (self.adult && pli.header.additional_properties.as_ref().is_some_and(StreamProperties::is_adult))
+            || self.filter.as_ref().is_some_and(|filter| filter.filter(&ValueProvider { pli, match_as_ascii: false }))
+    }
+
+    pub fn is_pin_valid(&self, pin: &str) -> bool {
+        self.mode == ParentalMode::Pin
+            && self.pin.as_deref().is_some_and(|p| constant_time_eq(p.as_bytes(), pin.trim().as_bytes()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ParentalProfile;
+    use shared::model::{ParentalMode, ParentalProfileDto, PlaylistItem, PlaylistItemHeader};
+    use shared::utils::Internable;
+
+    fn item(group: &str) -> PlaylistItem {
+        PlaylistItem {
+            header: PlaylistItemHeader { name: "channel".intern(), group: group.intern(), ..Default::default() },
+        }
+    }
+
+    #[test]
+    fn test_parental_profile_restricts_by_filter() {
+        let mut dto = ParentalProfileDto {
+            name: "kids".to_string(),
+            filter: Some(r#"Group ~ "(?i)^adult""#.to_string()),
+            mode: ParentalMode::Pin,
+            pin: Some("1234".to_string()),
+            ..Default::default()
+        };
+        dto.prepare().unwrap();
+        let profile = ParentalProfile::from(&dto);
+        assert!(profile.is_restricted(&item("Adult Movies")));
+        assert!(!profile.is_restricted(&item("News")));
+        assert!(profile.is_pin_valid("1234"));
+    }
+}
@@ -0,0 +1,37 @@
+use crate::model::macros;
+use shared::model::ResellerDto;
+
+#[derive(Debug, Clone)]
+pub struct Reseller {
+    pub name: String,
+    pub targets: Vec<String>,
+    pub credits: u32,
+    pub max_connections: u32,
+}
+
+macros::from_impl!(Reseller);
+impl From<&ResellerDto> for Reseller {
+    fn from(dto: &ResellerDto) -> Self {
+        Self {
+            name: dto.name.clone(),
+            targets: dto.targets.clone(),
+            credits: dto.credits,
+            max_connections: dto.max_connections,
+        }
+    }
+}
+
+impl From<&Reseller> for ResellerDto {
+    fn from(instance: &Reseller) -> Self {
+        Self {
+            name: instance.name.clone(),
+            targets: instance.targets.clone(),
+            credits: instance.credits,
+            max_connections: instance.max_connections,
+        }
+    }
+}
+
+impl Reseller {
+    pub fn has_target(&self, target: &str) -> bool { self.targets.iter().any(|t| t == target) }
+}
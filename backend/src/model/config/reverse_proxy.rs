@@ -1,8 +1,11 @@
 use crate::model::config::cache::CacheConfig;
-use crate::model::{macros, GeoIpConfig, QosAggregationConfig, RateLimitConfig, StreamConfig};
+use crate::model::{
+    macros, GeoIpConfig, LoginProtectionConfig, QosAggregationConfig, RateLimitConfig, StreamConfig, VodCacheConfig,
+};
 use regex::Regex;
 use shared::model::{ResourceRetryConfigDto, ReverseProxyConfigDto, ReverseProxyDisabledHeaderConfigDto, REGEX_CACHE};
 use shared::utils::{default_resource_retry_attempts, default_resource_retry_backoff_ms, default_resource_retry_backoff_multiplier, hex_to_u8_16, u8_16_to_hex};
+use shared::utils::IpNetwork;
 use std::cmp::max;
 use std::sync::Arc;
 
@@ -129,11 +132,15 @@ impl From<&ResourceRetryConfig> for ResourceRetryConfigDto {
 pub struct ReverseProxyConfig {
     pub resource_rewrite_disabled: bool,
     pub rewrite_secret: [u8; 16],
+    pub trusted_proxies: Vec<String>,
+    pub trusted_proxy_networks: Vec<IpNetwork>,
     pub resource_retry: ResourceRetryConfig,
     pub disabled_header: Option<ReverseProxyDisabledHeaderConfig>,
     pub stream: Option<StreamConfig>,
     pub cache: Option<CacheConfig>,
+    pub vod_cache: Option<VodCacheConfig>,
     pub rate_limit: Option<RateLimitConfig>,
+    pub login_protection: Option<LoginProtectionConfig>,
     pub geoip: Option<GeoIpConfig>,
     pub stream_history: Option<crate::model::StreamHistoryConfig>,
     pub qos_aggregation: Option<QosAggregationConfig>,
@@ -146,6 +153,8 @@ impl From<&ReverseProxyConfigDto> for ReverseProxyConfig {
         Self {
             resource_rewrite_disabled: dto.resource_rewrite_disabled,
             rewrite_secret: hex_to_u8_16(&dto.rewrite_secret).unwrap_or_default(),
+            trusted_proxies: dto.trusted_proxies.clone(),
+            trusted_proxy_networks: dto.trusted_proxies.iter().filter_map(|entry| IpNetwork::parse(entry)).collect(),
             resource_retry: dto
                 .resource_retry
                 .as_ref()
@@ -158,7 +167,9 @@ impl From<&ReverseProxyConfigDto> for ReverseProxyConfig {
             }),
             stream: dto.stream.as_ref().map(Into::into),
             cache: dto.cache.as_ref().map(Into::into),
+            vod_cache: dto.vod_cache.as_ref().map(Into::into),
             rate_limit: dto.rate_limit.as_ref().map(Into::into),
+            login_protection: dto.login_protection.as_ref().map(Into::into),
             geoip: dto.geoip.as_ref().map(Into::into),
             stream_history: dto.stream_history.as_ref().map(Into::into),
             qos_aggregation: dto.qos_aggregation.as_ref().map(Into::into),
@@ -171,6 +182,7 @@ impl From<&ReverseProxyConfig> for ReverseProxyConfigDto {
         Self {
             resource_rewrite_disabled: instance.resource_rewrite_disabled,
             rewrite_secret: u8_16_to_hex(&instance.rewrite_secret),
+            trusted_proxies: instance.trusted_proxies.clone(),
             resource_retry: Some(ResourceRetryConfigDto::from(&instance.resource_retry)),
             disabled_header: instance.disabled_header.as_ref().map(|d| ReverseProxyDisabledHeaderConfigDto {
                 referer_header: d.referer_header,
@@ -180,7 +192,9 @@ impl From<&ReverseProxyConfig> for ReverseProxyConfigDto {
             }),
             stream: instance.stream.as_ref().map(Into::into),
             cache: instance.cache.as_ref().map(Into::into),
+            vod_cache: instance.vod_cache.as_ref().map(Into::into),
             rate_limit: instance.rate_limit.as_ref().map(Into::into),
+            login_protection: instance.login_protection.as_ref().map(Into::into),
             geoip: instance.geoip.as_ref().map(Into::into),
             stream_history: instance.stream_history.as_ref().map(Into::into),
             qos_aggregation: instance.qos_aggregation.as_ref().map(Into::into),
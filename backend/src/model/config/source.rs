@@ -446,6 +446,10 @@ impl SourcesConfig {
         None
     }
 
+    pub fn get_target_by_name(&self, target_name: &str) -> Option<Arc<ConfigTarget>> {
+        self.sources.iter().flat_map(|source| source.targets.iter()).find(|target| target.name == target_name).cloned()
+    }
+
     pub fn get_source_inputs_by_target_by_name(&self, target_name: &str) -> Option<Vec<Arc<str>>> {
         for source in &self.sources {
             for target in &source.targets {
@@ -1,4 +1,4 @@
-use shared::model::{StreamBufferConfigDto, StreamConfigDto};
+use shared::model::{StreamBufferConfigDto, StreamConfigDto, StreamPrewarmConfigDto, StreamStallDetectionConfigDto};
 use shared::utils::parse_to_kbps;
 use crate::api::model::TransportStreamBuffer;
 use crate::model::macros;
@@ -28,6 +28,39 @@ impl From<&StreamBufferConfig> for StreamBufferConfigDto {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct StreamStallDetectionConfig {
+    pub timeout_secs: u64,
+    pub min_bitrate_str: Option<String>,
+    pub min_bitrate_kbps: u64,
+    pub bitrate_window_secs: u64,
+    pub failover: bool,
+}
+
+macros::from_impl!(StreamStallDetectionConfig);
+impl From<&StreamStallDetectionConfigDto> for StreamStallDetectionConfig {
+    fn from(dto: &StreamStallDetectionConfigDto) -> Self {
+        Self {
+            timeout_secs: dto.timeout_secs,
+            min_bitrate_str: dto.min_bitrate.clone(),
+            min_bitrate_kbps: dto.min_bitrate.as_ref().map_or(0u64, |bitrate| parse_to_kbps(bitrate).unwrap_or(0u64)),
+            bitrate_window_secs: dto.bitrate_window_secs,
+            failover: dto.failover,
+        }
+    }
+}
+
+impl From<&StreamStallDetectionConfig> for StreamStallDetectionConfigDto {
+    fn from(instance: &StreamStallDetectionConfig) -> Self {
+        Self {
+            timeout_secs: instance.timeout_secs,
+            min_bitrate: instance.min_bitrate_str.clone(),
+            bitrate_window_secs: instance.bitrate_window_secs,
+            failover: instance.failover,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct StreamConfig {
     pub retry: bool,
@@ -41,6 +74,8 @@ pub struct StreamConfig {
     pub throttle_str: Option<String>,
     pub throttle_kbps: u64,
     pub shared_burst_buffer_mb: u64,
+    pub stall_detection: Option<StreamStallDetectionConfig>,
+    pub prewarm: Option<StreamPrewarmConfigDto>,
 }
 
 macros::from_impl!(StreamConfig);
@@ -58,6 +93,8 @@ impl From<&StreamConfigDto> for StreamConfig {
             throttle_str: dto.throttle.clone(),
             throttle_kbps: dto.throttle.as_ref().map_or(0u64, |throttle| parse_to_kbps(throttle).unwrap_or(0u64)),
             shared_burst_buffer_mb: dto.shared_burst_buffer_mb,
+            stall_detection: dto.stall_detection.as_ref().map(Into::into),
+            prewarm: dto.prewarm.clone(),
         }
     }
 }
@@ -76,6 +113,8 @@ impl From<&StreamConfig> for StreamConfigDto {
             throttle: instance.throttle_str.clone(),
             throttle_kbps: instance.throttle_kbps,
             shared_burst_buffer_mb: instance.shared_burst_buffer_mb,
+            stall_detection: instance.stall_detection.as_ref().map(Into::into),
+            prewarm: instance.prewarm.clone(),
         }
     }
 }
@@ -87,5 +126,6 @@ pub struct CustomStreamResponse {
     pub provider_connections_exhausted: Option<TransportStreamBuffer>, // provider limit reached, has no more connections
     pub low_priority_preempted: Option<TransportStreamBuffer>, // stream was preempted by a higher-priority user
     pub user_account_expired: Option<TransportStreamBuffer>,
+    pub user_quota_exhausted: Option<TransportStreamBuffer>, // user used up the streaming quota
     pub panel_api_provisioning: Option<TransportStreamBuffer>,
 }
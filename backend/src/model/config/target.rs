@@ -7,14 +7,17 @@ use shared::foundation::Filter;
 use shared::foundation::ValueProvider;
 use shared::model::PlaylistItemType;
 use shared::model::{
-    ConfigTargetDto, ConfigTargetOptions, HdHomeRunTargetOutputDto, M3uTargetOutputDto, ProcessingOrder,
+    ConfigTargetDto, ConfigTargetOptions, CsvTargetOutputDto, Enigma2TargetOutputDto, HdHomeRunTargetOutputDto,
+    JsonTargetOutputDto, M3uTargetOutputDto, MulticastChannelDto, MulticastTargetOutputDto, ProcessingOrder,
     StrmExportStyle, StrmTargetOutputDto, TargetOutputDto, TargetType, TraktConfigDto, XtreamTargetOutputDto,
+    CSV_OUTPUT_DEFAULT_COLUMNS, CSV_OUTPUT_DEFAULT_DELIMITER,
 };
 use shared::{apply_flags, create_bitset};
+use std::net::{Ipv4Addr, SocketAddrV4};
 use std::sync::Arc;
 
 create_bitset!(u8, XtreamTargetFlags, SkipLiveDirectSource, SkipVideoDirectSource, SkipSeriesDirectSource);
-create_bitset!(u8, StrmTargetFlags, Flat, UnderscoreWhitespace, Cleanup, AddQualityToFilename);
+create_bitset!(u8, StrmTargetFlags, Flat, UnderscoreWhitespace, Cleanup, AddQualityToFilename, WriteNfo, DownloadArtwork);
 
 #[derive(Clone, Debug)]
 pub struct ProcessTargets {
@@ -121,7 +124,9 @@ impl From<&StrmTargetOutputDto> for StrmTargetOutput {
             (flat, Flat),
             (underscore_whitespace, UnderscoreWhitespace),
             (cleanup, Cleanup),
-            (add_quality_to_filename, AddQualityToFilename)
+            (add_quality_to_filename, AddQualityToFilename),
+            (write_nfo, WriteNfo),
+            (download_artwork, DownloadArtwork)
         );
         Self {
             directory: dto.directory.clone(),
@@ -148,6 +153,8 @@ impl From<&StrmTargetOutput> for StrmTargetOutputDto {
             filter: instance.filter.as_ref().map(ToString::to_string),
             t_filter: instance.filter.clone(),
             add_quality_to_filename: instance.flags.contains(StrmTargetFlags::AddQualityToFilename),
+            write_nfo: instance.flags.contains(StrmTargetFlags::WriteNfo),
+            download_artwork: instance.flags.contains(StrmTargetFlags::DownloadArtwork),
             probe_probe_size_bytes: instance.probe_probe_size_bytes,
             probe_analyze_duration: instance.probe_analyze_duration,
         }
@@ -173,12 +180,186 @@ impl From<&HdHomeRunTargetOutput> for HdHomeRunTargetOutputDto {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct Enigma2TargetOutput {
+    pub username: String,
+    pub directory: Option<String>,
+    pub bouquet_prefix: Option<String>,
+    pub include_vod: bool,
+    pub epgimport: bool,
+    pub filter: Option<Filter>,
+}
+
+macros::from_impl!(Enigma2TargetOutput);
+impl From<&Enigma2TargetOutputDto> for Enigma2TargetOutput {
+    fn from(dto: &Enigma2TargetOutputDto) -> Self {
+        Self {
+            username: dto.username.clone(),
+            directory: dto.directory.clone(),
+            bouquet_prefix: dto.bouquet_prefix.clone(),
+            include_vod: dto.include_vod,
+            epgimport: dto.epgimport,
+            filter: dto.t_filter.clone(),
+        }
+    }
+}
+impl From<&Enigma2TargetOutput> for Enigma2TargetOutputDto {
+    fn from(instance: &Enigma2TargetOutput) -> Self {
+        Self {
+            username: instance.username.clone(),
+            directory: instance.directory.clone(),
+            bouquet_prefix: instance.bouquet_prefix.clone(),
+            include_vod: instance.include_vod,
+            epgimport: instance.epgimport,
+            filter: instance.filter.as_ref().map(ToString::to_string),
+            t_filter: instance.filter.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct JsonTargetOutput {
+    pub username: String,
+    pub filename: Option<String>,
+    pub filter: Option<Filter>,
+}
+
+macros::from_impl!(JsonTargetOutput);
+impl From<&JsonTargetOutputDto> for JsonTargetOutput {
+    fn from(dto: &JsonTargetOutputDto) -> Self {
+        Self {
+            username: dto.username.clone(),
+            filename: dto.filename.clone(),
+            filter: dto.t_filter.clone(),
+        }
+    }
+}
+impl From<&JsonTargetOutput> for JsonTargetOutputDto {
+    fn from(instance: &JsonTargetOutput) -> Self {
+        Self {
+            username: instance.username.clone(),
+            filename: instance.filename.clone(),
+            filter: instance.filter.as_ref().map(ToString::to_string),
+            t_filter: instance.filter.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CsvTargetOutput {
+    pub username: String,
+    pub filename: Option<String>,
+    pub columns: Option<Vec<String>>,
+    pub delimiter: Option<String>,
+    pub filter: Option<Filter>,
+}
+
+impl CsvTargetOutput {
+    pub fn get_columns(&self) -> Vec<&str> {
+        match self.columns.as_ref() {
+            Some(columns) => columns.iter().map(String::as_str).collect(),
+            None => CSV_OUTPUT_DEFAULT_COLUMNS.to_vec(),
+        }
+    }
+
+    pub fn get_delimiter(&self) -> char {
+        self.delimiter.as_ref().and_then(|d| d.chars().next()).unwrap_or(CSV_OUTPUT_DEFAULT_DELIMITER)
+    }
+}
+
+macros::from_impl!(CsvTargetOutput);
+impl From<&CsvTargetOutputDto> for CsvTargetOutput {
+    fn from(dto: &CsvTargetOutputDto) -> Self {
+        Self {
+            username: dto.username.clone(),
+            filename: dto.filename.clone(),
+            columns: dto.columns.clone(),
+            delimiter: dto.delimiter.clone(),
+            filter: dto.t_filter.clone(),
+        }
+    }
+}
+impl From<&CsvTargetOutput> for CsvTargetOutputDto {
+    fn from(instance: &CsvTargetOutput) -> Self {
+        Self {
+            username: instance.username.clone(),
+            filename: instance.filename.clone(),
+            columns: instance.columns.clone(),
+            delimiter: instance.delimiter.clone(),
+            filter: instance.filter.as_ref().map(ToString::to_string),
+            t_filter: instance.filter.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MulticastChannelConfig {
+    pub name: String,
+    pub address: SocketAddrV4,
+    pub rtp: bool,
+    pub always_on: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct MulticastTargetOutput {
+    pub interface: Option<Ipv4Addr>,
+    pub ttl: u8,
+    pub rtp: bool,
+    pub always_on: bool,
+    pub idle_timeout_secs: u16,
+    pub channels: Vec<MulticastChannelConfig>,
+    channel_dtos: Vec<MulticastChannelDto>,
+}
+
+macros::from_impl!(MulticastTargetOutput);
+impl From<&MulticastTargetOutputDto> for MulticastTargetOutput {
+    fn from(dto: &MulticastTargetOutputDto) -> Self {
+        Self {
+            interface: dto.interface.as_ref().and_then(|interface| interface.parse().ok()),
+            ttl: dto.ttl,
+            rtp: dto.rtp,
+            always_on: dto.always_on,
+            idle_timeout_secs: dto.idle_timeout_secs,
+            // the addresses are validated when the dto is prepared
+            channels: dto
+                .channels
+                .iter()
+                .filter_map(|channel| {
+                    Some(MulticastChannelConfig {
+                        name: channel.name.clone(),
+                        address: channel.address.parse().ok()?,
+                        rtp: channel.rtp.unwrap_or(dto.rtp),
+                        always_on: channel.always_on.unwrap_or(dto.always_on),
+                    })
+                })
+                .collect(),
+            channel_dtos: dto.channels.clone(),
+        }
+    }
+}
+impl From<&MulticastTargetOutput> for MulticastTargetOutputDto {
+    fn from(instance: &MulticastTargetOutput) -> Self {
+        Self {
+            interface: instance.interface.as_ref().map(ToString::to_string),
+            ttl: instance.ttl,
+            rtp: instance.rtp,
+            always_on: instance.always_on,
+            idle_timeout_secs: instance.idle_timeout_secs,
+            channels: instance.channel_dtos.clone(),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum TargetOutput {
     Xtream(XtreamTargetOutput),
     M3u(M3uTargetOutput),
     Strm(StrmTargetOutput),
     HdHomeRun(HdHomeRunTargetOutput),
+    Enigma2(Enigma2TargetOutput),
+    Json(JsonTargetOutput),
+    Csv(CsvTargetOutput),
+    Multicast(MulticastTargetOutput),
 }
 
 macros::from_impl!(TargetOutput);
@@ -189,6 +370,10 @@ impl From<&TargetOutputDto> for TargetOutput {
             TargetOutputDto::M3u(o) => TargetOutput::M3u(M3uTargetOutput::from(o)),
             TargetOutputDto::Strm(o) => TargetOutput::Strm(StrmTargetOutput::from(o)),
             TargetOutputDto::HdHomeRun(o) => TargetOutput::HdHomeRun(HdHomeRunTargetOutput::from(o)),
+            TargetOutputDto::Enigma2(o) => TargetOutput::Enigma2(Enigma2TargetOutput::from(o)),
+            TargetOutputDto::Json(o) => TargetOutput::Json(JsonTargetOutput::from(o)),
+            TargetOutputDto::Csv(o) => TargetOutput::Csv(CsvTargetOutput::from(o)),
+            TargetOutputDto::Multicast(o) => TargetOutput::Multicast(MulticastTargetOutput::from(o)),
         }
     }
 }
@@ -200,6 +385,10 @@ impl From<&TargetOutput> for TargetOutputDto {
             TargetOutput::M3u(o) => TargetOutputDto::M3u(M3uTargetOutputDto::from(o)),
             TargetOutput::Strm(o) => TargetOutputDto::Strm(StrmTargetOutputDto::from(o)),
             TargetOutput::HdHomeRun(o) => TargetOutputDto::HdHomeRun(HdHomeRunTargetOutputDto::from(o)),
+            TargetOutput::Enigma2(o) => TargetOutputDto::Enigma2(Enigma2TargetOutputDto::from(o)),
+            TargetOutput::Json(o) => TargetOutputDto::Json(JsonTargetOutputDto::from(o)),
+            TargetOutput::Csv(o) => TargetOutputDto::Csv(CsvTargetOutputDto::from(o)),
+            TargetOutput::Multicast(o) => TargetOutputDto::Multicast(MulticastTargetOutputDto::from(o)),
         }
     }
 }
@@ -253,6 +442,40 @@ impl ConfigTarget {
         }
     }
 
+    pub(crate) fn get_enigma2_output(&self) -> Option<&Enigma2TargetOutput> {
+        if let Some(TargetOutput::Enigma2(output)) = self.output.iter().find(|o| matches!(o, TargetOutput::Enigma2(_))) {
+            Some(output)
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn get_json_output(&self) -> Option<&JsonTargetOutput> {
+        if let Some(TargetOutput::Json(output)) = self.output.iter().find(|o| matches!(o, TargetOutput::Json(_))) {
+            Some(output)
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn get_csv_output(&self) -> Option<&CsvTargetOutput> {
+        if let Some(TargetOutput::Csv(output)) = self.output.iter().find(|o| matches!(o, TargetOutput::Csv(_))) {
+            Some(output)
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn get_multicast_output(&self) -> Option<&MulticastTargetOutput> {
+        if let Some(TargetOutput::Multicast(output)) =
+            self.output.iter().find(|o| matches!(o, TargetOutput::Multicast(_)))
+        {
+            Some(output)
+        } else {
+            None
+        }
+    }
+
     pub fn has_output(&self, tt: TargetType) -> bool {
         for target_output in &self.output {
             match target_output {
@@ -276,6 +499,26 @@ impl ConfigTarget {
                         return true;
                     }
                 }
+                TargetOutput::Enigma2(_) => {
+                    if tt == TargetType::Enigma2 {
+                        return true;
+                    }
+                }
+                TargetOutput::Json(_) => {
+                    if tt == TargetType::Json {
+                        return true;
+                    }
+                }
+                TargetOutput::Csv(_) => {
+                    if tt == TargetType::Csv {
+                        return true;
+                    }
+                }
+                TargetOutput::Multicast(_) => {
+                    if tt == TargetType::Multicast {
+                        return true;
+                    }
+                }
             }
         }
         false
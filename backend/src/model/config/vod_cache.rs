@@ -0,0 +1,45 @@
+use crate::model::macros;
+use shared::model::VodCacheConfigDto;
+use shared::utils::{parse_duration_seconds, parse_size_base_2};
+
+#[derive(Debug, Clone)]
+pub struct VodCacheConfig {
+    pub enabled: bool,
+    pub directory: String,
+    /// Maximum size of all cached files in bytes.
+    pub size: u64,
+    pub size_str: Option<String>,
+    /// Files not requested for this many seconds are evicted, 0 keeps them.
+    pub max_age_secs: u64,
+    pub max_age_str: Option<String>,
+}
+
+macros::from_impl!(VodCacheConfig);
+impl From<&VodCacheConfigDto> for VodCacheConfig {
+    fn from(dto: &VodCacheConfigDto) -> Self {
+        Self {
+            enabled: dto.enabled,
+            // Dto prepare should have set the right path
+            directory: dto.directory.as_ref().map_or_else(Default::default, ToString::to_string),
+            size: dto.size.as_deref().and_then(|size| parse_size_base_2(size).ok()).unwrap_or_default(),
+            size_str: dto.size.clone(),
+            max_age_secs: dto
+                .max_age
+                .as_deref()
+                .and_then(|max_age| parse_duration_seconds(max_age, false))
+                .unwrap_or_default(),
+            max_age_str: dto.max_age.clone(),
+        }
+    }
+}
+
+impl From<&VodCacheConfig> for VodCacheConfigDto {
+    fn from(instance: &VodCacheConfig) -> Self {
+        Self {
+            enabled: instance.enabled,
+            directory: Some(instance.directory.clone()),
+            size: instance.size_str.clone(),
+            max_age: instance.max_age_str.clone(),
+        }
+    }
+}
@@ -18,6 +18,9 @@ pub struct XtreamCategory {
     #[serde(with = "arc_str_serde")]
     pub category_name: Arc<str>,
     //pub parent_id: i32,
+    /// Provider flag for adult categories, propagated to the category streams.
+    #[serde(default, deserialize_with = "deserialize_number_from_string_or_zero")]
+    pub is_adult: u32,
     #[serde(default)]
     pub channels: Vec<PlaylistItem>,
 }
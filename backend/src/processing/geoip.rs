@@ -1,22 +1,21 @@
 use crate::{
     api::model::AppState,
-    model::InputSource,
+    model::{AppConfig, InputSource},
     repository::get_geoip_path,
     utils::{request::download_text_content, GeoIp},
 };
-use log::{error, info};
+use log::{error, info, warn};
 use shared::{
-    model::{default_geoip_url, InputFetchMethod},
+    model::{default_geoip_ipv6_url, default_geoip_url, InputFetchMethod},
     utils::Internable,
 };
-use std::{collections::HashMap, io::Cursor, sync::Arc};
+use std::{collections::HashMap, io, io::Cursor, path::Path, sync::Arc};
 
 #[derive(Debug)]
 pub(crate) enum GeoIpUpdateError {
     Disabled,
     DownloadFailed(String),
     ProcessFailed(String),
-    UnknownProcessing,
 }
 
 impl std::fmt::Display for GeoIpUpdateError {
@@ -25,13 +24,40 @@ impl std::fmt::Display for GeoIpUpdateError {
             Self::Disabled => write!(f, "GeoIp update is disabled"),
             Self::DownloadFailed(err) => write!(f, "Failed to download geoip db: {err}"),
             Self::ProcessFailed(err) => write!(f, "Failed to process geoip db: {err}"),
-            Self::UnknownProcessing => write!(f, "Unknown GeoIp processing error"),
         }
     }
 }
 
 impl std::error::Error for GeoIpUpdateError {}
 
+async fn download_geoip_csv(app_state: &Arc<AppState>, url: String) -> Result<String, GeoIpUpdateError> {
+    let input_source = InputSource {
+        name: "GeoIP".intern(),
+        url,
+        provider: None,
+        username: None,
+        password: None,
+        method: InputFetchMethod::GET,
+        headers: HashMap::default(),
+    };
+    match download_text_content(&app_state.app_config, &app_state.http_client.load(), &input_source, None, None, false)
+        .await
+    {
+        Ok((content, _)) => Ok(content),
+        Err(err) => Err(GeoIpUpdateError::DownloadFailed(err.to_string())),
+    }
+}
+
+fn url_or_default(url: &str, default_url: fn() -> String) -> String {
+    if url.trim().is_empty() { default_url() } else { url.to_string() }
+}
+
+/// Loads the geoip db, a db in the legacy format is migrated on load which needs the write lock of the file.
+pub(crate) async fn load_geoip_db(app_config: &AppConfig, geoip_db_path: &Path) -> io::Result<GeoIp> {
+    let _file_lock = app_config.file_locks.write_lock(geoip_db_path).await;
+    GeoIp::load(geoip_db_path)
+}
+
 pub(crate) async fn update_geoip_db(app_state: &Arc<AppState>) -> Result<(), GeoIpUpdateError> {
     let config = app_state.app_config.config.load();
     if let Some(geoip) = config.reverse_proxy.as_ref().and_then(|r| r.geoip.as_ref()) {
@@ -39,52 +65,33 @@ pub(crate) async fn update_geoip_db(app_state: &Arc<AppState>) -> Result<(), Geo
             let geoip_db_path = &*get_geoip_path(&config.storage_dir);
             let _file_lock = app_state.app_config.file_locks.write_lock(geoip_db_path).await;
 
-            let url = if geoip.url.trim().is_empty() { default_geoip_url() } else { geoip.url.clone() };
-            let input_source = InputSource {
-                name: "GeoIP".intern(),
-                url,
-                provider: None,
-                username: None,
-                password: None,
-                method: InputFetchMethod::GET,
-                headers: HashMap::default(),
-            };
-            return match download_text_content(
-                &app_state.app_config,
-                &app_state.http_client.load(),
-                &input_source,
-                None,
-                None,
-                false,
-            )
-            .await
-            {
-                Ok((content, _)) => {
-                    let reader = Cursor::new(content);
-                    let mut geoip = GeoIp::new();
-                    let result = {
-                        match geoip.import_ipv4_from_csv(reader, geoip_db_path) {
-                            Ok(size) => (Some(size), None),
-                            Err(err) => (None, Some(err)),
-                        }
-                    };
+            let ipv4_content = download_geoip_csv(app_state, url_or_default(&geoip.url, default_geoip_url))
+                .await
+                .inspect_err(|err| error!("{err}"))?;
+            // Without IPv6 ranges IPv6 clients are unresolved, which is still better than no update at all.
+            let ipv6_content =
+                match download_geoip_csv(app_state, url_or_default(&geoip.ipv6_url, default_geoip_ipv6_url)).await {
+                    Ok(content) => Some(content),
+                    Err(err) => {
+                        warn!("{err}, updating the GeoIp db with IPv4 ranges only");
+                        None
+                    }
+                };
+
+            let mut geoip = GeoIp::new();
+            let result = geoip
+                .import_from_csv(Cursor::new(ipv4_content))
+                .and_then(|_| ipv6_content.map_or(Ok(0), |content| geoip.import_from_csv(Cursor::new(content))))
+                .and_then(|_| geoip.store(geoip_db_path));
 
-                    return match result {
-                        (Some(_), None) => {
-                            info!("GeoIp db updated");
-                            app_state.geoip.store(Some(Arc::new(geoip)));
-                            Ok(())
-                        }
-                        (None, Some(err)) => {
-                            let error = GeoIpUpdateError::ProcessFailed(err.to_string());
-                            error!("{error}");
-                            Err(error)
-                        }
-                        _ => Err(GeoIpUpdateError::UnknownProcessing),
-                    };
+            return match result {
+                Ok(_) => {
+                    info!("GeoIp db updated");
+                    app_state.geoip.store(Some(Arc::new(geoip)));
+                    Ok(())
                 }
                 Err(err) => {
-                    let error = GeoIpUpdateError::DownloadFailed(err.to_string());
+                    let error = GeoIpUpdateError::ProcessFailed(err.to_string());
                     error!("{error}");
                     Err(error)
                 }
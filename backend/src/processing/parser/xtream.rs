@@ -11,7 +11,7 @@ use shared::model::{EpisodeStreamProperties, LiveStreamProperties, PlaylistGroup
                     SeriesStreamProperties, StreamProperties, VideoStreamProperties,
                     XtreamCluster, XtreamPlaylistItem};
 use shared::utils::{generate_provider_playlist_uuid, trim_last_slash, Internable};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use tokio::task::spawn_blocking;
 
@@ -186,6 +186,7 @@ pub async fn parse_xtream(input: &ConfigInput,
                     let mut unknown_grp = XtreamCategory {
                         category_id: 0u32,
                         category_name: "Unknown".intern(),
+                        is_adult: 0,
                         channels: vec![],
                     };
 
@@ -194,8 +195,11 @@ pub async fn parse_xtream(input: &ConfigInput,
                         input.has_flag(ConfigInputFlags::XtreamLiveStreamWithoutExtension),
                     );
 
-                    for stream in xtream_streams {
+                    for mut stream in xtream_streams {
                         let group = group_map.get_mut(&stream.get_category_id()).unwrap_or(&mut unknown_grp);
+                        if group.is_adult != 0 {
+                            stream.set_adult();
+                        }
                         let category_name = &group.category_name;
                         let stream_url = create_xtream_url(xtream_cluster, url, username, password, &stream, live_stream_use_prefix, live_stream_without_extension);
                         let item_type = PlaylistItemType::from(xtream_cluster);
@@ -281,6 +285,8 @@ where
     // Map categories for lookup
     let group_map: IndexMap<u32, Arc<str>> = xtream_categories.iter().map(|c| (c.category_id, c.category_name.clone())).collect();
     let unknown_group_name = "Unknown".intern();
+    let adult_categories: HashSet<u32> =
+        xtream_categories.iter().filter(|c| c.is_adult != 0).map(|c| c.category_id).collect();
 
     // Category position lookup for source_ordinal: streams are ordered by
     // category-list position (primary) then arrival order within that
@@ -314,7 +320,7 @@ where
                     let ordinal = cat_source_ordinal(stream.category_id);
                     let stream_prop = StreamProperties::Live(Box::new(stream));
                     process_stream_item(&input_name, &url, &username, &password,
-                                        xtream_cluster, &group_map, &unknown_group_name,
+                                        xtream_cluster, &group_map, &unknown_group_name, &adult_categories,
                                         stream_prop, &mut on_item, live_stream_use_prefix, live_stream_without_extension, ordinal)
                 };
                 let visitor = XtreamItemVisitor { on_item: &mut on_stream, _marker: std::marker::PhantomData };
@@ -325,7 +331,7 @@ where
                     let ordinal = cat_source_ordinal(stream.category_id);
                     let stream_prop = StreamProperties::Video(Box::new(stream));
                     process_stream_item(&input_name, &url, &username, &password,
-                                        xtream_cluster, &group_map, &unknown_group_name,
+                                        xtream_cluster, &group_map, &unknown_group_name, &adult_categories,
                                         stream_prop, &mut on_item, live_stream_use_prefix, live_stream_without_extension, ordinal)
                 };
                 let visitor = XtreamItemVisitor { on_item: &mut on_stream, _marker: std::marker::PhantomData };
@@ -336,7 +342,7 @@ where
                     let ordinal = cat_source_ordinal(stream.category_id);
                     let stream_prop = StreamProperties::Series(Box::new(stream));
                     process_stream_item(&input_name, &url, &username, &password,
-                                        xtream_cluster, &group_map, &unknown_group_name,
+                                        xtream_cluster, &group_map, &unknown_group_name, &adult_categories,
                                         stream_prop, &mut on_item, live_stream_use_prefix, live_stream_without_extension, ordinal)
                 };
                 let visitor = XtreamItemVisitor { on_item: &mut on_stream, _marker: std::marker::PhantomData };
@@ -356,6 +362,7 @@ fn process_stream_item<F>(
     cluster: XtreamCluster,
     group_map: &IndexMap<u32, Arc<str>>,
     unknown_group_name: &Arc<str>,
+    adult_categories: &HashSet<u32>,
     mut stream: StreamProperties,
     callback: &mut F,
     live_stream_use_prefix: bool,
@@ -367,6 +374,9 @@ where
 {
     stream.prepare();
     let category_id = stream.get_category_id();
+    if adult_categories.contains(&category_id) {
+        stream.set_adult();
+    }
     let category_name = group_map.get(&category_id).unwrap_or(unknown_group_name);
     let stream_url = create_xtream_url(cluster, url, username, password, &stream, live_stream_use_prefix, live_stream_without_extension);
 
@@ -723,4 +733,58 @@ mod tests {
         assert_eq!(groups[1].channels[0].header.name.as_ref(), "unknown-1");
         assert_eq!(groups[1].channels[0].header.source_ordinal, 2);
     }
+
+    #[tokio::test]
+    async fn test_parse_xtream_propagates_adult_category_flag() {
+        let categories = r#"
+            [
+                {"category_id":"10","category_name":"Adult","is_adult":"1"},
+                {"category_id":"20","category_name":"News"}
+            ]
+        "#;
+        let streams = r#"
+            [
+                {"name":"adult-1","stream_id":101,"category_id":"10","added":"0"},
+                {"name":"news-1","stream_id":201,"category_id":"20","added":"0"}
+            ]
+        "#;
+
+        let groups = parse_xtream(
+            &test_input(),
+            XtreamCluster::Live,
+            make_reader(categories),
+            make_reader(streams),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        let is_adult = |group: usize| {
+            groups[group].channels[0].header.additional_properties.as_ref().is_some_and(shared::model::StreamProperties::is_adult)
+        };
+        assert!(is_adult(0));
+        assert!(!is_adult(1));
+
+        let items: Arc<Mutex<Vec<XtreamPlaylistItem>>> = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&items);
+        parse_xtream_streaming(
+            &test_input(),
+            XtreamCluster::Live,
+            make_reader(categories),
+            make_reader(streams),
+            move |item| {
+                sink.lock().unwrap().push(item);
+                Ok(())
+            },
+        )
+        .await
+        .unwrap();
+
+        let items = items.lock().unwrap();
+        let adult: Vec<&str> = items
+            .iter()
+            .filter(|item| item.additional_properties.as_ref().is_some_and(shared::model::StreamProperties::is_adult))
+            .map(|item| item.name.as_ref())
+            .collect();
+        assert_eq!(adult, vec!["adult-1"]);
+    }
 }
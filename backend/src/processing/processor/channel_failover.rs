@@ -0,0 +1,190 @@
+use crate::model::{AppConfig, ConfigTarget, EpgSmartMatchConfig};
+use crate::processing::parser::xmltv::normalize_channel_name;
+use crate::repository::{persist_channel_alternates, ChannelAlternate};
+use shared::error::TuliproxError;
+use shared::model::{
+    ChannelFailoverDto, ChannelLinkBy, EpgSmartMatchConfigDto, PlaylistGroup, PlaylistItemHeader, PlaylistItemType,
+    UUIDType,
+};
+use shared::utils::{is_dash_url, is_hls_url};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+/// A target channel with the same channel of other inputs.
+pub(in crate::processing::processor) struct ChannelAlternateLink {
+    uuid: UUIDType,
+    input_name: Arc<str>,
+    alternates: Vec<ChannelAlternate>,
+}
+
+fn is_linkable(header: &PlaylistItemHeader) -> bool {
+    header.item_type == PlaylistItemType::Live && !is_hls_url(&header.url) && !is_dash_url(&header.url)
+}
+
+fn link_keys(
+    header: &PlaylistItemHeader,
+    link_by: &[ChannelLinkBy],
+    normalize_config: &EpgSmartMatchConfig,
+) -> Vec<String> {
+    let mut keys = Vec::with_capacity(link_by.len());
+    for field in link_by {
+        match field {
+            ChannelLinkBy::EpgId => {
+                if let Some(epg_id) = header.epg_channel_id.as_ref().map(|id| id.trim()).filter(|id| !id.is_empty()) {
+                    keys.push(format!("epg:{}", epg_id.to_lowercase()));
+                }
+            }
+            ChannelLinkBy::Name => {
+                let caption = if header.title.is_empty() { &header.name } else { &header.title };
+                let name = normalize_channel_name(caption, normalize_config);
+                if !name.is_empty() {
+                    keys.push(format!("name:{name}"));
+                }
+            }
+        }
+    }
+    keys
+}
+
+/// Links the live channels of different inputs when the target has `channel_failover` options.
+pub(in crate::processing::processor) fn link_channel_alternates(
+    target: &ConfigTarget,
+    playlist: &mut Vec<PlaylistGroup>,
+) -> Vec<ChannelAlternateLink> {
+    match target.options.as_ref().and_then(|options| options.channel_failover.as_ref()) {
+        Some(failover) => link_failover_alternates(failover, playlist),
+        None => vec![],
+    }
+}
+
+/// Links the live channels of different inputs sharing an epg id or name.
+///
+/// The first channel in playlist order becomes the primary, the channels of other inputs
+/// are stored as its alternates, one per input. With `hide_alternates` the alternates are
+/// removed from the playlist.
+fn link_failover_alternates(
+    failover: &ChannelFailoverDto,
+    playlist: &mut Vec<PlaylistGroup>,
+) -> Vec<ChannelAlternateLink> {
+    let normalize_config: EpgSmartMatchConfig = EpgSmartMatchConfigDto::default().into();
+    let mut links: Vec<ChannelAlternateLink> = Vec::new();
+    let mut key_index: HashMap<String, usize> = HashMap::new();
+    let mut hidden: HashSet<UUIDType> = HashSet::new();
+
+    for channel in playlist.iter().flat_map(|group| group.channels.iter()) {
+        let header = &channel.header;
+        if !is_linkable(header) {
+            continue;
+        }
+        let keys = link_keys(header, &failover.link_by, &normalize_config);
+        let link_idx = if let Some(idx) = keys.iter().find_map(|key| key_index.get(key).copied()) {
+            let link = &mut links[idx];
+            if link.input_name != header.input_name
+                && !link.alternates.iter().any(|alternate| *alternate.input_name == *header.input_name)
+            {
+                link.alternates.push(ChannelAlternate {
+                    input_name: header.input_name.to_string(),
+                    url: header.url.to_string(),
+                });
+                if failover.hide_alternates {
+                    hidden.insert(*header.get_uuid());
+                }
+            }
+            idx
+        } else {
+            links.push(ChannelAlternateLink {
+                uuid: *header.get_uuid(),
+                input_name: Arc::clone(&header.input_name),
+                alternates: vec![],
+            });
+            links.len() - 1
+        };
+        for key in keys {
+            key_index.entry(key).or_insert(link_idx);
+        }
+    }
+
+    if !hidden.is_empty() {
+        for group in playlist.iter_mut() {
+            group.channels.retain(|channel| !hidden.contains(channel.header.get_uuid()));
+        }
+        playlist.retain(|group| !group.channels.is_empty());
+    }
+
+    links.retain(|link| !link.alternates.is_empty());
+    links
+}
+
+/// Stores the alternates by the virtual ids assigned while persisting the playlist.
+pub(in crate::processing::processor) async fn persist_channel_alternate_links(
+    app_config: &AppConfig,
+    target: &ConfigTarget,
+    playlist: &[PlaylistGroup],
+    links: Vec<ChannelAlternateLink>,
+) -> Result<(), TuliproxError> {
+    let virtual_ids: HashMap<&UUIDType, u32> = if links.is_empty() {
+        HashMap::new()
+    } else {
+        playlist
+            .iter()
+            .flat_map(|group| group.channels.iter())
+            .map(|channel| (channel.header.get_uuid(), channel.header.virtual_id))
+            .collect()
+    };
+    let alternates = links
+        .into_iter()
+        .filter_map(|link| virtual_ids.get(&link.uuid).map(|virtual_id| (*virtual_id, link.alternates)))
+        .collect();
+    persist_channel_alternates(app_config, &target.name, alternates).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::link_failover_alternates;
+    use shared::model::{
+        ChannelFailoverDto, PlaylistGroup, PlaylistItem, PlaylistItemHeader, PlaylistItemType, XtreamCluster,
+    };
+    use shared::utils::Internable;
+
+    fn channel(input: &str, name: &str, epg_id: Option<&str>) -> PlaylistItem {
+        let mut header = PlaylistItemHeader {
+            name: name.intern(),
+            title: name.intern(),
+            url: format!("http://{input}.example/live/{name}.ts").intern(),
+            input_name: input.intern(),
+            epg_channel_id: epg_id.map(Internable::intern),
+            item_type: PlaylistItemType::Live,
+            ..Default::default()
+        };
+        header.gen_uuid();
+        PlaylistItem { header }
+    }
+
+    fn group(channels: Vec<PlaylistItem>) -> PlaylistGroup {
+        PlaylistGroup { id: 1, title: "News".intern(), channels, xtream_cluster: XtreamCluster::Live }
+    }
+
+    #[test]
+    fn test_link_channel_alternates_across_inputs() {
+        let mut playlist = vec![group(vec![
+            channel("provider_a", "Das Erste HD", Some("daserste.de")),
+            channel("provider_a", "Arte", None),
+            channel("provider_b", "Das Erste", Some("DasErste.de")),
+            channel("provider_b", "Arte HD", None),
+            channel("provider_c", "Arte", None),
+            channel("provider_a", "Arte", None),
+        ])];
+        let links = link_failover_alternates(&ChannelFailoverDto::default(), &mut playlist);
+        assert_eq!(links.len(), 2);
+        assert_eq!(links[0].alternates.len(), 1);
+        assert_eq!(links[0].alternates[0].input_name, "provider_b");
+        let arte: Vec<&str> = links[1].alternates.iter().map(|alternate| alternate.input_name.as_str()).collect();
+        assert_eq!(arte, vec!["provider_b", "provider_c"]);
+        assert_eq!(playlist[0].channels.len(), 6);
+
+        let failover = ChannelFailoverDto { hide_alternates: true, ..ChannelFailoverDto::default() };
+        let links = link_failover_alternates(&failover, &mut playlist);
+        assert_eq!(links.len(), 2);
+        assert_eq!(playlist[0].channels.len(), 3);
+    }
+}
@@ -0,0 +1,86 @@
+use crate::model::{ingest_stream_url, AppConfig, ConfigInput, IngestConfig};
+use indexmap::IndexMap;
+use shared::error::TuliproxError;
+use shared::model::{PlaylistGroup, PlaylistItem, PlaylistItemHeader, PlaylistItemType, XtreamCluster};
+use shared::utils::Internable;
+use std::sync::Arc;
+
+pub fn download_ingest_playlist(
+    app_config: &Arc<AppConfig>,
+    input: &ConfigInput,
+) -> (Vec<PlaylistGroup>, Vec<TuliproxError>) {
+    let config = app_config.config.load();
+    let Some(ingest) = config.ingest.as_ref().filter(|ingest| ingest.enabled) else { return (vec![], vec![]) };
+    (ingest_playlist_groups(ingest, &input.name), vec![])
+}
+
+/// Creates the live channels of the configured ingest channels, grouped in configuration order.
+fn ingest_playlist_groups(ingest: &IngestConfig, input_name: &Arc<str>) -> Vec<PlaylistGroup> {
+    let mut groups: IndexMap<&str, Vec<PlaylistItem>> = IndexMap::new();
+    for channel in &ingest.channels {
+        let group = channel.group.as_deref().unwrap_or(&ingest.group);
+        let mut header = PlaylistItemHeader {
+            id: channel.key.as_str().intern(),
+            name: channel.name.as_str().intern(),
+            title: channel.name.as_str().intern(),
+            group: group.intern(),
+            logo: channel.logo.as_deref().unwrap_or_default().intern(),
+            url: ingest_stream_url(&channel.key).into(),
+            epg_channel_id: channel.epg_channel_id.as_deref().map(Internable::intern),
+            input_name: Arc::clone(input_name),
+            xtream_cluster: XtreamCluster::Live,
+            item_type: PlaylistItemType::Live,
+            ..PlaylistItemHeader::default()
+        };
+        header.gen_uuid();
+        groups.entry(group).or_default().push(PlaylistItem { header });
+    }
+
+    groups
+        .into_iter()
+        .map(|(title, channels)| PlaylistGroup {
+            id: 0,
+            title: title.intern(),
+            channels,
+            xtream_cluster: XtreamCluster::Live,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ingest_playlist_groups;
+    use crate::model::{IngestChannelConfig, IngestConfig};
+    use shared::model::PlaylistItemType;
+    use shared::utils::Internable;
+
+    fn channel(key: &str, group: Option<&str>) -> IngestChannelConfig {
+        IngestChannelConfig {
+            key: key.to_string(),
+            name: key.to_uppercase(),
+            group: group.map(ToString::to_string),
+            logo: None,
+            epg_channel_id: None,
+            token: None,
+        }
+    }
+
+    #[test]
+    fn ingest_playlist_groups_keeps_configuration_order() {
+        let ingest = IngestConfig {
+            enabled: true,
+            token: Some("secret".to_string()),
+            group: "Local".to_string(),
+            channels: vec![channel("cam", None), channel("lobby", Some("House")), channel("capture", None)],
+        };
+
+        let groups = ingest_playlist_groups(&ingest, &"local".intern());
+        let titles: Vec<&str> = groups.iter().map(|group| group.title.as_ref()).collect();
+        assert_eq!(titles, vec!["Local", "House"]);
+        assert_eq!(groups[0].channels.len(), 2);
+        let cam = &groups[0].channels[0].header;
+        assert_eq!(cam.url.as_ref(), "ingest://cam");
+        assert_eq!(cam.item_type, PlaylistItemType::Live);
+        assert_eq!(cam.input_name.as_ref(), "local");
+    }
+}
@@ -1,10 +1,10 @@
-use crate::library::{EpisodeMetadata, MediaMetadata, MetadataAsyncIter, MetadataCacheEntry, TechnicalMetadata};
+use crate::library::{EpisodeMetadata, LibrarySidecar, MediaMetadata, MetadataAsyncIter, MetadataCacheEntry, TechnicalMetadata};
 use crate::library::resolve_metadata_storage_path;
 use crate::model::{AppConfig, ConfigInput};
 use shared::concat_string;
 use shared::error::TuliproxError;
 use shared::model::UUIDType;
-use shared::model::{EpisodeStreamProperties, PlaylistGroup, PlaylistItem, PlaylistItemHeader, PlaylistItemType, SeriesStreamDetailEpisodeProperties, SeriesStreamDetailProperties, SeriesStreamDetailSeasonProperties, SeriesStreamProperties, StreamProperties, VideoStreamDetailProperties, VideoStreamProperties, XtreamCluster, normalize_episode_title};
+use shared::model::{EpisodeStreamProperties, MediaSidecarProperties, PlaylistGroup, PlaylistItem, PlaylistItemHeader, PlaylistItemType, SeriesStreamDetailEpisodeProperties, SeriesStreamDetailProperties, SeriesStreamDetailSeasonProperties, SeriesStreamProperties, StreamProperties, VideoStreamDetailProperties, VideoStreamProperties, XtreamCluster, normalize_episode_title};
 use shared::utils::{concat_path_leading_slash, generate_local_playlist_uuid, Internable};
 use std::collections::HashMap;
 use std::path::Path;
@@ -23,6 +23,23 @@ fn episode_thumbnail_url(episode: &EpisodeMetadata, api_base_path: &str) -> Opti
         .map(|id| concat_path_leading_slash(api_base_path, &format!("library/thumbnail/{id}")))
 }
 
+fn sidecar_properties(entry_uuid: &str, sidecars: &[LibrarySidecar]) -> Option<Vec<MediaSidecarProperties>> {
+    if sidecars.is_empty() {
+        return None;
+    }
+    let entry: Arc<str> = entry_uuid.into();
+    Some(sidecars.iter().map(|sidecar| MediaSidecarProperties {
+        kind: sidecar.kind,
+        entry: Arc::clone(&entry),
+        id: sidecar.id.as_str().into(),
+        extension: sidecar.extension.as_str().intern(),
+        language: sidecar.language.as_deref().map(Internable::intern),
+        forced: sidecar.forced,
+        hearing_impaired: sidecar.hearing_impaired,
+        suffix: sidecar.suffix.as_str().into(),
+    }).collect())
+}
+
 fn duration_secs_to_xtream_duration(duration_secs: u32) -> Arc<str> {
     let hours = duration_secs / 3600;
     let minutes = (duration_secs % 3600) / 60;
@@ -330,6 +347,7 @@ pub fn metadata_cache_entry_to_xtream_movie_info(
             bitrate: technical_bitrate(technical),
             runtime: duration_secs.map(|value| value.to_string().into()),
             status: Some("Released".intern()),
+            sidecars: sidecar_properties(&entry.uuid, &entry.sidecars),
         }),
     };
 
@@ -424,6 +442,7 @@ pub fn metadata_cache_entry_to_xtream_series_info(
                 video: technical.and_then(|metadata| metadata.video.as_ref()).map(|value| value.as_str().into()),
                 bitrate: technical_bitrate(technical),
                 rating: None,
+                sidecars: sidecar_properties(&entry.uuid, &episode.sidecars),
             }
         }).collect::<Vec<_>>()
     });
@@ -5,8 +5,10 @@ mod xtream_vod;
 mod xtream_series;
 mod epg;
 mod sort;
+mod channel_failover;
 mod trakt;
 mod library;
+mod ingest;
 mod stream_probe;
 mod filtered_playlist_source;
 mod probe_handle_guard;
@@ -17,6 +19,7 @@ pub use self::xtream::*;
 pub use self::xtream_vod::*;
 pub use self::xtream_series::*;
 pub use self::stream_probe::*;
+pub use self::ingest::*;
 pub(crate) use self::probe_handle_guard::*;
 pub use self::resolve_options::*;
 use crate::api::model::ProviderHandle;
@@ -17,8 +17,14 @@ use crate::{
         parser::xmltv::flatten_tvguide,
         playlist_watch::process_group_watch,
         processor::{
-            epg::process_playlist_epg, library, sort::sort_playlist, trakt::process_trakt_categories_for_target,
-            xtream_series::playlist_resolve_series, xtream_vod::playlist_resolve_vod,
+            channel_failover::{link_channel_alternates, persist_channel_alternate_links},
+            epg::process_playlist_epg,
+            ingest::download_ingest_playlist,
+            library,
+            sort::sort_playlist,
+            trakt::process_trakt_categories_for_target,
+            xtream_series::playlist_resolve_series,
+            xtream_vod::playlist_resolve_vod,
         },
     },
     repository::{
@@ -491,6 +497,10 @@ async fn playlist_download_from_input(
                 let (p, e) = library::download_library_playlist(client, app_config, input).await;
                 (p, e, false, 0, 0)
             }
+            InputType::Ingest => {
+                let (p, e) = download_ingest_playlist(app_config, input);
+                (p, e, false, 0, 0)
+            }
         }
     };
 
@@ -1114,18 +1124,31 @@ async fn process_playlist_for_target(
         step.tick("assigning channel counter");
         log_memory_snapshot(format!("target '{}' after_assign_channel_counter", target.name).as_str());
 
+        let channel_alternate_links = link_channel_alternates(target, &mut flat_new_playlist);
+        if !channel_alternate_links.is_empty() {
+            step.tick("linking channel alternates");
+        }
+
         if process_watch(&ctx.config, &ctx.client, target, &flat_new_playlist).await {
             step.tick("group watches");
             log_memory_snapshot(format!("target '{}' after_group_watches", target.name).as_str());
         }
         let result = persist_playlist(
             &ctx.config,
+            &ctx.client,
             &mut flat_new_playlist,
             flatten_tvguide(new_epg).as_ref(),
             target,
             ctx.playlist_state.as_ref(),
         )
         .await;
+        if result.is_ok() {
+            if let Err(err) =
+                persist_channel_alternate_links(&ctx.config, target, &flat_new_playlist, channel_alternate_links).await
+            {
+                errors.push(err);
+            }
+        }
         step.stop("Persisting playlists");
         log_memory_snapshot(format!("target '{}' after_persist", target.name).as_str());
         result
@@ -63,6 +63,8 @@ pub async fn update_generic_stream_metadata(
             get_input_local_library_playlist_file_path(&storage_path, &input.name),
             ProbeStorageKind::Library,
         ),
+        // Ingest channels are pushed by local encoders, there is no stream to probe without a viewer.
+        InputType::Ingest => return Ok(GenericProbeOutcome::Noop),
         InputType::Xtream | InputType::XtreamBatch => {
             let cluster = if item_type.is_live() {
                 XtreamCluster::Live
@@ -180,6 +180,7 @@ pub fn csv_read_inputs_from_reader(
         InputType::M3uBatch | InputType::M3u => InputType::M3uBatch,
         InputType::XtreamBatch | InputType::Xtream => InputType::XtreamBatch,
         InputType::Library => InputType::Library,
+        InputType::Ingest => InputType::Ingest,
     };
     let mut result = vec![];
     let mut default_columns = vec![];
@@ -2,7 +2,7 @@ use super::bplustree::{BPlusTree, MAGIC, STORAGE_VERSION};
 use super::storage_const;
 use fs2::FileExt as _;
 use log::{info, trace, warn};
-use shared::model::{ConfigPaths, ProxyType, ProxyUserStatus};
+use shared::model::{AccessScheduleDay, ConfigPaths, ProxyType, ProxyUserStatus, UserQuotaPeriod};
 use std::{
     collections::{HashSet, VecDeque},
     ffi::OsStr,
@@ -391,15 +391,23 @@ pub fn migrate_bplustree_databases_with_marker(
 fn marker_file_name() -> String { format!("{MARKER_FILE_GUARD_PREFIX}{STORAGE_VERSION}") }
 
 //
-// The user database has gone through five serialization schemas (MessagePack,
+// The user database has gone through twelve serialization schemas (MessagePack,
 // positional/sequence encoding via rmp_serde):
 //
 //   V1 (Deprecated) – original format, 13 fields, no epg_request_timeshift
 //   V2              – 14 fields, added epg_request_timeshift
 //   V3              – 15 fields, added priority
-//   V4 (current)    – 17 fields, added soft_connections and soft_priority
+//   V4              – 17 fields, added soft_connections and soft_priority
+//   V5              – 20 fields, added quota_period, quota_max_hours and quota_max_traffic_mb
+//   V6              – 21 fields, added access_schedule
+//   V7              – 22 fields, added parental_profile
+//   V8              – 23 fields, added device_limit
+//   V9              – 24 fields, added country_access
+//   V10             – 25 fields, added limit_profile
+//   V11             – 26 fields, added reseller
+//   V12 (current)   – 27 fields, added hls_packaging
 //
-// On first startup after an upgrade the file is still in V1 or V2 format.
+// On first startup after an upgrade the file is still in an older format.
 // `migrate_user_db_schema` detects this, converts every record in-place, and
 // writes a merge-guard marker so that config-driven user merges cannot
 // overwrite the freshly migrated data.
@@ -502,8 +510,6 @@ impl StoredApiUserV3 {
     }
 }
 
-// V4 mirror — same layout as user_repository::StoredProxyUserCredentials.
-// Defined here so the migration has no dependency on user_repository internals.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 struct StoredApiUserV4 {
     pub target: String,
@@ -553,6 +559,604 @@ impl StoredApiUserV4 {
     fn from_v1(v1: &StoredApiUserV1) -> Self { Self::from_v3(&StoredApiUserV3::from_v1(v1)) }
 }
 
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct StoredApiUserV5 {
+    pub target: String,
+    pub username: String,
+    pub password: String,
+    pub token: Option<String>,
+    pub proxy: ProxyType,
+    pub server: Option<String>,
+    pub epg_timeshift: Option<String>,
+    pub epg_request_timeshift: Option<String>,
+    pub created_at: Option<i64>,
+    pub exp_date: Option<i64>,
+    pub max_connections: Option<u32>,
+    pub status: Option<ProxyUserStatus>,
+    pub ui_enabled: bool,
+    pub comment: Option<String>,
+    pub priority: Option<i8>,
+    pub soft_connections: Option<u16>,
+    pub soft_priority: Option<i8>,
+    pub quota_period: Option<UserQuotaPeriod>,
+    pub quota_max_hours: Option<u32>,
+    pub quota_max_traffic_mb: Option<u64>,
+}
+
+impl StoredApiUserV5 {
+    fn from_v4(v4: &StoredApiUserV4) -> Self {
+        Self {
+            target: v4.target.clone(),
+            username: v4.username.clone(),
+            password: v4.password.clone(),
+            token: v4.token.clone(),
+            proxy: v4.proxy,
+            server: v4.server.clone(),
+            epg_timeshift: v4.epg_timeshift.clone(),
+            epg_request_timeshift: v4.epg_request_timeshift.clone(),
+            created_at: v4.created_at,
+            exp_date: v4.exp_date,
+            max_connections: v4.max_connections,
+            status: v4.status,
+            ui_enabled: v4.ui_enabled,
+            comment: v4.comment.clone(),
+            priority: v4.priority,
+            soft_connections: v4.soft_connections,
+            soft_priority: v4.soft_priority,
+            quota_period: None,
+            quota_max_hours: None,
+            quota_max_traffic_mb: None,
+        }
+    }
+
+    fn from_v3(v3: &StoredApiUserV3) -> Self { Self::from_v4(&StoredApiUserV4::from_v3(v3)) }
+
+    fn from_v2(v2: &StoredApiUserV2) -> Self { Self::from_v4(&StoredApiUserV4::from_v2(v2)) }
+
+    fn from_v1(v1: &StoredApiUserV1) -> Self { Self::from_v4(&StoredApiUserV4::from_v1(v1)) }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct StoredAccessWindowV6 {
+    pub days: Vec<AccessScheduleDay>,
+    pub from: String,
+    pub to: String,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct StoredAccessScheduleV6 {
+    pub timezone: Option<String>,
+    pub windows: Vec<StoredAccessWindowV6>,
+    pub kick_on_close: bool,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct StoredApiUserV6 {
+    pub target: String,
+    pub username: String,
+    pub password: String,
+    pub token: Option<String>,
+    pub proxy: ProxyType,
+    pub server: Option<String>,
+    pub epg_timeshift: Option<String>,
+    pub epg_request_timeshift: Option<String>,
+    pub created_at: Option<i64>,
+    pub exp_date: Option<i64>,
+    pub max_connections: Option<u32>,
+    pub status: Option<ProxyUserStatus>,
+    pub ui_enabled: bool,
+    pub comment: Option<String>,
+    pub priority: Option<i8>,
+    pub soft_connections: Option<u16>,
+    pub soft_priority: Option<i8>,
+    pub quota_period: Option<UserQuotaPeriod>,
+    pub quota_max_hours: Option<u32>,
+    pub quota_max_traffic_mb: Option<u64>,
+    pub access_schedule: Option<StoredAccessScheduleV6>,
+}
+
+impl StoredApiUserV6 {
+    fn from_v5(v5: &StoredApiUserV5) -> Self {
+        Self {
+            target: v5.target.clone(),
+            username: v5.username.clone(),
+            password: v5.password.clone(),
+            token: v5.token.clone(),
+            proxy: v5.proxy,
+            server: v5.server.clone(),
+            epg_timeshift: v5.epg_timeshift.clone(),
+            epg_request_timeshift: v5.epg_request_timeshift.clone(),
+            created_at: v5.created_at,
+            exp_date: v5.exp_date,
+            max_connections: v5.max_connections,
+            status: v5.status,
+            ui_enabled: v5.ui_enabled,
+            comment: v5.comment.clone(),
+            priority: v5.priority,
+            soft_connections: v5.soft_connections,
+            soft_priority: v5.soft_priority,
+            quota_period: v5.quota_period,
+            quota_max_hours: v5.quota_max_hours,
+            quota_max_traffic_mb: v5.quota_max_traffic_mb,
+            access_schedule: None,
+        }
+    }
+
+    fn from_v4(v4: &StoredApiUserV4) -> Self { Self::from_v5(&StoredApiUserV5::from_v4(v4)) }
+
+    fn from_v3(v3: &StoredApiUserV3) -> Self { Self::from_v5(&StoredApiUserV5::from_v3(v3)) }
+
+    fn from_v2(v2: &StoredApiUserV2) -> Self { Self::from_v5(&StoredApiUserV5::from_v2(v2)) }
+
+    fn from_v1(v1: &StoredApiUserV1) -> Self { Self::from_v5(&StoredApiUserV5::from_v1(v1)) }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct StoredApiUserV7 {
+    pub target: String,
+    pub username: String,
+    pub password: String,
+    pub token: Option<String>,
+    pub proxy: ProxyType,
+    pub server: Option<String>,
+    pub epg_timeshift: Option<String>,
+    pub epg_request_timeshift: Option<String>,
+    pub created_at: Option<i64>,
+    pub exp_date: Option<i64>,
+    pub max_connections: Option<u32>,
+    pub status: Option<ProxyUserStatus>,
+    pub ui_enabled: bool,
+    pub comment: Option<String>,
+    pub priority: Option<i8>,
+    pub soft_connections: Option<u16>,
+    pub soft_priority: Option<i8>,
+    pub quota_period: Option<UserQuotaPeriod>,
+    pub quota_max_hours: Option<u32>,
+    pub quota_max_traffic_mb: Option<u64>,
+    pub access_schedule: Option<StoredAccessScheduleV6>,
+    pub parental_profile: Option<String>,
+}
+
+impl StoredApiUserV7 {
+    fn from_v6(v6: &StoredApiUserV6) -> Self {
+        Self {
+            target: v6.target.clone(),
+            username: v6.username.clone(),
+            password: v6.password.clone(),
+            token: v6.token.clone(),
+            proxy: v6.proxy,
+            server: v6.server.clone(),
+            epg_timeshift: v6.epg_timeshift.clone(),
+            epg_request_timeshift: v6.epg_request_timeshift.clone(),
+            created_at: v6.created_at,
+            exp_date: v6.exp_date,
+            max_connections: v6.max_connections,
+            status: v6.status,
+            ui_enabled: v6.ui_enabled,
+            comment: v6.comment.clone(),
+            priority: v6.priority,
+            soft_connections: v6.soft_connections,
+            soft_priority: v6.soft_priority,
+            quota_period: v6.quota_period,
+            quota_max_hours: v6.quota_max_hours,
+            quota_max_traffic_mb: v6.quota_max_traffic_mb,
+            access_schedule: v6.access_schedule.clone(),
+            parental_profile: None,
+        }
+    }
+
+    fn from_v5(v5: &StoredApiUserV5) -> Self { Self::from_v6(&StoredApiUserV6::from_v5(v5)) }
+
+    fn from_v4(v4: &StoredApiUserV4) -> Self { Self::from_v6(&StoredApiUserV6::from_v4(v4)) }
+
+    fn from_v3(v3: &StoredApiUserV3) -> Self { Self::from_v6(&StoredApiUserV6::from_v3(v3)) }
+
+    fn from_v2(v2: &StoredApiUserV2) -> Self { Self::from_v6(&StoredApiUserV6::from_v2(v2)) }
+
+    fn from_v1(v1: &StoredApiUserV1) -> Self { Self::from_v6(&StoredApiUserV6::from_v1(v1)) }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct StoredDeviceLimitV8 {
+    pub max_devices: u16,
+    pub approve_new: bool,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct StoredApiUserV8 {
+    pub target: String,
+    pub username: String,
+    pub password: String,
+    pub token: Option<String>,
+    pub proxy: ProxyType,
+    pub server: Option<String>,
+    pub epg_timeshift: Option<String>,
+    pub epg_request_timeshift: Option<String>,
+    pub created_at: Option<i64>,
+    pub exp_date: Option<i64>,
+    pub max_connections: Option<u32>,
+    pub status: Option<ProxyUserStatus>,
+    pub ui_enabled: bool,
+    pub comment: Option<String>,
+    pub priority: Option<i8>,
+    pub soft_connections: Option<u16>,
+    pub soft_priority: Option<i8>,
+    pub quota_period: Option<UserQuotaPeriod>,
+    pub quota_max_hours: Option<u32>,
+    pub quota_max_traffic_mb: Option<u64>,
+    pub access_schedule: Option<StoredAccessScheduleV6>,
+    pub parental_profile: Option<String>,
+    pub device_limit: Option<StoredDeviceLimitV8>,
+}
+
+impl StoredApiUserV8 {
+    fn from_v7(v7: &StoredApiUserV7) -> Self {
+        Self {
+            target: v7.target.clone(),
+            username: v7.username.clone(),
+            password: v7.password.clone(),
+            token: v7.token.clone(),
+            proxy: v7.proxy,
+            server: v7.server.clone(),
+            epg_timeshift: v7.epg_timeshift.clone(),
+            epg_request_timeshift: v7.epg_request_timeshift.clone(),
+            created_at: v7.created_at,
+            exp_date: v7.exp_date,
+            max_connections: v7.max_connections,
+            status: v7.status,
+            ui_enabled: v7.ui_enabled,
+            comment: v7.comment.clone(),
+            priority: v7.priority,
+            soft_connections: v7.soft_connections,
+            soft_priority: v7.soft_priority,
+            quota_period: v7.quota_period,
+            quota_max_hours: v7.quota_max_hours,
+            quota_max_traffic_mb: v7.quota_max_traffic_mb,
+            access_schedule: v7.access_schedule.clone(),
+            parental_profile: v7.parental_profile.clone(),
+            device_limit: None,
+        }
+    }
+
+    fn from_v6(v6: &StoredApiUserV6) -> Self { Self::from_v7(&StoredApiUserV7::from_v6(v6)) }
+
+    fn from_v5(v5: &StoredApiUserV5) -> Self { Self::from_v7(&StoredApiUserV7::from_v5(v5)) }
+
+    fn from_v4(v4: &StoredApiUserV4) -> Self { Self::from_v7(&StoredApiUserV7::from_v4(v4)) }
+
+    fn from_v3(v3: &StoredApiUserV3) -> Self { Self::from_v7(&StoredApiUserV7::from_v3(v3)) }
+
+    fn from_v2(v2: &StoredApiUserV2) -> Self { Self::from_v7(&StoredApiUserV7::from_v2(v2)) }
+
+    fn from_v1(v1: &StoredApiUserV1) -> Self { Self::from_v7(&StoredApiUserV7::from_v1(v1)) }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct StoredCountryAccessV9 {
+    pub allow: Vec<String>,
+    pub deny: Vec<String>,
+    pub home_country: Option<String>,
+    pub travel_days: u16,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct StoredApiUserV9 {
+    pub target: String,
+    pub username: String,
+    pub password: String,
+    pub token: Option<String>,
+    pub proxy: ProxyType,
+    pub server: Option<String>,
+    pub epg_timeshift: Option<String>,
+    pub epg_request_timeshift: Option<String>,
+    pub created_at: Option<i64>,
+    pub exp_date: Option<i64>,
+    pub max_connections: Option<u32>,
+    pub status: Option<ProxyUserStatus>,
+    pub ui_enabled: bool,
+    pub comment: Option<String>,
+    pub priority: Option<i8>,
+    pub soft_connections: Option<u16>,
+    pub soft_priority: Option<i8>,
+    pub quota_period: Option<UserQuotaPeriod>,
+    pub quota_max_hours: Option<u32>,
+    pub quota_max_traffic_mb: Option<u64>,
+    pub access_schedule: Option<StoredAccessScheduleV6>,
+    pub parental_profile: Option<String>,
+    pub device_limit: Option<StoredDeviceLimitV8>,
+    pub country_access: Option<StoredCountryAccessV9>,
+}
+
+impl StoredApiUserV9 {
+    fn from_v8(v8: &StoredApiUserV8) -> Self {
+        Self {
+            target: v8.target.clone(),
+            username: v8.username.clone(),
+            password: v8.password.clone(),
+            token: v8.token.clone(),
+            proxy: v8.proxy,
+            server: v8.server.clone(),
+            epg_timeshift: v8.epg_timeshift.clone(),
+            epg_request_timeshift: v8.epg_request_timeshift.clone(),
+            created_at: v8.created_at,
+            exp_date: v8.exp_date,
+            max_connections: v8.max_connections,
+            status: v8.status,
+            ui_enabled: v8.ui_enabled,
+            comment: v8.comment.clone(),
+            priority: v8.priority,
+            soft_connections: v8.soft_connections,
+            soft_priority: v8.soft_priority,
+            quota_period: v8.quota_period,
+            quota_max_hours: v8.quota_max_hours,
+            quota_max_traffic_mb: v8.quota_max_traffic_mb,
+            access_schedule: v8.access_schedule.clone(),
+            parental_profile: v8.parental_profile.clone(),
+            device_limit: v8.device_limit.clone(),
+            country_access: None,
+        }
+    }
+
+    fn from_v7(v7: &StoredApiUserV7) -> Self { Self::from_v8(&StoredApiUserV8::from_v7(v7)) }
+
+    fn from_v6(v6: &StoredApiUserV6) -> Self { Self::from_v8(&StoredApiUserV8::from_v6(v6)) }
+
+    fn from_v5(v5: &StoredApiUserV5) -> Self { Self::from_v8(&StoredApiUserV8::from_v5(v5)) }
+
+    fn from_v4(v4: &StoredApiUserV4) -> Self { Self::from_v8(&StoredApiUserV8::from_v4(v4)) }
+
+    fn from_v3(v3: &StoredApiUserV3) -> Self { Self::from_v8(&StoredApiUserV8::from_v3(v3)) }
+
+    fn from_v2(v2: &StoredApiUserV2) -> Self { Self::from_v8(&StoredApiUserV8::from_v2(v2)) }
+
+    fn from_v1(v1: &StoredApiUserV1) -> Self { Self::from_v8(&StoredApiUserV8::from_v1(v1)) }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct StoredApiUserV10 {
+    pub target: String,
+    pub username: String,
+    pub password: String,
+    pub token: Option<String>,
+    pub proxy: ProxyType,
+    pub server: Option<String>,
+    pub epg_timeshift: Option<String>,
+    pub epg_request_timeshift: Option<String>,
+    pub created_at: Option<i64>,
+    pub exp_date: Option<i64>,
+    pub max_connections: Option<u32>,
+    pub status: Option<ProxyUserStatus>,
+    pub ui_enabled: bool,
+    pub comment: Option<String>,
+    pub priority: Option<i8>,
+    pub soft_connections: Option<u16>,
+    pub soft_priority: Option<i8>,
+    pub quota_period: Option<UserQuotaPeriod>,
+    pub quota_max_hours: Option<u32>,
+    pub quota_max_traffic_mb: Option<u64>,
+    pub access_schedule: Option<StoredAccessScheduleV6>,
+    pub parental_profile: Option<String>,
+    pub device_limit: Option<StoredDeviceLimitV8>,
+    pub country_access: Option<StoredCountryAccessV9>,
+    pub limit_profile: Option<String>,
+}
+
+impl StoredApiUserV10 {
+    fn from_v9(v9: &StoredApiUserV9) -> Self {
+        Self {
+            target: v9.target.clone(),
+            username: v9.username.clone(),
+            password: v9.password.clone(),
+            token: v9.token.clone(),
+            proxy: v9.proxy,
+            server: v9.server.clone(),
+            epg_timeshift: v9.epg_timeshift.clone(),
+            epg_request_timeshift: v9.epg_request_timeshift.clone(),
+            created_at: v9.created_at,
+            exp_date: v9.exp_date,
+            max_connections: v9.max_connections,
+            status: v9.status,
+            ui_enabled: v9.ui_enabled,
+            comment: v9.comment.clone(),
+            priority: v9.priority,
+            soft_connections: v9.soft_connections,
+            soft_priority: v9.soft_priority,
+            quota_period: v9.quota_period,
+            quota_max_hours: v9.quota_max_hours,
+            quota_max_traffic_mb: v9.quota_max_traffic_mb,
+            access_schedule: v9.access_schedule.clone(),
+            parental_profile: v9.parental_profile.clone(),
+            device_limit: v9.device_limit.clone(),
+            country_access: v9.country_access.clone(),
+            limit_profile: None,
+        }
+    }
+
+    fn from_v8(v8: &StoredApiUserV8) -> Self { Self::from_v9(&StoredApiUserV9::from_v8(v8)) }
+
+    fn from_v7(v7: &StoredApiUserV7) -> Self { Self::from_v9(&StoredApiUserV9::from_v7(v7)) }
+
+    fn from_v6(v6: &StoredApiUserV6) -> Self { Self::from_v9(&StoredApiUserV9::from_v6(v6)) }
+
+    fn from_v5(v5: &StoredApiUserV5) -> Self { Self::from_v9(&StoredApiUserV9::from_v5(v5)) }
+
+    fn from_v4(v4: &StoredApiUserV4) -> Self { Self::from_v9(&StoredApiUserV9::from_v4(v4)) }
+
+    fn from_v3(v3: &StoredApiUserV3) -> Self { Self::from_v9(&StoredApiUserV9::from_v3(v3)) }
+
+    fn from_v2(v2: &StoredApiUserV2) -> Self { Self::from_v9(&StoredApiUserV9::from_v2(v2)) }
+
+    fn from_v1(v1: &StoredApiUserV1) -> Self { Self::from_v9(&StoredApiUserV9::from_v1(v1)) }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct StoredApiUserV11 {
+    pub target: String,
+    pub username: String,
+    pub password: String,
+    pub token: Option<String>,
+    pub proxy: ProxyType,
+    pub server: Option<String>,
+    pub epg_timeshift: Option<String>,
+    pub epg_request_timeshift: Option<String>,
+    pub created_at: Option<i64>,
+    pub exp_date: Option<i64>,
+    pub max_connections: Option<u32>,
+    pub status: Option<ProxyUserStatus>,
+    pub ui_enabled: bool,
+    pub comment: Option<String>,
+    pub priority: Option<i8>,
+    pub soft_connections: Option<u16>,
+    pub soft_priority: Option<i8>,
+    pub quota_period: Option<UserQuotaPeriod>,
+    pub quota_max_hours: Option<u32>,
+    pub quota_max_traffic_mb: Option<u64>,
+    pub access_schedule: Option<StoredAccessScheduleV6>,
+    pub parental_profile: Option<String>,
+    pub device_limit: Option<StoredDeviceLimitV8>,
+    pub country_access: Option<StoredCountryAccessV9>,
+    pub limit_profile: Option<String>,
+    pub reseller: Option<String>,
+}
+
+impl StoredApiUserV11 {
+    fn from_v10(v10: &StoredApiUserV10) -> Self {
+        Self {
+            target: v10.target.clone(),
+            username: v10.username.clone(),
+            password: v10.password.clone(),
+            token: v10.token.clone(),
+            proxy: v10.proxy,
+            server: v10.server.clone(),
+            epg_timeshift: v10.epg_timeshift.clone(),
+            epg_request_timeshift: v10.epg_request_timeshift.clone(),
+            created_at: v10.created_at,
+            exp_date: v10.exp_date,
+            max_connections: v10.max_connections,
+            status: v10.status,
+            ui_enabled: v10.ui_enabled,
+            comment: v10.comment.clone(),
+            priority: v10.priority,
+            soft_connections: v10.soft_connections,
+            soft_priority: v10.soft_priority,
+            quota_period: v10.quota_period,
+            quota_max_hours: v10.quota_max_hours,
+            quota_max_traffic_mb: v10.quota_max_traffic_mb,
+            access_schedule: v10.access_schedule.clone(),
+            parental_profile: v10.parental_profile.clone(),
+            device_limit: v10.device_limit.clone(),
+            country_access: v10.country_access.clone(),
+            limit_profile: v10.limit_profile.clone(),
+            reseller: None,
+        }
+    }
+
+    fn from_v9(v9: &StoredApiUserV9) -> Self { Self::from_v10(&StoredApiUserV10::from_v9(v9)) }
+
+    fn from_v8(v8: &StoredApiUserV8) -> Self { Self::from_v10(&StoredApiUserV10::from_v8(v8)) }
+
+    fn from_v7(v7: &StoredApiUserV7) -> Self { Self::from_v10(&StoredApiUserV10::from_v7(v7)) }
+
+    fn from_v6(v6: &StoredApiUserV6) -> Self { Self::from_v10(&StoredApiUserV10::from_v6(v6)) }
+
+    fn from_v5(v5: &StoredApiUserV5) -> Self { Self::from_v10(&StoredApiUserV10::from_v5(v5)) }
+
+    fn from_v4(v4: &StoredApiUserV4) -> Self { Self::from_v10(&StoredApiUserV10::from_v4(v4)) }
+
+    fn from_v3(v3: &StoredApiUserV3) -> Self { Self::from_v10(&StoredApiUserV10::from_v3(v3)) }
+
+    fn from_v2(v2: &StoredApiUserV2) -> Self { Self::from_v10(&StoredApiUserV10::from_v2(v2)) }
+
+    fn from_v1(v1: &StoredApiUserV1) -> Self { Self::from_v10(&StoredApiUserV10::from_v1(v1)) }
+}
+
+// V11 mirror — same layout as user_repository::StoredProxyUserCredentials.
+// Defined here so the migration has no dependency on user_repository internals.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct StoredApiUserV12 {
+    pub target: String,
+    pub username: String,
+    pub password: String,
+    pub token: Option<String>,
+    pub proxy: ProxyType,
+    pub server: Option<String>,
+    pub epg_timeshift: Option<String>,
+    pub epg_request_timeshift: Option<String>,
+    pub created_at: Option<i64>,
+    pub exp_date: Option<i64>,
+    pub max_connections: Option<u32>,
+    pub status: Option<ProxyUserStatus>,
+    pub ui_enabled: bool,
+    pub comment: Option<String>,
+    pub priority: Option<i8>,
+    pub soft_connections: Option<u16>,
+    pub soft_priority: Option<i8>,
+    pub quota_period: Option<UserQuotaPeriod>,
+    pub quota_max_hours: Option<u32>,
+    pub quota_max_traffic_mb: Option<u64>,
+    pub access_schedule: Option<StoredAccessScheduleV6>,
+    pub parental_profile: Option<String>,
+    pub device_limit: Option<StoredDeviceLimitV8>,
+    pub country_access: Option<StoredCountryAccessV9>,
+    pub limit_profile: Option<String>,
+    pub reseller: Option<String>,
+    pub hls_packaging: Option<bool>,
+}
+
+impl StoredApiUserV12 {
+    fn from_v11(v11: &StoredApiUserV11) -> Self {
+        Self {
+            target: v11.target.clone(),
+            username: v11.username.clone(),
+            password: v11.password.clone(),
+            token: v11.token.clone(),
+            proxy: v11.proxy,
+            server: v11.server.clone(),
+            epg_timeshift: v11.epg_timeshift.clone(),
+            epg_request_timeshift: v11.epg_request_timeshift.clone(),
+            created_at: v11.created_at,
+            exp_date: v11.exp_date,
+            max_connections: v11.max_connections,
+            status: v11.status,
+            ui_enabled: v11.ui_enabled,
+            comment: v11.comment.clone(),
+            priority: v11.priority,
+            soft_connections: v11.soft_connections,
+            soft_priority: v11.soft_priority,
+            quota_period: v11.quota_period,
+            quota_max_hours: v11.quota_max_hours,
+            quota_max_traffic_mb: v11.quota_max_traffic_mb,
+            access_schedule: v11.access_schedule.clone(),
+            parental_profile: v11.parental_profile.clone(),
+            device_limit: v11.device_limit.clone(),
+            country_access: v11.country_access.clone(),
+            limit_profile: v11.limit_profile.clone(),
+            reseller: v11.reseller.clone(),
+            hls_packaging: None,
+        }
+    }
+
+    fn from_v10(v10: &StoredApiUserV10) -> Self { Self::from_v11(&StoredApiUserV11::from_v10(v10)) }
+
+    fn from_v9(v9: &StoredApiUserV9) -> Self { Self::from_v11(&StoredApiUserV11::from_v9(v9)) }
+
+    fn from_v8(v8: &StoredApiUserV8) -> Self { Self::from_v11(&StoredApiUserV11::from_v8(v8)) }
+
+    fn from_v7(v7: &StoredApiUserV7) -> Self { Self::from_v11(&StoredApiUserV11::from_v7(v7)) }
+
+    fn from_v6(v6: &StoredApiUserV6) -> Self { Self::from_v11(&StoredApiUserV11::from_v6(v6)) }
+
+    fn from_v5(v5: &StoredApiUserV5) -> Self { Self::from_v11(&StoredApiUserV11::from_v5(v5)) }
+
+    fn from_v4(v4: &StoredApiUserV4) -> Self { Self::from_v11(&StoredApiUserV11::from_v4(v4)) }
+
+    fn from_v3(v3: &StoredApiUserV3) -> Self { Self::from_v11(&StoredApiUserV11::from_v3(v3)) }
+
+    fn from_v2(v2: &StoredApiUserV2) -> Self { Self::from_v11(&StoredApiUserV11::from_v2(v2)) }
+
+    fn from_v1(v1: &StoredApiUserV1) -> Self { Self::from_v11(&StoredApiUserV11::from_v1(v1)) }
+}
+
 fn create_user_db_merge_guard(merge_guard_path: &Path) -> io::Result<()> {
     if !merge_guard_path.exists() {
         std::fs::write(merge_guard_path, b"")?;
@@ -564,54 +1168,84 @@ pub(crate) fn user_db_merge_guard_path(config_dir: &Path) -> PathBuf {
     config_dir.join(MARKER_FILE_API_USER_GUARD)
 }
 
-/// Migrates the user database file from V1, V2, or V3 schema to V4 (current) in
+/// Converts the user database when it can be read as `V` and stores it as V12.
+///
+/// Returns `None` when the file is not in the `V` format.
+fn migrate_user_db_from<V>(
+    db_path: &Path,
+    merge_guard_path: &Path,
+    convert: fn(&V) -> StoredApiUserV12,
+) -> Option<io::Result<bool>>
+where
+    V: serde::Serialize + for<'de> serde::Deserialize<'de> + Clone,
+{
+    let tree = BPlusTree::<String, V>::load(db_path).ok()?;
+    let mut v12_tree: BPlusTree<String, StoredApiUserV12> = BPlusTree::new();
+    for (key, value) in &tree {
+        v12_tree.insert(key.clone(), convert(value));
+    }
+    if let Err(err) = create_user_db_merge_guard(merge_guard_path) {
+        return Some(Err(err));
+    }
+    Some(v12_tree.store(db_path).map(|_| true))
+}
+
+/// Migrates the user database file from V1, V2, V3, V4, V5, V6, V7, V8, V9, V10, or V11 schema to V12 (current) in
 /// place and creates a merge-guard file so config-driven merges are skipped
 /// until the operator explicitly removes it.
 ///
 /// Returns `true` when a migration was performed, `false` when the file was
-/// already in V4 format or did not exist.
+/// already in V12 format or did not exist.
 fn migrate_user_db_schema(db_path: &Path, merge_guard_path: &Path) -> io::Result<bool> {
     if !db_path.exists() {
         return Ok(false);
     }
 
-    if let Ok(tree) = BPlusTree::<String, StoredApiUserV1>::load(db_path) {
-        let mut v4_tree: BPlusTree<String, StoredApiUserV4> = BPlusTree::new();
-        for (key, v1) in &tree {
-            v4_tree.insert(key.clone(), StoredApiUserV4::from_v1(v1));
-        }
-        create_user_db_merge_guard(merge_guard_path)?;
-        v4_tree.store(db_path)?;
-        return Ok(true);
+    if let Some(result) = migrate_user_db_from(db_path, merge_guard_path, StoredApiUserV12::from_v1) {
+        return result;
     }
-
-    if let Ok(tree) = BPlusTree::<String, StoredApiUserV2>::load(db_path) {
-        let mut v4_tree: BPlusTree<String, StoredApiUserV4> = BPlusTree::new();
-        for (key, v2) in &tree {
-            v4_tree.insert(key.clone(), StoredApiUserV4::from_v2(v2));
-        }
-        create_user_db_merge_guard(merge_guard_path)?;
-        v4_tree.store(db_path)?;
-        return Ok(true);
+    if let Some(result) = migrate_user_db_from(db_path, merge_guard_path, StoredApiUserV12::from_v2) {
+        return result;
     }
-
-    if let Ok(tree) = BPlusTree::<String, StoredApiUserV3>::load(db_path) {
-        let mut v4_tree: BPlusTree<String, StoredApiUserV4> = BPlusTree::new();
-        for (key, v3) in &tree {
-            v4_tree.insert(key.clone(), StoredApiUserV4::from_v3(v3));
-        }
-        create_user_db_merge_guard(merge_guard_path)?;
-        v4_tree.store(db_path)?;
-        return Ok(true);
+    if let Some(result) = migrate_user_db_from(db_path, merge_guard_path, StoredApiUserV12::from_v3) {
+        return result;
+    }
+    if let Some(result) = migrate_user_db_from(db_path, merge_guard_path, StoredApiUserV12::from_v4) {
+        return result;
+    }
+    if let Some(result) = migrate_user_db_from(db_path, merge_guard_path, StoredApiUserV12::from_v5) {
+        return result;
+    }
+    if let Some(result) = migrate_user_db_from(db_path, merge_guard_path, StoredApiUserV12::from_v6) {
+        return result;
+    }
+    if let Some(result) = migrate_user_db_from(db_path, merge_guard_path, StoredApiUserV12::from_v7) {
+        return result;
+    }
+    if let Some(result) = migrate_user_db_from(db_path, merge_guard_path, StoredApiUserV12::from_v8) {
+        return result;
+    }
+    if let Some(result) = migrate_user_db_from(db_path, merge_guard_path, StoredApiUserV12::from_v9) {
+        return result;
+    }
+    if let Some(result) = migrate_user_db_from(db_path, merge_guard_path, StoredApiUserV12::from_v10) {
+        return result;
+    }
+    if let Some(result) = migrate_user_db_from(db_path, merge_guard_path, StoredApiUserV12::from_v11) {
+        return result;
     }
 
-    if BPlusTree::<String, StoredApiUserV4>::load(db_path).is_ok() {
+    if BPlusTree::<String, StoredApiUserV12>::load(db_path).is_ok() {
         return Ok(false);
     }
 
     Err(io::Error::new(
         io::ErrorKind::InvalidData,
-        format!("User DB at '{}' exists but could not be read as V1, V2, V3, or V4 format", db_path.display()),
+        format!(
+            "User DB at '{}' exists but could not be read as V1, V2, V3, V4, V5, V6, V7, V8, V9, V10, V11, \
+             or V12 format",
+            db_path.display()
+        ),
     ))
 }
 
@@ -623,7 +1257,7 @@ pub struct AllStartupMigrationStats {
 
 /// Runs all startup migrations in sequence:
 /// 1. B+Tree storage-format migration (V1 → current binary format)
-/// 2. User DB schema migration (V1/V2/V3 → V4 `MessagePack` layout)
+/// 2. User DB schema migration (V1/V2/V3/V4/V5/V6/V7/V8/V9/V10/V11 → V12 `MessagePack` layout)
 ///
 /// `config_dir` is the directory that contains `api_user.db` and the merge-guard
 /// marker. `storage_dir` is used for the B+Tree migration marker.
@@ -673,7 +1307,7 @@ pub fn run_startup_migrations(config_paths: &ConfigPaths) {
                 );
             }
             if stats.user_db_migrated {
-                info!("User DB schema migrated to V4");
+                info!("User DB schema migrated to V12");
             }
         }
         Err(err) => {
@@ -867,7 +1501,7 @@ mod tests {
     }
 
     #[test]
-    fn user_db_schema_migration_v2_to_v4_creates_merge_guard() -> io::Result<()> {
+    fn user_db_schema_migration_v2_to_v12_creates_merge_guard() -> io::Result<()> {
         let temp = tempdir()?;
         let db_path = temp.path().join(storage_const::API_USER_DB_FILE);
         let merge_guard_path = user_db_merge_guard_path(temp.path());
@@ -899,8 +1533,8 @@ mod tests {
         assert!(migrated);
         assert!(merge_guard_path.exists());
 
-        let v4_tree = BPlusTree::<String, StoredApiUserV4>::load(&db_path)?;
-        let user = v4_tree
+        let migrated_tree = BPlusTree::<String, StoredApiUserV12>::load(&db_path)?;
+        let user = migrated_tree
             .query(&"alice".to_string())
             .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "alice missing after migration"))?;
         assert_eq!(user.username, "alice");
@@ -913,7 +1547,7 @@ mod tests {
     }
 
     #[test]
-    fn user_db_schema_migration_v3_to_v4_creates_merge_guard() -> io::Result<()> {
+    fn user_db_schema_migration_v3_to_v12_creates_merge_guard() -> io::Result<()> {
         let temp = tempdir()?;
         let db_path = temp.path().join(storage_const::API_USER_DB_FILE);
         let merge_guard_path = user_db_merge_guard_path(temp.path());
@@ -946,8 +1580,8 @@ mod tests {
         assert!(migrated);
         assert!(merge_guard_path.exists());
 
-        let v4_tree = BPlusTree::<String, StoredApiUserV4>::load(&db_path)?;
-        let user = v4_tree
+        let v12_tree = BPlusTree::<String, StoredApiUserV12>::load(&db_path)?;
+        let user = v12_tree
             .query(&"bob".to_string())
             .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "bob missing after migration"))?;
         assert_eq!(user.priority, Some(5));
@@ -958,7 +1592,7 @@ mod tests {
     }
 
     #[test]
-    fn user_db_schema_v4_is_detected_without_writing_merge_guard() -> io::Result<()> {
+    fn user_db_schema_migration_v4_to_v12_keeps_soft_connections() -> io::Result<()> {
         let temp = tempdir()?;
         let db_path = temp.path().join(storage_const::API_USER_DB_FILE);
         let merge_guard_path = user_db_merge_guard_path(temp.path());
@@ -989,6 +1623,443 @@ mod tests {
         let _ = v4_tree.store(&db_path)?;
         assert!(!merge_guard_path.exists());
 
+        let migrated = migrate_user_db_schema(&db_path, &merge_guard_path)?;
+        assert!(migrated);
+        assert!(merge_guard_path.exists());
+
+        let v12_tree = BPlusTree::<String, StoredApiUserV12>::load(&db_path)?;
+        let user = v12_tree
+            .query(&"carol".to_string())
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "carol missing after migration"))?;
+        assert_eq!(user.soft_connections, Some(2));
+        assert_eq!(user.soft_priority, Some(-4));
+        assert_eq!(user.quota_period, None);
+        assert_eq!(user.quota_max_hours, None);
+        assert_eq!(user.quota_max_traffic_mb, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn user_db_schema_migration_v5_to_v12_keeps_quota() -> io::Result<()> {
+        let temp = tempdir()?;
+        let db_path = temp.path().join(storage_const::API_USER_DB_FILE);
+        let merge_guard_path = user_db_merge_guard_path(temp.path());
+
+        let mut v5_tree: BPlusTree<String, StoredApiUserV5> = BPlusTree::new();
+        v5_tree.insert(
+            "dave".to_string(),
+            StoredApiUserV5 {
+                target: "channels".to_string(),
+                username: "dave".to_string(),
+                password: "secret".to_string(),
+                token: None,
+                proxy: ProxyType::Reverse(None),
+                server: None,
+                epg_timeshift: None,
+                epg_request_timeshift: None,
+                created_at: None,
+                exp_date: None,
+                max_connections: Some(1),
+                status: Some(ProxyUserStatus::Active),
+                ui_enabled: true,
+                comment: None,
+                priority: Some(5),
+                soft_connections: Some(2),
+                soft_priority: Some(-4),
+                quota_period: Some(UserQuotaPeriod::Month),
+                quota_max_hours: Some(40),
+                quota_max_traffic_mb: Some(50_000),
+            },
+        );
+        let _ = v5_tree.store(&db_path)?;
+        assert!(!merge_guard_path.exists());
+
+        let migrated = migrate_user_db_schema(&db_path, &merge_guard_path)?;
+        assert!(migrated);
+        assert!(merge_guard_path.exists());
+
+        let v12_tree = BPlusTree::<String, StoredApiUserV12>::load(&db_path)?;
+        let user = v12_tree
+            .query(&"dave".to_string())
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "dave missing after migration"))?;
+        assert_eq!(user.quota_period, Some(UserQuotaPeriod::Month));
+        assert_eq!(user.quota_max_hours, Some(40));
+        assert_eq!(user.quota_max_traffic_mb, Some(50_000));
+        assert!(user.access_schedule.is_none());
+        assert!(user.parental_profile.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn user_db_schema_migration_v6_to_v12_keeps_access_schedule() -> io::Result<()> {
+        let temp = tempdir()?;
+        let db_path = temp.path().join(storage_const::API_USER_DB_FILE);
+        let merge_guard_path = user_db_merge_guard_path(temp.path());
+
+        let mut v6_tree: BPlusTree<String, StoredApiUserV6> = BPlusTree::new();
+        v6_tree.insert(
+            "erin".to_string(),
+            StoredApiUserV6 {
+                target: "channels".to_string(),
+                username: "erin".to_string(),
+                password: "secret".to_string(),
+                token: None,
+                proxy: ProxyType::Reverse(None),
+                server: None,
+                epg_timeshift: None,
+                epg_request_timeshift: None,
+                created_at: None,
+                exp_date: None,
+                max_connections: Some(1),
+                status: Some(ProxyUserStatus::Active),
+                ui_enabled: true,
+                comment: None,
+                priority: None,
+                soft_connections: None,
+                soft_priority: None,
+                quota_period: None,
+                quota_max_hours: None,
+                quota_max_traffic_mb: None,
+                access_schedule: Some(StoredAccessScheduleV6 {
+                    timezone: None,
+                    windows: vec![StoredAccessWindowV6 {
+                        days: vec![AccessScheduleDay::Sun],
+                        from: "08:00".to_string(),
+                        to: "19:30".to_string(),
+                    }],
+                    kick_on_close: false,
+                }),
+            },
+        );
+        let _ = v6_tree.store(&db_path)?;
+
+        let migrated = migrate_user_db_schema(&db_path, &merge_guard_path)?;
+        assert!(migrated);
+        assert!(merge_guard_path.exists());
+
+        let v12_tree = BPlusTree::<String, StoredApiUserV12>::load(&db_path)?;
+        let user = v12_tree
+            .query(&"erin".to_string())
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "erin missing after migration"))?;
+        let schedule = user.access_schedule.as_ref().expect("access schedule kept");
+        assert_eq!(schedule.windows[0].days, vec![AccessScheduleDay::Sun]);
+        assert!(user.parental_profile.is_none());
+        assert!(user.device_limit.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn user_db_schema_migration_v7_to_v12_keeps_parental_profile() -> io::Result<()> {
+        let temp = tempdir()?;
+        let db_path = temp.path().join(storage_const::API_USER_DB_FILE);
+        let merge_guard_path = user_db_merge_guard_path(temp.path());
+
+        let mut v7_tree: BPlusTree<String, StoredApiUserV7> = BPlusTree::new();
+        v7_tree.insert(
+            "frank".to_string(),
+            StoredApiUserV7 {
+                target: "channels".to_string(),
+                username: "frank".to_string(),
+                password: "secret".to_string(),
+                token: None,
+                proxy: ProxyType::Reverse(None),
+                server: None,
+                epg_timeshift: None,
+                epg_request_timeshift: None,
+                created_at: None,
+                exp_date: None,
+                max_connections: Some(1),
+                status: Some(ProxyUserStatus::Active),
+                ui_enabled: true,
+                comment: None,
+                priority: None,
+                soft_connections: None,
+                soft_priority: None,
+                quota_period: None,
+                quota_max_hours: None,
+                quota_max_traffic_mb: None,
+                access_schedule: None,
+                parental_profile: Some("kids".to_string()),
+            },
+        );
+        let _ = v7_tree.store(&db_path)?;
+
+        let migrated = migrate_user_db_schema(&db_path, &merge_guard_path)?;
+        assert!(migrated);
+        assert!(merge_guard_path.exists());
+
+        let v12_tree = BPlusTree::<String, StoredApiUserV12>::load(&db_path)?;
+        let user = v12_tree
+            .query(&"frank".to_string())
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "frank missing after migration"))?;
+        assert_eq!(user.parental_profile.as_deref(), Some("kids"));
+        assert!(user.device_limit.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn user_db_schema_migration_v8_to_v12_keeps_device_limit() -> io::Result<()> {
+        let temp = tempdir()?;
+        let db_path = temp.path().join(storage_const::API_USER_DB_FILE);
+        let merge_guard_path = user_db_merge_guard_path(temp.path());
+
+        let mut v8_tree: BPlusTree<String, StoredApiUserV8> = BPlusTree::new();
+        v8_tree.insert(
+            "gina".to_string(),
+            StoredApiUserV8 {
+                target: "channels".to_string(),
+                username: "gina".to_string(),
+                password: "secret".to_string(),
+                token: None,
+                proxy: ProxyType::Reverse(None),
+                server: None,
+                epg_timeshift: None,
+                epg_request_timeshift: None,
+                created_at: None,
+                exp_date: None,
+                max_connections: Some(1),
+                status: Some(ProxyUserStatus::Active),
+                ui_enabled: true,
+                comment: None,
+                priority: None,
+                soft_connections: None,
+                soft_priority: None,
+                quota_period: None,
+                quota_max_hours: None,
+                quota_max_traffic_mb: None,
+                access_schedule: None,
+                parental_profile: None,
+                device_limit: Some(StoredDeviceLimitV8 { max_devices: 2, approve_new: true }),
+            },
+        );
+        let _ = v8_tree.store(&db_path)?;
+
+        let migrated = migrate_user_db_schema(&db_path, &merge_guard_path)?;
+        assert!(migrated);
+        assert!(merge_guard_path.exists());
+
+        let v12_tree = BPlusTree::<String, StoredApiUserV12>::load(&db_path)?;
+        let user = v12_tree
+            .query(&"gina".to_string())
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "gina missing after migration"))?;
+        assert_eq!(user.device_limit.as_ref().map(|limit| limit.max_devices), Some(2));
+        assert!(user.country_access.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn user_db_schema_migration_v9_to_v12_keeps_country_access() -> io::Result<()> {
+        let temp = tempdir()?;
+        let db_path = temp.path().join(storage_const::API_USER_DB_FILE);
+        let merge_guard_path = user_db_merge_guard_path(temp.path());
+
+        let mut v9_tree: BPlusTree<String, StoredApiUserV9> = BPlusTree::new();
+        v9_tree.insert(
+            "hank".to_string(),
+            StoredApiUserV9 {
+                target: "channels".to_string(),
+                username: "hank".to_string(),
+                password: "secret".to_string(),
+                token: None,
+                proxy: ProxyType::Reverse(None),
+                server: None,
+                epg_timeshift: None,
+                epg_request_timeshift: None,
+                created_at: None,
+                exp_date: None,
+                max_connections: Some(1),
+                status: Some(ProxyUserStatus::Active),
+                ui_enabled: true,
+                comment: None,
+                priority: None,
+                soft_connections: None,
+                soft_priority: None,
+                quota_period: None,
+                quota_max_hours: None,
+                quota_max_traffic_mb: None,
+                access_schedule: None,
+                parental_profile: None,
+                device_limit: None,
+                country_access: Some(StoredCountryAccessV9 {
+                    allow: vec![],
+                    deny: vec![],
+                    home_country: Some("DE".to_string()),
+                    travel_days: 14,
+                }),
+            },
+        );
+        let _ = v9_tree.store(&db_path)?;
+
+        let migrated = migrate_user_db_schema(&db_path, &merge_guard_path)?;
+        assert!(migrated);
+        assert!(merge_guard_path.exists());
+
+        let v12_tree = BPlusTree::<String, StoredApiUserV12>::load(&db_path)?;
+        let user = v12_tree
+            .query(&"hank".to_string())
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "hank missing after migration"))?;
+        assert_eq!(user.country_access.as_ref().map(|access| access.travel_days), Some(14));
+        assert!(user.limit_profile.is_none());
+        assert!(user.reseller.is_none());
+        assert!(user.hls_packaging.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn user_db_schema_migration_v10_to_v12_keeps_limit_profile() -> io::Result<()> {
+        let temp = tempdir()?;
+        let db_path = temp.path().join(storage_const::API_USER_DB_FILE);
+        let merge_guard_path = user_db_merge_guard_path(temp.path());
+
+        let mut v10_tree: BPlusTree<String, StoredApiUserV10> = BPlusTree::new();
+        v10_tree.insert(
+            "iris".to_string(),
+            StoredApiUserV10 {
+                target: "channels".to_string(),
+                username: "iris".to_string(),
+                password: "secret".to_string(),
+                token: None,
+                proxy: ProxyType::Reverse(None),
+                server: None,
+                epg_timeshift: None,
+                epg_request_timeshift: None,
+                created_at: None,
+                exp_date: None,
+                max_connections: Some(1),
+                status: Some(ProxyUserStatus::Active),
+                ui_enabled: true,
+                comment: None,
+                priority: None,
+                soft_connections: None,
+                soft_priority: None,
+                quota_period: None,
+                quota_max_hours: None,
+                quota_max_traffic_mb: None,
+                access_schedule: None,
+                parental_profile: None,
+                device_limit: None,
+                country_access: None,
+                limit_profile: Some("guest".to_string()),
+            },
+        );
+        let _ = v10_tree.store(&db_path)?;
+
+        let migrated = migrate_user_db_schema(&db_path, &merge_guard_path)?;
+        assert!(migrated);
+        assert!(merge_guard_path.exists());
+
+        let v12_tree = BPlusTree::<String, StoredApiUserV12>::load(&db_path)?;
+        let user = v12_tree
+            .query(&"iris".to_string())
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "iris missing after migration"))?;
+        assert_eq!(user.limit_profile.as_deref(), Some("guest"));
+        assert!(user.reseller.is_none());
+        assert!(user.hls_packaging.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn user_db_schema_migration_v11_to_v12_keeps_reseller() -> io::Result<()> {
+        let temp = tempdir()?;
+        let db_path = temp.path().join(storage_const::API_USER_DB_FILE);
+        let merge_guard_path = user_db_merge_guard_path(temp.path());
+
+        let mut v11_tree: BPlusTree<String, StoredApiUserV11> = BPlusTree::new();
+        v11_tree.insert(
+            "jack".to_string(),
+            StoredApiUserV11 {
+                target: "channels".to_string(),
+                username: "jack".to_string(),
+                password: "secret".to_string(),
+                token: None,
+                proxy: ProxyType::Reverse(None),
+                server: None,
+                epg_timeshift: None,
+                epg_request_timeshift: None,
+                created_at: None,
+                exp_date: None,
+                max_connections: Some(1),
+                status: Some(ProxyUserStatus::Active),
+                ui_enabled: true,
+                comment: None,
+                priority: None,
+                soft_connections: None,
+                soft_priority: None,
+                quota_period: None,
+                quota_max_hours: None,
+                quota_max_traffic_mb: None,
+                access_schedule: None,
+                parental_profile: None,
+                device_limit: None,
+                country_access: None,
+                limit_profile: None,
+                reseller: Some("bob".to_string()),
+            },
+        );
+        let _ = v11_tree.store(&db_path)?;
+
+        let migrated = migrate_user_db_schema(&db_path, &merge_guard_path)?;
+        assert!(migrated);
+        assert!(merge_guard_path.exists());
+
+        let v12_tree = BPlusTree::<String, StoredApiUserV12>::load(&db_path)?;
+        let user = v12_tree
+            .query(&"jack".to_string())
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "jack missing after migration"))?;
+        assert_eq!(user.reseller.as_deref(), Some("bob"));
+        assert!(user.hls_packaging.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn user_db_schema_v12_is_detected_without_writing_merge_guard() -> io::Result<()> {
+        let temp = tempdir()?;
+        let db_path = temp.path().join(storage_const::API_USER_DB_FILE);
+        let merge_guard_path = user_db_merge_guard_path(temp.path());
+
+        let mut v12_tree: BPlusTree<String, StoredApiUserV12> = BPlusTree::new();
+        v12_tree.insert(
+            "kate".to_string(),
+            StoredApiUserV12 {
+                target: "channels".to_string(),
+                username: "kate".to_string(),
+                password: "secret".to_string(),
+                token: None,
+                proxy: ProxyType::Reverse(None),
+                server: None,
+                epg_timeshift: None,
+                epg_request_timeshift: None,
+                created_at: None,
+                exp_date: None,
+                max_connections: Some(1),
+                status: Some(ProxyUserStatus::Active),
+                ui_enabled: true,
+                comment: None,
+                priority: None,
+                soft_connections: None,
+                soft_priority: None,
+                quota_period: None,
+                quota_max_hours: None,
+                quota_max_traffic_mb: None,
+                access_schedule: None,
+                parental_profile: None,
+                device_limit: None,
+                country_access: None,
+                limit_profile: None,
+                reseller: None,
+                hls_packaging: Some(false),
+            },
+        );
+        let _ = v12_tree.store(&db_path)?;
+
         let migrated = migrate_user_db_schema(&db_path, &merge_guard_path)?;
         assert!(!migrated);
         assert!(!merge_guard_path.exists());
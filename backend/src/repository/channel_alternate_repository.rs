@@ -0,0 +1,80 @@
+use crate::model::AppConfig;
+use crate::repository::bplustree::{BPlusTree, BPlusTreeQuery};
+use crate::repository::{get_target_storage_path, storage_const};
+use log::debug;
+use serde::{Deserialize, Serialize};
+use shared::error::{info_err, TuliproxError};
+use shared::utils::sanitize_sensitive_info;
+use std::path::{Path, PathBuf};
+
+/// The same live channel provided by another input, tried when the stream of the channel can't be opened.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ChannelAlternate {
+    pub input_name: String,
+    pub url: String,
+}
+
+fn get_channel_alternates_file(target_path: &Path) -> PathBuf {
+    target_path.join(storage_const::FILE_CHANNEL_ALTERNATES)
+}
+
+/// Stores the alternates of the target channels by virtual id, an empty list removes the stored alternates.
+pub async fn persist_channel_alternates(
+    app_config: &AppConfig,
+    target_name: &str,
+    alternates: Vec<(u32, Vec<ChannelAlternate>)>,
+) -> Result<(), TuliproxError> {
+    let Some(target_path) = get_target_storage_path(&app_config.config.load(), target_name) else {
+        return Err(info_err!("Can't find storage path for target {target_name}"));
+    };
+    let path = get_channel_alternates_file(&target_path);
+    let _file_lock = app_config.file_locks.write_lock(&path).await;
+    if alternates.is_empty() {
+        if tokio::fs::try_exists(&path).await.unwrap_or(false) {
+            tokio::fs::remove_file(&path)
+                .await
+                .map_err(|err| info_err!("Failed to remove channel alternates {}: {err}", path.display()))?;
+        }
+        return Ok(());
+    }
+    let store_path = path.clone();
+    tokio::task::spawn_blocking(move || {
+        let mut tree = BPlusTree::<u32, Vec<ChannelAlternate>>::new();
+        for (virtual_id, channel_alternates) in alternates {
+            tree.insert(virtual_id, channel_alternates);
+        }
+        tree.store(&store_path)
+    })
+    .await
+    .map_err(|err| info_err!("Failed to store channel alternates {}: {err}", path.display()))?
+    .map_err(|err| info_err!("Failed to store channel alternates {}: {err}", path.display()))?;
+    Ok(())
+}
+
+/// Returns the alternates of a target channel in failover order.
+pub async fn load_channel_alternates(
+    app_config: &AppConfig,
+    target_name: &str,
+    virtual_id: u32,
+) -> Vec<ChannelAlternate> {
+    let Some(target_path) = get_target_storage_path(&app_config.config.load(), target_name) else {
+        return vec![];
+    };
+    let path = get_channel_alternates_file(&target_path);
+    let file_lock = app_config.file_locks.read_lock(&path).await;
+    tokio::task::spawn_blocking(move || {
+        let _guard = file_lock;
+        if !path.exists() {
+            return vec![];
+        }
+        match BPlusTreeQuery::<u32, Vec<ChannelAlternate>>::try_new(&path) {
+            Ok(mut query) => query.query(&virtual_id).ok().flatten().unwrap_or_default(),
+            Err(err) => {
+                debug!("Failed to read channel alternates {}: {err}", sanitize_sensitive_info(&path.to_string_lossy()));
+                vec![]
+            }
+        }
+    })
+    .await
+    .unwrap_or_default()
+}
@@ -0,0 +1,497 @@
+use crate::model::{AppConfig, ConfigTarget, Enigma2TargetOutput, ProxyUserCredentials};
+use crate::repository::{m3u_rewrite_playlist_item, xtream_user_stream_url};
+use crate::utils;
+use indexmap::IndexMap;
+use log::error;
+use quick_xml::escape::escape;
+use shared::error::{info_err_res, TuliproxError};
+use shared::foundation::ValueProvider;
+use shared::model::{
+    M3uPlaylistItem, PlaylistGroup, PlaylistItem, PlaylistItemType, TargetType, XtreamCluster, XtreamPlaylistItem,
+};
+use shared::utils::deunicode_string;
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::fs;
+
+pub const ENIGMA2_BOUQUETS_INDEX: &str = "bouquets.tv";
+const ENIGMA2_PATH: &str = "enigma2";
+const ENIGMA2_BOUQUET_FILE_PREFIX: &str = "userbouquet.";
+const ENIGMA2_BOUQUET_FILE_SUFFIX: &str = ".tv";
+const ENIGMA2_EPGIMPORT_SOURCES_SUFFIX: &str = ".sources.xml";
+const ENIGMA2_EPGIMPORT_CHANNELS_SUFFIX: &str = ".channels.xml";
+// 4097 is the gstreamer based IPTV service type which all current images support.
+const ENIGMA2_SERVICE_TYPE: u16 = 4097;
+
+#[derive(Debug, Clone)]
+pub struct Enigma2Channel {
+    pub group: Arc<str>,
+    pub name: Arc<str>,
+    pub url: String,
+    pub epg_channel_id: Option<Arc<str>>,
+    pub virtual_id: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct Enigma2Bouquet {
+    pub name: Arc<str>,
+    pub filename: String,
+    pub channels: Vec<Enigma2Channel>,
+}
+
+/// Everything the bouquet files for one user consist of.
+pub struct Enigma2Export {
+    pub prefix: String,
+    pub bouquets: Vec<Enigma2Bouquet>,
+}
+
+impl Enigma2Export {
+    pub fn epgimport_sources_filename(&self) -> String {
+        format!("{}{ENIGMA2_EPGIMPORT_SOURCES_SUFFIX}", self.prefix)
+    }
+
+    pub fn epgimport_channels_filename(&self) -> String {
+        format!("{}{ENIGMA2_EPGIMPORT_CHANNELS_SUFFIX}", self.prefix)
+    }
+
+    pub fn find_bouquet(&self, filename: &str) -> Option<&Enigma2Bouquet> {
+        self.bouquets.iter().find(|b| b.filename == filename)
+    }
+}
+
+/// Converts a name into the restricted character set enigma2 accepts for bouquet filenames.
+fn enigma2_slug(name: &str) -> String {
+    let ascii = deunicode_string(name);
+    let mut slug = String::with_capacity(ascii.len());
+    for c in ascii.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.ends_with('_') {
+            slug.push('_');
+        }
+    }
+    let slug = slug.trim_matches('_');
+    if slug.is_empty() {
+        String::from("bouquet")
+    } else {
+        slug.to_string()
+    }
+}
+
+pub fn enigma2_bouquet_prefix(target: &ConfigTarget, output: &Enigma2TargetOutput) -> String {
+    enigma2_slug(output.bouquet_prefix.as_deref().unwrap_or(target.name.as_str()))
+}
+
+/// Groups the channels into one bouquet per group, keeping the playlist order.
+pub fn enigma2_build_export(prefix: String, channels: impl IntoIterator<Item=Enigma2Channel>) -> Enigma2Export {
+    let mut groups: IndexMap<Arc<str>, Vec<Enigma2Channel>> = IndexMap::new();
+    for channel in channels {
+        groups.entry(Arc::clone(&channel.group)).or_default().push(channel);
+    }
+
+    let mut used_filenames = HashSet::with_capacity(groups.len());
+    let bouquets = groups
+        .into_iter()
+        .map(|(name, channels)| {
+            let base = format!("{prefix}_{}", enigma2_slug(&name));
+            let mut stem = base.clone();
+            let mut counter = 1;
+            while !used_filenames.insert(stem.clone()) {
+                counter += 1;
+                stem = format!("{base}_{counter}");
+            }
+            Enigma2Bouquet {
+                name,
+                filename: format!("{ENIGMA2_BOUQUET_FILE_PREFIX}{stem}{ENIGMA2_BOUQUET_FILE_SUFFIX}"),
+                channels,
+            }
+        })
+        .collect();
+
+    Enigma2Export { prefix, bouquets }
+}
+
+fn enigma2_line_value(text: &str) -> String {
+    text.replace(['\r', '\n'], " ")
+}
+
+/// Enigma2 separates the service reference fields with `:`, so it has to be encoded inside the url.
+fn enigma2_encode_url(url: &str) -> String {
+    url.replace(':', "%3a")
+}
+
+fn enigma2_service_reference(channel: &Enigma2Channel) -> String {
+    format!("{ENIGMA2_SERVICE_TYPE}:0:1:{:X}:0:0:0:0:0:0", channel.virtual_id)
+}
+
+pub fn enigma2_render_bouquet(bouquet: &Enigma2Bouquet) -> String {
+    let mut content = format!("#NAME {}\n", enigma2_line_value(&bouquet.name));
+    for channel in &bouquet.channels {
+        let name = enigma2_line_value(&channel.name);
+        let _ = writeln!(
+            content,
+            "#SERVICE {}:{}:{name}\n#DESCRIPTION {name}",
+            enigma2_service_reference(channel),
+            enigma2_encode_url(&channel.url)
+        );
+    }
+    content
+}
+
+pub fn enigma2_render_bouquets_index(export: &Enigma2Export) -> String {
+    let mut content = String::from("#NAME User - bouquets (TV)\n");
+    for bouquet in &export.bouquets {
+        let _ = writeln!(
+            content,
+            "#SERVICE 1:7:1:0:0:0:0:0:0:0:FROM BOUQUET \"{}\" ORDER BY bouquet",
+            bouquet.filename
+        );
+    }
+    content
+}
+
+pub fn enigma2_render_epgimport_channels(export: &Enigma2Export) -> String {
+    let mut content = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<channels>\n");
+    for channel in export.bouquets.iter().flat_map(|b| &b.channels) {
+        if let Some(epg_id) = channel.epg_channel_id.as_ref().filter(|id| !id.is_empty()) {
+            // EPGImport matches the service reference without the stream url and with service type 1.
+            let _ = writeln!(
+                content,
+                "  <channel id=\"{}\">1:0:1:{:X}:0:0:0:0:0:0:http%3a//</channel> <!-- {} -->",
+                escape(epg_id.as_ref()),
+                channel.virtual_id,
+                escape(enigma2_line_value(&channel.name).replace("--", "-").as_str())
+            );
+        }
+    }
+    content.push_str("</channels>\n");
+    content
+}
+
+pub fn enigma2_render_epgimport_sources(description: &str, channels_url: &str, xmltv_url: &str) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="utf-8"?>
+<sources>
+  <sourcecat sourcecatname="{description}">
+    <source type="gen_xmltv" nocheck="1" channels="{}">
+      <description>{description}</description>
+      <url>{}</url>
+    </source>
+  </sourcecat>
+</sources>
+"#,
+        escape(channels_url),
+        escape(xmltv_url),
+        description = escape(description),
+    )
+}
+
+pub fn enigma2_includes_cluster(output: &Enigma2TargetOutput, cluster: XtreamCluster) -> bool {
+    match cluster {
+        XtreamCluster::Live => true,
+        XtreamCluster::Video => output.include_vod,
+        XtreamCluster::Series => false,
+    }
+}
+
+pub fn enigma2_filter_item(output: &Enigma2TargetOutput, pli: &PlaylistItem) -> bool {
+    output.filter.as_ref().is_none_or(|filter| filter.filter(&ValueProvider { pli, match_as_ascii: false }))
+}
+
+/// Percent-encodes a url path segment or query value.
+fn enigma2_encode_component(value: &str) -> String {
+    url::form_urlencoded::byte_serialize(value.as_bytes()).collect::<String>().replace('+', "%20")
+}
+
+pub fn enigma2_epgimport_urls(base_url: &str, user: &ProxyUserCredentials, export: &Enigma2Export) -> (String, String) {
+    let username = enigma2_encode_component(&user.username);
+    let password = enigma2_encode_component(&user.password);
+    let channels_url = format!(
+        "{base_url}/{ENIGMA2_PATH}/{username}/{password}/{}",
+        enigma2_encode_component(&export.epgimport_channels_filename())
+    );
+    let xmltv_url = format!("{base_url}/xmltv.php?username={username}&password={password}");
+    (channels_url, xmltv_url)
+}
+
+pub fn enigma2_xtream_item_channel(
+    app_config: &AppConfig,
+    target: &ConfigTarget,
+    user: &ProxyUserCredentials,
+    base_url: &str,
+    item: &XtreamPlaylistItem,
+) -> Enigma2Channel {
+    let container_extension = item.get_container_extension();
+    Enigma2Channel {
+        group: Arc::clone(&item.group),
+        name: Arc::clone(&item.name),
+        url: xtream_user_stream_url(
+            app_config,
+            target,
+            user,
+            base_url,
+            item.xtream_cluster,
+            item.item_type,
+            item.virtual_id,
+            &item.input_name,
+            &item.url,
+            container_extension.as_deref(),
+        ),
+        epg_channel_id: item.epg_channel_id.clone(),
+        virtual_id: item.virtual_id,
+    }
+}
+
+/// Channel of an m3u target, the stream url was already rewritten by the m3u playlist iterator.
+pub fn enigma2_m3u_item_channel(item: &M3uPlaylistItem) -> Enigma2Channel {
+    Enigma2Channel {
+        group: Arc::clone(&item.group),
+        name: Arc::clone(&item.name),
+        url: item.t_stream_url.to_string(),
+        epg_channel_id: item.epg_channel_id.clone(),
+        virtual_id: item.virtual_id,
+    }
+}
+
+fn is_enigma2_item_type(item_type: PlaylistItemType) -> bool {
+    matches!(
+        item_type,
+        PlaylistItemType::Live
+            | PlaylistItemType::LiveUnknown
+            | PlaylistItemType::LiveHls
+            | PlaylistItemType::LiveDash
+            | PlaylistItemType::Video
+            | PlaylistItemType::LocalVideo
+    )
+}
+
+/// Returns the cluster of bouquet items, `None` for items which are not listed in bouquets.
+pub fn enigma2_item_cluster(item_type: PlaylistItemType) -> Option<XtreamCluster> {
+    is_enigma2_item_type(item_type).then(|| XtreamCluster::try_from(item_type).unwrap_or(XtreamCluster::Live))
+}
+
+fn collect_enigma2_channels(
+    app_config: &AppConfig,
+    target: &ConfigTarget,
+    output: &Enigma2TargetOutput,
+    user: &ProxyUserCredentials,
+    base_url: &str,
+    playlist: &[PlaylistGroup],
+) -> Vec<Enigma2Channel> {
+    // Targets without xtream output get the stream urls of their m3u playlist.
+    let m3u_urls = !target.has_output(TargetType::Xtream);
+    playlist
+        .iter()
+        .filter(|group| enigma2_includes_cluster(output, group.xtream_cluster))
+        .flat_map(|group| &group.channels)
+        .filter(|pli| is_enigma2_item_type(pli.header.item_type))
+        .map(|pli| {
+            if m3u_urls {
+                if let Some(item) = m3u_rewrite_playlist_item(app_config, target, user, M3uPlaylistItem::from(pli)) {
+                    return enigma2_m3u_item_channel(&item);
+                }
+            }
+            let header = &pli.header;
+            let cluster = XtreamCluster::try_from(header.item_type).unwrap_or(XtreamCluster::Live);
+            let container_extension = header.get_container_extension();
+            Enigma2Channel {
+                group: Arc::clone(&header.group),
+                name: header.get_name(),
+                url: xtream_user_stream_url(
+                    app_config,
+                    target,
+                    user,
+                    base_url,
+                    cluster,
+                    header.item_type,
+                    header.virtual_id,
+                    &header.input_name,
+                    &header.url,
+                    container_extension.as_deref(),
+                ),
+                epg_channel_id: header.epg_channel_id.clone(),
+                virtual_id: header.virtual_id,
+            }
+        })
+        .collect()
+}
+
+fn get_enigma2_output_path(app_config: &AppConfig, target_output: &Enigma2TargetOutput, target_path: &Path) -> Option<PathBuf> {
+    match target_output.directory.as_ref() {
+        Some(directory) => {
+            let config = app_config.config.load();
+            utils::get_file_path(&config.storage_dir, Some(PathBuf::from(directory)))
+        }
+        None => Some(target_path.join(ENIGMA2_PATH)),
+    }
+}
+
+async fn write_enigma2_file(path: &Path, content: &str) -> Result<(), String> {
+    fs::write(path, content)
+        .await
+        .map_err(|err| format!("Failed to write enigma2 file {}: {err}", path.display()))
+}
+
+/// Removes bouquet files of a previous run which no longer correspond to a group.
+async fn cleanup_enigma2_bouquets(output_path: &Path, export: &Enigma2Export) -> Result<(), String> {
+    let stale_prefix = format!("{ENIGMA2_BOUQUET_FILE_PREFIX}{}_", export.prefix);
+    let mut entries = fs::read_dir(output_path)
+        .await
+        .map_err(|err| format!("Failed to read enigma2 directory {}: {err}", output_path.display()))?;
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        if file_name.starts_with(&stale_prefix)
+            && file_name.ends_with(ENIGMA2_BOUQUET_FILE_SUFFIX)
+            && export.find_bouquet(&file_name).is_none()
+        {
+            if let Err(err) = fs::remove_file(entry.path()).await {
+                error!("Failed to remove stale enigma2 bouquet {}: {err}", entry.path().display());
+            }
+        }
+    }
+    Ok(())
+}
+
+pub async fn enigma2_write_playlist(
+    app_config: &AppConfig,
+    target: &ConfigTarget,
+    target_output: &Enigma2TargetOutput,
+    target_path: &Path,
+    new_playlist: &[PlaylistGroup],
+) -> Result<(), TuliproxError> {
+    if new_playlist.is_empty() {
+        return Ok(());
+    }
+
+    let Some(user) = app_config.get_user_credentials(&target_output.username) else {
+        return info_err_res!("User {} for enigma2 output of target {} not found", target_output.username, target.name);
+    };
+    let Some(output_path) = get_enigma2_output_path(app_config, target_output, target_path) else {
+        return info_err_res!("Failed to get enigma2 output path for target {}", target.name);
+    };
+    if let Err(err) = fs::create_dir_all(&output_path).await {
+        return info_err_res!("Failed to create enigma2 directory {}: {err}", output_path.display());
+    }
+
+    let base_url = app_config.get_user_server_info(&user).get_base_url();
+    let channels = collect_enigma2_channels(app_config, target, target_output, &user, &base_url, new_playlist);
+    let export = enigma2_build_export(enigma2_bouquet_prefix(target, target_output), channels);
+
+    let mut failed = vec![];
+    for bouquet in &export.bouquets {
+        if let Err(err) = write_enigma2_file(&output_path.join(&bouquet.filename), &enigma2_render_bouquet(bouquet)).await {
+            failed.push(err);
+        }
+    }
+    if let Err(err) =
+        write_enigma2_file(&output_path.join(ENIGMA2_BOUQUETS_INDEX), &enigma2_render_bouquets_index(&export)).await
+    {
+        failed.push(err);
+    }
+
+    if target_output.epgimport {
+        let (channels_url, xmltv_url) = enigma2_epgimport_urls(&base_url, &user, &export);
+        let sources = enigma2_render_epgimport_sources(&target.name, &channels_url, &xmltv_url);
+        if let Err(err) = write_enigma2_file(&output_path.join(export.epgimport_sources_filename()), &sources).await {
+            failed.push(err);
+        }
+        if let Err(err) = write_enigma2_file(
+            &output_path.join(export.epgimport_channels_filename()),
+            &enigma2_render_epgimport_channels(&export),
+        )
+        .await
+        {
+            failed.push(err);
+        }
+    }
+
+    if let Err(err) = cleanup_enigma2_bouquets(&output_path, &export).await {
+        failed.push(err);
+    }
+
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        info_err_res!("{}", failed.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn channel(group: &str, name: &str, virtual_id: u32, epg_id: Option<&str>) -> Enigma2Channel {
+        Enigma2Channel {
+            group: Arc::from(group),
+            name: Arc::from(name),
+            url: format!("http://localhost:8901/live/user/pass/{virtual_id}.ts"),
+            epg_channel_id: epg_id.map(Arc::from),
+            virtual_id,
+        }
+    }
+
+    #[test]
+    fn build_export_creates_one_bouquet_per_group() {
+        let export = enigma2_build_export(
+            String::from("tuliprox"),
+            vec![channel("News", "CNN", 1, None), channel("Sport", "ESPN", 2, None), channel("News", "BBC", 3, None)],
+        );
+        assert_eq!(export.bouquets.len(), 2);
+        assert_eq!(export.bouquets[0].filename, "userbouquet.tuliprox_news.tv");
+        assert_eq!(export.bouquets[0].channels.len(), 2);
+        assert_eq!(export.bouquets[1].filename, "userbouquet.tuliprox_sport.tv");
+    }
+
+    #[test]
+    fn build_export_makes_colliding_filenames_unique() {
+        let export = enigma2_build_export(
+            String::from("tp"),
+            vec![channel("Ä News", "A", 1, None), channel("A News!", "B", 2, None)],
+        );
+        assert_eq!(export.bouquets[0].filename, "userbouquet.tp_a_news.tv");
+        assert_eq!(export.bouquets[1].filename, "userbouquet.tp_a_news_2.tv");
+    }
+
+    #[test]
+    fn render_bouquet_encodes_url() {
+        let export = enigma2_build_export(String::from("tp"), vec![channel("News", "CNN", 26, None)]);
+        let content = enigma2_render_bouquet(&export.bouquets[0]);
+        assert_eq!(
+            content,
+            "#NAME News\n#SERVICE 4097:0:1:1A:0:0:0:0:0:0:http%3a//localhost%3a8901/live/user/pass/26.ts:CNN\n#DESCRIPTION CNN\n"
+        );
+    }
+
+    #[test]
+    fn render_index_and_epgimport_channels() {
+        let export = enigma2_build_export(
+            String::from("tp"),
+            vec![channel("News", "CNN", 1, Some("cnn.us")), channel("News", "Local", 2, None)],
+        );
+        assert_eq!(
+            enigma2_render_bouquets_index(&export),
+            "#NAME User - bouquets (TV)\n#SERVICE 1:7:1:0:0:0:0:0:0:0:FROM BOUQUET \"userbouquet.tp_news.tv\" ORDER BY bouquet\n"
+        );
+        let channels = enigma2_render_epgimport_channels(&export);
+        assert!(channels.contains("<channel id=\"cnn.us\">1:0:1:1:0:0:0:0:0:0:http%3a//</channel> <!-- CNN -->"));
+        assert!(!channels.contains("Local"));
+    }
+
+    #[test]
+    fn epgimport_urls_and_sources_are_encoded() {
+        let export = enigma2_build_export(String::from("tp"), vec![channel("News", "CNN", 1, Some("cnn.us"))]);
+        let mut user = ProxyUserCredentials::default();
+        user.username = "tv user".to_string();
+        user.password = "p&w/1".to_string();
+        let (channels_url, xmltv_url) = enigma2_epgimport_urls("http://host", &user, &export);
+        assert_eq!(channels_url, "http://host/enigma2/tv%20user/p%26w%2F1/tp.channels.xml");
+        assert_eq!(xmltv_url, "http://host/xmltv.php?username=tv%20user&password=p%26w%2F1");
+
+        let sources = enigma2_render_epgimport_sources("A & B", "http://host/?a=1&b=2", "http://host/x?c=3&d=4");
+        assert!(sources.contains(r#"sourcecatname="A &amp; B""#));
+        assert!(sources.contains(r#"channels="http://host/?a=1&amp;b=2""#));
+        assert!(sources.contains("<url>http://host/x?c=3&amp;d=4</url>"));
+    }
+}
@@ -107,7 +107,12 @@ pub async fn epg_write_for_target(cfg: &Config, target: &ConfigTarget, target_pa
                 .await
                 .map_err(|err| notify_err!("Failed to write epg for target {}: {err}", target_name_err))??;
             }
-            TargetOutput::Strm(_) | TargetOutput::HdHomeRun(_) => {}
+            TargetOutput::Strm(_)
+            | TargetOutput::HdHomeRun(_)
+            | TargetOutput::Enigma2(_)
+            | TargetOutput::Json(_)
+            | TargetOutput::Csv(_)
+            | TargetOutput::Multicast(_) => {}
         }
     }
     Ok(())
@@ -0,0 +1,333 @@
+use crate::model::{AppConfig, ConfigTarget, CsvTargetOutput, JsonTargetOutput, ProxyUserCredentials};
+use crate::repository::{m3u_rewrite_playlist_item, storage_const, xtream_user_stream_url};
+use crate::utils;
+use crate::utils::async_file_writer;
+use log::error;
+use shared::error::{info_err_res, notify_err, TuliproxError};
+use shared::foundation::{Filter, ValueProvider};
+use shared::model::{M3uPlaylistItem, PlaylistGroup, PlaylistItem, PlaylistItemHeader, PlaylistItemType};
+use shared::utils::Internable;
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::fs;
+use tokio::io::AsyncWriteExt;
+
+/// Renders playlist items for the `json` and `csv` target outputs.
+///
+/// The json export is an array of `PlaylistItemHeader` objects, the csv export
+/// a header row followed by one row per item with the configured columns.
+pub enum PlaylistExportFormat<'a> {
+    Json,
+    Csv(&'a CsvTargetOutput),
+}
+
+impl PlaylistExportFormat<'_> {
+    pub fn default_filename(&self) -> &'static str {
+        match self {
+            PlaylistExportFormat::Json => storage_const::FILE_EXPORT_JSON,
+            PlaylistExportFormat::Csv(_) => storage_const::FILE_EXPORT_CSV,
+        }
+    }
+
+    pub fn content_type(&self) -> String {
+        match self {
+            PlaylistExportFormat::Json => mime::APPLICATION_JSON.to_string(),
+            PlaylistExportFormat::Csv(_) => mime::TEXT_CSV_UTF_8.to_string(),
+        }
+    }
+
+    pub fn render_start(&self) -> String {
+        match self {
+            PlaylistExportFormat::Json => String::from("["),
+            PlaylistExportFormat::Csv(output) => {
+                let delimiter = output.get_delimiter();
+                let mut line = output
+                    .get_columns()
+                    .iter()
+                    .map(|column| csv_escape(column, delimiter))
+                    .collect::<Vec<_>>()
+                    .join(delimiter.encode_utf8(&mut [0; 4]));
+                line.push('\n');
+                line
+            }
+        }
+    }
+
+    /// Renders a single item, `first` is needed to separate json array entries.
+    pub fn render_item(&self, header: &PlaylistItemHeader, first: bool) -> Option<String> {
+        match self {
+            PlaylistExportFormat::Json => match serde_json::to_string(header) {
+                Ok(json) => Some(if first { format!("\n{json}") } else { format!(",\n{json}") }),
+                Err(err) => {
+                    error!("Failed to serialize playlist item {}: {err}", header.name);
+                    None
+                }
+            },
+            PlaylistExportFormat::Csv(output) => {
+                let delimiter = output.get_delimiter();
+                let mut line = output
+                    .get_columns()
+                    .iter()
+                    .map(|column| csv_escape(&csv_column_value(header, column), delimiter).into_owned())
+                    .collect::<Vec<_>>()
+                    .join(delimiter.encode_utf8(&mut [0; 4]));
+                line.push('\n');
+                Some(line)
+            }
+        }
+    }
+
+    pub fn render_end(&self, empty: bool) -> String {
+        match self {
+            PlaylistExportFormat::Json => String::from(if empty { "]\n" } else { "\n]\n" }),
+            PlaylistExportFormat::Csv(_) => String::new(),
+        }
+    }
+}
+
+fn csv_column_value(header: &PlaylistItemHeader, column: &str) -> String {
+    match column {
+        "id" => header.id.to_string(),
+        "virtual_id" => header.virtual_id.to_string(),
+        "chno" => header.chno.to_string(),
+        "name" => header.name.to_string(),
+        "title" => header.title.to_string(),
+        "group" => header.group.to_string(),
+        "parent_code" => header.parent_code.to_string(),
+        "audio_track" => header.audio_track.to_string(),
+        "time_shift" => header.time_shift.to_string(),
+        "rec" => header.rec.to_string(),
+        "epg_channel_id" => header.epg_channel_id.as_deref().unwrap_or_default().to_string(),
+        "logo" => header.logo.to_string(),
+        "logo_small" => header.logo_small.to_string(),
+        "url" => header.url.to_string(),
+        "input_name" => header.input_name.to_string(),
+        "category_id" => header.category_id.to_string(),
+        "item_type" => header.item_type.to_string(),
+        "xtream_cluster" => header.xtream_cluster.to_string(),
+        _ => String::new(),
+    }
+}
+
+/// Quotes a csv field if it contains the delimiter, a quote or a line break.
+fn csv_escape(value: &str, delimiter: char) -> Cow<'_, str> {
+    if value.contains([delimiter, '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", value.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(value)
+    }
+}
+
+pub fn export_filter_item(filter: Option<&Filter>, pli: &PlaylistItem) -> bool {
+    filter.is_none_or(|filter| filter.filter(&ValueProvider { pli, match_as_ascii: false }))
+}
+
+/// Item as the m3u api delivers it, with the rewritten stream and logo urls of the user.
+pub fn export_m3u_item(m3u_item: &M3uPlaylistItem) -> PlaylistItem {
+    let mut pli = PlaylistItem::from(m3u_item);
+    if !m3u_item.t_stream_url.is_empty() {
+        pli.header.url = Arc::clone(&m3u_item.t_stream_url);
+    }
+    if let Some(resource_url) = m3u_item.t_resource_url.as_deref() {
+        pli.header.logo = resource_url.intern();
+    }
+    pli
+}
+
+/// Replaces the provider url of an item with the xtream stream url of the user.
+pub fn export_xtream_item(
+    app_config: &AppConfig,
+    target: &ConfigTarget,
+    user: &ProxyUserCredentials,
+    base_url: &str,
+    mut pli: PlaylistItem,
+) -> PlaylistItem {
+    let header = &pli.header;
+    let container_extension = header.get_container_extension();
+    let url = xtream_user_stream_url(
+        app_config,
+        target,
+        user,
+        base_url,
+        header.xtream_cluster,
+        header.item_type,
+        header.virtual_id,
+        &header.input_name,
+        &header.url,
+        container_extension.as_deref(),
+    );
+    pli.header.url = url.intern();
+    pli
+}
+
+/// Provider urls carry the provider credentials, the persisted export gets the urls of the configured user.
+struct ExportUserUrls<'a> {
+    app_config: &'a AppConfig,
+    target: &'a ConfigTarget,
+    user: ProxyUserCredentials,
+    base_url: String,
+}
+
+impl<'a> ExportUserUrls<'a> {
+    fn new(app_config: &'a AppConfig, target: &'a ConfigTarget, username: &str) -> Result<Self, TuliproxError> {
+        let Some(user) = app_config.get_user_credentials(username) else {
+            return info_err_res!("User {username} for export output of target {} not found", target.name);
+        };
+        let base_url = app_config.get_user_server_info(&user).get_base_url();
+        Ok(Self { app_config, target, user, base_url })
+    }
+
+    fn rewrite(&self, pli: &PlaylistItem) -> PlaylistItem {
+        match m3u_rewrite_playlist_item(self.app_config, self.target, &self.user, M3uPlaylistItem::from(pli)) {
+            Some(m3u_item) => export_m3u_item(&m3u_item),
+            None => export_xtream_item(self.app_config, self.target, &self.user, &self.base_url, pli.clone()),
+        }
+    }
+}
+
+fn get_export_file_path(
+    app_config: &AppConfig,
+    filename: Option<&String>,
+    target_path: &Path,
+    default_filename: &str,
+) -> Option<PathBuf> {
+    match filename {
+        Some(filename) => {
+            let config = app_config.config.load();
+            utils::get_file_path(&config.storage_dir, Some(PathBuf::from(filename)))
+        }
+        None => Some(target_path.join(default_filename)),
+    }
+}
+
+async fn write_export_file(
+    path: &Path,
+    format: &PlaylistExportFormat<'_>,
+    user_urls: &ExportUserUrls<'_>,
+    new_playlist: &[PlaylistGroup],
+) -> Result<(), TuliproxError> {
+    let file_name = path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+    let tmp_path = path.with_file_name(format!("{file_name}.tmp"));
+    let file = fs::File::create(&tmp_path)
+        .await
+        .map_err(|err| notify_err!("Can't write playlist export {} - {err}", tmp_path.display()))?;
+    let mut writer = async_file_writer(file);
+
+    let write_err = |err: std::io::Error| notify_err!("Failed to write playlist export {} - {err}", tmp_path.display());
+    writer.write_all(format.render_start().as_bytes()).await.map_err(write_err)?;
+    let mut first = true;
+    for pli in new_playlist.iter().flat_map(|pg| &pg.channels) {
+        if matches!(pli.header.item_type, PlaylistItemType::SeriesInfo | PlaylistItemType::LocalSeriesInfo) {
+            continue;
+        }
+        if let Some(content) = format.render_item(&user_urls.rewrite(pli).header, first) {
+            writer.write_all(content.as_bytes()).await.map_err(write_err)?;
+            first = false;
+        }
+    }
+    writer.write_all(format.render_end(first).as_bytes()).await.map_err(write_err)?;
+    writer.flush().await.map_err(write_err)?;
+
+    fs::rename(&tmp_path, path).await.map_err(|err| notify_err!("Failed to replace {} - {err}", path.display()))
+}
+
+pub async fn json_write_playlist(
+    app_config: &AppConfig,
+    target: &ConfigTarget,
+    target_output: &JsonTargetOutput,
+    target_path: &Path,
+    new_playlist: &[PlaylistGroup],
+) -> Result<(), TuliproxError> {
+    if new_playlist.is_empty() {
+        return Ok(());
+    }
+    let user_urls = ExportUserUrls::new(app_config, target, &target_output.username)?;
+    let format = PlaylistExportFormat::Json;
+    let Some(path) =
+        get_export_file_path(app_config, target_output.filename.as_ref(), target_path, format.default_filename())
+    else {
+        return Err(notify_err!("Failed to get json output path for target {}", target.name));
+    };
+    write_export_file(&path, &format, &user_urls, new_playlist).await
+}
+
+pub async fn csv_write_playlist(
+    app_config: &AppConfig,
+    target: &ConfigTarget,
+    target_output: &CsvTargetOutput,
+    target_path: &Path,
+    new_playlist: &[PlaylistGroup],
+) -> Result<(), TuliproxError> {
+    if new_playlist.is_empty() {
+        return Ok(());
+    }
+    let user_urls = ExportUserUrls::new(app_config, target, &target_output.username)?;
+    let format = PlaylistExportFormat::Csv(target_output);
+    let Some(path) =
+        get_export_file_path(app_config, target_output.filename.as_ref(), target_path, format.default_filename())
+    else {
+        return Err(notify_err!("Failed to get csv output path for target {}", target.name));
+    };
+    write_export_file(&path, &format, &user_urls, new_playlist).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{csv_escape, PlaylistExportFormat};
+    use crate::model::CsvTargetOutput;
+    use shared::model::PlaylistItemHeader;
+    use shared::utils::Internable;
+
+    fn header() -> PlaylistItemHeader {
+        PlaylistItemHeader {
+            name: "News, \"Live\"".intern(),
+            group: "News".intern(),
+            url: "http://localhost/live/1.ts".intern(),
+            epg_channel_id: Some("news.de".intern()),
+            virtual_id: 7,
+            chno: 12,
+            ..PlaylistItemHeader::default()
+        }
+    }
+
+    #[test]
+    fn csv_escape_quotes_special_fields() {
+        assert_eq!(csv_escape("plain", ','), "plain");
+        assert_eq!(csv_escape("a,b", ','), "\"a,b\"");
+        assert_eq!(csv_escape("a,b", ';'), "a,b");
+        assert_eq!(csv_escape("say \"hi\"", ','), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_escape("two\nlines", ','), "\"two\nlines\"");
+    }
+
+    #[test]
+    fn csv_renders_configured_columns() {
+        let output = CsvTargetOutput {
+            username: "test".to_string(),
+            filename: None,
+            columns: Some(vec!["chno".to_string(), "name".to_string(), "epg_channel_id".to_string()]),
+            delimiter: Some(";".to_string()),
+            filter: None,
+        };
+        let format = PlaylistExportFormat::Csv(&output);
+        assert_eq!(format.render_start(), "chno;name;epg_channel_id\n");
+        assert_eq!(format.render_item(&header(), true).unwrap(), "12;\"News, \"\"Live\"\"\";news.de\n");
+    }
+
+    #[test]
+    fn json_renders_array() {
+        let format = PlaylistExportFormat::Json;
+        let mut content = format.render_start();
+        content.push_str(&format.render_item(&header(), true).unwrap());
+        content.push_str(&format.render_item(&header(), false).unwrap());
+        content.push_str(&format.render_end(false));
+        let items: Vec<serde_json::Value> = serde_json::from_str(&content).unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0]["chno"], 12);
+        assert_eq!(items[0]["virtual_id"], 7);
+        assert_eq!(items[0]["epg_channel_id"], "news.de");
+
+        let empty = format!("{}{}", format.render_start(), format.render_end(true));
+        assert!(serde_json::from_str::<Vec<serde_json::Value>>(&empty).unwrap().is_empty());
+    }
+}
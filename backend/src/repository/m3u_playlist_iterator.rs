@@ -1,9 +1,12 @@
 use shared::error::info_err;
 use shared::error::TuliproxError;
-use crate::model::{AppConfig, ProxyUserCredentials};
+use crate::model::{is_ingest_url, AppConfig, M3uTargetOutput, ProxyUserCredentials};
 use crate::model::ConfigTarget;
+use crate::api::model::ChannelHealthFilter;
 use shared::create_bitset;
-use shared::model::{ConfigTargetOptions, M3uPlaylistItem, PlaylistItemType, ProxyType, TargetType, XtreamCluster};
+use shared::model::{
+    ConfigTargetOptions, M3uPlaylistItem, PlaylistItem, PlaylistItemType, ProxyType, TargetType, XtreamCluster,
+};
 use crate::repository::{LockedReceiverStream, open_playlist_reader};
 use crate::repository::m3u_get_file_path_for_db;
 use crate::repository::{ensure_target_storage_path, get_file_path_for_db_index};
@@ -99,7 +102,8 @@ fn apply_rewrite(
         || target_options
             .and_then(|o| o.force_redirect.as_ref())
             .is_some_and(|f| f.has_cluster(m3u_pli.item_type));
-    let should_rewrite_urls = if is_redirect {
+    // ingest channels have no provider url a client could be redirected to
+    let should_rewrite_urls = if is_redirect && !is_ingest_url(&m3u_pli.url) {
         flags.contains(M3uPlaylistIteratorFlags::MaskRedirectUrl)
     } else {
         true
@@ -147,11 +151,47 @@ fn apply_rewrite(
 }
 
 
+fn m3u_rewrite_flags(cfg: &AppConfig, m3u_output: &M3uTargetOutput) -> M3uPlaylistIteratorFlagsSet {
+    let mut flags = M3uPlaylistIteratorFlagsSet::new();
+    if m3u_output.include_type_in_url {
+        flags.set(M3uPlaylistIteratorFlags::IncludeTypeInUrl);
+    }
+    if m3u_output.mask_redirect_url {
+        flags.set(M3uPlaylistIteratorFlags::MaskRedirectUrl);
+    }
+    if cfg.is_reverse_proxy_resource_rewrite_enabled() {
+        flags.set(M3uPlaylistIteratorFlags::RewriteResource);
+    }
+    flags
+}
+
+/// Rewrites the urls of an item like the m3u playlist of the user does.
+/// Returns `None` when the target has no m3u output.
+pub fn m3u_rewrite_playlist_item(
+    cfg: &AppConfig,
+    target: &ConfigTarget,
+    user: &ProxyUserCredentials,
+    item: M3uPlaylistItem,
+) -> Option<M3uPlaylistItem> {
+    let m3u_output = target.get_m3u_output()?;
+    let base_url = cfg.get_user_server_info(user).get_base_url();
+    Some(apply_rewrite(
+        item,
+        &base_url,
+        &user.username,
+        &user.password,
+        target.options.as_ref(),
+        m3u_rewrite_flags(cfg, m3u_output),
+        user.proxy,
+    ))
+}
+
 impl M3uPlaylistIterator {
     pub async fn new(
         cfg: &AppConfig,
         target: &ConfigTarget,
         user: &ProxyUserCredentials,
+        channel_health: Option<ChannelHealthFilter>,
     ) -> Result<Self, TuliproxError> {
 
         // TODO use playlist memory cache, but be aware of sorting !
@@ -165,16 +205,8 @@ impl M3uPlaylistIterator {
         let bg_lock = cfg.file_locks.read_lock(&m3u_path).await;
 
         let filter = user_get_bouquet_filter(&config, &user.username, None, TargetType::M3u, XtreamCluster::Live).await;
-        let mut flags = M3uPlaylistIteratorFlagsSet::new();
-        if m3u_output.include_type_in_url {
-            flags.set(M3uPlaylistIteratorFlags::IncludeTypeInUrl);
-        }
-        if m3u_output.mask_redirect_url {
-            flags.set(M3uPlaylistIteratorFlags::MaskRedirectUrl);
-        }
-        if cfg.is_reverse_proxy_resource_rewrite_enabled() {
-            flags.set(M3uPlaylistIteratorFlags::RewriteResource);
-        }
+        let parental = cfg.get_user_parental_restriction(user);
+        let flags = m3u_rewrite_flags(cfg, m3u_output);
 
         let server_info = cfg.get_user_server_info(user);
         let base_url = server_info.get_base_url();
@@ -205,7 +237,7 @@ impl M3uPlaylistIterator {
 
             let mut pending: Option<M3uPlaylistItem> = None;
             for entry in reader {
-                let item = match entry {
+                let mut item = match entry {
                     Ok((_, item)) => item,
                     Err(err) => {
                         error!("Iterator error: {err}");
@@ -219,6 +251,16 @@ impl M3uPlaylistIterator {
                     }
                 }
 
+                if parental.as_ref().is_some_and(|profile| profile.is_restricted(&PlaylistItem::from(&item))) {
+                    continue;
+                }
+
+                if let Some(health) = channel_health.as_ref().filter(|health| health.is_dead(item.virtual_id)) {
+                    // a dead channel is hidden or moved to the "unavailable" group
+                    let Some(group) = health.unavailable_group() else { continue };
+                    item.group = group.into();
+                }
+
                 let item = apply_rewrite(
                     item,
                     &base_url,
@@ -275,9 +317,10 @@ impl M3uPlaylistM3uTextIterator {
         cfg: &AppConfig,
         target: &ConfigTarget,
         user: &ProxyUserCredentials,
+        channel_health: Option<ChannelHealthFilter>,
     ) -> Result<Self, TuliproxError> {
         Ok(Self {
-            inner: M3uPlaylistIterator::new(cfg, target, user).await?,
+            inner: M3uPlaylistIterator::new(cfg, target, user, channel_health).await?,
             started: false,
             target_options: target.options.clone(),
         })
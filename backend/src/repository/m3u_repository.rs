@@ -1,5 +1,5 @@
 use crate::{
-    api::model::AppState,
+    api::model::{AppState, ChannelHealthFilter},
     model::{AppConfig, Config, ConfigInput, ConfigTarget, M3uTargetOutput, ProxyUserCredentials},
     repository::{
         bplustree::{BPlusTree, BPlusTreeQuery},
@@ -316,8 +316,9 @@ pub async fn m3u_load_rewrite_playlist(
     cfg: &AppConfig,
     target: &ConfigTarget,
     user: &ProxyUserCredentials,
+    channel_health: Option<ChannelHealthFilter>,
 ) -> Result<M3uPlaylistM3uTextIterator, TuliproxError> {
-    M3uPlaylistM3uTextIterator::new(cfg, target, user).await
+    M3uPlaylistM3uTextIterator::new(cfg, target, user, channel_health).await
 }
 
 pub async fn m3u_get_item_for_stream_id(
@@ -7,6 +7,9 @@ mod m3u_repository;
 mod xtream_repository;
 mod epg_repository;
 mod strm_repository;
+mod strm_nfo;
+mod enigma2_repository;
+mod export_repository;
 mod m3u_playlist_iterator;
 mod xtream_playlist_iterator;
 mod user_repository;
@@ -20,6 +23,8 @@ mod playlist_stream;
 mod provider_dns_repository;
 mod stream_history;
 mod qos_snapshot_repository;
+mod channel_alternate_repository;
+mod multicast_repository;
 
 pub use storage::*;
 pub use target_id_mapping::*;
@@ -30,6 +35,8 @@ pub use m3u_repository::*;
 pub use xtream_repository::*;
 pub use epg_repository::*;
 pub use strm_repository::*;
+pub use enigma2_repository::*;
+pub use export_repository::*;
 pub use m3u_playlist_iterator::*;
 pub use xtream_playlist_iterator::*;
 pub use user_repository::*;
@@ -42,6 +49,8 @@ pub use playlist_stream::*;
 pub use provider_dns_repository::*;
 pub use stream_history::*;
 pub use qos_snapshot_repository::*;
+pub use channel_alternate_repository::*;
+pub use multicast_repository::*;
 
 #[cfg(test)]
 mod bplustree_stress;
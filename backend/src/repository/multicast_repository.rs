@@ -0,0 +1,197 @@
+use crate::model::{AppConfig, ConfigTarget, MulticastChannelConfig, MulticastTargetOutput};
+use crate::repository::{iter_raw_m3u_target_playlist, iter_raw_xtream_target_playlist};
+use futures::StreamExt;
+use log::warn;
+use shared::model::{PlaylistItemType, TargetType, VirtualId, XtreamCluster};
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::Arc;
+
+/// A configured multicast channel resolved to a live channel of the target playlist.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MulticastChannel {
+    pub config: MulticastChannelConfig,
+    pub virtual_id: VirtualId,
+    pub title: Arc<str>,
+    pub group: Arc<str>,
+    pub logo: Arc<str>,
+    pub epg_channel_id: Option<Arc<str>>,
+}
+
+struct LiveChannelCandidate {
+    virtual_id: VirtualId,
+    name: Arc<str>,
+    title: Arc<str>,
+    group: Arc<str>,
+    logo: Arc<str>,
+    epg_channel_id: Option<Arc<str>>,
+}
+
+fn normalize_channel_name(name: &str) -> String { name.trim().to_lowercase() }
+
+/// Maps the configured channels to live channels by title or name, the first match wins.
+fn match_multicast_channels(
+    output: &MulticastTargetOutput,
+    candidates: impl IntoIterator<Item = LiveChannelCandidate>,
+) -> Vec<MulticastChannel> {
+    let mut pending: HashMap<String, &MulticastChannelConfig> =
+        output.channels.iter().map(|channel| (normalize_channel_name(&channel.name), channel)).collect();
+    let mut resolved = Vec::with_capacity(pending.len());
+    for candidate in candidates {
+        if pending.is_empty() {
+            break;
+        }
+        let config = pending
+            .remove(&normalize_channel_name(&candidate.title))
+            .or_else(|| pending.remove(&normalize_channel_name(&candidate.name)));
+        if let Some(config) = config {
+            resolved.push(MulticastChannel {
+                config: config.clone(),
+                virtual_id: candidate.virtual_id,
+                title: candidate.title,
+                group: candidate.group,
+                logo: candidate.logo,
+                epg_channel_id: candidate.epg_channel_id,
+            });
+        }
+    }
+    for config in pending.values() {
+        warn!("Multicast channel {} not found in the live channels of the target", config.name);
+    }
+    resolved.sort_by_key(|channel| channel.config.address);
+    resolved
+}
+
+/// Resolves the multicast channels of a target from its stored playlist. The xtream playlist is preferred,
+/// the channels are streamed with the same virtual ids as the xtream or m3u api delivers.
+pub async fn multicast_resolve_channels(
+    app_config: &AppConfig,
+    target: &ConfigTarget,
+    output: &MulticastTargetOutput,
+) -> Vec<MulticastChannel> {
+    let mut candidates = Vec::new();
+    if target.has_output(TargetType::Xtream) {
+        if let Some(mut iter) = iter_raw_xtream_target_playlist(app_config, target, XtreamCluster::Live).await {
+            while let Some(item) = iter.next().await {
+                if item.item_type == PlaylistItemType::Live {
+                    candidates.push(LiveChannelCandidate {
+                        virtual_id: item.virtual_id,
+                        name: item.name,
+                        title: item.title,
+                        group: item.group,
+                        logo: item.logo,
+                        epg_channel_id: item.epg_channel_id,
+                    });
+                }
+            }
+        }
+    } else if let Some(mut iter) = iter_raw_m3u_target_playlist(app_config, target, Some(XtreamCluster::Live)).await {
+        while let Some(item) = iter.next().await {
+            match item {
+                Ok(item) if item.item_type == PlaylistItemType::Live => candidates.push(LiveChannelCandidate {
+                    virtual_id: item.virtual_id,
+                    name: item.name,
+                    title: item.title,
+                    group: item.group,
+                    logo: item.logo,
+                    epg_channel_id: item.epg_channel_id,
+                }),
+                Ok(_) => {}
+                Err(err) => warn!("Failed to read playlist of target {} for multicast: {err}", target.name),
+            }
+        }
+    }
+    match_multicast_channels(output, candidates)
+}
+
+fn m3u_attribute(value: &str) -> String { value.replace('"', "'") }
+
+/// Renders the m3u playlist the set-top boxes use to tune into the multicast groups.
+pub fn multicast_render_m3u(channels: &[MulticastChannel]) -> String {
+    let mut content = String::from("#EXTM3U\n");
+    for channel in channels {
+        let scheme = if channel.config.rtp { "rtp" } else { "udp" };
+        let _ = write!(
+            content,
+            "#EXTINF:-1 tvg-id=\"{}\" tvg-name=\"{}\" tvg-logo=\"{}\" group-title=\"{}\",{}\n{scheme}://@{}\n",
+            m3u_attribute(channel.epg_channel_id.as_deref().unwrap_or_default()),
+            m3u_attribute(&channel.title),
+            m3u_attribute(&channel.logo),
+            m3u_attribute(&channel.group),
+            channel.title,
+            channel.config.address,
+        );
+    }
+    content
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{match_multicast_channels, multicast_render_m3u, LiveChannelCandidate};
+    use crate::model::MulticastTargetOutput;
+    use shared::model::{MulticastChannelDto, MulticastTargetOutputDto};
+    use shared::utils::Internable;
+
+    fn candidate(virtual_id: u32, name: &str, title: &str) -> LiveChannelCandidate {
+        LiveChannelCandidate {
+            virtual_id,
+            name: name.intern(),
+            title: title.intern(),
+            group: "News".intern(),
+            logo: "".intern(),
+            epg_channel_id: Some(format!("{virtual_id}.de").intern()),
+        }
+    }
+
+    #[test]
+    fn multicast_channels_are_matched_by_title_or_name() {
+        let output = MulticastTargetOutput::from(&MulticastTargetOutputDto {
+            rtp: true,
+            channels: vec![
+                MulticastChannelDto {
+                    name: "das erste".to_string(),
+                    address: "239.1.1.2:5000".to_string(),
+                    rtp: Some(false),
+                    always_on: None,
+                },
+                MulticastChannelDto {
+                    name: "ZDF".to_string(),
+                    address: "239.1.1.1:5000".to_string(),
+                    rtp: None,
+                    always_on: Some(true),
+                },
+                MulticastChannelDto {
+                    name: "Unknown".to_string(),
+                    address: "239.1.1.3:5000".to_string(),
+                    rtp: None,
+                    always_on: None,
+                },
+            ],
+            ..MulticastTargetOutputDto::default()
+        });
+        let candidates = vec![
+            candidate(1, "DE: ZDF", "ZDF"),
+            candidate(2, "Das Erste", "DE: Das Erste"),
+            candidate(3, "ZDF", "ZDF"),
+        ];
+        let channels = match_multicast_channels(&output, candidates);
+        assert_eq!(channels.len(), 2);
+        assert_eq!(channels[0].virtual_id, 1);
+        assert!(channels[0].config.rtp && channels[0].config.always_on);
+        assert_eq!(channels[1].virtual_id, 2);
+        assert!(!channels[1].config.rtp && !channels[1].config.always_on);
+
+        let m3u = multicast_render_m3u(&channels);
+        let lines: Vec<&str> = m3u.lines().collect();
+        assert_eq!(
+            lines,
+            vec![
+                "#EXTM3U",
+                r#"#EXTINF:-1 tvg-id="1.de" tvg-name="ZDF" tvg-logo="" group-title="News",ZDF"#,
+                "rtp://@239.1.1.1:5000",
+                r#"#EXTINF:-1 tvg-id="2.de" tvg-name="DE: Das Erste" tvg-logo="" group-title="News",DE: Das Erste"#,
+                "udp://@239.1.1.2:5000",
+            ]
+        );
+    }
+}
@@ -3,20 +3,20 @@ use crate::model::Epg;
 use crate::model::{AppConfig, ConfigInput, ConfigTarget, TargetOutput};
 use crate::processing::processor::{apply_filter_to_playlist, PlaylistProcessingContext};
 use crate::repository::epg_write_for_target;
-use crate::repository::write_strm_playlist;
+use crate::repository::{csv_write_playlist, enigma2_write_playlist, json_write_playlist, write_strm_playlist};
 use crate::repository::FILE_SUFFIX_DB;
 use crate::repository::{ensure_target_storage_path, get_input_storage_path, get_target_id_mapping_file, get_target_storage_path};
 use crate::repository::{load_input_local_library_playlist, persist_input_library_playlist};
 use crate::repository::{load_input_m3u_playlist, m3u_get_file_path_for_db, m3u_write_playlist, persist_input_m3u_playlist};
 use crate::repository::{load_input_xtream_playlist, persist_input_xtream_playlist, xtream_get_file_path, xtream_get_storage_path, xtream_write_playlist};
-use crate::repository::BPlusTree;
+use crate::repository::{get_uuid_index_path, BPlusTree, BPlusTreeQuery};
 use crate::repository::{LocalLibraryDiskPlaylistSource, M3uDiskPlaylistSource, MemoryPlaylistSource, PlaylistSource, XtreamDiskPlaylistSource};
 use crate::repository::{TargetIdMapping, VirtualIdRecord};
 use crate::utils;
 use log::{info, warn};
 use shared::error::{info_err, TuliproxError};
 use shared::model::xtream_const::XTREAM_CLUSTER;
-use shared::model::{InputType, M3uPlaylistItem, PlaylistEntry, PlaylistGroup, PlaylistItem, PlaylistItemHeader, PlaylistItemType, StreamProperties, VirtualId, XtreamCluster, XtreamPlaylistItem};
+use shared::model::{InputType, M3uPlaylistItem, PlaylistEntry, PlaylistGroup, PlaylistItem, PlaylistItemHeader, PlaylistItemType, StreamProperties, UUIDType, VirtualId, XtreamCluster, XtreamPlaylistItem};
 use shared::utils::{is_dash_url, is_hls_url, Internable};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
@@ -33,7 +33,7 @@ pub struct ProviderEpisodeKey {
 }
 
 #[allow(clippy::too_many_lines)]
-pub async fn persist_playlist(app_config: &Arc<AppConfig>, playlist: &mut [PlaylistGroup], epg: Option<&Epg>,
+pub async fn persist_playlist(app_config: &Arc<AppConfig>, client: &reqwest::Client, playlist: &mut [PlaylistGroup], epg: Option<&Epg>,
                               target: &ConfigTarget, playlist_state: Option<&Arc<PlaylistStorageState>>) -> Result<(), Vec<TuliproxError>> {
     let mut errors = vec![];
     let config = &app_config.config.load();
@@ -106,7 +106,10 @@ pub async fn persist_playlist(app_config: &Arc<AppConfig>, playlist: &mut [Playl
             TargetOutput::Xtream(out) => out.filter.as_ref().and_then(|flt| apply_filter_to_playlist(playlist, flt)),
             TargetOutput::M3u(out) => out.filter.as_ref().and_then(|flt| apply_filter_to_playlist(playlist, flt)),
             TargetOutput::Strm(out) => out.filter.as_ref().and_then(|flt| apply_filter_to_playlist(playlist, flt)),
-            TargetOutput::HdHomeRun(_) => None,
+            TargetOutput::Enigma2(out) => out.filter.as_ref().and_then(|flt| apply_filter_to_playlist(playlist, flt)),
+            TargetOutput::Json(out) => out.filter.as_ref().and_then(|flt| apply_filter_to_playlist(playlist, flt)),
+            TargetOutput::Csv(out) => out.filter.as_ref().and_then(|flt| apply_filter_to_playlist(playlist, flt)),
+            TargetOutput::HdHomeRun(_) | TargetOutput::Multicast(_) => None,
         };
 
         let pl: &mut [PlaylistGroup] = if let Some(filtered_playlist) = filtered.as_mut() {
@@ -118,8 +121,11 @@ pub async fn persist_playlist(app_config: &Arc<AppConfig>, playlist: &mut [Playl
         let result = match output {
             TargetOutput::Xtream(_xtream_output) => xtream_write_playlist(app_config, target, pl).await,
             TargetOutput::M3u(m3u_output) => m3u_write_playlist(app_config, target, m3u_output, &target_path, pl).await,
-            TargetOutput::Strm(strm_output) => write_strm_playlist(app_config, target, strm_output, pl).await,
-            TargetOutput::HdHomeRun(_hdhomerun_output) => Ok(()),
+            TargetOutput::Strm(strm_output) => write_strm_playlist(app_config, client, target, strm_output, pl).await,
+            TargetOutput::Enigma2(enigma2_output) => enigma2_write_playlist(app_config, target, enigma2_output, &target_path, pl).await,
+            TargetOutput::Json(json_output) => json_write_playlist(app_config, target, json_output, &target_path, pl).await,
+            TargetOutput::Csv(csv_output) => csv_write_playlist(app_config, target, csv_output, &target_path, pl).await,
+            TargetOutput::HdHomeRun(_) | TargetOutput::Multicast(_) => Ok(()),
         };
 
         match result {
@@ -320,6 +326,22 @@ pub async fn get_target_id_mapping(cfg: &AppConfig, target_path: &Path, use_memo
     Ok((mapping, file_lock))
 }
 
+/// Returns true when the id mapping of the target contains the uuid, the uuid index is queried on disk.
+pub async fn target_id_mapping_contains_uuid(app_config: &AppConfig, target: &ConfigTarget, uuid: UUIDType) -> bool {
+    let Some(target_path) = get_target_storage_path(&app_config.config.load(), target.name.as_str()) else {
+        return false;
+    };
+    let target_id_mapping_file = get_target_id_mapping_file(&target_path);
+    let file_lock = app_config.file_locks.read_lock(&target_id_mapping_file).await;
+    let uuid_index_file = get_uuid_index_path(&target_id_mapping_file);
+    tokio::task::spawn_blocking(move || {
+        let _guard = file_lock;
+        BPlusTreeQuery::<UUIDType, u32>::try_new(&uuid_index_file)
+            .is_ok_and(|mut uuid_index| matches!(uuid_index.query(&uuid), Ok(Some(_))))
+    })
+        .await
+        .unwrap_or(false)
+}
 
 async fn load_target_id_mapping_as_tree(app_config: &AppConfig, target_path: &Path, target: &ConfigTarget) -> Result<BPlusTree<u32, VirtualIdRecord>, TuliproxError> {
     let target_id_mapping_file = get_target_id_mapping_file(target_path);
@@ -442,7 +464,7 @@ pub async fn persist_input_playlist(app_config: &Arc<AppConfig>, input: &ConfigI
             persist_input_xtream_playlist(app_config, &storage_path, playlist).await
         }
 
-        InputType::M3u | InputType::M3uBatch => {
+        InputType::M3u | InputType::M3uBatch | InputType::Ingest => {
             // Persist M3U
             let file_path = get_input_m3u_playlist_file_path(&storage_path, &input.name);
             if let Err(err) = persist_input_m3u_playlist(app_config, &file_path, &playlist).await {
@@ -483,7 +505,7 @@ pub async fn load_input_playlist(ctx: &PlaylistProcessingContext, input: &Config
                 Ok(Box::new(MemoryPlaylistSource::new(groups)))
             }
         }
-        InputType::M3u | InputType::M3uBatch => {
+        InputType::M3u | InputType::M3uBatch | InputType::Ingest => {
             // Load M3U
             let file_path = get_input_m3u_playlist_file_path(&storage_path, &input.name);
             if disk_based_processing && file_path.exists() {
@@ -559,6 +581,7 @@ mod tests {
                 rating: None,
                 video: None,
                 audio: None,
+                sidecars: None,
             })
             .collect();
 
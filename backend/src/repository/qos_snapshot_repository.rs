@@ -29,6 +29,17 @@ pub struct QosSnapshotWindow {
     pub sample_size: u64,
     pub score: u8,
     pub confidence: u8,
+    #[serde(default)]
+    pub provider_stall_count: u64,
+    #[serde(default)]
+    pub ts_cc_error_count: u64,
+    #[serde(default)]
+    pub ts_pcr_gap_count: u64,
+    /// Share of MPEG-TS null packets in all inspected packets, in per mille.
+    #[serde(default)]
+    pub ts_null_packet_permille: Option<u64>,
+    #[serde(default)]
+    pub avg_video_bitrate_kbps: Option<u64>,
 }
 
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
@@ -49,6 +60,20 @@ pub struct QosSnapshotDailyBucket {
     pub total_provider_reconnect_samples: u64,
     pub last_success_ts: Option<u64>,
     pub last_failure_ts: Option<u64>,
+    #[serde(default)]
+    pub provider_stall_count: u64,
+    #[serde(default)]
+    pub ts_packet_count: u64,
+    #[serde(default)]
+    pub ts_null_packet_count: u64,
+    #[serde(default)]
+    pub ts_cc_error_count: u64,
+    #[serde(default)]
+    pub ts_pcr_gap_count: u64,
+    #[serde(default)]
+    pub total_video_bitrate_kbps: u64,
+    #[serde(default)]
+    pub total_video_bitrate_samples: u64,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
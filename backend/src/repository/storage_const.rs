@@ -2,9 +2,12 @@ pub const FILE_EPG: &str = "epg.xml";
 pub(in crate::repository) const FILE_SUFFIX_DB: &str = "db";
 pub(in crate::repository) const FILE_SUFFIX_INDEX: &str = "idx";
 pub(in crate::repository) const FILE_ID_MAPPING: &str = "id_mapping.db";
+pub(in crate::repository) const FILE_CHANNEL_ALTERNATES: &str = "channel_alternates.db";
 pub(in crate::repository) const FILE_STRM: &str = "strm";
 pub(in crate::repository) const FILE_M3U: &str = "m3u";
 pub(in crate::repository) const PATH_M3U: &str = "m3u";
+pub(in crate::repository) const FILE_EXPORT_JSON: &str = "playlist.json";
+pub(in crate::repository) const FILE_EXPORT_CSV: &str = "playlist.csv";
 
 pub const M3U_STREAM_PATH: &str = "m3u-stream";
 pub const M3U_RESOURCE_PATH: &str = "resource/m3u";
@@ -234,6 +234,7 @@ mod tests {
             provider_name: Some("acme".to_string()),
             provider_username: None,
             input_name: Some("input".to_string()),
+            alternate_input: None,
             virtual_id: Some(1),
             item_type: Some("live".to_string()),
             title: Some("Test".to_string()),
@@ -259,6 +260,8 @@ mod tests {
             bytes_sent: None,
             first_byte_latency_ms: None,
             provider_reconnect_count: None,
+            provider_stall_count: None,
+            ts_quality: None,
             failure_stage: None,
             provider_http_status: None,
             provider_error_class: None,
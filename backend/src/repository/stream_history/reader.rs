@@ -285,6 +285,7 @@ mod tests {
             provider_name: None,
             provider_username: None,
             input_name: Some("input".to_string()),
+            alternate_input: None,
             virtual_id: None,
             item_type: None,
             title: None,
@@ -310,6 +311,8 @@ mod tests {
             bytes_sent: None,
             first_byte_latency_ms: None,
             provider_reconnect_count: None,
+            provider_stall_count: None,
+            ts_quality: None,
             failure_stage: None,
             provider_http_status: None,
             provider_error_class: None,
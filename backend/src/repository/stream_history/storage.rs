@@ -48,6 +48,12 @@ pub enum ConnectFailureReason {
     Preempted,
     SessionExpired,
     Provisioning,
+    UserQuotaExhausted,
+    OutsideAccessSchedule,
+    ParentalRestricted,
+    DeviceNotApproved,
+    DeviceLimitReached,
+    CountryDenied,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -81,6 +87,7 @@ pub enum DisconnectReason {
     SessionExpired,
     UserConnectionsExhausted,
     ProviderConnectionsExhausted,
+    UserQuotaExhausted,
 }
 
 /// Serialized as `MessagePack` named (map encoding) for schema evolution safety.
@@ -152,6 +159,9 @@ pub struct StreamHistoryRecord {
     /// carries the provider credential — at which point populate from there.
     pub provider_username: Option<String>,
     pub input_name: Option<String>,
+    /// Input of the linked alternate channel when the stream failed over from `input_name`.
+    #[serde(default)]
+    pub alternate_input: Option<String>,
     // Stream metadata
     pub virtual_id: Option<u32>,
     pub item_type: Option<String>,
@@ -182,6 +192,12 @@ pub struct StreamHistoryRecord {
     pub bytes_sent: Option<u64>,
     pub first_byte_latency_ms: Option<u64>,
     pub provider_reconnect_count: Option<u8>,
+    /// Stalled provider stream segments which were reconnected during the session.
+    #[serde(default)]
+    pub provider_stall_count: Option<u8>,
+    /// MPEG-TS transport quality of the delivered live stream.
+    #[serde(default)]
+    pub ts_quality: Option<TsQualityStats>,
     pub failure_stage: Option<FailureStage>,
     pub provider_http_status: Option<u16>,
     pub provider_error_class: Option<String>,
@@ -194,12 +210,27 @@ pub struct StreamHistoryRecord {
     pub target_id: Option<u16>,
 }
 
+/// MPEG-TS transport metrics of a live session, collected from the packets delivered to the client.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TsQualityStats {
+    pub packets: u64,
+    /// Packets with an unexpected continuity counter and no signalled discontinuity.
+    pub cc_errors: u64,
+    /// PCR intervals above 100 ms, including PCR jumps without a signalled discontinuity.
+    pub pcr_gaps: u64,
+    pub null_packets: u64,
+    /// Average bitrate of the video elementary streams.
+    pub video_bitrate_kbps: Option<u64>,
+}
+
 /// `QoS` metrics collected at disconnect time, passed as a bundle to avoid growing the signature.
 #[derive(Debug, Default)]
 pub struct DisconnectQos {
     pub bytes_sent: Option<u64>,
     pub first_byte_latency_ms: Option<u64>,
     pub provider_reconnect_count: Option<u8>,
+    pub provider_stall_count: Option<u8>,
+    pub ts_quality: Option<TsQualityStats>,
 }
 
 impl StreamHistoryRecord {
@@ -242,6 +273,7 @@ impl StreamHistoryRecord {
             } else {
                 Some(info.channel.input_name.to_string())
             },
+            alternate_input: info.channel.alternate_input.as_ref().map(ToString::to_string),
             virtual_id: Some(info.channel.virtual_id),
             item_type: Some(info.channel.item_type.to_string()),
             title: Some(info.channel.title.to_string()),
@@ -271,6 +303,8 @@ impl StreamHistoryRecord {
             bytes_sent: None,
             first_byte_latency_ms: None,
             provider_reconnect_count: None,
+            provider_stall_count: None,
+            ts_quality: None,
             failure_stage: None,
             provider_http_status: None,
             provider_error_class: None,
@@ -327,6 +361,8 @@ impl StreamHistoryRecord {
         record.bytes_sent = qos.bytes_sent;
         record.first_byte_latency_ms = qos.first_byte_latency_ms;
         record.provider_reconnect_count = qos.provider_reconnect_count;
+        record.provider_stall_count = qos.provider_stall_count;
+        record.ts_quality = qos.ts_quality;
         record.failure_stage = failure_stage;
         record.disconnect_reason = Some(reason);
         record
@@ -479,6 +515,7 @@ mod tests {
             provider_name: Some("acme-tv".to_string()),
             provider_username: Some("acme_user".to_string()),
             input_name: Some("provider-input".to_string()),
+            alternate_input: None,
             virtual_id: Some(1234),
             item_type: Some("live".to_string()),
             title: Some("News Channel".to_string()),
@@ -504,6 +541,8 @@ mod tests {
             bytes_sent: None,
             first_byte_latency_ms: None,
             provider_reconnect_count: None,
+            provider_stall_count: None,
+            ts_quality: None,
             failure_stage: None,
             provider_http_status: None,
             provider_error_class: None,
@@ -526,6 +565,7 @@ mod tests {
             provider_name: Some("acme-tv".to_string()),
             provider_username: Some("acme_user".to_string()),
             input_name: Some("provider-input".to_string()),
+            alternate_input: None,
             virtual_id: Some(1234),
             item_type: Some("live".to_string()),
             title: Some("News Channel".to_string()),
@@ -551,6 +591,8 @@ mod tests {
             bytes_sent: Some(1_234_567_890),
             first_byte_latency_ms: Some(150),
             provider_reconnect_count: Some(0),
+            provider_stall_count: None,
+            ts_quality: None,
             failure_stage: None,
             provider_http_status: None,
             provider_error_class: None,
@@ -591,6 +633,7 @@ mod tests {
                     audio_codec: "AAC".to_string(),
                     audio_channels: "STEREO".to_string(),
                 }),
+                alternate_input: None,
             },
             String::from("VLC/3.0"),
             Some(String::from("DE")),
@@ -0,0 +1,301 @@
+use quick_xml::escape::escape;
+use serde_json::{Map, Value};
+use shared::model::{
+    EpisodeStreamProperties, SeriesStreamDetailEpisodeProperties, SeriesStreamProperties, VideoStreamProperties,
+};
+use std::fmt::Write;
+
+const NFO_HEADER: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n";
+
+/// Poster and fanart urls of an item, downloaded next to the strm files.
+#[derive(Default)]
+pub(super) struct StrmArtwork<'a> {
+    pub poster: Option<&'a str>,
+    pub fanart: Option<&'a str>,
+}
+
+fn non_blank(value: Option<&str>) -> Option<&str> {
+    value.map(str::trim).filter(|v| !v.is_empty())
+}
+
+fn write_tag(nfo: &mut String, tag: &str, value: Option<&str>) {
+    if let Some(value) = non_blank(value) {
+        let _ = writeln!(nfo, "  <{tag}>{}</{tag}>", escape(value));
+    }
+}
+
+fn write_list(nfo: &mut String, tag: &str, value: Option<&str>) {
+    if let Some(value) = non_blank(value) {
+        for entry in value.split([',', '/', '|']).map(str::trim).filter(|e| !e.is_empty()) {
+            let _ = writeln!(nfo, "  <{tag}>{}</{tag}>", escape(entry));
+        }
+    }
+}
+
+fn write_actors(nfo: &mut String, value: Option<&str>) {
+    if let Some(value) = non_blank(value) {
+        for actor in value.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let _ = writeln!(nfo, "  <actor>\n    <name>{}</name>\n  </actor>", escape(actor));
+        }
+    }
+}
+
+fn write_tmdb_id(nfo: &mut String, tmdb_id: Option<u32>) {
+    if let Some(tmdb_id) = tmdb_id.filter(|&id| id != 0) {
+        let _ = writeln!(nfo, "  <uniqueid type=\"tmdb\" default=\"true\">{tmdb_id}</uniqueid>");
+    }
+}
+
+fn write_rating(nfo: &mut String, rating: Option<f64>) {
+    if let Some(rating) = rating.filter(|r| *r > 0.0) {
+        let _ = writeln!(nfo, "  <rating>{rating:.1}</rating>");
+    }
+}
+
+fn write_runtime(nfo: &mut String, runtime_secs: Option<u32>) {
+    if let Some(minutes) = runtime_secs.map(|secs| secs / 60).filter(|m| *m > 0) {
+        let _ = writeln!(nfo, "  <runtime>{minutes}</runtime>");
+    }
+}
+
+/// Returns the year of a release date like `2021-04-03` or `2021`.
+fn get_year(release_date: Option<&str>) -> Option<&str> {
+    let year = non_blank(release_date)?.get(..4)?;
+    year.bytes().all(|b| b.is_ascii_digit()).then_some(year)
+}
+
+fn parse_probe_info(info: Option<&str>) -> Option<Map<String, Value>> {
+    info.and_then(|v| serde_json::from_str::<Map<String, Value>>(v).ok())
+}
+
+/// Writes the kodi `fileinfo` block from the ffprobe video and audio info.
+fn write_stream_details(nfo: &mut String, video: Option<&str>, audio: Option<&str>, duration_secs: Option<u32>) {
+    let video = parse_probe_info(video);
+    let audio = parse_probe_info(audio);
+    if video.is_none() && audio.is_none() {
+        return;
+    }
+    nfo.push_str("  <fileinfo>\n    <streamdetails>\n");
+    if let Some(video) = video {
+        nfo.push_str("      <video>\n");
+        if let Some(codec) = video.get("codec_name").and_then(Value::as_str) {
+            let _ = writeln!(nfo, "        <codec>{}</codec>", escape(codec));
+        }
+        for (key, tag) in [("width", "width"), ("height", "height")] {
+            if let Some(value) = video.get(key).and_then(Value::as_u64) {
+                let _ = writeln!(nfo, "        <{tag}>{value}</{tag}>");
+            }
+        }
+        if let Some(duration) = duration_secs.filter(|d| *d > 0) {
+            let _ = writeln!(nfo, "        <durationinseconds>{duration}</durationinseconds>");
+        }
+        nfo.push_str("      </video>\n");
+    }
+    if let Some(audio) = audio {
+        nfo.push_str("      <audio>\n");
+        if let Some(codec) = audio.get("codec_name").and_then(Value::as_str) {
+            let _ = writeln!(nfo, "        <codec>{}</codec>", escape(codec));
+        }
+        if let Some(channels) = audio.get("channels").and_then(Value::as_u64) {
+            let _ = writeln!(nfo, "        <channels>{channels}</channels>");
+        }
+        if let Some(language) = audio.get("tags").and_then(|t| t.get("language")).and_then(Value::as_str) {
+            let _ = writeln!(nfo, "        <language>{}</language>", escape(language));
+        }
+        nfo.push_str("      </audio>\n");
+    }
+    nfo.push_str("    </streamdetails>\n  </fileinfo>\n");
+}
+
+pub(super) fn movie_nfo(title: &str, tmdb_id: Option<u32>, props: Option<&VideoStreamProperties>) -> String {
+    let details = props.and_then(|p| p.details.as_ref());
+    let mut nfo = String::from(NFO_HEADER);
+    nfo.push_str("<movie>\n");
+    write_tag(&mut nfo, "title", Some(title));
+    write_tag(&mut nfo, "originaltitle", details.and_then(|d| d.o_name.as_deref()));
+    let release_date = details.and_then(|d| d.release_date.as_deref());
+    write_tag(&mut nfo, "year", get_year(release_date));
+    write_tag(&mut nfo, "premiered", release_date);
+    write_tag(&mut nfo, "plot", details.and_then(|d| d.plot.as_deref().or(d.description.as_deref())));
+    let duration_secs = details.and_then(|d| d.duration_secs.as_deref()).and_then(|d| d.trim().parse::<u32>().ok());
+    write_runtime(&mut nfo, duration_secs.or_else(|| details.and_then(|d| d.episode_run_time).map(|m| m * 60)));
+    write_rating(&mut nfo, props.and_then(|p| p.rating));
+    write_tag(&mut nfo, "mpaa", details.and_then(|d| d.mpaa_rating.as_deref().or(d.age.as_deref())));
+    write_tmdb_id(&mut nfo, tmdb_id.or_else(|| props.and_then(|p| p.tmdb)));
+    write_list(&mut nfo, "genre", details.and_then(|d| d.genre.as_deref()));
+    write_list(&mut nfo, "country", details.and_then(|d| d.country.as_deref()));
+    write_list(&mut nfo, "director", details.and_then(|d| d.director.as_deref()));
+    write_actors(&mut nfo, details.and_then(|d| d.cast.as_deref().or(d.actors.as_deref())));
+    write_stream_details(
+        &mut nfo,
+        details.and_then(|d| d.video.as_deref()),
+        details.and_then(|d| d.audio.as_deref()),
+        duration_secs,
+    );
+    nfo.push_str("</movie>\n");
+    nfo
+}
+
+pub(super) fn tvshow_nfo(title: &str, tmdb_id: Option<u32>, props: Option<&SeriesStreamProperties>) -> String {
+    let mut nfo = String::from(NFO_HEADER);
+    nfo.push_str("<tvshow>\n");
+    write_tag(&mut nfo, "title", Some(title));
+    let release_date = props.and_then(|p| p.release_date.as_deref());
+    let year = props.and_then(|p| p.details.as_ref()).and_then(|d| d.year).map(|y| y.to_string());
+    write_tag(&mut nfo, "year", year.as_deref().or_else(|| get_year(release_date)));
+    write_tag(&mut nfo, "premiered", release_date);
+    write_tag(&mut nfo, "plot", props.and_then(|p| p.plot.as_deref()));
+    let run_time = props.and_then(|p| p.episode_run_time.as_deref()).and_then(|r| r.trim().parse::<u32>().ok());
+    write_runtime(&mut nfo, run_time.map(|m| m * 60));
+    write_rating(&mut nfo, props.map(|p| p.rating));
+    write_tmdb_id(&mut nfo, tmdb_id.or_else(|| props.and_then(|p| p.tmdb)));
+    write_list(&mut nfo, "genre", props.and_then(|p| p.genre.as_deref()));
+    write_list(&mut nfo, "director", props.map(|p| p.director.as_ref()));
+    write_actors(&mut nfo, props.map(|p| p.cast.as_ref()));
+    nfo.push_str("</tvshow>\n");
+    nfo
+}
+
+pub(super) fn episode_nfo(
+    title: &str,
+    season: u32,
+    episode: u32,
+    props: Option<&EpisodeStreamProperties>,
+    details: Option<&SeriesStreamDetailEpisodeProperties>,
+) -> String {
+    let mut nfo = String::from(NFO_HEADER);
+    nfo.push_str("<episodedetails>\n");
+    write_tag(&mut nfo, "title", details.map(|d| d.title.as_ref()).filter(|t| !t.trim().is_empty()).or(Some(title)));
+    let _ = writeln!(nfo, "  <season>{season}</season>\n  <episode>{episode}</episode>");
+    write_tag(&mut nfo, "plot", details.and_then(|d| d.plot.as_deref()));
+    write_tag(
+        &mut nfo,
+        "aired",
+        props.and_then(|p| p.release_date.as_deref()).or_else(|| details.map(|d| d.release_date.as_ref())),
+    );
+    let duration_secs = details.map(|d| d.duration_secs).filter(|d| *d > 0);
+    write_runtime(&mut nfo, duration_secs);
+    write_rating(&mut nfo, details.and_then(|d| d.rating));
+    write_tmdb_id(&mut nfo, props.and_then(|p| p.tmdb).or_else(|| details.and_then(|d| d.tmdb)));
+    write_list(&mut nfo, "credits", details.and_then(|d| d.crew.as_deref()));
+    write_stream_details(
+        &mut nfo,
+        props.and_then(|p| p.video.as_deref()).or_else(|| details.and_then(|d| d.video.as_deref())),
+        props.and_then(|p| p.audio.as_deref()).or_else(|| details.and_then(|d| d.audio.as_deref())),
+        duration_secs,
+    );
+    nfo.push_str("</episodedetails>\n");
+    nfo
+}
+
+pub(super) fn find_series_episode(
+    props: &SeriesStreamProperties,
+    season: u32,
+    episode: u32,
+) -> Option<&SeriesStreamDetailEpisodeProperties> {
+    props.details.as_ref()?.episodes.as_ref()?.iter().find(|e| e.season == season && e.episode_num == episode)
+}
+
+/// The same local episode can exist as multiple files, they are matched by the file path.
+pub(super) fn find_local_series_episode<'a>(
+    props: &'a SeriesStreamProperties,
+    season: u32,
+    episode: u32,
+    url: &str,
+) -> Option<&'a SeriesStreamDetailEpisodeProperties> {
+    let episodes = props.details.as_ref()?.episodes.as_ref()?;
+    let mut candidates = episodes.iter().filter(|e| e.season == season && e.episode_num == episode);
+    let first = candidates.next()?;
+    if url.ends_with(first.direct_source.as_ref()) {
+        return Some(first);
+    }
+    candidates.find(|e| url.ends_with(e.direct_source.as_ref())).or(Some(first))
+}
+
+pub(super) fn movie_artwork(props: Option<&VideoStreamProperties>) -> StrmArtwork<'_> {
+    let Some(props) = props else {
+        return StrmArtwork::default();
+    };
+    let details = props.details.as_ref();
+    StrmArtwork {
+        poster: non_blank(details.and_then(|d| d.cover_big.as_deref())).or_else(|| non_blank(Some(&props.stream_icon))),
+        fanart: details
+            .and_then(|d| d.backdrop_path.as_ref())
+            .and_then(|b| b.iter().find_map(|p| non_blank(Some(p))))
+            .or_else(|| non_blank(details.and_then(|d| d.movie_image.as_deref()))),
+    }
+}
+
+pub(super) fn series_artwork(props: Option<&SeriesStreamProperties>) -> StrmArtwork<'_> {
+    let Some(props) = props else {
+        return StrmArtwork::default();
+    };
+    StrmArtwork {
+        poster: non_blank(Some(&props.cover)),
+        fanart: props.backdrop_path.as_ref().and_then(|b| b.iter().find_map(|p| non_blank(Some(p)))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{episode_nfo, get_year, movie_nfo, tvshow_nfo};
+    use shared::model::{SeriesStreamProperties, VideoStreamDetailProperties, VideoStreamProperties};
+
+    #[test]
+    fn test_get_year() {
+        assert_eq!(get_year(Some("2021-04-03")), Some("2021"));
+        assert_eq!(get_year(Some("1999")), Some("1999"));
+        assert_eq!(get_year(Some("n/a")), None);
+        assert_eq!(get_year(None), None);
+    }
+
+    #[test]
+    fn test_movie_nfo() {
+        let props = VideoStreamProperties {
+            name: "Heat".into(),
+            rating: Some(8.3),
+            details: Some(VideoStreamDetailProperties {
+                release_date: Some("1995-12-15".into()),
+                plot: Some("Cops & robbers".into()),
+                genre: Some("Crime, Drama".into()),
+                cast: Some("Al Pacino, Robert De Niro".into()),
+                duration_secs: Some("10200".into()),
+                video: Some(r#"{"codec_name":"h264","width":1920,"height":1080}"#.into()),
+                audio: Some(r#"{"codec_name":"ac3","channels":6}"#.into()),
+                ..VideoStreamDetailProperties::default()
+            }),
+            ..VideoStreamProperties::default()
+        };
+        let nfo = movie_nfo("Heat", Some(949), Some(&props));
+        assert!(nfo.contains("<title>Heat</title>"));
+        assert!(nfo.contains("<year>1995</year>"));
+        assert!(nfo.contains("<plot>Cops &amp; robbers</plot>"));
+        assert!(nfo.contains("<runtime>170</runtime>"));
+        assert!(nfo.contains("<genre>Crime</genre>\n  <genre>Drama</genre>"));
+        assert!(nfo.contains("<name>Robert De Niro</name>"));
+        assert!(nfo.contains("<uniqueid type=\"tmdb\" default=\"true\">949</uniqueid>"));
+        assert!(nfo.contains("<codec>h264</codec>"));
+        assert!(nfo.contains("<height>1080</height>"));
+        assert!(nfo.contains("<channels>6</channels>"));
+        assert!(nfo.ends_with("</movie>\n"));
+    }
+
+    #[test]
+    fn test_series_nfo() {
+        let props = SeriesStreamProperties {
+            name: "Dark".into(),
+            plot: Some("Time travel".into()),
+            release_date: Some("2017-12-01".into()),
+            ..SeriesStreamProperties::default()
+        };
+        let nfo = tvshow_nfo("Dark", None, Some(&props));
+        assert!(nfo.contains("<title>Dark</title>"));
+        assert!(nfo.contains("<year>2017</year>"));
+        assert!(!nfo.contains("uniqueid"));
+
+        let nfo = episode_nfo("Dark S01E02", 1, 2, None, None);
+        assert!(nfo.contains("<title>Dark S01E02</title>"));
+        assert!(nfo.contains("<season>1</season>\n  <episode>2</episode>"));
+        assert!(!nfo.contains("<fileinfo>"));
+    }
+}
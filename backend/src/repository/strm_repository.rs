@@ -1,17 +1,23 @@
+use crate::library::{resolve_metadata_storage_path, MetadataCacheEntry, MetadataStorage};
 use crate::model::{ApiProxyServerInfo, AppConfig, ProxyUserCredentials};
 use crate::model::{ConfigTarget, StrmTargetFlags, StrmTargetOutput};
 use crate::repository::storage::ensure_target_storage_path;
 use crate::repository::storage_const;
+use crate::repository::strm_nfo::{
+    episode_nfo, find_local_series_episode, find_series_episode, movie_artwork, movie_nfo, series_artwork, tvshow_nfo, StrmArtwork,
+};
 use crate::utils::{async_file_reader, async_file_writer, normalize_string_path, truncate_filename,
                    IO_BUFFER_SIZE};
 use chrono::Datelike;
 use filetime::{set_file_times, FileTime};
-use log::{error, trace};
+use log::{error, trace, warn};
 use serde::Serialize;
 use shared::error::{info_err_res, TuliproxError};
-use shared::model::{ClusterFlags, MediaQuality, PlaylistGroup, PlaylistItem, PlaylistItemType, StreamProperties, StrmExportStyle};
+use shared::model::{ClusterFlags, MediaQuality, MediaSidecarKind, PlaylistEntry, PlaylistGroup, PlaylistItem, PlaylistItemType, SeriesStreamProperties,
+                    StreamProperties, StrmExportStyle};
 use shared::utils::{arc_str_option_serde, arc_str_serde, clean_playlist_title, extract_extension_from_url, hash_bytes,
-                    hash_string_as_hex, is_blank_optional_arc_str, truncate_string, ExportStyleConfig, CONSTANTS};
+                    hash_string_as_hex, is_blank_optional_arc_str, sanitize_sensitive_info, truncate_string, ExportStyleConfig,
+                    Internable, CONSTANTS};
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
@@ -348,6 +354,9 @@ struct StrmFile {
     file_name: Arc<String>,
     dir_path: PathBuf,
     strm_info: StrmItemInfo,
+    // only filled when nfo or artwork output is enabled
+    properties: Option<StreamProperties>,
+    parent_code: Arc<str>,
 }
 
 // Helper struct to hold common filename parts to avoid repetition
@@ -677,6 +686,8 @@ fn prepare_strm_files(
     let mut result = Vec::with_capacity(channel_count);
 
     let mut flat_dedup_paths = HashMap::new();
+    let with_metadata = strm_target_output.flags.contains(StrmTargetFlags::WriteNfo)
+        || strm_target_output.flags.contains(StrmTargetFlags::DownloadArtwork);
 
     // first we create the names to identify name collisions
     for pg in new_playlist.iter_mut() {
@@ -731,6 +742,12 @@ fn prepare_strm_files(
                 file_name: filename,
                 dir_path,
                 strm_info: strm_item_info,
+                properties: if with_metadata || pli.header.item_type == PlaylistItemType::LocalVideo {
+                    pli.header.additional_properties.clone()
+                } else {
+                    None
+                },
+                parent_code: Arc::clone(&pli.header.parent_code),
             });
         }
     }
@@ -838,8 +855,10 @@ fn strip_tmdb_markers(s: &str) -> String {
     result
 }
 
+#[allow(clippy::too_many_lines)]
 pub async fn write_strm_playlist(
     app_config: &AppConfig,
+    client: &reqwest::Client,
     target: &ConfigTarget,
     target_output: &StrmTargetOutput,
     new_playlist: &mut [PlaylistGroup],
@@ -890,7 +909,29 @@ pub async fn write_strm_playlist(
         new_playlist,
         target_output,
     );
-    
+    // directories holding more than one movie, e.g. flat exports or several versions of a movie
+    let mut movie_dir_counts: HashMap<&Path, usize> = HashMap::new();
+    for strm_file in strm_files.iter().filter(|f| {
+        matches!(f.strm_info.item_type, PlaylistItemType::Video | PlaylistItemType::LocalVideo)
+    }) {
+        *movie_dir_counts.entry(strm_file.dir_path.as_path()).or_default() += 1;
+    }
+    let shared_movie_dirs: HashSet<PathBuf> =
+        movie_dir_counts.into_iter().filter(|(_, count)| *count > 1).map(|(dir, _)| dir.to_path_buf()).collect();
+
+    let metadata_writer = StrmMetadataWriter {
+        client,
+        root_path: &root_path,
+        shared_movie_dirs,
+        write_nfo: target_output.flags.contains(StrmTargetFlags::WriteNfo),
+        download_artwork: target_output.flags.contains(StrmTargetFlags::DownloadArtwork),
+        series: StrmSeriesLookup::new(new_playlist),
+        library: MetadataStorage::new(resolve_metadata_storage_path(
+            config.metadata_update.as_ref(),
+            &config.storage_dir,
+        )),
+    };
+
     for strm_file in strm_files {
         // file paths
         let output_path = truncate_filename(&root_path.join(&strm_file.dir_path), 255);
@@ -917,31 +958,33 @@ pub async fn write_strm_playlist(
         let content_as_bytes = content_text.as_bytes();
         let content_hash = hash_bytes(content_as_bytes);
 
-        // check if file exists and has same hash
+        // check if file exists and has same hash, otherwise create it
         if file_exists && has_strm_file_same_hash(&target_file_path, content_hash).await {
             processed_strm.insert(target_relative_file_path);
-            continue; // skip creation
-        }
+        } else {
+            // if we can't create the directory skip this entry
+            let target_output_path = target_file_path.parent().map_or_else(|| output_path.clone(), std::path::Path::to_path_buf);
+            if !ensure_strm_file_directory(&mut failed, &target_output_path).await {
+                continue;
+            }
 
-        // if we can't create the directory skip this entry
-        let target_output_path = target_file_path.parent().map_or_else(|| output_path.clone(), std::path::Path::to_path_buf);
-        if !ensure_strm_file_directory(&mut failed, &target_output_path).await {
-            continue;
+            match write_strm_file(
+                &target_file_path,
+                content_as_bytes,
+                strm_file.strm_info.get_file_ts(),
+            ).await
+            {
+                Ok(()) => {
+                    processed_strm.insert(target_relative_file_path);
+                }
+                Err(err) => {
+                    failed.push(err);
+                    continue;
+                }
+            };
         }
 
-        match write_strm_file(
-            &target_file_path,
-            content_as_bytes,
-            strm_file.strm_info.get_file_ts(),
-        ).await
-        {
-            Ok(()) => {
-                processed_strm.insert(target_relative_file_path);
-            }
-            Err(err) => {
-                failed.push(err);
-            }
-        };
+        metadata_writer.write(&strm_file, &target_file_path, &mut processed_strm, &mut failed).await;
     }
 
     if let Err(err) = write_strm_index_file(app_config, &processed_strm, &strm_index_path).await {
@@ -966,6 +1009,233 @@ pub async fn write_strm_playlist(
         info_err_res!("{}", failed.join(", "))
     }
 }
+/// Series info items are not written as strm files, they are only used to resolve
+/// the series metadata for the episodes.
+struct StrmSeriesLookup<'a> {
+    by_parent_code: HashMap<Arc<str>, &'a SeriesStreamProperties>,
+    by_name: HashMap<Arc<str>, &'a SeriesStreamProperties>,
+}
+
+impl<'a> StrmSeriesLookup<'a> {
+    fn new(playlist: &'a [PlaylistGroup]) -> Self {
+        let mut by_parent_code = HashMap::new();
+        let mut by_name = HashMap::new();
+        for pli in playlist.iter().flat_map(|pg| &pg.channels) {
+            let parent_code = match pli.header.item_type {
+                PlaylistItemType::SeriesInfo => pli.get_uuid().intern(),
+                PlaylistItemType::LocalSeriesInfo => Arc::clone(&pli.header.id),
+                _ => continue,
+            };
+            if let Some(StreamProperties::Series(series)) = pli.header.additional_properties.as_ref() {
+                by_parent_code.insert(parent_code, series.as_ref());
+                by_name.insert(Arc::clone(&pli.header.name), series.as_ref());
+                if !series.name.is_empty() {
+                    by_name.insert(Arc::clone(&series.name), series.as_ref());
+                }
+            }
+        }
+        Self { by_parent_code, by_name }
+    }
+
+    fn get(&self, strm_file: &StrmFile) -> Option<&'a SeriesStreamProperties> {
+        // Local episodes have no parent code anymore, they are matched by the series name.
+        self.by_parent_code
+            .get(&strm_file.parent_code)
+            .or_else(|| strm_file.strm_info.series_name.as_ref().and_then(|name| self.by_name.get(name)))
+            .copied()
+    }
+}
+
+/// Writes the `.nfo` files and downloads the artwork next to the strm files.
+/// All written paths are added to the strm index, so they are cleaned up together with the strm files.
+struct StrmMetadataWriter<'a> {
+    client: &'a reqwest::Client,
+    root_path: &'a Path,
+    // movie artwork in these directories is named after the strm file
+    shared_movie_dirs: HashSet<PathBuf>,
+    write_nfo: bool,
+    download_artwork: bool,
+    series: StrmSeriesLookup<'a>,
+    // resolves the files of local library subtitles
+    library: MetadataStorage,
+}
+
+impl StrmMetadataWriter<'_> {
+    async fn write(
+        &self,
+        strm_file: &StrmFile,
+        strm_path: &Path,
+        processed: &mut HashSet<String>,
+        failed: &mut Vec<String>,
+    ) {
+        self.copy_subtitles(strm_file, strm_path, processed, failed).await;
+        if !(self.write_nfo || self.download_artwork) {
+            return;
+        }
+        let info = &strm_file.strm_info;
+        let timestamp = info.get_file_ts();
+        match info.item_type {
+            PlaylistItemType::Video | PlaylistItemType::LocalVideo => {
+                let props = match strm_file.properties.as_ref() {
+                    Some(StreamProperties::Video(video)) => Some(video.as_ref()),
+                    _ => None,
+                };
+                if self.write_nfo {
+                    let nfo = movie_nfo(&clean_playlist_title(&info.title), info.tmdb_id, props);
+                    self.write_file(&strm_path.with_extension("nfo"), nfo.as_bytes(), timestamp, processed, failed).await;
+                }
+                if let Some(movie_dir) = strm_path.parent().filter(|_| self.download_artwork) {
+                    let shared_dir = self.shared_movie_dirs.contains(&strm_file.dir_path);
+                    let file_stem = strm_path.file_stem().map(|s| s.to_string_lossy()).filter(|_| shared_dir);
+                    let file_stem = file_stem.as_deref();
+                    self.download_artwork(&movie_artwork(props), movie_dir, file_stem, processed).await;
+                }
+            }
+            PlaylistItemType::Series | PlaylistItemType::LocalSeries => {
+                let series = self.series.get(strm_file);
+                let episode = match strm_file.properties.as_ref() {
+                    Some(StreamProperties::Episode(episode)) => Some(episode.as_ref()),
+                    _ => None,
+                };
+                // episodes are written into `<series>/Season XX/`
+                let series_dir = strm_path.parent().and_then(Path::parent);
+                if self.write_nfo {
+                    let season = info.season.unwrap_or(1);
+                    let episode_num = info.episode.unwrap_or(1);
+                    let details = series.and_then(|s| find_series_episode(s, season, episode_num));
+                    let nfo = episode_nfo(&info.title, season, episode_num, episode, details);
+                    self.write_file(&strm_path.with_extension("nfo"), nfo.as_bytes(), timestamp, processed, failed).await;
+                    if let Some(series_dir) = series_dir {
+                        let title = info.series_name.as_deref().unwrap_or(&info.title);
+                        let nfo = tvshow_nfo(&clean_playlist_title(title), info.tmdb_id, series);
+                        self.write_file(&series_dir.join("tvshow.nfo"), nfo.as_bytes(), None, processed, failed).await;
+                    }
+                }
+                if self.download_artwork {
+                    if let Some(series_dir) = series_dir {
+                        self.download_artwork(&series_artwork(series), series_dir, None, processed).await;
+                    }
+                    if let Some(thumb) = episode.map(|e| e.movie_image.as_ref()).filter(|t| !t.is_empty()) {
+                        let file_stem = strm_path.file_stem().map(|s| s.to_string_lossy()).unwrap_or_default();
+                        let thumb_path = strm_path.with_file_name(format!("{file_stem}-thumb.jpg"));
+                        self.download_file(thumb, &thumb_path, processed).await;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Local library subtitles are copied next to the strm file with the same name,
+    /// `Movie.de.forced.srt` for `Movie.strm`, so the players pick them up.
+    async fn copy_subtitles(
+        &self,
+        strm_file: &StrmFile,
+        strm_path: &Path,
+        processed: &mut HashSet<String>,
+        failed: &mut Vec<String>,
+    ) {
+        let info = &strm_file.strm_info;
+        let sidecars = match (info.item_type, strm_file.properties.as_ref()) {
+            (PlaylistItemType::LocalVideo, Some(StreamProperties::Video(video))) => {
+                video.details.as_ref().and_then(|d| d.sidecars.as_ref())
+            }
+            (PlaylistItemType::LocalSeries, _) => self.series.get(strm_file).and_then(|series| {
+                find_local_series_episode(series, info.season.unwrap_or(1), info.episode.unwrap_or(1), &info.url)
+            }).and_then(|episode| episode.sidecars.as_ref()),
+            _ => None,
+        };
+        let Some(sidecars) = sidecars else {
+            return;
+        };
+        let file_stem = strm_path.file_stem().map(|s| s.to_string_lossy()).unwrap_or_default();
+        let mut entry: Option<MetadataCacheEntry> = None;
+        for sidecar in sidecars.iter().filter(|s| s.kind == MediaSidecarKind::Subtitle) {
+            if entry.as_ref().is_none_or(|entry| entry.uuid != sidecar.entry.as_ref()) {
+                entry = self.library.load_by_uuid(&sidecar.entry).await;
+            }
+            let Some(file_path) = entry.as_ref().and_then(|entry| entry.find_sidecar(&sidecar.id)).map(|s| &s.file_path)
+            else {
+                warn!("Subtitle {} of library entry {} not found", sidecar.suffix, sidecar.entry);
+                continue;
+            };
+            match tokio::fs::read(file_path).await {
+                Ok(content) => {
+                    let path = strm_path.with_file_name(format!("{file_stem}.{}", sidecar.suffix));
+                    self.write_file(&path, &content, None, processed, failed).await;
+                }
+                Err(err) => warn!("Failed to read subtitle {file_path}: {err}"),
+            }
+        }
+    }
+
+    async fn write_file(
+        &self,
+        path: &Path,
+        content: &[u8],
+        timestamp: Option<u64>,
+        processed: &mut HashSet<String>,
+        failed: &mut Vec<String>,
+    ) {
+        let relative_path = get_relative_path_str(path, self.root_path);
+        if processed.contains(&relative_path) {
+            return;
+        }
+        if !(path.exists() && has_strm_file_same_hash(&path.to_path_buf(), hash_bytes(content)).await) {
+            if let Err(err) = write_strm_file(path, content, timestamp).await {
+                failed.push(format!("{err} {}", path.display()));
+                return;
+            }
+        }
+        processed.insert(relative_path);
+    }
+
+    /// Per item directories get `poster.jpg`/`fanart.jpg`, directories shared by several items
+    /// `<stem>-poster.jpg`/`<stem>-fanart.jpg` like Kodi expects them.
+    async fn download_artwork(
+        &self,
+        artwork: &StrmArtwork<'_>,
+        dir: &Path,
+        file_stem: Option<&str>,
+        processed: &mut HashSet<String>,
+    ) {
+        let file_name =
+            |kind: &str| file_stem.map_or_else(|| format!("{kind}.jpg"), |stem| format!("{stem}-{kind}.jpg"));
+        if let Some(poster) = artwork.poster {
+            self.download_file(poster, &dir.join(file_name("poster")), processed).await;
+        }
+        if let Some(fanart) = artwork.fanart {
+            self.download_file(fanart, &dir.join(file_name("fanart")), processed).await;
+        }
+    }
+
+    /// Existing images are kept, artwork does not change for an item.
+    async fn download_file(&self, url: &str, path: &Path, processed: &mut HashSet<String>) {
+        let relative_path = get_relative_path_str(path, self.root_path);
+        if processed.contains(&relative_path) {
+            return;
+        }
+        if !path.exists() {
+            if !(url.starts_with("http://") || url.starts_with("https://")) {
+                return;
+            }
+            let content = match self.client.get(url).send().await.and_then(reqwest::Response::error_for_status) {
+                Ok(response) => response.bytes().await,
+                Err(err) => Err(err),
+            };
+            let result = match content {
+                Ok(bytes) => write_strm_file(path, &bytes, None).await,
+                Err(err) => Err(err.to_string()),
+            };
+            if let Err(err) = result {
+                warn!("Failed to download strm artwork {}: {}", sanitize_sensitive_info(url), sanitize_sensitive_info(&err));
+                return;
+            }
+        }
+        processed.insert(relative_path);
+    }
+}
+
 async fn write_strm_index_file(
     cfg: &AppConfig,
     entries: &HashSet<String>,
@@ -41,7 +41,7 @@ impl VirtualIdRecord {
 
 
 /// Helper to get UUID index path from primary path
-fn get_uuid_index_path(path: &Path) -> PathBuf {
+pub(crate) fn get_uuid_index_path(path: &Path) -> PathBuf {
     path.with_extension("uuid.db")
 }
 
@@ -8,13 +8,80 @@ use crate::utils;
 use crate::utils::{file_exists_async, json_write_documents_to_file};
 use chrono::Local;
 use log::error;
-use shared::model::{PlaylistBouquetDto, PlaylistClusterBouquetDto, ProxyType, ProxyUserStatus, TargetType, XtreamCluster};
+use shared::model::{
+    AccessScheduleDay, AccessWindowDto, PlaylistBouquetDto, PlaylistClusterBouquetDto, ProxyType,
+    ProxyUserAccessScheduleDto, ProxyUserCountryAccessDto, ProxyUserDeviceLimitDto, ProxyUserQuotaDto,
+    ProxyUserStatus, TargetType, UserQuotaPeriod, XtreamCluster,
+};
 use std::collections::{HashMap, HashSet};
 use std::io::Error;
 use std::path::{Path, PathBuf};
 use tokio::task;
 
-// V4 (current): added soft_connections and soft_priority. V1/V2/V3 are migrated to V4 at startup
+// Stored layouts must not skip fields, rmp_serde encodes them positionally.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct StoredAccessWindow {
+    pub days: Vec<AccessScheduleDay>,
+    pub from: String,
+    pub to: String,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct StoredAccessSchedule {
+    pub timezone: Option<String>,
+    pub windows: Vec<StoredAccessWindow>,
+    pub kick_on_close: bool,
+}
+
+impl StoredAccessSchedule {
+    fn from(schedule: &ProxyUserAccessScheduleDto) -> Self {
+        Self {
+            timezone: schedule.timezone.clone(),
+            windows: schedule
+                .windows
+                .iter()
+                .map(|window| StoredAccessWindow {
+                    days: window.days.clone(),
+                    from: window.from.clone(),
+                    to: window.to.clone(),
+                })
+                .collect(),
+            kick_on_close: schedule.kick_on_close,
+        }
+    }
+
+    fn to(&self) -> ProxyUserAccessScheduleDto {
+        ProxyUserAccessScheduleDto {
+            timezone: self.timezone.clone(),
+            windows: self
+                .windows
+                .iter()
+                .map(|window| AccessWindowDto {
+                    days: window.days.clone(),
+                    from: window.from.clone(),
+                    to: window.to.clone(),
+                })
+                .collect(),
+            kick_on_close: self.kick_on_close,
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct StoredDeviceLimit {
+    pub max_devices: u16,
+    pub approve_new: bool,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct StoredCountryAccess {
+    pub allow: Vec<String>,
+    pub deny: Vec<String>,
+    pub home_country: Option<String>,
+    pub travel_days: u16,
+}
+
+// V11 (current): added reseller. V1–V10 are migrated to V11 at startup
 // by `bplustree_migration::run_all_startup_migrations`.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 struct StoredProxyUserCredentials {
@@ -35,6 +102,16 @@ struct StoredProxyUserCredentials {
     pub priority: Option<i8>,
     pub soft_connections: Option<u16>,
     pub soft_priority: Option<i8>,
+    pub quota_period: Option<UserQuotaPeriod>,
+    pub quota_max_hours: Option<u32>,
+    pub quota_max_traffic_mb: Option<u64>,
+    pub access_schedule: Option<StoredAccessSchedule>,
+    pub parental_profile: Option<String>,
+    pub device_limit: Option<StoredDeviceLimit>,
+    pub country_access: Option<StoredCountryAccess>,
+    pub limit_profile: Option<String>,
+    pub reseller: Option<String>,
+    pub hls_packaging: Option<bool>,
 }
 
 impl StoredProxyUserCredentials {
@@ -57,6 +134,24 @@ impl StoredProxyUserCredentials {
             priority: if proxy.priority != 0 { Some(proxy.priority) } else { None },
             soft_connections: if proxy.soft_connections > 0 { Some(proxy.soft_connections) } else { None },
             soft_priority: if proxy.soft_priority != 0 { Some(proxy.soft_priority) } else { None },
+            quota_period: proxy.quota.as_ref().map(|quota| quota.period),
+            quota_max_hours: proxy.quota.as_ref().and_then(|quota| quota.max_hours),
+            quota_max_traffic_mb: proxy.quota.as_ref().and_then(|quota| quota.max_traffic_mb),
+            access_schedule: proxy.access_schedule.as_ref().map(StoredAccessSchedule::from),
+            parental_profile: proxy.parental_profile.clone(),
+            device_limit: proxy.device_limit.map(|limit| StoredDeviceLimit {
+                max_devices: limit.max_devices,
+                approve_new: limit.approve_new,
+            }),
+            country_access: proxy.country_access.as_ref().map(|access| StoredCountryAccess {
+                allow: access.allow.clone(),
+                deny: access.deny.clone(),
+                home_country: access.home_country.clone(),
+                travel_days: access.travel_days,
+            }),
+            limit_profile: proxy.limit_profile.clone(),
+            reseller: proxy.reseller.clone(),
+            hls_packaging: proxy.hls_packaging,
         }
     }
 
@@ -78,7 +173,28 @@ impl StoredProxyUserCredentials {
             priority: stored.priority.unwrap_or(0),
             soft_connections: stored.soft_connections.unwrap_or(0),
             soft_priority: stored.soft_priority.unwrap_or(0),
+            quota: stored.quota_period.map(|period| ProxyUserQuotaDto {
+                period,
+                max_hours: stored.quota_max_hours,
+                max_traffic_mb: stored.quota_max_traffic_mb,
+            }),
+            access_schedule: stored.access_schedule.as_ref().map(StoredAccessSchedule::to),
+            parental_profile: stored.parental_profile.clone(),
+            device_limit: stored.device_limit.as_ref().map(|limit| ProxyUserDeviceLimitDto {
+                max_devices: limit.max_devices,
+                approve_new: limit.approve_new,
+            }),
+            country_access: stored.country_access.as_ref().map(|access| ProxyUserCountryAccessDto {
+                allow: access.allow.clone(),
+                deny: access.deny.clone(),
+                home_country: access.home_country.clone(),
+                travel_days: access.travel_days,
+            }),
+            limit_profile: stored.limit_profile.clone(),
+            reseller: stored.reseller.clone(),
+            hls_packaging: stored.hls_packaging,
             t_is_api_user: false,
+            t_parental_unlocked: false,
         }
     }
 }
@@ -404,7 +520,16 @@ mod tests {
                         priority: 0,
                         soft_connections: 0,
                         soft_priority: 0,
+                        quota: None,
+                        access_schedule: None,
+                        parental_profile: None,
+                        device_limit: None,
+                        country_access: None,
+                        limit_profile: None,
+                        reseller: None,
+                        hls_packaging: None,
                         t_is_api_user: false,
+                        t_parental_unlocked: false,
                     },
                     ProxyUserCredentials {
                         username: "Test2".to_string(),
@@ -423,7 +548,16 @@ mod tests {
                         priority: 0,
                         soft_connections: 0,
                         soft_priority: 0,
+                        quota: None,
+                        access_schedule: None,
+                        parental_profile: None,
+                        device_limit: None,
+                        country_access: None,
+                        limit_profile: None,
+                        reseller: None,
+                        hls_packaging: None,
                         t_is_api_user: false,
+                        t_parental_unlocked: false,
                     },
                     ProxyUserCredentials {
                         username: "Test3".to_string(),
@@ -442,7 +576,16 @@ mod tests {
                         priority: 0,
                         soft_connections: 0,
                         soft_priority: 0,
+                        quota: None,
+                        access_schedule: None,
+                        parental_profile: None,
+                        device_limit: None,
+                        country_access: None,
+                        limit_profile: None,
+                        reseller: None,
+                        hls_packaging: None,
                         t_is_api_user: false,
+                        t_parental_unlocked: false,
                     },
                     ProxyUserCredentials {
                         username: "Test4".to_string(),
@@ -461,7 +604,33 @@ mod tests {
                         priority: -10, // non-zero priority to verify round-trip serialization
                         soft_connections: 2,
                         soft_priority: -3,
+                        quota: Some(ProxyUserQuotaDto {
+                            period: UserQuotaPeriod::Week,
+                            max_hours: Some(20),
+                            max_traffic_mb: None,
+                        }),
+                        access_schedule: Some(ProxyUserAccessScheduleDto {
+                            timezone: Some("Europe/Berlin".to_string()),
+                            windows: vec![AccessWindowDto {
+                                days: vec![AccessScheduleDay::Fri, AccessScheduleDay::Sat],
+                                from: "18:00".to_string(),
+                                to: "01:00".to_string(),
+                            }],
+                            kick_on_close: true,
+                        }),
+                        parental_profile: Some("kids".to_string()),
+                        device_limit: Some(ProxyUserDeviceLimitDto { max_devices: 3, approve_new: true }),
+                        country_access: Some(ProxyUserCountryAccessDto {
+                            allow: vec![],
+                            deny: vec!["RU".to_string()],
+                            home_country: Some("DE".to_string()),
+                            travel_days: 21,
+                        }),
+                        limit_profile: Some("guest".to_string()),
+                        reseller: Some("bob".to_string()),
+                        hls_packaging: Some(true),
                         t_is_api_user: false,
+                        t_parental_unlocked: false,
                     }
                 ],
             };
@@ -503,5 +672,19 @@ mod tests {
         assert_eq!(test4.priority, -10);
         assert_eq!(test4.soft_connections, 2);
         assert_eq!(test4.soft_priority, -3);
+        let quota = test4.quota.as_ref().unwrap();
+        assert_eq!(quota.period, UserQuotaPeriod::Week);
+        assert_eq!(quota.max_hours, Some(20));
+        assert_eq!(quota.max_traffic_mb, None);
+        let schedule = test4.access_schedule.as_ref().unwrap();
+        assert_eq!(schedule.timezone.as_deref(), Some("Europe/Berlin"));
+        assert_eq!(schedule.windows[0].days, vec![AccessScheduleDay::Fri, AccessScheduleDay::Sat]);
+        assert!(schedule.kick_on_close);
+        assert_eq!(test4.parental_profile.as_deref(), Some("kids"));
+        assert_eq!(test4.device_limit, Some(ProxyUserDeviceLimitDto { max_devices: 3, approve_new: true }));
+        assert_eq!(test4.country_access.as_ref().map(|access| access.travel_days), Some(21));
+        assert_eq!(test4.limit_profile.as_deref(), Some("guest"));
+        assert_eq!(test4.reseller.as_deref(), Some("bob"));
+        assert_eq!(test4.hls_packaging, Some(true));
     }
 }
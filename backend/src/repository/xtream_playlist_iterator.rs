@@ -1,12 +1,12 @@
 use crate::model::ConfigTarget;
-use crate::model::{xtream_mapping_option_from_target_options, AppConfig, ProxyUserCredentials};
+use crate::model::{xtream_mapping_option_from_target_options, AppConfig, ParentalProfile, ProxyUserCredentials};
 use crate::repository::{LockedReceiverStream, open_playlist_reader};
 use crate::repository::user_get_bouquet_filter;
 use crate::repository::{xtream_get_file_path, xtream_get_storage_path};
 use futures::Stream;
 use log::error;
 use shared::error::{TuliproxError, info_err, info_err_res};
-use shared::model::{PlaylistItemType, TargetType, XtreamCluster, XtreamMappingOptions, XtreamPlaylistItem};
+use shared::model::{PlaylistItem, PlaylistItemType, TargetType, XtreamCluster, XtreamMappingOptions, XtreamPlaylistItem};
 use std::collections::HashSet;
 use crate::repository::get_file_path_for_db_index;
 use std::pin::Pin;
@@ -14,7 +14,7 @@ use std::sync::Arc;
 use std::task::{Context, Poll};
 use tokio::sync::mpsc;
 use tokio::task;
-use crate::api::model::AppState;
+use crate::api::model::{AppState, ChannelHealthFilter, UNAVAILABLE_CATEGORY_ID};
 
 pub struct XtreamPlaylistIterator {
     inner: LockedReceiverStream<(XtreamPlaylistItem, bool)>,
@@ -27,6 +27,7 @@ impl XtreamPlaylistIterator {
         target: &ConfigTarget,
         category_id: Option<u32>,
         user: &ProxyUserCredentials,
+        channel_health: Option<ChannelHealthFilter>,
     ) -> Result<Self, TuliproxError> {
 
         // TODO use playlist memory cache and keep sorted
@@ -42,7 +43,13 @@ impl XtreamPlaylistIterator {
             let iter_lock = app_config.file_locks.read_lock(&xtream_path).await;
             let bg_lock = app_config.file_locks.read_lock(&xtream_path).await;
 
-            let filter = user_get_bouquet_filter(&config, &user.username, category_id, TargetType::Xtream, cluster).await;
+            // Dead channels only appear in the "unavailable" category when they are grouped.
+            let channel_health = channel_health.filter(|_| cluster == XtreamCluster::Live);
+            let unavailable_only = category_id == Some(UNAVAILABLE_CATEGORY_ID)
+                && channel_health.as_ref().is_some_and(|health| health.unavailable_group().is_some());
+            let requested_category = if unavailable_only { None } else { category_id.filter(|cid| *cid > 0) };
+            let filter =
+                user_get_bouquet_filter(&config, &user.username, requested_category, TargetType::Xtream, cluster).await;
             // Parse bouquet filter (strings) once into u32 set to minimize per-item allocations
             let filter_ids: Option<HashSet<u32>> = filter.as_ref().map(|set| {
                 set.iter().filter_map(|s| {
@@ -52,6 +59,7 @@ impl XtreamPlaylistIterator {
                     }).ok()
                 }).collect()
             });
+            let parental = app_config.get_user_parental_restriction(user);
 
             let xtream_path = xtream_path.clone();
             let index_path = get_file_path_for_db_index(&xtream_path);
@@ -77,7 +85,7 @@ impl XtreamPlaylistIterator {
 
                 let mut pending: Option<XtreamPlaylistItem> = None;
                 for entry in reader {
-                    let item = match entry {
+                    let mut item = match entry {
                         Ok((_, item)) => item,
                         Err(err) => {
                             error!("Error reading sorted index: {err}");
@@ -85,10 +93,24 @@ impl XtreamPlaylistIterator {
                         }
                     };
 
-                    if !Self::matches_filters(cluster, filter_ids.as_ref(), &item) {
+                    if !Self::matches_filters(cluster, filter_ids.as_ref(), parental.as_deref(), &item) {
                         continue;
                     }
 
+                    match channel_health.as_ref().filter(|health| health.is_dead(item.virtual_id)) {
+                        Some(health) => {
+                            // a dead channel is hidden or moved out of its category
+                            let Some(group) = health.unavailable_group() else { continue };
+                            if requested_category.is_some() {
+                                continue;
+                            }
+                            item.category_id = UNAVAILABLE_CATEGORY_ID;
+                            item.group = group.into();
+                        }
+                        None if unavailable_only => continue,
+                        None => {}
+                    }
+
                     if let Some(prev) = pending.replace(item) {
                         if tx.blocking_send((prev, true)).is_err() {
                             return;
@@ -117,7 +139,12 @@ impl XtreamPlaylistIterator {
         }
     }
 
-    fn matches_filters(cluster: XtreamCluster, filter_ids: Option<&HashSet<u32>>, item: &XtreamPlaylistItem) -> bool {
+    fn matches_filters(
+        cluster: XtreamCluster,
+        filter_ids: Option<&HashSet<u32>>,
+        parental: Option<&ParentalProfile>,
+        item: &XtreamPlaylistItem,
+    ) -> bool {
         // We can't serve episodes within series
         if cluster == XtreamCluster::Series
             && !matches!(item.item_type, PlaylistItemType::SeriesInfo | PlaylistItemType::LocalSeriesInfo) {
@@ -131,6 +158,11 @@ impl XtreamPlaylistIterator {
             }
         }
 
+        // hidden or locked by the parental profile
+        if parental.is_some_and(|profile| profile.is_restricted(&PlaylistItem::from(item))) {
+            return false;
+        }
+
         true
     }
 
@@ -159,6 +191,7 @@ impl XtreamPlaylistJsonIterator {
     ) -> Result<Self, TuliproxError> {
         let xtream_output = target.get_xtream_output().ok_or_else(|| info_err!("Unexpected: xtream output required for target {}", target.name))?;
         let encrypt_secret = app_state.get_encrypt_secret();
+        let channel_health = app_state.channel_health.output_filter(target);
         let options = xtream_mapping_option_from_target_options(
             target,
             xtream_output,
@@ -167,7 +200,15 @@ impl XtreamPlaylistJsonIterator {
             encrypt_secret
         );
         Ok(Self {
-            inner: XtreamPlaylistIterator::new(cluster, &app_state.app_config, target, category_id, user).await?,
+            inner: XtreamPlaylistIterator::new(
+                cluster,
+                &app_state.app_config,
+                target,
+                category_id,
+                user,
+                channel_health,
+            )
+            .await?,
             options,
         })
     }
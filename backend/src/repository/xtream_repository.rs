@@ -1,6 +1,6 @@
 use crate::api::model::AppState;
 use crate::model::XtreamCategory;
-use crate::model::{AppConfig, ProxyUserCredentials};
+use crate::model::{AppConfig, ConfigInputFlags, ProxyUserCredentials};
 use crate::model::{Config, ConfigTarget};
 use crate::model::{ConfigInput, PlaylistXtreamCategory};
 use crate::processing::parser::xtream;
@@ -25,7 +25,7 @@ use serde_json::{json, Value};
 use shared::error::{info_err_res, notify_err, string_to_io_error, TuliproxError};
 use shared::model::xtream_const::XTREAM_CLUSTER;
 use shared::model::{LiveStreamProperties, PlaylistGroup, PlaylistItem, PlaylistItemType, SeriesStreamProperties, StreamProperties, VideoStreamProperties, XtreamCluster, XtreamPlaylistItem};
-use shared::utils::{arc_str_serde, get_u32_from_serde_value, Internable};
+use shared::utils::{arc_str_serde, extract_extension_from_url, get_u32_from_serde_value, Internable};
 use shared::{concat_string, notify_err_res};
 use std::collections::HashMap;
 use std::fs::File;
@@ -288,6 +288,45 @@ pub struct CategoryEntry {
     pub parent_id: u32,
 }
 
+/// Builds the stream url of an item for the user, honoring redirect mode and the input url flags.
+#[allow(clippy::too_many_arguments)]
+pub fn xtream_user_stream_url(
+    app_config: &AppConfig,
+    target: &ConfigTarget,
+    user: &ProxyUserCredentials,
+    base_url: &str,
+    cluster: XtreamCluster,
+    item_type: PlaylistItemType,
+    virtual_id: u32,
+    input_name: &Arc<str>,
+    provider_url: &Arc<str>,
+    container_extension: Option<&str>,
+) -> String {
+    if user.proxy.is_redirect(item_type) || target.is_force_redirect(item_type) {
+        return provider_url.to_string();
+    }
+    let input = app_config.get_input_by_name(input_name);
+    let (live_stream_use_prefix, live_stream_without_extension) = input.as_ref().map_or((true, false), |i| {
+        (
+            i.has_flag(ConfigInputFlags::XtreamLiveStreamUsePrefix),
+            i.has_flag(ConfigInputFlags::XtreamLiveStreamWithoutExtension),
+        )
+    });
+    let extension = container_extension
+        .map(ToString::to_string)
+        .or_else(|| extract_extension_from_url(provider_url).map(|ext| ext.trim_start_matches('.').to_string()));
+    xtream::get_xtream_url(
+        cluster,
+        base_url,
+        &user.username,
+        &user.password,
+        virtual_id,
+        extension.as_deref(),
+        live_stream_use_prefix,
+        live_stream_without_extension,
+    )
+}
+
 pub async fn xtream_write_playlist(
     app_cfg: &Arc<AppConfig>,
     target: &ConfigTarget,
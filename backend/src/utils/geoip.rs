@@ -1,21 +1,89 @@
 use crate::repository::BPlusTree;
+use log::info;
+use shared::utils::{bin_deserialize, bin_serialize};
+use std::collections::HashMap;
 use std::io;
 use std::io::BufRead;
-use std::net::Ipv4Addr;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 use std::path::Path;
 
+const GEOIP_MAGIC: &[u8; 4] = b"TPGI";
+const GEOIP_VERSION: u32 = 1;
+const GEOIP_HEADER_LEN: usize = 8;
+
 fn ipv4_to_u32(ip: &str) -> Option<u32> {
     ip.parse::<Ipv4Addr>().ok().map(u32::from)
 }
 
-pub struct GeoIp {
-    tree: BPlusTree<u32, (u32, String)>,
+fn ipv6_to_u128(ip: &str) -> Option<u128> {
+    ip.parse::<Ipv6Addr>().ok().map(u128::from)
+}
+
+/// A parsed CSV range, either `start,end,country` or `cidr,country`.
+#[derive(Clone, Copy)]
+enum CsvRange {
+    V4(u32, u32),
+    V6(u128, u128),
+}
+
+fn parse_cidr(cidr: &str) -> Option<CsvRange> {
+    let (addr, prefix) = cidr.split_once('/')?;
+    let prefix = prefix.trim().parse::<u32>().ok()?;
+    match addr.trim().parse::<IpAddr>().ok()? {
+        IpAddr::V4(ip) if prefix <= 32 => {
+            let mask = u32::MAX.checked_shl(32 - prefix).unwrap_or(0);
+            let start = u32::from(ip) & mask;
+            Some(CsvRange::V4(start, start | !mask))
+        }
+        IpAddr::V6(ip) if prefix <= 128 => {
+            let mask = u128::MAX.checked_shl(128 - prefix).unwrap_or(0);
+            let start = u128::from(ip) & mask;
+            Some(CsvRange::V6(start, start | !mask))
+        }
+        _ => None,
+    }
 }
 
+fn parse_csv_range(start: &str, end: &str) -> Option<CsvRange> {
+    if let (Some(start), Some(end)) = (ipv4_to_u32(start), ipv4_to_u32(end)) {
+        return Some(CsvRange::V4(start, end));
+    }
+    if let (Some(start), Some(end)) = (ipv6_to_u128(start), ipv6_to_u128(end)) {
+        return Some(CsvRange::V6(start, end));
+    }
+    None
+}
+
+/// Finds the range with the largest start <= `ip`, ranges are sorted by start.
+fn find_range<T: Ord + Copy>(ranges: &[(T, T, u16)], ip: T) -> Option<u16> {
+    let idx = ranges.partition_point(|(start, _, _)| *start <= ip).checked_sub(1)?;
+    let (_, end, country) = ranges[idx];
+    (ip <= end).then_some(country)
+}
+
+fn sort_ranges<T: Ord + Copy>(ranges: &mut Vec<(T, T, u16)>) {
+    // a later import of the same start replaces the earlier one
+    ranges.reverse();
+    ranges.sort_by_key(|(start, _, _)| *start);
+    ranges.dedup_by_key(|(start, _, _)| *start);
+}
+
+#[derive(Default, serde::Serialize, serde::Deserialize)]
+struct GeoIpRanges {
+    countries: Vec<String>,
+    ipv4: Vec<(u32, u32, u16)>,
+    ipv6: Vec<(u128, u128, u16)>,
+}
+
+/// IP to country lookup over sorted IPv4 and IPv6 range indexes.
+pub struct GeoIp {
+    ranges: GeoIpRanges,
+    country_index: HashMap<String, u16>,
+}
 
 impl GeoIp {
 
-    fn seed_private_ranges(tree: &mut BPlusTree<u32, (u32, String)>) {
+    fn seed_private_ranges(&mut self) {
         /// Private and commonly used reserved IPv4 ranges.
         /// "Docker" subnets reflect typical defaults, not exclusive ownership.
         const PRIVATE_RANGES: [(&str, &str, &str); 8] = [
@@ -29,31 +97,100 @@ impl GeoIp {
             ("172.18.0.0", "172.18.255.255", "Docker"),
             ("172.19.0.0", "172.19.255.255", "Docker"),
         ];
+        /// Loopback, unique local and link-local IPv6 ranges.
+        const PRIVATE_RANGES_V6: [(&str, &str); 3] = [
+            ("::1/128", "Loopback"),
+            ("fc00::/7", "LAN"),
+            ("fe80::/10", "Link-Local"),
+        ];
 
         for (start, end, cc) in PRIVATE_RANGES {
             if let (Some(start), Some(end)) = (ipv4_to_u32(start), ipv4_to_u32(end)) {
-                tree.insert(start, (end, cc.to_string()));
+                self.insert(CsvRange::V4(start, end), cc);
+            }
+        }
+        for (cidr, cc) in PRIVATE_RANGES_V6 {
+            if let Some(range) = parse_cidr(cidr) {
+                self.insert(range, cc);
             }
         }
     }
 
+    fn country_id(&mut self, country: &str) -> u16 {
+        if let Some(id) = self.country_index.get(country) {
+            return *id;
+        }
+        let id = u16::try_from(self.ranges.countries.len()).unwrap_or(u16::MAX);
+        self.ranges.countries.push(country.to_string());
+        self.country_index.insert(country.to_string(), id);
+        id
+    }
+
+    fn insert(&mut self, range: CsvRange, country: &str) {
+        let id = self.country_id(country);
+        match range {
+            CsvRange::V4(start, end) => self.ranges.ipv4.push((start, end, id)),
+            CsvRange::V6(start, end) => self.ranges.ipv6.push((start, end, id)),
+        }
+    }
+
+    fn from_ranges(ranges: GeoIpRanges) -> Self {
+        let country_index = ranges
+            .countries
+            .iter()
+            .enumerate()
+            .filter_map(|(id, cc)| u16::try_from(id).ok().map(|id| (cc.clone(), id)))
+            .collect();
+        Self { ranges, country_index }
+    }
+
+    /// Converts a database written before IPv6 support, which stored the IPv4 ranges in a B+Tree.
+    fn load_legacy(path: &Path) -> io::Result<Self> {
+        let tree = BPlusTree::<u32, (u32, String)>::load(path)?;
+        let mut geoip = Self::new();
+        tree.traverse(|keys, values| {
+            for (start, (end, cc)) in keys.iter().zip(values) {
+                geoip.insert(CsvRange::V4(*start, *end), cc);
+            }
+        });
+        geoip.sort();
+        Ok(geoip)
+    }
+
+    /// Loads the db and migrates a db in the legacy format, the caller holds the write lock of the file.
     pub fn load(path: &Path) -> io::Result<Self> {
-        let mut tree = BPlusTree::load(path)?;
-        Self::seed_private_ranges(&mut tree);
+        let content = std::fs::read(path)?;
+        if content.len() >= GEOIP_HEADER_LEN && &content[0..4] == GEOIP_MAGIC {
+            let version = u32::from_le_bytes([content[4], content[5], content[6], content[7]]);
+            if version != GEOIP_VERSION {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, format!("Unsupported geoip db version: {version}")));
+            }
+            let ranges: GeoIpRanges = bin_deserialize(&content[GEOIP_HEADER_LEN..])?;
+            return Ok(Self::from_ranges(ranges));
+        }
 
-        Ok(Self { tree })
+        let geoip = Self::load_legacy(path)?;
+        geoip.store(path)?;
+        info!("GeoIp db migrated to the IPv6 capable format");
+        Ok(geoip)
     }
 
     pub fn new() -> Self {
-        let mut tree = BPlusTree::new();
-        Self::seed_private_ranges(&mut tree);
-        Self {
-            tree
-        }
+        let mut geoip = Self { ranges: GeoIpRanges::default(), country_index: HashMap::new() };
+        geoip.seed_private_ranges();
+        geoip
     }
 
-    pub fn import_ipv4_from_csv(&mut self, mut reader: impl BufRead, db_path: &Path) -> std::io::Result<u64> {
+    fn sort(&mut self) {
+        sort_ranges(&mut self.ranges.ipv4);
+        sort_ranges(&mut self.ranges.ipv6);
+    }
+
+    /// Imports IPv4 and IPv6 ranges, each line is either `range_start,range_end,country_code`
+    /// or `cidr,country_code`. Returns the number of imported ranges.
+    pub fn import_from_csv(&mut self, mut reader: impl BufRead) -> io::Result<u64> {
         let mut buf = String::new();
+        let mut count = 0;
 
         loop {
             buf.clear();
@@ -61,26 +198,41 @@ impl GeoIp {
             let line = buf.trim();
             if line.is_empty() || line.starts_with('#') { continue; }
 
-            let parts: Vec<&str> = line.split(',').collect();
-            if parts.len() != 3 { continue; }
-
-            if let (Some(start), Some(end)) = (ipv4_to_u32(parts[0]), ipv4_to_u32(parts[1])) {
-                let cc = parts[2].trim().to_string();
-                self.tree.insert(start, (end, cc));
+            let parts: Vec<&str> = line.split(',').map(str::trim).collect();
+            let (range, cc) = match parts.as_slice() {
+                [start, end, cc] => (parse_csv_range(start, end), *cc),
+                [cidr, cc] => (parse_cidr(cidr), *cc),
+                _ => continue,
+            };
+            if let Some(range) = range {
+                self.insert(range, cc);
+                count += 1;
             }
-            buf.clear();
         }
-        self.tree.store(db_path)
+        self.sort();
+        Ok(count)
+    }
+
+    pub fn store(&self, db_path: &Path) -> io::Result<u64> {
+        let mut content = Vec::with_capacity(GEOIP_HEADER_LEN);
+        content.extend_from_slice(GEOIP_MAGIC);
+        content.extend_from_slice(&GEOIP_VERSION.to_le_bytes());
+        content.extend_from_slice(&bin_serialize(&self.ranges)?);
+        let tmp_path = db_path.with_extension("db.tmp");
+        std::fs::write(&tmp_path, &content)?;
+        std::fs::rename(&tmp_path, db_path)?;
+        Ok(content.len() as u64)
     }
 
     pub fn lookup(&self, ip_str: &str) -> Option<String> {
-        let ip = ipv4_to_u32(ip_str)?;
-        if let Some((_, (end, cc))) = self.tree.find_le(&ip) {
-            if ip <= *end {
-                return Some(cc.clone());
-            }
-        }
-        None
+        let country = match ip_str.parse::<IpAddr>().ok()? {
+            IpAddr::V4(ip) => find_range(&self.ranges.ipv4, u32::from(ip)),
+            IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+                Some(ipv4) => find_range(&self.ranges.ipv4, u32::from(ipv4)),
+                None => find_range(&self.ranges.ipv6, u128::from(ip)),
+            },
+        }?;
+        self.ranges.countries.get(usize::from(country)).cloned()
     }
 }
 
@@ -93,26 +245,51 @@ impl Default for GeoIp {
 #[cfg(test)]
 mod test {
     // https://raw.githubusercontent.com/sapics/ip-location-db/refs/heads/main/asn-country/asn-country-ipv4.csv
+    use super::GeoIp;
+    use crate::repository::BPlusTree;
+    use std::io::Cursor;
+
+    #[test]
+    fn lookup_resolves_ipv4_and_ipv6_ranges() {
+        let csv = "1.0.0.0,1.0.0.255,AU\n2001:200::,2001:200:ffff:ffff:ffff:ffff:ffff:ffff,JP\n2a02:8100::/27,DE\n";
+        let mut geoip = GeoIp::new();
+        assert_eq!(geoip.import_from_csv(Cursor::new(csv)).unwrap(), 3);
 
-    // use crate::utils::geoip::GeoIp;
-    // use std::fs::File;
-    // use std::path::PathBuf;
-    // use crate::utils::file_reader;
-
-    // #[test]
-    // pub fn test_csv() {
-    //     let db_file = PathBuf::from("/projects/m3u-test/asn-country-ipv4.db");
-    //     let source = PathBuf::from("/projects/m3u-test/asn-country-ipv4.csv");
-    //     let file = File::open(source).expect("Could not open csv file");
-    //     let reader = file_reader(file);
-    //     let mut geo_ip = GeoIp::new();
-    //     let _ = geo_ip.import_ipv4_from_csv(reader, &db_file).expect("Could not import csv");
-    //
-    //     let geo_ip = GeoIp::load(&db_file).expect("Failed to load geoip db");
-    //     if let Some(cc) = geo_ip.lookup("72.13.24.23") {
-    //         assert_eq!(cc, "US");
-    //     } else {
-    //         panic!("GeoIP lookup returned no result");
-    //     }
-    // }
+        assert_eq!(geoip.lookup("1.0.0.17").as_deref(), Some("AU"));
+        assert_eq!(geoip.lookup("::ffff:1.0.0.17").as_deref(), Some("AU"));
+        assert_eq!(geoip.lookup("2001:200:1::1").as_deref(), Some("JP"));
+        assert_eq!(geoip.lookup("2a02:8109:abcd::1").as_deref(), Some("DE"));
+        assert_eq!(geoip.lookup("2a02:8120::1"), None);
+        assert_eq!(geoip.lookup("fd00::1").as_deref(), Some("LAN"));
+        assert_eq!(geoip.lookup("192.168.1.1").as_deref(), Some("LAN"));
+        assert_eq!(geoip.lookup("1.0.1.0"), None);
+    }
+
+    #[test]
+    fn store_and_load_round_trip() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("geoip.db");
+        let mut geoip = GeoIp::new();
+        geoip.import_from_csv(Cursor::new("2001:200::/32,JP\n")).unwrap();
+        geoip.store(&path).unwrap();
+
+        let loaded = GeoIp::load(&path).unwrap();
+        assert_eq!(loaded.lookup("2001:200::1").as_deref(), Some("JP"));
+    }
+
+    #[test]
+    fn legacy_ipv4_db_is_migrated_on_load() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("geoip.db");
+        let mut tree = BPlusTree::<u32, (u32, String)>::new();
+        tree.insert(u32::from(std::net::Ipv4Addr::new(72, 13, 0, 0)), (u32::from(std::net::Ipv4Addr::new(72, 13, 255, 255)), "US".to_string()));
+        tree.store(&path).unwrap();
+
+        let geoip = GeoIp::load(&path).unwrap();
+        assert_eq!(geoip.lookup("72.13.24.23").as_deref(), Some("US"));
+        // the file was rewritten in the new format
+        let reloaded = GeoIp::load(&path).unwrap();
+        assert_eq!(reloaded.lookup("72.13.24.23").as_deref(), Some("US"));
+        assert!(BPlusTree::<u32, (u32, String)>::load(&path).is_err());
+    }
 }
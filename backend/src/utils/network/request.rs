@@ -1869,6 +1869,8 @@ mod tests {
         cfg.reverse_proxy = Some(ReverseProxyConfig {
             resource_rewrite_disabled: false,
             rewrite_secret: [0; 16],
+            trusted_proxies: Vec::new(),
+            trusted_proxy_networks: Vec::new(),
             resource_retry: ResourceRetryConfig {
                 max_attempts: 1,
                 ..ResourceRetryConfig::default()
@@ -1876,7 +1878,9 @@ mod tests {
             disabled_header: None,
             stream: None,
             cache: None,
+            vod_cache: None,
             rate_limit: None,
+            login_protection: None,
             geoip: None,
             stream_history: None,
             qos_aggregation: None,
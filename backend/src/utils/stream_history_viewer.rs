@@ -24,7 +24,7 @@ pub(crate) struct StreamHistoryQuery {
 /// Parsed time range as (`start_ts_utc`, `end_ts_utc`) in seconds
 pub(crate) type TimeRange = (u64, u64);
 
-const SECS_PER_DAY: u64 = 86400;
+pub(crate) const SECS_PER_DAY: u64 = 86400;
 
 /// Parse a date or datetime string into a UTC unix timestamp.
 /// Accepts: "YYYY-MM-DD", "YYYY-MM-DD HH:MM", "YYYY-MM-DD HH:MM:SS"
@@ -108,6 +108,7 @@ const STRING_FIELDS: &[&str] = &[
     "provider_name",
     "provider_username",
     "input_name",
+    "alternate_input",
     "item_type",
     "title",
     "group",
@@ -203,6 +204,7 @@ fn get_record_field<'a>(record: &'a StreamHistoryRecord, field: &str) -> RecordF
         "provider_name" => RecordFieldValue::String(record.provider_name.as_deref()),
         "provider_username" => RecordFieldValue::String(record.provider_username.as_deref()),
         "input_name" => RecordFieldValue::String(record.input_name.as_deref()),
+        "alternate_input" => RecordFieldValue::String(record.alternate_input.as_deref()),
         "item_type" => RecordFieldValue::String(record.item_type.as_deref()),
         "title" => RecordFieldValue::String(record.title.as_deref()),
         "group" => RecordFieldValue::String(record.group.as_deref()),
@@ -232,6 +234,7 @@ fn get_record_field<'a>(record: &'a StreamHistoryRecord, field: &str) -> RecordF
                 DisconnectReason::SessionExpired => "session_expired",
                 DisconnectReason::UserConnectionsExhausted => "user_connections_exhausted",
                 DisconnectReason::ProviderConnectionsExhausted => "provider_connections_exhausted",
+                DisconnectReason::UserQuotaExhausted => "user_quota_exhausted",
             }))
         }
         "connect_failure_reason" => {
@@ -245,6 +248,12 @@ fn get_record_field<'a>(record: &'a StreamHistoryRecord, field: &str) -> RecordF
                 ConnectFailureReason::Preempted => "preempted",
                 ConnectFailureReason::SessionExpired => "session_expired",
                 ConnectFailureReason::Provisioning => "provisioning",
+                ConnectFailureReason::UserQuotaExhausted => "user_quota_exhausted",
+                ConnectFailureReason::OutsideAccessSchedule => "outside_access_schedule",
+                ConnectFailureReason::ParentalRestricted => "parental_restricted",
+                ConnectFailureReason::DeviceNotApproved => "device_not_approved",
+                ConnectFailureReason::DeviceLimitReached => "device_limit_reached",
+                ConnectFailureReason::CountryDenied => "country_denied",
             }))
         }
         "failure_stage" => {
@@ -769,6 +778,7 @@ mod tests {
             provider_name: None,
             provider_username: None,
             input_name: None,
+            alternate_input: None,
             virtual_id: None,
             item_type: None,
             title: None,
@@ -794,6 +804,8 @@ mod tests {
             bytes_sent: None,
             first_byte_latency_ms: None,
             provider_reconnect_count: None,
+            provider_stall_count: None,
+            ts_quality: None,
             failure_stage: None,
             provider_http_status: None,
             provider_error_class: None,
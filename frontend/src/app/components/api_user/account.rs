@@ -0,0 +1,427 @@
+use crate::{
+    app::components::{input::Input, IconButton, TextButton},
+    hooks::use_service_context,
+    html_if,
+    i18n::use_translation,
+    services::DialogService,
+    utils::{format_bytes, format_duration},
+};
+use qrcode::{render::svg, QrCode};
+use shared::{
+    model::{UserAccountDto, UserDeviceDto, UserPasswordChangeDto, UserStreamDto, UserStreamHistoryDto},
+    utils::unix_ts_to_str,
+};
+use std::rc::Rc;
+use yew::{platform::spawn_local, prelude::*};
+use yew_hooks::use_clipboard;
+
+/// Days of stream history shown in the account view.
+const HISTORY_DAYS: u64 = 7;
+
+fn to_qr_code_svg(text: &str) -> Option<String> {
+    QrCode::new(text.as_bytes()).ok().map(|code| {
+        code.render::<svg::Color>()
+            .min_dimensions(240, 240)
+            .dark_color(svg::Color("#000000"))
+            .light_color(svg::Color("#ffffff"))
+            .build()
+    })
+}
+
+fn format_unix_ts(ts: u64) -> String { i64::try_from(ts).ok().and_then(unix_ts_to_str).unwrap_or_default() }
+
+#[component]
+pub fn ApiUserAccount() -> Html {
+    let translate = use_translation();
+    let services = use_service_context();
+    let dialog = use_context::<DialogService>().expect("Dialog service not found");
+    let clipboard = use_clipboard();
+    let account = use_state(|| None::<Rc<UserAccountDto>>);
+    let streams = use_state(Vec::<UserStreamDto>::new);
+    let devices = use_state(Vec::<UserDeviceDto>::new);
+    let history = use_state(Vec::<UserStreamHistoryDto>::new);
+    let password = use_state(String::new);
+    let new_password = use_state(String::new);
+    let confirm_password = use_state(String::new);
+
+    let load_account = {
+        let services = services.clone();
+        let account = account.clone();
+        Callback::from(move |()| {
+            let services = services.clone();
+            let account = account.clone();
+            spawn_local(async move {
+                match services.user_api.get_account().await {
+                    Ok(result) => account.set(result),
+                    Err(err) => services.toastr.error(err.to_string()),
+                }
+            });
+        })
+    };
+
+    let load_activity = {
+        let services = services.clone();
+        let streams = streams.clone();
+        let devices = devices.clone();
+        let history = history.clone();
+        Callback::from(move |()| {
+            let services = services.clone();
+            let streams = streams.clone();
+            let devices = devices.clone();
+            let history = history.clone();
+            spawn_local(async move {
+                if let Ok(list) = services.user_api.get_streams().await {
+                    streams.set(list);
+                }
+                if let Ok(list) = services.user_api.get_devices().await {
+                    devices.set(list);
+                }
+                if let Ok(list) = services.user_api.get_history(HISTORY_DAYS).await {
+                    history.set(list);
+                }
+            });
+        })
+    };
+
+    {
+        let load_account = load_account.clone();
+        let load_activity = load_activity.clone();
+        use_effect_with((), move |()| {
+            load_account.emit(());
+            load_activity.emit(());
+            || ()
+        });
+    }
+
+    let handle_link_action = {
+        let dialog = dialog.clone();
+        let clipboard = clipboard.clone();
+        let services = services.clone();
+        let translate = translate.clone();
+        let account = account.clone();
+        Callback::from(move |(name, _event): (String, MouseEvent)| {
+            let Some((action, kind)) = name.split_once(':') else {
+                return;
+            };
+            let Some(account) = account.as_ref() else {
+                return;
+            };
+            let url = match kind {
+                "xtream" => account.xtream_url.clone(),
+                "m3u" => account.m3u_url.clone(),
+                "xmltv" => account.xmltv_url.clone(),
+                _ => None,
+            };
+            let Some(url) = url else {
+                return;
+            };
+            let dlg = dialog.clone();
+            if action == "qr" {
+                if let Some(qr_code) = to_qr_code_svg(&url) {
+                    spawn_local(async move {
+                        let content = html! {
+                            <div class="tp__api-user-account__qr-code">
+                                { Html::from_html_unchecked(AttrValue::from(qr_code)) }
+                            </div>
+                        };
+                        let _result = dlg.content(content, None, false).await;
+                    });
+                }
+            } else if *clipboard.is_supported {
+                clipboard.write_text(url);
+                services.toastr.success(translate.t("MESSAGES.COPIED_TO_CLIPBOARD"));
+            } else {
+                spawn_local(async move {
+                    let content = html! {<input value={url} readonly={true} class="tp__copy-input"/>};
+                    let _result = dlg.content(content, None, false).await;
+                });
+            }
+        })
+    };
+
+    let handle_activity_action = {
+        let services = services.clone();
+        let load_activity = load_activity.clone();
+        let load_account = load_account.clone();
+        Callback::from(move |(name, _event): (String, MouseEvent)| {
+            let Some((action, id)) = name.split_once(':') else {
+                return;
+            };
+            let (action, id) = (action.to_string(), id.to_string());
+            let services = services.clone();
+            let load_activity = load_activity.clone();
+            let load_account = load_account.clone();
+            spawn_local(async move {
+                let result = match action.as_str() {
+                    "stop" => match id.parse::<u32>() {
+                        Ok(uid) => services.user_api.stop_stream(uid).await,
+                        Err(_) => return,
+                    },
+                    "revoke" => services.user_api.revoke_device(&id).await,
+                    _ => return,
+                };
+                match result {
+                    Ok(()) => {
+                        load_account.emit(());
+                        load_activity.emit(());
+                    }
+                    Err(err) => services.toastr.error(err.to_string()),
+                }
+            });
+        })
+    };
+
+    let handle_refresh = {
+        let load_account = load_account.clone();
+        let load_activity = load_activity.clone();
+        Callback::from(move |_name: String| {
+            load_account.emit(());
+            load_activity.emit(());
+        })
+    };
+
+    let handle_password_change = {
+        let services = services.clone();
+        let translate = translate.clone();
+        let load_account = load_account.clone();
+        let password = password.clone();
+        let new_password = new_password.clone();
+        let confirm_password = confirm_password.clone();
+        Callback::from(move |_name: String| {
+            if *new_password != *confirm_password {
+                services.toastr.error(translate.t("MESSAGES.PASSWORD.MISMATCH"));
+                return;
+            }
+            let change = UserPasswordChangeDto { password: (*password).clone(), new_password: (*new_password).clone() };
+            if let Err(err) = change.validate() {
+                services.toastr.error(err.to_string());
+                return;
+            }
+            let services = services.clone();
+            let translate = translate.clone();
+            let load_account = load_account.clone();
+            let password = password.clone();
+            let new_password = new_password.clone();
+            let confirm_password = confirm_password.clone();
+            spawn_local(async move {
+                match services.user_api.change_password(&change).await {
+                    Ok(()) => {
+                        password.set(String::new());
+                        new_password.set(String::new());
+                        confirm_password.set(String::new());
+                        services.toastr.success(translate.t("MESSAGES.PASSWORD.SUCCESS"));
+                        load_account.emit(());
+                    }
+                    Err(err) => services.toastr.error(err.to_string()),
+                }
+            });
+        })
+    };
+
+    let set_value = |state: &UseStateHandle<String>| {
+        let state = state.clone();
+        Callback::from(move |value: String| state.set(value))
+    };
+
+    let render_field = |label: &str, value: String| {
+        html! {
+            <div class="tp__api-user-account__field">
+                <label>{ translate.t(label) }</label>
+                <span>{ value }</span>
+            </div>
+        }
+    };
+
+    let render_link = |kind: &str, label: &str, url: Option<&String>| {
+        url.map_or_else(Html::default, |url| {
+            html! {
+                <div class="tp__api-user-account__link">
+                    <label>{ translate.t(label) }</label>
+                    <span class="tp__api-user-account__link-url">{ url }</span>
+                    <IconButton name={format!("copy:{kind}")} icon="Clipboard"
+                        hint={translate.t("LABEL.COPY_TO_CLIPBOARD")} onclick={&handle_link_action} />
+                    <IconButton name={format!("qr:{kind}")} icon="Gallery"
+                        hint={translate.t("LABEL.QR_CODE")} onclick={&handle_link_action} />
+                </div>
+            }
+        })
+    };
+
+    let render_account = |account: &UserAccountDto| {
+        let max_connections = if account.max_connections == 0 {
+            translate.t("LABEL.UNLIMITED")
+        } else {
+            account.max_connections.to_string()
+        };
+        html! {
+            <>
+            <div class="tp__api-user-account__section">
+                <h2>{ translate.t("LABEL.ACCOUNT") }</h2>
+                { render_field("LABEL.USERNAME", account.username.clone()) }
+                { render_field("LABEL.STATUS", account.status.map(|s| s.to_string()).unwrap_or_default()) }
+                { render_field("LABEL.CREATED_AT",
+                    account.created_at.and_then(unix_ts_to_str).unwrap_or_default()) }
+                { render_field("LABEL.EXP_DATE",
+                    account.exp_date.and_then(unix_ts_to_str).unwrap_or_else(|| translate.t("LABEL.UNLIMITED"))) }
+                { render_field("LABEL.CONNECTIONS", format!("{} / {max_connections}", account.active_connections)) }
+                { account.quota.as_ref().map_or_else(Html::default, |quota| {
+                    let max_hours = quota.max_hours.filter(|h| *h > 0).map(|h| format!("{h}h"));
+                    let max_traffic = quota.max_traffic_mb.filter(|mb| *mb > 0).map(|mb| format!("{mb} MB"));
+                    html! {
+                        <>
+                        { render_field("LABEL.QUOTA_TIME", format!("{} / {}", format_duration(quota.used_secs),
+                            max_hours.unwrap_or_else(|| translate.t("LABEL.UNLIMITED")))) }
+                        { render_field("LABEL.QUOTA_TRAFFIC", format!("{} / {}", format_bytes(quota.used_bytes),
+                            max_traffic.unwrap_or_else(|| translate.t("LABEL.UNLIMITED")))) }
+                        </>
+                    }
+                })}
+            </div>
+            <div class="tp__api-user-account__section">
+                <h2>{ translate.t("LABEL.PLAYLIST_LINKS") }</h2>
+                { render_link("xtream", "LABEL.XTREAM_SERVER", account.xtream_url.as_ref()) }
+                { render_link("m3u", "LABEL.M3U_PLAYLIST", account.m3u_url.as_ref()) }
+                { render_link("xmltv", "LABEL.XMLTV_EPG", account.xmltv_url.as_ref()) }
+            </div>
+            </>
+        }
+    };
+
+    let render_stream = |stream: &UserStreamDto| {
+        html! {
+            <tr key={stream.uid.to_string()}>
+                <td>{ stream.title.as_str() }</td>
+                <td>{ stream.group.as_str() }</td>
+                <td>{ stream.item_type.to_string() }</td>
+                <td>{ stream.client_ip.as_str() }</td>
+                <td>{ format_unix_ts(stream.ts) }</td>
+                <td>
+                    <IconButton name={format!("stop:{}", stream.uid)} icon="Stop"
+                        hint={translate.t("LABEL.STOP")} onclick={&handle_activity_action} />
+                </td>
+            </tr>
+        }
+    };
+
+    let render_device = |device: &UserDeviceDto| {
+        html! {
+            <tr key={device.id.clone()}>
+                <td>{ device.device_id.as_deref().unwrap_or(device.id.as_str()) }</td>
+                <td>{ device.user_agent.as_str() }</td>
+                <td>{ format_unix_ts(device.last_seen) }</td>
+                <td>
+                    { html_if!(!device.approved, {
+                        <span class="tp__api-user-account__pending">{ translate.t("LABEL.PENDING_APPROVAL") }</span>
+                    })}
+                    <IconButton name={format!("revoke:{}", device.id)} icon="Delete"
+                        hint={translate.t("LABEL.REVOKE")} onclick={&handle_activity_action} />
+                </td>
+            </tr>
+        }
+    };
+
+    let render_history = |(idx, session): (usize, &UserStreamHistoryDto)| {
+        html! {
+            <tr key={idx.to_string()}>
+                <td>{ format_unix_ts(session.ts) }</td>
+                <td>{ session.title.as_deref().unwrap_or_default() }</td>
+                <td>{ session.item_type.as_deref().unwrap_or_default() }</td>
+                <td>{ session.duration_secs.map(format_duration).unwrap_or_default() }</td>
+                <td>{ session.bytes_sent.map(format_bytes).unwrap_or_default() }</td>
+            </tr>
+        }
+    };
+
+    html! {
+        <div class="tp__api-user-account">
+            <div class="tp__api-user-account__header">
+                <h1>{ translate.t("TITLE.USER_ACCOUNT") }</h1>
+                <TextButton class="secondary" name="refresh" icon="Refresh"
+                    title={translate.t("LABEL.REFRESH")} onclick={handle_refresh} />
+            </div>
+            <div class="tp__api-user-account__body">
+                { account.as_ref().map_or_else(Html::default, |account| render_account(account)) }
+                <div class="tp__api-user-account__section">
+                    <h2>{ translate.t("LABEL.CHANGE_PASSWORD") }</h2>
+                    <Input name="password" label={Some(translate.t("LABEL.CURRENT_PASSWORD"))} hidden={true}
+                        value={(*password).clone()} on_change={Some(set_value(&password))} />
+                    <Input name="new_password" label={Some(translate.t("LABEL.NEW_PASSWORD"))} hidden={true}
+                        value={(*new_password).clone()} on_change={Some(set_value(&new_password))} />
+                    <Input name="confirm_password" label={Some(translate.t("LABEL.CONFIRM_PASSWORD"))} hidden={true}
+                        value={(*confirm_password).clone()} on_change={Some(set_value(&confirm_password))} />
+                    <TextButton class="primary" name="change_password" icon="Lock"
+                        title={translate.t("LABEL.CHANGE_PASSWORD")} onclick={handle_password_change} />
+                </div>
+                <div class="tp__api-user-account__section">
+                    <h2>{ translate.t("LABEL.ACTIVE_STREAMS") }</h2>
+                    {
+                        if streams.is_empty() {
+                            html! { <span>{ translate.t("MESSAGES.NO_ACTIVE_STREAMS") }</span> }
+                        } else {
+                            html! {
+                                <table>
+                                    <thead>
+                                        <tr>
+                                            <th>{ translate.t("LABEL.TITLE") }</th>
+                                            <th>{ translate.t("LABEL.GROUP") }</th>
+                                            <th>{ translate.t("LABEL.TYPE") }</th>
+                                            <th>{ translate.t("LABEL.CLIENT_IP") }</th>
+                                            <th>{ translate.t("LABEL.STARTED") }</th>
+                                            <th></th>
+                                        </tr>
+                                    </thead>
+                                    <tbody>{ for streams.iter().map(render_stream) }</tbody>
+                                </table>
+                            }
+                        }
+                    }
+                </div>
+                <div class="tp__api-user-account__section">
+                    <h2>{ translate.t("LABEL.DEVICES") }</h2>
+                    {
+                        if devices.is_empty() {
+                            html! { <span>{ translate.t("MESSAGES.NO_DEVICES") }</span> }
+                        } else {
+                            html! {
+                                <table>
+                                    <thead>
+                                        <tr>
+                                            <th>{ translate.t("LABEL.DEVICE") }</th>
+                                            <th>{ translate.t("LABEL.USER_AGENT") }</th>
+                                            <th>{ translate.t("LABEL.LAST_SEEN") }</th>
+                                            <th></th>
+                                        </tr>
+                                    </thead>
+                                    <tbody>{ for devices.iter().map(render_device) }</tbody>
+                                </table>
+                            }
+                        }
+                    }
+                </div>
+                <div class="tp__api-user-account__section">
+                    <h2>{ translate.t("LABEL.STREAM_HISTORY") }</h2>
+                    {
+                        if history.is_empty() {
+                            html! { <span>{ translate.t("MESSAGES.NO_STREAM_HISTORY") }</span> }
+                        } else {
+                            html! {
+                                <table>
+                                    <thead>
+                                        <tr>
+                                            <th>{ translate.t("LABEL.STARTED") }</th>
+                                            <th>{ translate.t("LABEL.TITLE") }</th>
+                                            <th>{ translate.t("LABEL.TYPE") }</th>
+                                            <th>{ translate.t("LABEL.DURATION") }</th>
+                                            <th>{ translate.t("LABEL.TRAFFIC") }</th>
+                                        </tr>
+                                    </thead>
+                                    <tbody>{ for history.iter().enumerate().map(render_history) }</tbody>
+                                </table>
+                            }
+                        }
+                    }
+                </div>
+            </div>
+        </div>
+    }
+}
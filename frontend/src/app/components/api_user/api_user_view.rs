@@ -1,18 +1,43 @@
 use crate::{
     app::components::{
-        api_user::playlist::ApiUserPlaylist, loading_indicator::BusyIndicator, theme::Theme, AppIcon, IconButton,
-        ThemePicker, ToastrView, WebsocketStatus,
+        api_user::{account::ApiUserAccount, playlist::ApiUserPlaylist},
+        loading_indicator::BusyIndicator,
+        theme::Theme,
+        AppIcon, IconButton, TabItem, TabSet, ThemePicker, ToastrView, WebsocketStatus,
     },
     hooks::use_service_context,
+    i18n::use_translation,
     provider::DialogProvider,
 };
-use yew::{component, html, use_state, Callback, Html};
+use yew::{component, html, use_memo, use_state, Callback, Html};
 
 #[component]
 pub fn ApiUserView() -> Html {
     let services = use_service_context();
+    let translate = use_translation();
     let theme = use_state(Theme::get_current_theme);
 
+    let tabs = use_memo(translate.clone(), |translate| {
+        vec![
+            TabItem {
+                id: "account".to_string(),
+                title: translate.t("LABEL.ACCOUNT"),
+                icon: "UserOutline".to_string(),
+                children: html! { <ApiUserAccount /> },
+                active_class: None,
+                inactive_class: None,
+            },
+            TabItem {
+                id: "playlist".to_string(),
+                title: translate.t("LABEL.PLAYLIST"),
+                icon: "Playlist".to_string(),
+                children: html! { <ApiUserPlaylist /> },
+                active_class: None,
+                inactive_class: None,
+            },
+        ]
+    });
+
     let handle_theme_select = {
         let set_theme = theme.clone();
         Callback::from(move |new_theme: Theme| {
@@ -50,7 +75,7 @@ pub fn ApiUserView() -> Html {
                         </div>
                     </div>
                     <div class="tp__app-main__body">
-                        <ApiUserPlaylist />
+                        <TabSet tabs={tabs} class="tp__api-user-view__tabset"/>
                     </div>
               </div>
             </div>
@@ -1,3 +1,4 @@
+mod account;
 mod api_user_view;
 mod playlist;
 mod target_playlist;
@@ -14,12 +14,13 @@ use crate::{
     },
     config_field, config_field_bool, config_field_child, config_field_custom, config_field_hide, config_field_optional,
     edit_field_bool, edit_field_list, edit_field_number, edit_field_number_f64, edit_field_number_u16,
-    edit_field_number_u64, edit_field_number_usize, edit_field_text, edit_field_text_option, generate_form_reducer,
+    edit_field_number_u32, edit_field_number_u64, edit_field_number_usize, edit_field_text, edit_field_text_option,
+    generate_form_reducer,
     i18n::use_translation,
 };
 use shared::{
     model::{
-        CacheConfigDto, GeoIpConfigDto, QosAggregationConfigDto, RateLimitConfigDto, ResourceRetryConfigDto,
+        CacheConfigDto, GeoIpConfigDto, LoginProtectionConfigDto, QosAggregationConfigDto, RateLimitConfigDto, ResourceRetryConfigDto,
         ReverseProxyConfigDto, ReverseProxyDisabledHeaderConfigDto, StreamBufferConfigDto, StreamConfigDto,
         StreamHistoryConfigDto,
     },
@@ -51,11 +52,20 @@ const LABEL_BUFFER_SIZE: &str = "LABEL.BUFFER_SIZE";
 const LABEL_RATE_LIMIT: &str = "LABEL.RATE_LIMIT";
 const LABEL_PERIOD_MILLIS: &str = "LABEL.PERIOD_MILLIS";
 const LABEL_BURST_SIZE: &str = "LABEL.BURST_SIZE";
+const LABEL_LOGIN_PROTECTION: &str = "LABEL.LOGIN_PROTECTION";
+const LABEL_MAX_FAILURES: &str = "LABEL.MAX_FAILURES";
+const LABEL_USER_MAX_FAILURES: &str = "LABEL.USER_MAX_FAILURES";
+const LABEL_FIND_TIME_SECS: &str = "LABEL.FIND_TIME_SECS";
+const LABEL_BAN_SECS: &str = "LABEL.BAN_SECS";
+const LABEL_MAX_BAN_SECS: &str = "LABEL.MAX_BAN_SECS";
+const LABEL_ALLOWLIST: &str = "LABEL.ALLOWLIST";
+const LABEL_ADD_NETWORK: &str = "LABEL.ADD_NETWORK";
 const LABEL_SHARED_BURST_BUFFER_MB: &str = "LABEL.SHARED_BURST_BUFFER_BYTES";
 
 const LABEL_SETTINGS: &str = "LABEL.SETTINGS";
 const LABEL_RESOURCE_REWRITE_DISABLED: &str = "LABEL.RESOURCE_REWRITE_DISABLED";
 const LABEL_REWRITE_SECRET: &str = "LABEL.REWRITE_SECRET";
+const LABEL_TRUSTED_PROXIES: &str = "LABEL.TRUSTED_PROXIES";
 const LABEL_RESOURCE_RETRY: &str = "LABEL.RESOURCE_RETRY";
 const LABEL_MAX_ATTEMPTS: &str = "LABEL.MAX_ATTEMPTS";
 const LABEL_BACKOFF_MILLIS: &str = "LABEL.BACKOFF_MILLIS";
@@ -70,6 +80,7 @@ const LABEL_CUSTOM_HEADERS: &str = "LABEL.CUSTOM_HEADERS";
 const LABEL_ADD_HEADER: &str = "LABEL.ADD_HEADER";
 const LABEL_GEOIP: &str = "LABEL.GEOIP";
 const LABEL_URL: &str = "LABEL.URL";
+const LABEL_URL_IPV6: &str = "LABEL.URL_IPV6";
 
 const LABEL_STREAM_HISTORY: &str = "LABEL.STREAM_HISTORY";
 const LABEL_STREAM_HISTORY_ENABLED: &str = "LABEL.STREAM_HISTORY_ENABLED";
@@ -153,12 +164,27 @@ generate_form_reducer!(
     }
 );
 
+generate_form_reducer!(
+    state: LoginProtectionConfigFormState { form: LoginProtectionConfigDto },
+    action_name: LoginProtectionConfigFormAction,
+    fields {
+        Enabled => enabled: bool,
+        MaxFailures => max_failures: u32,
+        UserMaxFailures => user_max_failures: u32,
+        FindTimeSecs => find_time_secs: u64,
+        BanSecs => ban_secs: u64,
+        MaxBanSecs => max_ban_secs: u64,
+        Allowlist => allowlist: Vec<String>,
+    }
+);
+
 generate_form_reducer!(
     state: GeoIpConfigFormState { form: GeoIpConfigDto },
     action_name: GeoIpConfigFormAction,
     fields {
         Enabled => enabled: bool,
         Url => url: String,
+        Ipv6Url => ipv6_url: String,
     }
 );
 
@@ -188,6 +214,7 @@ generate_form_reducer!(
     fields {
         ResourceRewriteDisabled => resource_rewrite_disabled: bool,
         RewriteSecret => rewrite_secret: String,
+        TrustedProxies => trusted_proxies: Vec<String>,
     }
 );
 
@@ -227,6 +254,8 @@ pub fn ReverseProxyConfigView() -> Html {
     let stream_state: UseReducerHandle<StreamConfigFormState> =
         use_reducer(|| StreamConfigFormState { form: StreamConfigDto::default(), modified: false });
 
+    let login_protection_state: UseReducerHandle<LoginProtectionConfigFormState> =
+        use_reducer(|| LoginProtectionConfigFormState { form: LoginProtectionConfigDto::default(), modified: false });
     let geoip_state: UseReducerHandle<GeoIpConfigFormState> =
         use_reducer(|| GeoIpConfigFormState { form: GeoIpConfigDto::default(), modified: false });
 
@@ -246,6 +275,7 @@ pub fn ReverseProxyConfigView() -> Html {
         let disabled_header_state = disabled_header_state.clone();
         let cache_state = cache_state.clone();
         let rate_limit_state = rate_limit_state.clone();
+        let login_protection_state = login_protection_state.clone();
         let resource_retry_state = resource_retry_state.clone();
         let stream_state = stream_state.clone();
         let geoip_state = geoip_state.clone();
@@ -262,6 +292,7 @@ pub fn ReverseProxyConfigView() -> Html {
                     disabled_header_state.form.clone(),
                     cache_state.form.clone(),
                     rate_limit_state.form.clone(),
+                    login_protection_state.form.clone(),
                     resource_retry_state.form.clone(),
                     stream_state.form.clone(),
                     geoip_state.form.clone(),
@@ -275,6 +306,7 @@ pub fn ReverseProxyConfigView() -> Html {
                     disabled_header_state.modified,
                     cache_state.modified,
                     rate_limit_state.modified,
+                    login_protection_state.modified,
                     resource_retry_state.modified,
                     stream_state.modified,
                     geoip_state.modified,
@@ -291,6 +323,7 @@ pub fn ReverseProxyConfigView() -> Html {
                     disabled_header,
                     cache,
                     rl,
+                    login_protection,
                     resource_retry,
                     stream,
                     geoip,
@@ -304,6 +337,7 @@ pub fn ReverseProxyConfigView() -> Html {
                     disabled_header_modified,
                     cache_modified,
                     rl_modified,
+                    login_protection_modified,
                     resource_retry_modified,
                     stream_modified,
                     geoip_modified,
@@ -319,6 +353,8 @@ pub fn ReverseProxyConfigView() -> Html {
 
                 form.cache = Some(cache.clone());
                 form.rate_limit = Some(rl.clone());
+                form.login_protection =
+                    if login_protection.is_empty() { None } else { Some(login_protection.clone()) };
                 let mut resource_retry_form = resource_retry.clone();
                 resource_retry_form.failover_redirect_patterns =
                     if failover_patterns.is_empty() { None } else { Some(failover_patterns.patterns.clone()) };
@@ -333,6 +369,7 @@ pub fn ReverseProxyConfigView() -> Html {
                     || disabled_header_modified
                     || cache_modified
                     || rl_modified
+                    || login_protection_modified
                     || resource_retry_modified
                     || stream_modified
                     || geoip_modified
@@ -357,6 +394,7 @@ pub fn ReverseProxyConfigView() -> Html {
         let disabled_header_state = disabled_header_state.clone();
         let cache_state = cache_state.clone();
         let rate_limit_state = rate_limit_state.clone();
+        let login_protection_state = login_protection_state.clone();
         let resource_retry_state = resource_retry_state.clone();
         let stream_state = stream_state.clone();
         let geoip_state = geoip_state.clone();
@@ -392,6 +430,12 @@ pub fn ReverseProxyConfigView() -> Html {
                     rate_limit_state.dispatch(RateLimitConfigFormAction::SetAll(target_rate_limit));
                 }
 
+                let target_login_protection =
+                    rp.login_protection.as_ref().map_or_else(LoginProtectionConfigDto::default, |lp| lp.clone());
+                if login_protection_state.form != target_login_protection {
+                    login_protection_state.dispatch(LoginProtectionConfigFormAction::SetAll(target_login_protection));
+                }
+
                 let target_resource_retry =
                     rp.resource_retry.as_ref().map_or_else(ResourceRetryConfigDto::default, |rr| rr.clone());
                 if resource_retry_state.form != target_resource_retry {
@@ -457,6 +501,11 @@ pub fn ReverseProxyConfigView() -> Html {
                     rate_limit_state.dispatch(RateLimitConfigFormAction::SetAll(target_rate_limit));
                 }
 
+                let target_login_protection = LoginProtectionConfigDto::default();
+                if login_protection_state.form != target_login_protection {
+                    login_protection_state.dispatch(LoginProtectionConfigFormAction::SetAll(target_login_protection));
+                }
+
                 let target_resource_retry = ResourceRetryConfigDto::default();
                 if resource_retry_state.form != target_resource_retry {
                     resource_retry_state.dispatch(ResourceRetryConfigFormAction::SetAll(target_resource_retry));
@@ -552,22 +601,61 @@ pub fn ReverseProxyConfigView() -> Html {
         }
     };
 
+    let render_login_protection = || {
+        let allowlist = &login_protection_state.form.allowlist;
+        html! {
+            <Card class="tp__config-view__card">
+                <h1>{translate.t(LABEL_LOGIN_PROTECTION)}</h1>
+                { config_field_bool!(login_protection_state.form, translate.t(LABEL_ENABLED), enabled) }
+                { config_field!(login_protection_state.form, translate.t(LABEL_MAX_FAILURES), max_failures) }
+                { config_field!(login_protection_state.form, translate.t(LABEL_USER_MAX_FAILURES), user_max_failures) }
+                { config_field!(login_protection_state.form, translate.t(LABEL_FIND_TIME_SECS), find_time_secs) }
+                { config_field!(login_protection_state.form, translate.t(LABEL_BAN_SECS), ban_secs) }
+                { config_field!(login_protection_state.form, translate.t(LABEL_MAX_BAN_SECS), max_ban_secs) }
+                { config_field_child!(translate.t(LABEL_ALLOWLIST), "LOGIN_PROTECTION_CONFIG.ALLOWLIST", {
+                    html! {
+                        <div class="tp__config-view__tags">
+                        if allowlist.is_empty() {
+                            {"-"}
+                        } else {
+                            { for allowlist.iter().map(|entry| html! { <Chip label={entry.clone()} /> }) }
+                        }
+                        </div>
+                    }
+                })}
+            </Card>
+        }
+    };
+
     let render_geoip = || {
         html! {
             <Card class="tp__config-view__card">
                 <h1>{translate.t(LABEL_GEOIP)}</h1>
                 { config_field_bool!(geoip_state.form, translate.t(LABEL_ENABLED), enabled) }
                 { config_field!(geoip_state.form, translate.t(LABEL_URL), url) }
+                { config_field!(geoip_state.form, translate.t(LABEL_URL_IPV6), ipv6_url) }
             </Card>
         }
     };
 
     let render_settings_view = || {
+        let trusted_proxies = &reverse_proxy_state.form.trusted_proxies;
         html! {
             <Card class="tp__config-view__card">
                 <h1>{translate.t(LABEL_SETTINGS)}</h1>
                 { config_field_bool!(reverse_proxy_state.form, translate.t(LABEL_RESOURCE_REWRITE_DISABLED), resource_rewrite_disabled) }
                 { config_field_hide!(reverse_proxy_state.form, translate.t(LABEL_REWRITE_SECRET), rewrite_secret) }
+                { config_field_child!(translate.t(LABEL_TRUSTED_PROXIES), "REVERSE_PROXY_CONFIG.TRUSTED_PROXIES", {
+                    html! {
+                        <div class="tp__config-view__tags">
+                        if trusted_proxies.is_empty() {
+                            {"-"}
+                        } else {
+                            { for trusted_proxies.iter().map(|entry| html! { <Chip label={entry.clone()} /> }) }
+                        }
+                        </div>
+                    }
+                })}
             </Card>
         }
     };
@@ -578,6 +666,7 @@ pub fn ReverseProxyConfigView() -> Html {
                 <h1>{translate.t(LABEL_SETTINGS)}</h1>
                 { edit_field_bool!(reverse_proxy_state, translate.t(LABEL_RESOURCE_REWRITE_DISABLED), resource_rewrite_disabled, ReverseProxyConfigFormAction::ResourceRewriteDisabled) }
                 { edit_field_text!(reverse_proxy_state, translate.t(LABEL_REWRITE_SECRET), rewrite_secret, ReverseProxyConfigFormAction::RewriteSecret, true) }
+                { edit_field_list!(reverse_proxy_state, translate.t(LABEL_TRUSTED_PROXIES), trusted_proxies, ReverseProxyConfigFormAction::TrustedProxies, translate.t(LABEL_ADD_NETWORK)) }
             </Card>
         }
     };
@@ -657,6 +746,7 @@ pub fn ReverseProxyConfigView() -> Html {
                 <h1>{translate.t(LABEL_GEOIP)}</h1>
                 { edit_field_bool!(geoip_state, translate.t(LABEL_ENABLED), enabled, GeoIpConfigFormAction::Enabled) }
                 { edit_field_text!(geoip_state, translate.t(LABEL_URL), url, GeoIpConfigFormAction::Url) }
+                { edit_field_text!(geoip_state, translate.t(LABEL_URL_IPV6), ipv6_url, GeoIpConfigFormAction::Ipv6Url) }
             </Card>
         }
     };
@@ -683,6 +773,21 @@ pub fn ReverseProxyConfigView() -> Html {
         }
     };
 
+    let render_login_protection_edit = || {
+        html! {
+            <Card class="tp__config-view__card">
+                <h1>{translate.t(LABEL_LOGIN_PROTECTION)}</h1>
+                { edit_field_bool!(login_protection_state, translate.t(LABEL_ENABLED), enabled, LoginProtectionConfigFormAction::Enabled) }
+                { edit_field_number_u32!(login_protection_state, translate.t(LABEL_MAX_FAILURES), max_failures, LoginProtectionConfigFormAction::MaxFailures) }
+                { edit_field_number_u32!(login_protection_state, translate.t(LABEL_USER_MAX_FAILURES), user_max_failures, LoginProtectionConfigFormAction::UserMaxFailures) }
+                { edit_field_number_u64!(login_protection_state, translate.t(LABEL_FIND_TIME_SECS), find_time_secs, LoginProtectionConfigFormAction::FindTimeSecs) }
+                { edit_field_number_u64!(login_protection_state, translate.t(LABEL_BAN_SECS), ban_secs, LoginProtectionConfigFormAction::BanSecs) }
+                { edit_field_number_u64!(login_protection_state, translate.t(LABEL_MAX_BAN_SECS), max_ban_secs, LoginProtectionConfigFormAction::MaxBanSecs) }
+                { edit_field_list!(login_protection_state, translate.t(LABEL_ALLOWLIST), allowlist, LoginProtectionConfigFormAction::Allowlist, translate.t(LABEL_ADD_NETWORK)) }
+            </Card>
+        }
+    };
+
     let render_stream_edit = || {
         html! {
             <>
@@ -771,6 +876,7 @@ pub fn ReverseProxyConfigView() -> Html {
                 { render_cache() }
                 { render_resource_retry_view() }
                 { render_rate_limit() }
+                { render_login_protection() }
                 { render_stream() }
                 { render_stream_buffer() }
                 { render_stream_history() }
@@ -788,6 +894,7 @@ pub fn ReverseProxyConfigView() -> Html {
                 { render_cache_edit() }
                 { render_resource_retry_edit() }
                 { render_rate_limit_edit() }
+                { render_login_protection_edit() }
                 { render_stream_edit() }
                 { render_stream_buffer_edit() }
                 { render_stream_history_edit() }
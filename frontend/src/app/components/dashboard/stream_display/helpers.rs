@@ -225,6 +225,7 @@ mod tests {
                 shared_joined_existing: None,
                 shared_stream_id: None,
                 technical: None,
+                alternate_input: None,
             },
             provider: "provider".to_string(),
             addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 8080),
@@ -235,6 +236,7 @@ mod tests {
             session_token: has_session.then(|| "session".to_string()),
             preserved,
             previous_session_id: None,
+            throttle_kbps: 0,
         })
     }
 
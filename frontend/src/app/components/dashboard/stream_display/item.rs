@@ -10,7 +10,7 @@ use crate::{
 };
 use shared::{
     model::StreamInfo,
-    utils::{current_time_secs, strip_port},
+    utils::{current_time_secs, human_readable_kbps, strip_port},
 };
 use std::rc::Rc;
 use web_sys::MouseEvent;
@@ -132,6 +132,12 @@ pub fn StreamDisplayItem(props: &StreamDisplayItemProps) -> Html {
                             </span>
                         </div>
                     }
+                    if stream.throttle_kbps > 0 {
+                        <div class="tp__stream-display__stat">
+                            <span class="tp__stream-display__stat-label">{translate.t("LABEL.THROTTLE")}</span>
+                            <span class="tp__stream-display__stat-value">{human_readable_kbps(stream.throttle_kbps)}</span>
+                        </div>
+                    }
                     <div class="tp__stream-display__stat tp__stream-display__detail">
                         <span class="tp__stream-display__stat-label">{translate.t("LABEL.PLAYER")}</span>
                         <span class="tp__stream-display__stat-value">
@@ -2,11 +2,14 @@ mod helpers;
 mod item;
 mod meter;
 
+pub use self::{
+    helpers::{build_technical_chips, is_stream_metrics_enabled},
+    meter::{MeterDisplayKind, StreamMeterBadge},
+};
 use self::{
     helpers::{
         filter_visible_streams, get_adaptive_session_ttl_secs, is_background_transfer_stream,
-        is_stream_metrics_enabled, refresh_adaptive_last_seen, update_timestamps,
-        ADAPTIVE_STREAM_CLEANUP_INTERVAL_MILLIS,
+        refresh_adaptive_last_seen, update_timestamps, ADAPTIVE_STREAM_CLEANUP_INTERVAL_MILLIS,
     },
     item::StreamDisplayItem,
 };
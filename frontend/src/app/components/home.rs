@@ -3,7 +3,7 @@ use crate::{
         components::{
             config::ConfigView, loading_indicator::BusyIndicator, theme::Theme, AppIcon, DashboardView, DownloadsView,
             EpgView, IconButton, InputRow, NoAccess, Panel, ParticleFlowBackground, PlaylistExplorerView,
-            PlaylistSettingsView, PlaylistUpdateView, RbacView, Setup, Sidebar, SourceEditor, StatsView,
+            PlaylistSettingsView, PlaylistUpdateView, RbacView, ResellerView, Setup, Sidebar, SourceEditor, StatsView,
             StreamHistoryView, StreamsView, ThemePicker, ToastrView, UserlistView, WebsocketStatus,
         },
         context::{ConfigContext, PlaylistContext, StatusContext},
@@ -93,6 +93,7 @@ pub fn Home() -> Html {
     let can_read_epg = services.auth.has_permission(Permission::EpgRead);
     let can_read_downloads = services.auth.has_permission(Permission::DownloadRead);
     let is_admin = services.auth.is_admin();
+    let is_reseller = services.auth.is_reseller();
     let _ = use_server_status(status.clone(), system_info.clone(), !setup_mode && can_read_system_status);
 
     {
@@ -336,6 +337,11 @@ pub fn Home() -> Html {
                                                <RbacView />
                                            </Panel>
                                        })}
+                                       { html_if!(is_reseller, {
+                                           <Panel class="tp__full-width" value={ViewType::Reseller.to_string()} active={view_visible.to_string()}>
+                                               <ResellerView />
+                                           </Panel>
+                                       })}
                                     </>
                                 }
                             }
@@ -34,6 +34,7 @@ mod playlist;
 mod popup_menu;
 mod radio_button_group;
 mod rbac;
+mod reseller;
 mod reveal_content;
 mod role_based_content;
 mod search;
@@ -73,8 +74,8 @@ pub(crate) use self::{
     date_input_action::*, downloads::DownloadsView, drop_down_icon_button::*, field_explanation::*, field_id::*,
     filter::*, hide_content::*, home::*, icon_button::*, key_value_editor::*, loading_screen::*, login::*,
     no_access::*, no_content::*, panel::*, particle_flow_background::*, playlist::*, radio_button_group::*, rbac::*,
-    reveal_content::*, role_based_content::*, search::*, select::*, setup::*, sidebar::*, source_editor::*,
-    svg_icon::*, table::*, tabset::*, tag_list::*, text_button::*, textarea::*, theme_picker::*, title_card::*,
-    toastr::*, toggle_switch::*, userlist::*, websocket_status::*,
+    reseller::*, reveal_content::*, role_based_content::*, search::*, select::*, setup::*, sidebar::*,
+    source_editor::*, svg_icon::*, table::*, tabset::*, tag_list::*, text_button::*, textarea::*, theme_picker::*,
+    title_card::*, toastr::*, toggle_switch::*, userlist::*, websocket_status::*,
 };
 pub use self::{confirm_dialog::*, content_dialog::*};
@@ -14,6 +14,7 @@ mod playlist_explorer_view;
 mod playlist_settings_view;
 mod playlist_source_selector;
 mod playlist_update_view;
+mod preview_player;
 mod processing;
 mod target;
 mod target_table;
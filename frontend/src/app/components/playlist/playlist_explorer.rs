@@ -1,6 +1,9 @@
 use crate::{
     app::{
-        components::{menu_item::MenuItem, popup_menu::PopupMenu, AppIcon, Chip, IconButton, NoContent, Panel, Search},
+        components::{
+            menu_item::MenuItem, playlist::preview_player::PreviewPlayer, popup_menu::PopupMenu, AppIcon, Chip,
+            IconButton, NoContent, Panel, Search,
+        },
         context::{ConfigContext, PlaylistExplorerContext},
     },
     hooks::use_service_context,
@@ -28,6 +31,7 @@ const COPY_LINK_TULIPROX_WEBPLAYER_URL: &str = "copy_link_tuliprox_webplayer_url
 const COPY_LINK_PROVIDER_URL: &str = "copy_link_provider_url";
 const DOWNLOAD_ITEM: &str = "download_item";
 const RECORD_ITEM: &str = "record_item";
+const PREVIEW_ITEM: &str = "preview_item";
 
 #[derive(Clone)]
 struct ChannelSelection {
@@ -39,6 +43,15 @@ struct ChannelSelection {
     input_name: String,
 }
 
+#[derive(Clone, PartialEq)]
+struct PreviewSelection {
+    target_id: u16,
+    virtual_id: VirtualId,
+    cluster: XtreamCluster,
+    title: String,
+    url: String,
+}
+
 #[allow(clippy::enum_variant_names)]
 #[derive(Debug, Clone, Eq, PartialEq)]
 enum ExplorerAction {
@@ -47,6 +60,7 @@ enum ExplorerAction {
     CopyLinkProviderUrl,
     Download,
     Record,
+    Preview,
 }
 
 impl Display for ExplorerAction {
@@ -60,6 +74,7 @@ impl Display for ExplorerAction {
                 Self::CopyLinkProviderUrl => COPY_LINK_PROVIDER_URL,
                 Self::Download => DOWNLOAD_ITEM,
                 Self::Record => RECORD_ITEM,
+                Self::Preview => PREVIEW_ITEM,
             }
         )
     }
@@ -79,6 +94,8 @@ impl FromStr for ExplorerAction {
             Ok(Self::Download)
         } else if s.eq(RECORD_ITEM) {
             Ok(Self::Record)
+        } else if s.eq(PREVIEW_ITEM) {
+            Ok(Self::Preview)
         } else {
             info_err_res!("Unknown ExplorerAction: {}", s)
         }
@@ -163,6 +180,11 @@ fn can_show_download_action(can_write_downloads: bool, selected_channel: Option<
     can_write_downloads && selected_channel.is_some_and(|item| item.cluster != XtreamCluster::Live && item.downloadable)
 }
 
+fn can_show_preview_action(selected_channel: Option<&ChannelSelection>) -> bool {
+    // Series entries open the episode list, only episodes are playable.
+    selected_channel.is_some_and(|item| item.cluster != XtreamCluster::Series || item.downloadable)
+}
+
 fn can_show_record_action(can_write_downloads: bool, selected_channel: Option<&ChannelSelection>) -> bool {
     can_write_downloads && selected_channel.is_some_and(|item| item.cluster == XtreamCluster::Live)
 }
@@ -200,6 +222,7 @@ pub fn PlaylistExplorer() -> Html {
     let popup_is_open = use_state(|| false);
     let clipboard = use_clipboard();
     let cluster_visible = use_state(|| XtreamCluster::Live);
+    let preview = use_state(|| None::<PreviewSelection>);
 
     let handle_cluster_change = {
         let cluster_vis = cluster_visible.clone();
@@ -302,9 +325,11 @@ pub fn PlaylistExplorer() -> Html {
         let set_selected_channel = selected_channel.clone();
         let set_popup_is_open = popup_is_open.clone();
         let set_anchor_ref = popup_anchor_ref.clone();
+        let set_preview = preview.clone();
         use_effect_with((*context.playlist).clone(), move |new_playlist| {
             set_current_item.set(ExplorerLevel::Categories);
             set_playlist.set(new_playlist.clone());
+            set_preview.set(None);
             // Reset popup state and selection when the underlying data changes
             set_selected_channel.set(None);
             set_popup_is_open.set(false);
@@ -339,9 +364,39 @@ pub fn PlaylistExplorer() -> Html {
         let translate_clone = translate.clone();
         let can_queue_downloads = can_write_downloads;
         let copy_to_clipboard = copy_to_clipboard.clone();
+        let set_preview = preview.clone();
         Callback::from(move |(name, _): (String, _)| {
             if let Ok(action) = ExplorerAction::from_str(&name) {
                 match action {
+                    ExplorerAction::Preview => {
+                        if let (Some(PlaylistRequest::Target(target_id)), Some(dto)) =
+                            (playlist_ctx.playlist_request.as_ref(), &*selected_channel)
+                        {
+                            let services = services.clone();
+                            let translate_clone = translate_clone.clone();
+                            let set_preview = set_preview.clone();
+                            let target_id = *target_id;
+                            let virtual_id = dto.virtual_id;
+                            let cluster = dto.cluster;
+                            let title = dto.title.clone();
+                            spawn_local(async move {
+                                let request = PlaylistUrlResolveRequest::Preview { target_id, virtual_id, cluster };
+                                if let Some(url) = services.playlist.resolve_url(request).await {
+                                    set_preview.set(Some(PreviewSelection {
+                                        target_id,
+                                        virtual_id,
+                                        cluster,
+                                        title,
+                                        url,
+                                    }));
+                                } else {
+                                    services
+                                        .toastr
+                                        .error(translate_clone.t("MESSAGES.PLAYLIST_EXPLORER.PREVIEW_FAILED"));
+                                }
+                            });
+                        }
+                    }
                     ExplorerAction::CopyLinkTuliproxVirtualId => {
                         if let Some(dto) = &*selected_channel {
                             copy_to_clipboard.emit(dto.virtual_id.to_string());
@@ -747,6 +802,11 @@ pub fn PlaylistExplorer() -> Html {
         })
     };
 
+    let handle_preview_close = {
+        let set_preview = preview.clone();
+        Callback::from(move |()| set_preview.set(None))
+    };
+
     let handle_back_click = {
         let current_item = current_item.clone();
         Callback::from(move |_| match *current_item {
@@ -1098,6 +1158,17 @@ pub fn PlaylistExplorer() -> Html {
                 </div>
             </div>
         </div>
+        if let Some(selection) = preview.as_ref() {
+            <PreviewPlayer
+                key={selection.url.clone()}
+                target_id={selection.target_id}
+                virtual_id={selection.virtual_id}
+                cluster={selection.cluster}
+                title={selection.title.clone()}
+                url={selection.url.clone()}
+                on_close={handle_preview_close}
+            />
+        }
         <div class="tp__playlist-explorer__body">
           {
             match *current_item {
@@ -1111,6 +1182,9 @@ pub fn PlaylistExplorer() -> Html {
         <PopupMenu is_open={*popup_is_open} anchor_ref={(*popup_anchor_ref).clone()} on_close={handle_popup_close}>
             { html_if!(context.playlist_request.as_ref().is_some_and(|r| matches!(r, PlaylistRequest::Target(_))), {
                 <>
+                 { html_if!(can_show_preview_action(selected_channel.as_ref()), {
+                    <MenuItem icon="PlayArrow" name={ExplorerAction::Preview.to_string()} label={translate.t("LABEL.PREVIEW")} onclick={&handle_menu_click}></MenuItem>
+                 })}
                  <MenuItem icon="Clipboard" name={ExplorerAction::CopyLinkTuliproxVirtualId.to_string()} label={translate.t("LABEL.COPY_LINK_TULIPROX_VIRTUAL_ID")} onclick={&handle_menu_click}></MenuItem>
                  <MenuItem icon="Clipboard" name={ExplorerAction::CopyLinkTuliproxWebPlayerUrl.to_string()} label={translate.t("LABEL.COPY_LINK_TULIPROX_WEBPLAYER_URL")} onclick={&handle_menu_click}></MenuItem>
                 </>
@@ -1135,8 +1209,9 @@ pub fn PlaylistExplorer() -> Html {
 #[cfg(test)]
 mod tests {
     use super::{
-        build_download_filename, can_show_download_action, can_show_record_action, normalize_input_name,
-        parse_optional_priority_input, parse_record_duration_minutes, parse_record_start_value, ChannelSelection,
+        build_download_filename, can_show_download_action, can_show_preview_action, can_show_record_action,
+        normalize_input_name, parse_optional_priority_input, parse_record_duration_minutes, parse_record_start_value,
+        ChannelSelection,
     };
     use shared::model::{VirtualId, XtreamCluster};
 
@@ -1209,6 +1284,10 @@ mod tests {
         assert!(!can_show_download_action(true, Some(&series_container)));
         assert!(can_show_download_action(true, Some(&episode)));
         assert!(!can_show_record_action(true, Some(&vod)));
+        assert!(can_show_preview_action(Some(&live)));
+        assert!(can_show_preview_action(Some(&vod)));
+        assert!(!can_show_preview_action(Some(&series_container)));
+        assert!(can_show_preview_action(Some(&episode)));
     }
 
     #[test]
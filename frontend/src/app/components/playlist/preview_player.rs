@@ -0,0 +1,219 @@
+use crate::{
+    app::{
+        components::{
+            build_technical_chips, is_stream_metrics_enabled, Chip, IconButton, MeterDisplayKind, StreamMeterBadge,
+        },
+        context::{ConfigContext, StatusContext},
+    },
+    html_if,
+    i18n::use_translation,
+};
+use shared::model::{StreamInfo, XtreamCluster};
+use wasm_bindgen::JsCast;
+use web_sys::HtmlVideoElement;
+use yew::prelude::*;
+
+// Streams opened by the Web UI run as the internal api user.
+const PREVIEW_USERNAME: &str = "api_user";
+const HLS_MIME_TYPE: &str = "application/vnd.apple.mpegurl";
+
+fn supports_native_hls() -> bool {
+    gloo_utils::document()
+        .create_element("video")
+        .ok()
+        .and_then(|element| element.dyn_into::<HtmlVideoElement>().ok())
+        .is_some_and(|video| !video.can_play_type(HLS_MIME_TYPE).is_empty())
+}
+
+/// Prefers the preview stream, a channel packaged for another viewer has no preview stream of its own.
+fn find_preview_stream(streams: &[StreamInfo], target_id: u16, virtual_id: u32) -> Option<&StreamInfo> {
+    let mut channel_streams = streams
+        .iter()
+        .filter(|stream| stream.channel.target_id == target_id && stream.channel.virtual_id == virtual_id);
+    let first = channel_streams.next()?;
+    if first.username == PREVIEW_USERNAME {
+        return Some(first);
+    }
+    channel_streams.find(|stream| stream.username == PREVIEW_USERNAME).or(Some(first))
+}
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct PreviewPlayerProps {
+    pub target_id: u16,
+    pub virtual_id: u32,
+    pub cluster: XtreamCluster,
+    pub title: String,
+    pub url: String,
+    pub on_close: Callback<()>,
+}
+
+#[component]
+pub fn PreviewPlayer(props: &PreviewPlayerProps) -> Html {
+    let translate = use_translation();
+    let config_ctx = use_context::<ConfigContext>().expect("ConfigContext not found");
+    let status_ctx = use_context::<StatusContext>().expect("Status context not found");
+    let video_ref = use_node_ref();
+    let playback_failed = use_state(|| false);
+    let video_size = use_state(|| None::<(u32, u32)>);
+    let hls_unsupported = use_state(|| props.cluster == XtreamCluster::Live && !supports_native_hls());
+
+    {
+        let video_ref = video_ref.clone();
+        let playback_failed = playback_failed.clone();
+        let video_size = video_size.clone();
+        use_effect_with(props.url.clone(), move |_| {
+            playback_failed.set(false);
+            video_size.set(None);
+            move || {
+                // Detaching the source closes the connection, a removed element would keep loading.
+                if let Some(video) = video_ref.cast::<HtmlVideoElement>() {
+                    let _ = video.pause();
+                    video.remove_attribute("src").ok();
+                    video.load();
+                }
+            }
+        });
+    }
+
+    let handle_close = {
+        let on_close = props.on_close.clone();
+        Callback::from(move |_| on_close.emit(()))
+    };
+
+    let handle_loaded_metadata = {
+        let video_ref = video_ref.clone();
+        let video_size = video_size.clone();
+        Callback::from(move |_: Event| {
+            if let Some(video) = video_ref.cast::<HtmlVideoElement>() {
+                video_size.set(Some((video.video_width(), video.video_height())));
+            }
+        })
+    };
+
+    let handle_error = {
+        let playback_failed = playback_failed.clone();
+        Callback::from(move |_: Event| playback_failed.set(true))
+    };
+
+    let stream = status_ctx
+        .status
+        .as_ref()
+        .and_then(|status| find_preview_stream(&status.active_user_streams, props.target_id, props.virtual_id));
+    let metrics_enabled = is_stream_metrics_enabled(&config_ctx);
+
+    let mut chips = stream
+        .map(|stream| build_technical_chips(stream.channel.item_type, stream.channel.technical.as_ref()))
+        .unwrap_or_default();
+    if let Some((width, height)) = *video_size {
+        if width > 0 && height > 0 {
+            chips.push((format!("{width}x{height}"), "tp__stream-display__chip--resolution"));
+        }
+    }
+
+    html! {
+        <div class="tp__preview-player">
+            <div class="tp__preview-player__header">
+                <span class="tp__preview-player__title">{props.title.clone()}</span>
+                <IconButton name="close" icon="Close" onclick={handle_close} />
+            </div>
+            <div class="tp__preview-player__body">
+                if *hls_unsupported {
+                    <div class="tp__preview-player__message">{translate.t("MESSAGES.PLAYLIST_EXPLORER.PREVIEW_HLS_UNSUPPORTED")}</div>
+                } else {
+                    <video
+                        ref={video_ref}
+                        class="tp__preview-player__video"
+                        src={props.url.clone()}
+                        controls={true}
+                        autoplay={true}
+                        playsinline={true}
+                        onloadedmetadata={handle_loaded_metadata}
+                        onerror={handle_error}
+                    />
+                    { html_if!(*playback_failed, {
+                        <div class="tp__preview-player__message">{translate.t("MESSAGES.PLAYLIST_EXPLORER.PREVIEW_FAILED")}</div>
+                    })}
+                }
+            </div>
+            <div class="tp__preview-player__info">
+                if let Some(stream) = stream {
+                    <div class="tp__stream-display__stat">
+                        <span class="tp__stream-display__stat-label">{translate.t("LABEL.PROVIDER")}</span>
+                        <span class="tp__stream-display__stat-value">{stream.provider.clone()}</span>
+                    </div>
+                    if metrics_enabled && stream.meter_uid != 0 {
+                        <div class="tp__stream-display__stat">
+                            <span class="tp__stream-display__stat-label">{translate.t("LABEL.BANDWIDTH")}</span>
+                            <span class="tp__stream-display__stat-value">
+                                <StreamMeterBadge uid={stream.uid} meter_uid={stream.meter_uid} kind={MeterDisplayKind::Bandwidth} />
+                            </span>
+                        </div>
+                    }
+                }
+                if !chips.is_empty() {
+                    <div class="tp__stream-display__chips">
+                        { for chips.into_iter().map(|(label, chip_class)| html! {
+                            <Chip label={label} class={Some(format!("tp__stream-display__chip {chip_class}"))} />
+                        })}
+                    </div>
+                }
+            </div>
+        </div>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_preview_stream, PREVIEW_USERNAME};
+    use shared::{
+        model::{PlaylistItemType, StreamChannel, StreamInfo, XtreamCluster},
+        utils::Internable,
+    };
+    use std::net::SocketAddr;
+
+    fn test_stream(uid: u32, username: &str, virtual_id: u32) -> StreamInfo {
+        StreamInfo {
+            uid,
+            meter_uid: 0,
+            username: username.to_string(),
+            channel: StreamChannel {
+                target_id: 1,
+                virtual_id,
+                provider_id: 0,
+                input_name: "input".intern(),
+                item_type: PlaylistItemType::Live,
+                cluster: XtreamCluster::Live,
+                group: "group".intern(),
+                title: "title".intern(),
+                url: "http://localhost/stream.ts".intern(),
+                shared: false,
+                shared_joined_existing: None,
+                shared_stream_id: None,
+                technical: None,
+                alternate_input: None,
+            },
+            provider: format!("provider-{uid}"),
+            addr: "127.0.0.1:8901".parse::<SocketAddr>().unwrap_or_else(|_| unreachable!()),
+            client_ip: "127.0.0.1".to_string(),
+            user_agent: String::new(),
+            ts: 0,
+            country_code: None,
+            session_token: None,
+            preserved: false,
+            previous_session_id: None,
+            throttle_kbps: 0,
+        }
+    }
+
+    #[test]
+    fn find_preview_stream_prefers_preview_user() {
+        let streams = vec![
+            test_stream(1, "alice", 42),
+            test_stream(2, PREVIEW_USERNAME, 42),
+            test_stream(3, PREVIEW_USERNAME, 7),
+        ];
+        assert_eq!(find_preview_stream(&streams, 1, 42).map(|stream| stream.uid), Some(2));
+        assert_eq!(find_preview_stream(&streams[..1], 1, 42).map(|stream| stream.uid), Some(1));
+        assert!(find_preview_stream(&streams, 2, 42).is_none());
+    }
+}
@@ -0,0 +1,49 @@
+use crate::{
+    app::components::{FilterView, RevealContent},
+    html_if,
+    i18n::use_translation,
+};
+use shared::model::CsvTargetOutputDto;
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct CsvOutputProps {
+    pub output: CsvTargetOutputDto,
+}
+
+#[component]
+pub fn CsvOutput(props: &CsvOutputProps) -> Html {
+    let translator = use_translation();
+
+    html! {
+      <div class="tp__csv-output tp__target-common">
+        { html_if!(props.output.t_filter.is_some(), {
+        <div class="tp__target-common__section">
+            <RevealContent preview={Some(html!{<FilterView inline={true} filter={props.output.t_filter.clone()} />})}><FilterView pretty={true} filter={props.output.t_filter.clone()} /></RevealContent>
+        </div>
+        }) }
+        <div class="tp__target-common__section tp__target-common__row">
+            <span class="tp__target-common__label">{translator.t("LABEL.USERNAME")}</span>
+            <span>{ props.output.username.clone() }</span>
+        </div>
+        { html_if!(props.output.filename.is_some(), {
+        <div class="tp__target-common__section tp__target-common__row">
+            <span class="tp__target-common__label">{translator.t("LABEL.FILENAME")}</span>
+            { props.output.filename.as_ref().map(|f| html! {<span>{ f }</span>}) }
+        </div>
+        }) }
+        { html_if!(props.output.columns.is_some(), {
+        <div class="tp__target-common__section tp__target-common__row">
+            <span class="tp__target-common__label">{translator.t("LABEL.COLUMNS")}</span>
+            { props.output.columns.as_ref().map(|c| html! {<span>{ c.join(", ") }</span>}) }
+        </div>
+        }) }
+        { html_if!(props.output.delimiter.is_some(), {
+        <div class="tp__target-common__section tp__target-common__row">
+            <span class="tp__target-common__label">{translator.t("LABEL.DELIMITER")}</span>
+            { props.output.delimiter.as_ref().map(|d| html! {<span>{ d }</span>}) }
+        </div>
+        }) }
+      </div>
+    }
+}
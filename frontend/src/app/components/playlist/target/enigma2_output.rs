@@ -0,0 +1,58 @@
+use crate::{
+    app::components::{convert_bool_to_chip_style, FilterView, RevealContent, Tag, TagList},
+    html_if,
+    i18n::use_translation,
+};
+use shared::model::Enigma2TargetOutputDto;
+use std::rc::Rc;
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct Enigma2OutputProps {
+    pub output: Enigma2TargetOutputDto,
+}
+
+#[component]
+pub fn Enigma2Output(props: &Enigma2OutputProps) -> Html {
+    let translator = use_translation();
+
+    let tags = {
+        let output = props.output.clone();
+        let translate = translator.clone();
+        use_memo(output, move |output| {
+            vec![
+                Rc::new(Tag { class: convert_bool_to_chip_style(output.include_vod), label: translate.t("LABEL.INCLUDE_VOD") }),
+                Rc::new(Tag { class: convert_bool_to_chip_style(output.epgimport), label: translate.t("LABEL.EPGIMPORT") }),
+            ]
+        })
+    };
+
+    html! {
+      <div class="tp__enigma2-output tp__target-common">
+        { html_if!(props.output.t_filter.is_some(), {
+        <div class="tp__target-common__section">
+            <RevealContent preview={Some(html!{<FilterView inline={true} filter={props.output.t_filter.clone()} />})}><FilterView pretty={true} filter={props.output.t_filter.clone()} /></RevealContent>
+        </div>
+        }) }
+        <div class="tp__target-common__section tp__target-common__row">
+            <span class="tp__target-common__label">{translator.t("LABEL.USERNAME")}</span>
+            <span>{ props.output.username.clone() }</span>
+        </div>
+        { html_if!(props.output.directory.is_some(), {
+        <div class="tp__target-common__section tp__target-common__row">
+            <span class="tp__target-common__label">{translator.t("LABEL.DIRECTORY")}</span>
+            { props.output.directory.as_ref().map(|d| html! {<span>{ d }</span>}) }
+        </div>
+        }) }
+        { html_if!(props.output.bouquet_prefix.is_some(), {
+        <div class="tp__target-common__section tp__target-common__row">
+            <span class="tp__target-common__label">{translator.t("LABEL.BOUQUET_PREFIX")}</span>
+            { props.output.bouquet_prefix.as_ref().map(|p| html! {<span>{ p }</span>}) }
+        </div>
+        }) }
+        <div class="tp__target-common__section">
+            <TagList tags={(*tags).clone()} />
+        </div>
+      </div>
+    }
+}
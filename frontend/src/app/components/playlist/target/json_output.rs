@@ -0,0 +1,37 @@
+use crate::{
+    app::components::{FilterView, RevealContent},
+    html_if,
+    i18n::use_translation,
+};
+use shared::model::JsonTargetOutputDto;
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct JsonOutputProps {
+    pub output: JsonTargetOutputDto,
+}
+
+#[component]
+pub fn JsonOutput(props: &JsonOutputProps) -> Html {
+    let translator = use_translation();
+
+    html! {
+      <div class="tp__json-output tp__target-common">
+        { html_if!(props.output.t_filter.is_some(), {
+        <div class="tp__target-common__section">
+            <RevealContent preview={Some(html!{<FilterView inline={true} filter={props.output.t_filter.clone()} />})}><FilterView pretty={true} filter={props.output.t_filter.clone()} /></RevealContent>
+        </div>
+        }) }
+        <div class="tp__target-common__section tp__target-common__row">
+            <span class="tp__target-common__label">{translator.t("LABEL.USERNAME")}</span>
+            <span>{ props.output.username.clone() }</span>
+        </div>
+        { html_if!(props.output.filename.is_some(), {
+        <div class="tp__target-common__section tp__target-common__row">
+            <span class="tp__target-common__label">{translator.t("LABEL.FILENAME")}</span>
+            { props.output.filename.as_ref().map(|f| html! {<span>{ f }</span>}) }
+        </div>
+        }) }
+      </div>
+    }
+}
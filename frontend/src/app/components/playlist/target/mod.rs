@@ -0,0 +1,19 @@
+mod csv_output;
+mod enigma2_output;
+mod hdhomerun_output;
+mod json_output;
+mod m3u_output;
+mod multicast_output;
+mod strm_output;
+mod target_options;
+mod target_output;
+mod target_rename;
+mod target_sort;
+mod target_watch;
+mod xtream_output;
+
+pub use self::{
+    csv_output::*, enigma2_output::*, hdhomerun_output::*, json_output::*, m3u_output::*, multicast_output::*,
+    strm_output::*, target_options::*, target_output::*, target_rename::*, target_sort::*, target_watch::*,
+    xtream_output::*,
+};
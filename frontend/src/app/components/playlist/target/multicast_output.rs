@@ -0,0 +1,61 @@
+use crate::{
+    app::components::{convert_bool_to_chip_style, Tag, TagList},
+    html_if,
+    i18n::use_translation,
+};
+use shared::model::MulticastTargetOutputDto;
+use std::rc::Rc;
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct MulticastOutputProps {
+    pub output: MulticastTargetOutputDto,
+}
+
+#[component]
+pub fn MulticastOutput(props: &MulticastOutputProps) -> Html {
+    let translator = use_translation();
+
+    let tags = {
+        let output = props.output.clone();
+        let translate = translator.clone();
+        use_memo(output, move |output| {
+            vec![
+                Rc::new(Tag { class: convert_bool_to_chip_style(output.rtp), label: translate.t("LABEL.RTP") }),
+                Rc::new(Tag { class: convert_bool_to_chip_style(output.always_on), label: translate.t("LABEL.ALWAYS_ON") }),
+            ]
+        })
+    };
+
+    html! {
+      <div class="tp__multicast-output tp__target-common">
+        { html_if!(props.output.interface.is_some(), {
+        <div class="tp__target-common__section tp__target-common__row">
+            <span class="tp__target-common__label">{translator.t("LABEL.INTERFACE")}</span>
+            { props.output.interface.as_ref().map(|i| html! {<span>{ i }</span>}) }
+        </div>
+        }) }
+        <div class="tp__target-common__section tp__target-common__row">
+            <span class="tp__target-common__label">{translator.t("LABEL.TTL")}</span>
+            <span>{ props.output.ttl }</span>
+        </div>
+        <div class="tp__target-common__section tp__target-common__row">
+            <span class="tp__target-common__label">{translator.t("LABEL.IDLE_TIMEOUT_SECS")}</span>
+            <span>{ props.output.idle_timeout_secs }</span>
+        </div>
+        <div class="tp__target-common__section">
+            <TagList tags={(*tags).clone()} />
+        </div>
+        { html_if!(!props.output.channels.is_empty(), {
+        <div class="tp__target-common__section">
+            <span class="tp__target-common__label">{translator.t("LABEL.CHANNELS")}</span>
+            <ul class="tp__multicast-output__channels">
+            { for props.output.channels.iter().map(|channel| html! {
+                <li><span>{ &channel.name }</span><span>{ &channel.address }</span></li>
+            }) }
+            </ul>
+        </div>
+        }) }
+      </div>
+    }
+}
@@ -0,0 +1,3 @@
+mod reseller_view;
+
+pub(crate) use self::reseller_view::*;
@@ -0,0 +1,355 @@
+use crate::{
+    app::components::{
+        input::Input, number_input::NumberInput, DropDownOption, DropDownSelection, IconButton, Select, TextButton,
+    },
+    hooks::use_service_context,
+    i18n::use_translation,
+    utils::{format_bytes, format_duration},
+};
+use shared::{
+    model::{
+        ResellerAccountDto, ResellerStreamDto, ResellerStreamHistoryDto, ResellerUserCreateDto, ResellerUserDto,
+        RESELLER_MAX_MONTHS,
+    },
+    utils::unix_ts_to_str,
+};
+use std::rc::Rc;
+use yew::{platform::spawn_local, prelude::*};
+
+/// Days of stream history shown in the reseller view.
+const HISTORY_DAYS: u64 = 7;
+
+fn format_unix_ts(ts: u64) -> String { i64::try_from(ts).ok().and_then(unix_ts_to_str).unwrap_or_default() }
+
+fn to_months(value: Option<i64>) -> u32 {
+    value.and_then(|v| u32::try_from(v).ok()).unwrap_or(1).clamp(1, RESELLER_MAX_MONTHS)
+}
+
+#[component]
+pub fn ResellerView() -> Html {
+    let translate = use_translation();
+    let services = use_service_context();
+    let account = use_state(|| None::<Rc<ResellerAccountDto>>);
+    let streams = use_state(Vec::<ResellerStreamDto>::new);
+    let history = use_state(Vec::<ResellerStreamHistoryDto>::new);
+    let new_user = use_state(|| ResellerUserCreateDto { max_connections: 1, months: 1, ..Default::default() });
+    let extend_months = use_state(|| 1u32);
+
+    let load = {
+        let services = services.clone();
+        let account = account.clone();
+        let streams = streams.clone();
+        let history = history.clone();
+        Callback::from(move |()| {
+            let services = services.clone();
+            let account = account.clone();
+            let streams = streams.clone();
+            let history = history.clone();
+            spawn_local(async move {
+                match services.reseller.get_account().await {
+                    Ok(result) => account.set(result),
+                    Err(err) => services.toastr.error(err.to_string()),
+                }
+                if let Ok(list) = services.reseller.get_streams().await {
+                    streams.set(list);
+                }
+                if let Ok(list) = services.reseller.get_history(HISTORY_DAYS).await {
+                    history.set(list);
+                }
+            });
+        })
+    };
+
+    {
+        let load = load.clone();
+        use_effect_with((), move |()| {
+            load.emit(());
+            || ()
+        });
+    }
+
+    let handle_refresh = {
+        let load = load.clone();
+        Callback::from(move |_name: String| load.emit(()))
+    };
+
+    let handle_create = {
+        let services = services.clone();
+        let translate = translate.clone();
+        let load = load.clone();
+        let new_user = new_user.clone();
+        let account = account.clone();
+        Callback::from(move |_name: String| {
+            let mut user = (*new_user).clone();
+            if user.target.is_empty() {
+                if let Some(target) = account.as_ref().and_then(|a| a.targets.first()) {
+                    user.target.clone_from(target);
+                }
+            }
+            user.prepare();
+            if let Err(err) = user.validate() {
+                services.toastr.error(err.to_string());
+                return;
+            }
+            let services = services.clone();
+            let translate = translate.clone();
+            let load = load.clone();
+            let new_user = new_user.clone();
+            spawn_local(async move {
+                match services.reseller.create_user(&user).await {
+                    Ok(()) => {
+                        services.toastr.success(translate.t("MESSAGES.RESELLER.USER_CREATED"));
+                        new_user.set(ResellerUserCreateDto {
+                            target: user.target,
+                            max_connections: 1,
+                            months: 1,
+                            ..Default::default()
+                        });
+                        load.emit(());
+                    }
+                    Err(err) => services.toastr.error(err.to_string()),
+                }
+            });
+        })
+    };
+
+    let handle_extend = {
+        let services = services.clone();
+        let translate = translate.clone();
+        let load = load.clone();
+        let extend_months = extend_months.clone();
+        Callback::from(move |(name, _event): (String, MouseEvent)| {
+            let services = services.clone();
+            let translate = translate.clone();
+            let load = load.clone();
+            let months = *extend_months;
+            spawn_local(async move {
+                match services.reseller.extend_user(&name, months).await {
+                    Ok(()) => {
+                        services.toastr.success(translate.t("MESSAGES.RESELLER.USER_EXTENDED"));
+                        load.emit(());
+                    }
+                    Err(err) => services.toastr.error(err.to_string()),
+                }
+            });
+        })
+    };
+
+    let set_text = |update: fn(&mut ResellerUserCreateDto, String)| {
+        let new_user = new_user.clone();
+        Callback::from(move |value: String| {
+            let mut user = (*new_user).clone();
+            update(&mut user, value);
+            new_user.set(user);
+        })
+    };
+
+    let set_number = |update: fn(&mut ResellerUserCreateDto, Option<i64>)| {
+        let new_user = new_user.clone();
+        Callback::from(move |value: Option<i64>| {
+            let mut user = (*new_user).clone();
+            update(&mut user, value);
+            new_user.set(user);
+        })
+    };
+
+    let handle_target_select = {
+        let new_user = new_user.clone();
+        Callback::from(move |(_name, selection): (String, DropDownSelection)| {
+            if let DropDownSelection::Single(target) = selection {
+                let mut user = (*new_user).clone();
+                user.target = target;
+                new_user.set(user);
+            }
+        })
+    };
+
+    let handle_extend_months = {
+        let extend_months = extend_months.clone();
+        Callback::from(move |value: Option<i64>| extend_months.set(to_months(value)))
+    };
+
+    let render_field = |label: &str, value: String| {
+        html! {
+            <div class="tp__reseller-view__field">
+                <label>{ translate.t(label) }</label>
+                <span>{ value }</span>
+            </div>
+        }
+    };
+
+    let render_user = |user: &ResellerUserDto| {
+        html! {
+            <tr key={user.username.clone()}>
+                <td>{ user.username.as_str() }</td>
+                <td>{ user.password.as_str() }</td>
+                <td>{ user.target.as_str() }</td>
+                <td>{ user.status.map(|s| s.to_string()).unwrap_or_default() }</td>
+                <td>{ user.exp_date.and_then(unix_ts_to_str).unwrap_or_default() }</td>
+                <td>{ format!("{} / {}", user.active_connections, user.max_connections) }</td>
+                <td>{ user.comment.as_deref().unwrap_or_default() }</td>
+                <td>
+                    <IconButton name={user.username.clone()} icon="ScheduleAdd"
+                        hint={translate.t("LABEL.EXTEND")} onclick={&handle_extend} />
+                </td>
+            </tr>
+        }
+    };
+
+    let render_stream = |stream: &ResellerStreamDto| {
+        html! {
+            <tr key={stream.stream.uid.to_string()}>
+                <td>{ stream.username.as_str() }</td>
+                <td>{ stream.stream.title.as_str() }</td>
+                <td>{ stream.stream.item_type.to_string() }</td>
+                <td>{ stream.stream.client_ip.as_str() }</td>
+                <td>{ format_unix_ts(stream.stream.ts) }</td>
+            </tr>
+        }
+    };
+
+    let render_history = |(idx, entry): (usize, &ResellerStreamHistoryDto)| {
+        html! {
+            <tr key={idx.to_string()}>
+                <td>{ format_unix_ts(entry.session.ts) }</td>
+                <td>{ entry.username.as_str() }</td>
+                <td>{ entry.session.title.as_deref().unwrap_or_default() }</td>
+                <td>{ entry.session.duration_secs.map(format_duration).unwrap_or_default() }</td>
+                <td>{ entry.session.bytes_sent.map(format_bytes).unwrap_or_default() }</td>
+            </tr>
+        }
+    };
+
+    let render_create_form = |account: &ResellerAccountDto| {
+        let target_options = Rc::new(
+            account
+                .targets
+                .iter()
+                .enumerate()
+                .map(|(idx, target)| {
+                    let selected = if new_user.target.is_empty() { idx == 0 } else { *target == new_user.target };
+                    DropDownOption::new(target, html! { target }, selected)
+                })
+                .collect::<Vec<_>>(),
+        );
+        html! {
+            <div class="tp__reseller-view__section">
+                <h2>{ translate.t("LABEL.CREATE_USER") }</h2>
+                <div class="tp__reseller-view__field">
+                    <label>{ translate.t("LABEL.TARGET") }</label>
+                    <Select name="target" options={target_options} on_select={handle_target_select.clone()} />
+                </div>
+                <Input name="username" label={Some(translate.t("LABEL.USERNAME"))} value={new_user.username.clone()}
+                    on_change={Some(set_text(|user, value| user.username = value))} />
+                <Input name="password" label={Some(translate.t("LABEL.PASSWORD"))} value={new_user.password.clone()}
+                    on_change={Some(set_text(|user, value| user.password = value))} />
+                <NumberInput name="max_connections" label={Some(translate.t("LABEL.MAX_CONNECTIONS"))}
+                    value={Some(i64::from(new_user.max_connections))}
+                    on_change={set_number(|user, value| {
+                        user.max_connections = value.and_then(|v| u32::try_from(v).ok()).unwrap_or_default();
+                    })} />
+                <NumberInput name="months" label={Some(translate.t("LABEL.MONTHS"))}
+                    value={Some(i64::from(new_user.months))}
+                    on_change={set_number(|user, value| user.months = to_months(value))} />
+                <Input name="comment" label={Some(translate.t("LABEL.COMMENT"))}
+                    value={new_user.comment.clone().unwrap_or_default()}
+                    on_change={Some(set_text(|user, value| user.comment = Some(value)))} />
+                <TextButton class="primary" name="create_user" icon="UserAdd"
+                    title={translate.t("LABEL.CREATE_USER")} onclick={handle_create.clone()} />
+            </div>
+        }
+    };
+
+    let render_account = |account: &ResellerAccountDto| {
+        html! {
+            <>
+            <div class="tp__reseller-view__section">
+                <h2>{ translate.t("LABEL.ACCOUNT") }</h2>
+                { render_field("LABEL.CREDITS", account.credits.to_string()) }
+                { render_field("LABEL.MAX_CONNECTIONS", account.max_connections.to_string()) }
+                { render_field("LABEL.TARGETS", account.targets.join(", ")) }
+            </div>
+            { render_create_form(account) }
+            <div class="tp__reseller-view__section">
+                <h2>{ translate.t("LABEL.USERS") }</h2>
+                <NumberInput name="extend_months" label={Some(translate.t("LABEL.EXTEND_MONTHS"))}
+                    value={Some(i64::from(*extend_months))} on_change={handle_extend_months.clone()} />
+                <table>
+                    <thead>
+                        <tr>
+                            <th>{ translate.t("LABEL.USERNAME") }</th>
+                            <th>{ translate.t("LABEL.PASSWORD") }</th>
+                            <th>{ translate.t("LABEL.TARGET") }</th>
+                            <th>{ translate.t("LABEL.STATUS") }</th>
+                            <th>{ translate.t("LABEL.EXP_DATE") }</th>
+                            <th>{ translate.t("LABEL.CONNECTIONS") }</th>
+                            <th>{ translate.t("LABEL.COMMENT") }</th>
+                            <th></th>
+                        </tr>
+                    </thead>
+                    <tbody>{ for account.users.iter().map(render_user) }</tbody>
+                </table>
+            </div>
+            </>
+        }
+    };
+
+    html! {
+        <div class="tp__reseller-view">
+            <div class="tp__reseller-view__header">
+                <h1>{ translate.t("TITLE.RESELLER") }</h1>
+                <TextButton class="secondary" name="refresh" icon="Refresh"
+                    title={translate.t("LABEL.REFRESH")} onclick={handle_refresh} />
+            </div>
+            <div class="tp__reseller-view__body">
+                { account.as_ref().map_or_else(Html::default, |account| render_account(account)) }
+                <div class="tp__reseller-view__section">
+                    <h2>{ translate.t("LABEL.ACTIVE_STREAMS") }</h2>
+                    {
+                        if streams.is_empty() {
+                            html! { <span>{ translate.t("MESSAGES.NO_ACTIVE_STREAMS") }</span> }
+                        } else {
+                            html! {
+                                <table>
+                                    <thead>
+                                        <tr>
+                                            <th>{ translate.t("LABEL.USERNAME") }</th>
+                                            <th>{ translate.t("LABEL.TITLE") }</th>
+                                            <th>{ translate.t("LABEL.TYPE") }</th>
+                                            <th>{ translate.t("LABEL.CLIENT_IP") }</th>
+                                            <th>{ translate.t("LABEL.STARTED") }</th>
+                                        </tr>
+                                    </thead>
+                                    <tbody>{ for streams.iter().map(render_stream) }</tbody>
+                                </table>
+                            }
+                        }
+                    }
+                </div>
+                <div class="tp__reseller-view__section">
+                    <h2>{ translate.t("LABEL.STREAM_HISTORY") }</h2>
+                    {
+                        if history.is_empty() {
+                            html! { <span>{ translate.t("MESSAGES.NO_STREAM_HISTORY") }</span> }
+                        } else {
+                            html! {
+                                <table>
+                                    <thead>
+                                        <tr>
+                                            <th>{ translate.t("LABEL.STARTED") }</th>
+                                            <th>{ translate.t("LABEL.USERNAME") }</th>
+                                            <th>{ translate.t("LABEL.TITLE") }</th>
+                                            <th>{ translate.t("LABEL.DURATION") }</th>
+                                            <th>{ translate.t("LABEL.TRAFFIC") }</th>
+                                        </tr>
+                                    </thead>
+                                    <tbody>{ for history.iter().enumerate().map(render_history) }</tbody>
+                                </table>
+                            }
+                        }
+                    }
+                </div>
+            </div>
+        </div>
+    }
+}
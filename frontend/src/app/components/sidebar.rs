@@ -148,6 +148,9 @@ pub fn Sidebar(props: &SidebarProps) -> Html {
             {html_if!(auth.has_permission(Permission::DownloadRead), {
                 <MenuItem class={if *active_menu == ViewType::Downloads { "active" } else {""}} icon="Download" name={ViewType::Downloads.to_string()} label={translate.t("LABEL.DOWNLOADS")} onclick={&handle_menu_click}></MenuItem>
              })}
+            {html_if!(auth.is_reseller(), {
+                <MenuItem class={if *active_menu == ViewType::Reseller { "active" } else {""}} icon="Group" name={ViewType::Reseller.to_string()} label={translate.t("LABEL.RESELLER")} onclick={&handle_menu_click}></MenuItem>
+             })}
             {html_if!(
                 auth.has_any_permissions(Permission::ConfigRead | Permission::SourceRead | Permission::UserRead),
                 {
@@ -207,6 +210,9 @@ pub fn Sidebar(props: &SidebarProps) -> Html {
             {html_if!(auth.has_permission(Permission::DownloadRead), {
                 <IconButton class={format!("tp__app-sidebar-menu--{}{}", ViewType::Downloads, if *active_menu == ViewType::Downloads { " active" } else {""})} icon="Download" name={ViewType::Downloads.to_string()} onclick={&handle_menu_click}></IconButton>
             })}
+            {html_if!(auth.is_reseller(), {
+                <IconButton class={format!("tp__app-sidebar-menu--{}{}", ViewType::Reseller, if *active_menu == ViewType::Reseller { " active" } else {""})} icon="Group" name={ViewType::Reseller.to_string()} onclick={&handle_menu_click}></IconButton>
+            })}
             {html_if!(
                 auth.has_any_permissions(Permission::ConfigRead | Permission::SourceRead | Permission::UserRead),
                 {
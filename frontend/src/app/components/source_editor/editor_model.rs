@@ -14,11 +14,16 @@ pub enum BlockType {
     InputXtream,
     InputM3u,
     InputLibrary,
+    InputIngest,
     Target,
     OutputM3u,
     OutputXtream,
     OutputHdHomeRun,
     OutputStrm,
+    OutputEnigma2,
+    OutputJson,
+    OutputCsv,
+    OutputMulticast,
 }
 
 // Define string constants
@@ -26,18 +31,35 @@ impl BlockType {
     pub const INPUT_XTREAM: &'static str = "InputXtream";
     pub const INPUT_M3U: &'static str = "InputM3u";
     pub const INPUT_LIBRARY: &'static str = "InputLibrary";
+    pub const INPUT_INGEST: &'static str = "InputIngest";
     pub const TARGET: &'static str = "Target";
     pub const OUTPUT_M3U: &'static str = "OutputM3u";
     pub const OUTPUT_XTREAM: &'static str = "OutputXtream";
     pub const OUTPUT_HDHOMERUN: &'static str = "OutputHdHomeRun";
     pub const OUTPUT_STRM: &'static str = "OutputStrm";
+    pub const OUTPUT_ENIGMA2: &'static str = "OutputEnigma2";
+    pub const OUTPUT_JSON: &'static str = "OutputJson";
+    pub const OUTPUT_CSV: &'static str = "OutputCsv";
+    pub const OUTPUT_MULTICAST: &'static str = "OutputMulticast";
 
-    pub fn is_input(&self) -> bool { matches!(self, Self::InputXtream | Self::InputM3u | Self::InputLibrary) }
+    pub fn is_input(&self) -> bool {
+        matches!(self, Self::InputXtream | Self::InputM3u | Self::InputLibrary | Self::InputIngest)
+    }
 
     pub fn is_target(&self) -> bool { matches!(self, Self::Target) }
 
     pub fn is_output(&self) -> bool {
-        matches!(self, Self::OutputXtream | Self::OutputM3u | Self::OutputHdHomeRun | Self::OutputStrm)
+        matches!(
+            self,
+            Self::OutputXtream
+                | Self::OutputM3u
+                | Self::OutputHdHomeRun
+                | Self::OutputStrm
+                | Self::OutputEnigma2
+                | Self::OutputJson
+                | Self::OutputCsv
+                | Self::OutputMulticast
+        )
     }
 }
 
@@ -48,11 +70,16 @@ impl From<&str> for BlockType {
             BlockType::INPUT_XTREAM => BlockType::InputXtream,
             BlockType::INPUT_M3U => BlockType::InputM3u,
             BlockType::INPUT_LIBRARY => BlockType::InputLibrary,
+            BlockType::INPUT_INGEST => BlockType::InputIngest,
             BlockType::TARGET => BlockType::Target,
             BlockType::OUTPUT_M3U => BlockType::OutputM3u,
             BlockType::OUTPUT_XTREAM => BlockType::OutputXtream,
             BlockType::OUTPUT_HDHOMERUN => BlockType::OutputHdHomeRun,
             BlockType::OUTPUT_STRM => BlockType::OutputStrm,
+            BlockType::OUTPUT_ENIGMA2 => BlockType::OutputEnigma2,
+            BlockType::OUTPUT_JSON => BlockType::OutputJson,
+            BlockType::OUTPUT_CSV => BlockType::OutputCsv,
+            BlockType::OUTPUT_MULTICAST => BlockType::OutputMulticast,
             _ => BlockType::Target, // fallback
         }
     }
@@ -68,6 +95,7 @@ impl From<InputType> for BlockType {
             InputType::M3uBatch | InputType::M3u => BlockType::InputM3u,
             InputType::XtreamBatch | InputType::Xtream => BlockType::InputXtream,
             InputType::Library => BlockType::InputLibrary,
+            InputType::Ingest => BlockType::InputIngest,
         }
     }
 }
@@ -79,11 +107,16 @@ impl fmt::Display for BlockType {
             BlockType::InputXtream => Self::INPUT_XTREAM,
             BlockType::InputM3u => Self::INPUT_M3U,
             BlockType::InputLibrary => Self::INPUT_LIBRARY,
+            BlockType::InputIngest => Self::INPUT_INGEST,
             BlockType::Target => Self::TARGET,
             BlockType::OutputM3u => Self::OUTPUT_M3U,
             BlockType::OutputXtream => Self::OUTPUT_XTREAM,
             BlockType::OutputHdHomeRun => Self::OUTPUT_HDHOMERUN,
             BlockType::OutputStrm => Self::OUTPUT_STRM,
+            BlockType::OutputEnigma2 => Self::OUTPUT_ENIGMA2,
+            BlockType::OutputJson => Self::OUTPUT_JSON,
+            BlockType::OutputCsv => Self::OUTPUT_CSV,
+            BlockType::OutputMulticast => Self::OUTPUT_MULTICAST,
         };
         write!(f, "{}", s)
     }
@@ -15,7 +15,8 @@ use crate::{
 use gloo_timers::callback::Timeout;
 use shared::{
     model::{
-        permission::Permission, ConfigInputDto, ConfigSourceDto, ConfigTargetDto, HdHomeRunTargetOutputDto, InputType,
+        permission::Permission, ConfigInputDto, ConfigSourceDto, ConfigTargetDto, CsvTargetOutputDto, Enigma2TargetOutputDto, HdHomeRunTargetOutputDto, InputType,
+        JsonTargetOutputDto, MulticastTargetOutputDto,
         M3uTargetOutputDto, SourcesConfigDto, StrmTargetOutputDto, TargetOutputDto, XtreamTargetOutputDto,
     },
     utils::BATCH_SCHEME_PREFIX,
@@ -268,6 +269,7 @@ fn create_instance(block_type: BlockType) -> BlockInstance {
         BlockType::InputXtream => BlockInstance::Input(Rc::new(ConfigInputDto::new_with_type(InputType::Xtream))),
         BlockType::InputM3u => BlockInstance::Input(Rc::new(ConfigInputDto::new_with_type(InputType::M3u))),
         BlockType::InputLibrary => BlockInstance::Input(Rc::new(ConfigInputDto::new_with_type(InputType::Library))),
+        BlockType::InputIngest => BlockInstance::Input(Rc::new(ConfigInputDto::new_with_type(InputType::Ingest))),
         BlockType::Target => {
             let dto = ConfigTargetDto {
                 name: String::new(),
@@ -284,6 +286,14 @@ fn create_instance(block_type: BlockType) -> BlockInstance {
             BlockInstance::Output(Rc::new(TargetOutputDto::HdHomeRun(HdHomeRunTargetOutputDto::default())))
         }
         BlockType::OutputStrm => BlockInstance::Output(Rc::new(TargetOutputDto::Strm(StrmTargetOutputDto::default()))),
+        BlockType::OutputEnigma2 => {
+            BlockInstance::Output(Rc::new(TargetOutputDto::Enigma2(Enigma2TargetOutputDto::default())))
+        }
+        BlockType::OutputJson => BlockInstance::Output(Rc::new(TargetOutputDto::Json(JsonTargetOutputDto::default()))),
+        BlockType::OutputCsv => BlockInstance::Output(Rc::new(TargetOutputDto::Csv(CsvTargetOutputDto::default()))),
+        BlockType::OutputMulticast => {
+            BlockInstance::Output(Rc::new(TargetOutputDto::Multicast(MulticastTargetOutputDto::default())))
+        }
     }
 }
 
@@ -305,6 +315,18 @@ fn create_output_instance(output: &TargetOutputDto) -> (BlockInstance, BlockType
         TargetOutputDto::HdHomeRun(dto) => {
             (BlockInstance::Output(Rc::new(TargetOutputDto::HdHomeRun(dto.clone()))), BlockType::OutputHdHomeRun)
         }
+        TargetOutputDto::Enigma2(dto) => {
+            (BlockInstance::Output(Rc::new(TargetOutputDto::Enigma2(dto.clone()))), BlockType::OutputEnigma2)
+        }
+        TargetOutputDto::Json(dto) => {
+            (BlockInstance::Output(Rc::new(TargetOutputDto::Json(dto.clone()))), BlockType::OutputJson)
+        }
+        TargetOutputDto::Csv(dto) => {
+            (BlockInstance::Output(Rc::new(TargetOutputDto::Csv(dto.clone()))), BlockType::OutputCsv)
+        }
+        TargetOutputDto::Multicast(dto) => {
+            (BlockInstance::Output(Rc::new(TargetOutputDto::Multicast(dto.clone()))), BlockType::OutputMulticast)
+        }
     }
 }
 
@@ -320,6 +342,9 @@ fn normalize_input_type_by_url(input: &mut ConfigInputDto, block_type: BlockType
         BlockType::InputLibrary => {
             input.input_type = InputType::Library;
         }
+        BlockType::InputIngest => {
+            input.input_type = InputType::Ingest;
+        }
         _ => {}
     }
 }
@@ -320,7 +320,8 @@ pub fn ConfigInputView(props: &ConfigInputViewProps) -> Html {
                 .and_then(|cfg| cfg.sources.provider.clone())
                 .unwrap_or_default();
             if let Some(input) = cfg {
-                if input.input_type.is_library() && matches!(*view_visible, InputFormPage::Staged | InputFormPage::Epg)
+                if (input.input_type.is_library() || input.input_type.is_ingest())
+                    && matches!(*view_visible, InputFormPage::Staged | InputFormPage::Epg)
                 {
                     view_visible.set(InputFormPage::Main);
                 }
@@ -675,7 +676,9 @@ pub fn ConfigInputView(props: &ConfigInputViewProps) -> Html {
         })
     };
 
-    let library_input = input_form_state.form.input_type.is_library();
+    // library and ingest inputs have no provider
+    let local_input =
+        input_form_state.form.input_type.is_library() || input_form_state.form.input_type.is_ingest();
     let xtream_input = input_form_state.form.input_type.is_xtream();
 
     let render_options = || {
@@ -1044,7 +1047,7 @@ pub fn ConfigInputView(props: &ConfigInputViewProps) -> Html {
                    { config_field!(input_form_state.form, translate.t(LABEL_NAME), name) }
                    { config_field_bool!(input_form_state.form, translate.t(LABEL_ENABLED), enabled) }
                    </div>
-                   { html_if!(!library_input, {
+                   { html_if!(!local_input, {
                     <>
                      { config_field!(input_form_state.form, translate.t(LABEL_URL), url) }
                      <div class="tp__config-view__cols-2">
@@ -1072,7 +1075,7 @@ pub fn ConfigInputView(props: &ConfigInputViewProps) -> Html {
                { edit_field_text!(input_form_state, translate.t(LABEL_NAME),  name, ConfigInputFormAction::Name) }
                { edit_field_bool!(input_form_state, translate.t(LABEL_ENABLED), enabled, ConfigInputFormAction::Enabled) }
                </div>
-               { html_if!(!library_input, {
+               { html_if!(!local_input, {
                 <>
                  { edit_field_text!(input_form_state, translate.t(LABEL_URL),  url, ConfigInputFormAction::Url) }
                  <div class="tp__config-view__cols-2">
@@ -1486,12 +1489,12 @@ pub fn ConfigInputView(props: &ConfigInputViewProps) -> Html {
                 <Panel value={InputFormPage::Main.to_string()} active={view_visible.to_string()}>
                 {render_input()}
                 </Panel>
-                { html_if!(!library_input, {
+                { html_if!(!local_input, {
                     <Panel value={InputFormPage::Alias.to_string()} active={view_visible.to_string()}>
                     {render_alias()}
                     </Panel>
                 })}
-                { html_if!(!library_input, {
+                { html_if!(!local_input, {
                  <>
                   <Panel value={InputFormPage::Options.to_string()} active={view_visible.to_string()}>
                    {render_options()}
@@ -1519,10 +1522,10 @@ pub fn ConfigInputView(props: &ConfigInputViewProps) -> Html {
         html! {
             <div class={concat_string!("tp__source-editor-form__sidebar", if button_disabled {" disabled"} else {""})}>
             <IconButton class={format!("tp__app-sidebar-menu--{}{}", InputFormPage::Main, if *view_visible == InputFormPage::Main { " active" } else {""})}  icon="Settings" hint={translate.t(LABEL_MAIN)} name={InputFormPage::Main.to_string()} onclick={&handle_menu_click}></IconButton>
-            {html_if!(!library_input, {
+            {html_if!(!local_input, {
             <IconButton class={format!("tp__app-sidebar-menu--{}{}", InputFormPage::Alias, if *view_visible == InputFormPage::Alias { " active" } else {""})}  icon="Alias" hint={translate.t(LABEL_ALIAS)} name={InputFormPage::Alias.to_string()} onclick={&handle_menu_click}></IconButton>
             })}
-            { html_if!(!library_input, {
+            { html_if!(!local_input, {
                 <>
             <IconButton class={format!("tp__app-sidebar-menu--{}{}", InputFormPage::Options, if *view_visible == InputFormPage::Options { " active" } else {""})}  icon="Options" hint={translate.t(LABEL_OPTIONS)} name={InputFormPage::Options.to_string()} onclick={&handle_menu_click}></IconButton>
             <IconButton class={format!("tp__app-sidebar-menu--{}{}", InputFormPage::Epg, if *view_visible == InputFormPage::Epg { " active" } else {""})}  icon="Epg" hint={translate.t(LABEL_EPG)} name={InputFormPage::Epg.to_string()} onclick={&handle_menu_click}></IconButton>
@@ -8,8 +8,12 @@ mod epg_source_item_form;
 mod input_form;
 mod layout;
 mod output_form;
+mod output_csv_form;
+mod output_enigma2_form;
 mod output_hdhomerun_form;
+mod output_json_form;
 mod output_m3u_form;
+mod output_multicast_form;
 mod output_strm_form;
 mod output_xtream_form;
 mod provider_item_form;
@@ -27,8 +31,12 @@ pub(crate) use epg_smart_match_form::*;
 pub(crate) use epg_source_item_form::*;
 pub(crate) use input_form::*;
 pub(crate) use output_form::*;
+pub(crate) use output_csv_form::*;
+pub(crate) use output_enigma2_form::*;
 pub(crate) use output_hdhomerun_form::*;
+pub(crate) use output_json_form::*;
 pub(crate) use output_m3u_form::*;
+pub(crate) use output_multicast_form::*;
 pub(crate) use output_strm_form::*;
 pub(crate) use output_xtream_form::*;
 pub(crate) use provider_item_form::*;
@@ -0,0 +1,138 @@
+use crate::{
+    app::components::{
+        config::HasFormData, BlockId, BlockInstance, Card, EditMode, FilterInput, SourceEditorContext, TextButton,
+    },
+    config_field, config_field_bool, config_field_child, config_field_custom, config_field_optional, edit_field_bool,
+    edit_field_text, edit_field_text_option, generate_form_reducer,
+    i18n::use_translation,
+};
+use shared::model::{Enigma2TargetOutputDto, TargetOutputDto};
+use std::rc::Rc;
+use yew::{component, html, use_context, use_effect_with, use_reducer, Callback, Html, Properties, UseReducerHandle};
+
+const LABEL_USERNAME: &str = "LABEL.USERNAME";
+const LABEL_DIRECTORY: &str = "LABEL.DIRECTORY";
+const LABEL_BOUQUET_PREFIX: &str = "LABEL.BOUQUET_PREFIX";
+const LABEL_INCLUDE_VOD: &str = "LABEL.INCLUDE_VOD";
+const LABEL_EPGIMPORT: &str = "LABEL.EPGIMPORT";
+const LABEL_FILTER: &str = "LABEL.FILTER";
+
+generate_form_reducer!(
+    state: Enigma2TargetOutputFormState { form: Enigma2TargetOutputDto },
+    action_name: Enigma2TargetOutputFormAction,
+    fields {
+        Username => username: String,
+        Directory => directory: Option<String>,
+        BouquetPrefix => bouquet_prefix: Option<String>,
+        IncludeVod => include_vod: bool,
+        Epgimport => epgimport: bool,
+        Filter => filter: Option<String>,
+    }
+);
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct Enigma2TargetOutputViewProps {
+    pub(crate) block_id: BlockId,
+    pub(crate) output: Option<Rc<Enigma2TargetOutputDto>>,
+    #[prop_or(true)]
+    pub(crate) allow_write: bool,
+}
+
+#[component]
+pub fn Enigma2TargetOutputView(props: &Enigma2TargetOutputViewProps) -> Html {
+    let translate = use_translation();
+    let source_editor_ctx = use_context::<SourceEditorContext>().expect("SourceEditorContext not found");
+
+    let output_form_state: UseReducerHandle<Enigma2TargetOutputFormState> =
+        use_reducer(|| Enigma2TargetOutputFormState { form: Enigma2TargetOutputDto::default(), modified: false });
+
+    {
+        let output_form_state = output_form_state.clone();
+        let config_output = props.output.clone();
+
+        use_effect_with(config_output, move |cfg| {
+            if let Some(output) = cfg {
+                output_form_state.dispatch(Enigma2TargetOutputFormAction::SetAll(output.as_ref().clone()));
+            } else {
+                output_form_state.dispatch(Enigma2TargetOutputFormAction::SetAll(Enigma2TargetOutputDto::default()));
+            }
+            || ()
+        });
+    }
+
+    let render_output = || {
+        if !props.allow_write {
+            html! {
+                <Card class="tp__config-view__card">
+                    { config_field!(output_form_state.form, translate.t(LABEL_USERNAME), username) }
+                    { config_field_optional!(output_form_state.form, translate.t(LABEL_DIRECTORY), directory) }
+                    { config_field_optional!(output_form_state.form, translate.t(LABEL_BOUQUET_PREFIX), bouquet_prefix) }
+                    { config_field_bool!(output_form_state.form, translate.t(LABEL_INCLUDE_VOD), include_vod) }
+                    { config_field_bool!(output_form_state.form, translate.t(LABEL_EPGIMPORT), epgimport) }
+                    { config_field_custom!(
+                        translate.t(LABEL_FILTER),
+                        output_form_state.form.filter.clone().unwrap_or_default()
+                    ) }
+                </Card>
+            }
+        } else {
+            let output_form_state_1 = output_form_state.clone();
+            html! {
+                <Card class="tp__config-view__card">
+                    { edit_field_text!(output_form_state, translate.t(LABEL_USERNAME), username, Enigma2TargetOutputFormAction::Username) }
+                    { edit_field_text_option!(output_form_state, translate.t(LABEL_DIRECTORY), directory, Enigma2TargetOutputFormAction::Directory) }
+                    { edit_field_text_option!(output_form_state, translate.t(LABEL_BOUQUET_PREFIX), bouquet_prefix, Enigma2TargetOutputFormAction::BouquetPrefix) }
+                    { edit_field_bool!(output_form_state, translate.t(LABEL_INCLUDE_VOD), include_vod, Enigma2TargetOutputFormAction::IncludeVod) }
+                    { edit_field_bool!(output_form_state, translate.t(LABEL_EPGIMPORT), epgimport, Enigma2TargetOutputFormAction::Epgimport) }
+                    { config_field_child!(translate.t(LABEL_FILTER), "OUTPUT_ENIGMA2_FORM.FILTER", {
+                       html! {
+                            <FilterInput filter={output_form_state_1.form.filter.clone()} on_change={Callback::from(move |new_filter| {
+                                output_form_state_1.dispatch(Enigma2TargetOutputFormAction::Filter(new_filter));
+                            })} />
+                       }
+                    })}
+                </Card>
+            }
+        }
+    };
+
+    let handle_apply = {
+        let source_editor_ctx = source_editor_ctx.clone();
+        let output_form_state = output_form_state.clone();
+        let block_id = props.block_id;
+        Callback::from(move |_| {
+            let output = output_form_state.data().clone();
+            source_editor_ctx
+                .on_form_change
+                .emit((block_id, BlockInstance::Output(Rc::new(TargetOutputDto::Enigma2(output)))));
+            source_editor_ctx.edit_mode.set(EditMode::Inactive);
+        })
+    };
+
+    let handle_cancel = {
+        let source_editor_ctx = source_editor_ctx.clone();
+        Callback::from(move |_| {
+            source_editor_ctx.edit_mode.set(EditMode::Inactive);
+        })
+    };
+
+    html! {
+        <div class="tp__source-editor-form tp__config-view-page">
+            <div class="tp__source-editor-form__toolbar tp__form-page__toolbar">
+                <TextButton class="secondary" name="cancel_enigma2_output"
+                    icon="Cancel"
+                    title={ translate.t("LABEL.CANCEL")}
+                    onclick={handle_cancel}></TextButton>
+                if props.allow_write {
+                    <TextButton class="primary" name="apply_enigma2_output"
+                        icon="Accept"
+                        title={ translate.t("LABEL.OK")}
+                        onclick={handle_apply}></TextButton>
+                }
+            </div>
+            <div class="tp__input-form__body">
+                { render_output() }
+            </div>
+        </div>
+    }
+}
@@ -1,6 +1,7 @@
 use crate::app::components::{
-    BlockId, BlockType, EditMode, HdHomeRunTargetOutputView, M3uTargetOutputView, SourceEditorContext,
-    StrmTargetOutputView, XtreamTargetOutputView,
+    BlockId, BlockType, CsvTargetOutputView, EditMode, Enigma2TargetOutputView, HdHomeRunTargetOutputView,
+    JsonTargetOutputView, M3uTargetOutputView, MulticastTargetOutputView, SourceEditorContext, StrmTargetOutputView,
+    XtreamTargetOutputView,
 };
 use shared::model::TargetOutputDto;
 use std::rc::Rc;
@@ -22,7 +23,11 @@ pub fn ConfigOutputView(props: &ConfigOutputViewProps) -> Html {
 
     match &*source_editor_ctx.edit_mode {
         EditMode::Active(block_instance) => match block_instance.block_type {
-            BlockType::InputXtream | BlockType::InputM3u | BlockType::InputLibrary | BlockType::Target => html! {},
+            BlockType::InputXtream
+            | BlockType::InputM3u
+            | BlockType::InputLibrary
+            | BlockType::InputIngest
+            | BlockType::Target => html! {},
             BlockType::OutputM3u => {
                 let output = props.output.as_ref().and_then(|to| {
                     if let TargetOutputDto::M3u(m3u) = &**to {
@@ -67,6 +72,50 @@ pub fn ConfigOutputView(props: &ConfigOutputViewProps) -> Html {
 
                 html! { <StrmTargetOutputView block_id={block_id} output={output} allow_write={props.allow_write} /> }
             }
+            BlockType::OutputEnigma2 => {
+                let output = props.output.as_ref().and_then(|to| {
+                    if let TargetOutputDto::Enigma2(enigma2) = &**to {
+                        Some(Rc::new(enigma2.clone()))
+                    } else {
+                        None
+                    }
+                });
+
+                html! { <Enigma2TargetOutputView block_id={block_id} output={output} allow_write={props.allow_write} /> }
+            }
+            BlockType::OutputJson => {
+                let output = props.output.as_ref().and_then(|to| {
+                    if let TargetOutputDto::Json(json) = &**to {
+                        Some(Rc::new(json.clone()))
+                    } else {
+                        None
+                    }
+                });
+
+                html! { <JsonTargetOutputView block_id={block_id} output={output} allow_write={props.allow_write} /> }
+            }
+            BlockType::OutputCsv => {
+                let output = props.output.as_ref().and_then(|to| {
+                    if let TargetOutputDto::Csv(csv) = &**to {
+                        Some(Rc::new(csv.clone()))
+                    } else {
+                        None
+                    }
+                });
+
+                html! { <CsvTargetOutputView block_id={block_id} output={output} allow_write={props.allow_write} /> }
+            }
+            BlockType::OutputMulticast => {
+                let output = props.output.as_ref().and_then(|to| {
+                    if let TargetOutputDto::Multicast(multicast) = &**to {
+                        Some(Rc::new(multicast.clone()))
+                    } else {
+                        None
+                    }
+                });
+
+                html! { <MulticastTargetOutputView block_id={block_id} output={output} allow_write={props.allow_write} /> }
+            }
         },
         EditMode::Inactive => html! {},
     }
@@ -0,0 +1,126 @@
+use crate::{
+    app::components::{
+        config::HasFormData, BlockId, BlockInstance, Card, EditMode, FilterInput, SourceEditorContext, TextButton,
+    },
+    config_field, config_field_child, config_field_custom, config_field_optional, edit_field_text,
+    edit_field_text_option, generate_form_reducer,
+    i18n::use_translation,
+};
+use shared::model::{JsonTargetOutputDto, TargetOutputDto};
+use std::rc::Rc;
+use yew::{component, html, use_context, use_effect_with, use_reducer, Callback, Html, Properties, UseReducerHandle};
+
+const LABEL_USERNAME: &str = "LABEL.USERNAME";
+const LABEL_FILENAME: &str = "LABEL.FILENAME";
+const LABEL_FILTER: &str = "LABEL.FILTER";
+
+generate_form_reducer!(
+    state: JsonTargetOutputFormState { form: JsonTargetOutputDto },
+    action_name: JsonTargetOutputFormAction,
+    fields {
+        Username => username: String,
+        Filename => filename: Option<String>,
+        Filter => filter: Option<String>,
+    }
+);
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct JsonTargetOutputViewProps {
+    pub(crate) block_id: BlockId,
+    pub(crate) output: Option<Rc<JsonTargetOutputDto>>,
+    #[prop_or(true)]
+    pub(crate) allow_write: bool,
+}
+
+#[component]
+pub fn JsonTargetOutputView(props: &JsonTargetOutputViewProps) -> Html {
+    let translate = use_translation();
+    let source_editor_ctx = use_context::<SourceEditorContext>().expect("SourceEditorContext not found");
+
+    let output_form_state: UseReducerHandle<JsonTargetOutputFormState> =
+        use_reducer(|| JsonTargetOutputFormState { form: JsonTargetOutputDto::default(), modified: false });
+
+    {
+        let output_form_state = output_form_state.clone();
+        let config_output = props.output.clone();
+
+        use_effect_with(config_output, move |cfg| {
+            if let Some(output) = cfg {
+                output_form_state.dispatch(JsonTargetOutputFormAction::SetAll(output.as_ref().clone()));
+            } else {
+                output_form_state.dispatch(JsonTargetOutputFormAction::SetAll(JsonTargetOutputDto::default()));
+            }
+            || ()
+        });
+    }
+
+    let render_output = || {
+        if !props.allow_write {
+            html! {
+                <Card class="tp__config-view__card">
+                    { config_field!(output_form_state.form, translate.t(LABEL_USERNAME), username) }
+                    { config_field_optional!(output_form_state.form, translate.t(LABEL_FILENAME), filename) }
+                    { config_field_custom!(
+                        translate.t(LABEL_FILTER),
+                        output_form_state.form.filter.clone().unwrap_or_default()
+                    ) }
+                </Card>
+            }
+        } else {
+            let output_form_state_1 = output_form_state.clone();
+            html! {
+                <Card class="tp__config-view__card">
+                    { edit_field_text!(output_form_state, translate.t(LABEL_USERNAME), username, JsonTargetOutputFormAction::Username) }
+                    { edit_field_text_option!(output_form_state, translate.t(LABEL_FILENAME), filename, JsonTargetOutputFormAction::Filename) }
+                    { config_field_child!(translate.t(LABEL_FILTER), "OUTPUT_JSON_FORM.FILTER", {
+                       html! {
+                            <FilterInput filter={output_form_state_1.form.filter.clone()} on_change={Callback::from(move |new_filter| {
+                                output_form_state_1.dispatch(JsonTargetOutputFormAction::Filter(new_filter));
+                            })} />
+                       }
+                    })}
+                </Card>
+            }
+        }
+    };
+
+    let handle_apply = {
+        let source_editor_ctx = source_editor_ctx.clone();
+        let output_form_state = output_form_state.clone();
+        let block_id = props.block_id;
+        Callback::from(move |_| {
+            let output = output_form_state.data().clone();
+            source_editor_ctx
+                .on_form_change
+                .emit((block_id, BlockInstance::Output(Rc::new(TargetOutputDto::Json(output)))));
+            source_editor_ctx.edit_mode.set(EditMode::Inactive);
+        })
+    };
+
+    let handle_cancel = {
+        let source_editor_ctx = source_editor_ctx.clone();
+        Callback::from(move |_| {
+            source_editor_ctx.edit_mode.set(EditMode::Inactive);
+        })
+    };
+
+    html! {
+        <div class="tp__source-editor-form tp__config-view-page">
+            <div class="tp__source-editor-form__toolbar tp__form-page__toolbar">
+                <TextButton class="secondary" name="cancel_json_output"
+                    icon="Cancel"
+                    title={ translate.t("LABEL.CANCEL")}
+                    onclick={handle_cancel}></TextButton>
+                if props.allow_write {
+                    <TextButton class="primary" name="apply_json_output"
+                        icon="Accept"
+                        title={ translate.t("LABEL.OK")}
+                        onclick={handle_apply}></TextButton>
+                }
+            </div>
+            <div class="tp__input-form__body">
+                { render_output() }
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,177 @@
+use crate::{
+    app::components::{
+        config::HasFormData, dto_field_id, BlockId, BlockInstance, Card, EditMode, SourceEditorContext, Tag, TagList,
+        TextButton,
+    },
+    config_field, config_field_bool, config_field_child, config_field_custom, config_field_optional, edit_field_bool,
+    edit_field_number_u16, edit_field_number_u8, edit_field_text_option, generate_form_reducer,
+    i18n::use_translation,
+};
+use shared::model::{MulticastChannelDto, MulticastTargetOutputDto, TargetOutputDto};
+use std::rc::Rc;
+use yew::{component, html, use_context, use_effect_with, use_reducer, Callback, Html, Properties, UseReducerHandle};
+
+const LABEL_INTERFACE: &str = "LABEL.INTERFACE";
+const LABEL_TTL: &str = "LABEL.TTL";
+const LABEL_RTP: &str = "LABEL.RTP";
+const LABEL_ALWAYS_ON: &str = "LABEL.ALWAYS_ON";
+const LABEL_IDLE_TIMEOUT_SECS: &str = "LABEL.IDLE_TIMEOUT_SECS";
+const LABEL_CHANNELS: &str = "LABEL.CHANNELS";
+const LABEL_ADD_MULTICAST_CHANNEL: &str = "LABEL.ADD_MULTICAST_CHANNEL";
+
+generate_form_reducer!(
+    state: MulticastTargetOutputFormState { form: MulticastTargetOutputDto },
+    action_name: MulticastTargetOutputFormAction,
+    fields {
+        Interface => interface: Option<String>,
+        Ttl => ttl: u8,
+        Rtp => rtp: bool,
+        AlwaysOn => always_on: bool,
+        IdleTimeoutSecs => idle_timeout_secs: u16,
+        Channels => channels: Vec<MulticastChannelDto>,
+    }
+);
+
+fn channel_to_entry(channel: &MulticastChannelDto) -> String { format!("{}={}", channel.name, channel.address) }
+
+/// Parses the `name=address` entries, the per channel settings of existing channels are kept.
+fn entries_to_channels(entries: &[String], current: &[MulticastChannelDto]) -> Vec<MulticastChannelDto> {
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let (name, address) = entry.rsplit_once('=')?;
+            let name = name.trim();
+            let address = address.trim();
+            if name.is_empty() || address.is_empty() {
+                return None;
+            }
+            let existing = current.iter().find(|channel| channel.name == name);
+            Some(MulticastChannelDto {
+                name: name.to_string(),
+                address: address.to_string(),
+                rtp: existing.and_then(|channel| channel.rtp),
+                always_on: existing.and_then(|channel| channel.always_on),
+            })
+        })
+        .collect()
+}
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct MulticastTargetOutputViewProps {
+    pub(crate) block_id: BlockId,
+    pub(crate) output: Option<Rc<MulticastTargetOutputDto>>,
+    #[prop_or(true)]
+    pub(crate) allow_write: bool,
+}
+
+#[component]
+pub fn MulticastTargetOutputView(props: &MulticastTargetOutputViewProps) -> Html {
+    let translate = use_translation();
+    let source_editor_ctx = use_context::<SourceEditorContext>().expect("SourceEditorContext not found");
+
+    let output_form_state: UseReducerHandle<MulticastTargetOutputFormState> =
+        use_reducer(|| MulticastTargetOutputFormState { form: MulticastTargetOutputDto::default(), modified: false });
+
+    {
+        let output_form_state = output_form_state.clone();
+        let config_output = props.output.clone();
+
+        use_effect_with(config_output, move |cfg| {
+            if let Some(output) = cfg {
+                output_form_state.dispatch(MulticastTargetOutputFormAction::SetAll(output.as_ref().clone()));
+            } else {
+                output_form_state
+                    .dispatch(MulticastTargetOutputFormAction::SetAll(MulticastTargetOutputDto::default()));
+            }
+            || ()
+        });
+    }
+
+    let render_output = || {
+        if !props.allow_write {
+            html! {
+                <Card class="tp__config-view__card">
+                    { config_field_optional!(output_form_state.form, translate.t(LABEL_INTERFACE), interface) }
+                    { config_field!(output_form_state.form, translate.t(LABEL_TTL), ttl) }
+                    { config_field_bool!(output_form_state.form, translate.t(LABEL_RTP), rtp) }
+                    { config_field_bool!(output_form_state.form, translate.t(LABEL_ALWAYS_ON), always_on) }
+                    { config_field!(output_form_state.form, translate.t(LABEL_IDLE_TIMEOUT_SECS), idle_timeout_secs) }
+                    { config_field_custom!(
+                        translate.t(LABEL_CHANNELS),
+                        output_form_state.form.channels.iter().map(channel_to_entry).collect::<Vec<_>>().join(", ")
+                    ) }
+                </Card>
+            }
+        } else {
+            let output_form_state_1 = output_form_state.clone();
+            let channel_tags = output_form_state
+                .form
+                .channels
+                .iter()
+                .map(|channel| Rc::new(Tag { label: channel_to_entry(channel), class: None }))
+                .collect::<Vec<Rc<Tag>>>();
+            html! {
+                <Card class="tp__config-view__card">
+                    { edit_field_text_option!(output_form_state, translate.t(LABEL_INTERFACE), interface, MulticastTargetOutputFormAction::Interface) }
+                    { edit_field_number_u8!(output_form_state, translate.t(LABEL_TTL), ttl, MulticastTargetOutputFormAction::Ttl) }
+                    { edit_field_bool!(output_form_state, translate.t(LABEL_RTP), rtp, MulticastTargetOutputFormAction::Rtp) }
+                    { edit_field_bool!(output_form_state, translate.t(LABEL_ALWAYS_ON), always_on, MulticastTargetOutputFormAction::AlwaysOn) }
+                    { edit_field_number_u16!(output_form_state, translate.t(LABEL_IDLE_TIMEOUT_SECS), idle_timeout_secs, MulticastTargetOutputFormAction::IdleTimeoutSecs) }
+                    { config_field_child!(translate.t(LABEL_CHANNELS), dto_field_id(&output_form_state.form, "channels"), {
+                       html! {
+                            <TagList
+                                tags={channel_tags}
+                                placeholder={translate.t(LABEL_ADD_MULTICAST_CHANNEL)}
+                                readonly={false}
+                                on_change={Callback::from(move |value: Vec<Rc<Tag>>| {
+                                    let entries: Vec<String> = value.iter().map(|tag| tag.label.clone()).collect();
+                                    let channels = entries_to_channels(&entries, &output_form_state_1.form.channels);
+                                    output_form_state_1.dispatch(MulticastTargetOutputFormAction::Channels(channels));
+                                })} />
+                       }
+                    })}
+                </Card>
+            }
+        }
+    };
+
+    let handle_apply = {
+        let source_editor_ctx = source_editor_ctx.clone();
+        let output_form_state = output_form_state.clone();
+        let block_id = props.block_id;
+        Callback::from(move |_| {
+            let output = output_form_state.data().clone();
+            source_editor_ctx
+                .on_form_change
+                .emit((block_id, BlockInstance::Output(Rc::new(TargetOutputDto::Multicast(output)))));
+            source_editor_ctx.edit_mode.set(EditMode::Inactive);
+        })
+    };
+
+    let handle_cancel = {
+        let source_editor_ctx = source_editor_ctx.clone();
+        Callback::from(move |_| {
+            source_editor_ctx.edit_mode.set(EditMode::Inactive);
+        })
+    };
+
+    html! {
+        <div class="tp__source-editor-form tp__config-view-page">
+            <div class="tp__source-editor-form__toolbar tp__form-page__toolbar">
+                <TextButton class="secondary" name="cancel_multicast_output"
+                    icon="Cancel"
+                    title={ translate.t("LABEL.CANCEL")}
+                    onclick={handle_cancel}></TextButton>
+                if props.allow_write {
+                    <TextButton class="primary" name="apply_multicast_output"
+                        icon="Accept"
+                        title={ translate.t("LABEL.OK")}
+                        onclick={handle_apply}></TextButton>
+                }
+            </div>
+            <div class="tp__input-form__body">
+                { render_output() }
+            </div>
+        </div>
+    }
+}
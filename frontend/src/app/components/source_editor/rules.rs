@@ -9,6 +9,10 @@ use crate::app::components::{Block, BlockType, Connection};
 ///   - 1x OutputXtream
 ///   - 1x OutputHdhomerun
 ///   - up to 4x OutputStrm
+///   - 1x OutputEnigma2
+///   - 1x OutputJson
+///   - 1x OutputCsv
+///   - 1x OutputMulticast
 pub fn can_connect(from_block: &Block, to_block: &Block, connections: &[Connection], blocks: &[Block]) -> bool {
     // Prevent self-connection
     if from_block.id == to_block.id {
@@ -49,6 +53,10 @@ pub fn can_connect(from_block: &Block, to_block: &Block, connections: &[Connecti
         let mut count_xtream = 0;
         let mut count_hdhomerun = 0;
         let mut count_strm = 0;
+        let mut count_enigma2 = 0;
+        let mut count_json = 0;
+        let mut count_csv = 0;
+        let mut count_multicast = 0;
 
         for conn in connections.iter().filter(|c| c.from == from_id) {
             if let Some(out_block) = blocks.iter().find(|b| b.id == conn.to) {
@@ -57,6 +65,10 @@ pub fn can_connect(from_block: &Block, to_block: &Block, connections: &[Connecti
                     BlockType::OutputXtream => count_xtream += 1,
                     BlockType::OutputHdHomeRun => count_hdhomerun += 1,
                     BlockType::OutputStrm => count_strm += 1,
+                    BlockType::OutputEnigma2 => count_enigma2 += 1,
+                    BlockType::OutputJson => count_json += 1,
+                    BlockType::OutputCsv => count_csv += 1,
+                    BlockType::OutputMulticast => count_multicast += 1,
                     _ => {}
                 }
             }
@@ -67,6 +79,10 @@ pub fn can_connect(from_block: &Block, to_block: &Block, connections: &[Connecti
             BlockType::OutputXtream if count_xtream >= 1 => return false,
             BlockType::OutputHdHomeRun if count_hdhomerun >= 1 => return false,
             BlockType::OutputStrm if count_strm >= 4 => return false,
+            BlockType::OutputEnigma2 if count_enigma2 >= 1 => return false,
+            BlockType::OutputJson if count_json >= 1 => return false,
+            BlockType::OutputCsv if count_csv >= 1 => return false,
+            BlockType::OutputMulticast if count_multicast >= 1 => return false,
             _ => {}
         }
     }
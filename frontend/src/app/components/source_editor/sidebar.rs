@@ -4,12 +4,21 @@ use crate::{
 };
 use yew::prelude::*;
 
-pub const BLOCK_TYPES_INPUT: [BlockType; 3] = [BlockType::InputXtream, BlockType::InputM3u, BlockType::InputLibrary];
+pub const BLOCK_TYPES_INPUT: [BlockType; 4] =
+    [BlockType::InputXtream, BlockType::InputM3u, BlockType::InputLibrary, BlockType::InputIngest];
 
 pub const BLOCK_TYPES_TARGET: [BlockType; 1] = [BlockType::Target];
 
-pub const BLOCK_TYPES_OUTPUT: [BlockType; 4] =
-    [BlockType::OutputXtream, BlockType::OutputM3u, BlockType::OutputHdHomeRun, BlockType::OutputStrm];
+pub const BLOCK_TYPES_OUTPUT: [BlockType; 8] = [
+    BlockType::OutputXtream,
+    BlockType::OutputM3u,
+    BlockType::OutputHdHomeRun,
+    BlockType::OutputStrm,
+    BlockType::OutputEnigma2,
+    BlockType::OutputJson,
+    BlockType::OutputCsv,
+    BlockType::OutputMulticast,
+];
 
 fn create_brick(
     t: &BlockType,
@@ -5,8 +5,9 @@ mod page;
 mod proxy_type_input;
 mod proxy_type_view;
 mod proxy_user_credentials_form;
+mod user_devices;
 mod user_status;
 mod user_table;
 mod userlist_view;
 
-pub use self::{max_connections::*, page::*, proxy_type_view::*, user_status::*, userlist_view::*};
+pub use self::{max_connections::*, page::*, proxy_type_view::*, user_devices::*, user_status::*, userlist_view::*};
@@ -1,8 +1,9 @@
 use crate::{
     app::{
         components::{
-            config::HasFormData, select::Select, userlist::proxy_type_input::ProxyTypeInput, DropDownOption,
-            DropDownSelection, TextButton, UserStatus,
+            config::HasFormData, input::Input, select::Select, userlist::proxy_type_input::ProxyTypeInput,
+            DropDownOption, FieldLabel, number_input::NumberInput, DropDownSelection, TextButton, ToggleSwitch,
+            UserStatus,
         },
         TargetUser,
     },
@@ -15,8 +16,9 @@ use crate::{
 use chrono::{Duration, Utc};
 use shared::{
     model::{
-        permission::Permission, ApiProxyServerInfoDto, ConfigTargetDto, ProxyType, ProxyUserCredentialsDto,
-        ProxyUserStatus,
+        parse_time_of_day, permission::Permission, AccessScheduleDay, AccessWindowDto, ApiProxyServerInfoDto,
+        ConfigTargetDto, ProxyType, ProxyUserAccessScheduleDto, ProxyUserCountryAccessDto, ProxyUserCredentialsDto,
+        ProxyUserDeviceLimitDto, ProxyUserQuotaDto, ProxyUserStatus, UserQuotaPeriod,
     },
     utils::generate_random_string,
 };
@@ -25,6 +27,111 @@ use yew::prelude::*;
 
 const DEFAULT_MAX_CONNECTIONS: u32 = 1;
 const DEFAULT_EXPIRATION_DAYS: i64 = 365;
+const QUOTA_PERIODS: [(UserQuotaPeriod, &str); 3] = [
+    (UserQuotaPeriod::Day, "day"),
+    (UserQuotaPeriod::Week, "week"),
+    (UserQuotaPeriod::Month, "month"),
+];
+
+fn update_quota(form: &ProxyUserCredentialsDto, apply: impl FnOnce(&mut ProxyUserQuotaDto)) -> UserFormAction {
+    let mut quota = form.quota.clone().unwrap_or_default();
+    apply(&mut quota);
+    UserFormAction::Quota(if quota.is_empty() && quota.period == UserQuotaPeriod::Day { None } else { Some(quota) })
+}
+
+fn update_device_limit(
+    form: &ProxyUserCredentialsDto,
+    apply: impl FnOnce(&mut ProxyUserDeviceLimitDto),
+) -> UserFormAction {
+    let mut device_limit = form.device_limit.unwrap_or_default();
+    apply(&mut device_limit);
+    UserFormAction::DeviceLimit(if device_limit.is_empty() { None } else { Some(device_limit) })
+}
+
+fn update_country_access(
+    form: &ProxyUserCredentialsDto,
+    apply: impl FnOnce(&mut ProxyUserCountryAccessDto),
+) -> UserFormAction {
+    let mut country_access = form.country_access.clone().unwrap_or_default();
+    apply(&mut country_access);
+    let mut prepared = country_access.clone();
+    prepared.prepare();
+    UserFormAction::CountryAccess(if prepared.is_empty() { None } else { Some(country_access) })
+}
+
+fn parse_country_list(value: &str) -> Vec<String> {
+    if value.trim().is_empty() {
+        Vec::new()
+    } else {
+        value.split(',').map(ToString::to_string).collect()
+    }
+}
+
+const ACCESS_SCHEDULE_DAYS: [&str; 7] = ["mon", "tue", "wed", "thu", "fri", "sat", "sun"];
+
+fn update_access_schedule(
+    form: &ProxyUserCredentialsDto,
+    apply: impl FnOnce(&mut ProxyUserAccessScheduleDto),
+) -> UserFormAction {
+    let mut schedule = form.access_schedule.clone().unwrap_or_default();
+    apply(&mut schedule);
+    UserFormAction::AccessSchedule(if schedule == ProxyUserAccessScheduleDto::default() { None } else { Some(schedule) })
+}
+
+fn access_schedule_day_index(name: &str) -> Option<u32> {
+    ACCESS_SCHEDULE_DAYS.iter().position(|day| name.trim().eq_ignore_ascii_case(day)).and_then(|idx| u32::try_from(idx).ok())
+}
+
+fn format_access_windows(schedule: Option<&ProxyUserAccessScheduleDto>) -> String {
+    schedule.map_or_else(String::new, |schedule| {
+        schedule
+            .windows
+            .iter()
+            .map(|window| {
+                let range = format!("{}-{}", window.from, window.to);
+                if window.days.is_empty() {
+                    range
+                } else {
+                    let days: Vec<&str> =
+                        window.days.iter().map(|day| ACCESS_SCHEDULE_DAYS[day.index() as usize]).collect();
+                    format!("{} {range}", days.join(","))
+                }
+            })
+            .collect::<Vec<String>>()
+            .join("; ")
+    })
+}
+
+/// Parses windows written as `mon-fri 18:00-21:00; sat,sun 09:00-22:00`.
+/// Entries without days apply to every day, malformed entries are dropped.
+fn parse_access_windows(value: &str) -> Vec<AccessWindowDto> {
+    value
+        .split([';', '\n'])
+        .filter_map(|entry| {
+            let entry = entry.trim();
+            let (days, range) = entry.rsplit_once(' ').map_or(("", entry), |(days, range)| (days, range));
+            let (from, to) = range.split_once('-')?;
+            parse_time_of_day(from)?;
+            parse_time_of_day(to)?;
+            let mut window_days = Vec::new();
+            for part in days.split(',').map(str::trim).filter(|part| !part.is_empty()) {
+                let (first, last) = part.split_once('-').unwrap_or((part, part));
+                let (mut index, last) = (access_schedule_day_index(first)?, access_schedule_day_index(last)?);
+                loop {
+                    let day = AccessScheduleDay::from_index(index);
+                    if !window_days.contains(&day) {
+                        window_days.push(day);
+                    }
+                    if index % 7 == last {
+                        break;
+                    }
+                    index += 1;
+                }
+            }
+            Some(AccessWindowDto { days: window_days, from: from.trim().to_string(), to: to.trim().to_string() })
+        })
+        .collect()
+}
 
 generate_form_reducer!(
     state: UserFormState { form: ProxyUserCredentialsDto },
@@ -45,6 +152,13 @@ generate_form_reducer!(
         EpgTimeshift => epg_timeshift: Option<String>,
         EpgRequestTimeshift => epg_request_timeshift: Option<String>,
         Comment => comment: Option<String>,
+        Quota => quota: Option<ProxyUserQuotaDto>,
+        AccessSchedule => access_schedule: Option<ProxyUserAccessScheduleDto>,
+        ParentalProfile => parental_profile: Option<String>,
+        LimitProfile => limit_profile: Option<String>,
+        Reseller => reseller: Option<String>,
+        DeviceLimit => device_limit: Option<ProxyUserDeviceLimitDto>,
+        CountryAccess => country_access: Option<ProxyUserCountryAccessDto>,
     }
 );
 
@@ -63,6 +177,7 @@ pub fn ProxyUserCredentialsForm(props: &ProxyUserCredentialsFormProps) -> Html {
     let service_ctx = use_service_context();
     let selected_target = use_state(|| None);
     let update = use_state(|| false);
+    let access_windows = use_state(String::new);
 
     let form_state: UseReducerHandle<UserFormState> =
         use_reducer(|| UserFormState { form: ProxyUserCredentialsDto::default(), modified: false });
@@ -85,6 +200,20 @@ pub fn ProxyUserCredentialsForm(props: &ProxyUserCredentialsFormProps) -> Html {
         .collect::<Vec<DropDownOption>>()
     });
 
+    let quota_periods = {
+        let translate = translate.clone();
+        use_memo(form_state.data().quota.as_ref().map(|quota| quota.period).unwrap_or_default(), move |period| {
+            QUOTA_PERIODS
+                .iter()
+                .map(|(p, id)| DropDownOption {
+                    id: (*id).to_string(),
+                    label: html! { translate.t(&format!("LABEL.QUOTA_{}", id.to_uppercase())) },
+                    selected: period == p,
+                })
+                .collect::<Vec<DropDownOption>>()
+        })
+    };
+
     let targets = use_memo((props.targets.clone(), (*selected_target).clone()), |(targets, selected)| {
         targets
             .iter()
@@ -111,12 +240,15 @@ pub fn ProxyUserCredentialsForm(props: &ProxyUserCredentialsFormProps) -> Html {
         let form_state = form_state.clone();
         let set_selected_target = selected_target.clone();
         let set_update = update.clone();
+        let set_access_windows = access_windows.clone();
         use_effect_with((props.user.clone(), props.server.clone()), move |(user, server)| {
             if let Some(u) = user.clone() {
+                set_access_windows.set(format_access_windows(u.credentials.access_schedule.as_ref()));
                 set_update.set(true);
                 set_selected_target.set(Some(u.target.clone()));
                 form_state.dispatch(UserFormAction::SetAll((*u.credentials).clone()));
             } else {
+                set_access_windows.set(String::new());
                 set_update.set(false);
                 set_selected_target.set(None);
                 let mut user = ProxyUserCredentialsDto::default();
@@ -195,6 +327,23 @@ pub fn ProxyUserCredentialsForm(props: &ProxyUserCredentialsFormProps) -> Html {
     let instance_status = form_state.clone();
     let instance_proxy = form_state.clone();
     let instance_server = form_state.clone();
+    let instance_quota_period = form_state.clone();
+    let instance_quota_hours = form_state.clone();
+    let instance_quota_traffic = form_state.clone();
+    let quota = form_state.data().quota.clone().unwrap_or_default();
+    let instance_schedule_windows = form_state.clone();
+    let instance_schedule_timezone = form_state.clone();
+    let instance_schedule_kick = form_state.clone();
+    let set_access_windows = access_windows.clone();
+    let access_schedule = form_state.data().access_schedule.clone().unwrap_or_default();
+    let instance_max_devices = form_state.clone();
+    let instance_approve_devices = form_state.clone();
+    let device_limit = form_state.data().device_limit.unwrap_or_default();
+    let instance_allow_countries = form_state.clone();
+    let instance_deny_countries = form_state.clone();
+    let instance_home_country = form_state.clone();
+    let instance_travel_days = form_state.clone();
+    let country_access = form_state.data().country_access.clone().unwrap_or_default();
     html! {
         <div class="tp__proxy-user-credentials-form tp__form-page">
           <div class="tp__proxy-user-credentials-form__body tp__form-page__body">
@@ -262,6 +411,163 @@ pub fn ProxyUserCredentialsForm(props: &ProxyUserCredentialsFormProps) -> Html {
             { edit_field_number_u16!(form_state,  translate.t("LABEL.SOFT_CONNECTIONS"), soft_connections, UserFormAction::SoftConnections) }
             { edit_field_number_i8!(form_state, translate.t("LABEL.PRIORITY"), priority, UserFormAction::Priority) }
             { edit_field_number_i8!(form_state, translate.t("LABEL.SOFT_PRIORITY"), soft_priority, UserFormAction::SoftPriority) }
+            { config_field_child!(translate.t("LABEL.QUOTA_PERIOD"), "PROXY_USER_CREDENTIALS.QUOTA_PERIOD", {
+               html! {
+                <Select name="quota_period"
+                    multi_select={false}
+                    on_select={Callback::from(move |(_name, selections):(String, DropDownSelection)| {
+                        let selected = match selections {
+                            DropDownSelection::Empty => None,
+                            DropDownSelection::Single(option) => Some(option),
+                            DropDownSelection::Multi(options) => options.first().cloned(),
+                        };
+                        let period = QUOTA_PERIODS
+                            .iter()
+                            .find(|(_, id)| selected.as_deref() == Some(*id))
+                            .map_or(UserQuotaPeriod::Day, |(period, _)| *period);
+                        instance_quota_period.dispatch(update_quota(instance_quota_period.data(), |q| q.period = period));
+                    })}
+                    options={quota_periods.clone()}
+                />
+            }})}
+            <div class="tp__form-field tp__form-field__number">
+                <NumberInput
+                    label={translate.t("LABEL.QUOTA_MAX_HOURS")}
+                    name="quota_max_hours"
+                    field_id={Some("PROXY_USER_CREDENTIALS.QUOTA_MAX_HOURS".to_string())}
+                    value={quota.max_hours.map(i64::from)}
+                    on_change={Callback::from(move |value: Option<i64>| {
+                        let max_hours = value.and_then(|v| u32::try_from(v).ok()).filter(|v| *v > 0);
+                        instance_quota_hours.dispatch(update_quota(instance_quota_hours.data(), |q| q.max_hours = max_hours));
+                    })}
+                />
+            </div>
+            <div class="tp__form-field tp__form-field__number">
+                <NumberInput
+                    label={translate.t("LABEL.QUOTA_MAX_TRAFFIC_MB")}
+                    name="quota_max_traffic_mb"
+                    field_id={Some("PROXY_USER_CREDENTIALS.QUOTA_MAX_TRAFFIC_MB".to_string())}
+                    value={quota.max_traffic_mb.map(|v| i64::try_from(v).unwrap_or(i64::MAX))}
+                    on_change={Callback::from(move |value: Option<i64>| {
+                        let max_traffic_mb = value.and_then(|v| u64::try_from(v).ok()).filter(|v| *v > 0);
+                        instance_quota_traffic.dispatch(update_quota(instance_quota_traffic.data(), |q| q.max_traffic_mb = max_traffic_mb));
+                    })}
+                />
+            </div>
+            <div class="tp__form-field tp__form-field__text">
+                <Input
+                    label={translate.t("LABEL.ACCESS_SCHEDULE_WINDOWS")}
+                    name="access_schedule_windows"
+                    field_id={Some("PROXY_USER_CREDENTIALS.ACCESS_SCHEDULE_WINDOWS".to_string())}
+                    placeholder={Some("mon-fri 18:00-21:00; sat,sun 09:00-22:00".to_string())}
+                    value={(*access_windows).clone()}
+                    on_change={Callback::from(move |value: String| {
+                        let windows = parse_access_windows(&value);
+                        set_access_windows.set(value);
+                        instance_schedule_windows.dispatch(update_access_schedule(instance_schedule_windows.data(), |s| s.windows = windows));
+                    })}
+                />
+            </div>
+            <div class="tp__form-field tp__form-field__text">
+                <Input
+                    label={translate.t("LABEL.ACCESS_SCHEDULE_TIMEZONE")}
+                    name="access_schedule_timezone"
+                    field_id={Some("PROXY_USER_CREDENTIALS.ACCESS_SCHEDULE_TIMEZONE".to_string())}
+                    value={access_schedule.timezone.clone().unwrap_or_default()}
+                    on_change={Callback::from(move |value: String| {
+                        let timezone = if value.is_empty() { None } else { Some(value) };
+                        instance_schedule_timezone.dispatch(update_access_schedule(instance_schedule_timezone.data(), |s| s.timezone = timezone));
+                    })}
+                />
+            </div>
+            <div class="tp__form-field tp__form-field__bool">
+                <ToggleSwitch
+                    value={access_schedule.kick_on_close}
+                    readonly={false}
+                    on_change={Callback::from(move |value| {
+                        instance_schedule_kick.dispatch(update_access_schedule(instance_schedule_kick.data(), |s| s.kick_on_close = value));
+                    })} />
+                <FieldLabel
+                    label={translate.t("LABEL.ACCESS_SCHEDULE_KICK_ON_CLOSE")}
+                    field_id={"PROXY_USER_CREDENTIALS.ACCESS_SCHEDULE_KICK_ON_CLOSE".to_string()}
+                />
+            </div>
+            { edit_field_text_option!(form_state,  translate.t("LABEL.PARENTAL_PROFILE"), parental_profile, UserFormAction::ParentalProfile) }
+            { edit_field_text_option!(form_state,  translate.t("LABEL.LIMIT_PROFILE"), limit_profile, UserFormAction::LimitProfile) }
+            { edit_field_text_option!(form_state,  translate.t("LABEL.RESELLER"), reseller, UserFormAction::Reseller) }
+            <div class="tp__form-field tp__form-field__number">
+                <NumberInput
+                    label={translate.t("LABEL.MAX_DEVICES")}
+                    name="max_devices"
+                    field_id={Some("PROXY_USER_CREDENTIALS.MAX_DEVICES".to_string())}
+                    value={Some(i64::from(device_limit.max_devices))}
+                    on_change={Callback::from(move |value: Option<i64>| {
+                        let max_devices = value.and_then(|v| u16::try_from(v).ok()).unwrap_or(0);
+                        instance_max_devices.dispatch(update_device_limit(instance_max_devices.data(), |d| d.max_devices = max_devices));
+                    })}
+                />
+            </div>
+            <div class="tp__form-field tp__form-field__bool">
+                <ToggleSwitch
+                    value={device_limit.approve_new}
+                    readonly={false}
+                    on_change={Callback::from(move |value| {
+                        instance_approve_devices.dispatch(update_device_limit(instance_approve_devices.data(), |d| d.approve_new = value));
+                    })} />
+                <FieldLabel
+                    label={translate.t("LABEL.APPROVE_NEW_DEVICES")}
+                    field_id={"PROXY_USER_CREDENTIALS.APPROVE_NEW_DEVICES".to_string()}
+                />
+            </div>
+            <div class="tp__form-field tp__form-field__text">
+                <Input
+                    label={translate.t("LABEL.ALLOWED_COUNTRIES")}
+                    name="allowed_countries"
+                    field_id={Some("PROXY_USER_CREDENTIALS.ALLOWED_COUNTRIES".to_string())}
+                    placeholder={Some("DE,AT,CH".to_string())}
+                    value={country_access.allow.join(",")}
+                    on_change={Callback::from(move |value: String| {
+                        let allow = parse_country_list(&value);
+                        instance_allow_countries.dispatch(update_country_access(instance_allow_countries.data(), |c| c.allow = allow));
+                    })}
+                />
+            </div>
+            <div class="tp__form-field tp__form-field__text">
+                <Input
+                    label={translate.t("LABEL.DENIED_COUNTRIES")}
+                    name="denied_countries"
+                    field_id={Some("PROXY_USER_CREDENTIALS.DENIED_COUNTRIES".to_string())}
+                    value={country_access.deny.join(",")}
+                    on_change={Callback::from(move |value: String| {
+                        let deny = parse_country_list(&value);
+                        instance_deny_countries.dispatch(update_country_access(instance_deny_countries.data(), |c| c.deny = deny));
+                    })}
+                />
+            </div>
+            <div class="tp__form-field tp__form-field__text">
+                <Input
+                    label={translate.t("LABEL.HOME_COUNTRY")}
+                    name="home_country"
+                    field_id={Some("PROXY_USER_CREDENTIALS.HOME_COUNTRY".to_string())}
+                    value={country_access.home_country.clone().unwrap_or_default()}
+                    on_change={Callback::from(move |value: String| {
+                        let home_country = if value.is_empty() { None } else { Some(value) };
+                        instance_home_country.dispatch(update_country_access(instance_home_country.data(), |c| c.home_country = home_country));
+                    })}
+                />
+            </div>
+            <div class="tp__form-field tp__form-field__number">
+                <NumberInput
+                    label={translate.t("LABEL.TRAVEL_DAYS")}
+                    name="travel_days"
+                    field_id={Some("PROXY_USER_CREDENTIALS.TRAVEL_DAYS".to_string())}
+                    value={Some(i64::from(country_access.travel_days))}
+                    on_change={Callback::from(move |value: Option<i64>| {
+                        let travel_days = value.and_then(|v| u16::try_from(v).ok()).unwrap_or(0);
+                        instance_travel_days.dispatch(update_country_access(instance_travel_days.data(), |c| c.travel_days = travel_days));
+                    })}
+                />
+            </div>
             { edit_field_text_option!(form_state,  translate.t("LABEL.EPG_TIMESHIFT"), epg_timeshift, UserFormAction::EpgTimeshift) }
             { edit_field_text_option!(form_state,  translate.t("LABEL.EPG_REQUEST_TIMESHIFT"), epg_request_timeshift, UserFormAction::EpgRequestTimeshift) }
             { edit_field_bool!(form_state,  translate.t("LABEL.USER_UI_ENABLED"), ui_enabled, UserFormAction::UiEnabled) }
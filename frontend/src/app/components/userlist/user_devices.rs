@@ -0,0 +1,117 @@
+use crate::{app::components::IconButton, hooks::use_service_context, html_if, i18n::use_translation};
+use shared::{model::UserDeviceDto, utils::unix_ts_to_str};
+use yew::{platform::spawn_local, prelude::*};
+
+#[derive(Properties, Clone, PartialEq, Debug)]
+pub struct UserDevicesProps {
+    pub username: String,
+    pub can_write: bool,
+}
+
+#[component]
+pub fn UserDevices(props: &UserDevicesProps) -> Html {
+    let translate = use_translation();
+    let services = use_service_context();
+    let devices = use_state(|| None::<Vec<UserDeviceDto>>);
+
+    let load_devices = {
+        let services = services.clone();
+        let devices = devices.clone();
+        let username = props.username.clone();
+        Callback::from(move |()| {
+            let services = services.clone();
+            let devices = devices.clone();
+            let username = username.clone();
+            spawn_local(async move {
+                match services.user.get_devices(&username).await {
+                    Ok(list) => devices.set(Some(list)),
+                    Err(err) => services.toastr.error(err.to_string()),
+                }
+            });
+        })
+    };
+
+    {
+        let load_devices = load_devices.clone();
+        use_effect_with(props.username.clone(), move |_| {
+            load_devices.emit(());
+            || ()
+        });
+    }
+
+    let handle_action = {
+        let services = services.clone();
+        let username = props.username.clone();
+        Callback::from(move |(name, _event): (String, MouseEvent)| {
+            let Some((action, device)) = name.split_once(':') else {
+                return;
+            };
+            let (action, device) = (action.to_string(), device.to_string());
+            let services = services.clone();
+            let username = username.clone();
+            let load_devices = load_devices.clone();
+            spawn_local(async move {
+                let result = if action == "approve" {
+                    services.user.approve_device(&username, &device).await
+                } else {
+                    services.user.revoke_device(&username, &device).await
+                };
+                match result {
+                    Ok(()) => load_devices.emit(()),
+                    Err(err) => services.toastr.error(err.to_string()),
+                }
+            });
+        })
+    };
+
+    let render_device = |device: &UserDeviceDto| {
+        let last_seen = i64::try_from(device.last_seen).ok().and_then(unix_ts_to_str).unwrap_or_default();
+        html! {
+            <tr key={device.id.clone()}>
+                <td>{ device.device_id.as_deref().unwrap_or(device.id.as_str()) }</td>
+                <td>{ device.user_agent.as_str() }</td>
+                <td>{ device.network.as_str() }</td>
+                <td>{ last_seen }</td>
+                <td class="tp__user-devices__actions">
+                    { html_if!(props.can_write && !device.approved, {
+                        <IconButton name={format!("approve:{}", device.id)} icon="Accept"
+                            hint={translate.t("LABEL.APPROVE")} onclick={&handle_action} />
+                    })}
+                    { html_if!(props.can_write, {
+                        <IconButton name={format!("revoke:{}", device.id)} icon="Delete"
+                            hint={translate.t("LABEL.REVOKE")} onclick={&handle_action} />
+                    })}
+                    { html_if!(!device.approved, {
+                        <span class="tp__user-devices__pending">{ translate.t("LABEL.PENDING_APPROVAL") }</span>
+                    })}
+                </td>
+            </tr>
+        }
+    };
+
+    html! {
+        <div class="tp__user-devices">
+            <h2>{ format!("{} - {}", translate.t("LABEL.DEVICES"), props.username) }</h2>
+            {
+                match &*devices {
+                    None => html! {},
+                    Some(list) if list.is_empty() => html! { <span>{ translate.t("MESSAGES.NO_DEVICES") }</span> },
+                    Some(list) => html! {
+                        <table>
+                            <thead>
+                                <tr>
+                                    <th>{ translate.t("LABEL.DEVICE") }</th>
+                                    <th>{ translate.t("LABEL.USER_AGENT") }</th>
+                                    <th>{ translate.t("LABEL.NETWORK") }</th>
+                                    <th>{ translate.t("LABEL.LAST_SEEN") }</th>
+                                    <th></th>
+                                </tr>
+                            </thead>
+                            <tbody>{ for list.iter().map(render_device) }</tbody>
+                        </table>
+                    },
+                }
+            }
+        </div>
+    }
+}
@@ -2,8 +2,8 @@ use crate::{
     app::{
         components::{
             convert_bool_to_chip_style, menu_item::MenuItem, popup_menu::PopupMenu, AppIcon, CellValue, Chip,
-            HideContent, MaxConnections, ProxyTypeView, RevealContent, Table, TableDefinition, UserStatus,
-            UserlistContext, UserlistPage,
+            HideContent, MaxConnections, ProxyTypeView, RevealContent, Table, TableDefinition, UserDevices,
+            UserStatus, UserlistContext, UserlistPage,
         },
         context::{target_users_to_api_proxy_users, TargetUser},
         ConfigContext, TargetUserList,
@@ -71,6 +71,7 @@ enum TableAction {
     Refresh,
     Delete,
     CopyCredentials,
+    Devices,
 }
 
 impl Display for TableAction {
@@ -83,6 +84,7 @@ impl Display for TableAction {
                 Self::Refresh => "refresh",
                 Self::Delete => "delete",
                 Self::CopyCredentials => "copy_credentials",
+                Self::Devices => "devices",
             }
         )
     }
@@ -100,6 +102,8 @@ impl FromStr for TableAction {
             Ok(Self::Delete)
         } else if s.eq("copy_credentials") {
             Ok(Self::CopyCredentials)
+        } else if s.eq("devices") {
+            Ok(Self::Devices)
         } else {
             info_err_res!("Unknown TableAction: {}", s)
         }
@@ -385,6 +389,17 @@ pub fn UserTable(props: &UserTableProps) -> Html {
                             }
                         }
                     }
+                    TableAction::Devices => {
+                        if let Some(dto) = &*selected_dto {
+                            let username = dto.credentials.username.clone();
+                            let dlg = dialog.clone();
+                            spawn_local(async move {
+                                let _result = dlg
+                                    .content(html! {<UserDevices {username} can_write={can_write_users}/>}, None, false)
+                                    .await;
+                            });
+                        }
+                    }
                 }
             }
             popup_is_open_state.set(false);
@@ -402,6 +417,9 @@ pub fn UserTable(props: &UserTableProps) -> Html {
                         <MenuItem icon="Edit" name={TableAction::Edit.to_string()} label={translate.t("LABEL.EDIT")} onclick={&handle_menu_click}></MenuItem>
                     })}
                     <MenuItem icon="Clipboard" name={TableAction::CopyCredentials.to_string()} label={translate.t("LABEL.COPY_CREDENTIALS")} onclick={&handle_menu_click}></MenuItem>
+                    { html_if!(!userlist_context.local_mode, {
+                        <MenuItem icon="Router" name={TableAction::Devices.to_string()} label={translate.t("LABEL.DEVICES")} onclick={&handle_menu_click}></MenuItem>
+                    })}
                     { html_if!(can_write_users, {
                         <>
                             <hr/>
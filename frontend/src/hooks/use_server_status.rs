@@ -89,6 +89,7 @@ fn download_task_to_stream_with_ts(download: &FileDownloadDto, ts: u64) -> Strea
             shared_joined_existing: None,
             shared_stream_id: None,
             technical: None,
+            alternate_input: None,
         },
         provider: BACKGROUND_TRANSFER_PROVIDER.to_string(),
         addr: download_stream_addr(uid),
@@ -99,6 +100,7 @@ fn download_task_to_stream_with_ts(download: &FileDownloadDto, ts: u64) -> Strea
         session_token: None,
         preserved: false,
         previous_session_id: None,
+        throttle_kbps: 0,
     }
 }
 
@@ -345,6 +347,7 @@ mod tests {
                 shared_joined_existing: None,
                 shared_stream_id: None,
                 technical: None,
+                alternate_input: None,
             },
             provider: "provider".to_string(),
             addr: addr.parse::<SocketAddr>().unwrap_or_else(|_| unreachable!()),
@@ -355,6 +358,7 @@ mod tests {
             session_token: session_token.map(ToOwned::to_owned),
             preserved: false,
             previous_session_id: None,
+            throttle_kbps: 0,
         }
     }
 
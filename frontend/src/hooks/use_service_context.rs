@@ -2,8 +2,8 @@ use crate::{
     model::WebConfig,
     services::{
         AuthService, ConfigService, DownloadsService, EventService, FlagsService, PlaylistService, RbacService,
-        StatusService, StreamHistoryService, StreamsService, ToastrService, UserApiService, UserService,
-        WebSocketService,
+        ResellerService, StatusService, StreamHistoryService, StreamsService, ToastrService, UserApiService,
+        UserService, WebSocketService,
     },
 };
 use std::rc::Rc;
@@ -23,6 +23,7 @@ pub struct Services {
     pub toastr: Rc<ToastrService>,
     pub websocket: Rc<WebSocketService>,
     pub rbac: Rc<RbacService>,
+    pub reseller: Rc<ResellerService>,
     pub flags: Rc<FlagsService>,
 }
 
@@ -41,6 +42,7 @@ impl Services {
         let user_api = Rc::new(UserApiService::new());
         let websocket = Rc::new(WebSocketService::new(Rc::clone(&status), Rc::clone(&event)));
         let rbac = Rc::new(RbacService::new());
+        let reseller = Rc::new(ResellerService::new());
         let flags = Rc::new(flags_service);
         Self {
             auth,
@@ -56,6 +58,7 @@ impl Services {
             toastr,
             websocket,
             rbac,
+            reseller,
             flags,
         }
     }
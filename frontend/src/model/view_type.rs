@@ -15,6 +15,7 @@ const PLAYLIST_EDITOR_LEGACY: &str = "playlist_editor";
 const PLAYLIST_EXPLORER: &str = "playlist_explorer";
 const PLAYLIST_EPG: &str = "playlist_epg";
 const RBAC: &str = "rbac";
+const RESELLER: &str = "reseller";
 const SOURCE_EDITOR: &str = "source_editor";
 const STREAM_HISTORY: &str = "stream_history";
 
@@ -33,6 +34,7 @@ pub enum ViewType {
     PlaylistExplorer,
     PlaylistEpg,
     Rbac,
+    Reseller,
 }
 
 impl FromStr for ViewType {
@@ -55,6 +57,7 @@ impl FromStr for ViewType {
             PLAYLIST_EXPLORER => Ok(ViewType::PlaylistExplorer),
             PLAYLIST_EPG => Ok(ViewType::PlaylistEpg),
             RBAC => Ok(ViewType::Rbac),
+            RESELLER => Ok(ViewType::Reseller),
             _ => info_err_res!("Unknown view type: {s}"),
         }
     }
@@ -76,6 +79,7 @@ impl fmt::Display for ViewType {
             ViewType::PlaylistExplorer => PLAYLIST_EXPLORER,
             ViewType::PlaylistEpg => PLAYLIST_EPG,
             ViewType::Rbac => RBAC,
+            ViewType::Reseller => RESELLER,
         };
         write!(f, "{s}")
     }
@@ -6,7 +6,7 @@ use log::warn;
 use shared::{
     model::{
         permission::{Permission, PermissionSet, PERM_ALL},
-        Claims, TokenResponse, UserCredential, ROLE_ADMIN, ROLE_API_USER, TOKEN_NO_AUTH,
+        Claims, TokenResponse, UserCredential, ROLE_ADMIN, ROLE_API_USER, ROLE_RESELLER, TOKEN_NO_AUTH,
     },
     utils::{concat_path, concat_path_leading_slash},
 };
@@ -43,6 +43,8 @@ impl AuthService {
 
     pub fn is_api_user(&self) -> bool { self.roles.borrow().iter().any(|r| r == ROLE_API_USER) }
 
+    pub fn is_reseller(&self) -> bool { self.roles.borrow().iter().any(|r| r == ROLE_RESELLER) }
+
     pub fn has_permission(&self, permission: Permission) -> bool {
         self.is_admin() || self.permissions.borrow().contains(permission)
     }
@@ -237,7 +237,28 @@ impl ConfigService {
                                                 .ok()
                                         })
                                     }
-                                    TargetOutputDto::HdHomeRun(_) => {}
+                                    TargetOutputDto::Enigma2(o) => {
+                                        o.t_filter = o.filter.as_ref().and_then(|flt| {
+                                            get_filter(flt, prepared_templates)
+                                                .map_err(|e| error!("Failed to parse Enigma2 output filter: {}", e))
+                                                .ok()
+                                        })
+                                    }
+                                    TargetOutputDto::Json(o) => {
+                                        o.t_filter = o.filter.as_ref().and_then(|flt| {
+                                            get_filter(flt, prepared_templates)
+                                                .map_err(|e| error!("Failed to parse Json output filter: {}", e))
+                                                .ok()
+                                        })
+                                    }
+                                    TargetOutputDto::Csv(o) => {
+                                        o.t_filter = o.filter.as_ref().and_then(|flt| {
+                                            get_filter(flt, prepared_templates)
+                                                .map_err(|e| error!("Failed to parse Csv output filter: {}", e))
+                                                .ok()
+                                        })
+                                    }
+                                    TargetOutputDto::HdHomeRun(_) | TargetOutputDto::Multicast(_) => {}
                                 }
                             }
                         }
@@ -7,6 +7,7 @@ mod flags_service;
 mod playlist_service;
 mod rbac_service;
 mod requests;
+mod reseller_service;
 mod status_service;
 mod stream_history_service;
 mod streams_service;
@@ -17,6 +18,7 @@ mod websocket_service;
 
 pub use self::{
     auth_service::*, config_service::*, dialog_service::*, downloads_service::*, event_service::*, flags_service::*,
-    playlist_service::*, rbac_service::*, requests::*, status_service::*, stream_history_service::*,
-    streams_service::*, toastr_service::*, user_api_service::*, user_service::*, websocket_service::*,
+    playlist_service::*, rbac_service::*, requests::*, reseller_service::*, status_service::*,
+    stream_history_service::*, streams_service::*, toastr_service::*, user_api_service::*, user_service::*,
+    websocket_service::*,
 };
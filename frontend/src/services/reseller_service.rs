@@ -0,0 +1,68 @@
+use crate::{
+    error::Error,
+    services::{get_base_href, request_get, request_post},
+};
+use log::error;
+use shared::{
+    model::{
+        ResellerAccountDto, ResellerStreamDto, ResellerStreamHistoryDto, ResellerUserCreateDto, ResellerUserExtendDto,
+    },
+    utils::{concat_path, concat_path_leading_slash},
+};
+use std::rc::Rc;
+
+#[derive(Debug, Default)]
+pub struct ResellerService {
+    reseller_account_path: String,
+    reseller_user_path: String,
+    reseller_streams_path: String,
+    reseller_history_path: String,
+}
+
+impl ResellerService {
+    pub fn new() -> Self {
+        let base_href = get_base_href();
+        Self {
+            reseller_account_path: concat_path_leading_slash(&base_href, "api/v1/reseller/account"),
+            reseller_user_path: concat_path_leading_slash(&base_href, "api/v1/reseller/user"),
+            reseller_streams_path: concat_path_leading_slash(&base_href, "api/v1/reseller/streams"),
+            reseller_history_path: concat_path_leading_slash(&base_href, "api/v1/reseller/history"),
+        }
+    }
+
+    pub async fn get_account(&self) -> Result<Option<Rc<ResellerAccountDto>>, Error> {
+        request_get::<Rc<ResellerAccountDto>>(&self.reseller_account_path, None, None)
+            .await
+            .inspect_err(|err| error!("{err}"))
+    }
+
+    pub async fn create_user(&self, user: &ResellerUserCreateDto) -> Result<(), Error> {
+        request_post::<&ResellerUserCreateDto, ()>(&self.reseller_user_path, user, None, None)
+            .await
+            .inspect_err(|err| error!("{err}"))
+            .map(|_| ())
+    }
+
+    pub async fn extend_user(&self, username: &str, months: u32) -> Result<(), Error> {
+        let path = concat_path(&concat_path(&self.reseller_user_path, username), "extend");
+        request_post::<ResellerUserExtendDto, ()>(&path, ResellerUserExtendDto { months }, None, None)
+            .await
+            .inspect_err(|err| error!("{err}"))
+            .map(|_| ())
+    }
+
+    pub async fn get_streams(&self) -> Result<Vec<ResellerStreamDto>, Error> {
+        request_get::<Vec<ResellerStreamDto>>(&self.reseller_streams_path, None, None)
+            .await
+            .map(Option::unwrap_or_default)
+            .inspect_err(|err| error!("{err}"))
+    }
+
+    pub async fn get_history(&self, days: u64) -> Result<Vec<ResellerStreamHistoryDto>, Error> {
+        let path = format!("{}?days={days}", self.reseller_history_path);
+        request_get::<Vec<ResellerStreamHistoryDto>>(&path, None, None)
+            .await
+            .map(Option::unwrap_or_default)
+            .inspect_err(|err| error!("{err}"))
+    }
+}
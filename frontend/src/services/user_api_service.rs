@@ -1,11 +1,14 @@
 use crate::{
     error::Error,
-    services::{get_base_href, request_get, request_post},
+    services::{get_base_href, request_delete, request_get, request_post},
 };
 use log::error;
 use shared::{
-    model::{PlaylistBouquetDto, PlaylistCategoriesDto},
-    utils::concat_path_leading_slash,
+    model::{
+        PlaylistBouquetDto, PlaylistCategoriesDto, UserAccountDto, UserDeviceDto, UserPasswordChangeDto, UserStreamDto,
+        UserStreamHistoryDto,
+    },
+    utils::{concat_path, concat_path_leading_slash},
 };
 use std::rc::Rc;
 
@@ -13,6 +16,11 @@ use std::rc::Rc;
 pub struct UserApiService {
     user_playlist_categories_path: String,
     user_playlist_bouquet_path: String,
+    user_account_path: String,
+    user_password_path: String,
+    user_streams_path: String,
+    user_history_path: String,
+    user_devices_path: String,
 }
 
 impl UserApiService {
@@ -21,6 +29,11 @@ impl UserApiService {
         Self {
             user_playlist_categories_path: concat_path_leading_slash(&base_href, "api/v1/user/playlist/categories"),
             user_playlist_bouquet_path: concat_path_leading_slash(&base_href, "api/v1/user/playlist/bouquet"),
+            user_account_path: concat_path_leading_slash(&base_href, "api/v1/user/account"),
+            user_password_path: concat_path_leading_slash(&base_href, "api/v1/user/password"),
+            user_streams_path: concat_path_leading_slash(&base_href, "api/v1/user/streams"),
+            user_history_path: concat_path_leading_slash(&base_href, "api/v1/user/history"),
+            user_devices_path: concat_path_leading_slash(&base_href, "api/v1/user/devices"),
         }
     }
 
@@ -42,4 +55,47 @@ impl UserApiService {
             .inspect_err(|err| error!("{err}"))
             .map(|_| ())
     }
+
+    pub async fn get_account(&self) -> Result<Option<Rc<UserAccountDto>>, Error> {
+        request_get::<Rc<UserAccountDto>>(&self.user_account_path, None, None).await.inspect_err(|err| error!("{err}"))
+    }
+
+    pub async fn change_password(&self, change: &UserPasswordChangeDto) -> Result<(), Error> {
+        request_post::<&UserPasswordChangeDto, ()>(&self.user_password_path, change, None, None)
+            .await
+            .inspect_err(|err| error!("{err}"))
+            .map(|_| ())
+    }
+
+    pub async fn get_streams(&self) -> Result<Vec<UserStreamDto>, Error> {
+        request_get::<Vec<UserStreamDto>>(&self.user_streams_path, None, None)
+            .await
+            .map(Option::unwrap_or_default)
+            .inspect_err(|err| error!("{err}"))
+    }
+
+    pub async fn stop_stream(&self, uid: u32) -> Result<(), Error> {
+        let path = concat_path(&self.user_streams_path, &uid.to_string());
+        request_delete::<()>(&path, None, None).await.map(|_| ()).inspect_err(|err| error!("{err}"))
+    }
+
+    pub async fn get_history(&self, days: u64) -> Result<Vec<UserStreamHistoryDto>, Error> {
+        let path = format!("{}?days={days}", self.user_history_path);
+        request_get::<Vec<UserStreamHistoryDto>>(&path, None, None)
+            .await
+            .map(Option::unwrap_or_default)
+            .inspect_err(|err| error!("{err}"))
+    }
+
+    pub async fn get_devices(&self) -> Result<Vec<UserDeviceDto>, Error> {
+        request_get::<Vec<UserDeviceDto>>(&self.user_devices_path, None, None)
+            .await
+            .map(Option::unwrap_or_default)
+            .inspect_err(|err| error!("{err}"))
+    }
+
+    pub async fn revoke_device(&self, device: &str) -> Result<(), Error> {
+        let path = concat_path(&self.user_devices_path, device);
+        request_delete::<()>(&path, None, None).await.map(|_| ()).inspect_err(|err| error!("{err}"))
+    }
 }
@@ -1,23 +1,28 @@
 use crate::{
     error::Error,
-    services::{get_base_href, request_delete, request_post, request_put, EventService},
+    services::{get_base_href, request_delete, request_get, request_post, request_put, EventService},
 };
 use log::error;
 use shared::{
-    model::ProxyUserCredentialsDto,
+    model::{ProxyUserCredentialsDto, UserDeviceDto},
     utils::{concat_path, concat_path_leading_slash},
 };
 use std::rc::Rc;
 
 pub struct UserService {
     user_path: String,
+    devices_path: String,
     event_service: Rc<EventService>,
 }
 
 impl UserService {
     pub fn new(event_service: Rc<EventService>) -> Self {
         let base_href = get_base_href();
-        Self { user_path: concat_path_leading_slash(&base_href, "api/v1/user"), event_service }
+        Self {
+            user_path: concat_path_leading_slash(&base_href, "api/v1/user"),
+            devices_path: concat_path_leading_slash(&base_href, "api/v1/devices"),
+            event_service,
+        }
     }
 
     pub async fn create_user(&self, target: String, user: ProxyUserCredentialsDto) -> Result<(), Error> {
@@ -62,4 +67,22 @@ impl UserService {
             }
         }
     }
+
+    pub async fn get_devices(&self, username: &str) -> Result<Vec<UserDeviceDto>, Error> {
+        let path = concat_path(&self.devices_path, username);
+        request_get::<Vec<UserDeviceDto>>(&path, None, None)
+            .await
+            .map(Option::unwrap_or_default)
+            .inspect_err(|err| error!("{err}"))
+    }
+
+    pub async fn approve_device(&self, username: &str, device: &str) -> Result<(), Error> {
+        let path = concat_path(&concat_path(&concat_path(&self.devices_path, username), device), "approve");
+        request_post::<(), ()>(&path, (), None, None).await.map(|_| ()).inspect_err(|err| error!("{err}"))
+    }
+
+    pub async fn revoke_device(&self, username: &str, device: &str) -> Result<(), Error> {
+        let path = concat_path(&concat_path(&self.devices_path, username), device);
+        request_delete::<()>(&path, None, None).await.map(|_| ()).inspect_err(|err| error!("{err}"))
+    }
 }
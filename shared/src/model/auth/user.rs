@@ -5,6 +5,7 @@ pub const TOKEN_NO_AUTH: &str = "authorized";
 
 pub const ROLE_ADMIN: &str = "ADMIN";
 pub const ROLE_API_USER: &str = "API_USER";
+pub const ROLE_RESELLER: &str = "RESELLER";
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Claims {
@@ -1,6 +1,6 @@
 use crate::{
     error::{info_err_res, TuliproxError},
-    model::ProxyUserCredentialsDto,
+    model::{LimitProfileDto, ParentalProfileDto, ProxyUserAccessScheduleDto, ProxyUserCredentialsDto, ResellerDto},
     utils::{default_auth_error_status, is_blank_optional_string, is_default_auth_error_status, is_false},
 };
 use std::collections::HashSet;
@@ -37,6 +37,15 @@ pub struct ApiProxyConfigDto {
     /// HTTP status code returned for authentication failures (default: 403).
     #[serde(default = "default_auth_error_status", skip_serializing_if = "is_default_auth_error_status")]
     pub auth_error_status: u16,
+    /// Parental profiles referenced by users to hide or pin-protect restricted content.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parental_profiles: Vec<ParentalProfileDto>,
+    /// Rate limit and throttle profiles referenced by users and targets.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub limit_profiles: Vec<LimitProfileDto>,
+    /// Web UI users provisioning their own proxy users with credits.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub resellers: Vec<ResellerDto>,
 }
 
 impl Default for ApiProxyConfigDto {
@@ -46,6 +55,9 @@ impl Default for ApiProxyConfigDto {
             user: Vec::new(),
             use_user_db: false,
             auth_error_status: default_auth_error_status(),
+            parental_profiles: Vec::new(),
+            limit_profiles: Vec::new(),
+            resellers: Vec::new(),
         }
     }
 }
@@ -117,12 +129,50 @@ impl ApiProxyConfigDto {
         }
     }
 
+    fn prepare_parental_profiles(&mut self, errors: &mut Vec<String>) {
+        let mut names = HashSet::new();
+        for profile in &mut self.parental_profiles {
+            if let Err(err) = profile.prepare() {
+                errors.push(err.message);
+            } else if !names.insert(profile.name.clone()) {
+                errors.push(format!("Non unique parental profile name found {}", &profile.name));
+            }
+        }
+    }
+
+    fn prepare_limit_profiles(&mut self, errors: &mut Vec<String>) {
+        let mut names = HashSet::new();
+        for profile in &mut self.limit_profiles {
+            if let Err(err) = profile.prepare() {
+                errors.push(err.message);
+            } else if !names.insert(profile.name.clone()) {
+                errors.push(format!("Non unique limit profile name found {}", &profile.name));
+            }
+        }
+    }
+
+    fn prepare_resellers(&mut self, errors: &mut Vec<String>) {
+        let mut names = HashSet::new();
+        for reseller in &mut self.resellers {
+            if let Err(err) = reseller.prepare() {
+                errors.push(err.message);
+            } else if !names.insert(reseller.name.clone()) {
+                errors.push(format!("Non unique reseller name found {}", &reseller.name));
+            }
+        }
+    }
+
     fn prepare_target_user(&mut self, errors: &mut Vec<String>) {
         let mut usernames = HashSet::new();
         let mut tokens = HashSet::new();
         for target_user in &mut self.user {
             for user in &mut target_user.credentials {
-                user.prepare();
+                if let Err(err) = user.prepare() {
+                    errors.push(format!("Invalid user {}: {}", &user.username, err.message));
+                }
+                if let Some(Err(err)) = user.access_schedule.as_ref().map(ProxyUserAccessScheduleDto::validate) {
+                    errors.push(format!("Invalid access schedule for user {}: {}", &user.username, err.message));
+                }
                 if usernames.contains(&user.username) {
                     errors.push(format!("Non unique username found {}", &user.username));
                 } else {
@@ -142,6 +192,33 @@ impl ApiProxyConfigDto {
                     }
                 }
 
+                if let Some(profile_name) = &user.parental_profile {
+                    if !self.parental_profiles.iter().any(|profile| profile.name.eq(profile_name)) {
+                        errors.push(format!(
+                            "No parental profile with name {} found for user {}",
+                            profile_name, &user.username
+                        ));
+                    }
+                }
+
+                if let Some(profile_name) = &user.limit_profile {
+                    if !self.limit_profiles.iter().any(|profile| profile.name.eq(profile_name)) {
+                        errors.push(format!(
+                            "No limit profile with name {} found for user {}",
+                            profile_name, &user.username
+                        ));
+                    }
+                }
+
+                if let Some(reseller_name) = &user.reseller {
+                    if !self.resellers.iter().any(|reseller| reseller.name.eq(reseller_name)) {
+                        errors.push(format!(
+                            "No reseller with name {} found for user {}",
+                            reseller_name, &user.username
+                        ));
+                    }
+                }
+
                 if let Some(server_info_name) = &user.server {
                     if !&self.server.iter().any(|server_info| server_info.name.eq(server_info_name)) {
                         errors.push(format!(
@@ -161,6 +238,9 @@ impl ApiProxyConfigDto {
         } else {
             self.prepare_server_config(&mut errors);
         }
+        self.prepare_parental_profiles(&mut errors);
+        self.prepare_limit_profiles(&mut errors);
+        self.prepare_resellers(&mut errors);
         self.prepare_target_user(&mut errors);
         if errors.is_empty() {
             Ok(())
@@ -1,9 +1,9 @@
 use crate::{
     error::{TuliproxError, TuliproxErrorKind},
-    model::{ProxyType, ProxyUserStatus},
+    model::{normalize_country_codes, validate_country_code, ProxyType, ProxyUserStatus},
     utils::{
         default_as_true, default_user_priority, deserialize_timestamp, is_blank_optional_string,
-        is_default_user_priority, is_true,
+        is_default_user_priority, is_false, is_true, is_zero_u16,
     },
 };
 
@@ -14,6 +14,283 @@ pub enum UserConnectionPermission {
     GracePeriod,
 }
 
+/// Rolling window a user quota is measured over.
+#[derive(Debug, Copy, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum UserQuotaPeriod {
+    #[default]
+    Day,
+    Week,
+    Month,
+}
+
+impl UserQuotaPeriod {
+    /// Window length in seconds, a month is counted as 30 days.
+    pub const fn window_secs(self) -> u64 {
+        match self {
+            Self::Day => 86_400,
+            Self::Week => 7 * 86_400,
+            Self::Month => 30 * 86_400,
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq, Default)]
+#[serde(deny_unknown_fields)]
+pub struct ProxyUserQuotaDto {
+    #[serde(default)]
+    pub period: UserQuotaPeriod,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_hours: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_traffic_mb: Option<u64>,
+}
+
+impl ProxyUserQuotaDto {
+    pub fn is_empty(&self) -> bool {
+        self.max_hours.is_none_or(|h| h == 0) && self.max_traffic_mb.is_none_or(|mb| mb == 0)
+    }
+}
+
+/// Quota usage of a user within the current rolling window.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq, Default)]
+pub struct UserQuotaStatusDto {
+    pub period: UserQuotaPeriod,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_hours: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_traffic_mb: Option<u64>,
+    pub used_secs: u64,
+    pub used_bytes: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remaining_secs: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remaining_bytes: Option<u64>,
+    pub exhausted: bool,
+}
+
+impl UserQuotaStatusDto {
+    pub fn new(quota: &ProxyUserQuotaDto, used_secs: u64, used_bytes: u64) -> Self {
+        let remaining_secs =
+            quota.max_hours.filter(|h| *h > 0).map(|h| (u64::from(h) * 3_600).saturating_sub(used_secs));
+        let remaining_bytes =
+            quota.max_traffic_mb.filter(|mb| *mb > 0).map(|mb| (mb * 1_048_576).saturating_sub(used_bytes));
+        Self {
+            period: quota.period,
+            max_hours: quota.max_hours,
+            max_traffic_mb: quota.max_traffic_mb,
+            used_secs,
+            used_bytes,
+            remaining_secs,
+            remaining_bytes,
+            exhausted: remaining_secs == Some(0) || remaining_bytes == Some(0),
+        }
+    }
+}
+
+/// Limits the number of distinct devices a user may stream from.
+#[derive(Debug, Copy, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq, Default)]
+#[serde(deny_unknown_fields)]
+pub struct ProxyUserDeviceLimitDto {
+    /// Maximum number of registered devices, `0` = unlimited.
+    #[serde(default)]
+    pub max_devices: u16,
+    /// New devices have to be approved by an admin before they may stream.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub approve_new: bool,
+}
+
+impl ProxyUserDeviceLimitDto {
+    pub fn is_empty(&self) -> bool { self.max_devices == 0 && !self.approve_new }
+}
+
+/// Result of checking a client country against the country access of a user.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum UserCountryCheck {
+    Allowed,
+    /// Outside the home country, admitted while travel days are left.
+    Travel,
+    Denied,
+}
+
+/// Country restrictions of a single user. A user with a home country may stream from other
+/// countries on up to `travel_days` days within a rolling year.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq, Default)]
+#[serde(deny_unknown_fields)]
+pub struct ProxyUserCountryAccessDto {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allow: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deny: Vec<String>,
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub home_country: Option<String>,
+    #[serde(default, skip_serializing_if = "is_zero_u16")]
+    pub travel_days: u16,
+}
+
+impl ProxyUserCountryAccessDto {
+    pub fn is_empty(&self) -> bool { self.allow.is_empty() && self.deny.is_empty() && self.home_country.is_none() }
+
+    pub fn prepare(&mut self) {
+        normalize_country_codes(&mut self.allow);
+        normalize_country_codes(&mut self.deny);
+        self.home_country =
+            self.home_country.as_ref().map(|c| c.trim().to_ascii_uppercase()).filter(|c| !c.is_empty());
+    }
+
+    pub fn validate(&self) -> Result<(), TuliproxError> {
+        self.allow
+            .iter()
+            .chain(self.deny.iter())
+            .chain(self.home_country.iter())
+            .try_for_each(|code| validate_country_code(code))
+    }
+
+    pub fn check(&self, country: &str) -> UserCountryCheck {
+        if self.deny.iter().any(|code| code.eq_ignore_ascii_case(country)) {
+            return UserCountryCheck::Denied;
+        }
+        let is_home = self.home_country.as_ref().is_some_and(|home| home.eq_ignore_ascii_case(country));
+        if is_home || self.allow.iter().any(|code| code.eq_ignore_ascii_case(country)) {
+            return UserCountryCheck::Allowed;
+        }
+        match self.home_country {
+            Some(_) if self.travel_days > 0 => UserCountryCheck::Travel,
+            Some(_) => UserCountryCheck::Denied,
+            None if self.allow.is_empty() => UserCountryCheck::Allowed,
+            None => UserCountryCheck::Denied,
+        }
+    }
+}
+
+/// A device registered on first use by a user with a device limit.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq, Default)]
+pub struct UserDeviceDto {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device_id: Option<String>,
+    pub user_agent: String,
+    pub network: String,
+    pub last_ip: String,
+    pub first_seen: u64,
+    pub last_seen: u64,
+    pub approved: bool,
+}
+
+/// Day of the week an access window applies to.
+#[derive(Debug, Copy, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum AccessScheduleDay {
+    Mon,
+    Tue,
+    Wed,
+    Thu,
+    Fri,
+    Sat,
+    Sun,
+}
+
+impl AccessScheduleDay {
+    pub const ALL: [Self; 7] = [Self::Mon, Self::Tue, Self::Wed, Self::Thu, Self::Fri, Self::Sat, Self::Sun];
+
+    /// `index` counts the days from monday (0) to sunday (6).
+    pub const fn from_index(index: u32) -> Self { Self::ALL[(index % 7) as usize] }
+
+    pub const fn index(self) -> u32 { self as u32 }
+
+    pub const fn previous(self) -> Self { Self::from_index(self.index() + 6) }
+}
+
+/// Parses a `HH:MM` time of day into minutes since midnight, `24:00` is accepted as end of day.
+pub fn parse_time_of_day(value: &str) -> Option<u16> {
+    let (hours, minutes) = value.trim().split_once(':')?;
+    let hours: u16 = hours.parse().ok()?;
+    let minutes: u16 = minutes.parse().ok()?;
+    if minutes > 59 || hours > 24 || (hours == 24 && minutes > 0) {
+        return None;
+    }
+    Some(hours * 60 + minutes)
+}
+
+/// Allowed time window. An empty `days` list applies the window to every day.
+/// A window whose `to` is not after `from` runs past midnight into the next day.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq, Default)]
+#[serde(deny_unknown_fields)]
+pub struct AccessWindowDto {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub days: Vec<AccessScheduleDay>,
+    pub from: String,
+    pub to: String,
+}
+
+impl AccessWindowDto {
+    fn applies_to(&self, day: AccessScheduleDay) -> bool { self.days.is_empty() || self.days.contains(&day) }
+
+    pub fn contains(&self, day: AccessScheduleDay, minute_of_day: u16) -> bool {
+        let (Some(from), Some(to)) = (parse_time_of_day(&self.from), parse_time_of_day(&self.to)) else {
+            return false;
+        };
+        if from < to {
+            self.applies_to(day) && from <= minute_of_day && minute_of_day < to
+        } else {
+            (self.applies_to(day) && minute_of_day >= from) || (self.applies_to(day.previous()) && minute_of_day < to)
+        }
+    }
+}
+
+/// Weekly schedule restricting when a proxy user can log in and stream.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq, Default)]
+#[serde(deny_unknown_fields)]
+pub struct ProxyUserAccessScheduleDto {
+    /// IANA timezone name, the timezone of the user's server info is used when not set.
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub timezone: Option<String>,
+    #[serde(default)]
+    pub windows: Vec<AccessWindowDto>,
+    /// Terminate running streams when the current window closes.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub kick_on_close: bool,
+}
+
+impl ProxyUserAccessScheduleDto {
+    pub fn is_empty(&self) -> bool { self.windows.is_empty() }
+
+    pub fn is_allowed(&self, day: AccessScheduleDay, minute_of_day: u16) -> bool {
+        self.windows.iter().any(|window| window.contains(day, minute_of_day))
+    }
+
+    fn prepare(&mut self) -> Result<(), TuliproxError> {
+        self.timezone = self.timezone.as_ref().map(|tz| tz.trim().to_string()).filter(|tz| !tz.is_empty());
+        for window in &mut self.windows {
+            window.from = window.from.trim().to_string();
+            window.to = window.to.trim().to_string();
+        }
+        if let Some(timezone) = self.timezone.as_deref() {
+            if timezone.parse::<chrono_tz::Tz>().is_err() {
+                return Err(TuliproxError::new(
+                    TuliproxErrorKind::Info,
+                    format!("Unknown access schedule timezone '{timezone}'"),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn validate(&self) -> Result<(), TuliproxError> {
+        for window in &self.windows {
+            for value in [&window.from, &window.to] {
+                if parse_time_of_day(value).is_none() {
+                    return Err(TuliproxError::new(
+                        TuliproxErrorKind::Info,
+                        format!("Invalid access schedule time '{value}', expected HH:MM"),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Default)]
 #[serde(deny_unknown_fields)]
 pub struct ProxyUserCredentialsDto {
@@ -47,10 +324,49 @@ pub struct ProxyUserCredentialsDto {
     pub soft_connections: u16,
     #[serde(default = "default_user_priority", skip_serializing_if = "is_default_user_priority")]
     pub soft_priority: i8,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quota: Option<ProxyUserQuotaDto>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub access_schedule: Option<ProxyUserAccessScheduleDto>,
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub parental_profile: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device_limit: Option<ProxyUserDeviceLimitDto>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub country_access: Option<ProxyUserCountryAccessDto>,
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub limit_profile: Option<String>,
+    /// Reseller owning the user.
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub reseller: Option<String>,
+    /// Overrides the `hls_packaging` target option, `false` keeps the provider HLS for `.m3u8` requests.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hls_packaging: Option<bool>,
 }
 
 impl ProxyUserCredentialsDto {
-    pub fn prepare(&mut self) { self.trim(); }
+    pub fn prepare(&mut self) -> Result<(), TuliproxError> {
+        self.trim();
+        if self.quota.as_ref().is_some_and(ProxyUserQuotaDto::is_empty) {
+            self.quota = None;
+        }
+        if let Some(schedule) = self.access_schedule.as_mut() {
+            schedule.prepare()?;
+        }
+        if self.access_schedule.as_ref().is_some_and(ProxyUserAccessScheduleDto::is_empty) {
+            self.access_schedule = None;
+        }
+        if self.device_limit.as_ref().is_some_and(ProxyUserDeviceLimitDto::is_empty) {
+            self.device_limit = None;
+        }
+        if let Some(access) = self.country_access.as_mut() {
+            access.prepare();
+        }
+        if self.country_access.as_ref().is_some_and(ProxyUserCountryAccessDto::is_empty) {
+            self.country_access = None;
+        }
+        Ok(())
+    }
 
     fn trim(&mut self) {
         self.username = self.username.trim().to_string();
@@ -61,6 +377,10 @@ impl ProxyUserCredentialsDto {
                 self.token = Some(tkn.trim().to_string());
             }
         }
+        self.parental_profile =
+            self.parental_profile.as_ref().map(|p| p.trim().to_string()).filter(|p| !p.is_empty());
+        self.limit_profile = self.limit_profile.as_ref().map(|p| p.trim().to_string()).filter(|p| !p.is_empty());
+        self.reseller = self.reseller.as_ref().map(|r| r.trim().to_string()).filter(|r| !r.is_empty());
     }
 
     pub fn validate(&self) -> Result<(), TuliproxError> {
@@ -70,6 +390,12 @@ impl ProxyUserCredentialsDto {
         if self.password.is_empty() {
             return Err(TuliproxError::new(TuliproxErrorKind::Info, "Password required".to_string()));
         }
+        if let Some(schedule) = &self.access_schedule {
+            schedule.validate()?;
+        }
+        if let Some(access) = &self.country_access {
+            access.validate()?;
+        }
         Ok(())
     }
 
@@ -94,3 +420,92 @@ impl ProxyUserCredentialsDto {
         true
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        parse_time_of_day, AccessScheduleDay, AccessWindowDto, ProxyUserAccessScheduleDto, ProxyUserCountryAccessDto,
+        UserCountryCheck,
+    };
+
+    fn window(days: &[AccessScheduleDay], from: &str, to: &str) -> AccessWindowDto {
+        AccessWindowDto { days: days.to_vec(), from: from.to_string(), to: to.to_string() }
+    }
+
+    #[test]
+    fn parses_time_of_day() {
+        assert_eq!(parse_time_of_day("07:30"), Some(450));
+        assert_eq!(parse_time_of_day("24:00"), Some(1_440));
+        assert_eq!(parse_time_of_day("24:01"), None);
+        assert_eq!(parse_time_of_day("12:60"), None);
+        assert_eq!(parse_time_of_day("noon"), None);
+    }
+
+    #[test]
+    fn same_day_window_applies_to_listed_days_only() {
+        let schedule = ProxyUserAccessScheduleDto {
+            windows: vec![window(&[AccessScheduleDay::Sat, AccessScheduleDay::Sun], "09:00", "20:00")],
+            ..ProxyUserAccessScheduleDto::default()
+        };
+        assert!(schedule.is_allowed(AccessScheduleDay::Sat, 9 * 60));
+        assert!(!schedule.is_allowed(AccessScheduleDay::Sat, 20 * 60));
+        assert!(!schedule.is_allowed(AccessScheduleDay::Mon, 12 * 60));
+    }
+
+    #[test]
+    fn overnight_window_continues_into_next_day() {
+        let schedule = ProxyUserAccessScheduleDto {
+            windows: vec![window(&[AccessScheduleDay::Fri], "18:00", "02:00")],
+            ..ProxyUserAccessScheduleDto::default()
+        };
+        assert!(schedule.is_allowed(AccessScheduleDay::Fri, 23 * 60));
+        assert!(schedule.is_allowed(AccessScheduleDay::Sat, 60));
+        assert!(!schedule.is_allowed(AccessScheduleDay::Sat, 3 * 60));
+        assert!(!schedule.is_allowed(AccessScheduleDay::Fri, 60));
+    }
+
+    #[test]
+    fn validate_rejects_malformed_times() {
+        let schedule = ProxyUserAccessScheduleDto {
+            windows: vec![window(&[], "7pm", "22:00")],
+            ..ProxyUserAccessScheduleDto::default()
+        };
+        assert!(schedule.validate().is_err());
+    }
+
+    #[test]
+    fn prepare_rejects_unknown_timezones() {
+        let mut schedule = ProxyUserAccessScheduleDto {
+            timezone: Some(" Europe/Vienna ".to_string()),
+            windows: vec![window(&[], "08:00", "22:00")],
+            ..ProxyUserAccessScheduleDto::default()
+        };
+        assert!(schedule.prepare().is_ok());
+        assert_eq!(schedule.timezone.as_deref(), Some("Europe/Vienna"));
+        schedule.timezone = Some("Mars/Olympus".to_string());
+        assert!(schedule.prepare().is_err());
+    }
+
+    #[test]
+    fn home_country_admits_travel_while_days_are_left() {
+        let mut access = ProxyUserCountryAccessDto {
+            allow: vec!["at".to_string()],
+            deny: vec!["RU".to_string()],
+            home_country: Some(" de ".to_string()),
+            travel_days: 14,
+        };
+        access.prepare();
+        assert!(access.validate().is_ok());
+        assert_eq!(access.check("DE"), UserCountryCheck::Allowed);
+        assert_eq!(access.check("AT"), UserCountryCheck::Allowed);
+        assert_eq!(access.check("ES"), UserCountryCheck::Travel);
+        assert_eq!(access.check("RU"), UserCountryCheck::Denied);
+
+        access.travel_days = 0;
+        assert_eq!(access.check("ES"), UserCountryCheck::Denied);
+        access.home_country = None;
+        assert_eq!(access.check("ES"), UserCountryCheck::Denied);
+        access.allow.clear();
+        assert_eq!(access.check("ES"), UserCountryCheck::Allowed);
+    }
+}
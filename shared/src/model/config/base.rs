@@ -1,9 +1,9 @@
 use crate::{
     error::{TuliproxError, TuliproxErrorKind},
     model::{
-        ConfigApiDto, HdHomeRunConfigDto, IpCheckConfigDto, LibraryConfigDto, LogConfigDto, MessagingConfigDto,
-        MetadataUpdateConfigDto, ProxyConfigDto, ReverseProxyConfigDto, ScheduleConfigDto, VideoConfigDto,
-        WebUiConfigDto,
+        ConfigApiDto, DlnaConfigDto, HdHomeRunConfigDto, IngestConfigDto, IpCheckConfigDto, LibraryConfigDto,
+        LogConfigDto, MessagingConfigDto, MetadataUpdateConfigDto, ProxyConfigDto, ReverseProxyConfigDto,
+        ScheduleConfigDto, VideoConfigDto, WebUiConfigDto,
     },
     utils::{
         default_connect_timeout_secs, default_custom_stream_response_path, default_default_user_agent,
@@ -75,11 +75,15 @@ pub struct ConfigDto {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub hdhomerun: Option<HdHomeRunConfigDto>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dlna: Option<DlnaConfigDto>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub proxy: Option<ProxyConfigDto>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub ipcheck: Option<IpCheckConfigDto>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub library: Option<LibraryConfigDto>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ingest: Option<IngestConfigDto>,
 }
 
 impl Default for ConfigDto {
@@ -110,9 +114,11 @@ impl Default for ConfigDto {
             messaging: None,
             reverse_proxy: None,
             hdhomerun: None,
+            dlna: None,
             proxy: None,
             ipcheck: None,
             library: None,
+            ingest: None,
         }
     }
 }
@@ -256,6 +262,12 @@ impl ConfigDto {
 
         self.prepare_web()?;
         self.prepare_hdhomerun(include_computed)?;
+        if let Some(dlna) = &mut self.dlna {
+            dlna.prepare(include_computed)?;
+        }
+        if let Some(ingest) = &mut self.ingest {
+            ingest.prepare()?;
+        }
         self.prepare_video_config()?;
         self.prepare_metadata_update_config()?;
 
@@ -0,0 +1,66 @@
+use crate::{
+    error::{info_err_res, TuliproxError},
+    utils::is_false,
+};
+
+/// Field used to recognise the same live channel in different inputs.
+#[derive(Debug, Copy, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ChannelLinkBy {
+    /// Same `epg_channel_id`, also assignable with a mapper to link channels explicitly.
+    EpgId,
+    /// Same channel name after EPG smart match normalization.
+    Name,
+}
+
+fn default_channel_link_by() -> Vec<ChannelLinkBy> { vec![ChannelLinkBy::EpgId, ChannelLinkBy::Name] }
+
+/// Links equivalent live channels of different inputs, the reverse proxy falls back to them on connect failures.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct ChannelFailoverDto {
+    #[serde(default = "default_channel_link_by")]
+    pub link_by: Vec<ChannelLinkBy>,
+    /// Removes the linked alternates from the playlist, only the first channel is listed.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub hide_alternates: bool,
+}
+
+impl Default for ChannelFailoverDto {
+    fn default() -> Self { Self { link_by: default_channel_link_by(), hide_alternates: false } }
+}
+
+impl ChannelFailoverDto {
+    pub fn prepare(&mut self) -> Result<(), TuliproxError> {
+        let mut link_by = Vec::with_capacity(self.link_by.len());
+        for field in &self.link_by {
+            if !link_by.contains(field) {
+                link_by.push(*field);
+            }
+        }
+        if link_by.is_empty() {
+            return info_err_res!("channel_failover needs at least one link_by field");
+        }
+        self.link_by = link_by;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ChannelFailoverDto, ChannelLinkBy};
+
+    #[test]
+    fn test_channel_failover_link_by() {
+        let mut failover: ChannelFailoverDto = serde_saphyr::from_str("hide_alternates: true\n").unwrap();
+        assert!(failover.prepare().is_ok());
+        assert_eq!(failover.link_by, vec![ChannelLinkBy::EpgId, ChannelLinkBy::Name]);
+
+        let mut failover: ChannelFailoverDto = serde_saphyr::from_str("link_by: [ name, name ]\n").unwrap();
+        assert!(failover.prepare().is_ok());
+        assert_eq!(failover.link_by, vec![ChannelLinkBy::Name]);
+
+        failover.link_by.clear();
+        assert!(failover.prepare().is_err());
+    }
+}
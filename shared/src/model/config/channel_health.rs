@@ -0,0 +1,80 @@
+use crate::error::{info_err_res, TuliproxError};
+
+/// How the outputs of a target list dead channels.
+#[derive(Debug, Copy, Clone, Default, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ChannelHealthMode {
+    /// Dead channels are removed from the outputs.
+    #[default]
+    Hide,
+    /// Dead channels are moved to the `group`.
+    Group,
+}
+
+fn default_channel_health_group() -> String { "Currently unavailable".to_string() }
+fn default_channel_health_dead_after() -> u16 { 3 }
+fn default_channel_health_reprobe_mins() -> u32 { 30 }
+
+/// Tracks the health of live channels and takes dead channels out of the Xtream, M3U and HDHomeRun outputs.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct ChannelHealthConfigDto {
+    #[serde(default)]
+    pub mode: ChannelHealthMode,
+    /// Group of the dead channels in `group` mode.
+    #[serde(default = "default_channel_health_group")]
+    pub group: String,
+    /// Consecutive failed connects and probes until a channel counts as dead.
+    #[serde(default = "default_channel_health_dead_after")]
+    pub dead_after: u16,
+    /// Minutes until a dead channel is probed again, doubled after every failed probe up to a day.
+    #[serde(default = "default_channel_health_reprobe_mins")]
+    pub reprobe_mins: u32,
+}
+
+impl Default for ChannelHealthConfigDto {
+    fn default() -> Self {
+        Self {
+            mode: ChannelHealthMode::default(),
+            group: default_channel_health_group(),
+            dead_after: default_channel_health_dead_after(),
+            reprobe_mins: default_channel_health_reprobe_mins(),
+        }
+    }
+}
+
+impl ChannelHealthConfigDto {
+    pub fn prepare(&mut self) -> Result<(), TuliproxError> {
+        self.group = self.group.trim().to_string();
+        if self.mode == ChannelHealthMode::Group && self.group.is_empty() {
+            return info_err_res!("channel_health group required for mode group");
+        }
+        if self.dead_after == 0 {
+            return info_err_res!("channel_health dead_after must be greater than 0");
+        }
+        if self.reprobe_mins == 0 {
+            return info_err_res!("channel_health reprobe_mins must be greater than 0");
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ChannelHealthConfigDto, ChannelHealthMode};
+
+    #[test]
+    fn test_channel_health_defaults_and_validation() {
+        let mut health: ChannelHealthConfigDto = serde_saphyr::from_str("mode: group\n").unwrap();
+        assert!(health.prepare().is_ok());
+        assert_eq!(health.mode, ChannelHealthMode::Group);
+        assert_eq!(health.group, "Currently unavailable");
+        assert_eq!(health.dead_after, 3);
+
+        health.group = "  ".to_string();
+        assert!(health.prepare().is_err());
+
+        let mut health = ChannelHealthConfigDto { dead_after: 0, ..ChannelHealthConfigDto::default() };
+        assert!(health.prepare().is_err());
+    }
+}
@@ -0,0 +1,121 @@
+use crate::{
+    error::TuliproxError,
+    info_err_res,
+    utils::{default_as_true, hash_string, is_blank_optional_string, is_true},
+};
+use log::warn;
+
+const DEFAULT_DLNA_FRIENDLY_NAME: &str = "Tuliprox";
+
+fn default_dlna_friendly_name() -> String { DEFAULT_DLNA_FRIENDLY_NAME.to_string() }
+fn is_default_dlna_friendly_name(value: &String) -> bool { value == DEFAULT_DLNA_FRIENDLY_NAME }
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct DlnaConfigDto {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Proxy user whose target is browsed and streamed.
+    #[serde(default)]
+    pub username: String,
+    #[serde(default = "default_dlna_friendly_name", skip_serializing_if = "is_default_dlna_friendly_name")]
+    pub friendly_name: String,
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub device_udn: Option<String>,
+    #[serde(default = "default_as_true", skip_serializing_if = "is_true")]
+    pub ssdp_discovery: bool,
+    /// Only clients from local networks are served. Otherwise every DLNA url carries the `token` of the user.
+    #[serde(default = "default_as_true", skip_serializing_if = "is_true")]
+    pub lan_only: bool,
+}
+
+impl Default for DlnaConfigDto {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            username: String::new(),
+            friendly_name: default_dlna_friendly_name(),
+            device_udn: None,
+            ssdp_discovery: true,
+            lan_only: true,
+        }
+    }
+}
+
+impl DlnaConfigDto {
+    pub fn is_empty(&self) -> bool {
+        !self.enabled
+            && self.username.trim().is_empty()
+            && is_default_dlna_friendly_name(&self.friendly_name)
+            && is_blank_optional_string(&self.device_udn)
+            && self.ssdp_discovery
+            && self.lan_only
+    }
+
+    pub fn clean(&mut self) {
+        self.username = self.username.trim().to_string();
+        self.friendly_name = self.friendly_name.trim().to_string();
+        if self.friendly_name.is_empty() {
+            self.friendly_name = default_dlna_friendly_name();
+        }
+        if let Some(udn) = self.device_udn.as_mut() {
+            // Ensure only the UUID part is stored.
+            let trimmed = udn.trim();
+            let uuid_part = trimmed.strip_prefix("uuid:").unwrap_or(trimmed);
+            *udn = uuid_part.split("::").next().unwrap_or(uuid_part).to_string();
+        }
+        if is_blank_optional_string(&self.device_udn) {
+            self.device_udn = None;
+        }
+    }
+
+    pub fn prepare(&mut self, include_computed: bool) -> Result<(), TuliproxError> {
+        self.clean();
+        if !self.enabled {
+            return Ok(());
+        }
+        if self.username.is_empty() {
+            return info_err_res!("DLNA requires a username");
+        }
+        if include_computed && self.device_udn.is_none() {
+            // A stable UUID derived from the user keeps the device identity across restarts.
+            let udn = hash_string(&format!("dlna:{}", self.username)).to_valid_uuid();
+            warn!("DLNA server is missing a device_udn. A new one has been generated: {udn}");
+            self.device_udn = Some(udn);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DlnaConfigDto;
+
+    #[test]
+    fn prepare_requires_username_when_enabled() {
+        let mut dto = DlnaConfigDto { enabled: true, ..DlnaConfigDto::default() };
+        assert!(dto.prepare(true).is_err());
+        dto.enabled = false;
+        assert!(dto.prepare(true).is_ok());
+    }
+
+    #[test]
+    fn prepare_generates_stable_udn_and_strips_prefix() {
+        let mut first = DlnaConfigDto { enabled: true, username: " tv ".to_string(), ..DlnaConfigDto::default() };
+        let mut second = first.clone();
+        first.prepare(true).unwrap();
+        second.prepare(true).unwrap();
+        assert_eq!(first.username, "tv");
+        assert!(first.device_udn.is_some());
+        assert_eq!(first.device_udn, second.device_udn);
+
+        let mut custom = DlnaConfigDto {
+            enabled: true,
+            username: "tv".to_string(),
+            device_udn: Some("uuid:1234::urn:schemas-upnp-org:device:MediaServer:1".to_string()),
+            ..DlnaConfigDto::default()
+        };
+        custom.prepare(true).unwrap();
+        assert_eq!(custom.device_udn.as_deref(), Some("1234"));
+    }
+}
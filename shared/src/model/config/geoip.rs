@@ -1,9 +1,17 @@
+use crate::error::{info_err_res, TuliproxError};
+
 pub fn default_geoip_url() -> String {
     String::from(
         "https://raw.githubusercontent.com/sapics/ip-location-db/refs/heads/main/asn-country/asn-country-ipv4.csv",
     )
 }
 
+pub fn default_geoip_ipv6_url() -> String {
+    String::from(
+        "https://raw.githubusercontent.com/sapics/ip-location-db/refs/heads/main/asn-country/asn-country-ipv6.csv",
+    )
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Default, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct GeoIpConfigDto {
@@ -11,8 +19,109 @@ pub struct GeoIpConfigDto {
     pub enabled: bool,
     #[serde(default = "default_geoip_url")]
     pub url: String,
+    #[serde(default = "default_geoip_ipv6_url")]
+    pub ipv6_url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub country_access: Option<CountryAccessDto>,
 }
 
 impl GeoIpConfigDto {
-    pub fn is_empty(&self) -> bool { !self.enabled && self.url.trim().is_empty() }
+    pub fn is_empty(&self) -> bool {
+        !self.enabled
+            && self.url.trim().is_empty()
+            && self.ipv6_url.trim().is_empty()
+            && self.country_access.as_ref().is_none_or(CountryAccessDto::is_empty)
+    }
+
+    pub fn prepare(&mut self) -> Result<(), TuliproxError> {
+        if let Some(access) = self.country_access.as_mut() {
+            access.prepare();
+            access.validate()?;
+        }
+        if self.country_access.as_ref().is_some_and(CountryAccessDto::is_empty) {
+            self.country_access = None;
+        }
+        if self.country_access.is_some() && !self.enabled {
+            return info_err_res!("geoip country_access requires geoip to be enabled");
+        }
+        Ok(())
+    }
+}
+
+/// Country allow and deny lists evaluated against the GeoIP country of a client.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Default, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct CountryAccessDto {
+    /// When not empty, only clients from these countries are admitted.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allow: Vec<String>,
+    /// Clients from these countries are always rejected.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deny: Vec<String>,
+}
+
+impl CountryAccessDto {
+    pub fn is_empty(&self) -> bool { self.allow.is_empty() && self.deny.is_empty() }
+
+    /// Uppercases the country codes and drops blank entries.
+    pub fn prepare(&mut self) {
+        normalize_country_codes(&mut self.allow);
+        normalize_country_codes(&mut self.deny);
+    }
+
+    pub fn validate(&self) -> Result<(), TuliproxError> {
+        self.allow.iter().chain(self.deny.iter()).try_for_each(|code| validate_country_code(code))
+    }
+
+    pub fn is_denied(&self, country: &str) -> bool {
+        self.deny.iter().any(|code| code.eq_ignore_ascii_case(country))
+    }
+
+    pub fn is_allowed(&self, country: &str) -> bool {
+        !self.is_denied(country)
+            && (self.allow.is_empty() || self.allow.iter().any(|code| code.eq_ignore_ascii_case(country)))
+    }
+}
+
+pub fn normalize_country_codes(codes: &mut Vec<String>) {
+    codes.retain(|code| !code.trim().is_empty());
+    for code in codes.iter_mut() {
+        *code = code.trim().to_ascii_uppercase();
+    }
+}
+
+/// Country codes are ISO 3166-1 alpha-2 codes like `DE` or `US`.
+pub fn validate_country_code(code: &str) -> Result<(), TuliproxError> {
+    if code.len() == 2 && code.chars().all(|c| c.is_ascii_alphabetic()) {
+        Ok(())
+    } else {
+        info_err_res!("Invalid country code '{code}', expected a two letter ISO code like 'DE'")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn country_access_deny_wins_over_allow() {
+        let mut access = CountryAccessDto { allow: vec![" de".to_string(), "at".to_string()], deny: vec!["AT".to_string()] };
+        access.prepare();
+        assert!(access.validate().is_ok());
+        assert_eq!(access.allow, vec!["DE".to_string(), "AT".to_string()]);
+        assert!(access.is_allowed("DE"));
+        assert!(!access.is_allowed("AT"));
+        assert!(!access.is_allowed("US"));
+        assert!(CountryAccessDto::default().is_allowed("US"));
+        assert!(CountryAccessDto { allow: vec!["GER".to_string()], deny: vec![] }.validate().is_err());
+    }
+
+    #[test]
+    fn country_access_requires_enabled_geoip() {
+        let access = CountryAccessDto { allow: vec!["DE".to_string()], deny: vec![] };
+        let mut geoip = GeoIpConfigDto { country_access: Some(access), ..GeoIpConfigDto::default() };
+        assert!(geoip.prepare().is_err());
+        geoip.enabled = true;
+        assert!(geoip.prepare().is_ok());
+    }
 }
@@ -0,0 +1,74 @@
+use crate::error::{info_err_res, TuliproxError};
+
+const MAX_HLS_SEGMENT_DURATION_SECS: u16 = 30;
+const MAX_HLS_WINDOW_SIZE: u16 = 30;
+
+/// Where the segments of a packaged HLS stream are kept.
+#[derive(Debug, Copy, Clone, Default, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HlsSegmentStorage {
+    #[default]
+    Memory,
+    /// Segment files in a temporary directory below the storage dir.
+    TempDir,
+}
+
+fn default_hls_segment_duration_secs() -> u16 { 4 }
+fn default_hls_window_size() -> u16 { 6 }
+
+/// Packages MPEG-TS live channels as rolling HLS playlists for clients requesting `.m3u8`.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct HlsPackagingConfigDto {
+    #[serde(default = "default_hls_segment_duration_secs")]
+    pub segment_duration_secs: u16,
+    /// Number of segments listed in the playlist.
+    #[serde(default = "default_hls_window_size")]
+    pub window_size: u16,
+    #[serde(default)]
+    pub storage: HlsSegmentStorage,
+}
+
+impl Default for HlsPackagingConfigDto {
+    fn default() -> Self {
+        Self {
+            segment_duration_secs: default_hls_segment_duration_secs(),
+            window_size: default_hls_window_size(),
+            storage: HlsSegmentStorage::default(),
+        }
+    }
+}
+
+impl HlsPackagingConfigDto {
+    pub fn prepare(&mut self) -> Result<(), TuliproxError> {
+        if self.segment_duration_secs == 0 || self.segment_duration_secs > MAX_HLS_SEGMENT_DURATION_SECS {
+            return info_err_res!(
+                "hls_packaging segment_duration_secs must be between 1 and {MAX_HLS_SEGMENT_DURATION_SECS}"
+            );
+        }
+        if self.window_size < 2 || self.window_size > MAX_HLS_WINDOW_SIZE {
+            return info_err_res!("hls_packaging window_size must be between 2 and {MAX_HLS_WINDOW_SIZE}");
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HlsPackagingConfigDto, HlsSegmentStorage};
+
+    #[test]
+    fn test_hls_packaging_defaults_and_limits() {
+        let mut packaging: HlsPackagingConfigDto = serde_saphyr::from_str("storage: temp_dir\n").unwrap();
+        assert!(packaging.prepare().is_ok());
+        assert_eq!(packaging.segment_duration_secs, 4);
+        assert_eq!(packaging.window_size, 6);
+        assert_eq!(packaging.storage, HlsSegmentStorage::TempDir);
+
+        packaging.window_size = 1;
+        assert!(packaging.prepare().is_err());
+        packaging.window_size = 6;
+        packaging.segment_duration_secs = 0;
+        assert!(packaging.prepare().is_err());
+    }
+}
@@ -0,0 +1,133 @@
+use crate::{error::TuliproxError, info_err_res, utils::is_blank_optional_string};
+use std::collections::HashSet;
+
+const DEFAULT_INGEST_GROUP: &str = "Local";
+
+fn default_ingest_group() -> String { DEFAULT_INGEST_GROUP.to_string() }
+fn is_default_ingest_group(value: &String) -> bool { value == DEFAULT_INGEST_GROUP }
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct IngestConfigDto {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Token the encoders send as bearer token or `token` query parameter.
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub token: Option<String>,
+    #[serde(default = "default_ingest_group", skip_serializing_if = "is_default_ingest_group")]
+    pub group: String,
+    #[serde(default)]
+    pub channels: Vec<IngestChannelConfigDto>,
+}
+
+impl Default for IngestConfigDto {
+    fn default() -> Self {
+        Self { enabled: false, token: None, group: default_ingest_group(), channels: Vec::new() }
+    }
+}
+
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct IngestChannelConfigDto {
+    /// Channel key of the ingest url `/ingest/{key}`.
+    pub key: String,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub group: Option<String>,
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub logo: Option<String>,
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub epg_channel_id: Option<String>,
+    /// Overrides the ingest token for this channel.
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub token: Option<String>,
+}
+
+fn clean_optional_string(value: &mut Option<String>) {
+    *value = value.as_deref().map(str::trim).filter(|v| !v.is_empty()).map(ToString::to_string);
+}
+
+impl IngestConfigDto {
+    pub fn is_empty(&self) -> bool {
+        !self.enabled
+            && is_blank_optional_string(&self.token)
+            && is_default_ingest_group(&self.group)
+            && self.channels.is_empty()
+    }
+
+    pub fn clean(&mut self) {
+        clean_optional_string(&mut self.token);
+        self.group = self.group.trim().to_string();
+        if self.group.is_empty() {
+            self.group = default_ingest_group();
+        }
+        for channel in &mut self.channels {
+            channel.key = channel.key.trim().to_string();
+            channel.name = channel.name.trim().to_string();
+            clean_optional_string(&mut channel.group);
+            clean_optional_string(&mut channel.logo);
+            clean_optional_string(&mut channel.epg_channel_id);
+            clean_optional_string(&mut channel.token);
+        }
+    }
+
+    pub fn prepare(&mut self) -> Result<(), TuliproxError> {
+        self.clean();
+        if !self.enabled {
+            return Ok(());
+        }
+        let mut keys = HashSet::new();
+        for channel in &self.channels {
+            if channel.key.is_empty()
+                || !channel.key.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+            {
+                return info_err_res!("Ingest channel key '{}' may only contain letters, digits, - and _", channel.key);
+            }
+            if !keys.insert(channel.key.as_str()) {
+                return info_err_res!("Ingest channel key '{}' is not unique", channel.key);
+            }
+            if channel.name.is_empty() {
+                return info_err_res!("Ingest channel '{}' requires a name", channel.key);
+            }
+            if self.token.is_none() && channel.token.is_none() {
+                return info_err_res!("Ingest channel '{}' requires a token", channel.key);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{IngestChannelConfigDto, IngestConfigDto};
+
+    fn channel(key: &str) -> IngestChannelConfigDto {
+        IngestChannelConfigDto { key: key.to_string(), name: "Camera".to_string(), ..IngestChannelConfigDto::default() }
+    }
+
+    #[test]
+    fn prepare_requires_token_and_unique_keys() {
+        let mut dto = IngestConfigDto { enabled: true, channels: vec![channel("cam")], ..IngestConfigDto::default() };
+        assert!(dto.prepare().is_err());
+        dto.channels[0].token = Some(" secret ".to_string());
+        assert!(dto.prepare().is_ok());
+        assert_eq!(dto.channels[0].token.as_deref(), Some("secret"));
+
+        dto.token = Some("secret".to_string());
+        dto.channels.push(channel("cam"));
+        assert!(dto.prepare().is_err());
+    }
+
+    #[test]
+    fn prepare_rejects_invalid_keys() {
+        let mut dto = IngestConfigDto {
+            enabled: true,
+            token: Some("secret".to_string()),
+            channels: vec![channel("house/announcements")],
+            ..IngestConfigDto::default()
+        };
+        assert!(dto.prepare().is_err());
+        dto.channels[0].key = "house_announcements-1".to_string();
+        assert!(dto.prepare().is_ok());
+    }
+}
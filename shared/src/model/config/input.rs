@@ -94,6 +94,8 @@ pub enum InputType {
     XtreamBatch,
     #[serde(rename = "library")]
     Library,
+    #[serde(rename = "ingest")]
+    Ingest,
 }
 
 impl InputType {
@@ -102,10 +104,13 @@ impl InputType {
     const M3U_BATCH: &'static str = "m3u_batch";
     const XTREAM_BATCH: &'static str = "xtream_batch";
     const LIBRARY: &'static str = "library";
+    const INGEST: &'static str = "ingest";
     pub fn is_xtream(&self) -> bool { matches!(self, Self::Xtream | Self::XtreamBatch) }
     pub fn is_m3u(&self) -> bool { matches!(self, Self::M3u | Self::M3uBatch) }
 
     pub fn is_library(&self) -> bool { matches!(self, Self::Library) }
+
+    pub fn is_ingest(&self) -> bool { matches!(self, Self::Ingest) }
 }
 
 impl Display for InputType {
@@ -119,6 +124,7 @@ impl Display for InputType {
                 Self::M3uBatch => Self::M3U_BATCH,
                 Self::XtreamBatch => Self::XTREAM_BATCH,
                 Self::Library => Self::LIBRARY,
+                Self::Ingest => Self::INGEST,
             }
         )
     }
@@ -138,6 +144,8 @@ impl FromStr for InputType {
             Ok(Self::XtreamBatch)
         } else if s.eq(Self::LIBRARY) {
             Ok(Self::Library)
+        } else if s.eq(Self::INGEST) {
+            Ok(Self::Ingest)
         } else {
             info_err_res!("Unknown InputType: {}", s)
         }
@@ -538,6 +546,7 @@ impl ConfigInputDto {
                 }
             }
             InputType::Library => InputType::Library,
+            InputType::Ingest => InputType::Ingest,
         };
     }
 
@@ -0,0 +1,65 @@
+use crate::{
+    error::{info_err_res, TuliproxError},
+    utils::{is_blank_optional_string, is_zero_u32, is_zero_u64, parse_to_kbps},
+};
+
+/// Request rate limit and stream bandwidth of the users and targets referencing the profile.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct LimitProfileDto {
+    pub name: String,
+    /// Bandwidth of each stream, e.g. `4Mbps`.
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub throttle: Option<String>,
+    /// Milliseconds to replenish one request token, 0 disables the request limit.
+    #[serde(default, skip_serializing_if = "is_zero_u64")]
+    pub period_millis: u64,
+    /// Requests a user can send at once before the period applies.
+    #[serde(default, skip_serializing_if = "is_zero_u32")]
+    pub burst_size: u32,
+    #[serde(skip)]
+    pub throttle_kbps: u64,
+}
+
+impl LimitProfileDto {
+    pub fn prepare(&mut self) -> Result<(), TuliproxError> {
+        self.name = self.name.trim().to_string();
+        if self.name.is_empty() {
+            return info_err_res!("Limit profile name is empty");
+        }
+        self.throttle = self.throttle.as_ref().map(|t| t.trim().to_string()).filter(|t| !t.is_empty());
+        self.throttle_kbps = match self.throttle.as_deref() {
+            Some(throttle) => match parse_to_kbps(throttle) {
+                Ok(kbps) => kbps,
+                Err(err) => return info_err_res!("Invalid throttle for limit profile {}: {err}", self.name),
+            },
+            None => 0,
+        };
+        if (self.period_millis == 0) != (self.burst_size == 0) {
+            return info_err_res!("Limit profile {} needs both period_millis and burst_size or none", self.name);
+        }
+        Ok(())
+    }
+
+    pub fn has_rate_limit(&self) -> bool { self.period_millis > 0 && self.burst_size > 0 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LimitProfileDto;
+
+    #[test]
+    fn test_limit_profile_parses_throttle() {
+        let mut profile: LimitProfileDto =
+            serde_saphyr::from_str("name: guest\nthrottle: 4Mbps\nperiod_millis: 2000\nburst_size: 30\n").unwrap();
+        assert!(profile.prepare().is_ok());
+        assert_eq!(profile.throttle_kbps, 4000);
+        assert!(profile.has_rate_limit());
+
+        profile.burst_size = 0;
+        assert!(profile.prepare().is_err());
+        profile.period_millis = 0;
+        profile.throttle = Some("fast".to_string());
+        assert!(profile.prepare().is_err());
+    }
+}
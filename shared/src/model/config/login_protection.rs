@@ -0,0 +1,106 @@
+use crate::{
+    error::{info_err_res, TuliproxError},
+    utils::{is_false, is_zero_u32, IpNetwork},
+};
+
+const fn default_login_max_failures() -> u32 { 5 }
+const fn is_default_login_max_failures(value: &u32) -> bool { *value == default_login_max_failures() }
+const fn default_login_find_time_secs() -> u64 { 600 }
+const fn is_default_login_find_time_secs(value: &u64) -> bool { *value == default_login_find_time_secs() }
+const fn default_login_ban_secs() -> u64 { 900 }
+const fn is_default_login_ban_secs(value: &u64) -> bool { *value == default_login_ban_secs() }
+const fn default_login_max_ban_secs() -> u64 { 86_400 }
+const fn is_default_login_max_ban_secs(value: &u64) -> bool { *value == default_login_max_ban_secs() }
+
+/// Tracks failed player API logins and temporarily bans clients and usernames.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct LoginProtectionConfigDto {
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub enabled: bool,
+    /// Failed logins of a client ip within `find_time_secs` until the ip is banned.
+    #[serde(default = "default_login_max_failures", skip_serializing_if = "is_default_login_max_failures")]
+    pub max_failures: u32,
+    /// Failed logins of a username from any ip until the username is locked, 0 disables user locks.
+    #[serde(default, skip_serializing_if = "is_zero_u32")]
+    pub user_max_failures: u32,
+    #[serde(default = "default_login_find_time_secs", skip_serializing_if = "is_default_login_find_time_secs")]
+    pub find_time_secs: u64,
+    /// Duration of the first ban, each repeated ban doubles it up to `max_ban_secs`.
+    #[serde(default = "default_login_ban_secs", skip_serializing_if = "is_default_login_ban_secs")]
+    pub ban_secs: u64,
+    #[serde(default = "default_login_max_ban_secs", skip_serializing_if = "is_default_login_max_ban_secs")]
+    pub max_ban_secs: u64,
+    /// Ips and networks which are never banned.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowlist: Vec<String>,
+}
+
+impl Default for LoginProtectionConfigDto {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_failures: default_login_max_failures(),
+            user_max_failures: 0,
+            find_time_secs: default_login_find_time_secs(),
+            ban_secs: default_login_ban_secs(),
+            max_ban_secs: default_login_max_ban_secs(),
+            allowlist: Vec::new(),
+        }
+    }
+}
+
+impl LoginProtectionConfigDto {
+    pub fn is_empty(&self) -> bool { self == &Self::default() }
+
+    pub(crate) fn prepare(&mut self) -> Result<(), TuliproxError> {
+        self.allowlist = self.allowlist.iter().map(|entry| entry.trim().to_string()).filter(|e| !e.is_empty()).collect();
+        if let Some(invalid) = self.allowlist.iter().find(|entry| IpNetwork::parse(entry).is_none()) {
+            return info_err_res!("Invalid ip or network in login_protection allowlist: {invalid}");
+        }
+        if self.enabled {
+            if self.max_failures == 0 {
+                return info_err_res!("`login_protection.max_failures` must be > 0");
+            }
+            if self.find_time_secs == 0 || self.ban_secs == 0 {
+                return info_err_res!("`login_protection.find_time_secs` and `ban_secs` must be > 0");
+            }
+            if self.max_ban_secs < self.ban_secs {
+                self.max_ban_secs = self.ban_secs;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A temporary ban of a client ip or username after repeated failed logins.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq, Default)]
+pub struct LoginBanDto {
+    /// The banned ip or username.
+    pub key: String,
+    /// `true` for a username lock, `false` for an ip ban.
+    pub user: bool,
+    pub banned_at: u64,
+    pub until: u64,
+    /// Number of consecutive bans, the ban duration doubles with each level.
+    pub level: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LoginProtectionConfigDto;
+
+    #[test]
+    fn prepare_validates_allowlist() {
+        let mut config = LoginProtectionConfigDto {
+            enabled: true,
+            allowlist: vec![" 10.0.0.0/8 ".to_string(), String::new(), "::1".to_string()],
+            ..LoginProtectionConfigDto::default()
+        };
+        assert!(config.prepare().is_ok());
+        assert_eq!(config.allowlist, vec!["10.0.0.0/8".to_string(), "::1".to_string()]);
+
+        config.allowlist.push("10.0.0.0/40".to_string());
+        assert!(config.prepare().is_err());
+    }
+}
@@ -6,7 +6,7 @@ macro_rules! check_input_credentials {
         let __tp_input_name_suffix =
             if __tp_input_name.is_empty() { String::new() } else { format!(" (input: {})", __tp_input_name) };
 
-        if !matches!($input_type, InputType::Library) {
+        if !matches!($input_type, InputType::Library | InputType::Ingest) {
             $this.url = $this.url.trim().to_string();
             if $this.url.is_empty() {
                 return info_err_res!("url for input is mandatory{}", __tp_input_name_suffix);
@@ -76,7 +76,7 @@ macro_rules! check_input_credentials {
                     }
                 }
             }
-            InputType::Library => {
+            InputType::Library | InputType::Ingest => {
                 // nothing to do
             }
         }
@@ -125,7 +125,7 @@ macro_rules! check_input_connections {
                     }
                 }
             }
-            InputType::Library => {}
+            InputType::Library | InputType::Ingest => {}
         }
     };
 }
@@ -4,17 +4,25 @@ mod api_user;
 mod app_config;
 mod base;
 mod cache;
+mod vod_cache;
+mod channel_failover;
+mod channel_health;
 mod config_type;
+mod dlna;
 mod epg;
 mod epg_smart_match;
 mod hdhomerun;
+mod hls_packaging;
+mod ingest;
 mod input;
 mod ipcheck;
+mod limit_profile;
 mod log;
 pub mod macros;
 mod messaging;
 mod metadata_update;
 mod panel_api;
+mod parental;
 mod paths;
 mod pattern_template;
 mod playlist_update_state;
@@ -22,6 +30,7 @@ mod proxy;
 mod proxy_type;
 mod rate_limit;
 mod rename;
+mod reseller;
 mod reverse_proxy;
 mod schedule;
 mod sort;
@@ -37,6 +46,7 @@ mod web_ui;
 
 mod favourites;
 mod geoip;
+mod login_protection;
 mod library;
 mod proxy_user_status;
 mod qos_aggregation;
@@ -48,19 +58,28 @@ pub use api_user::*;
 pub use app_config::*;
 pub use base::*;
 pub use cache::*;
+pub use vod_cache::*;
+pub use channel_failover::*;
+pub use channel_health::*;
 pub use config_type::*;
+pub use dlna::*;
 pub use epg::*;
 pub use epg_smart_match::*;
 pub use favourites::*;
 pub use geoip::*;
+pub use login_protection::*;
 pub use hdhomerun::*;
+pub use hls_packaging::*;
+pub use ingest::*;
 pub use input::*;
 pub use ipcheck::*;
+pub use limit_profile::*;
 pub use library::*;
 pub use log::*;
 pub use messaging::*;
 pub use metadata_update::*;
 pub use panel_api::*;
+pub use parental::*;
 pub use paths::*;
 pub use pattern_template::*;
 pub use playlist_update_state::*;
@@ -70,6 +89,7 @@ pub use proxy_user_status::*;
 pub use qos_aggregation::*;
 pub use rate_limit::*;
 pub use rename::*;
+pub use reseller::*;
 pub use reverse_proxy::*;
 pub use schedule::*;
 pub use sort::*;
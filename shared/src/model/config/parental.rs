@@ -0,0 +1,115 @@
+use crate::{
+    error::{info_err_res, TuliproxError},
+    foundation::{get_filter, Filter},
+    utils::{default_as_true, default_parental_unlock_mins, is_blank_optional_string, is_default_parental_unlock_mins, is_true},
+};
+
+/// How restricted content is handled for users of a parental profile.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ParentalMode {
+    /// Restricted content is never listed nor streamed.
+    #[default]
+    Hide,
+    /// Restricted content is unlocked by appending the profile pin to the request.
+    Pin,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ParentalProfileDto {
+    pub name: String,
+    /// Filter expression marking groups/channels as restricted.
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub filter: Option<String>,
+    /// Treat content flagged as adult by the provider as restricted.
+    #[serde(default = "default_as_true", skip_serializing_if = "is_true")]
+    pub adult: bool,
+    #[serde(default)]
+    pub mode: ParentalMode,
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub pin: Option<String>,
+    /// How long a correct pin keeps the restricted content unlocked for the user.
+    #[serde(default = "default_parental_unlock_mins", skip_serializing_if = "is_default_parental_unlock_mins")]
+    pub unlock_mins: u32,
+    #[serde(skip)]
+    pub t_filter: Option<Filter>,
+}
+
+impl Default for ParentalProfileDto {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            filter: None,
+            adult: default_as_true(),
+            mode: ParentalMode::default(),
+            pin: None,
+            unlock_mins: default_parental_unlock_mins(),
+            t_filter: None,
+        }
+    }
+}
+
+impl ParentalProfileDto {
+    pub fn prepare(&mut self) -> Result<(), TuliproxError> {
+        self.name = self.name.trim().to_string();
+        if self.name.is_empty() {
+            return info_err_res!("Parental profile name is empty");
+        }
+        self.filter = self.filter.as_ref().map(|f| f.trim().to_string()).filter(|f| !f.is_empty());
+        self.pin = self.pin.as_ref().map(|p| p.trim().to_string()).filter(|p| !p.is_empty());
+        if self.mode == ParentalMode::Pin && self.pin.is_none() {
+            return info_err_res!("Parental profile {} uses pin mode but has no pin", self.name);
+        }
+        self.t_filter = match self.filter.as_deref() {
+            Some(filter) => match get_filter(filter, None) {
+                Ok(filter) => Some(filter),
+                Err(err) => return info_err_res!("Invalid filter for parental profile {}: {}", self.name, err.message),
+            },
+            None => None,
+        };
+        Ok(())
+    }
+
+    pub fn is_pin_valid(&self, pin: &str) -> bool {
+        self.mode == ParentalMode::Pin && self.pin.as_deref().is_some_and(|p| p == pin.trim())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ParentalMode, ParentalProfileDto};
+
+    #[test]
+    fn test_parental_profile_pin_mode_requires_pin() {
+        let mut profile = ParentalProfileDto { name: "kids".to_string(), mode: ParentalMode::Pin, ..Default::default() };
+        assert!(profile.prepare().is_err());
+        profile.pin = Some(" 1234 ".to_string());
+        assert!(profile.prepare().is_ok());
+        assert!(profile.is_pin_valid("1234"));
+        assert!(!profile.is_pin_valid("0000"));
+    }
+
+    #[test]
+    fn test_parental_profile_compiles_filter() {
+        let mut profile = ParentalProfileDto {
+            name: "kids".to_string(),
+            filter: Some(r#"Group ~ "(?i)adult""#.to_string()),
+            ..Default::default()
+        };
+        assert!(profile.prepare().is_ok());
+        assert!(profile.t_filter.is_some());
+        assert!(profile.adult);
+
+        profile.filter = Some("Group ~".to_string());
+        assert!(profile.prepare().is_err());
+    }
+
+    #[test]
+    fn test_parental_profile_defaults_from_yaml() {
+        let profile: ParentalProfileDto = serde_saphyr::from_str("name: kids\n").unwrap();
+        assert_eq!(profile.mode, ParentalMode::Hide);
+        assert!(profile.adult);
+        assert_eq!(profile.unlock_mins, 60);
+    }
+}
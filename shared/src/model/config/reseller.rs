@@ -0,0 +1,57 @@
+use crate::{
+    error::{info_err_res, TuliproxError},
+    utils::{default_reseller_max_connections, is_default_reseller_max_connections},
+};
+
+/// A web UI user who provisions proxy users for the listed targets and pays with credits.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct ResellerDto {
+    /// Username of the web UI user acting as reseller.
+    pub name: String,
+    pub targets: Vec<String>,
+    /// Remaining credits, one credit is consumed per user and month.
+    #[serde(default)]
+    pub credits: u32,
+    /// Maximum connections the reseller can grant a single user.
+    #[serde(default = "default_reseller_max_connections", skip_serializing_if = "is_default_reseller_max_connections")]
+    pub max_connections: u32,
+}
+
+impl ResellerDto {
+    pub fn prepare(&mut self) -> Result<(), TuliproxError> {
+        self.name = self.name.trim().to_string();
+        if self.name.is_empty() {
+            return info_err_res!("Reseller name is empty");
+        }
+        self.targets = self.targets.iter().map(|t| t.trim().to_string()).filter(|t| !t.is_empty()).collect();
+        if self.targets.is_empty() {
+            return info_err_res!("Reseller {} has no targets", self.name);
+        }
+        if self.max_connections == 0 {
+            return info_err_res!("Reseller {} needs max_connections greater than 0", self.name);
+        }
+        Ok(())
+    }
+
+    pub fn has_target(&self, target: &str) -> bool { self.targets.iter().any(|t| t == target) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ResellerDto;
+
+    #[test]
+    fn test_reseller_requires_targets() {
+        let mut reseller: ResellerDto =
+            serde_saphyr::from_str("name: ' bob '\ntargets: [ 'iptv', ' ' ]\ncredits: 12\n").unwrap();
+        assert!(reseller.prepare().is_ok());
+        assert_eq!(reseller.name, "bob");
+        assert_eq!(reseller.targets, vec!["iptv".to_string()]);
+        assert_eq!(reseller.max_connections, 1);
+        assert!(reseller.has_target("iptv"));
+
+        reseller.targets.clear();
+        assert!(reseller.prepare().is_err());
+    }
+}
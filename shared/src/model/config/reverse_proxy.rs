@@ -2,13 +2,14 @@ use crate::{
     error::{TuliproxError, TuliproxErrorKind},
     info_err_res,
     model::{
-        CacheConfigDto, GeoIpConfigDto, QosAggregationConfigDto, RateLimitConfigDto, StreamConfigDto,
-        StreamHistoryConfigDto,
+        CacheConfigDto, GeoIpConfigDto, LoginProtectionConfigDto, QosAggregationConfigDto, RateLimitConfigDto,
+        StreamConfigDto, StreamHistoryConfigDto, VodCacheConfigDto,
     },
     utils::{
         default_resource_retry_attempts, default_resource_retry_backoff_ms, default_resource_retry_backoff_multiplier,
         hex_to_u8_16, is_default_resource_retry_attempts, is_default_resource_retry_backoff_ms,
         is_default_resource_retry_backoff_multiplier, is_empty_optional_vec, is_false,
+        IpNetwork,
     },
 };
 use log::warn;
@@ -43,6 +44,9 @@ pub struct ReverseProxyConfigDto {
     #[serde(default, skip_serializing_if = "is_false")]
     pub resource_rewrite_disabled: bool,
     pub rewrite_secret: String,
+    /// Proxies (ips or networks) whose `X-Real-IP` / `X-Forwarded-For` headers are trusted.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub trusted_proxies: Vec<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub resource_retry: Option<ResourceRetryConfigDto>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -52,8 +56,12 @@ pub struct ReverseProxyConfigDto {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub cache: Option<CacheConfigDto>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vod_cache: Option<VodCacheConfigDto>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub rate_limit: Option<RateLimitConfigDto>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub login_protection: Option<LoginProtectionConfigDto>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub geoip: Option<GeoIpConfigDto>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub stream_history: Option<StreamHistoryConfigDto>,
@@ -64,11 +72,14 @@ pub struct ReverseProxyConfigDto {
 impl ReverseProxyConfigDto {
     pub fn is_empty(&self) -> bool {
         !self.resource_rewrite_disabled
+            && self.trusted_proxies.is_empty()
             && self.disabled_header.as_ref().is_none_or(|d| d.is_empty())
             && self.resource_retry.as_ref().is_none_or(ResourceRetryConfigDto::is_default)
             && (self.stream.is_none() || self.stream.as_ref().is_some_and(|s| s.is_empty()))
             && (self.cache.is_none() || self.cache.as_ref().is_some_and(|c| c.is_empty()))
+            && self.vod_cache.as_ref().is_none_or(VodCacheConfigDto::is_empty)
             && (self.rate_limit.is_none() || self.rate_limit.as_ref().is_some_and(|r| r.is_empty()))
+            && self.login_protection.as_ref().is_none_or(LoginProtectionConfigDto::is_empty)
             && (self.geoip.is_none() || self.geoip.as_ref().is_some_and(|g| g.is_empty()))
             && (self.stream_history.is_none() || self.stream_history.as_ref().is_some_and(|s| s.is_empty()))
             && (self.qos_aggregation.is_none()
@@ -91,9 +102,15 @@ impl ReverseProxyConfigDto {
         if self.cache.as_ref().is_some_and(CacheConfigDto::is_empty) {
             self.cache = None;
         }
+        if self.vod_cache.as_ref().is_some_and(VodCacheConfigDto::is_empty) {
+            self.vod_cache = None;
+        }
         if self.rate_limit.as_ref().is_some_and(RateLimitConfigDto::is_empty) {
             self.rate_limit = None;
         }
+        if self.login_protection.as_ref().is_some_and(LoginProtectionConfigDto::is_empty) {
+            self.login_protection = None;
+        }
         if self.geoip.as_ref().is_some_and(GeoIpConfigDto::is_empty) {
             self.geoip = None;
         }
@@ -114,6 +131,12 @@ impl ReverseProxyConfigDto {
             hex_to_u8_16(&self.rewrite_secret).map_err(|e| TuliproxError::new(TuliproxErrorKind::Info, e))?;
         }
 
+        self.trusted_proxies =
+            self.trusted_proxies.iter().map(|entry| entry.trim().to_string()).filter(|e| !e.is_empty()).collect();
+        if let Some(invalid) = self.trusted_proxies.iter().find(|entry| IpNetwork::parse(entry).is_none()) {
+            return info_err_res!("Invalid ip or network in reverse_proxy trusted_proxies: {invalid}");
+        }
+
         if let Some(stream) = self.stream.as_mut() {
             stream.prepare()?;
         }
@@ -124,6 +147,13 @@ impl ReverseProxyConfigDto {
             }
             cache.prepare(storage_dir)?;
         }
+        if let Some(vod_cache) = self.vod_cache.as_mut() {
+            vod_cache.prepare()?;
+        }
+
+        if let Some(geoip) = self.geoip.as_mut() {
+            geoip.prepare()?;
+        }
 
         if let Some(rate_limit) = self.rate_limit.as_mut() {
             if rate_limit.enabled {
@@ -131,6 +161,10 @@ impl ReverseProxyConfigDto {
             }
         }
 
+        if let Some(login_protection) = self.login_protection.as_mut() {
+            login_protection.prepare()?;
+        }
+
         let mut stream_history_enabled = false;
         if let Some(stream_history) = self.stream_history.as_mut() {
             stream_history.prepare(storage_dir)?;
@@ -1,11 +1,15 @@
 use crate::{
     error::{info_err_res, TuliproxError, TuliproxErrorKind},
+    model::{parse_time_of_day, AccessWindowDto},
     utils::{
         default_as_true, default_catchup_session_ttl_secs, default_grace_period_millis,
-        default_grace_period_timeout_secs, default_hls_session_ttl_secs, default_shared_burst_buffer_mb,
-        is_blank_optional_string, is_default_catchup_session_ttl_secs, is_default_grace_period_millis,
-        is_default_grace_period_timeout_secs, is_default_hls_session_ttl_secs, is_default_shared_burst_buffer_mb,
-        is_true, parse_to_kbps,
+        default_grace_period_timeout_secs, default_hls_session_ttl_secs, default_prewarm_lookback_days,
+        default_prewarm_reserve_slots, default_prewarm_top_n, default_shared_burst_buffer_mb,
+        default_stall_bitrate_window_secs, default_stall_timeout_secs, is_blank_optional_string,
+        is_default_catchup_session_ttl_secs, is_default_grace_period_millis, is_default_grace_period_timeout_secs,
+        is_default_hls_session_ttl_secs, is_default_prewarm_lookback_days, is_default_prewarm_reserve_slots,
+        is_default_prewarm_top_n, is_default_shared_burst_buffer_mb, is_default_stall_bitrate_window_secs,
+        is_default_stall_timeout_secs, is_false, is_true, parse_to_kbps,
     },
 };
 
@@ -30,6 +34,127 @@ impl StreamBufferConfigDto {
     }
 }
 
+/// Reconnects live provider streams which stall without closing the connection.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct StreamStallDetectionConfigDto {
+    /// Seconds without payload until the provider stream counts as stalled.
+    #[serde(default = "default_stall_timeout_secs", skip_serializing_if = "is_default_stall_timeout_secs")]
+    pub timeout_secs: u64,
+    /// Bitrate floor like `256kbps`, a lower bitrate over `bitrate_window_secs` counts as stall.
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub min_bitrate: Option<String>,
+    #[serde(
+        default = "default_stall_bitrate_window_secs",
+        skip_serializing_if = "is_default_stall_bitrate_window_secs"
+    )]
+    pub bitrate_window_secs: u64,
+    /// Reconnects to the next provider url after a stall.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub failover: bool,
+}
+
+impl Default for StreamStallDetectionConfigDto {
+    fn default() -> Self {
+        Self {
+            timeout_secs: default_stall_timeout_secs(),
+            min_bitrate: None,
+            bitrate_window_secs: default_stall_bitrate_window_secs(),
+            failover: false,
+        }
+    }
+}
+
+impl StreamStallDetectionConfigDto {
+    fn prepare(&mut self) -> Result<(), TuliproxError> {
+        if self.timeout_secs == 0 {
+            return info_err_res!("`stall_detection.timeout_secs` must be greater than 0");
+        }
+        if let Some(min_bitrate) = self.min_bitrate.as_deref() {
+            parse_to_kbps(min_bitrate).map_err(|err| TuliproxError::new(TuliproxErrorKind::Info, err))?;
+            if self.bitrate_window_secs == 0 {
+                return info_err_res!("`stall_detection.bitrate_window_secs` must be greater than 0");
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Live channel of a target which is kept warm.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq, Default)]
+#[serde(deny_unknown_fields)]
+pub struct StreamPrewarmChannelDto {
+    pub target: String,
+    pub virtual_id: u32,
+}
+
+/// Keeps popular live channels connected as idle shared streams to cut the zapping time.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct StreamPrewarmConfigDto {
+    /// Number of the most watched channels kept warm.
+    #[serde(default = "default_prewarm_top_n", skip_serializing_if = "is_default_prewarm_top_n")]
+    pub top_n: u16,
+    /// Days of stream history which rank the channels by their views in the current hour.
+    #[serde(default = "default_prewarm_lookback_days", skip_serializing_if = "is_default_prewarm_lookback_days")]
+    pub lookback_days: u16,
+    /// Explicit channels, they replace the ranking from the stream history.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub channels: Vec<StreamPrewarmChannelDto>,
+    /// Provider connections which are kept free for viewers.
+    #[serde(default = "default_prewarm_reserve_slots", skip_serializing_if = "is_default_prewarm_reserve_slots")]
+    pub reserve_slots: u16,
+    /// Time windows in which channels are kept warm, an empty list keeps them warm all day.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub windows: Vec<AccessWindowDto>,
+    /// IANA timezone name of the windows and the hour ranking, UTC when not set.
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub timezone: Option<String>,
+}
+
+impl Default for StreamPrewarmConfigDto {
+    fn default() -> Self {
+        Self {
+            top_n: default_prewarm_top_n(),
+            lookback_days: default_prewarm_lookback_days(),
+            channels: Vec::new(),
+            reserve_slots: default_prewarm_reserve_slots(),
+            windows: Vec::new(),
+            timezone: None,
+        }
+    }
+}
+
+impl StreamPrewarmConfigDto {
+    fn prepare(&mut self) -> Result<(), TuliproxError> {
+        self.timezone = self.timezone.as_ref().map(|tz| tz.trim().to_string()).filter(|tz| !tz.is_empty());
+        if self.channels.is_empty() {
+            if self.top_n == 0 {
+                return info_err_res!("`prewarm.top_n` must be greater than 0");
+            }
+            if self.lookback_days == 0 {
+                return info_err_res!("`prewarm.lookback_days` must be greater than 0");
+            }
+        }
+        for channel in &mut self.channels {
+            channel.target = channel.target.trim().to_string();
+            if channel.target.is_empty() {
+                return info_err_res!("`prewarm.channels` entries need a target");
+            }
+        }
+        for window in &mut self.windows {
+            window.from = window.from.trim().to_string();
+            window.to = window.to.trim().to_string();
+            for value in [&window.from, &window.to] {
+                if parse_time_of_day(value).is_none() {
+                    return info_err_res!("Invalid prewarm window time '{value}', expected HH:MM");
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct StreamConfigDto {
@@ -59,6 +184,10 @@ pub struct StreamConfigDto {
     pub throttle_kbps: u64,
     #[serde(default = "default_shared_burst_buffer_mb", skip_serializing_if = "is_default_shared_burst_buffer_mb")]
     pub shared_burst_buffer_mb: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stall_detection: Option<StreamStallDetectionConfigDto>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prewarm: Option<StreamPrewarmConfigDto>,
 }
 
 impl Default for StreamConfigDto {
@@ -75,6 +204,8 @@ impl Default for StreamConfigDto {
             grace_period_hold_stream: true,
             hls_session_ttl_secs: default_hls_session_ttl_secs(),
             catchup_session_ttl_secs: default_catchup_session_ttl_secs(),
+            stall_detection: None,
+            prewarm: None,
         }
     }
 }
@@ -92,6 +223,8 @@ impl StreamConfigDto {
             && self.grace_period_hold_stream
             && self.hls_session_ttl_secs == default_hls_session_ttl_secs()
             && self.catchup_session_ttl_secs == default_catchup_session_ttl_secs()
+            && self.stall_detection.is_none()
+            && self.prewarm.is_none()
     }
 
     pub(crate) fn prepare(&mut self) -> Result<(), TuliproxError> {
@@ -117,6 +250,13 @@ impl StreamConfigDto {
             }
         }
 
+        if let Some(stall_detection) = self.stall_detection.as_mut() {
+            stall_detection.prepare()?;
+        }
+        if let Some(prewarm) = self.prewarm.as_mut() {
+            prewarm.prepare()?;
+        }
+
         if self.shared_burst_buffer_mb < MIN_SHARED_BURST_BUFFER_MB {
             return info_err_res!("`shared_burst_buffer_mb` must be at least {MIN_SHARED_BURST_BUFFER_MB} MB");
         }
@@ -3,14 +3,17 @@ use crate::{
     foundation::{get_filter, Filter},
     handle_tuliprox_error_result_list, info_err_res,
     model::{
-        ClusterFlags, ConfigFavouritesDto, ConfigRenameDto, ConfigSortDto, HdHomeRunDeviceOverview, PatternTemplate,
-        ProcessingOrder, StrmExportStyle, TargetType, TraktConfigDto,
+        ChannelFailoverDto, ChannelHealthConfigDto, ClusterFlags, ConfigFavouritesDto, ConfigRenameDto, ConfigSortDto,
+        CountryAccessDto, HdHomeRunDeviceOverview, HlsPackagingConfigDto, PatternTemplate, ProcessingOrder,
+        StrmExportStyle, TargetType, TraktConfigDto,
     },
     utils::{
         default_as_default, default_as_true, is_blank_optional_string, is_config_target_options_empty,
         is_default_processing_order, is_false, is_true, is_zero_u16,
     },
 };
+use std::collections::HashSet;
+use std::net::{Ipv4Addr, SocketAddrV4};
 
 #[derive(Default, Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
 #[serde(deny_unknown_fields)]
@@ -23,6 +26,17 @@ pub struct ConfigTargetOptions {
     pub remove_duplicates: bool,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub force_redirect: Option<ClusterFlags>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub country_access: Option<CountryAccessDto>,
+    /// Limit profile of the api-proxy config for users without their own profile.
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub limit_profile: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel_failover: Option<ChannelFailoverDto>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hls_packaging: Option<HlsPackagingConfigDto>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel_health: Option<ChannelHealthConfigDto>,
 }
 
 impl ConfigTargetOptions {
@@ -32,6 +46,11 @@ impl ConfigTargetOptions {
             && !self.remove_duplicates
             && (self.force_redirect.is_none()
                 || self.force_redirect.is_some_and(|f| f.has_full_flags() || f.is_empty()))
+            && self.country_access.as_ref().is_none_or(CountryAccessDto::is_empty)
+            && is_blank_optional_string(&self.limit_profile)
+            && self.channel_failover.is_none()
+            && self.hls_packaging.is_none()
+            && self.channel_health.is_none()
     }
 }
 
@@ -134,6 +153,10 @@ pub struct StrmTargetOutputDto {
     pub filter: Option<String>,
     #[serde(default, skip_serializing_if = "is_false")]
     pub add_quality_to_filename: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub write_nfo: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub download_artwork: bool,
 
     // New Fields for Metadata and Probe
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -167,6 +190,235 @@ impl Default for HdHomeRunTargetOutputDto {
     fn default() -> Self { Self { device: String::new(), username: String::new(), use_output: Some(TargetType::M3u) } }
 }
 
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Enigma2TargetOutputDto {
+    pub username: String,
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub directory: Option<String>,
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub bouquet_prefix: Option<String>,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub include_vod: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub epgimport: bool,
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub filter: Option<String>,
+    #[serde(skip)]
+    pub t_filter: Option<Filter>,
+}
+
+impl Enigma2TargetOutputDto {
+    pub fn prepare(&mut self, templates: Option<&[PatternTemplate]>) -> Result<(), TuliproxError> {
+        if let Some(raw_filter) = &self.filter {
+            self.t_filter = Some(get_filter(raw_filter, templates)?);
+        }
+        Ok(())
+    }
+
+    pub fn has_any_option(&self) -> bool {
+        self.directory.is_some()
+            || self.bouquet_prefix.is_some()
+            || self.include_vod
+            || self.epgimport
+            || self.filter.is_some()
+    }
+}
+
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct JsonTargetOutputDto {
+    pub username: String,
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub filename: Option<String>,
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub filter: Option<String>,
+    #[serde(skip)]
+    pub t_filter: Option<Filter>,
+}
+
+impl JsonTargetOutputDto {
+    pub fn prepare(&mut self, templates: Option<&[PatternTemplate]>) -> Result<(), TuliproxError> {
+        if let Some(raw_filter) = &self.filter {
+            self.t_filter = Some(get_filter(raw_filter, templates)?);
+        }
+        Ok(())
+    }
+
+    pub fn has_any_option(&self) -> bool {
+        self.filename.is_some() || self.filter.is_some()
+    }
+}
+
+/// Columns which can be selected for the csv output, named after the `PlaylistItemHeader` fields.
+pub const CSV_OUTPUT_COLUMNS: &[&str] = &[
+    "id", "virtual_id", "chno", "name", "title", "group", "parent_code", "audio_track", "time_shift", "rec",
+    "epg_channel_id", "logo", "logo_small", "url", "input_name", "category_id", "item_type", "xtream_cluster",
+];
+pub const CSV_OUTPUT_DEFAULT_COLUMNS: &[&str] =
+    &["virtual_id", "chno", "name", "group", "epg_channel_id", "logo", "url"];
+pub const CSV_OUTPUT_DEFAULT_DELIMITER: char = ',';
+
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct CsvTargetOutputDto {
+    pub username: String,
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub filename: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub columns: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub delimiter: Option<String>,
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub filter: Option<String>,
+    #[serde(skip)]
+    pub t_filter: Option<Filter>,
+}
+
+impl CsvTargetOutputDto {
+    pub fn prepare(&mut self, templates: Option<&[PatternTemplate]>) -> Result<(), TuliproxError> {
+        if let Some(columns) = self.columns.as_mut() {
+            columns.iter_mut().for_each(|c| *c = c.trim().to_lowercase());
+            columns.retain(|c| !c.is_empty());
+            if let Some(invalid) = columns.iter().find(|c| !CSV_OUTPUT_COLUMNS.contains(&c.as_str())) {
+                return info_err_res!(
+                    "Unknown csv output column: {invalid}, allowed are: {}",
+                    CSV_OUTPUT_COLUMNS.join(", ")
+                );
+            }
+        }
+        if self.columns.as_ref().is_some_and(Vec::is_empty) {
+            self.columns = None;
+        }
+        if let Some(delimiter) = self.delimiter.as_ref() {
+            if delimiter.chars().count() != 1 {
+                return info_err_res!("The csv output delimiter must be a single character: {delimiter:?}");
+            }
+        }
+        if let Some(raw_filter) = &self.filter {
+            self.t_filter = Some(get_filter(raw_filter, templates)?);
+        }
+        Ok(())
+    }
+
+    pub fn has_any_option(&self) -> bool {
+        self.filename.is_some() || self.columns.is_some() || self.delimiter.is_some() || self.filter.is_some()
+    }
+}
+
+pub const MULTICAST_OUTPUT_DEFAULT_TTL: u8 = 1;
+/// Default IGMP group membership interval (robustness 2 * query interval 125s + response 10s).
+pub const MULTICAST_OUTPUT_DEFAULT_IDLE_TIMEOUT_SECS: u16 = 260;
+
+fn default_multicast_ttl() -> u8 { MULTICAST_OUTPUT_DEFAULT_TTL }
+fn is_default_multicast_ttl(value: &u8) -> bool { *value == MULTICAST_OUTPUT_DEFAULT_TTL }
+fn default_multicast_idle_timeout_secs() -> u16 { MULTICAST_OUTPUT_DEFAULT_IDLE_TIMEOUT_SECS }
+fn is_default_multicast_idle_timeout_secs(value: &u16) -> bool { *value == MULTICAST_OUTPUT_DEFAULT_IDLE_TIMEOUT_SECS }
+
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct MulticastChannelDto {
+    /// Name or title of a live channel of the target.
+    pub name: String,
+    /// Multicast group and port, e.g. `239.1.1.1:5000`.
+    pub address: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rtp: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub always_on: Option<bool>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct MulticastTargetOutputDto {
+    /// IPv4 address of the network interface the groups are sent on.
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub interface: Option<String>,
+    #[serde(default = "default_multicast_ttl", skip_serializing_if = "is_default_multicast_ttl")]
+    pub ttl: u8,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub rtp: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub always_on: bool,
+    /// Seconds without IGMP membership report until an on-demand channel is stopped.
+    #[serde(
+        default = "default_multicast_idle_timeout_secs",
+        skip_serializing_if = "is_default_multicast_idle_timeout_secs"
+    )]
+    pub idle_timeout_secs: u16,
+    #[serde(default)]
+    pub channels: Vec<MulticastChannelDto>,
+}
+
+impl Default for MulticastTargetOutputDto {
+    fn default() -> Self {
+        Self {
+            interface: None,
+            ttl: default_multicast_ttl(),
+            rtp: false,
+            always_on: false,
+            idle_timeout_secs: default_multicast_idle_timeout_secs(),
+            channels: Vec::new(),
+        }
+    }
+}
+
+impl MulticastTargetOutputDto {
+    pub fn prepare(&mut self) -> Result<(), TuliproxError> {
+        self.interface = self.interface.as_ref().map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+        if let Some(interface) = self.interface.as_ref() {
+            if interface.parse::<Ipv4Addr>().is_err() {
+                return info_err_res!("Multicast output interface must be an IPv4 address: {interface}");
+            }
+        }
+        if self.ttl == 0 {
+            return info_err_res!("Multicast output ttl must be at least 1");
+        }
+        if self.idle_timeout_secs == 0 {
+            self.idle_timeout_secs = default_multicast_idle_timeout_secs();
+        }
+        let mut names = HashSet::new();
+        let mut addresses = HashSet::new();
+        for channel in &mut self.channels {
+            channel.name = channel.name.trim().to_string();
+            channel.address = channel.address.trim().to_string();
+            if channel.name.is_empty() {
+                return info_err_res!("Multicast output channel requires a name: {}", channel.address);
+            }
+            let Ok(address) = channel.address.parse::<SocketAddrV4>() else {
+                return info_err_res!(
+                    "Multicast output channel '{}' requires an address like 239.1.1.1:5000: {}",
+                    channel.name,
+                    channel.address
+                );
+            };
+            if !address.ip().is_multicast() || address.port() == 0 {
+                return info_err_res!(
+                    "Multicast output channel '{}' has no multicast group address: {}",
+                    channel.name,
+                    channel.address
+                );
+            }
+            if !names.insert(channel.name.to_lowercase()) {
+                return info_err_res!("Multicast output channel '{}' is defined twice", channel.name);
+            }
+            if !addresses.insert(address) {
+                return info_err_res!("Multicast output address {address} is used by more than one channel");
+            }
+        }
+        Ok(())
+    }
+
+    pub fn has_any_option(&self) -> bool {
+        self.interface.is_some()
+            || self.ttl != MULTICAST_OUTPUT_DEFAULT_TTL
+            || self.rtp
+            || self.always_on
+            || self.idle_timeout_secs != MULTICAST_OUTPUT_DEFAULT_IDLE_TIMEOUT_SECS
+            || !self.channels.is_empty()
+    }
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
 #[serde(deny_unknown_fields, tag = "type", rename_all = "lowercase")]
 pub enum TargetOutputDto {
@@ -174,6 +426,10 @@ pub enum TargetOutputDto {
     M3u(M3uTargetOutputDto),
     Strm(StrmTargetOutputDto),
     HdHomeRun(HdHomeRunTargetOutputDto),
+    Enigma2(Enigma2TargetOutputDto),
+    Json(JsonTargetOutputDto),
+    Csv(CsvTargetOutputDto),
+    Multicast(MulticastTargetOutputDto),
 }
 
 impl TargetOutputDto {
@@ -183,6 +439,10 @@ impl TargetOutputDto {
             TargetOutputDto::M3u(output) => output.prepare(templates),
             TargetOutputDto::Strm(output) => output.prepare(templates),
             TargetOutputDto::HdHomeRun(_) => Ok(()),
+            TargetOutputDto::Enigma2(output) => output.prepare(templates),
+            TargetOutputDto::Json(output) => output.prepare(templates),
+            TargetOutputDto::Csv(output) => output.prepare(templates),
+            TargetOutputDto::Multicast(output) => output.prepare(),
         }
     }
 }
@@ -256,6 +516,19 @@ impl ConfigTargetDto {
         if self.name.is_empty() {
             return info_err_res!("target name required");
         }
+        if let Some(access) = self.options.as_mut().and_then(|options| options.country_access.as_mut()) {
+            access.prepare();
+            access.validate()?;
+        }
+        if let Some(failover) = self.options.as_mut().and_then(|options| options.channel_failover.as_mut()) {
+            failover.prepare()?;
+        }
+        if let Some(packaging) = self.options.as_mut().and_then(|options| options.hls_packaging.as_mut()) {
+            packaging.prepare()?;
+        }
+        if let Some(health) = self.options.as_mut().and_then(|options| options.channel_health.as_mut()) {
+            health.prepare()?;
+        }
 
         let mut m3u_cnt = 0;
         let mut xtream_cnt = 0;
@@ -264,6 +537,10 @@ impl ConfigTargetDto {
         let mut hdhr_cnt = 0;
         let mut hdhomerun_needs_m3u = false;
         let mut hdhomerun_needs_xtream = false;
+        let mut enigma2_cnt = 0;
+        let mut json_cnt = 0;
+        let mut csv_cnt = 0;
+        let mut multicast_cnt = 0;
 
         //let mut strm_export_styles = vec![];
         let mut strm_directories: Vec<&str> = vec![];
@@ -348,10 +625,61 @@ impl ConfigTargetDto {
                         }
                     }
                 }
+                TargetOutputDto::Enigma2(enigma2_output) => {
+                    enigma2_cnt += 1;
+                    enigma2_output.username = enigma2_output.username.trim().to_string();
+                    if enigma2_output.username.is_empty() {
+                        return info_err_res!("Username is required for enigma2 type: {}", self.name);
+                    }
+                    enigma2_output.directory = enigma2_output.directory.as_ref().and_then(|s| {
+                        let trimmed = s.trim();
+                        if trimmed.is_empty() {
+                            None
+                        } else {
+                            Some(trimmed.to_string())
+                        }
+                    });
+                    enigma2_output.bouquet_prefix = enigma2_output.bouquet_prefix.as_ref().and_then(|s| {
+                        let trimmed = s.trim();
+                        if trimmed.is_empty() {
+                            None
+                        } else {
+                            Some(trimmed.to_string())
+                        }
+                    });
+                }
+                TargetOutputDto::Json(json_output) => {
+                    json_cnt += 1;
+                    json_output.username = json_output.username.trim().to_string();
+                    if json_output.username.is_empty() {
+                        return info_err_res!("Username is required for json type: {}", self.name);
+                    }
+                    json_output.filename =
+                        json_output.filename.as_ref().map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+                }
+                TargetOutputDto::Csv(csv_output) => {
+                    csv_cnt += 1;
+                    csv_output.username = csv_output.username.trim().to_string();
+                    if csv_output.username.is_empty() {
+                        return info_err_res!("Username is required for csv type: {}", self.name);
+                    }
+                    csv_output.filename =
+                        csv_output.filename.as_ref().map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+                }
+                TargetOutputDto::Multicast(_) => {
+                    multicast_cnt += 1;
+                }
             }
         }
 
-        if m3u_cnt > 1 || xtream_cnt > 1 || hdhr_cnt > 1 {
+        if m3u_cnt > 1
+            || xtream_cnt > 1
+            || hdhr_cnt > 1
+            || enigma2_cnt > 1
+            || json_cnt > 1
+            || csv_cnt > 1
+            || multicast_cnt > 1
+        {
             return info_err_res!("Multiple output formats with same type : {}", self.name);
         }
 
@@ -362,6 +690,27 @@ impl ConfigTargetDto {
             );
         }
 
+        if enigma2_cnt > 0 && xtream_cnt == 0 && m3u_cnt == 0 {
+            return info_err_res!(
+                "enigma2 output is only permitted when used in combination with xtream or m3u output: {}",
+                self.name
+            );
+        }
+
+        if (json_cnt > 0 || csv_cnt > 0) && m3u_cnt == 0 && xtream_cnt == 0 {
+            return info_err_res!(
+                "json and csv output are only permitted when used in combination with m3u or xtream output: {}",
+                self.name
+            );
+        }
+
+        if multicast_cnt > 0 && xtream_cnt == 0 && m3u_cnt == 0 {
+            return info_err_res!(
+                "multicast output is only permitted when used in combination with xtream or m3u output: {}",
+                self.name
+            );
+        }
+
         if hdhr_cnt > 0 {
             if xtream_cnt == 0 && m3u_cnt == 0 {
                 return info_err_res!(
@@ -0,0 +1,82 @@
+use crate::{
+    error::TuliproxError,
+    info_err_res,
+    utils::{
+        is_blank_optional_str, is_blank_optional_string, parse_duration_seconds, parse_size_base_2,
+        DEFAULT_VOD_CACHE_DIR,
+    },
+};
+
+fn is_blank_or_default_vod_cache_dir(path: &Option<String>) -> bool {
+    path.as_deref().is_none_or(|value| value.trim().is_empty() || value.trim() == DEFAULT_VOD_CACHE_DIR)
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct VodCacheConfigDto {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Maximum size of all cached files, e.g. `50GB`.
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub size: Option<String>,
+    #[serde(default, alias = "dir", skip_serializing_if = "is_blank_or_default_vod_cache_dir")]
+    pub directory: Option<String>,
+    /// Files not requested for this duration are evicted, e.g. `30d`.
+    #[serde(default, skip_serializing_if = "is_blank_optional_string")]
+    pub max_age: Option<String>,
+}
+
+impl VodCacheConfigDto {
+    pub fn is_empty(&self) -> bool {
+        !self.enabled
+            && is_blank_optional_str(self.size.as_deref())
+            && is_blank_optional_str(self.directory.as_deref())
+            && is_blank_optional_str(self.max_age.as_deref())
+    }
+
+    pub(crate) fn prepare(&mut self) -> Result<(), TuliproxError> {
+        if self.enabled {
+            if is_blank_or_default_vod_cache_dir(&self.directory) {
+                self.directory = Some(DEFAULT_VOD_CACHE_DIR.to_string());
+            } else if let Some(dir) = self.directory.as_ref() {
+                self.directory = Some(dir.trim().to_string());
+            }
+
+            match self.size.as_deref().map(str::trim) {
+                Some(size) if !size.is_empty() => {
+                    if let Err(err) = parse_size_base_2(size) {
+                        return info_err_res!("Failed to read vod cache size: {err}");
+                    }
+                }
+                _ => return info_err_res!("vod_cache requires a size"),
+            }
+
+            if let Some(max_age) = self.max_age.as_deref().filter(|v| !v.trim().is_empty()) {
+                if parse_duration_seconds(max_age, false).is_none() {
+                    return info_err_res!("Failed to read vod cache max_age: {max_age}");
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::VodCacheConfigDto;
+    use crate::utils::DEFAULT_VOD_CACHE_DIR;
+
+    #[test]
+    fn prepare_requires_valid_size_and_max_age() {
+        let mut cache = VodCacheConfigDto { enabled: true, ..VodCacheConfigDto::default() };
+        assert!(cache.prepare().is_err());
+
+        cache.size = Some("20GB".to_string());
+        cache.max_age = Some("2 weeks".to_string());
+        assert!(cache.prepare().is_err());
+
+        cache.max_age = Some("14d".to_string());
+        assert!(cache.prepare().is_ok());
+        assert_eq!(cache.directory.as_deref(), Some(DEFAULT_VOD_CACHE_DIR));
+    }
+}
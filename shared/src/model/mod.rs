@@ -19,6 +19,7 @@ mod playlist_info_document;
 mod playlist_request;
 mod processing_order;
 mod regex_cache;
+mod reseller;
 mod search_request;
 mod short_epg;
 mod stats;
@@ -31,6 +32,7 @@ pub mod system_info;
 mod target_type;
 mod transfer;
 mod ui_playlist_item;
+mod user_account;
 mod user_command;
 mod uuidtype;
 mod web_socket;
@@ -41,7 +43,7 @@ pub use self::{
     active_user_connection_change::*, auth::*, cluster_flags::*, config::*, download::*, epg::*, epg_request::*,
     ip_check::*, item_field::*, library_request::*, mapping::*, media_properties::*, messaging::*, playlist::*,
     playlist_categories::*, playlist_info_document::*, playlist_request::*, processing_order::*, regex_cache::*,
-    search_request::*, short_epg::*, stats::*, status_check::*, stream_info::*, stream_meter::*, stream_properties::*,
-    strm_export_style::*, system_info::*, target_type::*, transfer::*, ui_playlist_item::*, user_command::*,
-    uuidtype::*, web_socket::*, xtream::*,
+    reseller::*, search_request::*, short_epg::*, stats::*, status_check::*, stream_info::*, stream_meter::*,
+    stream_properties::*, strm_export_style::*, system_info::*, target_type::*, transfer::*, ui_playlist_item::*,
+    user_account::*, user_command::*, uuidtype::*, web_socket::*, xtream::*,
 };
@@ -2,7 +2,8 @@ use crate::{
     create_bitset,
     model::{
         xtream_const, ClusterFlags, CommonPlaylistItem, ConfigTargetOptions, EpisodeStreamProperties,
-        SeriesStreamProperties, StreamProperties, UUIDType, VideoStreamProperties, XtreamInfoDocument,
+        MediaSidecarProperties, SeriesStreamProperties, StreamProperties, UUIDType, VideoStreamProperties,
+        XtreamInfoDocument,
     },
     utils::{
         arc_str_option_serde, arc_str_serde, concat_path, extract_extension_from_url, generate_runtime_playlist_uuid,
@@ -735,6 +736,18 @@ impl XtreamMappingOptions {
         }
         resource_url.to_string()
     }
+
+    /// Authenticated url of a local library subtitle or audio file.
+    /// Web UI requests have no user credentials, there are no sidecar urls for them.
+    pub fn get_sidecar_url(&self, sidecar: &MediaSidecarProperties) -> Option<String> {
+        if self.web_ui_request {
+            return None;
+        }
+        Some(format!(
+            "{}/library/sidecar/{}/{}/{}/{}.{}",
+            self.base_url, self.username, self.password, sidecar.entry, sidecar.id, sidecar.extension
+        ))
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
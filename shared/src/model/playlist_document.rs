@@ -158,6 +158,8 @@ impl XtreamPlaylistItem {
                         rating: Arc::clone(&empty_str),
                         runtime: Arc::clone(&empty_str),
                         status: "Released".intern(),
+                        subtitles: Vec::new(),
+                        audio_tracks: Vec::new(),
                     },
                     movie_data: XtreamVideoMovieData {
                         stream_id: self.virtual_id,
@@ -1,9 +1,9 @@
 use crate::{
     concat_string,
     model::{
-        info_doc_utils::InfoDocUtils, PlaylistItemType, SeriesStreamDetailEpisodeProperties,
-        SeriesStreamDetailSeasonProperties, SeriesStreamProperties, StreamProperties, VideoStreamProperties, VirtualId,
-        XtreamCluster, XtreamMappingFlags, XtreamMappingOptions,
+        info_doc_utils::InfoDocUtils, MediaSidecarKind, MediaSidecarProperties, PlaylistItemType,
+        SeriesStreamDetailEpisodeProperties, SeriesStreamDetailSeasonProperties, SeriesStreamProperties, StreamProperties,
+        VideoStreamProperties, VirtualId, XtreamCluster, XtreamMappingFlags, XtreamMappingOptions,
     },
     utils::{arc_str_option_null_if_empty_serde, arc_str_option_serde, arc_str_serde, arc_str_vec_serde, Internable},
 };
@@ -95,6 +95,24 @@ pub struct XtreamVideoInfoData {
     pub runtime: Arc<str>,
     #[serde(with = "arc_str_serde")]
     pub status: Arc<str>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub subtitles: Vec<XtreamSidecarInfoDoc>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub audio_tracks: Vec<XtreamSidecarInfoDoc>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct XtreamSidecarInfoDoc {
+    #[serde(with = "arc_str_serde")]
+    pub url: Arc<str>,
+    #[serde(default, with = "arc_str_option_serde", skip_serializing_if = "Option::is_none")]
+    pub language: Option<Arc<str>>,
+    #[serde(default)]
+    pub forced: bool,
+    #[serde(default)]
+    pub hearing_impaired: bool,
+    #[serde(with = "arc_str_serde")]
+    pub container_extension: Arc<str>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -220,6 +238,32 @@ pub struct XtreamSeriesEpisodeInfoData {
     pub video: Value,
     pub audio: Value,
     pub bitrate: u32,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub subtitles: Vec<XtreamSidecarInfoDoc>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub audio_tracks: Vec<XtreamSidecarInfoDoc>,
+}
+
+fn sidecars_to_info_document(
+    options: &XtreamMappingOptions,
+    sidecars: Option<&Vec<MediaSidecarProperties>>,
+    kind: MediaSidecarKind,
+) -> Vec<XtreamSidecarInfoDoc> {
+    sidecars.map_or_else(Vec::new, |sidecars| {
+        sidecars
+            .iter()
+            .filter(|sidecar| sidecar.kind == kind)
+            .filter_map(|sidecar| {
+                options.get_sidecar_url(sidecar).map(|url| XtreamSidecarInfoDoc {
+                    url: url.intern(),
+                    language: sidecar.language.as_ref().map(Arc::clone),
+                    forced: sidecar.forced,
+                    hearing_impaired: sidecar.hearing_impaired,
+                    container_extension: Arc::clone(&sidecar.extension),
+                })
+            })
+            .collect()
+    })
 }
 
 impl StreamProperties {
@@ -381,6 +425,8 @@ impl StreamProperties {
                 rating: InfoDocUtils::limited(video.rating.unwrap_or_default()).intern(),
                 runtime: details.runtime.as_ref().map(Arc::clone).unwrap_or_else(|| Arc::clone(&empty_str)),
                 status: details.status.as_ref().map(Arc::clone).unwrap_or_else(|| Arc::clone(&empty_str)),
+                subtitles: sidecars_to_info_document(options, details.sidecars.as_ref(), MediaSidecarKind::Subtitle),
+                audio_tracks: sidecars_to_info_document(options, details.sidecars.as_ref(), MediaSidecarKind::Audio),
             }
         } else {
             XtreamVideoInfoData {
@@ -412,6 +458,8 @@ impl StreamProperties {
                 rating: InfoDocUtils::limited(video.rating.unwrap_or_default()).intern(),
                 runtime: zero_str,
                 status: "Released".intern(),
+                subtitles: Vec::new(),
+                audio_tracks: Vec::new(),
             }
         };
 
@@ -536,6 +584,8 @@ impl StreamProperties {
                     video: InfoDocUtils::build_value(ep.video.as_ref().map(Arc::as_ref)),
                     audio: InfoDocUtils::build_value(ep.audio.as_ref().map(Arc::as_ref)),
                     bitrate: ep.bitrate,
+                    subtitles: sidecars_to_info_document(options, ep.sidecars.as_ref(), MediaSidecarKind::Subtitle),
+                    audio_tracks: sidecars_to_info_document(options, ep.sidecars.as_ref(), MediaSidecarKind::Audio),
                 },
                 custom_sid: ep.custom_sid.as_ref().map(Arc::clone),
                 added: Arc::clone(&ep.added),
@@ -586,6 +636,8 @@ impl Default for XtreamVideoInfoDoc {
                 rating: Arc::clone(&empty_str),
                 runtime: Arc::clone(&empty_str),
                 status: Arc::clone(&empty_str),
+                subtitles: Vec::new(),
+                audio_tracks: Vec::new(),
             },
             movie_data: XtreamVideoMovieData {
                 stream_id: 0,
@@ -29,6 +29,8 @@ pub enum PlaylistRequest {
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 pub enum PlaylistUrlResolveRequest {
     Webplayer { target_id: u16, virtual_id: u32, cluster: XtreamCluster },
+    /// Url of the Web UI preview player, live channels are requested as packaged HLS.
+    Preview { target_id: u16, virtual_id: u32, cluster: XtreamCluster },
     Provider { playlist_request: PlaylistRequest, url: String },
 }
 
@@ -0,0 +1,126 @@
+use crate::{
+    error::{info_err_res, TuliproxError},
+    model::{ProxyUserStatus, UserStreamDto, UserStreamHistoryDto},
+};
+
+/// Months a reseller can book for a user with one request.
+pub const RESELLER_MAX_MONTHS: u32 = 12;
+
+/// Credits, allowed targets and owned users of the logged-in reseller.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Default, PartialEq)]
+pub struct ResellerAccountDto {
+    pub name: String,
+    pub credits: u32,
+    pub max_connections: u32,
+    pub targets: Vec<String>,
+    pub users: Vec<ResellerUserDto>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Default, PartialEq, Eq)]
+pub struct ResellerUserDto {
+    pub username: String,
+    pub password: String,
+    pub target: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<ProxyUserStatus>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exp_date: Option<i64>,
+    pub max_connections: u32,
+    pub active_connections: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Default, PartialEq, Eq)]
+pub struct ResellerUserCreateDto {
+    pub target: String,
+    pub username: String,
+    pub password: String,
+    pub max_connections: u32,
+    pub months: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+impl ResellerUserCreateDto {
+    pub fn prepare(&mut self) {
+        self.target = self.target.trim().to_string();
+        self.username = self.username.trim().to_string();
+        self.password = self.password.trim().to_string();
+        self.comment = self.comment.as_ref().map(|c| c.trim().to_string()).filter(|c| !c.is_empty());
+    }
+
+    pub fn validate(&self) -> Result<(), TuliproxError> {
+        if self.username.is_empty() {
+            return info_err_res!("Username required");
+        }
+        if self.password.is_empty() {
+            return info_err_res!("Password required");
+        }
+        if self.max_connections == 0 {
+            return info_err_res!("At least one connection required");
+        }
+        validate_months(self.months)
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Default, PartialEq, Eq)]
+pub struct ResellerUserExtendDto {
+    pub months: u32,
+}
+
+impl ResellerUserExtendDto {
+    pub fn validate(&self) -> Result<(), TuliproxError> { validate_months(self.months) }
+}
+
+fn validate_months(months: u32) -> Result<(), TuliproxError> {
+    if months == 0 || months > RESELLER_MAX_MONTHS {
+        return info_err_res!("Months must be between 1 and {RESELLER_MAX_MONTHS}");
+    }
+    Ok(())
+}
+
+/// A running stream of a user owned by the reseller.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
+pub struct ResellerStreamDto {
+    pub username: String,
+    #[serde(flatten)]
+    pub stream: UserStreamDto,
+}
+
+/// A finished stream session of a user owned by the reseller.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Default, PartialEq, Eq)]
+pub struct ResellerStreamHistoryDto {
+    pub username: String,
+    #[serde(flatten)]
+    pub session: UserStreamHistoryDto,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ResellerUserCreateDto, ResellerUserExtendDto, RESELLER_MAX_MONTHS};
+
+    #[test]
+    fn reseller_requests_require_months_in_range() {
+        let mut create = ResellerUserCreateDto {
+            target: " iptv ".to_string(),
+            username: " alice ".to_string(),
+            password: "secret".to_string(),
+            max_connections: 1,
+            months: 1,
+            comment: Some(" ".to_string()),
+        };
+        create.prepare();
+        assert_eq!(create.username, "alice");
+        assert!(create.comment.is_none());
+        assert!(create.validate().is_ok());
+        create.max_connections = 0;
+        assert!(create.validate().is_err());
+
+        assert!(ResellerUserExtendDto { months: 0 }.validate().is_err());
+        assert!(ResellerUserExtendDto { months: RESELLER_MAX_MONTHS }.validate().is_ok());
+        assert!(ResellerUserExtendDto { months: RESELLER_MAX_MONTHS + 1 }.validate().is_err());
+    }
+}
@@ -1,6 +1,9 @@
 use crate::{
     model::{M3uPlaylistItem, PlaylistEntry, PlaylistItemType, StreamProperties, XtreamCluster, XtreamPlaylistItem},
-    utils::{arc_str_serde, current_time_secs, extract_extension_from_url, is_blank_optional_string},
+    utils::{
+        arc_str_option_serde, arc_str_serde, current_time_secs, extract_extension_from_url, is_blank_optional_string,
+        is_zero_u64,
+    },
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -55,6 +58,9 @@ pub struct StreamChannel {
     pub shared_stream_id: Option<u64>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub technical: Option<StreamTechnicalInfo>,
+    /// Input of the linked alternate channel serving the stream after a failover.
+    #[serde(default, with = "arc_str_option_serde", skip_serializing_if = "Option::is_none")]
+    pub alternate_input: Option<Arc<str>>,
 }
 
 pub fn create_stream_channel_with_type(
@@ -85,6 +91,7 @@ impl XtreamPlaylistItem {
             shared_joined_existing: None,
             shared_stream_id: None,
             technical: stream_technical_from_properties(self.additional_properties.as_ref(), self.url.as_ref()),
+            alternate_input: None,
         }
     }
 }
@@ -106,6 +113,7 @@ impl M3uPlaylistItem {
             shared_joined_existing: None,
             shared_stream_id: None,
             technical: stream_technical_from_properties(self.additional_properties.as_ref(), self.url.as_ref()),
+            alternate_input: None,
         }
     }
 }
@@ -290,6 +298,9 @@ pub struct StreamInfo {
     pub preserved: bool,
     #[serde(default)]
     pub previous_session_id: Option<u64>,
+    /// Bandwidth limit of the stream in kbps, 0 when the stream is not throttled.
+    #[serde(default, skip_serializing_if = "is_zero_u64")]
+    pub throttle_kbps: u64,
 }
 
 impl StreamInfo {
@@ -320,6 +331,7 @@ impl StreamInfo {
             session_token: session_token.map(|token| token.to_string()),
             preserved: false,
             previous_session_id: None,
+            throttle_kbps: 0,
         }
     }
 }
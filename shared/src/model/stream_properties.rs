@@ -2,7 +2,7 @@ use crate::{
     model::{info_doc_utils::InfoDocUtils, PlaylistEntry, XtreamSeriesInfo, XtreamSeriesInfoDoc, XtreamVideoInfo},
     utils::{
         arc_str_default_on_null, arc_str_none_default_on_null, arc_str_option_null_if_empty_serde,
-        arc_str_option_serde, deserialize_as_option_arc_str, deserialize_as_string_array,
+        arc_str_option_serde, arc_str_serde, deserialize_as_option_arc_str, deserialize_as_string_array,
         deserialize_json_as_opt_string, deserialize_number_from_string, deserialize_number_from_string_or_zero,
         serialize_json_as_opt_string, Internable, CONSTANTS,
     },
@@ -85,6 +85,35 @@ pub struct LiveStreamProperties {
     pub last_success_timestamp: Option<i64>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MediaSidecarKind {
+    Subtitle,
+    Audio,
+}
+
+/// External subtitle or audio file found next to a local library video.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct MediaSidecarProperties {
+    pub kind: MediaSidecarKind,
+    // uuid of the library metadata entry
+    #[serde(with = "arc_str_serde")]
+    pub entry: Arc<str>,
+    #[serde(with = "arc_str_serde")]
+    pub id: Arc<str>,
+    #[serde(with = "arc_str_serde")]
+    pub extension: Arc<str>,
+    #[serde(default, with = "arc_str_option_serde")]
+    pub language: Option<Arc<str>>,
+    #[serde(default)]
+    pub forced: bool,
+    #[serde(default)]
+    pub hearing_impaired: bool,
+    // file name suffix after the video name, e.g. `de.forced.srt`
+    #[serde(with = "arc_str_serde")]
+    pub suffix: Arc<str>,
+}
+
 #[derive(Default, Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct VideoStreamDetailProperties {
     #[serde(default, with = "arc_str_option_serde")]
@@ -145,6 +174,8 @@ pub struct VideoStreamDetailProperties {
     pub runtime: Option<Arc<str>>,
     #[serde(default, with = "arc_str_option_serde")]
     pub status: Option<Arc<str>>,
+    #[serde(default)]
+    pub sidecars: Option<Vec<MediaSidecarProperties>>,
 }
 
 #[derive(Default, Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -253,6 +284,8 @@ pub struct SeriesStreamDetailEpisodeProperties {
         deserialize_with = "deserialize_json_as_opt_string"
     )]
     pub audio: Option<Arc<str>>,
+    #[serde(default)]
+    pub sidecars: Option<Vec<MediaSidecarProperties>>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -421,6 +454,23 @@ impl StreamProperties {
         }
     }
 
+    pub fn is_adult(&self) -> bool {
+        match self {
+            StreamProperties::Live(live) => live.is_adult != 0,
+            StreamProperties::Video(video) => video.is_adult != 0,
+            StreamProperties::Series(_) | StreamProperties::Episode(_) => false,
+        }
+    }
+
+    /// Marks live and movie streams as adult content, series carry no such flag.
+    pub fn set_adult(&mut self) {
+        match self {
+            StreamProperties::Live(live) => live.is_adult = 1,
+            StreamProperties::Video(video) => video.is_adult = 1,
+            StreamProperties::Series(_) | StreamProperties::Episode(_) => {}
+        }
+    }
+
     pub fn get_tmdb_id(&self) -> Option<u32> {
         match self {
             StreamProperties::Live(_) => None,
@@ -697,6 +747,7 @@ impl VideoStreamProperties {
                 bitrate: info.info.bitrate,
                 runtime: info.info.runtime.clone(),
                 status: info.info.status.clone(),
+                sidecars: None,
             }),
         }
     }
@@ -791,6 +842,7 @@ impl SeriesStreamProperties {
                             rating: e.info.as_ref().map(|i| i.rating),
                             video: e.info.as_ref().map(|i| i.video.clone()).unwrap_or_default(),
                             audio: e.info.as_ref().map(|i| i.audio.clone()).unwrap_or_default(),
+                            sidecars: None,
                         })
                         .collect();
                     episodes.sort_by_key(|episode| (episode.season, episode.episode_num));
@@ -892,6 +944,7 @@ impl SeriesStreamProperties {
                             rating: Some(e.info.rating),
                             video: None,
                             audio: None,
+                            sidecars: None,
                         })
                         .collect();
                     episodes.sort_by_key(|episode| (episode.season, episode.episode_num));
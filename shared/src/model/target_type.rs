@@ -11,6 +11,14 @@ pub enum TargetType {
     Strm,
     #[serde(rename = "hdhomerun")]
     HdHomeRun,
+    #[serde(rename = "enigma2")]
+    Enigma2,
+    #[serde(rename = "json")]
+    Json,
+    #[serde(rename = "csv")]
+    Csv,
+    #[serde(rename = "multicast")]
+    Multicast,
 }
 
 impl TargetType {
@@ -18,6 +26,10 @@ impl TargetType {
     const XTREAM: &'static str = "Xtream";
     const STRM: &'static str = "Strm";
     const HDHOMERUN: &'static str = "HdHomeRun";
+    const ENIGMA2: &'static str = "Enigma2";
+    const JSON: &'static str = "Json";
+    const CSV: &'static str = "Csv";
+    const MULTICAST: &'static str = "Multicast";
 }
 
 impl Display for TargetType {
@@ -30,6 +42,10 @@ impl Display for TargetType {
                 Self::Xtream => Self::XTREAM,
                 Self::Strm => Self::STRM,
                 Self::HdHomeRun => Self::HDHOMERUN,
+                Self::Enigma2 => Self::ENIGMA2,
+                Self::Json => Self::JSON,
+                Self::Csv => Self::CSV,
+                Self::Multicast => Self::MULTICAST,
             }
         )
     }
@@ -44,6 +60,10 @@ impl FromStr for TargetType {
             Self::XTREAM => Ok(Self::Xtream),
             Self::STRM => Ok(Self::Strm),
             Self::HDHOMERUN => Ok(Self::HdHomeRun),
+            Self::ENIGMA2 => Ok(Self::Enigma2),
+            Self::JSON => Ok(Self::Json),
+            Self::CSV => Ok(Self::Csv),
+            Self::MULTICAST => Ok(Self::Multicast),
             _ => Err(format!("Unknown TargetType: {}", s)),
         }
     }
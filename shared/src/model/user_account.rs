@@ -0,0 +1,111 @@
+use crate::{
+    error::{info_err_res, TuliproxError},
+    model::{PlaylistItemType, ProxyUserStatus, StreamInfo, UserQuotaStatusDto},
+};
+
+/// Account details of a proxy user for the self-service area of the web UI.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Default, PartialEq)]
+pub struct UserAccountDto {
+    pub username: String,
+    pub target: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<ProxyUserStatus>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exp_date: Option<i64>,
+    pub max_connections: u32,
+    pub active_connections: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quota: Option<UserQuotaStatusDto>,
+    /// Server url for Xtream apps, set when the target has a Xtream output.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub xtream_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub m3u_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub xmltv_url: Option<String>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Default, PartialEq, Eq)]
+pub struct UserPasswordChangeDto {
+    pub password: String,
+    pub new_password: String,
+}
+
+impl UserPasswordChangeDto {
+    pub fn validate(&self) -> Result<(), TuliproxError> {
+        let new_password = self.new_password.trim();
+        if new_password.is_empty() {
+            return info_err_res!("Password required");
+        }
+        if new_password == self.password.trim() {
+            return info_err_res!("The new password equals the current password");
+        }
+        Ok(())
+    }
+}
+
+/// A running stream of the proxy user, without provider details.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq)]
+pub struct UserStreamDto {
+    pub uid: u32,
+    pub title: String,
+    pub group: String,
+    pub item_type: PlaylistItemType,
+    pub client_ip: String,
+    #[serde(default)]
+    pub user_agent: String,
+    pub ts: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub country_code: Option<String>,
+}
+
+impl From<StreamInfo> for UserStreamDto {
+    fn from(stream: StreamInfo) -> Self {
+        Self {
+            uid: stream.uid,
+            title: stream.channel.title.to_string(),
+            group: stream.channel.group.to_string(),
+            item_type: stream.channel.item_type,
+            client_ip: stream.client_ip,
+            user_agent: stream.user_agent,
+            ts: stream.ts,
+            country_code: stream.country_code,
+        }
+    }
+}
+
+/// A finished stream session of the proxy user from the stream history.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Default, PartialEq, Eq)]
+pub struct UserStreamHistoryDto {
+    pub ts: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub item_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_secs: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bytes_sent: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_ip: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UserPasswordChangeDto;
+
+    #[test]
+    fn password_change_requires_new_password() {
+        let change = |password: &str, new_password: &str| UserPasswordChangeDto {
+            password: password.to_string(),
+            new_password: new_password.to_string(),
+        };
+        assert!(change("secret", "better").validate().is_ok());
+        assert!(change("secret", "  ").validate().is_err());
+        assert!(change("secret", " secret ").validate().is_err());
+    }
+}
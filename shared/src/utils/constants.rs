@@ -10,6 +10,7 @@ pub const DEFAULT_WEB_ROOT_ENV_VAR: &str = "TULIPROX_WEB_ROOT";
 pub const DEFAULT_USER_AGENT: &str = "VLC/3.0.16 LibVLC/3.0.16";
 pub const DEFAULT_BACKUP_DIR: &str = "backup";
 pub const DEFAULT_CACHE_DIR: &str = "cache";
+pub const DEFAULT_VOD_CACHE_DIR: &str = "vod_cache";
 pub const DEFAULT_STORAGE_TEMP_DIR: &str = "tmp";
 pub const DEFAULT_USER_CONFIG_DIR: &str = "user_config";
 pub const DEFAULT_DOWNLOAD_DIR: &str = "downloads";
@@ -45,6 +46,7 @@ pub const USER_CONNECTIONS_EXHAUSTED: &str = "user_connections_exhausted.ts";
 pub const PROVIDER_CONNECTIONS_EXHAUSTED: &str = "provider_connections_exhausted.ts";
 pub const LOW_PRIORITY_PREEMPTED: &str = "low_priority_preempted.ts";
 pub const USER_ACCOUNT_EXPIRED: &str = "user_account_expired.ts";
+pub const USER_QUOTA_EXHAUSTED: &str = "user_quota_exhausted.ts";
 pub const PANEL_API_PROVISIONING: &str = "panel_api_provisioning.ts";
 
 pub const DEFAULT_EPISODE_PATTERN: &str = r".*(?P<episode>[Ss]\d{1,2}(.*?)[Ee]\d{1,2}).*";
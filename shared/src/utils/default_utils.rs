@@ -11,6 +11,7 @@ use std::sync::Arc;
 pub const fn is_zero_u16(v: &u16) -> bool { *v == 0 }
 pub const fn is_zero_i16(v: &i16) -> bool { *v == 0 }
 pub const fn is_zero_u32(v: &u32) -> bool { *v == 0 }
+pub const fn is_zero_u64(v: &u64) -> bool { *v == 0 }
 pub const fn is_true(v: &bool) -> bool { *v }
 pub const fn is_false(v: &bool) -> bool { !*v }
 pub const fn default_as_true() -> bool { true }
@@ -46,6 +47,16 @@ pub const fn default_hls_session_ttl_secs() -> u64 { 15 }
 pub const fn is_default_hls_session_ttl_secs(v: &u64) -> bool { *v == default_hls_session_ttl_secs() }
 pub const fn default_catchup_session_ttl_secs() -> u64 { 45 }
 pub const fn is_default_catchup_session_ttl_secs(v: &u64) -> bool { *v == default_catchup_session_ttl_secs() }
+pub const fn default_stall_timeout_secs() -> u64 { 10 }
+pub const fn is_default_stall_timeout_secs(v: &u64) -> bool { *v == default_stall_timeout_secs() }
+pub const fn default_stall_bitrate_window_secs() -> u64 { 10 }
+pub const fn is_default_stall_bitrate_window_secs(v: &u64) -> bool { *v == default_stall_bitrate_window_secs() }
+pub const fn default_prewarm_top_n() -> u16 { 5 }
+pub const fn is_default_prewarm_top_n(v: &u16) -> bool { *v == default_prewarm_top_n() }
+pub const fn default_prewarm_lookback_days() -> u16 { 7 }
+pub const fn is_default_prewarm_lookback_days(v: &u16) -> bool { *v == default_prewarm_lookback_days() }
+pub const fn default_prewarm_reserve_slots() -> u16 { 1 }
+pub const fn is_default_prewarm_reserve_slots(v: &u16) -> bool { *v == default_prewarm_reserve_slots() }
 pub const fn default_panel_api_provision_timeout_secs() -> u64 { 65 }
 pub const fn default_panel_api_provision_probe_interval_secs() -> u64 { 15 }
 pub const fn default_panel_api_provision_cooldown_secs() -> u64 { 0 }
@@ -99,6 +110,10 @@ pub const fn is_default_token_ttl_mins(v: &u32) -> bool { *v == default_token_tt
 
 pub const fn default_auth_error_status() -> u16 { 403 }
 pub const fn is_default_auth_error_status(v: &u16) -> bool { *v == default_auth_error_status() }
+pub const fn default_parental_unlock_mins() -> u32 { 60 }
+pub const fn is_default_parental_unlock_mins(v: &u32) -> bool { *v == default_parental_unlock_mins() }
+pub const fn default_reseller_max_connections() -> u32 { 1 }
+pub const fn is_default_reseller_max_connections(v: &u32) -> bool { *v == default_reseller_max_connections() }
 
 pub const fn default_epg_match_threshold() -> u16 { 80 }
 pub const fn is_default_epg_match_threshold(v: &u16) -> bool { *v == default_epg_match_threshold() }
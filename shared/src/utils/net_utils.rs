@@ -1,4 +1,4 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, net::IpAddr};
 
 pub fn strip_port<'a>(input: &'a str) -> Cow<'a, str> {
     // IPv6 with port: [2001:db8::1]:8080
@@ -23,3 +23,103 @@ pub fn strip_port<'a>(input: &'a str) -> Cow<'a, str> {
         Cow::Borrowed(input)
     }
 }
+
+/// An IP address or CIDR network like `10.0.0.0/8` or `2001:db8::/32`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IpNetwork {
+    addr: IpAddr,
+    prefix: u8,
+}
+
+impl IpNetwork {
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        let (addr, prefix) = match value.split_once('/') {
+            Some((addr, prefix)) => (addr.trim().parse::<IpAddr>().ok()?, Some(prefix.trim().parse::<u8>().ok()?)),
+            None => (value.parse::<IpAddr>().ok()?, None),
+        };
+        let max_prefix = if addr.is_ipv4() { 32 } else { 128 };
+        let prefix = prefix.unwrap_or(max_prefix);
+        (prefix <= max_prefix).then_some(Self { addr, prefix })
+    }
+
+    pub fn contains(&self, ip: &IpAddr) -> bool {
+        match (self.addr, ip) {
+            (IpAddr::V4(net), IpAddr::V4(ip)) => {
+                let mask = u32::MAX.checked_shl(32 - u32::from(self.prefix)).unwrap_or(0);
+                u32::from(net) & mask == u32::from(*ip) & mask
+            }
+            (IpAddr::V6(net), IpAddr::V6(ip)) => {
+                let mask = u128::MAX.checked_shl(128 - u32::from(self.prefix)).unwrap_or(0);
+                u128::from(net) & mask == u128::from(*ip) & mask
+            }
+            (IpAddr::V4(_), IpAddr::V6(ip)) => {
+                ip.to_ipv4_mapped().is_some_and(|ip| self.contains(&IpAddr::V4(ip)))
+            }
+            (IpAddr::V6(_), IpAddr::V4(_)) => false,
+        }
+    }
+}
+
+/// Parses the client ip of a request, the first entry of a forwarded list is used and a port is removed.
+pub fn parse_client_ip(client_ip: &str) -> Option<IpAddr> {
+    let client_ip = client_ip.split(',').next().unwrap_or_default().trim();
+    strip_port(client_ip).parse::<IpAddr>().ok()
+}
+
+/// Resolves the client ip from the socket peer and the forwarded header value of a request.
+/// The header is only used when the peer is one of the trusted proxies, the list is then walked from the right
+/// and the first entry which is not a trusted proxy is the client. Returns `None` for an unparsable entry.
+pub fn resolve_client_ip(peer: IpAddr, forwarded: &str, trusted_proxies: &[IpNetwork]) -> Option<IpAddr> {
+    let is_trusted = |ip: &IpAddr| trusted_proxies.iter().any(|network| network.contains(ip));
+    let peer = peer.to_canonical();
+    if !is_trusted(&peer) {
+        return Some(peer);
+    }
+    let mut client_ip = peer;
+    for entry in forwarded.rsplit(',') {
+        client_ip = strip_port(entry.trim()).parse::<IpAddr>().ok()?.to_canonical();
+        if !is_trusted(&client_ip) {
+            break;
+        }
+    }
+    Some(client_ip)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_client_ip, resolve_client_ip, IpNetwork};
+
+    #[test]
+    fn ip_network_matches_addresses() {
+        let lan = IpNetwork::parse("192.168.0.0/16").unwrap();
+        assert!(lan.contains(&"192.168.10.1".parse().unwrap()));
+        assert!(lan.contains(&"::ffff:192.168.10.1".parse().unwrap()));
+        assert!(!lan.contains(&"10.0.0.1".parse().unwrap()));
+
+        let host = IpNetwork::parse("2001:db8::1").unwrap();
+        assert!(host.contains(&"2001:db8::1".parse().unwrap()));
+        assert!(!host.contains(&"2001:db8::2".parse().unwrap()));
+        assert!(IpNetwork::parse("2001:db8::/32").unwrap().contains(&"2001:db8:ffff::1".parse().unwrap()));
+
+        assert!(IpNetwork::parse("10.0.0.0/33").is_none());
+        assert!(IpNetwork::parse("example.com").is_none());
+        assert_eq!(parse_client_ip("10.0.0.1:5000, 172.16.0.1"), "10.0.0.1".parse().ok());
+        assert_eq!(parse_client_ip("[2001:db8::1]:8080"), "2001:db8::1".parse().ok());
+    }
+
+    #[test]
+    fn resolve_client_ip_trusts_headers_only_from_trusted_proxies() {
+        let trusted = [IpNetwork::parse("172.16.0.0/12").unwrap()];
+        let proxy = "172.17.0.2".parse().unwrap();
+        let stranger = "203.0.113.9".parse().unwrap();
+
+        assert_eq!(resolve_client_ip(stranger, "192.168.1.5", &trusted), Some(stranger));
+        assert_eq!(resolve_client_ip(proxy, "192.168.1.5", &trusted), "192.168.1.5".parse().ok());
+        let chain = "10.0.0.1, 198.51.100.7, 172.17.0.3";
+        assert_eq!(resolve_client_ip(proxy, chain, &trusted), "198.51.100.7".parse().ok());
+        assert_eq!(resolve_client_ip(proxy, "172.17.0.3", &trusted), "172.17.0.3".parse().ok());
+        assert_eq!(resolve_client_ip(proxy, "", &trusted), None);
+        assert_eq!(resolve_client_ip("::ffff:203.0.113.9".parse().unwrap(), "10.0.0.1", &trusted), Some(stranger));
+    }
+}